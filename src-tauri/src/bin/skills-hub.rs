@@ -0,0 +1,5 @@
+//! Headless companion to the app; see `app_lib::cli`.
+
+fn main() {
+    std::process::exit(app_lib::cli::run(std::env::args().skip(1).collect()));
+}
@@ -0,0 +1,475 @@
+//! `skills-hub`: manage skills without the GUI, e.g. on servers and in CI.
+//! It opens the same store and central repo as the app, so both see the
+//! same skills.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::commands::sync_skill_to_local_tool;
+use crate::core::access_tokens::{
+    authorize, create_access_token, list_access_tokens, revoke_access_token, Scope,
+};
+use crate::core::app_paths::{AppPaths, APP_IDENTIFIER};
+use crate::core::clawhub_api::install_clawhub_skill;
+use crate::core::installer::{
+    check_skill_updates, install_git_skill, install_local_skill, looks_like_github_shorthand,
+    update_managed_skill_from_source, InstallResult,
+};
+use crate::core::multi_host_sync::{sync_skills_to_hosts, MAX_PARALLEL_HOSTS};
+use crate::core::skill_lock::is_skill_locked;
+use crate::core::skill_store::{SkillRecord, SkillStore};
+use crate::core::source_trust::{check_clawhub_source, check_git_source};
+use crate::core::undo::UndoStack;
+use crate::open_store;
+
+const USAGE: &str = "usage: skills-hub <command> [options]
+
+commands:
+  list                                   managed skills and where they are synced
+  install <source> [--name N] [--tool T]... [--trust]
+                                         install from a git URL, owner/repo,
+                                         clawhub://<slug> or a local folder;
+                                         --trust allows unlisted sources
+  sync (<skill>... | --all) --tool T... [--overwrite]
+                                         sync skills into local tools
+  update (<skill>... | --all) [--force]  pull new versions from the source;
+                                         --all updates what has changed;
+                                         locked skills need --force
+  remote-sync (<host>... | --group G) [--skill S]... [--tool T]...
+                                         push skills (all by default) to hosts
+  token create <name> --scope S...       add an access token (scopes: read,
+                                         install, sync, remote)
+  token list | token revoke <name>       show or remove access tokens
+  help                                   show this text
+
+<skill> is a skill name or id, <host> a host label or id.
+
+Once a token exists every command needs one, from --token or
+SKILLS_HUB_TOKEN, holding the command's scope: list needs read, install
+and update need install (install --tool also needs sync), sync needs
+sync, remote-sync needs remote and token needs all four.";
+
+/// The scopes a token must hold to run `command` with `args`.
+fn required_scopes(command: &str, args: &[String]) -> Vec<Scope> {
+    match command {
+        "list" => vec![Scope::Read],
+        // `install --tool` also syncs the new skill into those tools.
+        "install" if args.iter().any(|arg| arg == "--tool") => {
+            vec![Scope::Install, Scope::Sync]
+        }
+        "install" | "update" => vec![Scope::Install],
+        "sync" => vec![Scope::Sync],
+        "remote-sync" => vec![Scope::Remote],
+        // Managing tokens can hand out any scope, so it needs all of them.
+        "token" => Scope::ALL.to_vec(),
+        _ => Vec::new(),
+    }
+}
+
+/// Pull `--token` out of `args`, falling back to `SKILLS_HUB_TOKEN`.
+fn take_token(args: &[String]) -> Result<(Option<String>, Vec<String>)> {
+    let mut token = std::env::var("SKILLS_HUB_TOKEN")
+        .ok()
+        .filter(|t| !t.is_empty());
+    let mut rest = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--token" {
+            let value = iter
+                .next()
+                .ok_or_else(|| anyhow::anyhow!("--token needs a value"))?;
+            token = Some(value.clone());
+        } else {
+            rest.push(arg.clone());
+        }
+    }
+    Ok((token, rest))
+}
+
+/// Parsed command line: positional arguments plus repeatable `--key value`
+/// options and `--flag`s.
+#[derive(Debug, Default)]
+struct Args {
+    positional: Vec<String>,
+    options: Vec<(String, String)>,
+    flags: Vec<String>,
+}
+
+impl Args {
+    fn parse(args: &[String], flags: &[&str]) -> Result<Self> {
+        let mut parsed = Args::default();
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            match arg.strip_prefix("--") {
+                Some(flag) if flags.contains(&flag) => parsed.flags.push(flag.to_string()),
+                Some(key) => {
+                    let value = iter
+                        .next()
+                        .ok_or_else(|| anyhow::anyhow!("--{} needs a value", key))?;
+                    parsed.options.push((key.to_string(), value.clone()));
+                }
+                None => parsed.positional.push(arg.clone()),
+            }
+        }
+        Ok(parsed)
+    }
+
+    fn all(&self, key: &str) -> Vec<String> {
+        self.options
+            .iter()
+            .filter(|(k, _)| k == key)
+            .map(|(_, v)| v.clone())
+            .collect()
+    }
+
+    fn one(&self, key: &str) -> Option<String> {
+        self.all(key).pop()
+    }
+
+    fn flag(&self, flag: &str) -> bool {
+        self.flags.iter().any(|f| f == flag)
+    }
+
+    fn reject_unknown(&self, known: &[&str]) -> Result<()> {
+        match self
+            .options
+            .iter()
+            .find(|(k, _)| !known.contains(&k.as_str()))
+        {
+            Some((key, _)) => anyhow::bail!("unknown option --{}", key),
+            None => Ok(()),
+        }
+    }
+}
+
+struct Headless {
+    paths: AppPaths,
+    store: SkillStore,
+    undo: UndoStack,
+}
+
+impl Headless {
+    fn open() -> Result<Self> {
+        // The app's folders, and with them its store, without starting Tauri.
+        let paths = AppPaths::for_identifier(APP_IDENTIFIER)?;
+        let store = open_store(&paths)?;
+        Ok(Headless {
+            paths,
+            store,
+            undo: UndoStack::in_temp_dir()?,
+        })
+    }
+
+    /// Skills matching `names` by name or id, or every skill for `--all`.
+    fn select_skills(&self, args: &Args) -> Result<Vec<SkillRecord>> {
+        let skills = self.store.list_skills()?;
+        if args.flag("all") {
+            return Ok(skills);
+        }
+        if args.positional.is_empty() {
+            anyhow::bail!("name at least one skill, or pass --all");
+        }
+        args.positional
+            .iter()
+            .map(|wanted| {
+                skills
+                    .iter()
+                    .find(|s| &s.id == wanted || &s.name == wanted)
+                    .cloned()
+                    .ok_or_else(|| anyhow::anyhow!("no managed skill named {}", wanted))
+            })
+            .collect()
+    }
+}
+
+/// Run the CLI with `args` (without the program name) and return the exit
+/// code.
+pub fn run(args: Vec<String>) -> i32 {
+    let Some((command, rest)) = args.split_first() else {
+        eprintln!("{}", USAGE);
+        return 2;
+    };
+    if matches!(command.as_str(), "help" | "--help" | "-h") {
+        println!("{}", USAGE);
+        return 0;
+    }
+    let result = take_token(rest).and_then(|(token, rest)| {
+        let hub = Headless::open()?;
+        authorize(
+            &hub.store,
+            token.as_deref(),
+            &required_scopes(command, &rest),
+        )?;
+        match command.as_str() {
+            "list" => list(&hub),
+            "install" => install(&hub, &rest),
+            "sync" => sync(&hub, &rest),
+            "update" => update(&hub, &rest),
+            "remote-sync" => remote_sync(&hub, &rest),
+            "token" => token_command(&hub, &rest),
+            other => Err(anyhow::anyhow!("unknown command {}\n\n{}", other, USAGE)),
+        }
+    });
+    match result {
+        Ok(true) => 0,
+        Ok(false) => 1,
+        Err(err) => {
+            eprintln!("error: {:#}", err);
+            1
+        }
+    }
+}
+
+// Each command returns whether everything it tried succeeded.
+
+fn list(hub: &Headless) -> Result<bool> {
+    for skill in hub.store.list_skills()? {
+        let tools: Vec<String> = hub
+            .store
+            .list_skill_targets(&skill.id)?
+            .into_iter()
+            .map(|t| t.tool)
+            .collect();
+        println!(
+            "{}\t{}\t{}\t{}",
+            skill.name,
+            skill.source_type,
+            skill.source_ref.as_deref().unwrap_or("-"),
+            if tools.is_empty() {
+                "-".to_string()
+            } else {
+                tools.join(",")
+            }
+        );
+    }
+    Ok(true)
+}
+
+fn install(hub: &Headless, args: &[String]) -> Result<bool> {
+    let args = Args::parse(args, &["trust"])?;
+    args.reject_unknown(&["name", "tool"])?;
+    let [source] = args.positional.as_slice() else {
+        anyhow::bail!("install takes one source");
+    };
+    let name = args.one("name");
+    let trust = args.flag("trust");
+    let result = if let Some(slug) = source.strip_prefix("clawhub://") {
+        check_clawhub_source(&hub.store, slug, trust)?;
+        install_clawhub_skill(&hub.paths, &hub.store, slug, None, name)?
+    } else if Path::new(source).exists() {
+        install_local_skill(&hub.paths, &hub.store, Path::new(source), name)?
+    } else if source.contains("://")
+        || source.starts_with("git@")
+        || looks_like_github_shorthand(source)
+    {
+        check_git_source(&hub.store, source, trust)?;
+        install_git_skill(&hub.paths, &hub.store, source, name)?
+    } else {
+        anyhow::bail!("{} is not a folder, git URL or clawhub:// slug", source);
+    };
+    println!(
+        "installed {} -> {}",
+        result.name,
+        result.central_path.display()
+    );
+    for finding in &result.content_warnings {
+        eprintln!(
+            "warning: {}:{}: {} ({})",
+            finding.file, finding.line, finding.excerpt, finding.code
+        );
+    }
+    let skill = installed_record(hub, &result)?;
+    sync_to_tools(hub, &[skill], &args.all("tool"), false)
+}
+
+fn installed_record(hub: &Headless, result: &InstallResult) -> Result<SkillRecord> {
+    hub.store
+        .get_skill_by_id(&result.skill_id)?
+        .ok_or_else(|| anyhow::anyhow!("skill not found"))
+}
+
+fn sync(hub: &Headless, args: &[String]) -> Result<bool> {
+    let args = Args::parse(args, &["all", "overwrite"])?;
+    args.reject_unknown(&["tool"])?;
+    let tools = args.all("tool");
+    if tools.is_empty() {
+        anyhow::bail!("name at least one --tool");
+    }
+    let skills = hub.select_skills(&args)?;
+    sync_to_tools(hub, &skills, &tools, args.flag("overwrite"))
+}
+
+fn sync_to_tools(
+    hub: &Headless,
+    skills: &[SkillRecord],
+    tools: &[String],
+    overwrite: bool,
+) -> Result<bool> {
+    let mut ok = true;
+    for skill in skills {
+        for tool in tools {
+            let outcome = sync_skill_to_local_tool(
+                &hub.store,
+                &hub.undo,
+                &PathBuf::from(&skill.central_path),
+                &skill.id,
+                tool,
+                skill.name.clone(),
+                overwrite,
+                None,
+            );
+            match outcome {
+                Ok(result) => println!(
+                    "synced {} -> {} ({})",
+                    skill.name, result.target_path, result.mode_used
+                ),
+                Err(err) => {
+                    ok = false;
+                    eprintln!("failed {} -> {}: {:#}", skill.name, tool, err);
+                }
+            }
+        }
+    }
+    Ok(ok)
+}
+
+fn update(hub: &Headless, args: &[String]) -> Result<bool> {
+    let args = Args::parse(args, &["all", "force"])?;
+    args.reject_unknown(&[])?;
+    let force = args.flag("force");
+    let skill_ids: Vec<String> = if args.flag("all") {
+        check_skill_updates(&hub.store)
+            .into_iter()
+            .filter(|status| status.has_update)
+            .filter(|status| force || !is_skill_locked(&hub.store, &status.skill_id))
+            .map(|status| status.skill_id)
+            .collect()
+    } else {
+        hub.select_skills(&args)?
+            .into_iter()
+            .map(|s| s.id)
+            .collect()
+    };
+    if skill_ids.is_empty() {
+        println!("everything is up to date");
+    }
+    let mut ok = true;
+    for skill_id in skill_ids {
+        match update_managed_skill_from_source(&hub.paths, &hub.store, &skill_id, force) {
+            Ok(result) => println!(
+                "updated {} to {}",
+                result.name,
+                result.source_revision.as_deref().unwrap_or("latest")
+            ),
+            Err(err) => {
+                ok = false;
+                eprintln!("failed to update {}: {:#}", skill_id, err);
+            }
+        }
+    }
+    Ok(ok)
+}
+
+fn remote_sync(hub: &Headless, args: &[String]) -> Result<bool> {
+    let args = Args::parse(args, &[])?;
+    args.reject_unknown(&["group", "skill", "tool"])?;
+    let hosts = hub.store.list_remote_hosts()?;
+    let mut host_ids = Vec::new();
+    for wanted in &args.positional {
+        let host = hosts
+            .iter()
+            .find(|h| &h.id == wanted || &h.label == wanted)
+            .ok_or_else(|| anyhow::anyhow!("no remote host named {}", wanted))?;
+        host_ids.push(host.id.clone());
+    }
+    if let Some(group) = args.one("group") {
+        for host in hub.store.list_remote_hosts_in_group(&group)? {
+            if !host_ids.contains(&host.id) {
+                host_ids.push(host.id);
+            }
+        }
+    }
+    if host_ids.is_empty() {
+        anyhow::bail!("name at least one host, or pass --group");
+    }
+    let wanted_skills = args.all("skill");
+    let skill_ids: Vec<String> = if wanted_skills.is_empty() {
+        hub.store.list_skills()?.into_iter().map(|s| s.id).collect()
+    } else {
+        let selection = Args {
+            positional: wanted_skills,
+            ..Args::default()
+        };
+        hub.select_skills(&selection)?
+            .into_iter()
+            .map(|s| s.id)
+            .collect()
+    };
+
+    let mut ok = true;
+    let results = sync_skills_to_hosts(
+        &hub.store,
+        &host_ids,
+        &skill_ids,
+        &args.all("tool"),
+        MAX_PARALLEL_HOSTS,
+    )?;
+    for host in results {
+        if let Some(error) = host.error {
+            ok = false;
+            eprintln!("{}: {}", host.label, error);
+            continue;
+        }
+        for cell in host.skills {
+            match cell.error {
+                Some(error) => {
+                    ok = false;
+                    eprintln!("{}: {} {}: {}", host.label, cell.name, cell.status, error);
+                }
+                None => println!("{}: {} {}", host.label, cell.name, cell.status),
+            }
+        }
+    }
+    Ok(ok)
+}
+
+fn token_command(hub: &Headless, args: &[String]) -> Result<bool> {
+    let args = Args::parse(args, &[])?;
+    match args.positional.as_slice() {
+        [action, name] if action == "create" => {
+            args.reject_unknown(&["scope"])?;
+            let scopes = args
+                .all("scope")
+                .iter()
+                .map(|scope| Scope::parse(scope))
+                .collect::<Result<Vec<_>>>()?;
+            let (token, secret) = create_access_token(&hub.store, name, &scopes)?;
+            eprintln!(
+                "created token {}; store it now, it is not shown again",
+                token.name
+            );
+            println!("{}", secret);
+        }
+        [action] if action == "list" => {
+            args.reject_unknown(&[])?;
+            for token in list_access_tokens(&hub.store) {
+                let scopes: Vec<&str> = token.scopes.iter().map(|s| s.name()).collect();
+                println!("{}\t{}\t{}", token.name, token.id, scopes.join(","));
+            }
+        }
+        [action, name] if action == "revoke" => {
+            args.reject_unknown(&[])?;
+            let token = revoke_access_token(&hub.store, name)?;
+            println!("revoked {}", token.name);
+        }
+        _ => anyhow::bail!(
+            "usage: token create <name> --scope S... | token list | token revoke <name>"
+        ),
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+#[path = "tests/cli.rs"]
+mod tests;
@@ -0,0 +1,73 @@
+use super::*;
+
+fn strings(args: &[&str]) -> Vec<String> {
+    args.iter().map(|a| a.to_string()).collect()
+}
+
+#[test]
+fn parses_positionals_options_and_flags() {
+    let args = Args::parse(
+        &strings(&[
+            "pdf",
+            "--tool",
+            "cursor",
+            "docs",
+            "--overwrite",
+            "--tool",
+            "codex",
+        ]),
+        &["overwrite"],
+    )
+    .unwrap();
+    assert_eq!(args.positional, vec!["pdf", "docs"]);
+    assert_eq!(args.all("tool"), vec!["cursor", "codex"]);
+    assert_eq!(args.one("tool").as_deref(), Some("codex"));
+    assert!(args.flag("overwrite"));
+    assert!(!args.flag("all"));
+    assert!(args.reject_unknown(&["tool"]).is_ok());
+    assert!(args.reject_unknown(&["name"]).is_err());
+
+    assert!(Args::parse(&strings(&["--name"]), &[]).is_err());
+}
+
+#[test]
+fn help_and_missing_command_do_not_open_the_store() {
+    assert_eq!(run(strings(&["help"])), 0);
+    assert_eq!(run(Vec::new()), 2);
+}
+
+#[test]
+fn takes_the_token_from_anywhere_in_the_arguments() {
+    let (token, rest) = take_token(&strings(&["pdf", "--token", "shub_x", "--all"])).unwrap();
+    assert_eq!(token.as_deref(), Some("shub_x"));
+    assert_eq!(rest, vec!["pdf", "--all"]);
+    assert!(take_token(&strings(&["--token"])).is_err());
+}
+
+#[test]
+fn every_command_names_its_scope() {
+    for command in ["list", "install", "sync", "update", "remote-sync", "token"] {
+        assert!(!required_scopes(command, &[]).is_empty(), "{}", command);
+    }
+    assert_eq!(required_scopes("update", &[]), vec![Scope::Install]);
+    assert_eq!(required_scopes("token", &[]), Scope::ALL.to_vec());
+}
+
+#[test]
+fn install_into_tools_needs_the_sync_scope() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    let (_, secret) = create_access_token(&store, "ci", &[Scope::Install]).unwrap();
+
+    let plain = strings(&["owner/repo"]);
+    assert!(authorize(&store, Some(&secret), &required_scopes("install", &plain)).is_ok());
+    let into_tools = strings(&["owner/repo", "--tool", "cursor"]);
+    let err = authorize(
+        &store,
+        Some(&secret),
+        &required_scopes("install", &into_tools),
+    )
+    .unwrap_err();
+    assert_eq!(err.to_string(), "token ci lacks the sync scope");
+}
@@ -0,0 +1,122 @@
+use std::fmt;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::core::errors::{find_coded, ErrorCode};
+use crate::core::locale::{tr, Msg};
+
+/// What every command fails with. The frontend branches on `code` and
+/// reads `payload` (a target path, a tool key, ...) instead of parsing
+/// `message`, which is only meant to be shown.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct CommandError {
+    pub code: ErrorCode,
+    pub message: String,
+    pub payload: Option<Value>,
+}
+
+impl CommandError {
+    pub fn other(message: impl Into<String>) -> Self {
+        CommandError {
+            code: ErrorCode::Other,
+            message: message.into(),
+            payload: None,
+        }
+    }
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl From<anyhow::Error> for CommandError {
+    fn from(err: anyhow::Error) -> Self {
+        match find_coded(&err) {
+            Some(coded) => CommandError {
+                code: coded.code,
+                message: coded.message.clone(),
+                payload: coded.payload.clone(),
+            },
+            None => CommandError::other(describe(&err)),
+        }
+    }
+}
+
+/// A command's blocking task panicked or was cancelled.
+impl From<tauri::Error> for CommandError {
+    fn from(err: tauri::Error) -> Self {
+        CommandError::other(err.to_string())
+    }
+}
+
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        CommandError::other(message)
+    }
+}
+
+impl From<&str> for CommandError {
+    fn from(message: &str) -> Self {
+        CommandError::other(message)
+    }
+}
+
+/// The full cause chain of an uncoded error, with hints for common GitHub
+/// clone failures in the user's language.
+fn describe(err: &anyhow::Error) -> String {
+    // Include the full error chain (causes), not just the top context.
+    let mut full = format!("{:#}", err);
+
+    // Redact noisy temp paths from clone context (we care about the cause, not the dest).
+    // Example: `clone https://... into "/Users/.../skills-hub-git-<uuid>"`
+    if let Some(head) = full.lines().next() {
+        if head.starts_with("clone ") {
+            if let Some(pos) = head.find(" into ") {
+                let head_redacted = format!("{} {}", &head[..pos], tr(Msg::CloneTempDirOmitted));
+                let rest: String = full.lines().skip(1).collect::<Vec<_>>().join("\n");
+                full = if rest.is_empty() {
+                    head_redacted
+                } else {
+                    format!("{}\n{}", head_redacted, rest)
+                };
+            }
+        }
+    }
+
+    let root = err.root_cause().to_string();
+    let lower = full.to_lowercase();
+
+    // Heuristic-friendly messaging for GitHub clone failures.
+    if lower.contains("github.com")
+        && (lower.contains("clone ") || lower.contains("remote") || lower.contains("fetch"))
+    {
+        let hint = if lower.contains("securetransport") {
+            Msg::GithubTls
+        } else if lower.contains("authentication")
+            || lower.contains("permission denied")
+            || lower.contains("credentials")
+        {
+            Msg::GithubAuth
+        } else if lower.contains("not found") {
+            Msg::GithubNotFound
+        } else if lower.contains("failed to resolve")
+            || lower.contains("could not resolve")
+            || lower.contains("dns")
+        {
+            Msg::GithubDns
+        } else if lower.contains("timed out") || lower.contains("timeout") {
+            Msg::GithubTimeout
+        } else if lower.contains("connection refused") || lower.contains("connection reset") {
+            Msg::GithubConnection
+        } else {
+            Msg::GithubUnreachable
+        };
+
+        return format!("{}\n\n{}{}", tr(hint), tr(Msg::Details), root);
+    }
+
+    full
+}
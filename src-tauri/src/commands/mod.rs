@@ -1,101 +1,126 @@
+mod error;
+
+use std::collections::HashMap;
+
 use anyhow::Context;
 use serde::Serialize;
 use tauri::State;
 
+use crate::core::app_logs::{self, LogEntry};
+use crate::core::app_paths::AppPaths;
+use crate::core::backup::{self, BackupManifest, BackupVerification};
 use crate::core::cache_cleanup::{
-    cleanup_git_cache_dirs, get_git_cache_cleanup_days as get_git_cache_cleanup_days_core,
-    get_git_cache_ttl_secs as get_git_cache_ttl_secs_core,
+    build_git_cache_report, cleanup_git_cache_dirs,
+    evict_git_cache_entry as evict_git_cache_entry_core,
+    get_git_cache_cleanup_days as get_git_cache_cleanup_days_core,
+    get_git_cache_max_mb as get_git_cache_max_mb_core,
+    get_git_cache_ttl_secs as get_git_cache_ttl_secs_core, git_cache_max_bytes, git_cache_root,
     set_git_cache_cleanup_days as set_git_cache_cleanup_days_core,
-    set_git_cache_ttl_secs as set_git_cache_ttl_secs_core,
+    set_git_cache_max_mb as set_git_cache_max_mb_core,
+    set_git_cache_ttl_secs as set_git_cache_ttl_secs_core, GitCacheReport,
+};
+use crate::core::central_history::{self, SkillRevision};
+use crate::core::central_migration::{
+    plan_central_migration, run_central_migration, MigrationPlan,
+};
+use crate::core::central_remote::{self, CentralRemote, CentralRemoteSummary, PullResult};
+use crate::core::central_repo::{
+    self, ensure_central_repo, ensure_central_repo_writable, resolve_central_repo_path,
+    SHARED_SOURCE_TYPE,
 };
-use crate::core::central_repo::{ensure_central_repo, resolve_central_repo_path};
 use crate::core::clawhub_api;
-use crate::core::github_search::{search_github_repos, RepoSummary};
+use crate::core::cloud_folders::{self, CloudFolderStatus};
+use crate::core::content_scan::ContentFinding;
+use crate::core::cross_refs::{sync_with_cross_refs, sync_with_cross_refs_in_mode};
+use crate::core::errors::{coded, find_coded, resource_unsupported, tool_not_installed, ErrorCode};
+use crate::core::existing_skills::{self, ExistingSkillScan};
+use crate::core::github_search::{self, search_github_repos, RepoSearchFilters, RepoSummary};
+use crate::core::github_stars::{self, StarSuggestion};
+use crate::core::http_client::{self, HttpSettings};
 use crate::core::installer::{
     check_skill_updates as check_skill_updates_core, install_git_skill,
-    install_git_skill_from_selection, install_local_skill, install_local_skill_from_selection,
-    list_git_skills, list_local_skills, update_managed_skill_from_source, GitSkillCandidate,
-    InstallResult, LocalSkillCandidate, SkillUpdateStatus,
+    install_git_skill_from_selection, install_local_resource, install_local_skill,
+    install_local_skill_from_selection, list_git_skills, list_local_skills,
+    reinstall_skill_from_source, update_managed_skill_from_source, GitSkillCandidate,
+    InstallResult, LocalSkillCandidate, ReinstallResult, SkillUpdateStatus,
 };
+use crate::core::integrity::{self, IntegrityReport};
+use crate::core::jobs::{Job, JobQueue};
+use crate::core::locale::{self, tr, Msg};
+use crate::core::mcp_servers::{self, McpServer, McpSyncResult};
+use crate::core::multi_host_sync::{self, HostSyncResult};
+use crate::core::now_ms;
 use crate::core::onboarding::{build_onboarding_plan, OnboardingPlan};
-use crate::core::remote_sync;
+use crate::core::operations::{Operation, OperationHandle, OperationKind, OperationRegistry};
+use crate::core::orphan_targets::{self, OrphanReport};
+use crate::core::profiles::{self, Profile, ProfileList};
+use crate::core::project_scan::DetectedProject;
+use crate::core::project_targets::{self, ProjectSyncResult, ProjectTargetStatus};
+use crate::core::quarantine;
+use crate::core::registry_auth::{
+    DeviceLogin, DevicePollStatus, RegistryAuth, RegistryAuthSummary,
+};
+use crate::core::remote_propagation::{
+    forget_remote_tool, parse_remote_target_key, propagate_skill_update, record_remote_deployment,
+    remote_skill_info, RemotePropagationReport,
+};
+use crate::core::remote_schedule::{RemoteSyncSchedule, ScheduledSyncRun};
+use crate::core::remote_sync::{self, RemoteHostHealth};
+use crate::core::resource_types::{
+    resource_dir, resource_file, resource_target, sync_resource_file, tools_supporting,
+    ResourceType,
+};
+use crate::core::skill_authoring::{self, SkillWriteResult};
+use crate::core::skill_dependencies::{self, SkillDependency, SkillDependent};
+use crate::core::skill_drift::{self, SkillDrift};
+use crate::core::skill_events;
+use crate::core::skill_files::{self, SkillFileContent, SkillFileEntry};
+use crate::core::skill_format::{self, SkillFormat};
+use crate::core::skill_freshness::{self, SkillFreshness};
+use crate::core::skill_health::{skills_health, SkillHealth};
+use crate::core::skill_lock;
+use crate::core::skill_metadata::{self, SkillMetadata};
 use crate::core::skill_store::{
-    CustomTargetRecord, RemoteHostRecord, SkillStore, SkillTargetRecord,
+    default_db_path, CustomTargetRecord, RemoteHostRecord, SkillRecord, SkillStore,
+    SkillTargetRecord,
+};
+use crate::core::skill_templates::{self, TemplateList};
+use crate::core::skill_validation::{self, SkillIssue, SkillValidation};
+use crate::core::skillpkg::{self, SkillPkgInspection, SkillPkgManifest};
+use crate::core::source_providers::{self, resolve_provider, SourceProvider};
+use crate::core::source_trust::{self, check_clawhub_source, check_git_source, SourceTrustPolicy};
+use crate::core::ssh_retry::{get_retry_policy, set_retry_policy, RetryPolicy};
+use crate::core::startup::{StartupState, StartupStatus};
+use crate::core::storage_report::{build_storage_report, StorageReport};
+use crate::core::symlink_migration::{
+    adopt_farm_skill, detect_symlink_farms as detect_symlink_farms_core, SymlinkFarm,
+    LINKED_SOURCE_TYPE,
 };
-use crate::core::sync_engine::{
-    copy_dir_recursive, sync_dir_for_tool_with_overwrite, sync_dir_hybrid, SyncMode,
+use crate::core::sync_engine::{sync_dir_hybrid, SyncMode, SyncOutcome};
+use crate::core::sync_modes::{self, SyncModePreference, SyncModePrefs};
+use crate::core::sync_plan::{self, SyncAction, SyncPlan};
+use crate::core::sync_topology::{build_sync_topology, SyncTopology};
+use crate::core::target_backup::{self, OverwrittenTarget};
+use crate::core::target_conflict::target_conflict;
+use crate::core::target_naming::{
+    apply_local_rename, get_target_name_template as get_target_name_template_core,
+    plan_target_renames, record_renamed_target,
+    set_target_name_template as set_target_name_template_core, target_name_for, TargetRename,
 };
+use crate::core::target_staleness::{deployment_is_stale, target_is_stale};
+use crate::core::target_variables::{
+    custom_target_id, custom_target_key, expand_target_path, path_variables, remember_project,
+    PROJECT_VARIABLE,
+};
+use crate::core::target_verify::{self, TargetVerifyReport};
 use crate::core::tool_adapters::{adapter_by_key, is_tool_installed, resolve_default_path};
+use crate::core::tool_status::detect_tools;
+use crate::core::tool_versions::{clear_version_cache, detect_tool_versions};
+use crate::core::undo::{HeldContent, UndoKind, UndoStack, UndoSummary};
+use crate::core::watch_settings::{self, WatchSettings};
 use uuid::Uuid;
 
-fn format_anyhow_error(err: anyhow::Error) -> String {
-    let first = err.to_string();
-    // Frontend relies on these prefixes for special flows.
-    if first.starts_with("MULTI_SKILLS|")
-        || first.starts_with("TARGET_EXISTS|")
-        || first.starts_with("TOOL_NOT_INSTALLED|")
-    {
-        return first;
-    }
-
-    // Include the full error chain (causes), not just the top context.
-    let mut full = format!("{:#}", err);
-
-    // Redact noisy temp paths from clone context (we care about the cause, not the dest).
-    // Example: `clone https://... into "/Users/.../skills-hub-git-<uuid>"`
-    if let Some(head) = full.lines().next() {
-        if head.starts_with("clone ") {
-            if let Some(pos) = head.find(" into ") {
-                let head_redacted = format!("{} (已省略临时目录)", &head[..pos]);
-                let rest: String = full.lines().skip(1).collect::<Vec<_>>().join("\n");
-                full = if rest.is_empty() {
-                    head_redacted
-                } else {
-                    format!("{}\n{}", head_redacted, rest)
-                };
-            }
-        }
-    }
-
-    let root = err.root_cause().to_string();
-    let lower = full.to_lowercase();
-
-    // Heuristic-friendly messaging for GitHub clone failures.
-    if lower.contains("github.com")
-        && (lower.contains("clone ") || lower.contains("remote") || lower.contains("fetch"))
-    {
-        if lower.contains("securetransport") {
-            return format!(
-        "无法从 GitHub 拉取仓库：TLS/证书校验失败（macOS SecureTransport）。\n\n建议：\n- 检查网络/代理是否拦截 HTTPS\n- 如在公司网络，可能需要安装公司根证书或使用可信代理\n- 也可在终端确认 `git clone {}` 是否可用\n\n详细：{}",
-        "https://github.com/<owner>/<repo>",
-        root
-      );
-        }
-        let hint = if lower.contains("authentication")
-            || lower.contains("permission denied")
-            || lower.contains("credentials")
-        {
-            "无法访问该仓库：可能是私有仓库/权限不足/需要鉴权。"
-        } else if lower.contains("not found") {
-            "仓库不存在或无权限访问（GitHub 返回 not found）。"
-        } else if lower.contains("failed to resolve")
-            || lower.contains("could not resolve")
-            || lower.contains("dns")
-        {
-            "无法解析 GitHub 域名（DNS）。请检查网络/代理。"
-        } else if lower.contains("timed out") || lower.contains("timeout") {
-            "连接 GitHub 超时。请检查网络/代理。"
-        } else if lower.contains("connection refused") || lower.contains("connection reset") {
-            "连接 GitHub 失败（连接被拒绝/重置）。请检查网络/代理。"
-        } else {
-            "无法从 GitHub 拉取仓库。请检查网络/代理，或稍后重试。"
-        };
-
-        return format!("{}\n\n详细：{}", hint, root);
-    }
-
-    full
-}
+pub use error::CommandError;
 
 #[derive(Debug, Serialize)]
 pub struct ToolInfoDto {
@@ -103,6 +128,8 @@ pub struct ToolInfoDto {
     pub label: String,
     pub installed: bool,
     pub skills_dir: String,
+    /// From the tool's `--version`; `None` when not installed or unknown.
+    pub version: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -112,27 +139,44 @@ pub struct ToolStatusDto {
     pub newly_installed: Vec<String>,
 }
 
+/// Installed tools and their skill folders. Detection is cached briefly;
+/// `force` probes again and re-reads tool versions.
 #[tauri::command]
-pub async fn get_tool_status(store: State<'_, SkillStore>) -> Result<ToolStatusDto, String> {
+pub async fn get_tool_status(
+    store: State<'_, SkillStore>,
+    force: Option<bool>,
+) -> Result<ToolStatusDto, CommandError> {
     let store = store.inner().clone();
+    let force = force.unwrap_or(false);
     tauri::async_runtime::spawn_blocking(move || {
+        if force {
+            clear_version_cache();
+        }
+        let detected = detect_tools(force)?;
         let adapters = crate::core::tool_adapters::default_tool_adapters();
+        let installed_adapters: Vec<_> = adapters
+            .iter()
+            .filter(|adapter| {
+                detected
+                    .iter()
+                    .any(|tool| tool.installed && tool.key == adapter.id.as_key())
+            })
+            .collect();
+        let mut versions = detect_tool_versions(&installed_adapters);
+
         let mut tools: Vec<ToolInfoDto> = Vec::new();
         let mut installed: Vec<String> = Vec::new();
-
-        for adapter in &adapters {
-            let ok = is_tool_installed(adapter)?;
-            let key = adapter.id.as_key().to_string();
-            let skills_dir = resolve_default_path(adapter)?.to_string_lossy().to_string();
+        for tool in detected {
+            if tool.installed {
+                installed.push(tool.key.clone());
+            }
             tools.push(ToolInfoDto {
-                key: key.clone(),
-                label: adapter.display_name.to_string(),
-                installed: ok,
-                skills_dir,
+                version: versions.remove(&tool.key).flatten(),
+                key: tool.key,
+                label: tool.label,
+                installed: tool.installed,
+                skills_dir: tool.skills_dir,
             });
-            if ok {
-                installed.push(key);
-            }
         }
 
         installed.dedup();
@@ -162,787 +206,3672 @@ pub async fn get_tool_status(store: State<'_, SkillStore>) -> Result<ToolStatusD
         })
     })
     .await
-    .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
-pub async fn get_onboarding_plan(
-    app: tauri::AppHandle,
+pub async fn get_tool_path_overrides(
+    store: State<'_, SkillStore>,
+) -> Result<HashMap<String, String>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::core::tool_adapters::get_tool_path_overrides(&store)
+    })
+    .await
+    .map_err(CommandError::from)
+}
+
+/// An empty or missing `path` removes the override.
+#[tauri::command]
+pub async fn set_tool_path_override(
+    store: State<'_, SkillStore>,
+    tool: String,
+    path: Option<String>,
+) -> Result<HashMap<String, String>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::core::tool_adapters::set_tool_path_override(&store, &tool, path.as_deref())
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn get_tool_formats(
     store: State<'_, SkillStore>,
-) -> Result<OnboardingPlan, String> {
+) -> Result<HashMap<String, SkillFormat>, CommandError> {
     let store = store.inner().clone();
-    tauri::async_runtime::spawn_blocking(move || build_onboarding_plan(&app, &store))
+    tauri::async_runtime::spawn_blocking(move || skill_format::get_tool_formats(&store))
         .await
-        .map_err(|err| err.to_string())?
-        .map_err(format_anyhow_error)
+        .map_err(CommandError::from)
 }
 
+/// A missing `format` goes back to the tool's default.
 #[tauri::command]
-pub async fn get_git_cache_cleanup_days(store: State<'_, SkillStore>) -> Result<i64, String> {
+pub async fn set_tool_format(
+    store: State<'_, SkillStore>,
+    tool: String,
+    format: Option<SkillFormat>,
+) -> Result<HashMap<String, SkillFormat>, CommandError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
-        Ok::<_, anyhow::Error>(get_git_cache_cleanup_days_core(&store))
+        skill_format::set_tool_format(&store, &tool, format)
     })
     .await
-    .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
-pub async fn set_git_cache_cleanup_days(
+pub async fn get_sync_mode_prefs(
     store: State<'_, SkillStore>,
-    days: i64,
-) -> Result<i64, String> {
+) -> Result<SyncModePrefs, CommandError> {
     let store = store.inner().clone();
-    tauri::async_runtime::spawn_blocking(move || set_git_cache_cleanup_days_core(&store, days))
+    tauri::async_runtime::spawn_blocking(move || sync_modes::get_sync_mode_prefs(&store))
         .await
-        .map_err(|err| err.to_string())?
-        .map_err(format_anyhow_error)
+        .map_err(CommandError::from)
 }
 
+/// A missing `mode` goes back to `auto`.
 #[tauri::command]
-pub async fn clear_git_cache_now(app: tauri::AppHandle) -> Result<usize, String> {
+pub async fn set_tool_sync_mode(
+    store: State<'_, SkillStore>,
+    tool: String,
+    mode: Option<SyncModePreference>,
+) -> Result<SyncModePrefs, CommandError> {
+    let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
-        cleanup_git_cache_dirs(&app, std::time::Duration::from_secs(0))
+        sync_modes::set_tool_sync_mode(&store, &tool, mode)
     })
     .await
-    .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
+/// A missing `mode` follows the tools' preferences again.
 #[tauri::command]
-pub async fn get_git_cache_ttl_secs(store: State<'_, SkillStore>) -> Result<i64, String> {
+#[allow(non_snake_case)]
+pub async fn set_skill_sync_mode(
+    store: State<'_, SkillStore>,
+    skillId: String,
+    mode: Option<SyncModePreference>,
+) -> Result<SyncModePrefs, CommandError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
-        Ok::<_, anyhow::Error>(get_git_cache_ttl_secs_core(&store))
+        sync_modes::set_skill_sync_mode(&store, &skillId, mode)
     })
     .await
-    .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
+/// Keys of the tools that have a directory for `resourceType`.
 #[tauri::command]
-pub async fn set_git_cache_ttl_secs(
+#[allow(non_snake_case)]
+pub fn list_resource_tools(resourceType: ResourceType) -> Vec<String> {
+    tools_supporting(resourceType)
+}
+
+#[tauri::command]
+pub async fn list_mcp_servers(
     store: State<'_, SkillStore>,
-    secs: i64,
-) -> Result<i64, String> {
+) -> Result<Vec<McpServer>, CommandError> {
     let store = store.inner().clone();
-    tauri::async_runtime::spawn_blocking(move || set_git_cache_ttl_secs_core(&store, secs))
+    tauri::async_runtime::spawn_blocking(move || mcp_servers::list_mcp_servers(&store))
         .await
-        .map_err(|err| err.to_string())?
-        .map_err(format_anyhow_error)
+        .map_err(CommandError::from)
 }
 
-#[derive(Debug, Serialize)]
-pub struct InstallResultDto {
-    pub skill_id: String,
-    pub name: String,
-    pub central_path: String,
-    pub content_hash: Option<String>,
+/// Add a server, or replace the one with the same name.
+#[tauri::command]
+pub async fn save_mcp_server(
+    store: State<'_, SkillStore>,
+    server: McpServer,
+) -> Result<Vec<McpServer>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || mcp_servers::upsert_mcp_server(&store, server))
+        .await
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
 }
 
-fn expand_home_path(input: &str) -> Result<std::path::PathBuf, anyhow::Error> {
-    let trimmed = input.trim();
-    if trimmed.is_empty() {
-        anyhow::bail!("storage path is empty");
-    }
-    if trimmed == "~" {
-        let home = dirs::home_dir().context("failed to resolve home directory")?;
-        return Ok(home);
-    }
-    if let Some(stripped) = trimmed.strip_prefix("~/") {
-        let home = dirs::home_dir().context("failed to resolve home directory")?;
-        return Ok(home.join(stripped));
-    }
-    Ok(std::path::PathBuf::from(trimmed))
+#[tauri::command]
+pub async fn delete_mcp_server(
+    store: State<'_, SkillStore>,
+    name: String,
+) -> Result<Vec<McpServer>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || mcp_servers::remove_mcp_server(&store, &name))
+        .await
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
-pub async fn get_central_repo_path(
-    app: tauri::AppHandle,
+pub fn list_mcp_tools() -> Vec<String> {
+    mcp_servers::mcp_tools()
+}
+
+/// Write the central servers into `tool`'s MCP config. Same-named entries
+/// the user added by hand are reported as conflicts unless `overwrite`.
+#[tauri::command]
+pub async fn sync_mcp_servers(
     store: State<'_, SkillStore>,
-) -> Result<String, String> {
+    tool: String,
+    overwrite: Option<bool>,
+) -> Result<McpSyncResult, CommandError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
-        let path = resolve_central_repo_path(&app, &store)?;
-        ensure_central_repo(&path)?;
-        Ok::<_, anyhow::Error>(path.to_string_lossy().to_string())
+        mcp_servers::sync_mcp_servers(&store, &tool, overwrite.unwrap_or(false))
     })
     .await
-    .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
-pub async fn set_central_repo_path(
-    app: tauri::AppHandle,
+pub async fn get_onboarding_plan(
+    paths: State<'_, AppPaths>,
     store: State<'_, SkillStore>,
-    path: String,
-) -> Result<String, String> {
+) -> Result<OnboardingPlan, CommandError> {
+    let paths = paths.inner().clone();
     let store = store.inner().clone();
-    tauri::async_runtime::spawn_blocking(move || {
-        let new_base = expand_home_path(&path)?;
-        if !new_base.is_absolute() {
-            anyhow::bail!("storage path must be absolute");
-        }
-        ensure_central_repo(&new_base)?;
-
-        let current_base = resolve_central_repo_path(&app, &store)?;
-        let skills = store.list_skills()?;
-        if current_base == new_base {
-            store.set_setting("central_repo_path", new_base.to_string_lossy().as_ref())?;
-            return Ok::<_, anyhow::Error>(new_base.to_string_lossy().to_string());
-        }
-
-        if !skills.is_empty() {
-            for skill in skills {
-                let old_path = std::path::PathBuf::from(&skill.central_path);
-                if !old_path.exists() {
-                    anyhow::bail!("central path not found: {:?}", old_path);
-                }
-                let file_name = old_path
-                    .file_name()
-                    .ok_or_else(|| anyhow::anyhow!("invalid central path: {:?}", old_path))?;
-                let new_path = new_base.join(file_name);
-                if new_path.exists() {
-                    anyhow::bail!("target path already exists: {:?}", new_path);
-                }
+    tauri::async_runtime::spawn_blocking(move || build_onboarding_plan(&paths, &store))
+        .await
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
+}
 
-                if let Err(err) = std::fs::rename(&old_path, &new_path) {
-                    copy_dir_recursive(&old_path, &new_path)
-                        .with_context(|| format!("copy {:?} -> {:?}", old_path, new_path))?;
-                    std::fs::remove_dir_all(&old_path)
-                        .with_context(|| format!("cleanup {:?}", old_path))?;
-                    // Surface rename error in logs for troubleshooting.
-                    eprintln!("rename failed, fallback used: {}", err);
-                }
+#[tauri::command]
+pub async fn get_git_cache_cleanup_days(store: State<'_, SkillStore>) -> Result<i64, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        Ok::<_, anyhow::Error>(get_git_cache_cleanup_days_core(&store))
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
 
-                let mut updated = skill.clone();
-                updated.central_path = new_path.to_string_lossy().to_string();
-                updated.updated_at = now_ms();
-                store.upsert_skill(&updated)?;
-            }
-        }
+#[tauri::command]
+pub async fn set_git_cache_cleanup_days(
+    store: State<'_, SkillStore>,
+    days: i64,
+) -> Result<i64, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || set_git_cache_cleanup_days_core(&store, days))
+        .await
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
+}
 
-        store.set_setting("central_repo_path", new_base.to_string_lossy().as_ref())?;
-        Ok::<_, anyhow::Error>(new_base.to_string_lossy().to_string())
+#[tauri::command]
+pub async fn clear_git_cache_now(paths: State<'_, AppPaths>) -> Result<usize, CommandError> {
+    let paths = paths.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        cleanup_git_cache_dirs(&paths, Some(std::time::Duration::ZERO), None)
     })
     .await
-    .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
+/// Cached clones with their sizes and last use, largest first.
 #[tauri::command]
-#[allow(non_snake_case)]
-pub async fn install_local(
-    app: tauri::AppHandle,
+pub async fn get_git_cache_report(
+    paths: State<'_, AppPaths>,
     store: State<'_, SkillStore>,
-    sourcePath: String,
-    name: Option<String>,
-) -> Result<InstallResultDto, String> {
+) -> Result<GitCacheReport, CommandError> {
+    let paths = paths.inner().clone();
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
-        let result = install_local_skill(&app, &store, sourcePath.as_ref(), name)?;
-        Ok::<_, anyhow::Error>(to_install_dto(result))
+        build_git_cache_report(&git_cache_root(&paths), git_cache_max_bytes(&store))
     })
     .await
-    .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
+/// Drop the cached clones of one repository; returns how many were removed.
 #[tauri::command]
 #[allow(non_snake_case)]
-pub async fn list_local_skills_cmd(basePath: String) -> Result<Vec<LocalSkillCandidate>, String> {
+pub async fn evict_git_cache_entry(
+    paths: State<'_, AppPaths>,
+    repoUrl: String,
+) -> Result<usize, CommandError> {
+    let paths = paths.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
-        let path = std::path::PathBuf::from(basePath);
-        list_local_skills(&path)
+        evict_git_cache_entry_core(&git_cache_root(&paths), &repoUrl)
     })
     .await
-    .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
-#[allow(non_snake_case)]
-pub async fn install_local_selection(
-    app: tauri::AppHandle,
-    store: State<'_, SkillStore>,
-    basePath: String,
-    subpath: String,
-    name: Option<String>,
-) -> Result<InstallResultDto, String> {
+pub async fn get_git_cache_ttl_secs(store: State<'_, SkillStore>) -> Result<i64, CommandError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
-        let base = std::path::PathBuf::from(basePath);
-        let result =
-            install_local_skill_from_selection(&app, &store, base.as_ref(), &subpath, name)?;
-        Ok::<_, anyhow::Error>(to_install_dto(result))
+        Ok::<_, anyhow::Error>(get_git_cache_ttl_secs_core(&store))
     })
     .await
-    .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
-#[allow(non_snake_case)]
-pub async fn install_git(
-    app: tauri::AppHandle,
+pub async fn set_git_cache_ttl_secs(
     store: State<'_, SkillStore>,
-    repoUrl: String,
-    name: Option<String>,
-) -> Result<InstallResultDto, String> {
+    secs: i64,
+) -> Result<i64, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || set_git_cache_ttl_secs_core(&store, secs))
+        .await
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn get_git_cache_max_mb(store: State<'_, SkillStore>) -> Result<i64, CommandError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
-        let result = install_git_skill(&app, &store, &repoUrl, name)?;
-        Ok::<_, anyhow::Error>(to_install_dto(result))
+        Ok::<_, anyhow::Error>(get_git_cache_max_mb_core(&store))
     })
     .await
-    .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
+/// Size cap of the git clone cache in MB; 0 removes the cap.
 #[tauri::command]
-#[allow(non_snake_case)]
-pub async fn list_git_skills_cmd(
-    app: tauri::AppHandle,
+pub async fn set_git_cache_max_mb(
     store: State<'_, SkillStore>,
-    repoUrl: String,
-) -> Result<Vec<GitSkillCandidate>, String> {
+    mb: i64,
+) -> Result<i64, CommandError> {
     let store = store.inner().clone();
-    tauri::async_runtime::spawn_blocking(move || list_git_skills(&app, &store, &repoUrl))
+    tauri::async_runtime::spawn_blocking(move || set_git_cache_max_mb_core(&store, mb))
         .await
-        .map_err(|err| err.to_string())?
-        .map_err(format_anyhow_error)
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
-#[allow(non_snake_case)]
-pub async fn install_git_selection(
-    app: tauri::AppHandle,
-    store: State<'_, SkillStore>,
-    repoUrl: String,
-    subpath: String,
-    name: Option<String>,
-) -> Result<InstallResultDto, String> {
-    let store = store.inner().clone();
+pub async fn list_profiles(paths: State<'_, AppPaths>) -> Result<ProfileList, CommandError> {
+    let paths = paths.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
-        let result = install_git_skill_from_selection(&app, &store, &repoUrl, &subpath, name)?;
-        Ok::<_, anyhow::Error>(to_install_dto(result))
+        let db_path = default_db_path(&paths)?;
+        Ok::<_, anyhow::Error>(profiles::list_profiles(&db_path))
     })
     .await
-    .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
-#[derive(Debug, Serialize)]
-pub struct SyncResultDto {
-    pub mode_used: String,
-    pub target_path: String,
+#[tauri::command]
+pub async fn create_profile(
+    paths: State<'_, AppPaths>,
+    name: String,
+) -> Result<Profile, CommandError> {
+    let paths = paths.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let db_path = default_db_path(&paths)?;
+        profiles::create_profile(&db_path, &name)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
+/// Choose the profile the app opens with. Skills, targets and settings all
+/// live in the profile's database, so the switch happens on relaunch.
 #[tauri::command]
-pub async fn sync_skill_dir(
-    source_path: String,
-    target_path: String,
-) -> Result<SyncResultDto, String> {
+#[allow(non_snake_case)]
+pub async fn switch_profile(
+    paths: State<'_, AppPaths>,
+    profileId: String,
+) -> Result<(), CommandError> {
+    let paths = paths.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
-        let result = sync_dir_hybrid(source_path.as_ref(), target_path.as_ref())?;
-        Ok::<_, anyhow::Error>(SyncResultDto {
-            mode_used: match result.mode_used {
-                SyncMode::Auto => "auto",
-                SyncMode::Symlink => "symlink",
-                SyncMode::Junction => "junction",
-                SyncMode::Copy => "copy",
-            }
-            .to_string(),
-            target_path: result.target_path.to_string_lossy().to_string(),
-        })
+        let db_path = default_db_path(&paths)?;
+        profiles::set_next_profile(&db_path, &profileId)
     })
     .await
-    .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
 #[allow(non_snake_case)]
-pub async fn sync_skill_to_tool(
+pub async fn delete_profile(
+    paths: State<'_, AppPaths>,
+    profileId: String,
+) -> Result<(), CommandError> {
+    let paths = paths.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let db_path = default_db_path(&paths)?;
+        profiles::delete_profile(&db_path, &profileId)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+/// Check skill records against the central repo and their targets; with
+/// `repair`, re-hash edited skills, re-sync broken targets and adopt orphans.
+#[tauri::command]
+pub async fn verify_central_repo(
+    paths: State<'_, AppPaths>,
     store: State<'_, SkillStore>,
-    sourcePath: String,
-    skillId: String,
-    tool: String,
-    name: String,
-    overwrite: Option<bool>,
-) -> Result<SyncResultDto, String> {
+    repair: Option<bool>,
+) -> Result<IntegrityReport, CommandError> {
+    let paths = paths.inner().clone();
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
-        let adapter = adapter_by_key(&tool).ok_or_else(|| anyhow::anyhow!("unknown tool"))?;
-        if !is_tool_installed(&adapter)? {
-            anyhow::bail!("TOOL_NOT_INSTALLED|{}", adapter.id.as_key());
-        }
-        let tool_root = resolve_default_path(&adapter)?;
-        let target = tool_root.join(&name);
-        let overwrite = overwrite.unwrap_or(false);
-        let result =
-            sync_dir_for_tool_with_overwrite(&tool, sourcePath.as_ref(), &target, overwrite)
-                .map_err(|err| {
-                    let msg = err.to_string();
-                    if msg.contains("target already exists") {
-                        anyhow::anyhow!("TARGET_EXISTS|{}", target.to_string_lossy())
-                    } else {
-                        anyhow::anyhow!(msg)
-                    }
-                })?;
-
-        // Some tools share the same global skills directory; keep DB records consistent across them.
-        let group = crate::core::tool_adapters::adapters_sharing_skills_dir(&adapter);
-        for a in group {
-            if !is_tool_installed(&a)? {
-                continue;
-            }
-            let record = SkillTargetRecord {
-                id: Uuid::new_v4().to_string(),
-                skill_id: skillId.clone(),
-                tool: a.id.as_key().to_string(),
-                target_path: result.target_path.to_string_lossy().to_string(),
-                mode: match result.mode_used {
-                    SyncMode::Auto => "auto",
-                    SyncMode::Symlink => "symlink",
-                    SyncMode::Junction => "junction",
-                    SyncMode::Copy => "copy",
-                }
-                .to_string(),
-                status: "ok".to_string(),
-                last_error: None,
-                synced_at: Some(now_ms()),
-            };
-            store.upsert_skill_target(&record)?;
-        }
+        let central = resolve_central_repo_path(&paths, &store)?;
+        integrity::verify_central_repo(&store, &central, repair.unwrap_or(false))
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
 
-        Ok::<_, anyhow::Error>(SyncResultDto {
-            mode_used: match result.mode_used {
-                SyncMode::Auto => "auto",
-                SyncMode::Symlink => "symlink",
-                SyncMode::Junction => "junction",
-                SyncMode::Copy => "copy",
-            }
-            .to_string(),
-            target_path: result.target_path.to_string_lossy().to_string(),
-        })
+/// Check every local target link or copy; with `repair`, sync broken ones
+/// again in the same pass.
+#[tauri::command]
+pub async fn verify_all_targets(
+    store: State<'_, SkillStore>,
+    repair: Option<bool>,
+) -> Result<TargetVerifyReport, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        target_verify::verify_all_targets(&store, repair.unwrap_or(false))
     })
     .await
-    .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
+/// What the central repo, the git cache and the undo holding area take up.
 #[tauri::command]
-#[allow(non_snake_case)]
-pub async fn unsync_skill_from_tool(
+pub async fn get_storage_report(
+    paths: State<'_, AppPaths>,
     store: State<'_, SkillStore>,
-    skillId: String,
-    tool: String,
-) -> Result<(), String> {
+    undo: State<'_, UndoStack>,
+) -> Result<StorageReport, CommandError> {
+    let paths = paths.inner().clone();
     let store = store.inner().clone();
+    let trash_root = undo.root().to_path_buf();
     tauri::async_runtime::spawn_blocking(move || {
-        // Some tools share the same global skills directory; unsync should update all of them.
-        let group_tool_keys: Vec<String> = if let Some(adapter) = adapter_by_key(&tool) {
-            let group = crate::core::tool_adapters::adapters_sharing_skills_dir(&adapter);
-            // If none of the group tools are installed, do nothing (treat as already not effective).
-            let mut any_installed = false;
-            for a in &group {
-                if is_tool_installed(a)? {
-                    any_installed = true;
-                    break;
-                }
-            }
-            if !any_installed {
-                return Ok::<_, anyhow::Error>(());
-            }
-            group
-                .into_iter()
-                .map(|a| a.id.as_key().to_string())
-                .collect()
-        } else {
-            vec![tool.clone()]
-        };
-
-        // Remove filesystem target once (shared dir => shared target path).
-        let mut removed = false;
-        for k in &group_tool_keys {
-            if let Some(target) = store.get_skill_target(&skillId, k)? {
-                if !removed {
-                    remove_path_any(&target.target_path).map_err(anyhow::Error::msg)?;
-                    removed = true;
-                }
-                store.delete_skill_target(&skillId, k)?;
-            }
-        }
-
-        Ok::<_, anyhow::Error>(())
+        let central = resolve_central_repo_path(&paths, &store)?;
+        let cache = git_cache_root(&paths);
+        build_storage_report(&store, &central, &cache, &trash_root)
     })
     .await
-    .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
-#[derive(Debug, Serialize)]
-pub struct UpdateResultDto {
-    pub skill_id: String,
-    pub name: String,
-    pub content_hash: Option<String>,
-    pub source_revision: Option<String>,
-    pub updated_targets: Vec<String>,
+#[tauri::command]
+pub async fn get_remote_retry_policy(
+    store: State<'_, SkillStore>,
+) -> Result<RetryPolicy, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || Ok::<_, CommandError>(get_retry_policy(&store)))
+        .await
+        .map_err(CommandError::from)?
 }
 
 #[tauri::command]
 #[allow(non_snake_case)]
-pub async fn update_managed_skill(
-    app: tauri::AppHandle,
+pub async fn set_remote_retry_policy(
     store: State<'_, SkillStore>,
-    skillId: String,
-) -> Result<UpdateResultDto, String> {
+    attempts: u32,
+    backoffMs: u64,
+) -> Result<RetryPolicy, CommandError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
-        let res = update_managed_skill_from_source(&app, &store, &skillId)?;
-        Ok::<_, anyhow::Error>(UpdateResultDto {
-            skill_id: res.skill_id,
-            name: res.name,
-            content_hash: res.content_hash,
-            source_revision: res.source_revision,
-            updated_targets: res.updated_targets,
-        })
+        set_retry_policy(
+            &store,
+            RetryPolicy {
+                attempts,
+                backoff_ms: backoffMs,
+            },
+        )
     })
     .await
-    .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
-pub async fn check_skill_updates(
+pub async fn get_http_settings(store: State<'_, SkillStore>) -> Result<HttpSettings, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        Ok::<_, CommandError>(http_client::get_http_settings(&store))
+    })
+    .await
+    .map_err(CommandError::from)?
+}
+
+#[tauri::command]
+pub async fn set_http_settings(
     store: State<'_, SkillStore>,
-) -> Result<Vec<SkillUpdateStatus>, String> {
+    settings: HttpSettings,
+) -> Result<HttpSettings, CommandError> {
     let store = store.inner().clone();
-    tauri::async_runtime::spawn_blocking(move || Ok::<_, String>(check_skill_updates_core(&store)))
+    tauri::async_runtime::spawn_blocking(move || http_client::set_http_settings(&store, settings))
         .await
-        .map_err(|err| err.to_string())?
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
 }
 
+/// `error`, `warn`, `info`, `debug`, `trace` or `off`.
 #[tauri::command]
-pub async fn search_github(query: String, limit: Option<u32>) -> Result<Vec<RepoSummary>, String> {
-    let limit = limit.unwrap_or(10) as usize;
-    tauri::async_runtime::spawn_blocking(move || search_github_repos(&query, limit))
-        .await
-        .map_err(|err| err.to_string())?
-        .map_err(format_anyhow_error)
+pub async fn get_log_level(store: State<'_, SkillStore>) -> Result<String, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        Ok::<_, CommandError>(app_logs::level_name(app_logs::get_log_level(&store)))
+    })
+    .await
+    .map_err(CommandError::from)?
 }
 
+/// Takes effect immediately and is kept across restarts.
 #[tauri::command]
-#[allow(non_snake_case)]
-pub async fn import_existing_skill(
-    app: tauri::AppHandle,
+pub async fn set_log_level(
     store: State<'_, SkillStore>,
-    sourcePath: String,
-    name: Option<String>,
-) -> Result<InstallResultDto, String> {
+    level: String,
+) -> Result<String, CommandError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
-        let result = install_local_skill(&app, &store, sourcePath.as_ref(), name)?;
-        Ok::<_, anyhow::Error>(to_install_dto(result))
+        let level = app_logs::set_log_level(&store, &level)?;
+        Ok::<_, anyhow::Error>(app_logs::level_name(level))
     })
     .await
-    .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
-#[derive(Debug, Serialize)]
-pub struct ManagedSkillDto {
-    pub id: String,
-    pub name: String,
-    pub source_type: String,
-    pub source_ref: Option<String>,
-    pub central_path: String,
-    pub created_at: i64,
-    pub updated_at: i64,
-    pub last_sync_at: Option<i64>,
-    pub status: String,
-    pub targets: Vec<SkillTargetDto>,
-    pub group_name: Option<String>,
+/// Language of backend error messages: `en`, `zh-CN` or `zh-TW`.
+#[tauri::command]
+pub async fn get_locale(store: State<'_, SkillStore>) -> Result<locale::Locale, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || Ok::<_, CommandError>(locale::get_locale(&store)))
+        .await
+        .map_err(CommandError::from)?
 }
 
-#[derive(Debug, Serialize)]
-pub struct SkillTargetDto {
-    pub tool: String,
-    pub mode: String,
-    pub status: String,
-    pub target_path: String,
-    pub synced_at: Option<i64>,
+/// The frontend calls this whenever its language changes.
+#[tauri::command]
+pub async fn set_locale(
+    store: State<'_, SkillStore>,
+    locale: String,
+) -> Result<locale::Locale, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || locale::set_locale(&store, &locale))
+        .await
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
 }
 
+/// The last `lines` log records (200 by default) at `level` or more severe,
+/// oldest first, for attaching to bug reports.
 #[tauri::command]
-pub fn get_managed_skills(store: State<'_, SkillStore>) -> Result<Vec<ManagedSkillDto>, String> {
-    get_managed_skills_impl(store.inner())
+pub async fn get_recent_logs(
+    paths: State<'_, AppPaths>,
+    lines: Option<usize>,
+    level: Option<String>,
+) -> Result<Vec<LogEntry>, CommandError> {
+    let paths = paths.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let min_level = match level {
+            Some(level) => app_logs::parse_level(&level)?.to_level(),
+            None => Some(log::Level::Trace),
+        };
+        let Some(min_level) = min_level else {
+            return Ok(Vec::new());
+        };
+        let dir = app_logs::app_log_dir(&paths);
+        app_logs::read_recent_logs(&dir, lines.unwrap_or(200), min_level)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
-#[allow(non_snake_case)]
-pub async fn delete_managed_skill(
+pub async fn get_remote_sync_schedule(
     store: State<'_, SkillStore>,
-    skillId: String,
-) -> Result<(), String> {
+) -> Result<RemoteSyncSchedule, CommandError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
-        // 便于排查“按钮点了没反应”：确认前端确实触发了命令
-        println!("[delete_managed_skill] skillId={}", skillId);
-
-        // 先删除已同步到各工具目录的副本/软链接
-        // 注意：如果先删 skills 行，会触发 skill_targets cascade，导致无法再拿到 target_path
-        let targets = store.list_skill_targets(&skillId)?;
-
-        let mut remove_failures: Vec<String> = Vec::new();
-        for target in targets {
-            if let Err(err) = remove_path_any(&target.target_path) {
-                remove_failures.push(format!("{}: {}", target.target_path, err));
-            }
-        }
-
-        let record = store.get_skill_by_id(&skillId)?;
-        if let Some(ref skill) = record {
-            // ── 清理所有 remote hosts 上的技能 ──
-            if let Ok(hosts) = store.list_remote_hosts() {
-                println!(
-                    "[delete_managed_skill] found {} remote hosts to clean",
-                    hosts.len()
-                );
-                for host in hosts {
-                    match remote_sync::create_ssh_session(
-                        &host.host,
-                        host.port as u16,
-                        &host.username,
-                        &host.auth_method,
-                        host.key_path.as_deref(),
-                    ) {
-                        Ok(sess) => {
-                            if let Err(err) =
-                                remote_sync::remove_skill_from_remote(&sess, &skill.name)
-                            {
-                                remove_failures.push(format!("remote({}): {}", host.label, err));
-                            }
-                        }
-                        Err(err) => {
-                            remove_failures
-                                .push(format!("remote({}) SSH 连接失败: {}", host.label, err));
-                        }
-                    }
-                }
-            }
-
-            let path = std::path::PathBuf::from(&skill.central_path);
-            if path.exists() {
-                std::fs::remove_dir_all(&path)?;
-            }
-            store.delete_skill(&skillId)?;
-        }
-
-        if !remove_failures.is_empty() {
-            anyhow::bail!(
-                "已删除托管记录，但清理部分目录失败：\n- {}",
-                remove_failures.join("\n- ")
-            );
-        }
-
-        Ok::<_, anyhow::Error>(())
+        Ok::<_, anyhow::Error>(crate::core::remote_schedule::get_schedule(&store))
     })
     .await
-    .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
-}
-
-fn remove_path_any(path: &str) -> Result<(), String> {
-    let p = std::path::Path::new(path);
-    if !p.exists() {
-        return Ok(());
-    }
-
-    let meta = std::fs::symlink_metadata(p).map_err(|err| err.to_string())?;
-    let ft = meta.file_type();
-
-    // 软链接（即使指向目录）也应该用 remove_file 删除链接本身
-    if ft.is_symlink() {
-        std::fs::remove_file(p).map_err(|err| err.to_string())?;
-        return Ok(());
-    }
-
-    if ft.is_dir() {
-        std::fs::remove_dir_all(p).map_err(|err| err.to_string())?;
-        return Ok(());
-    }
-
-    std::fs::remove_file(p).map_err(|err| err.to_string())?;
-    Ok(())
-}
-
-fn to_install_dto(result: InstallResult) -> InstallResultDto {
-    InstallResultDto {
-        skill_id: result.skill_id,
-        name: result.name,
-        central_path: result.central_path.to_string_lossy().to_string(),
-        content_hash: result.content_hash,
-    }
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
-fn now_ms() -> i64 {
-    let now = std::time::SystemTime::now()
-        .duration_since(std::time::SystemTime::UNIX_EPOCH)
-        .unwrap_or_default();
-    now.as_millis() as i64
+#[tauri::command]
+pub async fn set_remote_sync_schedule(
+    store: State<'_, SkillStore>,
+    schedule: RemoteSyncSchedule,
+) -> Result<RemoteSyncSchedule, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::core::remote_schedule::set_schedule(&store, schedule)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
-fn get_managed_skills_impl(store: &SkillStore) -> Result<Vec<ManagedSkillDto>, String> {
-    let skills = store.list_skills().map_err(|err| err.to_string())?;
-    Ok(skills
-        .into_iter()
-        .map(|skill| {
-            let targets = store
-                .list_skill_targets(&skill.id)
-                .unwrap_or_default()
-                .into_iter()
-                .map(|target| SkillTargetDto {
-                    tool: target.tool,
-                    mode: target.mode,
-                    status: target.status,
-                    target_path: target.target_path,
-                    synced_at: target.synced_at,
-                })
-                .collect();
-
-            ManagedSkillDto {
-                id: skill.id,
-                name: skill.name,
-                source_type: skill.source_type,
-                source_ref: skill.source_ref,
-                central_path: skill.central_path,
-                created_at: skill.created_at,
-                updated_at: skill.updated_at,
-                last_sync_at: skill.last_sync_at,
-                status: skill.status,
-                targets,
-                group_name: skill.group_name,
-            }
-        })
-        .collect())
+#[tauri::command]
+pub async fn get_last_scheduled_sync(
+    store: State<'_, SkillStore>,
+) -> Result<Option<ScheduledSyncRun>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || crate::core::remote_schedule::last_run(&store))
+        .await
+        .map_err(CommandError::from)
 }
 
+/// Run the schedule now. Returns `None` if a scheduled run is already going.
 #[tauri::command]
-#[allow(non_snake_case)]
-pub async fn update_skill_group(
+pub async fn run_scheduled_sync_now(
+    app: tauri::AppHandle,
     store: State<'_, SkillStore>,
-    skillId: String,
-    groupName: Option<String>,
-) -> Result<(), String> {
+) -> Result<Option<ScheduledSyncRun>, CommandError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
-        store
-            .update_skill_group(&skillId, groupName.as_deref())
-            .map_err(format_anyhow_error)
+        crate::core::remote_schedule::run_and_emit(&app, &store, "manual")
     })
     .await
-    .map_err(|err| err.to_string())?
+    .map_err(CommandError::from)
 }
 
-// ── Skill content preview ───────────────────────────────────────────
-
 #[tauri::command]
-#[allow(non_snake_case)]
-pub async fn read_skill_content(
+pub async fn get_target_name_template(
     store: State<'_, SkillStore>,
-    skillId: String,
-) -> Result<String, String> {
+) -> Result<String, CommandError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
-        let skill = store
-            .get_skill_by_id(&skillId)?
-            .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
-        let path = std::path::PathBuf::from(&skill.central_path).join("SKILL.md");
-        if !path.exists() {
-            anyhow::bail!("SKILL.md not found");
-        }
-        let content = std::fs::read_to_string(&path).with_context(|| format!("read {:?}", path))?;
-        Ok::<_, anyhow::Error>(content)
+        Ok::<_, anyhow::Error>(get_target_name_template_core(&store))
     })
     .await
-    .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
-// ── ClawHub commands ────────────────────────────────────────────────
-
 #[tauri::command]
-pub async fn search_clawhub(
-    query: String,
-    limit: Option<u32>,
-) -> Result<Vec<clawhub_api::ClawHubSkill>, String> {
-    let limit = limit.unwrap_or(20) as usize;
-    tauri::async_runtime::spawn_blocking(move || clawhub_api::search_clawhub(&query, limit))
+pub async fn set_target_name_template(
+    store: State<'_, SkillStore>,
+    template: String,
+) -> Result<String, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || set_target_name_template_core(&store, &template))
         .await
-        .map_err(|err| err.to_string())?
-        .map_err(format_anyhow_error)
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
 }
 
-#[tauri::command]
-pub async fn get_clawhub_skill_cmd(
-    slug: String,
-) -> Result<clawhub_api::ClawHubSkillDetail, String> {
-    tauri::async_runtime::spawn_blocking(move || clawhub_api::get_clawhub_skill(&slug))
-        .await
-        .map_err(|err| err.to_string())?
-        .map_err(format_anyhow_error)
+#[derive(Debug, Serialize)]
+pub struct ReapplyNamingResultDto {
+    pub renamed: Vec<TargetRename>,
+    pub failed: Vec<String>,
 }
 
+/// Rename existing tracked targets so they match the current naming template.
 #[tauri::command]
-pub async fn get_github_tree_cmd(
-    owner: String,
-    repo: String,
-) -> Result<Vec<clawhub_api::SkillFileEntry>, String> {
-    tauri::async_runtime::spawn_blocking(move || clawhub_api::get_github_tree(&owner, &repo))
-        .await
-        .map_err(|err| err.to_string())?
-        .map_err(format_anyhow_error)
+pub async fn reapply_target_naming(
+    store: State<'_, SkillStore>,
+) -> Result<ReapplyNamingResultDto, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut renamed = Vec::new();
+        let mut failed = Vec::new();
+        let mut sessions: std::collections::HashMap<String, ssh2::Session> =
+            std::collections::HashMap::new();
+
+        // Tools sharing a skills dir track the same path; move it once, update every row.
+        let mut moved: std::collections::HashSet<String> = std::collections::HashSet::new();
+
+        for rename in plan_target_renames(&store)? {
+            let outcome = match rename.remote_host_id.as_deref() {
+                None if moved.contains(&rename.from) => record_renamed_target(&store, &rename),
+                None => apply_local_rename(&store, &rename).map(|_| {
+                    moved.insert(rename.from.clone());
+                }),
+                Some(host_id) => {
+                    if !sessions.contains_key(host_id) {
+                        let host = store
+                            .get_remote_host_by_id(host_id)?
+                            .ok_or_else(|| anyhow::anyhow!("remote host not found"))?;
+                        sessions.insert(host_id.to_string(), open_host_session(&host)?);
+                    }
+                    let sess = &sessions[host_id];
+                    remote_sync::ssh_exec(
+                        sess,
+                        &format!(
+                            "test ! -e '{}' && mv '{}' '{}'",
+                            rename.to, rename.from, rename.to
+                        ),
+                    )
+                    .and_then(|_| record_renamed_target(&store, &rename))
+                }
+            };
+            match outcome {
+                Ok(()) => renamed.push(rename),
+                Err(err) => failed.push(format!("{}: {:#}", rename.from, err)),
+            }
+        }
+
+        Ok::<_, anyhow::Error>(ReapplyNamingResultDto { renamed, failed })
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
-#[tauri::command]
-pub async fn install_clawhub_skill(
-    app: tauri::AppHandle,
+#[derive(Debug, Serialize)]
+pub struct InstallResultDto {
+    pub skill_id: String,
+    pub name: String,
+    pub central_path: String,
+    pub content_hash: Option<String>,
+    /// Problems found in the installed SKILL.md; the install still happened.
+    pub warnings: Vec<SkillIssue>,
+    /// Secrets, piped installers and suspicious instructions to review
+    /// before syncing.
+    pub content_warnings: Vec<ContentFinding>,
+    /// `verified`, `unsigned` or `untrusted` for `.skillpkg` installs.
+    pub signature_status: Option<String>,
+}
+
+fn expand_home_path(input: &str) -> Result<std::path::PathBuf, anyhow::Error> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        anyhow::bail!("storage path is empty");
+    }
+    if trimmed == "~" {
+        let home = dirs::home_dir().context("failed to resolve home directory")?;
+        return Ok(home);
+    }
+    if let Some(stripped) = trimmed.strip_prefix("~/") {
+        let home = dirs::home_dir().context("failed to resolve home directory")?;
+        return Ok(home.join(stripped));
+    }
+    Ok(std::path::PathBuf::from(trimmed))
+}
+
+#[tauri::command]
+pub async fn get_central_repo_path(
+    paths: State<'_, AppPaths>,
+    store: State<'_, SkillStore>,
+) -> Result<String, CommandError> {
+    let paths = paths.inner().clone();
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let path = resolve_central_repo_path(&paths, &store)?;
+        ensure_central_repo(&path)?;
+        Ok::<_, anyhow::Error>(path.to_string_lossy().to_string())
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+/// Check what moving the central repo to `path` involves, without moving
+/// anything.
+#[tauri::command]
+pub async fn plan_central_repo_migration(
+    paths: State<'_, AppPaths>,
+    store: State<'_, SkillStore>,
+    path: String,
+) -> Result<MigrationPlan, CommandError> {
+    let paths = paths.inner().clone();
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let new_base = expand_home_path(&path)?;
+        let current_base = resolve_central_repo_path(&paths, &store)?;
+        plan_central_migration(&store, &current_base, &new_base)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn set_central_repo_path(
+    paths: State<'_, AppPaths>,
+    store: State<'_, SkillStore>,
+    ops: State<'_, OperationRegistry>,
+    path: String,
+) -> Result<String, CommandError> {
+    let paths = paths.inner().clone();
+    let store = store.inner().clone();
+    let op = ops.start(OperationKind::Migrate, path.clone());
+    tauri::async_runtime::spawn_blocking(move || {
+        op.run(|op| {
+            ensure_central_repo_writable(&store)?;
+            let new_base = expand_home_path(&path)?;
+            if !new_base.is_absolute() {
+                anyhow::bail!("storage path must be absolute");
+            }
+
+            cloud_folders::warn_if_cloud_folder(&new_base);
+            let current_base = resolve_central_repo_path(&paths, &store)?;
+            if current_base == new_base {
+                ensure_central_repo(&new_base)?;
+                store.set_setting("central_repo_path", new_base.to_string_lossy().as_ref())?;
+                return Ok::<_, anyhow::Error>(new_base.to_string_lossy().to_string());
+            }
+
+            let plan = plan_central_migration(&store, &current_base, &new_base)?;
+            run_central_migration(&store, &plan, |done, total| op.progress(done, total))?;
+            sync_modes::load_sync_mode_prefs(&store);
+            Ok::<_, anyhow::Error>(new_base.to_string_lossy().to_string())
+        })
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+/// Whether the central repo is in an iCloud Drive, Dropbox or OneDrive
+/// folder, where linked targets break.
+#[tauri::command]
+pub async fn get_central_repo_cloud_status(
+    paths: State<'_, AppPaths>,
+    store: State<'_, SkillStore>,
+) -> Result<CloudFolderStatus, CommandError> {
+    let paths = paths.inner().clone();
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let path = resolve_central_repo_path(&paths, &store)?;
+        Ok::<_, anyhow::Error>(cloud_folders::cloud_folder_status(&store, &path))
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+/// Copy instead of link skills while the central repo is in a cloud folder.
+#[tauri::command]
+pub async fn set_cloud_force_copy(
+    store: State<'_, SkillStore>,
+    enabled: bool,
+) -> Result<bool, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        cloud_folders::set_cloud_force_copy(&store, enabled)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[derive(Debug, Serialize)]
+pub struct SharedCentralRepoDto {
+    pub shared: bool,
+    pub path: String,
+    pub skill_count: usize,
+}
+
+#[tauri::command]
+pub async fn get_shared_central_repo(
+    paths: State<'_, AppPaths>,
+    store: State<'_, SkillStore>,
+) -> Result<SharedCentralRepoDto, CommandError> {
+    let paths = paths.inner().clone();
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let shared = central_repo::is_shared_central_repo(&store);
+        let skill_count = store
+            .list_skills()?
+            .iter()
+            .filter(|s| s.source_type == SHARED_SOURCE_TYPE)
+            .count();
+        Ok::<_, anyhow::Error>(SharedCentralRepoDto {
+            shared,
+            path: resolve_central_repo_path(&paths, &store)?
+                .to_string_lossy()
+                .to_string(),
+            skill_count,
+        })
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+/// Use a read-only shared folder (e.g. a lab network mount) as the central
+/// repo. Returns the names of the skills found in it.
+#[tauri::command]
+pub async fn use_shared_central_repo(
+    store: State<'_, SkillStore>,
+    path: String,
+) -> Result<Vec<String>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let path = expand_home_path(&path)?;
+        let skills = central_repo::use_shared_central_repo(&store, &path)?;
+        Ok::<_, anyhow::Error>(skills.into_iter().map(|s| s.name).collect())
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn refresh_shared_central_repo(
+    store: State<'_, SkillStore>,
+) -> Result<Vec<String>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let skills = central_repo::refresh_shared_skills(&store)?;
+        Ok::<_, anyhow::Error>(skills.into_iter().map(|s| s.name).collect())
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn leave_shared_central_repo(store: State<'_, SkillStore>) -> Result<(), CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || central_repo::leave_shared_central_repo(&store))
+        .await
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn install_local(
+    paths: State<'_, AppPaths>,
+    store: State<'_, SkillStore>,
+    ops: State<'_, OperationRegistry>,
+    sourcePath: String,
+    name: Option<String>,
+    resourceType: Option<ResourceType>,
+) -> Result<InstallResultDto, CommandError> {
+    let paths = paths.inner().clone();
+    let store = store.inner().clone();
+    let op = ops.start(OperationKind::Install, sourcePath.clone());
+    tauri::async_runtime::spawn_blocking(move || {
+        op.run(|_| {
+            let result = install_local_resource(
+                &paths,
+                &store,
+                resourceType.unwrap_or_default(),
+                sourcePath.as_ref(),
+                name,
+            )?;
+            Ok::<_, anyhow::Error>(to_install_dto(result))
+        })
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreateSkillResultDto {
+    pub skill: InstallResultDto,
+    /// One entry per requested tool; empty when none were selected.
+    pub synced: Vec<ToolSyncResultDto>,
+}
+
+#[tauri::command]
+pub async fn list_skill_templates(
+    paths: State<'_, AppPaths>,
+    store: State<'_, SkillStore>,
+) -> Result<TemplateList, CommandError> {
+    let paths = paths.inner().clone();
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        skill_templates::list_skill_templates(&paths, &store)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+/// Author a new skill from a template, then sync it to `tools` if given.
+/// Sync failures are reported per tool and do not undo the creation.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn create_skill(
+    paths: State<'_, AppPaths>,
+    store: State<'_, SkillStore>,
+    ops: State<'_, OperationRegistry>,
+    undo: State<'_, UndoStack>,
+    name: String,
+    description: Option<String>,
+    template: Option<String>,
+    author: Option<String>,
+    tools: Option<Vec<String>>,
+) -> Result<CreateSkillResultDto, CommandError> {
+    let paths = paths.inner().clone();
+    let store = store.inner().clone();
+    let undo = undo.inner().clone();
+    let op = ops.start(OperationKind::Install, name.clone());
+    tauri::async_runtime::spawn_blocking(move || {
+        op.run(|op| {
+            let created = skill_authoring::create_skill(
+                &paths,
+                &store,
+                name.trim(),
+                description.as_deref(),
+                template.as_deref(),
+                author.as_deref(),
+            )?;
+            let tools = tools.unwrap_or_default();
+            let synced = if tools.is_empty() {
+                Vec::new()
+            } else {
+                let skill = store
+                    .get_skill_by_id(&created.skill_id)?
+                    .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+                sync_to_each_tool(&store, &undo, op, &skill, &tools, false)?
+            };
+            Ok::<_, anyhow::Error>(CreateSkillResultDto {
+                skill: to_install_dto(created),
+                synced,
+            })
+        })
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+/// Fork a managed skill into an editable authored copy named `newName`.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn duplicate_skill(
+    paths: State<'_, AppPaths>,
+    store: State<'_, SkillStore>,
+    ops: State<'_, OperationRegistry>,
+    skillId: String,
+    newName: String,
+) -> Result<InstallResultDto, CommandError> {
+    let paths = paths.inner().clone();
+    let store = store.inner().clone();
+    let op = ops.start(OperationKind::Install, newName.clone());
+    tauri::async_runtime::spawn_blocking(move || {
+        op.run(|_| {
+            let result =
+                skill_authoring::duplicate_skill(&paths, &store, &skillId, newName.trim())?;
+            Ok::<_, anyhow::Error>(to_install_dto(result))
+        })
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn list_local_skills_cmd(
+    basePath: String,
+) -> Result<Vec<LocalSkillCandidate>, CommandError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let path = std::path::PathBuf::from(basePath);
+        list_local_skills(&path)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn install_local_selection(
+    paths: State<'_, AppPaths>,
+    store: State<'_, SkillStore>,
+    ops: State<'_, OperationRegistry>,
+    basePath: String,
+    subpath: String,
+    name: Option<String>,
+) -> Result<InstallResultDto, CommandError> {
+    let paths = paths.inner().clone();
+    let store = store.inner().clone();
+    let op = ops.start(
+        OperationKind::Install,
+        format!("{} ({})", basePath, subpath),
+    );
+    tauri::async_runtime::spawn_blocking(move || {
+        op.run(|_| {
+            let base = std::path::PathBuf::from(basePath);
+            let result =
+                install_local_skill_from_selection(&paths, &store, base.as_ref(), &subpath, name)?;
+            Ok::<_, anyhow::Error>(to_install_dto(result))
+        })
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn install_git(
+    paths: State<'_, AppPaths>,
+    store: State<'_, SkillStore>,
+    ops: State<'_, OperationRegistry>,
+    repoUrl: String,
+    name: Option<String>,
+    confirmUntrusted: Option<bool>,
+) -> Result<InstallResultDto, CommandError> {
+    let paths = paths.inner().clone();
+    let store = store.inner().clone();
+    let op = ops.start(OperationKind::Install, repoUrl.clone());
+    tauri::async_runtime::spawn_blocking(move || {
+        op.run(|_| {
+            check_git_source(&store, &repoUrl, confirmUntrusted.unwrap_or(false))?;
+            let result = install_git_skill(&paths, &store, &repoUrl, name)?;
+            Ok::<_, anyhow::Error>(to_install_dto(result))
+        })
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+/// `install_git` as a background job; returns the job id.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn queue_install_git(
+    paths: State<'_, AppPaths>,
+    store: State<'_, SkillStore>,
+    jobs: State<'_, JobQueue>,
+    repoUrl: String,
+    name: Option<String>,
+    confirmUntrusted: Option<bool>,
+) -> Result<String, CommandError> {
+    let paths = paths.inner().clone();
+    let store = store.inner().clone();
+    check_git_source(&store, &repoUrl, confirmUntrusted.unwrap_or(false))?;
+    let label = repoUrl.clone();
+    Ok(jobs.enqueue(OperationKind::Install, label, move |job| {
+        job.log(format!("installing {}", repoUrl));
+        let result =
+            install_git_skill(&paths, &store, &repoUrl, name).map_err(CommandError::from)?;
+        job.log(format!("installed {}", result.name));
+        Ok::<_, CommandError>(to_install_dto(result))
+    }))
+}
+
+#[tauri::command]
+pub async fn get_source_trust_policy(
+    store: State<'_, SkillStore>,
+) -> Result<SourceTrustPolicy, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        Ok::<_, CommandError>(source_trust::get_source_trust_policy(&store))
+    })
+    .await
+    .map_err(CommandError::from)?
+}
+
+/// Replace the allowlists and denylists installs are checked against.
+#[tauri::command]
+pub async fn set_source_trust_policy(
+    store: State<'_, SkillStore>,
+    policy: SourceTrustPolicy,
+) -> Result<SourceTrustPolicy, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        source_trust::set_source_trust_policy(&store, policy)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn list_git_skills_cmd(
+    paths: State<'_, AppPaths>,
+    store: State<'_, SkillStore>,
+    repoUrl: String,
+) -> Result<Vec<GitSkillCandidate>, CommandError> {
+    let paths = paths.inner().clone();
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || list_git_skills(&paths, &store, &repoUrl))
+        .await
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn install_git_selection(
+    paths: State<'_, AppPaths>,
+    store: State<'_, SkillStore>,
+    ops: State<'_, OperationRegistry>,
+    repoUrl: String,
+    subpath: String,
+    name: Option<String>,
+    confirmUntrusted: Option<bool>,
+) -> Result<InstallResultDto, CommandError> {
+    let paths = paths.inner().clone();
+    let store = store.inner().clone();
+    let op = ops.start(OperationKind::Install, format!("{} ({})", repoUrl, subpath));
+    tauri::async_runtime::spawn_blocking(move || {
+        op.run(|_| {
+            check_git_source(&store, &repoUrl, confirmUntrusted.unwrap_or(false))?;
+            let result =
+                install_git_skill_from_selection(&paths, &store, &repoUrl, &subpath, name)?;
+            Ok::<_, anyhow::Error>(to_install_dto(result))
+        })
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SyncResultDto {
+    pub mode_used: String,
+    /// Why the skill was not simply symlinked (a junction or a copy instead).
+    pub mode_reason: Option<String>,
+    pub target_path: String,
+    /// Cross-references to other skills that could not be resolved for this tool.
+    pub warnings: Vec<String>,
+}
+
+#[tauri::command]
+pub async fn sync_skill_dir(
+    source_path: String,
+    target_path: String,
+) -> Result<SyncResultDto, CommandError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let result = sync_dir_hybrid(source_path.as_ref(), target_path.as_ref())?;
+        Ok::<_, anyhow::Error>(SyncResultDto {
+            mode_used: sync_plan::mode_name(&result.mode_used).to_string(),
+            mode_reason: result.mode_reason.clone(),
+            target_path: result.target_path.to_string_lossy().to_string(),
+            warnings: Vec::new(),
+        })
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case, clippy::too_many_arguments)]
+pub async fn sync_skill_to_tool(
+    store: State<'_, SkillStore>,
+    ops: State<'_, OperationRegistry>,
+    undo: State<'_, UndoStack>,
+    sourcePath: String,
+    skillId: String,
+    tool: String,
+    name: String,
+    overwrite: Option<bool>,
+    mode: Option<SyncModePreference>,
+) -> Result<SyncResultDto, CommandError> {
+    let store = store.inner().clone();
+    let undo = undo.inner().clone();
+    let op = ops.start(OperationKind::Sync, format!("{} -> {}", name, tool));
+    tauri::async_runtime::spawn_blocking(move || {
+        op.run(|_| {
+            sync_skill_to_local_tool(
+                &store,
+                &undo,
+                sourcePath.as_ref(),
+                &skillId,
+                &tool,
+                name,
+                overwrite.unwrap_or(false),
+                mode,
+            )
+        })
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[derive(Debug, Serialize)]
+pub struct ToolSyncResultDto {
+    pub tool: String,
+    pub result: Option<SyncResultDto>,
+    /// Why this tool failed, as `sync_skill_to_tool` would report it.
+    pub error: Option<CommandError>,
+}
+
+/// Sync one skill to each of `tools` as a single operation; see
+/// [`sync_to_each_tool`].
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn sync_skill_to_tools(
+    store: State<'_, SkillStore>,
+    ops: State<'_, OperationRegistry>,
+    undo: State<'_, UndoStack>,
+    skillId: String,
+    tools: Vec<String>,
+    overwrite: Option<bool>,
+) -> Result<Vec<ToolSyncResultDto>, CommandError> {
+    let store = store.inner().clone();
+    let undo = undo.inner().clone();
+    let op = ops.start(
+        OperationKind::Sync,
+        format!("{} -> {} tools", skillId, tools.len()),
+    );
+    tauri::async_runtime::spawn_blocking(move || {
+        op.run(|op| {
+            let skill = store
+                .get_skill_by_id(&skillId)?
+                .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+            sync_to_each_tool(
+                &store,
+                &undo,
+                op,
+                &skill,
+                &tools,
+                overwrite.unwrap_or(false),
+            )
+        })
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+/// Sync `skill` to each of `tools`, reporting progress on `op`. A tool that
+/// fails does not stop the others; tools sharing a skills directory are
+/// synced once. Results keep the order of `tools`.
+fn sync_to_each_tool(
+    store: &SkillStore,
+    undo: &UndoStack,
+    op: &OperationHandle,
+    skill: &SkillRecord,
+    tools: &[String],
+    overwrite: bool,
+) -> anyhow::Result<Vec<ToolSyncResultDto>> {
+    let source = std::path::PathBuf::from(&skill.central_path);
+    if !source.exists() {
+        anyhow::bail!("central copy is missing: {}", skill.central_path);
+    }
+    let is_skill =
+        ResourceType::parse(&store.get_skill_resource_type(&skill.id)?) == ResourceType::Skill;
+
+    let mut synced: HashMap<String, SyncResultDto> = HashMap::new();
+    let mut results = Vec::with_capacity(tools.len());
+    for (done, tool) in tools.iter().enumerate() {
+        op.progress(done, tools.len());
+        if let Some(result) = synced.get(tool) {
+            results.push(ToolSyncResultDto {
+                tool: tool.clone(),
+                result: Some(result.clone()),
+                error: None,
+            });
+            continue;
+        }
+        let outcome = sync_skill_to_local_tool(
+            store,
+            undo,
+            &source,
+            &skill.id,
+            tool,
+            skill.name.clone(),
+            overwrite,
+            None,
+        );
+        match outcome {
+            Ok(result) => {
+                if is_skill {
+                    if let Some(adapter) = adapter_by_key(tool) {
+                        for key in installed_group_tool_keys(&adapter)? {
+                            synced.insert(key, result.clone());
+                        }
+                    }
+                }
+                results.push(ToolSyncResultDto {
+                    tool: tool.clone(),
+                    result: Some(result),
+                    error: None,
+                });
+            }
+            Err(err) => results.push(ToolSyncResultDto {
+                tool: tool.clone(),
+                result: None,
+                error: Some(CommandError::from(err)),
+            }),
+        }
+    }
+    Ok(results)
+}
+
+#[derive(Debug, Serialize)]
+pub struct SkillSyncIssueDto {
+    pub skill_id: String,
+    pub name: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdoptToolResultDto {
+    pub tool: String,
+    /// Ids of the skills synced by this call.
+    pub synced: Vec<String>,
+    pub already_synced: usize,
+    /// Skills left alone because something else already uses their name in
+    /// the tool's directory, or the tool has no place for their kind.
+    pub skipped: Vec<SkillSyncIssueDto>,
+    pub failed: Vec<SkillSyncIssueDto>,
+}
+
+/// Sync every managed skill that is not synced to `toolKey` yet into that
+/// tool, e.g. one reported in `newly_installed`. Existing content at a
+/// target is never replaced; those skills are reported as skipped.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn sync_all_skills_to_tool(
+    store: State<'_, SkillStore>,
+    ops: State<'_, OperationRegistry>,
+    undo: State<'_, UndoStack>,
+    toolKey: String,
+) -> Result<AdoptToolResultDto, CommandError> {
+    let store = store.inner().clone();
+    let undo = undo.inner().clone();
+    let op = ops.start(OperationKind::Sync, format!("all skills -> {}", toolKey));
+    tauri::async_runtime::spawn_blocking(move || {
+        op.run(|op| {
+            let adapter =
+                adapter_by_key(&toolKey).ok_or_else(|| anyhow::anyhow!("unknown tool"))?;
+            if !is_tool_installed(&adapter)? {
+                return Err(tool_not_installed(adapter.id.as_key()));
+            }
+            let mut report = AdoptToolResultDto {
+                tool: toolKey.clone(),
+                synced: Vec::new(),
+                already_synced: 0,
+                skipped: Vec::new(),
+                failed: Vec::new(),
+            };
+            let skills = store.list_skills()?;
+            for (done, skill) in skills.iter().enumerate() {
+                op.progress(done, skills.len());
+                if store.get_skill_target(&skill.id, &toolKey)?.is_some() {
+                    report.already_synced += 1;
+                    continue;
+                }
+                let issue = |message: String| SkillSyncIssueDto {
+                    skill_id: skill.id.clone(),
+                    name: skill.name.clone(),
+                    message,
+                };
+                let source = std::path::PathBuf::from(&skill.central_path);
+                if !source.exists() {
+                    report
+                        .failed
+                        .push(issue("central copy is missing".to_string()));
+                    continue;
+                }
+                match sync_skill_to_local_tool(
+                    &store,
+                    &undo,
+                    &source,
+                    &skill.id,
+                    &toolKey,
+                    skill.name.clone(),
+                    false,
+                    None,
+                ) {
+                    Ok(_) => report.synced.push(skill.id.clone()),
+                    Err(err) => {
+                        let error = CommandError::from(err);
+                        if matches!(
+                            error.code,
+                            ErrorCode::TargetExists
+                                | ErrorCode::ResourceUnsupported
+                                | ErrorCode::SkillQuarantined
+                        ) {
+                            report.skipped.push(issue(error.message));
+                        } else {
+                            report.failed.push(issue(error.message));
+                        }
+                    }
+                }
+            }
+            Ok::<_, anyhow::Error>(report)
+        })
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+/// Sync `source_path` to the skills (or agents, commands) directory of the
+/// installed tool `tool`, recording the target and an undo entry.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn sync_skill_to_local_tool(
+    store: &SkillStore,
+    undo: &UndoStack,
+    source_path: &std::path::Path,
+    skill_id: &str,
+    tool: &str,
+    name: String,
+    overwrite: bool,
+    mode: Option<SyncModePreference>,
+) -> anyhow::Result<SyncResultDto> {
+    let adapter = adapter_by_key(tool).ok_or_else(|| anyhow::anyhow!("unknown tool"))?;
+    if !is_tool_installed(&adapter)? {
+        return Err(tool_not_installed(adapter.id.as_key()));
+    }
+    let skill = store.get_skill_by_id(skill_id)?;
+    if let Some(skill) = &skill {
+        quarantine::ensure_approved(skill)?;
+    }
+    let kind = ResourceType::parse(&store.get_skill_resource_type(skill_id)?);
+    if kind != ResourceType::Skill {
+        return sync_resource_to_tool(store, undo, skill_id, &adapter, kind, overwrite);
+    }
+    let tool_root = resolve_default_path(&adapter)?;
+    let name = match &skill {
+        Some(skill) => target_name_for(store, skill),
+        None => name,
+    };
+    let content_hash = skill.and_then(|s| s.content_hash);
+    let target = tool_root.join(&name);
+
+    let group_tool_keys = installed_group_tool_keys(&adapter)?;
+    let previous_rows = existing_target_rows(store, skill_id, &group_tool_keys)?;
+
+    let mut warnings = Vec::new();
+    let (result, held) = sync_with_undo(store, undo, source_path, &target, overwrite, || {
+        let (outcome, unresolved) =
+            sync_with_cross_refs_in_mode(store, tool, source_path, &target, overwrite, mode)?;
+        warnings = unresolved;
+        Ok(outcome)
+    })?;
+
+    record_local_sync(
+        store,
+        undo,
+        skill_id,
+        group_tool_keys,
+        &result,
+        held,
+        previous_rows,
+        content_hash,
+    )?;
+
+    Ok(SyncResultDto {
+        mode_used: sync_plan::mode_name(&result.mode_used).to_string(),
+        mode_reason: result.mode_reason.clone(),
+        target_path: result.target_path.to_string_lossy().to_string(),
+        warnings,
+    })
+}
+
+/// Work out what syncing a skill to `tool` would do (link or copy, files
+/// added, overwritten and deleted) without touching the target.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn plan_skill_sync(
+    store: State<'_, SkillStore>,
+    skillId: String,
+    tool: String,
+) -> Result<SyncPlan, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let (skill, _, target) = local_sync_target(&store, &skillId, &tool)?;
+        sync_plan::plan_sync(
+            &store,
+            &tool,
+            std::path::Path::new(&skill.central_path),
+            &target,
+        )
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+/// Carry out a plan from `plan_skill_sync`. Fails with `SYNC_PLAN_STALE`
+/// if the target changed in the meantime; what it replaces can be undone.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn apply_skill_sync_plan(
+    store: State<'_, SkillStore>,
+    undo: State<'_, UndoStack>,
+    skillId: String,
+    plan: SyncPlan,
+) -> Result<SyncResultDto, CommandError> {
+    let store = store.inner().clone();
+    let undo = undo.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let (skill, adapter, target) = local_sync_target(&store, &skillId, &plan.tool)?;
+        if std::path::Path::new(&plan.target) != target
+            || std::path::Path::new(&plan.source) != std::path::Path::new(&skill.central_path)
+        {
+            return Err(coded(
+                ErrorCode::SyncPlanStale,
+                "the skill's target or source moved; plan again",
+            ));
+        }
+        sync_plan::ensure_plan_current(&plan)?;
+        let group_tool_keys = installed_group_tool_keys(&adapter)?;
+        let previous_rows = existing_target_rows(&store, &skillId, &group_tool_keys)?;
+        let replace = plan.action == SyncAction::Replace;
+        let source = std::path::Path::new(&skill.central_path);
+        let (result, held) = sync_with_undo(&store, &undo, source, &target, replace, || {
+            sync_plan::execute_sync_plan(&store, &plan)
+        })?;
+        let mode_used = sync_plan::mode_name(&result.mode_used).to_string();
+        record_local_sync(
+            &store,
+            &undo,
+            &skillId,
+            group_tool_keys,
+            &result,
+            held,
+            previous_rows,
+            skill.content_hash.clone(),
+        )?;
+        Ok::<_, anyhow::Error>(SyncResultDto {
+            mode_used,
+            mode_reason: result.mode_reason.clone(),
+            target_path: result.target_path.to_string_lossy().to_string(),
+            warnings: Vec::new(),
+        })
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+/// The skill, the tool's adapter and where the skill lands in the tool's
+/// global skills directory.
+fn local_sync_target(
+    store: &SkillStore,
+    skill_id: &str,
+    tool: &str,
+) -> anyhow::Result<(
+    SkillRecord,
+    crate::core::tool_adapters::ToolAdapter,
+    std::path::PathBuf,
+)> {
+    let adapter = adapter_by_key(tool).ok_or_else(|| anyhow::anyhow!("unknown tool"))?;
+    if !is_tool_installed(&adapter)? {
+        return Err(tool_not_installed(adapter.id.as_key()));
+    }
+    let kind = ResourceType::parse(&store.get_skill_resource_type(skill_id)?);
+    if kind != ResourceType::Skill {
+        return Err(resource_unsupported(tool, kind.as_str()));
+    }
+    let skill = store
+        .get_skill_by_id(skill_id)?
+        .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+    quarantine::ensure_approved(&skill)?;
+    let target = resolve_default_path(&adapter)?.join(target_name_for(store, &skill));
+    Ok((skill, adapter, target))
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn unsync_skill_from_tool(
+    store: State<'_, SkillStore>,
+    undo: State<'_, UndoStack>,
+    skillId: String,
+    tool: String,
+) -> Result<(), CommandError> {
+    let store = store.inner().clone();
+    let undo = undo.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        // Some tools share the same global skills directory; unsync should update all of them.
+        let group_tool_keys: Vec<String> = if let Some(adapter) = adapter_by_key(&tool) {
+            let group = crate::core::tool_adapters::adapters_sharing_skills_dir(&adapter);
+            // If none of the group tools are installed, do nothing (treat as already not effective).
+            let mut any_installed = false;
+            for a in &group {
+                if is_tool_installed(a)? {
+                    any_installed = true;
+                    break;
+                }
+            }
+            if !any_installed {
+                return Ok::<_, anyhow::Error>(());
+            }
+            group
+                .into_iter()
+                .map(|a| a.id.as_key().to_string())
+                .collect()
+        } else {
+            vec![tool.clone()]
+        };
+
+        // Remove filesystem target once (shared dir => shared target path).
+        // The removed content is held so the unsync can be undone.
+        let previous_rows = existing_target_rows(&store, &skillId, &group_tool_keys)?;
+        let Some(first) = previous_rows.first() else {
+            return Ok(());
+        };
+        let target_path = std::path::PathBuf::from(&first.target_path);
+        let held = undo.stash(&target_path)?;
+        for row in &previous_rows {
+            store.delete_skill_target(&skillId, &row.tool)?;
+        }
+        let tools = previous_rows.iter().map(|r| r.tool.clone()).collect();
+        undo.push(
+            UndoKind::Unsync,
+            &skillId,
+            tools,
+            &target_path,
+            held,
+            previous_rows,
+        );
+
+        Ok::<_, anyhow::Error>(())
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn sync_skill_to_project(
+    store: State<'_, SkillStore>,
+    ops: State<'_, OperationRegistry>,
+    skillId: String,
+    projectPath: String,
+    tool: String,
+    overwrite: Option<bool>,
+) -> Result<ProjectSyncResult, CommandError> {
+    let store = store.inner().clone();
+    let op = ops.start(
+        OperationKind::Sync,
+        format!("{} -> {} ({})", skillId, projectPath, tool),
+    );
+    tauri::async_runtime::spawn_blocking(move || {
+        op.run(|_| {
+            project_targets::sync_skill_to_project(
+                &store,
+                &skillId,
+                &projectPath,
+                &tool,
+                overwrite.unwrap_or(false),
+            )
+        })
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn unsync_skill_from_project(
+    store: State<'_, SkillStore>,
+    targetId: String,
+) -> Result<(), CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        project_targets::unsync_project_target(&store, &targetId)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn list_project_targets(
+    store: State<'_, SkillStore>,
+    skillId: Option<String>,
+) -> Result<Vec<ProjectTargetStatus>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        project_targets::list_project_targets(&store, skillId.as_deref())
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+/// Forget project targets whose project directory was deleted.
+#[tauri::command]
+pub async fn cleanup_project_targets(store: State<'_, SkillStore>) -> Result<usize, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || project_targets::cleanup_missing_projects(&store))
+        .await
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub fn get_last_undoable_operation(undo: State<'_, UndoStack>) -> Option<UndoSummary> {
+    undo.last()
+}
+
+/// Revert the most recent local sync or unsync (within `UNDO_TTL`).
+#[tauri::command]
+pub async fn undo_last_operation(
+    store: State<'_, SkillStore>,
+    undo: State<'_, UndoStack>,
+) -> Result<Option<UndoSummary>, CommandError> {
+    let store = store.inner().clone();
+    let undo = undo.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || undo.undo_last(&store))
+        .await
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
+}
+
+/// Installs, syncs, updates and remote pushes, newest first. Live changes
+/// arrive as `operations://updated` events.
+#[tauri::command]
+pub async fn list_operations(
+    ops: State<'_, OperationRegistry>,
+) -> Result<Vec<Operation>, CommandError> {
+    Ok(ops.list())
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn get_operation(
+    ops: State<'_, OperationRegistry>,
+    operationId: String,
+) -> Result<Option<Operation>, CommandError> {
+    Ok(ops.get(&operationId))
+}
+
+/// Background jobs with their logs and results, newest first. They outlive
+/// the window, so a reloaded UI picks them up here.
+#[tauri::command]
+pub async fn list_jobs(jobs: State<'_, JobQueue>) -> Result<Vec<Job>, CommandError> {
+    Ok(jobs.list())
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn get_job(
+    jobs: State<'_, JobQueue>,
+    jobId: String,
+) -> Result<Option<Job>, CommandError> {
+    Ok(jobs.get(&jobId))
+}
+
+#[derive(Debug, Serialize)]
+pub struct UpdateResultDto {
+    pub skill_id: String,
+    pub name: String,
+    pub content_hash: Option<String>,
+    pub source_revision: Option<String>,
+    pub updated_targets: Vec<String>,
+    pub remotes: RemotePropagationReport,
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn update_managed_skill(
+    paths: State<'_, AppPaths>,
+    store: State<'_, SkillStore>,
+    ops: State<'_, OperationRegistry>,
+    skillId: String,
+    force: Option<bool>,
+) -> Result<UpdateResultDto, CommandError> {
+    let paths = paths.inner().clone();
+    let store = store.inner().clone();
+    let op = ops.start(OperationKind::Update, skillId.clone());
+    let force = force.unwrap_or(false);
+    tauri::async_runtime::spawn_blocking(move || {
+        op.run(|_| update_skill_and_remotes(&paths, &store, &skillId, force))
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+fn update_skill_and_remotes(
+    paths: &AppPaths,
+    store: &SkillStore,
+    skill_id: &str,
+    force: bool,
+) -> anyhow::Result<UpdateResultDto> {
+    let res = update_managed_skill_from_source(paths, store, skill_id, force)?;
+    // Remote pushes are best effort; the local update already succeeded.
+    let remotes =
+        propagate_skill_update(store, skill_id).unwrap_or_else(|err| RemotePropagationReport {
+            errors: vec![format!("{:#}", err)],
+            ..Default::default()
+        });
+    Ok(UpdateResultDto {
+        skill_id: res.skill_id,
+        name: res.name,
+        content_hash: res.content_hash,
+        source_revision: res.source_revision,
+        updated_targets: res.updated_targets,
+        remotes,
+    })
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct UpdateSkillsJobResult {
+    pub updated: Vec<UpdateResultDto>,
+    pub failed: Vec<SkillUpdateFailure>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SkillUpdateFailure {
+    pub skill_id: String,
+    pub error: CommandError,
+}
+
+/// Update several skills in one background job; without `skillIds`, every
+/// unlocked skill `check_skill_updates` reports as outdated. One failure
+/// does not stop the rest. Returns the job id.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn queue_update_skills(
+    paths: State<'_, AppPaths>,
+    store: State<'_, SkillStore>,
+    jobs: State<'_, JobQueue>,
+    skillIds: Option<Vec<String>>,
+) -> Result<String, CommandError> {
+    let paths = paths.inner().clone();
+    let store = store.inner().clone();
+    let label = match &skillIds {
+        Some(ids) => format!("update {} skills", ids.len()),
+        None => "update all skills".to_string(),
+    };
+    Ok(jobs.enqueue(OperationKind::Update, label, move |job| {
+        let skill_ids = match skillIds {
+            Some(ids) => ids,
+            None => check_skill_updates_core(&store)
+                .into_iter()
+                .filter(|status| status.has_update)
+                .filter(|status| !skill_lock::is_skill_locked(&store, &status.skill_id))
+                .map(|status| status.skill_id)
+                .collect(),
+        };
+        let mut report = UpdateSkillsJobResult::default();
+        for (index, skill_id) in skill_ids.iter().enumerate() {
+            job.progress(index, skill_ids.len());
+            match update_skill_and_remotes(&paths, &store, skill_id, false) {
+                Ok(updated) => {
+                    job.log(format!("updated {}", updated.name));
+                    report.updated.push(updated);
+                }
+                Err(err) => {
+                    let error = CommandError::from(err);
+                    job.log(format!("{}: {}", skill_id, error));
+                    report.failed.push(SkillUpdateFailure {
+                        skill_id: skill_id.clone(),
+                        error,
+                    });
+                }
+            }
+        }
+        Ok::<_, CommandError>(report)
+    }))
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn reinstall_skill(
+    paths: State<'_, AppPaths>,
+    store: State<'_, SkillStore>,
+    ops: State<'_, OperationRegistry>,
+    skillId: String,
+) -> Result<ReinstallResult, CommandError> {
+    let paths = paths.inner().clone();
+    let store = store.inner().clone();
+    let op = ops.start(OperationKind::Update, format!("reinstall {}", skillId));
+    tauri::async_runtime::spawn_blocking(move || {
+        op.run(|_| reinstall_skill_from_source(&paths, &store, &skillId))
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn check_skill_updates(
+    store: State<'_, SkillStore>,
+) -> Result<Vec<SkillUpdateStatus>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        Ok::<_, CommandError>(check_skill_updates_core(&store))
+    })
+    .await
+    .map_err(CommandError::from)?
+}
+
+/// Last known upstream activity of git-sourced skills (freshness badges).
+#[tauri::command]
+pub async fn list_skill_freshness(
+    store: State<'_, SkillStore>,
+) -> Result<Vec<SkillFreshness>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || skill_freshness::list_skill_freshness(&store))
+        .await
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn refresh_skill_freshness(
+    store: State<'_, SkillStore>,
+    force: Option<bool>,
+) -> Result<Vec<SkillFreshness>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        skill_freshness::refresh_skill_freshness(&store, force.unwrap_or(false))
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn search_github(
+    query: String,
+    limit: Option<u32>,
+    filters: Option<RepoSearchFilters>,
+    provider: Option<String>,
+) -> Result<Vec<RepoSummary>, CommandError> {
+    let limit = limit.unwrap_or(10) as usize;
+    let filters = filters.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || {
+        let provider = resolve_provider(provider.as_deref())?;
+        search_github_repos(&provider, &query, limit, &filters)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[allow(non_snake_case)]
+#[tauri::command]
+pub async fn list_github_repos(
+    ownerOrOrg: String,
+    limit: Option<u32>,
+    provider: Option<String>,
+) -> Result<Vec<RepoSummary>, CommandError> {
+    let limit = limit.unwrap_or(100) as usize;
+    tauri::async_runtime::spawn_blocking(move || {
+        let provider = resolve_provider(provider.as_deref())?;
+        github_search::list_github_repos(&provider, &ownerOrOrg, limit)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn list_source_providers(
+    store: State<'_, SkillStore>,
+) -> Result<Vec<SourceProvider>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || source_providers::list_source_providers(&store))
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn save_source_provider(
+    store: State<'_, SkillStore>,
+    provider: SourceProvider,
+) -> Result<SourceProvider, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        source_providers::save_source_provider(&store, provider)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn delete_source_provider(
+    store: State<'_, SkillStore>,
+    id: String,
+) -> Result<bool, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        source_providers::delete_source_provider(&store, &id)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn has_github_token() -> Result<bool, CommandError> {
+    Ok(github_search::has_github_token())
+}
+
+/// Returns whether a token is set afterwards; an empty token clears it.
+#[tauri::command]
+pub async fn set_github_token(
+    store: State<'_, SkillStore>,
+    token: Option<String>,
+) -> Result<bool, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || github_search::set_github_token(&store, token))
+        .await
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn get_github_account(
+    store: State<'_, SkillStore>,
+) -> Result<Option<String>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        github_stars::get_github_account(&store).map_err(CommandError::from)
+    })
+    .await
+    .map_err(CommandError::from)?
+}
+
+/// Link (or with `None`, unlink) the GitHub account whose stars feed Explore.
+#[tauri::command]
+pub async fn set_github_account(
+    store: State<'_, SkillStore>,
+    account: Option<String>,
+) -> Result<(), CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        github_stars::set_github_account(&store, account.as_deref()).map_err(CommandError::from)
+    })
+    .await
+    .map_err(CommandError::from)?
+}
+
+#[tauri::command]
+pub async fn list_github_star_suggestions(
+    store: State<'_, SkillStore>,
+) -> Result<Vec<StarSuggestion>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || github_stars::list_star_suggestions(&store))
+        .await
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn import_existing_skill(
+    paths: State<'_, AppPaths>,
+    store: State<'_, SkillStore>,
+    sourcePath: String,
+    name: Option<String>,
+) -> Result<InstallResultDto, CommandError> {
+    let paths = paths.inner().clone();
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let result = install_local_skill(&paths, &store, sourcePath.as_ref(), name)?;
+        Ok::<_, anyhow::Error>(to_install_dto(result))
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+/// Skill folders in installed tools that Skills Hub does not manage yet,
+/// grouped by content.
+#[tauri::command]
+pub async fn scan_existing_skills(
+    store: State<'_, SkillStore>,
+) -> Result<ExistingSkillScan, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || existing_skills::scan_existing_skills(&store))
+        .await
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportExistingResultDto {
+    pub imported: Vec<InstallResultDto>,
+    /// Found copies replaced by targets synced from the central repo.
+    pub linked: usize,
+    pub failed: Vec<String>,
+}
+
+/// Import the groups from `scan_existing_skills` whose content hash is in
+/// `contentHashes` into the central repo (or reuse the managed skill with
+/// that content), then replace every found copy with a normal target. The
+/// replaced folders are kept with the other overwritten targets.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn import_existing_skills(
+    paths: State<'_, AppPaths>,
+    store: State<'_, SkillStore>,
+    ops: State<'_, OperationRegistry>,
+    undo: State<'_, UndoStack>,
+    contentHashes: Vec<String>,
+) -> Result<ImportExistingResultDto, CommandError> {
+    let paths = paths.inner().clone();
+    let store = store.inner().clone();
+    let undo = undo.inner().clone();
+    let op = ops.start(
+        OperationKind::Install,
+        format!("{} existing skills", contentHashes.len()),
+    );
+    tauri::async_runtime::spawn_blocking(move || {
+        op.run(|op| {
+            let wanted: std::collections::HashSet<String> = contentHashes.into_iter().collect();
+            let groups: Vec<_> = existing_skills::scan_existing_skills(&store)?
+                .skills
+                .into_iter()
+                .filter(|group| wanted.contains(&group.content_hash))
+                .collect();
+            let mut result = ImportExistingResultDto {
+                imported: Vec::new(),
+                linked: 0,
+                failed: Vec::new(),
+            };
+
+            for (done, group) in groups.iter().enumerate() {
+                op.progress(done, groups.len());
+                let skill = match &group.managed_skill_id {
+                    Some(id) => store.get_skill_by_id(id)?,
+                    None => match install_local_skill(
+                        &paths,
+                        &store,
+                        &group.copies[0].path,
+                        Some(group.name.clone()),
+                    ) {
+                        Ok(installed) => {
+                            let id = installed.skill_id.clone();
+                            result.imported.push(to_install_dto(installed));
+                            store.get_skill_by_id(&id)?
+                        }
+                        Err(err) => {
+                            result.failed.push(format!("{}: {:#}", group.name, err));
+                            continue;
+                        }
+                    },
+                };
+                let Some(skill) = skill else {
+                    result
+                        .failed
+                        .push(format!("{}: skill not found", group.name));
+                    continue;
+                };
+                for copy in &group.copies {
+                    match replace_with_target(&store, &undo, &skill, &copy.tool, &copy.path) {
+                        Ok(()) => result.linked += 1,
+                        Err(err) => result
+                            .failed
+                            .push(format!("{} ({}): {:#}", group.name, copy.tool, err)),
+                    }
+                }
+            }
+            Ok::<_, anyhow::Error>(result)
+        })
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+/// Sync `skill` over the folder at `path` in `tool`'s skills directory and
+/// record it as the tool's target.
+fn replace_with_target(
+    store: &SkillStore,
+    undo: &UndoStack,
+    skill: &SkillRecord,
+    tool: &str,
+    path: &std::path::Path,
+) -> anyhow::Result<()> {
+    let adapter = adapter_by_key(tool).ok_or_else(|| anyhow::anyhow!("unknown tool"))?;
+    let group_tool_keys = installed_group_tool_keys(&adapter)?;
+    let previous_rows = existing_target_rows(store, &skill.id, &group_tool_keys)?;
+    let central_path = std::path::Path::new(&skill.central_path);
+    let (result, held) = sync_with_undo(store, undo, central_path, path, true, || {
+        sync_with_cross_refs(store, tool, central_path, path, true).map(|(outcome, _)| outcome)
+    })?;
+    record_local_sync(
+        store,
+        undo,
+        &skill.id,
+        group_tool_keys,
+        &result,
+        held,
+        previous_rows,
+        skill.content_hash.clone(),
+    )
+}
+
+// ── Symlink farm migration ──────────────────────────────────────────
+
+#[tauri::command]
+pub async fn detect_symlink_farms(
+    paths: State<'_, AppPaths>,
+    store: State<'_, SkillStore>,
+) -> Result<Vec<SymlinkFarm>, CommandError> {
+    let paths = paths.inner().clone();
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || detect_symlink_farms_core(&paths, &store))
+        .await
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdoptFarmResultDto {
+    pub adopted: Vec<InstallResultDto>,
+    pub failed: Vec<String>,
+}
+
+/// Adopt the detected symlink skills whose source directory is in `sourceDirs`.
+/// Files stay where they are unless `copyIntoCentral` is set.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn adopt_symlink_farm_skills(
+    paths: State<'_, AppPaths>,
+    store: State<'_, SkillStore>,
+    sourceDirs: Vec<String>,
+    copyIntoCentral: Option<bool>,
+) -> Result<AdoptFarmResultDto, CommandError> {
+    let paths = paths.inner().clone();
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let wanted: std::collections::HashSet<std::path::PathBuf> =
+            sourceDirs.iter().map(std::path::PathBuf::from).collect();
+        let copy = copyIntoCentral.unwrap_or(false);
+        let mut adopted = Vec::new();
+        let mut failed = Vec::new();
+
+        for farm in detect_symlink_farms_core(&paths, &store)? {
+            for skill in farm.skills {
+                if !wanted.contains(&skill.source_dir) {
+                    continue;
+                }
+                match adopt_farm_skill(&paths, &store, &skill, copy) {
+                    Ok(result) => adopted.push(to_install_dto(result)),
+                    Err(err) => failed.push(format!("{}: {:#}", skill.name, err)),
+                }
+            }
+        }
+
+        Ok::<_, anyhow::Error>(AdoptFarmResultDto { adopted, failed })
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[derive(Debug, Serialize)]
+pub struct ManagedSkillDto {
+    pub id: String,
+    pub name: String,
+    pub source_type: String,
+    pub source_ref: Option<String>,
+    pub central_path: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub last_sync_at: Option<i64>,
+    pub status: String,
+    pub targets: Vec<SkillTargetDto>,
+    /// Tool links on remote hosts (`remote:<hostId>:<tool>` targets).
+    pub remote_targets: Vec<RemoteTargetDto>,
+    pub group_name: Option<String>,
+    pub propagate_to_remotes: bool,
+    pub remote_deployments: Vec<RemoteDeploymentDto>,
+    pub resource_type: ResourceType,
+    /// From the SKILL.md frontmatter; empty for agents and commands.
+    pub metadata: SkillMetadata,
+    pub locked: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SkillTargetDto {
+    pub tool: String,
+    pub mode: String,
+    pub status: String,
+    pub target_path: String,
+    pub synced_at: Option<i64>,
+    pub is_stale: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RemoteTargetDto {
+    pub host_id: String,
+    pub tool: String,
+    pub target_path: String,
+    pub synced_at: Option<i64>,
+    pub is_stale: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RemoteDeploymentDto {
+    pub host_id: String,
+    pub tool_keys: Vec<String>,
+    pub synced_at: i64,
+    pub is_stale: bool,
+}
+
+#[tauri::command]
+pub fn get_managed_skills(
+    store: State<'_, SkillStore>,
+) -> Result<Vec<ManagedSkillDto>, CommandError> {
+    get_managed_skills_impl(store.inner())
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn delete_managed_skill(
+    store: State<'_, SkillStore>,
+    skillId: String,
+) -> Result<(), CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        // 便于排查“按钮点了没反应”：确认前端确实触发了命令
+        log::info!("delete_managed_skill: skillId={}", skillId);
+
+        if store
+            .get_skill_by_id(&skillId)?
+            .is_some_and(|s| s.source_type == SHARED_SOURCE_TYPE)
+        {
+            ensure_central_repo_writable(&store)?;
+        }
+
+        // 先删除已同步到各工具目录的副本/软链接
+        // 注意：如果先删 skills 行，会触发 skill_targets cascade，导致无法再拿到 target_path
+        let targets = store.list_skill_targets(&skillId)?;
+
+        let mut remove_failures: Vec<String> = Vec::new();
+        // Remote tool links are cleaned up per host below.
+        let project_paths = store
+            .list_project_targets_for_skill(&skillId)?
+            .into_iter()
+            .map(|t| t.target_path);
+        for target_path in targets
+            .into_iter()
+            .filter(|t| parse_remote_target_key(&t.tool).is_none())
+            .map(|t| t.target_path)
+            .chain(project_paths)
+        {
+            if let Err(err) = remove_path_any(&target_path) {
+                remove_failures.push(format!("{}: {}", target_path, err));
+            }
+        }
+
+        let record = store.get_skill_by_id(&skillId)?;
+        if let Some(ref skill) = record {
+            // ── 清理所有 remote hosts 上的技能 ──
+            if let Ok(hosts) = store.list_remote_hosts() {
+                log::debug!(
+                    "delete_managed_skill: found {} remote hosts to clean",
+                    hosts.len()
+                );
+                for host in hosts {
+                    match open_host_session(&host) {
+                        Ok(sess) => {
+                            if let Err(err) = remote_sync::remove_skill_from_remote(
+                                &sess,
+                                &skill.name,
+                                &target_name_for(&store, skill),
+                            ) {
+                                remove_failures.push(format!("remote({}): {}", host.label, err));
+                            }
+                        }
+                        Err(err) => {
+                            remove_failures.push(format!(
+                                "remote({}) {}: {}",
+                                host.label,
+                                tr(Msg::RemoteSshFailed),
+                                err
+                            ));
+                        }
+                    }
+                }
+            }
+
+            // Adopted in-place skills point at the user's own folder; keep it.
+            let path = std::path::PathBuf::from(&skill.central_path);
+            if skill.source_type != LINKED_SOURCE_TYPE && path.exists() {
+                std::fs::remove_dir_all(&path)?;
+                central_history::record_change(&store, &path, &format!("Delete {}", skill.name));
+            }
+            store.delete_skill(&skillId)?;
+            skill_events::skill_deleted(&skill.id, &skill.name);
+        }
+
+        if !remove_failures.is_empty() {
+            anyhow::bail!(
+                "{}\n- {}",
+                tr(Msg::DeleteCleanupFailed),
+                remove_failures.join("\n- ")
+            );
+        }
+
+        Ok::<_, anyhow::Error>(())
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+/// Root directory and `skill_targets` tool key for a custom target. Templated
+/// local paths are expanded with `variables` and tracked per expanded root.
+fn resolve_custom_target(
+    ct: &CustomTargetRecord,
+    variables: Option<&HashMap<String, String>>,
+) -> anyhow::Result<(String, String)> {
+    if path_variables(&ct.path).is_empty() {
+        return Ok((ct.path.clone(), custom_target_key(&ct.id, None)));
+    }
+    let empty = HashMap::new();
+    let expanded = expand_target_path(&ct.path, variables.unwrap_or(&empty))?;
+    let root = expand_home_path(&expanded)?;
+    if !root.is_absolute() {
+        anyhow::bail!("custom target path must be absolute: {}", expanded);
+    }
+    let root = root.to_string_lossy().to_string();
+    let key = custom_target_key(&ct.id, Some(&root));
+    Ok((root, key))
+}
+
+/// Existing target rows for `tools`, captured before an undoable operation.
+/// Sync an agent or command into the tool's directory for its type. Unlike
+/// skills, these never share a directory between tools.
+fn sync_resource_to_tool(
+    store: &SkillStore,
+    undo: &UndoStack,
+    skill_id: &str,
+    adapter: &crate::core::tool_adapters::ToolAdapter,
+    kind: ResourceType,
+    overwrite: bool,
+) -> anyhow::Result<SyncResultDto> {
+    let skill = store
+        .get_skill_by_id(skill_id)?
+        .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+    let tool = adapter.id.as_key().to_string();
+    let dir =
+        resource_dir(adapter, kind)?.ok_or_else(|| resource_unsupported(&tool, kind.as_str()))?;
+    let central_path = std::path::PathBuf::from(&skill.central_path);
+    let source = resource_file(&central_path)?;
+    let target = resource_target(&dir, &target_name_for(store, &skill), &source);
+    let previous_rows = existing_target_rows(store, skill_id, std::slice::from_ref(&tool))?;
+
+    let (result, held) = sync_with_undo(store, undo, &source, &target, overwrite, || {
+        sync_resource_file(&tool, &central_path, &target, overwrite, false)
+    })?;
+    let mode_used = sync_plan::mode_name(&result.mode_used).to_string();
+    store.upsert_skill_target(&SkillTargetRecord {
+        id: Uuid::new_v4().to_string(),
+        skill_id: skill_id.to_string(),
+        tool: tool.clone(),
+        target_path: result.target_path.to_string_lossy().to_string(),
+        mode: mode_used.clone(),
+        status: "ok".to_string(),
+        last_error: None,
+        synced_at: Some(now_ms()),
+        content_hash: skill.content_hash.clone(),
+    })?;
+    undo.push(
+        UndoKind::Sync,
+        skill_id,
+        vec![tool],
+        &result.target_path,
+        held,
+        previous_rows,
+    );
+
+    Ok(SyncResultDto {
+        mode_used,
+        mode_reason: result.mode_reason.clone(),
+        target_path: result.target_path.to_string_lossy().to_string(),
+        warnings: Vec::new(),
+    })
+}
+
+/// Installed tools sharing `adapter`'s skills directory, `adapter` included.
+/// One sync serves them all, so their records are kept consistent.
+fn installed_group_tool_keys(
+    adapter: &crate::core::tool_adapters::ToolAdapter,
+) -> anyhow::Result<Vec<String>> {
+    let mut keys = Vec::new();
+    for a in crate::core::tool_adapters::adapters_sharing_skills_dir(adapter) {
+        if is_tool_installed(&a)? {
+            keys.push(a.id.as_key().to_string());
+        }
+    }
+    Ok(keys)
+}
+
+/// Record a local sync for every tool in `group_tool_keys` and make it
+/// undoable.
+#[allow(clippy::too_many_arguments)]
+fn record_local_sync(
+    store: &SkillStore,
+    undo: &UndoStack,
+    skill_id: &str,
+    group_tool_keys: Vec<String>,
+    result: &SyncOutcome,
+    held: Option<HeldContent>,
+    previous_rows: Vec<SkillTargetRecord>,
+    content_hash: Option<String>,
+) -> anyhow::Result<()> {
+    for key in &group_tool_keys {
+        let record = SkillTargetRecord {
+            id: Uuid::new_v4().to_string(),
+            skill_id: skill_id.to_string(),
+            tool: key.clone(),
+            target_path: result.target_path.to_string_lossy().to_string(),
+            mode: sync_plan::mode_name(&result.mode_used).to_string(),
+            status: "ok".to_string(),
+            last_error: None,
+            synced_at: Some(now_ms()),
+            content_hash: content_hash.clone(),
+        };
+        store.upsert_skill_target(&record)?;
+        skill_events::skill_synced(&record);
+    }
+    undo.push(
+        UndoKind::Sync,
+        skill_id,
+        group_tool_keys,
+        &result.target_path,
+        held,
+        previous_rows,
+    );
+    Ok(())
+}
+
+fn existing_target_rows(
+    store: &SkillStore,
+    skill_id: &str,
+    tools: &[String],
+) -> anyhow::Result<Vec<SkillTargetRecord>> {
+    let mut rows = Vec::new();
+    for tool in tools {
+        if let Some(row) = store.get_skill_target(skill_id, tool)? {
+            rows.push(row);
+        }
+    }
+    Ok(rows)
+}
+
+/// Run a local sync into `target`. With `overwrite`, whatever it replaces is
+/// moved to the undo holding area first (and put back if the sync fails).
+/// Content Skills Hub did not put there is also kept in the backup area,
+/// which outlives the undo window.
+fn sync_with_undo(
+    store: &SkillStore,
+    undo: &UndoStack,
+    source: &std::path::Path,
+    target: &std::path::Path,
+    overwrite: bool,
+    sync: impl FnOnce() -> anyhow::Result<SyncOutcome>,
+) -> anyhow::Result<(SyncOutcome, Option<HeldContent>)> {
+    if overwrite {
+        target_backup::backup_target(store, target)?;
+    }
+    let held = if overwrite { undo.stash(target)? } else { None };
+    match sync() {
+        Ok(outcome) => Ok((outcome, held)),
+        Err(err) => {
+            if let Some(held) = &held {
+                undo.restore(held, target).ok();
+            }
+            if find_coded(&err).is_some_and(|coded| coded.code == ErrorCode::TargetExists) {
+                Err(target_conflict(target, source))
+            } else {
+                Err(err)
+            }
+        }
+    }
+}
+
+fn remove_path_any(path: &str) -> Result<(), CommandError> {
+    let p = std::path::Path::new(path);
+    if !p.exists() {
+        return Ok(());
+    }
+
+    let meta = std::fs::symlink_metadata(p).map_err(|err| err.to_string())?;
+    let ft = meta.file_type();
+
+    // 软链接（即使指向目录）也应该用 remove_file 删除链接本身
+    if ft.is_symlink() {
+        std::fs::remove_file(p).map_err(|err| err.to_string())?;
+        return Ok(());
+    }
+
+    if ft.is_dir() {
+        std::fs::remove_dir_all(p).map_err(|err| err.to_string())?;
+        return Ok(());
+    }
+
+    std::fs::remove_file(p).map_err(|err| err.to_string())?;
+    Ok(())
+}
+
+fn to_install_dto(result: InstallResult) -> InstallResultDto {
+    InstallResultDto {
+        warnings: skill_validation::install_warnings(&result.central_path),
+        skill_id: result.skill_id,
+        name: result.name,
+        central_path: result.central_path.to_string_lossy().to_string(),
+        content_hash: result.content_hash,
+        content_warnings: result.content_warnings,
+        signature_status: result.signature_status,
+    }
+}
+
+fn get_managed_skills_impl(store: &SkillStore) -> Result<Vec<ManagedSkillDto>, CommandError> {
+    let skills = store.list_skills().map_err(|err| err.to_string())?;
+    let custom_targets = store.list_custom_targets().unwrap_or_default();
+    Ok(skills
+        .into_iter()
+        .map(|skill| {
+            let current_hash = skill.content_hash.as_deref();
+            let deployments = store
+                .list_remote_deployments_for_skill(&skill.id)
+                .unwrap_or_default();
+            let mut targets = Vec::new();
+            let mut remote_targets = Vec::new();
+            for target in store.list_skill_targets(&skill.id).unwrap_or_default() {
+                let is_stale =
+                    target_is_stale(&target, current_hash, &custom_targets, &deployments);
+                if let Some((host_id, tool)) = parse_remote_target_key(&target.tool) {
+                    remote_targets.push(RemoteTargetDto {
+                        host_id: host_id.to_string(),
+                        tool: tool.to_string(),
+                        target_path: target.target_path,
+                        synced_at: target.synced_at,
+                        is_stale,
+                    });
+                    continue;
+                }
+                targets.push(SkillTargetDto {
+                    is_stale,
+                    tool: target.tool,
+                    mode: target.mode,
+                    status: target.status,
+                    target_path: target.target_path,
+                    synced_at: target.synced_at,
+                });
+            }
+            let remote_deployments = deployments
+                .iter()
+                .map(|d| RemoteDeploymentDto {
+                    host_id: d.host_id.clone(),
+                    tool_keys: d.tool_keys.clone(),
+                    synced_at: d.synced_at,
+                    is_stale: deployment_is_stale(d, current_hash),
+                })
+                .collect();
+
+            let propagate_to_remotes = store
+                .get_skill_propagate_to_remotes(&skill.id)
+                .unwrap_or(false);
+            let resource_type = store
+                .get_skill_resource_type(&skill.id)
+                .map(|raw| ResourceType::parse(&raw))
+                .unwrap_or_default();
+            let metadata = skill_metadata::skill_metadata(
+                store,
+                &skill.id,
+                std::path::Path::new(&skill.central_path),
+            );
+            let locked = skill_lock::is_skill_locked(store, &skill.id);
+
+            ManagedSkillDto {
+                id: skill.id,
+                name: skill.name,
+                source_type: skill.source_type,
+                source_ref: skill.source_ref,
+                central_path: skill.central_path,
+                created_at: skill.created_at,
+                updated_at: skill.updated_at,
+                last_sync_at: skill.last_sync_at,
+                status: skill.status,
+                targets,
+                remote_targets,
+                group_name: skill.group_name,
+                propagate_to_remotes,
+                remote_deployments,
+                resource_type,
+                metadata,
+                locked,
+            }
+        })
+        .collect())
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn get_skill_history(
+    store: State<'_, SkillStore>,
+    skillId: String,
+) -> Result<Vec<SkillRevision>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        central_history::get_skill_history(&store, &skillId)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+/// Put a skill back to an earlier revision from `get_skill_history`. Copy-mode
+/// targets show as stale until synced again.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn restore_skill_revision(
+    store: State<'_, SkillStore>,
+    skillId: String,
+    commit: String,
+) -> Result<(), CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        central_history::restore_skill_revision(&store, &skillId, &commit).map(|_| ())
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn get_central_remote(
+    store: State<'_, SkillStore>,
+) -> Result<Option<CentralRemoteSummary>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || central_remote::get_central_remote(&store))
+        .await
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
+}
+
+/// Save the private git remote the central repo is backed up to; `None`
+/// forgets it.
+#[tauri::command]
+pub async fn set_central_remote(
+    store: State<'_, SkillStore>,
+    remote: Option<CentralRemote>,
+) -> Result<Option<CentralRemoteSummary>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || central_remote::set_central_remote(&store, remote))
+        .await
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn push_central_repo(
+    paths: State<'_, AppPaths>,
+    store: State<'_, SkillStore>,
+) -> Result<String, CommandError> {
+    let paths = paths.inner().clone();
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let root = central_repo::writable_central_repo(&paths, &store)?;
+        central_remote::push_central_repo(&store, &root)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn pull_central_repo(
+    paths: State<'_, AppPaths>,
+    store: State<'_, SkillStore>,
+) -> Result<PullResult, CommandError> {
+    let paths = paths.inner().clone();
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let root = central_repo::writable_central_repo(&paths, &store)?;
+        central_remote::pull_central_repo(&store, &root)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn update_skill_group(
+    store: State<'_, SkillStore>,
+    skillId: String,
+    groupName: Option<String>,
+) -> Result<(), CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        store
+            .update_skill_group(&skillId, groupName.as_deref())
+            .map_err(CommandError::from)
+    })
+    .await
+    .map_err(CommandError::from)?
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn set_skill_propagate_to_remotes(
+    store: State<'_, SkillStore>,
+    skillId: String,
+    enabled: bool,
+) -> Result<(), CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        store
+            .set_skill_propagate_to_remotes(&skillId, enabled)
+            .map_err(CommandError::from)
+    })
+    .await
+    .map_err(CommandError::from)?
+}
+
+/// Let a quarantined skill be synced to tools.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn approve_skill(
+    store: State<'_, SkillStore>,
+    skillId: String,
+) -> Result<(), CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || quarantine::approve_skill(&store, &skillId))
+        .await
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn get_quarantine_new_installs(
+    store: State<'_, SkillStore>,
+) -> Result<bool, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        Ok::<_, CommandError>(quarantine::get_quarantine_new_installs(&store))
+    })
+    .await
+    .map_err(CommandError::from)?
+}
+
+/// New installs wait for `approve_skill` before they can be synced.
+#[tauri::command]
+pub async fn set_quarantine_new_installs(
+    store: State<'_, SkillStore>,
+    enabled: bool,
+) -> Result<bool, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        quarantine::set_quarantine_new_installs(&store, enabled)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+/// Locked skills are skipped by update-all and refuse updates without
+/// `force` and in-app edits.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn set_skill_locked(
+    store: State<'_, SkillStore>,
+    skillId: String,
+    locked: bool,
+) -> Result<bool, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        skill_lock::set_skill_locked(&store, &skillId, locked)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn get_skill_watch_settings(
+    store: State<'_, SkillStore>,
+    skillId: String,
+) -> Result<WatchSettings, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        watch_settings::get_watch_settings(&store, &skillId)
+    })
+    .await
+    .map_err(CommandError::from)
+}
+
+/// Save file-watcher settings for a skill; `null` restores the defaults.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn set_skill_watch_settings(
+    store: State<'_, SkillStore>,
+    skillId: String,
+    settings: Option<WatchSettings>,
+) -> Result<WatchSettings, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        watch_settings::set_watch_settings(&store, &skillId, settings)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+// ── Orphaned targets ────────────────────────────────────────────────
+
+/// Dangling links in tools' skills directories and target records whose
+/// path is gone.
+#[tauri::command]
+pub async fn find_orphan_targets(
+    store: State<'_, SkillStore>,
+) -> Result<OrphanReport, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || orphan_targets::find_orphan_targets(&store))
+        .await
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
+}
+
+/// Remove dangling links; `paths` limits it to those links.
+#[tauri::command]
+pub async fn remove_dangling_links(
+    store: State<'_, SkillStore>,
+    paths: Option<Vec<String>>,
+) -> Result<usize, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        orphan_targets::remove_dangling_links(&store, paths.as_deref())
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+/// Delete target records whose path is gone; `targetPaths` limits it to
+/// those paths.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn forget_missing_targets(
+    store: State<'_, SkillStore>,
+    targetPaths: Option<Vec<String>>,
+) -> Result<usize, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        orphan_targets::forget_missing_targets(&store, targetPaths.as_deref())
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+// ── Overwritten targets ─────────────────────────────────────────────
+
+/// Content that syncing with overwrite replaced, newest first.
+#[tauri::command]
+pub async fn list_overwritten_targets(
+    store: State<'_, SkillStore>,
+) -> Result<Vec<OverwrittenTarget>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || target_backup::list_overwritten_targets(&store))
+        .await
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
+}
+
+/// Put replaced content back at its original path. Fails with
+/// `TARGET_EXISTS` when something is there, unless `overwrite`.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn restore_overwritten_target(
+    store: State<'_, SkillStore>,
+    backupId: String,
+    overwrite: Option<bool>,
+) -> Result<OverwrittenTarget, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        target_backup::restore_overwritten_target(&store, &backupId, overwrite.unwrap_or(false))
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn delete_overwritten_target(
+    store: State<'_, SkillStore>,
+    backupId: String,
+) -> Result<(), CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        target_backup::delete_overwritten_target(&store, &backupId)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+// ── Sync topology ───────────────────────────────────────────────────
+
+#[tauri::command]
+pub async fn get_sync_topology(store: State<'_, SkillStore>) -> Result<SyncTopology, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || build_sync_topology(&store))
+        .await
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
+}
+
+// ── Startup ─────────────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_startup_status(state: State<'_, StartupState>) -> StartupStatus {
+    state.snapshot()
+}
+
+// ── Skill content preview ───────────────────────────────────────────
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn read_skill_content(
+    store: State<'_, SkillStore>,
+    skillId: String,
+) -> Result<String, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let skill = store
+            .get_skill_by_id(&skillId)?
+            .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+        let path = std::path::PathBuf::from(&skill.central_path).join("SKILL.md");
+        if !path.exists() {
+            anyhow::bail!("SKILL.md not found");
+        }
+        let content = std::fs::read_to_string(&path).with_context(|| format!("read {:?}", path))?;
+        Ok::<_, anyhow::Error>(content)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn list_skill_files(
+    store: State<'_, SkillStore>,
+    skillId: String,
+) -> Result<Vec<SkillFileEntry>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || skill_files::list_skill_files(&store, &skillId))
+        .await
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
+}
+
+/// Any file of a skill for preview; see [`skill_files::read_skill_file`].
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn read_skill_file(
+    store: State<'_, SkillStore>,
+    skillId: String,
+    relativePath: String,
+) -> Result<SkillFileContent, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        skill_files::read_skill_file(&store, &skillId, &relativePath)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn validate_skill(
+    store: State<'_, SkillStore>,
+    skillId: String,
+) -> Result<SkillValidation, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || skill_validation::validate_skill(&store, &skillId))
+        .await
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
+}
+
+/// Per-skill targets, updates, source reachability, drift and size in one
+/// call. `checkSources` (default true) contacts git remotes and registries.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn get_skills_health(
+    store: State<'_, SkillStore>,
+    checkSources: Option<bool>,
+) -> Result<Vec<SkillHealth>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        skills_health(&store, checkSources.unwrap_or(true))
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+/// Files edited in the central copy since install, and copy-mode targets
+/// that no longer match it.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn detect_skill_drift(
+    store: State<'_, SkillStore>,
+    skillId: String,
+) -> Result<SkillDrift, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || skill_drift::detect_skill_drift(&store, &skillId))
+        .await
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn get_skill_dependencies(
+    store: State<'_, SkillStore>,
+    skillId: String,
+) -> Result<Vec<SkillDependency>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        skill_dependencies::skill_dependencies(&store, &skillId)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+/// Skills that declare `skillId` as a dependency; shown before a delete.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn get_skill_dependents(
+    store: State<'_, SkillStore>,
+    skillId: String,
+) -> Result<Vec<SkillDependent>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        skill_dependencies::skill_dependents(&store, &skillId)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[derive(Debug, Serialize)]
+pub struct DependencyFailureDto {
+    pub spec: String,
+    pub error: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DependencyInstallDto {
+    pub installed: Vec<InstallResultDto>,
+    pub failed: Vec<DependencyFailureDto>,
+}
+
+/// Install what `skillId` declares but is missing, after the user confirmed
+/// the list from `get_skill_dependencies`. `specs` narrows it to the
+/// confirmed entries.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn install_skill_dependencies(
+    paths: State<'_, AppPaths>,
+    store: State<'_, SkillStore>,
+    ops: State<'_, OperationRegistry>,
+    skillId: String,
+    specs: Option<Vec<String>>,
+) -> Result<DependencyInstallDto, CommandError> {
+    let paths = paths.inner().clone();
+    let store = store.inner().clone();
+    let op = ops.start(
+        OperationKind::Install,
+        format!("dependencies of {}", skillId),
+    );
+    tauri::async_runtime::spawn_blocking(move || {
+        op.run(|_| {
+            let report = skill_dependencies::install_missing_dependencies(
+                &paths,
+                &store,
+                &skillId,
+                specs.as_deref(),
+            )?;
+            Ok::<_, anyhow::Error>(DependencyInstallDto {
+                installed: report.installed.into_iter().map(to_install_dto).collect(),
+                failed: report
+                    .failed
+                    .into_iter()
+                    .map(|(spec, error)| DependencyFailureDto { spec, error })
+                    .collect(),
+            })
+        })
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+/// Save `content` to `relativePath` inside a skill's central folder; see
+/// [`skill_authoring::write_skill_content`].
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn write_skill_content(
+    store: State<'_, SkillStore>,
+    skillId: String,
+    relativePath: String,
+    content: String,
+) -> Result<SkillWriteResult, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        skill_authoring::write_skill_content(&store, &skillId, &relativePath, &content)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+/// Preview a file from a remote host's central copy of a skill
+/// (SKILL.md unless `relativePath` is given).
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn read_remote_skill_content(
+    store: State<'_, SkillStore>,
+    hostId: String,
+    skillName: String,
+    relativePath: Option<String>,
+) -> Result<String, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let host = store
+            .get_remote_host_by_id(&hostId)?
+            .ok_or_else(|| anyhow::anyhow!("remote host not found: {}", hostId))?;
+        let sess = open_host_session(&host)?;
+        remote_sync::read_remote_skill_file(&sess, &skillName, relativePath.as_deref())
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+// ── ClawHub commands ────────────────────────────────────────────────
+
+#[tauri::command]
+pub async fn list_registry_auth(
+    store: State<'_, SkillStore>,
+) -> Result<Vec<RegistryAuthSummary>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::core::registry_auth::list_registry_auth(&store)
+    })
+    .await
+    .map_err(CommandError::from)
+}
+
+/// Save credentials for a registry; `auth: null` removes them.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn set_registry_auth(
+    store: State<'_, SkillStore>,
+    registryUrl: String,
+    auth: Option<RegistryAuth>,
+) -> Result<Vec<RegistryAuthSummary>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::core::registry_auth::set_registry_auth(&store, &registryUrl, auth)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn start_registry_device_login(
+    store: State<'_, SkillStore>,
+    registryUrl: String,
+) -> Result<DeviceLogin, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::core::registry_auth::start_device_login(&store, &registryUrl)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn poll_registry_device_login(
+    store: State<'_, SkillStore>,
+    registryUrl: String,
+    deviceCode: String,
+) -> Result<DevicePollStatus, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::core::registry_auth::poll_device_login(&store, &registryUrl, &deviceCode)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn search_clawhub(
+    query: String,
+    limit: Option<u32>,
+) -> Result<Vec<clawhub_api::ClawHubSkill>, CommandError> {
+    let limit = limit.unwrap_or(20) as usize;
+    tauri::async_runtime::spawn_blocking(move || clawhub_api::search_clawhub(&query, limit))
+        .await
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
+}
+
+/// One page of ClawHub search results; feed `nextCursor`/`nextOffset` back
+/// in `page` to load more.
+#[tauri::command]
+pub async fn search_clawhub_page(
+    query: String,
+    limit: Option<u32>,
+    page: Option<clawhub_api::SearchPageRequest>,
+) -> Result<clawhub_api::ClawHubSearchPage, CommandError> {
+    let limit = limit.unwrap_or(20) as usize;
+    let page = page.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || {
+        clawhub_api::search_clawhub_page(&query, limit, &page)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+/// ClawHub listings without a query: `sort` is one of `trending`,
+/// `newest`, `updated`, `downloads`, `stars` (default `trending`).
+#[tauri::command]
+pub async fn browse_clawhub(
+    category: Option<String>,
+    sort: Option<String>,
+    limit: Option<u32>,
+    page: Option<clawhub_api::SearchPageRequest>,
+) -> Result<clawhub_api::ClawHubSearchPage, CommandError> {
+    let sort = sort.unwrap_or_else(|| "trending".to_string());
+    let limit = limit.unwrap_or(20) as usize;
+    let page = page.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || {
+        clawhub_api::browse_clawhub(category.as_deref(), &sort, limit, &page)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn get_clawhub_skill_cmd(
+    slug: String,
+) -> Result<clawhub_api::ClawHubSkillDetail, CommandError> {
+    tauri::async_runtime::spawn_blocking(move || clawhub_api::get_clawhub_skill(&slug))
+        .await
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn get_clawhub_skill_readme(
+    slug: String,
+    version: Option<String>,
+) -> Result<clawhub_api::ClawHubReadme, CommandError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        clawhub_api::get_clawhub_skill_readme(&slug, version.as_deref())
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn get_github_file(
+    owner: String,
+    repo: String,
+    path: String,
+    r#ref: Option<String>,
+    provider: Option<String>,
+) -> Result<clawhub_api::GitHubFile, CommandError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let provider = resolve_provider(provider.as_deref())?;
+        clawhub_api::get_github_file(&provider, &owner, &repo, &path, r#ref.as_deref())
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn get_github_tree_cmd(
+    owner: String,
+    repo: String,
+    r#ref: Option<String>,
+    provider: Option<String>,
+) -> Result<Vec<clawhub_api::SkillFileEntry>, CommandError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let provider = resolve_provider(provider.as_deref())?;
+        clawhub_api::get_github_tree(&provider, &owner, &repo, r#ref.as_deref())
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn install_clawhub_skill(
+    paths: State<'_, AppPaths>,
+    store: State<'_, SkillStore>,
+    ops: State<'_, OperationRegistry>,
+    slug: String,
+    version: Option<String>,
+    name: Option<String>,
+    confirmUntrusted: Option<bool>,
+) -> Result<InstallResultDto, CommandError> {
+    let paths = paths.inner().clone();
+    let store = store.inner().clone();
+    let op = ops.start(OperationKind::Install, format!("clawhub://{}", slug));
+    tauri::async_runtime::spawn_blocking(move || {
+        op.run(|_| {
+            check_clawhub_source(&store, &slug, confirmUntrusted.unwrap_or(false))?;
+            let result = clawhub_api::install_clawhub_skill(&paths, &store, &slug, version, name)?;
+            Ok::<_, anyhow::Error>(to_install_dto(result))
+        })
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn has_clawhub_token() -> Result<bool, CommandError> {
+    Ok(clawhub_api::has_clawhub_token())
+}
+
+/// Returns whether a token is set afterwards; an empty token clears it.
+#[tauri::command]
+pub async fn set_clawhub_token(
+    store: State<'_, SkillStore>,
+    token: Option<String>,
+) -> Result<bool, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || clawhub_api::set_clawhub_token(&store, token))
+        .await
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn publish_clawhub_skill(
+    store: State<'_, SkillStore>,
+    ops: State<'_, OperationRegistry>,
+    skillId: String,
+    version: String,
+    changelog: Option<String>,
+) -> Result<clawhub_api::ClawHubPublishResult, CommandError> {
+    let store = store.inner().clone();
+    let op = ops.start(OperationKind::Publish, format!("{}@{}", skillId, version));
+    tauri::async_runtime::spawn_blocking(move || {
+        op.run(|_| {
+            let skill = store
+                .get_skill_by_id(&skillId)?
+                .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+            clawhub_api::publish_clawhub_skill(
+                &skill,
+                &version,
+                changelog.as_deref().unwrap_or_default(),
+            )
+        })
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+// ── Skill packages (.skillpkg) ─────────────────────────────────────
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn export_skill_package(
+    store: State<'_, SkillStore>,
+    skillId: String,
+    outPath: String,
+) -> Result<SkillPkgManifest, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let skill = store
+            .get_skill_by_id(&skillId)?
+            .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+        skillpkg::export_skillpkg(&skill, std::path::Path::new(&outPath))
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn create_backup(
+    paths: State<'_, AppPaths>,
+    store: State<'_, SkillStore>,
+    outPath: String,
+) -> Result<BackupManifest, CommandError> {
+    let paths = paths.inner().clone();
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let central = resolve_central_repo_path(&paths, &store)?;
+        backup::create_backup(&store, &central, std::path::Path::new(&outPath))
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn verify_backup(archivePath: String) -> Result<BackupVerification, CommandError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        backup::verify_backup(std::path::Path::new(&archivePath))
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn inspect_skill_package(
+    store: State<'_, SkillStore>,
+    pkgPath: String,
+) -> Result<SkillPkgInspection, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        skillpkg::inspect_skillpkg(&store, std::path::Path::new(&pkgPath))
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn install_skill_package(
+    paths: State<'_, AppPaths>,
+    store: State<'_, SkillStore>,
+    ops: State<'_, OperationRegistry>,
+    pkgPath: String,
+    name: Option<String>,
+    confirmUnverified: Option<bool>,
+) -> Result<InstallResultDto, CommandError> {
+    let paths = paths.inner().clone();
+    let store = store.inner().clone();
+    let op = ops.start(OperationKind::Install, pkgPath.clone());
+    tauri::async_runtime::spawn_blocking(move || {
+        op.run(|_| {
+            let result = skillpkg::install_skillpkg(
+                &paths,
+                &store,
+                std::path::Path::new(&pkgPath),
+                name,
+                confirmUnverified.unwrap_or(false),
+            )?;
+            Ok::<_, anyhow::Error>(to_install_dto(result))
+        })
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn attach_skill_package_signature(
+    pkgPath: String,
+    signature: String,
+) -> Result<SkillPkgManifest, CommandError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        skillpkg::attach_signature(std::path::Path::new(&pkgPath), &signature)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn get_skillpkg_trusted_keys(
     store: State<'_, SkillStore>,
-    slug: String,
-    version: Option<String>,
-    name: Option<String>,
-) -> Result<InstallResultDto, String> {
+) -> Result<Vec<String>, CommandError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
-        let temp_dir = tempfile::tempdir().context("create temp dir for clawhub download")?;
-        let extracted_path = clawhub_api::download_and_extract_clawhub_skill(
-            &slug,
-            version.as_deref(),
-            temp_dir.path(),
-        )?;
-
-        let display_name = name.unwrap_or_else(|| slug.clone());
-        let result = install_local_skill(&app, &store, &extracted_path, Some(display_name))?;
-
-        // Fix source info: replace temp path with clawhub slug so the record
-        // remains valid after the temp dir is cleaned up.
-        if let Some(mut record) = store.get_skill_by_id(&result.skill_id)? {
-            record.source_type = "clawhub".to_string();
-            record.source_ref = Some(format!("clawhub://{}", slug));
-            store.upsert_skill(&record)?;
-        }
-
-        // temp_dir is automatically cleaned up when dropped
-        Ok::<_, anyhow::Error>(to_install_dto(result))
+        Ok::<_, anyhow::Error>(skillpkg::get_trusted_keys(&store))
     })
     .await
-    .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn set_skillpkg_trusted_keys(
+    store: State<'_, SkillStore>,
+    keys: Vec<String>,
+) -> Result<Vec<String>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || skillpkg::set_trusted_keys(&store, keys))
+        .await
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
 }
 
 // ── Remote Host commands ───────────────────────────────────────────────
@@ -960,6 +3889,8 @@ pub struct RemoteHostDto {
     pub updated_at: i64,
     pub last_sync_at: Option<i64>,
     pub status: String,
+    pub group_name: Option<String>,
+    pub health: Option<RemoteHostHealth>,
 }
 
 fn record_to_dto(r: RemoteHostRecord) -> RemoteHostDto {
@@ -975,18 +3906,30 @@ fn record_to_dto(r: RemoteHostRecord) -> RemoteHostDto {
         updated_at: r.updated_at,
         last_sync_at: r.last_sync_at,
         status: r.status,
+        group_name: r.group_name,
+        health: r
+            .health_json
+            .as_deref()
+            .and_then(|json| serde_json::from_str(json).ok()),
     }
 }
 
+/// Open (or reuse) the pooled SSH session for a saved remote host.
+fn open_host_session(host: &RemoteHostRecord) -> anyhow::Result<ssh2::Session> {
+    remote_sync::host_session(host)
+}
+
 #[tauri::command]
-pub async fn list_remote_hosts(store: State<'_, SkillStore>) -> Result<Vec<RemoteHostDto>, String> {
+pub async fn list_remote_hosts(
+    store: State<'_, SkillStore>,
+) -> Result<Vec<RemoteHostDto>, CommandError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
-        let hosts = store.list_remote_hosts().map_err(format_anyhow_error)?;
+        let hosts = store.list_remote_hosts().map_err(CommandError::from)?;
         Ok(hosts.into_iter().map(record_to_dto).collect())
     })
     .await
-    .map_err(|err| err.to_string())?
+    .map_err(CommandError::from)?
 }
 
 #[tauri::command]
@@ -999,7 +3942,7 @@ pub async fn add_remote_host(
     username: String,
     authMethod: Option<String>,
     keyPath: Option<String>,
-) -> Result<RemoteHostDto, String> {
+) -> Result<RemoteHostDto, CommandError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
         let port = port.unwrap_or(22);
@@ -1019,13 +3962,15 @@ pub async fn add_remote_host(
             updated_at: now,
             last_sync_at: None,
             status: "idle".to_string(),
+            group_name: None,
+            health_json: None,
         };
         store.upsert_remote_host(&record)?;
         Ok::<_, anyhow::Error>(record_to_dto(record))
     })
     .await
-    .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -1039,7 +3984,7 @@ pub async fn update_remote_host(
     username: String,
     authMethod: Option<String>,
     keyPath: Option<String>,
-) -> Result<RemoteHostDto, String> {
+) -> Result<RemoteHostDto, CommandError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
         let port = port.unwrap_or(22);
@@ -1062,13 +4007,37 @@ pub async fn update_remote_host(
             updated_at: now_ms(),
             last_sync_at: existing.last_sync_at,
             status: existing.status,
+            group_name: existing.group_name,
+            health_json: existing.health_json,
         };
         store.upsert_remote_host(&record)?;
+        remote_sync::evict_session(&record.id);
         Ok::<_, anyhow::Error>(record_to_dto(record))
     })
     .await
-    .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn update_remote_host_group(
+    store: State<'_, SkillStore>,
+    hostId: String,
+    groupName: Option<String>,
+) -> Result<(), CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let group = groupName
+            .as_deref()
+            .map(str::trim)
+            .filter(|g| !g.is_empty());
+        store
+            .update_remote_host_group(&hostId, group)
+            .map_err(CommandError::from)
+    })
+    .await
+    .map_err(CommandError::from)?
 }
 
 #[tauri::command]
@@ -1076,15 +4045,42 @@ pub async fn update_remote_host(
 pub async fn delete_remote_host(
     store: State<'_, SkillStore>,
     hostId: String,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
+        remote_sync::evict_session(&hostId);
         store
             .delete_remote_host(&hostId)
-            .map_err(format_anyhow_error)
+            .map_err(CommandError::from)
+    })
+    .await
+    .map_err(CommandError::from)?
+}
+
+/// Probe a saved host and store the result on its record. Unreachable hosts
+/// still return a snapshot, with `reachable: false` and the error.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn check_remote_host_health(
+    store: State<'_, SkillStore>,
+    hostId: String,
+) -> Result<RemoteHostHealth, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let host = store
+            .get_remote_host_by_id(&hostId)?
+            .ok_or_else(|| anyhow::anyhow!("remote host not found: {}", hostId))?;
+        let health = remote_sync::check_host_health(&host, now_ms());
+        store.update_remote_host_health(&hostId, &serde_json::to_string(&health)?)?;
+        let status = if health.reachable { "ok" } else { "error" };
+        store
+            .update_remote_host_sync_status(&hostId, status, None)
+            .ok();
+        Ok::<_, anyhow::Error>(health)
     })
     .await
-    .map_err(|err| err.to_string())?
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -1095,7 +4091,7 @@ pub async fn test_remote_connection(
     username: String,
     authMethod: Option<String>,
     keyPath: Option<String>,
-) -> Result<String, String> {
+) -> Result<String, CommandError> {
     tauri::async_runtime::spawn_blocking(move || {
         remote_sync::test_connection(
             &host,
@@ -1106,8 +4102,8 @@ pub async fn test_remote_connection(
         )
     })
     .await
-    .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
 #[derive(Debug, Serialize)]
@@ -1129,24 +4125,17 @@ pub struct RemoteToolStatusDto {
 pub async fn get_remote_tool_status(
     store: State<'_, SkillStore>,
     hostId: String,
-) -> Result<RemoteToolStatusDto, String> {
+) -> Result<RemoteToolStatusDto, CommandError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
         let host = store
             .get_remote_host_by_id(&hostId)
-            .map_err(format_anyhow_error)?
+            .map_err(CommandError::from)?
             .ok_or_else(|| format!("remote host not found: {}", hostId))?;
 
-        let sess = remote_sync::create_ssh_session(
-            &host.host,
-            host.port as u16,
-            &host.username,
-            &host.auth_method,
-            host.key_path.as_deref(),
-        )
-        .map_err(format_anyhow_error)?;
+        let sess = open_host_session(&host).map_err(CommandError::from)?;
 
-        let tools = remote_sync::detect_remote_tools(&sess).map_err(format_anyhow_error)?;
+        let tools = remote_sync::detect_remote_tools(&sess).map_err(CommandError::from)?;
 
         Ok(RemoteToolStatusDto {
             hostId,
@@ -1161,7 +4150,7 @@ pub async fn get_remote_tool_status(
         })
     })
     .await
-    .map_err(|err| err.to_string())?
+    .map_err(CommandError::from)?
 }
 
 #[derive(Debug, Serialize)]
@@ -1170,67 +4159,146 @@ pub struct RemoteSyncResultDto {
     pub syncedSkills: Vec<String>,
 }
 
+/// Fan out to several hosts at once; the frontend gets one row per host with
+/// one cell per skill instead of looping hosts itself. `groupName` adds every
+/// host in that group to `hostIds`.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn sync_skills_to_hosts(
+    store: State<'_, SkillStore>,
+    ops: State<'_, OperationRegistry>,
+    hostIds: Vec<String>,
+    groupName: Option<String>,
+    skillIds: Vec<String>,
+    toolKeys: Vec<String>,
+) -> Result<Vec<HostSyncResult>, CommandError> {
+    let store = store.inner().clone();
+    let op = ops.start(
+        OperationKind::RemotePush,
+        groupName.clone().unwrap_or_else(|| hostIds.join(", ")),
+    );
+    tauri::async_runtime::spawn_blocking(move || {
+        op.run(|_| {
+            let mut host_ids = hostIds;
+            if let Some(group) = groupName.as_deref() {
+                for host in store.list_remote_hosts_in_group(group)? {
+                    if !host_ids.contains(&host.id) {
+                        host_ids.push(host.id);
+                    }
+                }
+            }
+            multi_host_sync::sync_skills_to_hosts(
+                &store,
+                &host_ids,
+                &skillIds,
+                &toolKeys,
+                multi_host_sync::MAX_PARALLEL_HOSTS,
+            )
+        })
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
 #[tauri::command]
 #[allow(non_snake_case)]
 pub async fn sync_all_skills_to_remote(
     store: State<'_, SkillStore>,
+    ops: State<'_, OperationRegistry>,
     hostId: String,
     toolKeys: Vec<String>,
-) -> Result<RemoteSyncResultDto, String> {
+) -> Result<RemoteSyncResultDto, CommandError> {
     let store = store.inner().clone();
+    let op = ops.start(OperationKind::RemotePush, hostId.clone());
     tauri::async_runtime::spawn_blocking(move || {
-        let host = store
-            .get_remote_host_by_id(&hostId)
-            .map_err(format_anyhow_error)?
-            .ok_or_else(|| format!("remote host not found: {}", hostId))?;
-
-        store
-            .update_remote_host_sync_status(&hostId, "syncing", None)
-            .ok();
-
-        let sess = remote_sync::create_ssh_session(
-            &host.host,
-            host.port as u16,
-            &host.username,
-            &host.auth_method,
-            host.key_path.as_deref(),
-        )
-        .map_err(|e| {
-            store
-                .update_remote_host_sync_status(&hostId, "error", None)
-                .ok();
-            format_anyhow_error(e)
-        })?;
-
-        let skills = store.list_skills().map_err(format_anyhow_error)?;
-        let skill_infos: Vec<remote_sync::RemoteSkillInfo> = skills
-            .into_iter()
-            .map(|s| remote_sync::RemoteSkillInfo {
-                name: s.name,
-                local_path: std::path::PathBuf::from(s.central_path),
-                source_type: s.source_type,
-                source_ref: s.source_ref,
+        op.run(|op| {
+            sync_all_to_remote_host(&store, &hostId, &toolKeys, &|done, total| {
+                op.progress(done, total)
             })
-            .collect();
+        })
+    })
+    .await
+    .map_err(CommandError::from)?
+}
 
-        let synced = remote_sync::sync_all_skills_to_remote(&sess, &skill_infos, &toolKeys)
-            .map_err(|e| {
-                store
-                    .update_remote_host_sync_status(&hostId, "error", None)
-                    .ok();
-                format_anyhow_error(e)
-            })?;
+/// `sync_all_skills_to_remote` as a background job; returns the job id.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn queue_remote_sync(
+    store: State<'_, SkillStore>,
+    jobs: State<'_, JobQueue>,
+    hostId: String,
+    toolKeys: Vec<String>,
+) -> Result<String, CommandError> {
+    let store = store.inner().clone();
+    let label = hostId.clone();
+    Ok(jobs.enqueue(OperationKind::RemotePush, label, move |job| {
+        job.log(format!("syncing all skills to {}", hostId));
+        let result = sync_all_to_remote_host(&store, &hostId, &toolKeys, &|done, total| {
+            job.progress(done, total)
+        })?;
+        job.log(format!("{} skills synced", result.syncedSkills.len()));
+        Ok::<_, CommandError>(result)
+    }))
+}
 
+fn sync_all_to_remote_host(
+    store: &SkillStore,
+    host_id: &str,
+    tool_keys: &[String],
+    progress: &(dyn Fn(usize, usize) + Sync),
+) -> Result<RemoteSyncResultDto, CommandError> {
+    let host = store
+        .get_remote_host_by_id(host_id)
+        .map_err(CommandError::from)?
+        .ok_or_else(|| format!("remote host not found: {}", host_id))?;
+
+    store
+        .update_remote_host_sync_status(host_id, "syncing", None)
+        .ok();
+
+    let sess = open_host_session(&host).map_err(|e| {
+        store
+            .update_remote_host_sync_status(host_id, "error", None)
+            .ok();
+        skill_events::remote_sync_finished(host_id, &[], Some(&format!("{:#}", e)));
+        CommandError::from(e)
+    })?;
+
+    let skills = store.list_skills().map_err(CommandError::from)?;
+    let skill_infos: Vec<remote_sync::RemoteSkillInfo> = skills
+        .iter()
+        .filter(|s| !quarantine::is_quarantined(s))
+        .map(|s| remote_skill_info(store, s))
+        .collect();
+
+    let synced = remote_sync::sync_all_skills_to_remote_with_progress(
+        &sess,
+        Some(&|| remote_sync::fresh_host_session(&host)),
+        &skill_infos,
+        tool_keys,
+        progress,
+    )
+    .map_err(|e| {
         store
-            .update_remote_host_sync_status(&hostId, "ok", Some(now_ms()))
+            .update_remote_host_sync_status(host_id, "error", None)
             .ok();
+        skill_events::remote_sync_finished(host_id, &[], Some(&format!("{:#}", e)));
+        CommandError::from(e)
+    })?;
+
+    store
+        .update_remote_host_sync_status(host_id, "ok", Some(now_ms()))
+        .ok();
+    skill_events::remote_sync_finished(host_id, &synced, None);
+    for skill in skills.iter().filter(|s| synced.contains(&s.name)) {
+        record_remote_deployment(store, host_id, skill, tool_keys, true).ok();
+    }
 
-        Ok(RemoteSyncResultDto {
-            syncedSkills: synced,
-        })
+    Ok(RemoteSyncResultDto {
+        syncedSkills: synced,
     })
-    .await
-    .map_err(|err| err.to_string())?
 }
 
 #[tauri::command]
@@ -1240,41 +4308,38 @@ pub async fn sync_remote_skill_to_tool(
     hostId: String,
     skillId: String,
     toolKey: String,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
         let host = store
             .get_remote_host_by_id(&hostId)
-            .map_err(format_anyhow_error)?
+            .map_err(CommandError::from)?
             .ok_or_else(|| format!("remote host not found: {}", hostId))?;
 
         let skill = store
             .get_skill_by_id(&skillId)
-            .map_err(format_anyhow_error)?
+            .map_err(CommandError::from)?
             .ok_or_else(|| format!("skill not found: {}", skillId))?;
+        quarantine::ensure_approved(&skill).map_err(CommandError::from)?;
 
-        let sess = remote_sync::create_ssh_session(
-            &host.host,
-            host.port as u16,
-            &host.username,
-            &host.auth_method,
-            host.key_path.as_deref(),
-        )
-        .map_err(format_anyhow_error)?;
+        let sess = open_host_session(&host).map_err(CommandError::from)?;
 
-        let info = remote_sync::RemoteSkillInfo {
-            name: skill.name.clone(),
-            local_path: std::path::PathBuf::from(&skill.central_path),
-            source_type: skill.source_type.clone(),
-            source_ref: skill.source_ref.clone(),
-        };
+        let info = remote_skill_info(&store, &skill);
         remote_sync::sync_skill_to_remote_tool(&sess, &info, &toolKey)
-            .map_err(format_anyhow_error)?;
+            .map_err(CommandError::from)?;
+        record_remote_deployment(
+            &store,
+            &hostId,
+            &skill,
+            std::slice::from_ref(&toolKey),
+            true,
+        )
+        .map_err(CommandError::from)?;
 
         Ok(())
     })
     .await
-    .map_err(|err| err.to_string())?
+    .map_err(CommandError::from)?
 }
 
 #[tauri::command]
@@ -1284,35 +4349,84 @@ pub async fn unsync_remote_skill_from_tool(
     hostId: String,
     skillId: String,
     toolKey: String,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
         let host = store
             .get_remote_host_by_id(&hostId)
-            .map_err(format_anyhow_error)?
+            .map_err(CommandError::from)?
             .ok_or_else(|| format!("remote host not found: {}", hostId))?;
 
         let skill = store
             .get_skill_by_id(&skillId)
-            .map_err(format_anyhow_error)?
+            .map_err(CommandError::from)?
             .ok_or_else(|| format!("skill not found: {}", skillId))?;
 
-        let sess = remote_sync::create_ssh_session(
-            &host.host,
-            host.port as u16,
-            &host.username,
-            &host.auth_method,
-            host.key_path.as_deref(),
-        )
-        .map_err(format_anyhow_error)?;
+        let sess = open_host_session(&host).map_err(CommandError::from)?;
 
-        remote_sync::unsync_skill_from_remote_tool(&sess, &skill.name, &toolKey)
-            .map_err(format_anyhow_error)?;
+        remote_sync::unsync_skill_from_remote_tool(
+            &sess,
+            &target_name_for(&store, &skill),
+            &toolKey,
+        )
+        .map_err(CommandError::from)?;
+        forget_remote_tool(&store, &hostId, &skill.id, &toolKey).map_err(CommandError::from)?;
 
         Ok(())
     })
     .await
-    .map_err(|err| err.to_string())?
+    .map_err(CommandError::from)?
+}
+
+/// Delete a skill from a remote host: the symlinks in `toolKeys` (every tool
+/// when empty), the `~/.skillshub/<name>` copy, and any remote custom-target
+/// links on that host that pointed at it.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn remove_remote_skill(
+    store: State<'_, SkillStore>,
+    hostId: String,
+    skillName: String,
+    toolKeys: Vec<String>,
+) -> Result<(), CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let host = store
+            .get_remote_host_by_id(&hostId)?
+            .ok_or_else(|| anyhow::anyhow!("remote host not found: {}", hostId))?;
+        let sess = open_host_session(&host)?;
+
+        let skill = store
+            .list_skills()?
+            .into_iter()
+            .find(|s| s.name == skillName);
+        let target_name = skill
+            .as_ref()
+            .map(|s| target_name_for(&store, s))
+            .unwrap_or_else(|| skillName.clone());
+
+        remote_sync::remove_skill_from_remote_tools(&sess, &skillName, &target_name, &toolKeys)?;
+
+        // Custom targets on this host symlink to the central copy we just removed.
+        if let Some(skill) = skill {
+            store.delete_remote_deployment(&hostId, &skill.id)?;
+            for ct in store.list_custom_targets()? {
+                if ct.remote_host_id.as_deref() != Some(hostId.as_str()) {
+                    continue;
+                }
+                let tool_key = format!("custom:{}", ct.id);
+                if let Some(target) = store.get_skill_target(&skill.id, &tool_key)? {
+                    remote_sync::remove_remote_symlink(&sess, &target.target_path)?;
+                    store.delete_skill_target(&skill.id, &tool_key)?;
+                }
+            }
+        }
+
+        Ok::<_, anyhow::Error>(())
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
 #[derive(Debug, Serialize)]
@@ -1329,6 +4443,8 @@ pub struct RemoteSkillsDto {
     pub hostId: String,
     pub skills: Vec<String>,
     pub toolLinks: Vec<RemoteToolLinkDto>,
+    /// Skills whose remote copy is older than the local one.
+    pub staleSkills: Vec<String>,
 }
 
 #[tauri::command]
@@ -1336,35 +4452,41 @@ pub struct RemoteSkillsDto {
 pub async fn list_remote_skills(
     store: State<'_, SkillStore>,
     hostId: String,
-) -> Result<RemoteSkillsDto, String> {
+) -> Result<RemoteSkillsDto, CommandError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
         let host = store
             .get_remote_host_by_id(&hostId)
-            .map_err(format_anyhow_error)?
+            .map_err(CommandError::from)?
             .ok_or_else(|| format!("remote host not found: {}", hostId))?;
 
-        let sess = remote_sync::create_ssh_session(
-            &host.host,
-            host.port as u16,
-            &host.username,
-            &host.auth_method,
-            host.key_path.as_deref(),
-        )
-        .map_err(format_anyhow_error)?;
+        let sess = open_host_session(&host).map_err(CommandError::from)?;
 
-        let skills = remote_sync::list_remote_skills(&sess).map_err(format_anyhow_error)?;
+        let skills = remote_sync::list_remote_skills(&sess).map_err(CommandError::from)?;
 
         // Detect installed tools so we can check per-tool symlinks
-        let tools = remote_sync::detect_remote_tools(&sess).map_err(format_anyhow_error)?;
+        let tools = remote_sync::detect_remote_tools(&sess).map_err(CommandError::from)?;
         let installed_keys: Vec<String> = tools
             .iter()
             .filter(|(_, _, installed)| *installed)
             .map(|(key, _, _)| key.clone())
             .collect();
 
-        let links = remote_sync::list_remote_skill_links(&sess, &skills, &installed_keys)
-            .map_err(format_anyhow_error)?;
+        let managed = store.list_skills().map_err(CommandError::from)?;
+        let skill_names: Vec<(String, String)> = skills
+            .iter()
+            .map(|name| {
+                let target_name = managed
+                    .iter()
+                    .find(|s| &s.name == name)
+                    .map(|s| target_name_for(&store, s))
+                    .unwrap_or_else(|| name.clone());
+                (name.clone(), target_name)
+            })
+            .collect();
+
+        let links = remote_sync::list_remote_skill_links(&sess, &skill_names, &installed_keys)
+            .map_err(CommandError::from)?;
 
         let tool_links: Vec<RemoteToolLinkDto> = links
             .into_iter()
@@ -1375,6 +4497,15 @@ pub async fn list_remote_skills(
             })
             .collect();
 
+        let stale_skills: Vec<String> = store
+            .list_remote_deployments_for_host(&hostId)
+            .map_err(CommandError::from)?
+            .into_iter()
+            .filter(|d| d.status == "stale")
+            .filter_map(|d| managed.iter().find(|s| s.id == d.skill_id))
+            .map(|s| s.name.clone())
+            .collect();
+
         // SSH succeeded → reset status if it was previously "error"
         store
             .update_remote_host_sync_status(&hostId, "ok", None)
@@ -1384,77 +4515,86 @@ pub async fn list_remote_skills(
             hostId,
             skills,
             toolLinks: tool_links,
+            staleSkills: stale_skills,
         })
     })
     .await
-    .map_err(|err| err.to_string())?
+    .map_err(CommandError::from)?
 }
 
 #[tauri::command]
 #[allow(non_snake_case)]
 pub async fn sync_selected_skills_to_remote(
     store: State<'_, SkillStore>,
+    ops: State<'_, OperationRegistry>,
     hostId: String,
     skillIds: Vec<String>,
     toolKeys: Vec<String>,
-) -> Result<RemoteSyncResultDto, String> {
+) -> Result<RemoteSyncResultDto, CommandError> {
     let store = store.inner().clone();
+    let op = ops.start(OperationKind::RemotePush, hostId.clone());
     tauri::async_runtime::spawn_blocking(move || {
-        let host = store
-            .get_remote_host_by_id(&hostId)
-            .map_err(format_anyhow_error)?
-            .ok_or_else(|| format!("remote host not found: {}", hostId))?;
-
-        store
-            .update_remote_host_sync_status(&hostId, "syncing", None)
-            .ok();
+        op.run(|op| {
+            let host = store
+                .get_remote_host_by_id(&hostId)
+                .map_err(CommandError::from)?
+                .ok_or_else(|| format!("remote host not found: {}", hostId))?;
 
-        let sess = remote_sync::create_ssh_session(
-            &host.host,
-            host.port as u16,
-            &host.username,
-            &host.auth_method,
-            host.key_path.as_deref(),
-        )
-        .map_err(|e| {
             store
-                .update_remote_host_sync_status(&hostId, "error", None)
+                .update_remote_host_sync_status(&hostId, "syncing", None)
                 .ok();
-            format_anyhow_error(e)
-        })?;
 
-        let all_skills = store.list_skills().map_err(format_anyhow_error)?;
-        let skill_ids_set: std::collections::HashSet<&str> =
-            skillIds.iter().map(|s| s.as_str()).collect();
-        let skill_infos: Vec<remote_sync::RemoteSkillInfo> = all_skills
-            .into_iter()
-            .filter(|s| skill_ids_set.contains(s.id.as_str()))
-            .map(|s| remote_sync::RemoteSkillInfo {
-                name: s.name,
-                local_path: std::path::PathBuf::from(s.central_path),
-                source_type: s.source_type,
-                source_ref: s.source_ref,
-            })
-            .collect();
+            let sess = open_host_session(&host).map_err(|e| {
+                store
+                    .update_remote_host_sync_status(&hostId, "error", None)
+                    .ok();
+                skill_events::remote_sync_finished(&hostId, &[], Some(&format!("{:#}", e)));
+                CommandError::from(e)
+            })?;
+
+            let all_skills = store.list_skills().map_err(CommandError::from)?;
+            let skill_ids_set: std::collections::HashSet<&str> =
+                skillIds.iter().map(|s| s.as_str()).collect();
+            let skills: Vec<_> = all_skills
+                .into_iter()
+                .filter(|s| skill_ids_set.contains(s.id.as_str()))
+                .filter(|s| !quarantine::is_quarantined(s))
+                .collect();
+            let skill_infos: Vec<remote_sync::RemoteSkillInfo> = skills
+                .iter()
+                .map(|s| remote_skill_info(&store, s))
+                .collect();
 
-        let synced = remote_sync::sync_all_skills_to_remote(&sess, &skill_infos, &toolKeys)
+            let synced = remote_sync::sync_all_skills_to_remote_with_progress(
+                &sess,
+                Some(&|| remote_sync::fresh_host_session(&host)),
+                &skill_infos,
+                &toolKeys,
+                &|done, total| op.progress(done, total),
+            )
             .map_err(|e| {
                 store
                     .update_remote_host_sync_status(&hostId, "error", None)
                     .ok();
-                format_anyhow_error(e)
+                skill_events::remote_sync_finished(&hostId, &[], Some(&format!("{:#}", e)));
+                CommandError::from(e)
             })?;
 
-        store
-            .update_remote_host_sync_status(&hostId, "ok", Some(now_ms()))
-            .ok();
+            store
+                .update_remote_host_sync_status(&hostId, "ok", Some(now_ms()))
+                .ok();
+            skill_events::remote_sync_finished(&hostId, &synced, None);
+            for skill in skills.iter().filter(|s| synced.contains(&s.name)) {
+                record_remote_deployment(&store, &hostId, skill, &toolKeys, true).ok();
+            }
 
-        Ok(RemoteSyncResultDto {
-            syncedSkills: synced,
+            Ok(RemoteSyncResultDto {
+                syncedSkills: synced,
+            })
         })
     })
     .await
-    .map_err(|err| err.to_string())?
+    .map_err(CommandError::from)?
 }
 
 // ── Custom Target Commands ──────────────────────────────────────────────
@@ -1467,12 +4607,14 @@ pub struct CustomTargetDto {
     #[serde(rename = "remote_host_id")]
     pub remote_host_id: Option<String>,
     pub created_at: i64,
+    /// Variables (e.g. `project`) that must be supplied when syncing.
+    pub variables: Vec<String>,
 }
 
 #[tauri::command]
 pub async fn list_custom_targets(
     store: State<'_, SkillStore>,
-) -> Result<Vec<CustomTargetDto>, String> {
+) -> Result<Vec<CustomTargetDto>, CommandError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
         let targets = store.list_custom_targets()?;
@@ -1482,6 +4624,7 @@ pub async fn list_custom_targets(
                 .map(|t| CustomTargetDto {
                     id: t.id,
                     label: t.label,
+                    variables: path_variables(&t.path),
                     path: t.path,
                     remote_host_id: t.remote_host_id,
                     created_at: t.created_at,
@@ -1490,8 +4633,8 @@ pub async fn list_custom_targets(
         )
     })
     .await
-    .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -1501,10 +4644,14 @@ pub async fn add_custom_target(
     label: String,
     path: String,
     remoteHostId: Option<String>,
-) -> Result<CustomTargetDto, String> {
+) -> Result<CustomTargetDto, CommandError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
+        let variables = path_variables(&path);
         let canonical = if remoteHostId.is_some() {
+            if !variables.is_empty() {
+                anyhow::bail!("path variables are only supported for local custom targets");
+            }
             // Remote: path is a remote path, just validate it looks absolute-ish
             if !path.starts_with('/') {
                 anyhow::bail!("remote custom target path must be absolute (start with /)");
@@ -1516,6 +4663,12 @@ pub async fn add_custom_target(
                     .ok_or_else(|| anyhow::anyhow!("remote host not found"))?;
             }
             path.clone()
+        } else if !variables.is_empty() {
+            // Local template: resolved (and created) per sync.
+            if !path.trim().starts_with('{') && !expand_home_path(&path)?.is_absolute() {
+                anyhow::bail!("custom target path must be absolute");
+            }
+            path.trim().to_string()
         } else {
             // Local: expand ~ and ensure directory exists
             let expanded = expand_home_path(&path)?;
@@ -1542,11 +4695,12 @@ pub async fn add_custom_target(
             path: canonical,
             remote_host_id: remoteHostId,
             created_at: record.created_at,
+            variables,
         })
     })
     .await
-    .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -1554,159 +4708,200 @@ pub async fn add_custom_target(
 pub async fn delete_custom_target(
     store: State<'_, SkillStore>,
     targetId: String,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
-        let tool_key = format!("custom:{}", targetId);
-        // Remove filesystem targets for all skills synced to this custom target.
+        // Remove filesystem targets for all skills synced to this custom target
+        // (every expanded root, for templated targets).
         let all_skills = store.list_skills()?;
         for skill in &all_skills {
-            if let Some(target) = store.get_skill_target(&skill.id, &tool_key)? {
-                let _ = remove_path_any(&target.target_path);
+            for target in store.list_skill_targets(&skill.id)? {
+                if custom_target_id(&target.tool) == Some(targetId.as_str()) {
+                    let _ = remove_path_any(&target.target_path);
+                }
             }
         }
         store.delete_custom_target(&targetId)?;
         Ok::<_, anyhow::Error>(())
     })
     .await
-    .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
-#[allow(non_snake_case)]
+#[allow(non_snake_case, clippy::too_many_arguments)]
 pub async fn sync_skill_to_custom_target(
     store: State<'_, SkillStore>,
+    ops: State<'_, OperationRegistry>,
+    undo: State<'_, UndoStack>,
     sourcePath: String,
     skillId: String,
     customTargetId: String,
     name: String,
     overwrite: Option<bool>,
-) -> Result<SyncResultDto, String> {
+    variables: Option<HashMap<String, String>>,
+) -> Result<SyncResultDto, CommandError> {
     let store = store.inner().clone();
+    let undo = undo.inner().clone();
+    let op = ops.start(
+        OperationKind::Sync,
+        format!("{} -> custom:{}", name, customTargetId),
+    );
     tauri::async_runtime::spawn_blocking(move || {
-        let ct = store
-            .get_custom_target_by_id(&customTargetId)?
-            .ok_or_else(|| anyhow::anyhow!("custom target not found"))?;
+        op.run(|_| {
+            let ct = store
+                .get_custom_target_by_id(&customTargetId)?
+                .ok_or_else(|| anyhow::anyhow!("custom target not found"))?;
+            if let Some(skill) = store.get_skill_by_id(&skillId)? {
+                quarantine::ensure_approved(&skill)?;
+            }
 
-        let tool_key = format!("custom:{}", customTargetId);
+            let (target_root, tool_key) = resolve_custom_target(&ct, variables.as_ref())?;
 
-        if let Some(ref remote_host_id) = ct.remote_host_id {
-            // ── Remote sync via SSH (symlink from central) ──────────
-            let host = store
-                .get_remote_host_by_id(remote_host_id)?
-                .ok_or_else(|| anyhow::anyhow!("remote host not found"))?;
-
-            let sess = crate::core::remote_sync::create_ssh_session(
-                &host.host,
-                host.port as u16,
-                &host.username,
-                &host.auth_method,
-                host.key_path.as_deref(),
-            )?;
+            if let Some(ref remote_host_id) = ct.remote_host_id {
+                // ── Remote sync via SSH (symlink from central) ──────────
+                let host = store
+                    .get_remote_host_by_id(remote_host_id)?
+                    .ok_or_else(|| anyhow::anyhow!("remote host not found"))?;
 
-            let skill = store
-                .get_skill_by_id(&skillId)?
-                .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+                let sess = open_host_session(&host)?;
 
-            let info = remote_sync::RemoteSkillInfo {
-                name: name.clone(),
-                local_path: std::path::PathBuf::from(&sourcePath),
-                source_type: skill.source_type.clone(),
-                source_ref: skill.source_ref.clone(),
-            };
+                let skill = store
+                    .get_skill_by_id(&skillId)?
+                    .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
 
-            // 1. Ensure skill exists in VM central (~/.skillshub/<name>/)
-            let home = crate::core::remote_sync::ssh_exec(&sess, "echo $HOME")?;
-            let home = home.trim();
-            crate::core::remote_sync::ensure_skill_on_remote(&sess, &info, home)?;
-
-            // 2. Symlink from central to custom target path
-            let abs_central = format!("{}/.skillshub/{}", home, name);
-            let remote_dest = format!("{}/{}", ct.path.trim_end_matches('/'), name);
-            crate::core::remote_sync::create_remote_symlink(&sess, &abs_central, &remote_dest)?;
-
-            let record = SkillTargetRecord {
-                id: Uuid::new_v4().to_string(),
-                skill_id: skillId.clone(),
-                tool: tool_key,
-                target_path: remote_dest.clone(),
-                mode: "symlink".to_string(),
-                status: "ok".to_string(),
-                last_error: None,
-                synced_at: Some(now_ms()),
-            };
-            store.upsert_skill_target(&record)?;
+                let target_name = target_name_for(&store, &skill);
+                let info = remote_sync::RemoteSkillInfo {
+                    name: name.clone(),
+                    target_name: target_name.clone(),
+                    local_path: std::path::PathBuf::from(&sourcePath),
+                    source_type: skill.source_type.clone(),
+                    source_ref: skill.source_ref.clone(),
+                };
 
-            Ok::<_, anyhow::Error>(SyncResultDto {
-                mode_used: "symlink".to_string(),
-                target_path: remote_dest,
-            })
-        } else {
-            // ── Local sync ──────────────────────────────────────────
-            let target_root = std::path::PathBuf::from(&ct.path);
-            let target = target_root.join(&name);
-            let overwrite = overwrite.unwrap_or(false);
-            let result = crate::core::sync_engine::sync_dir_hybrid_with_overwrite(
-                sourcePath.as_ref(),
-                &target,
-                overwrite,
-            )
-            .map_err(|err| {
-                let msg = err.to_string();
-                if msg.contains("target already exists") {
-                    anyhow::anyhow!("TARGET_EXISTS|{}", target.to_string_lossy())
-                } else {
-                    anyhow::anyhow!(msg)
-                }
-            })?;
+                // 1. Ensure skill exists in VM central (~/.skillshub/<name>/)
+                let home = crate::core::remote_sync::ssh_exec(&sess, "echo $HOME")?;
+                let home = home.trim();
+                crate::core::remote_sync::ensure_skill_on_remote(&sess, &info, home)?;
+
+                // 2. Symlink from central to custom target path
+                let abs_central = format!("{}/.skillshub/{}", home, name);
+                let remote_dest = format!("{}/{}", ct.path.trim_end_matches('/'), target_name);
+                crate::core::remote_sync::create_remote_symlink(&sess, &abs_central, &remote_dest)?;
+                record_remote_deployment(&store, remote_host_id, &skill, &[], true)?;
+
+                let record = SkillTargetRecord {
+                    id: Uuid::new_v4().to_string(),
+                    skill_id: skillId.clone(),
+                    tool: tool_key,
+                    target_path: remote_dest.clone(),
+                    mode: "symlink".to_string(),
+                    status: "ok".to_string(),
+                    last_error: None,
+                    synced_at: Some(now_ms()),
+                    content_hash: skill.content_hash.clone(),
+                };
+                store.upsert_skill_target(&record)?;
 
-            let record = SkillTargetRecord {
-                id: Uuid::new_v4().to_string(),
-                skill_id: skillId.clone(),
-                tool: tool_key,
-                target_path: result.target_path.to_string_lossy().to_string(),
-                mode: match result.mode_used {
-                    SyncMode::Auto => "auto",
-                    SyncMode::Symlink => "symlink",
-                    SyncMode::Junction => "junction",
-                    SyncMode::Copy => "copy",
-                }
-                .to_string(),
-                status: "ok".to_string(),
-                last_error: None,
-                synced_at: Some(now_ms()),
-            };
-            store.upsert_skill_target(&record)?;
-
-            Ok::<_, anyhow::Error>(SyncResultDto {
-                mode_used: match result.mode_used {
-                    SyncMode::Auto => "auto",
-                    SyncMode::Symlink => "symlink",
-                    SyncMode::Junction => "junction",
-                    SyncMode::Copy => "copy",
+                Ok::<_, anyhow::Error>(SyncResultDto {
+                    mode_used: "symlink".to_string(),
+                    mode_reason: None,
+                    target_path: remote_dest,
+                    warnings: Vec::new(),
+                })
+            } else {
+                // ── Local sync ──────────────────────────────────────────
+                if target_root != ct.path {
+                    std::fs::create_dir_all(&target_root)
+                        .with_context(|| format!("failed to create directory {:?}", target_root))?;
+                    if let Some(project) = variables.as_ref().and_then(|v| v.get(PROJECT_VARIABLE))
+                    {
+                        remember_project(&store, project)?;
+                    }
                 }
-                .to_string(),
-                target_path: result.target_path.to_string_lossy().to_string(),
-            })
-        }
+                let target_root = std::path::PathBuf::from(&target_root);
+                let skill = store.get_skill_by_id(&skillId)?;
+                let target_name = match &skill {
+                    Some(skill) => target_name_for(&store, skill),
+                    None => name,
+                };
+                let target = target_root.join(&target_name);
+                let overwrite = overwrite.unwrap_or(false);
+                let previous_rows =
+                    existing_target_rows(&store, &skillId, std::slice::from_ref(&tool_key))?;
+                let mut warnings = Vec::new();
+                let (result, held) = sync_with_undo(
+                    &store,
+                    &undo,
+                    sourcePath.as_ref(),
+                    &target,
+                    overwrite,
+                    || {
+                        let (outcome, unresolved) = sync_with_cross_refs(
+                            &store,
+                            &tool_key,
+                            sourcePath.as_ref(),
+                            &target,
+                            overwrite,
+                        )?;
+                        warnings = unresolved;
+                        Ok(outcome)
+                    },
+                )?;
+
+                let record = SkillTargetRecord {
+                    id: Uuid::new_v4().to_string(),
+                    skill_id: skillId.clone(),
+                    tool: tool_key.clone(),
+                    target_path: result.target_path.to_string_lossy().to_string(),
+                    mode: sync_plan::mode_name(&result.mode_used).to_string(),
+                    status: "ok".to_string(),
+                    last_error: None,
+                    synced_at: Some(now_ms()),
+                    content_hash: skill.and_then(|s| s.content_hash),
+                };
+                store.upsert_skill_target(&record)?;
+                undo.push(
+                    UndoKind::Sync,
+                    &skillId,
+                    vec![tool_key],
+                    &result.target_path,
+                    held,
+                    previous_rows,
+                );
+
+                Ok::<_, anyhow::Error>(SyncResultDto {
+                    mode_used: sync_plan::mode_name(&result.mode_used).to_string(),
+                    mode_reason: result.mode_reason.clone(),
+                    target_path: result.target_path.to_string_lossy().to_string(),
+                    warnings,
+                })
+            }
+        })
     })
     .await
-    .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
 #[allow(non_snake_case)]
 pub async fn unsync_skill_from_custom_target(
     store: State<'_, SkillStore>,
+    undo: State<'_, UndoStack>,
     skillId: String,
     customTargetId: String,
-) -> Result<(), String> {
+    variables: Option<HashMap<String, String>>,
+) -> Result<(), CommandError> {
     let store = store.inner().clone();
+    let undo = undo.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
         let ct = store.get_custom_target_by_id(&customTargetId)?;
-        let tool_key = format!("custom:{}", customTargetId);
+        let tool_key = match &ct {
+            Some(ct) => resolve_custom_target(ct, variables.as_ref())?.1,
+            None => custom_target_key(&customTargetId, None),
+        };
 
         if let Some(target) = store.get_skill_target(&skillId, &tool_key)? {
             if let Some(ct) = ct {
@@ -1715,20 +4910,23 @@ pub async fn unsync_skill_from_custom_target(
                     let host = store
                         .get_remote_host_by_id(remote_host_id)?
                         .ok_or_else(|| anyhow::anyhow!("remote host not found"))?;
-                    let sess = crate::core::remote_sync::create_ssh_session(
-                        &host.host,
-                        host.port as u16,
-                        &host.username,
-                        &host.auth_method,
-                        host.key_path.as_deref(),
-                    )?;
+                    let sess = open_host_session(&host)?;
                     crate::core::remote_sync::ssh_exec(
                         &sess,
                         &format!("rm -rf '{}'", target.target_path),
                     )?;
                 } else {
-                    // ── Local: remove path ───────────────────────────
-                    remove_path_any(&target.target_path).map_err(anyhow::Error::msg)?;
+                    // ── Local: hold the removed content for undo ─────
+                    let target_path = std::path::PathBuf::from(&target.target_path);
+                    let held = undo.stash(&target_path)?;
+                    undo.push(
+                        UndoKind::Unsync,
+                        &skillId,
+                        vec![tool_key.clone()],
+                        &target_path,
+                        held,
+                        vec![target.clone()],
+                    );
                 }
             } else {
                 // custom target was deleted but skill_target remains; just clean up local
@@ -1739,8 +4937,89 @@ pub async fn unsync_skill_from_custom_target(
         Ok::<_, anyhow::Error>(())
     })
     .await
-    .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn list_recent_projects(
+    store: State<'_, SkillStore>,
+) -> Result<Vec<String>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::core::target_variables::list_recent_projects(&store)
+    })
+    .await
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn forget_recent_project(
+    store: State<'_, SkillStore>,
+    project: String,
+) -> Result<Vec<String>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::core::target_variables::forget_project(&store, &project)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn get_workspace_roots(
+    store: State<'_, SkillStore>,
+) -> Result<Vec<String>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::core::project_scan::get_workspace_roots(&store)
+    })
+    .await
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn set_workspace_roots(
+    store: State<'_, SkillStore>,
+    roots: Vec<String>,
+) -> Result<Vec<String>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::core::project_scan::set_workspace_roots(&store, &roots)
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+/// Scan `rootDirs` (the saved workspace roots when omitted) for projects with
+/// agent config dirs and keep the result as the candidate project list.
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn scan_projects(
+    store: State<'_, SkillStore>,
+    rootDirs: Option<Vec<String>>,
+) -> Result<Vec<DetectedProject>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::core::project_scan::scan_and_store(&store, &rootDirs.unwrap_or_default())
+    })
+    .await
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn list_detected_projects(
+    store: State<'_, SkillStore>,
+) -> Result<Vec<DetectedProject>, CommandError> {
+    let store = store.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::core::project_scan::list_detected_projects(&store)
+    })
+    .await
+    .map_err(CommandError::from)
 }
 
 // ── Remote Directory Browsing ───────────────────────────────────────────
@@ -1765,20 +5044,14 @@ pub async fn browse_remote_directory(
     store: State<'_, SkillStore>,
     hostId: String,
     path: Option<String>,
-) -> Result<RemoteBrowseResult, String> {
+) -> Result<RemoteBrowseResult, CommandError> {
     let store = store.inner().clone();
     tauri::async_runtime::spawn_blocking(move || {
         let host = store
             .get_remote_host_by_id(&hostId)?
             .ok_or_else(|| anyhow::anyhow!("remote host not found"))?;
 
-        let sess = crate::core::remote_sync::create_ssh_session(
-            &host.host,
-            host.port as u16,
-            &host.username,
-            &host.auth_method,
-            host.key_path.as_deref(),
-        )?;
+        let sess = open_host_session(&host)?;
 
         // Resolve path: default to ~ (home), resolve ~ prefix
         let raw_path = path.unwrap_or_else(|| "~".to_string());
@@ -1816,8 +5089,8 @@ pub async fn browse_remote_directory(
         })
     })
     .await
-    .map_err(|err| err.to_string())?
-    .map_err(format_anyhow_error)
+    .map_err(CommandError::from)?
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -1826,10 +5099,11 @@ pub fn is_homebrew_installed() -> bool {
 }
 
 #[tauri::command]
-pub async fn brew_upgrade_cask() -> Result<String, String> {
+pub async fn brew_upgrade_cask() -> Result<String, CommandError> {
     tauri::async_runtime::spawn_blocking(crate::core::update_checker::brew_upgrade_cask)
         .await
-        .map_err(|err| err.to_string())?
+        .map_err(CommandError::from)?
+        .map_err(CommandError::from)
 }
 
 #[cfg(test)]
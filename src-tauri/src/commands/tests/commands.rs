@@ -1,4 +1,5 @@
 use super::*;
+use crate::core::errors::target_exists;
 use crate::core::skill_store::SkillRecord;
 
 fn make_store() -> (tempfile::TempDir, SkillStore) {
@@ -9,24 +10,38 @@ fn make_store() -> (tempfile::TempDir, SkillStore) {
 }
 
 #[test]
-fn format_anyhow_error_passthrough_prefixes() {
-    let err = anyhow::anyhow!("MULTI_SKILLS|abc");
-    assert_eq!(format_anyhow_error(err), "MULTI_SKILLS|abc");
+fn command_error_keeps_code_and_payload_through_context() {
+    let err = target_exists("/tmp/tools/pdf").context("sync pdf");
+    let error = CommandError::from(err);
+    assert_eq!(error.code, ErrorCode::TargetExists);
+    assert_eq!(error.message, "target already exists: /tmp/tools/pdf");
+    assert_eq!(
+        serde_json::to_value(&error).unwrap(),
+        serde_json::json!({
+            "code": "TARGET_EXISTS",
+            "message": "target already exists: /tmp/tools/pdf",
+            "payload": { "path": "/tmp/tools/pdf" },
+        })
+    );
+
+    let error = CommandError::from(anyhow::anyhow!("disk full"));
+    assert_eq!(error.code, ErrorCode::Other);
+    assert_eq!(error.payload, None);
 }
 
 #[test]
-fn format_anyhow_error_redacts_clone_temp_path() {
+fn command_error_redacts_clone_temp_path() {
     let err = anyhow::anyhow!("clone https://example.com/a/b into /tmp/skills-hub-git-123");
-    let msg = format_anyhow_error(err);
-    assert!(msg.contains("已省略临时目录"));
+    let msg = CommandError::from(err).message;
+    assert!(msg.contains(tr(Msg::CloneTempDirOmitted)));
     assert!(!msg.contains("/tmp/skills-hub-git-123"));
 }
 
 #[test]
-fn format_anyhow_error_github_hint_auth() {
+fn command_error_github_hint_auth() {
     let err = anyhow::anyhow!("git clone https://github.com/a/b failed: authentication failed");
-    let msg = format_anyhow_error(err);
-    assert!(msg.contains("无法访问该仓库"));
+    let msg = CommandError::from(err).message;
+    assert!(msg.starts_with(tr(Msg::GithubAuth)));
 }
 
 #[test]
@@ -103,6 +118,7 @@ fn get_managed_skills_impl_maps_targets() {
         status: "ok".to_string(),
         last_error: None,
         synced_at: None,
+        content_hash: None,
     };
     store.upsert_skill_target(&target).unwrap();
 
@@ -0,0 +1,153 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::now_ms;
+use super::skill_store::SkillStore;
+
+pub const ACCESS_TOKENS_KEY: &str = "access_tokens";
+
+/// What an automation client may do with its token.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Scope {
+    /// List skills and where they are synced.
+    Read,
+    /// Install and update skills in the central repo.
+    Install,
+    /// Sync skills into local tools.
+    Sync,
+    /// Push skills to remote hosts.
+    Remote,
+}
+
+impl Scope {
+    pub const ALL: [Scope; 4] = [Scope::Read, Scope::Install, Scope::Sync, Scope::Remote];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Scope::Read => "read",
+            Scope::Install => "install",
+            Scope::Sync => "sync",
+            Scope::Remote => "remote",
+        }
+    }
+
+    pub fn parse(name: &str) -> Result<Self> {
+        Scope::ALL
+            .into_iter()
+            .find(|scope| scope.name() == name)
+            .ok_or_else(|| anyhow::anyhow!("unknown scope {} (read, install, sync, remote)", name))
+    }
+}
+
+/// A token for an automation client. Only the SHA-256 of the secret is
+/// stored; the secret itself is shown once when the token is created.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccessToken {
+    pub id: String,
+    pub name: String,
+    pub scopes: Vec<Scope>,
+    pub secret_sha256: String,
+    pub created_at: i64,
+}
+
+pub fn list_access_tokens(store: &SkillStore) -> Vec<AccessToken> {
+    store
+        .get_setting(ACCESS_TOKENS_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn save_access_tokens(store: &SkillStore, tokens: &[AccessToken]) -> Result<()> {
+    let raw = serde_json::to_string(tokens).context("serialize access tokens")?;
+    store.set_setting(ACCESS_TOKENS_KEY, &raw)
+}
+
+fn secret_sha256(secret: &str) -> String {
+    hex::encode(Sha256::digest(secret.as_bytes()))
+}
+
+/// Add a token named `name` with `scopes` and return it with its secret.
+pub fn create_access_token(
+    store: &SkillStore,
+    name: &str,
+    scopes: &[Scope],
+) -> Result<(AccessToken, String)> {
+    let name = name.trim();
+    if name.is_empty() {
+        anyhow::bail!("a token needs a name");
+    }
+    if scopes.is_empty() {
+        anyhow::bail!("a token needs at least one scope");
+    }
+    let mut tokens = list_access_tokens(store);
+    if tokens.iter().any(|t| t.name == name) {
+        anyhow::bail!("a token named {} already exists", name);
+    }
+    let mut unique: Vec<Scope> = Vec::new();
+    for scope in scopes {
+        if !unique.contains(scope) {
+            unique.push(*scope);
+        }
+    }
+    let secret = format!("shub_{}", uuid::Uuid::new_v4().simple());
+    let token = AccessToken {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: name.to_string(),
+        scopes: unique,
+        secret_sha256: secret_sha256(&secret),
+        created_at: now_ms(),
+    };
+    tokens.push(token.clone());
+    save_access_tokens(store, &tokens)?;
+    Ok((token, secret))
+}
+
+/// Remove the token with id or name `wanted`.
+pub fn revoke_access_token(store: &SkillStore, wanted: &str) -> Result<AccessToken> {
+    let mut tokens = list_access_tokens(store);
+    let index = tokens
+        .iter()
+        .position(|t| t.id == wanted || t.name == wanted)
+        .ok_or_else(|| anyhow::anyhow!("no token named {}", wanted))?;
+    let token = tokens.remove(index);
+    save_access_tokens(store, &tokens)?;
+    Ok(token)
+}
+
+/// Check that `secret` grants every scope in `needed`. Entry points call
+/// this before running a command. While no token exists nothing is
+/// restricted; after the first one is created every caller has to present
+/// a token.
+pub fn authorize(store: &SkillStore, secret: Option<&str>, needed: &[Scope]) -> Result<()> {
+    let tokens = list_access_tokens(store);
+    if tokens.is_empty() {
+        return Ok(());
+    }
+    let secret = secret.ok_or_else(|| anyhow::anyhow!("an access token is required"))?;
+    let hash = secret_sha256(secret);
+    let token = tokens
+        .iter()
+        .find(|t| t.secret_sha256 == hash)
+        .ok_or_else(|| anyhow::anyhow!("unknown access token"))?;
+    let missing: Vec<&str> = needed
+        .iter()
+        .filter(|scope| !token.scopes.contains(scope))
+        .map(|scope| scope.name())
+        .collect();
+    if !missing.is_empty() {
+        anyhow::bail!(
+            "token {} lacks the {} scope",
+            token.name,
+            missing.join(", ")
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "tests/access_tokens.rs"]
+mod tests;
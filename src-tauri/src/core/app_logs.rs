@@ -0,0 +1,164 @@
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use log::{Level, LevelFilter};
+use serde::Serialize;
+
+use super::app_paths::AppPaths;
+use super::skill_store::SkillStore;
+
+pub const LOG_LEVEL_KEY: &str = "log_level";
+pub const LOG_DIR_NAME: &str = "logs";
+/// The active file is `skills-hub.log`; rotated ones get a timestamp suffix.
+pub const LOG_FILE_NAME: &str = "skills-hub";
+pub const MAX_LOG_FILE_BYTES: u128 = 1_000_000;
+/// Rotated files kept next to the active one.
+pub const KEEP_LOG_FILES: usize = 5;
+const DEFAULT_LOG_LEVEL: LevelFilter = LevelFilter::Info;
+const MAX_RECENT_LOGS: usize = 5_000;
+
+/// One record from the log files. Continuation lines of a multi-line
+/// message are folded into `message`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct LogEntry {
+    /// `YYYY-MM-DD HH:MM:SS`, UTC.
+    pub timestamp: String,
+    /// Lowercase: `error`, `warn`, `info`, `debug` or `trace`.
+    pub level: String,
+    /// The module that logged it.
+    pub target: String,
+    pub message: String,
+}
+
+/// `logs/` under the app data dir, where the rotating log files live.
+pub fn app_log_dir(paths: &AppPaths) -> PathBuf {
+    paths.data_dir.join(LOG_DIR_NAME)
+}
+
+pub fn parse_level(raw: &str) -> Result<LevelFilter> {
+    LevelFilter::from_str(raw.trim()).map_err(|_| {
+        anyhow::anyhow!(
+            "unknown log level {:?}; expected off, error, warn, info, debug or trace",
+            raw
+        )
+    })
+}
+
+pub fn get_log_level(store: &SkillStore) -> LevelFilter {
+    store
+        .get_setting(LOG_LEVEL_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| parse_level(&raw).ok())
+        .unwrap_or(DEFAULT_LOG_LEVEL)
+}
+
+/// Persist `level` and apply it to the running logger.
+pub fn set_log_level(store: &SkillStore, level: &str) -> Result<LevelFilter> {
+    let level = parse_level(level)?;
+    store.set_setting(LOG_LEVEL_KEY, &level_name(level))?;
+    log::set_max_level(level);
+    Ok(level)
+}
+
+/// Apply the saved level; the logger itself is built permissive at startup.
+pub fn load_log_level(store: &SkillStore) {
+    log::set_max_level(get_log_level(store));
+}
+
+pub fn level_name(level: LevelFilter) -> String {
+    level.as_str().to_ascii_lowercase()
+}
+
+/// The last `lines` records at `min_level` or more severe, oldest first,
+/// across the active and rotated log files in `dir`.
+pub fn read_recent_logs(dir: &Path, lines: usize, min_level: Level) -> Result<Vec<LogEntry>> {
+    let lines = lines.min(MAX_RECENT_LOGS);
+    let mut entries: Vec<LogEntry> = Vec::new();
+    for file in log_files(dir)? {
+        let text = match std::fs::read(&file) {
+            Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+            Err(err) => {
+                log::warn!("failed to read log file {:?}: {}", file, err);
+                continue;
+            }
+        };
+        entries.extend(parse_log(&text));
+    }
+    entries.retain(|entry| {
+        Level::from_str(&entry.level)
+            .map(|level| level <= min_level)
+            .unwrap_or(false)
+    });
+    let skip = entries.len().saturating_sub(lines);
+    Ok(entries.split_off(skip))
+}
+
+/// Log files in `dir`, oldest first; the active file is always last.
+fn log_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let active = format!("{}.log", LOG_FILE_NAME);
+    let rotated_prefix = format!("{}_", LOG_FILE_NAME);
+    let mut rotated = Vec::new();
+    let mut current = None;
+    for entry in
+        std::fs::read_dir(dir).with_context(|| format!("failed to read log dir {:?}", dir))?
+    {
+        let path = entry?.path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if name == active {
+            current = Some(path);
+        } else if name.starts_with(&rotated_prefix) && name.ends_with(".log") {
+            rotated.push(path);
+        }
+    }
+    // Rotated names end in a sortable timestamp.
+    rotated.sort();
+    rotated.extend(current);
+    Ok(rotated)
+}
+
+/// Parse text written in the log plugin's desktop format,
+/// `[date][time][target][LEVEL] message`.
+pub fn parse_log(text: &str) -> Vec<LogEntry> {
+    let mut entries: Vec<LogEntry> = Vec::new();
+    for line in text.lines() {
+        match parse_record(line) {
+            Some(entry) => entries.push(entry),
+            None => {
+                if let Some(last) = entries.last_mut() {
+                    last.message.push('\n');
+                    last.message.push_str(line);
+                }
+            }
+        }
+    }
+    entries
+}
+
+fn parse_record(line: &str) -> Option<LogEntry> {
+    let mut fields = Vec::with_capacity(4);
+    let mut rest = line;
+    for _ in 0..4 {
+        let inner = rest.strip_prefix('[')?;
+        let end = inner.find(']')?;
+        fields.push(&inner[..end]);
+        rest = &inner[end + 1..];
+    }
+    let level = Level::from_str(fields[3]).ok()?;
+    Some(LogEntry {
+        timestamp: format!("{} {}", fields[0], fields[1]),
+        level: level.as_str().to_ascii_lowercase(),
+        target: fields[2].to_string(),
+        message: rest.strip_prefix(' ').unwrap_or(rest).to_string(),
+    })
+}
+
+#[cfg(test)]
+#[path = "tests/app_logs.rs"]
+mod tests;
@@ -0,0 +1,53 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use tauri::Manager;
+
+/// The bundle identifier from `tauri.conf.json`; it names the app's folders.
+pub const APP_IDENTIFIER: &str = "com.qufei1993.skillshub";
+
+/// Per-user folders for the app's data, cache and config. Core services take
+/// these rather than an `AppHandle`, so the CLI can use them without a
+/// Tauri runtime.
+#[derive(Clone, Debug)]
+pub struct AppPaths {
+    pub data_dir: PathBuf,
+    pub cache_dir: PathBuf,
+    pub config_dir: PathBuf,
+}
+
+impl AppPaths {
+    /// The folders Tauri resolved for the running app.
+    pub fn for_app<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Result<Self> {
+        let path = app.path();
+        Ok(Self {
+            data_dir: path
+                .app_data_dir()
+                .context("failed to resolve app data dir")?,
+            cache_dir: path
+                .app_cache_dir()
+                .context("failed to resolve app cache dir")?,
+            config_dir: path
+                .app_config_dir()
+                .context("failed to resolve app config dir")?,
+        })
+    }
+
+    /// The same folders without a running app: the platform's data, cache
+    /// and config dirs joined with `identifier`, as Tauri resolves them.
+    pub fn for_identifier(identifier: &str) -> Result<Self> {
+        let app_dir = |base: Option<PathBuf>, kind: &str| {
+            base.map(|dir| dir.join(identifier))
+                .ok_or_else(|| anyhow::anyhow!("failed to resolve app {} dir", kind))
+        };
+        Ok(Self {
+            data_dir: app_dir(dirs::data_dir(), "data")?,
+            cache_dir: app_dir(dirs::cache_dir(), "cache")?,
+            config_dir: app_dir(dirs::config_dir(), "config")?,
+        })
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/app_paths.rs"]
+mod tests;
@@ -0,0 +1,82 @@
+use std::io::{Read, Seek};
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+/// Whether `path` is a plain relative path that cannot leave the directory it
+/// is joined onto: no root, drive prefix, `.` or `..` components.
+/// Backslashes count as separators so Windows-made archives are checked too.
+pub fn is_safe_relative_path(path: &str) -> bool {
+    let path = path.replace('\\', "/");
+    let path = Path::new(&path);
+    !path.as_os_str().is_empty()
+        && !path.is_absolute()
+        && path.components().all(|c| matches!(c, Component::Normal(_)))
+}
+
+/// Where archive entry `name` goes under `dest`, or an error when the entry
+/// would land outside it. `dest` must exist. The deepest existing parent is
+/// resolved before any directory is created, so a symlink already inside
+/// `dest` cannot redirect the write or the directories made for it.
+pub fn safe_entry_path(dest: &Path, name: &str) -> Result<PathBuf> {
+    let rel = name.trim_end_matches(['/', '\\']);
+    if !is_safe_relative_path(rel) {
+        anyhow::bail!("archive entry escapes the extract dir: {}", name);
+    }
+    let out = dest.join(rel.replace('\\', "/"));
+    let root = dest
+        .canonicalize()
+        .with_context(|| format!("resolve extract dir {:?}", dest))?;
+    let parent = out
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("invalid archive entry: {}", name))?;
+    let mut existing = parent;
+    while std::fs::symlink_metadata(existing).is_err() {
+        existing = existing.parent().unwrap_or(dest);
+    }
+    let resolved = existing
+        .canonicalize()
+        .with_context(|| format!("resolve dir {:?}", existing))?;
+    if !resolved.starts_with(&root) {
+        anyhow::bail!("archive entry escapes the extract dir: {}", name);
+    }
+    std::fs::create_dir_all(parent).with_context(|| format!("create dir {:?}", parent))?;
+    if std::fs::symlink_metadata(&out).is_ok_and(|m| m.file_type().is_symlink()) {
+        anyhow::bail!("archive entry would overwrite a symlink: {}", name);
+    }
+    Ok(out)
+}
+
+/// Extract every regular file of `archive` into `dest`. Symlink entries are
+/// skipped and entries accepted by `skip` are left out. Returns the number of
+/// files written.
+pub fn extract_zip<R: Read + Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    dest: &Path,
+    skip: impl Fn(&str) -> bool,
+) -> Result<usize> {
+    std::fs::create_dir_all(dest).with_context(|| format!("create extract dir {:?}", dest))?;
+    let mut written = 0;
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i).context("read zip entry")?;
+        let name = file.name().to_string();
+        if file.is_dir() || skip(&name) {
+            continue;
+        }
+        if file.is_symlink() {
+            log::warn!("skipping symlink entry in archive: {}", name);
+            continue;
+        }
+        let out_path = safe_entry_path(dest, &name)?;
+        let mut out_file = std::fs::File::create(&out_path)
+            .with_context(|| format!("create file {:?}", out_path))?;
+        std::io::copy(&mut file, &mut out_file)
+            .with_context(|| format!("write file {:?}", out_path))?;
+        written += 1;
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+#[path = "tests/archive.rs"]
+mod tests;
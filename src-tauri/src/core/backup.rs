@@ -0,0 +1,304 @@
+use std::io::{Read, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use rusqlite::{Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+
+use super::archive::is_safe_relative_path;
+use super::content_hash::is_ignored;
+use super::now_ms;
+use super::skill_store::SkillStore;
+use super::temp_cleanup::operation_temp_dir;
+
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_NAME: &str = "manifest.json";
+const DB_ENTRY: &str = "skills_hub.db";
+const CONTENT_PREFIX: &str = "central/";
+/// How many central repo files are written to disk and read back.
+const SAMPLE_SIZE: usize = 10;
+/// Largest database a backup may hold; the zip header's size is not trusted.
+const MAX_DB_BYTES: u64 = 1 << 30;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct BackupFile {
+    /// Path relative to the central repo, always `/`-separated.
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+/// `manifest.json` at the root of a backup zip. The database sits next to it
+/// as `skills_hub.db`, the central repo under `central/`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub format_version: u32,
+    pub created_at: i64,
+    pub db_sha256: String,
+    pub files: Vec<BackupFile>,
+}
+
+/// Outcome of checking a backup without restoring it. The archive is only
+/// trustworthy when `problems` is empty.
+#[derive(Clone, Debug, Serialize)]
+pub struct BackupVerification {
+    pub format_version: u32,
+    pub created_at: i64,
+    pub files_checked: usize,
+    pub files_sampled: usize,
+    /// Skills recorded in the backed up database, if it could be opened.
+    pub skill_count: Option<i64>,
+    pub problems: Vec<String>,
+    pub ok: bool,
+}
+
+/// Write a backup of the store's database and every file in the central
+/// repo to `out_path`, in the format [`verify_backup`] checks. Symlinks and
+/// VCS folders are left out.
+pub fn create_backup(
+    store: &SkillStore,
+    central_root: &Path,
+    out_path: &Path,
+) -> Result<BackupManifest> {
+    let scratch = operation_temp_dir("backup-create")?;
+    // VACUUM INTO gives a consistent copy even while the app is writing.
+    let db_copy = scratch.path().join(DB_ENTRY);
+    Connection::open(store.db_path())
+        .context("open database")?
+        .execute("VACUUM INTO ?1", [db_copy.to_string_lossy().as_ref()])
+        .context("snapshot database")?;
+    let db = std::fs::read(&db_copy).with_context(|| format!("read {:?}", db_copy))?;
+
+    let partial = scratch.path().join("backup.zip");
+    let mut zip = zip::ZipWriter::new(
+        std::fs::File::create(&partial).with_context(|| format!("create {:?}", partial))?,
+    );
+    let options = zip::write::SimpleFileOptions::default();
+    zip.start_file(DB_ENTRY, options)?;
+    zip.write_all(&db)?;
+
+    let mut files = Vec::new();
+    if central_root.is_dir() {
+        for entry in WalkDir::new(central_root)
+            .follow_links(false)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|e| !is_ignored(e))
+        {
+            let entry = entry?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let path = entry
+                .path()
+                .strip_prefix(central_root)?
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            let bytes =
+                std::fs::read(entry.path()).with_context(|| format!("read {:?}", entry.path()))?;
+            zip.start_file(format!("{}{}", CONTENT_PREFIX, path), options)?;
+            zip.write_all(&bytes)?;
+            files.push(BackupFile {
+                path,
+                size: bytes.len() as u64,
+                sha256: hex::encode(Sha256::digest(&bytes)),
+            });
+        }
+    }
+
+    let manifest = BackupManifest {
+        format_version: BACKUP_FORMAT_VERSION,
+        created_at: now_ms(),
+        db_sha256: hex::encode(Sha256::digest(&db)),
+        files,
+    };
+    zip.start_file(MANIFEST_NAME, options)?;
+    zip.write_all(&serde_json::to_vec_pretty(&manifest)?)?;
+    zip.finish().context("finish backup archive")?;
+
+    if let Some(parent) = out_path.parent() {
+        std::fs::create_dir_all(parent).with_context(|| format!("create dir {:?}", parent))?;
+    }
+    // Copy then rename so a failed write never leaves a half backup behind.
+    let staged = out_path.with_extension("partial");
+    std::fs::copy(&partial, &staged).with_context(|| format!("write {:?}", staged))?;
+    std::fs::rename(&staged, out_path).with_context(|| format!("write {:?}", out_path))?;
+    Ok(manifest)
+}
+
+/// Check a backup archive: every file against its manifest hash, the
+/// database with SQLite's integrity check, and a sample of files extracted
+/// to a scratch directory and read back. Nothing is restored.
+pub fn verify_backup(archive_path: &Path) -> Result<BackupVerification> {
+    let file = std::fs::File::open(archive_path)
+        .with_context(|| format!("open backup {:?}", archive_path))?;
+    let mut archive =
+        zip::ZipArchive::new(file).with_context(|| format!("read backup {:?}", archive_path))?;
+    let manifest = read_manifest(&mut archive)?;
+    let mut problems = Vec::new();
+
+    for i in 0..archive.len() {
+        let entry = archive.by_index(i).context("read backup entry")?;
+        let name = entry.name().to_string();
+        if entry.is_dir() || name == MANIFEST_NAME || name == DB_ENTRY {
+            continue;
+        }
+        let listed = name
+            .strip_prefix(CONTENT_PREFIX)
+            .is_some_and(|rel| manifest.files.iter().any(|f| f.path == rel));
+        if !listed {
+            problems.push(format!("unlisted file in backup: {}", name));
+        }
+    }
+
+    let mut files_checked = 0;
+    for file in &manifest.files {
+        if !is_safe_relative_path(&file.path) {
+            problems.push(format!("unsafe path in manifest: {}", file.path));
+            continue;
+        }
+        match read_entry(
+            &mut archive,
+            &format!("{}{}", CONTENT_PREFIX, file.path),
+            file.size,
+        ) {
+            Ok(bytes) if matches_manifest(&bytes, file.size, &file.sha256) => files_checked += 1,
+            Ok(_) => problems.push(format!("{} does not match its manifest hash", file.path)),
+            Err(err) => problems.push(format!("{:#}", err)),
+        }
+    }
+
+    let scratch = operation_temp_dir("backup-verify")?;
+    let skill_count = match check_database(&mut archive, &manifest, scratch.path()) {
+        Ok(count) => Some(count),
+        Err(err) => {
+            problems.push(format!("database: {:#}", err));
+            None
+        }
+    };
+
+    let mut files_sampled = 0;
+    for file in sample(&manifest.files) {
+        if !is_safe_relative_path(&file.path) {
+            continue;
+        }
+        match extract_and_read_back(&mut archive, file, scratch.path()) {
+            Ok(true) => files_sampled += 1,
+            Ok(false) => problems.push(format!("{} changed after extraction", file.path)),
+            Err(err) => problems.push(format!("extract {}: {:#}", file.path, err)),
+        }
+    }
+
+    Ok(BackupVerification {
+        format_version: manifest.format_version,
+        created_at: manifest.created_at,
+        files_checked,
+        files_sampled,
+        skill_count,
+        ok: problems.is_empty(),
+        problems,
+    })
+}
+
+fn read_manifest<R: Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+) -> Result<BackupManifest> {
+    let mut raw = String::new();
+    archive
+        .by_name(MANIFEST_NAME)
+        .context("backup has no manifest.json")?
+        .read_to_string(&mut raw)
+        .context("read manifest.json")?;
+    let manifest: BackupManifest = serde_json::from_str(&raw).context("parse manifest.json")?;
+    if manifest.format_version > BACKUP_FORMAT_VERSION {
+        anyhow::bail!(
+            "unsupported backup format version {} (max {})",
+            manifest.format_version,
+            BACKUP_FORMAT_VERSION
+        );
+    }
+    Ok(manifest)
+}
+
+/// Extract the database, check it against the manifest and let SQLite
+/// verify it. Returns the number of skills it records.
+fn check_database<R: Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    manifest: &BackupManifest,
+    scratch: &Path,
+) -> Result<i64> {
+    let bytes = read_entry(archive, DB_ENTRY, MAX_DB_BYTES)?;
+    if hex::encode(Sha256::digest(&bytes)) != manifest.db_sha256 {
+        anyhow::bail!("does not match its manifest hash");
+    }
+    let db_path = scratch.join(DB_ENTRY);
+    std::fs::write(&db_path, &bytes).with_context(|| format!("write {:?}", db_path))?;
+
+    let conn = Connection::open_with_flags(&db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .context("open backed up database")?;
+    let integrity: String = conn
+        .query_row("PRAGMA integrity_check", [], |row| row.get(0))
+        .context("run integrity check")?;
+    if integrity != "ok" {
+        anyhow::bail!("integrity check failed: {}", integrity);
+    }
+    conn.query_row("SELECT COUNT(*) FROM skills", [], |row| row.get(0))
+        .context("read skills table")
+}
+
+fn extract_and_read_back<R: Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    file: &BackupFile,
+    scratch: &Path,
+) -> Result<bool> {
+    let bytes = read_entry(
+        archive,
+        &format!("{}{}", CONTENT_PREFIX, file.path),
+        file.size,
+    )?;
+    let out = scratch.join("central").join(&file.path);
+    if let Some(parent) = out.parent() {
+        std::fs::create_dir_all(parent).with_context(|| format!("create dir {:?}", parent))?;
+    }
+    std::fs::write(&out, bytes).with_context(|| format!("write {:?}", out))?;
+    let written = std::fs::read(&out).with_context(|| format!("read {:?}", out))?;
+    Ok(matches_manifest(&written, file.size, &file.sha256))
+}
+
+/// Up to `SAMPLE_SIZE` files spread evenly over the manifest.
+fn sample(files: &[BackupFile]) -> Vec<&BackupFile> {
+    let step = files.len().div_ceil(SAMPLE_SIZE).max(1);
+    files.iter().step_by(step).collect()
+}
+
+/// Read `name`, stopping one byte past `limit` so an oversized entry fails
+/// the caller's size or hash check. The size in the zip header comes from the
+/// archive itself, so it is never used to size buffers.
+fn read_entry<R: Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    name: &str,
+    limit: u64,
+) -> Result<Vec<u8>> {
+    let entry = archive
+        .by_name(name)
+        .with_context(|| format!("backup is missing {}", name))?;
+    let mut bytes = Vec::new();
+    entry
+        .take(limit.saturating_add(1))
+        .read_to_end(&mut bytes)
+        .with_context(|| format!("read {}", name))?;
+    Ok(bytes)
+}
+
+fn matches_manifest(bytes: &[u8], size: u64, sha256: &str) -> bool {
+    bytes.len() as u64 == size && hex::encode(Sha256::digest(bytes)) == sha256
+}
+
+#[cfg(test)]
+#[path = "tests/backup.rs"]
+mod tests;
@@ -1,11 +1,14 @@
 use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, OnceLock};
 use std::time::{Duration, SystemTime};
 
 use anyhow::{Context, Result};
-use serde::Deserialize;
-use tauri::Manager;
+use serde::{Deserialize, Serialize};
 
+use super::app_paths::AppPaths;
+use super::now_ms;
 use super::skill_store::SkillStore;
+use super::storage_report::dir_usage;
 
 const CACHE_DIR_NAME: &str = "skills-hub-git-cache";
 const CACHE_META_FILE: &str = ".skills-hub-cache.json";
@@ -15,10 +18,76 @@ const MAX_GIT_CACHE_CLEANUP_DAYS: i64 = 3650;
 pub const GIT_CACHE_TTL_SECS_KEY: &str = "git_cache_ttl_secs";
 pub const DEFAULT_GIT_CACHE_TTL_SECS: i64 = 60;
 const MAX_GIT_CACHE_TTL_SECS: i64 = 3600;
+pub const GIT_CACHE_MAX_MB_KEY: &str = "git_cache_max_mb";
+pub const DEFAULT_GIT_CACHE_MAX_MB: i64 = 2048;
+const MAX_GIT_CACHE_MAX_MB: i64 = 1024 * 1024;
 
-#[derive(Debug, Deserialize)]
-struct RepoCacheMeta {
-    last_fetched_ms: i64,
+/// Written next to each cached clone.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RepoCacheMeta {
+    pub last_fetched_ms: i64,
+    /// Last time an install or browse read the clone; older caches lack it.
+    #[serde(default)]
+    pub last_used_ms: i64,
+    #[serde(default)]
+    pub head: Option<String>,
+    /// The URL the clone was made from; older caches lack it.
+    #[serde(default)]
+    pub repo_url: Option<String>,
+    #[serde(default)]
+    pub branch: Option<String>,
+}
+
+/// One cached clone as listed by [`build_git_cache_report`].
+#[derive(Clone, Debug, Serialize)]
+pub struct GitCacheEntry {
+    /// Directory name under the cache root.
+    pub key: String,
+    pub repo_url: Option<String>,
+    pub branch: Option<String>,
+    pub head: Option<String>,
+    pub bytes: u64,
+    pub files: u64,
+    pub last_used_ms: i64,
+    pub last_fetched_ms: Option<i64>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct GitCacheReport {
+    pub root: String,
+    pub total_bytes: u64,
+    /// The configured size cap; `None` when uncapped.
+    pub max_bytes: Option<u64>,
+    /// Cached clones, largest first.
+    pub entries: Vec<GitCacheEntry>,
+}
+
+static GIT_CACHE_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+
+/// Held while a cached clone is fetched or evicted.
+pub fn lock_git_cache() -> MutexGuard<'static, ()> {
+    GIT_CACHE_LOCK
+        .get_or_init(|| Mutex::new(()))
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+}
+
+pub fn read_cache_meta(repo_dir: &Path) -> Option<RepoCacheMeta> {
+    let raw = std::fs::read_to_string(repo_dir.join(CACHE_META_FILE)).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+pub fn write_cache_meta(repo_dir: &Path, meta: &RepoCacheMeta) {
+    if let Ok(raw) = serde_json::to_string(meta) {
+        let _ = std::fs::write(repo_dir.join(CACHE_META_FILE), raw);
+    }
+}
+
+/// Record that the clone in `repo_dir` was just used, for LRU eviction.
+pub fn touch_cache_entry(repo_dir: &Path) {
+    let mut meta = read_cache_meta(repo_dir).unwrap_or_default();
+    meta.last_used_ms = now_ms();
+    write_cache_meta(repo_dir, &meta);
 }
 
 pub fn get_git_cache_cleanup_days(store: &SkillStore) -> i64 {
@@ -53,73 +122,186 @@ pub fn set_git_cache_ttl_secs(store: &SkillStore, secs: i64) -> Result<i64> {
     Ok(secs)
 }
 
-pub fn cleanup_git_cache_dirs<R: tauri::Runtime>(
-    app: &tauri::AppHandle<R>,
-    max_age: Duration,
-) -> Result<usize> {
-    let cache_dir = app
-        .path()
-        .app_cache_dir()
-        .context("failed to resolve app cache dir")?;
-    cleanup_git_cache_dirs_in(&cache_dir, max_age)
+pub fn get_git_cache_max_mb(store: &SkillStore) -> i64 {
+    let raw = store.get_setting(GIT_CACHE_MAX_MB_KEY).ok().flatten();
+    parse_cache_max_mb(raw).unwrap_or(DEFAULT_GIT_CACHE_MAX_MB)
 }
 
-fn cleanup_git_cache_dirs_in(cache_dir: &Path, max_age: Duration) -> Result<usize> {
-    let cache_root = cache_dir.join(CACHE_DIR_NAME);
-    if !cache_root.exists() {
-        return Ok(0);
+/// Size cap for the clone cache; 0 means no cap.
+pub fn set_git_cache_max_mb(store: &SkillStore, mb: i64) -> Result<i64> {
+    if !(0..=MAX_GIT_CACHE_MAX_MB).contains(&mb) {
+        anyhow::bail!(
+            "cache size cap must be between 0 and {} MB",
+            MAX_GIT_CACHE_MAX_MB
+        );
     }
+    store.set_setting(GIT_CACHE_MAX_MB_KEY, &mb.to_string())?;
+    Ok(mb)
+}
 
-    let cutoff_ms = now_ms().saturating_sub(max_age.as_millis().try_into().unwrap_or(i64::MAX));
-    let cutoff_time = SystemTime::now()
-        .checked_sub(max_age)
-        .unwrap_or(SystemTime::UNIX_EPOCH);
+/// The size cap in bytes, if one is set.
+pub fn git_cache_max_bytes(store: &SkillStore) -> Option<u64> {
+    match get_git_cache_max_mb(store) {
+        0 => None,
+        mb => Some(mb as u64 * 1024 * 1024),
+    }
+}
 
-    let mut removed = 0usize;
-    let rd = match std::fs::read_dir(&cache_root) {
-        Ok(v) => v,
-        Err(err) => {
-            return Err(anyhow::anyhow!(
-                "failed to read cache dir {:?}: {}",
-                cache_root,
-                err
-            ));
-        }
+/// Remove cached clones unused for `max_age`, then the least recently used
+/// ones until the cache fits in `max_bytes`. Returns how many were removed.
+pub fn cleanup_git_cache_dirs(
+    paths: &AppPaths,
+    max_age: Option<Duration>,
+    max_bytes: Option<u64>,
+) -> Result<usize> {
+    cleanup_git_cache_dirs_in(&paths.cache_dir, max_age, max_bytes, None)
+}
+
+/// Evict least recently used clones over the size cap, never `keep` (the
+/// clone about to be read).
+pub fn enforce_git_cache_cap(paths: &AppPaths, store: &SkillStore, keep: &Path) -> Result<usize> {
+    let Some(max_bytes) = git_cache_max_bytes(store) else {
+        return Ok(0);
     };
+    cleanup_git_cache_dirs_in(&paths.cache_dir, None, Some(max_bytes), Some(keep))
+}
 
-    for entry in rd.flatten() {
-        let path: PathBuf = entry.path();
-        if !path.is_dir() {
-            continue;
-        }
+/// Where cloned repos are cached between installs.
+pub fn git_cache_root(paths: &AppPaths) -> PathBuf {
+    paths.cache_dir.join(CACHE_DIR_NAME)
+}
+
+/// Every cached clone under `cache_root` with its size and last use.
+pub fn build_git_cache_report(cache_root: &Path, max_bytes: Option<u64>) -> Result<GitCacheReport> {
+    let mut entries: Vec<GitCacheEntry> = cached_clones(cache_root)?
+        .into_iter()
+        .map(|path| {
+            let meta = read_cache_meta(&path);
+            let usage = dir_usage(&path);
+            GitCacheEntry {
+                key: path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                repo_url: cached_repo_url(&path, meta.as_ref()),
+                branch: meta.as_ref().and_then(|meta| meta.branch.clone()),
+                head: meta.as_ref().and_then(|meta| meta.head.clone()),
+                bytes: usage.bytes,
+                files: usage.files,
+                last_used_ms: last_used_ms(&path),
+                last_fetched_ms: meta
+                    .as_ref()
+                    .map(|meta| meta.last_fetched_ms)
+                    .filter(|ms| *ms > 0),
+            }
+        })
+        .collect();
+    entries.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.key.cmp(&b.key)));
+
+    Ok(GitCacheReport {
+        root: cache_root.to_string_lossy().to_string(),
+        total_bytes: entries.iter().map(|entry| entry.bytes).sum(),
+        max_bytes,
+        entries,
+    })
+}
 
-        if !path.join(".git").exists() {
+/// Remove every cached clone of `repo_url`, whatever branch it tracks.
+/// Returns how many were removed.
+pub fn evict_git_cache_entry(cache_root: &Path, repo_url: &str) -> Result<usize> {
+    let wanted = normalize_repo_url(repo_url);
+    if wanted.is_empty() {
+        anyhow::bail!("repo url is required");
+    }
+    let _guard = lock_git_cache();
+    let mut removed = 0usize;
+    for path in cached_clones(cache_root)? {
+        let url = cached_repo_url(&path, read_cache_meta(&path).as_ref());
+        if !url.is_some_and(|url| normalize_repo_url(&url) == wanted) {
             continue;
         }
+        std::fs::remove_dir_all(&path)
+            .with_context(|| format!("failed to remove cached clone {:?}", path))?;
+        removed += 1;
+    }
+    Ok(removed)
+}
 
-        let meta_path = path.join(CACHE_META_FILE);
-        let mut should_remove = false;
+/// Clones under `cache_root`; half-written directories without `.git` are
+/// skipped.
+fn cached_clones(cache_root: &Path) -> Result<Vec<PathBuf>> {
+    if !cache_root.exists() {
+        return Ok(Vec::new());
+    }
+    let rd = std::fs::read_dir(cache_root)
+        .with_context(|| format!("failed to read cache dir {:?}", cache_root))?;
+    Ok(rd
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir() && path.join(".git").exists())
+        .collect())
+}
 
-        if let Ok(raw) = std::fs::read_to_string(&meta_path) {
-            if let Ok(meta) = serde_json::from_str::<RepoCacheMeta>(&raw) {
-                if meta.last_fetched_ms > 0 && meta.last_fetched_ms <= cutoff_ms {
-                    should_remove = true;
-                }
-            }
-        }
+/// The clone's source URL, from its metadata or else its `origin` remote.
+fn cached_repo_url(repo_dir: &Path, meta: Option<&RepoCacheMeta>) -> Option<String> {
+    if let Some(url) = meta.and_then(|meta| meta.repo_url.clone()) {
+        return Some(url);
+    }
+    let repo = git2::Repository::open(repo_dir).ok()?;
+    let remote = repo.find_remote("origin").ok()?;
+    remote.url().map(str::to_string)
+}
 
-        if !should_remove {
-            let meta = match std::fs::metadata(&path) {
-                Ok(m) => m,
-                Err(_) => continue,
-            };
-            let modified = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
-            if modified <= cutoff_time {
-                should_remove = true;
-            }
-        }
+fn normalize_repo_url(url: &str) -> String {
+    let url = url.trim().trim_end_matches('/');
+    url.strip_suffix(".git").unwrap_or(url).to_string()
+}
 
-        if should_remove && std::fs::remove_dir_all(&path).is_ok() {
+struct CacheEntry {
+    path: PathBuf,
+    last_used_ms: i64,
+    bytes: u64,
+}
+
+fn cleanup_git_cache_dirs_in(
+    cache_dir: &Path,
+    max_age: Option<Duration>,
+    max_bytes: Option<u64>,
+    keep: Option<&Path>,
+) -> Result<usize> {
+    let cache_root = cache_dir.join(CACHE_DIR_NAME);
+    if !cache_root.exists() {
+        return Ok(0);
+    }
+    let _guard = lock_git_cache();
+
+    let mut entries: Vec<CacheEntry> = cached_clones(&cache_root)?
+        .into_iter()
+        .map(|path| CacheEntry {
+            last_used_ms: last_used_ms(&path),
+            bytes: if max_bytes.is_some() {
+                dir_usage(&path).bytes
+            } else {
+                0
+            },
+            path,
+        })
+        .collect();
+    // Least recently used first.
+    entries.sort_by_key(|entry| entry.last_used_ms);
+
+    let cutoff_ms =
+        max_age.map(|age| now_ms().saturating_sub(age.as_millis().try_into().unwrap_or(i64::MAX)));
+    let mut total: u64 = entries.iter().map(|entry| entry.bytes).sum();
+    let mut removed = 0usize;
+    for entry in entries {
+        let expired = cutoff_ms.is_some_and(|cutoff| entry.last_used_ms <= cutoff);
+        let over_cap = max_bytes.is_some_and(|cap| total > cap);
+        if (!expired && !over_cap) || keep == Some(entry.path.as_path()) {
+            continue;
+        }
+        if std::fs::remove_dir_all(&entry.path).is_ok() {
+            total = total.saturating_sub(entry.bytes);
             removed += 1;
         }
     }
@@ -127,6 +309,23 @@ fn cleanup_git_cache_dirs_in(cache_dir: &Path, max_age: Duration) -> Result<usiz
     Ok(removed)
 }
 
+/// When the clone was last used: from its metadata, else the directory's
+/// modification time.
+fn last_used_ms(repo_dir: &Path) -> i64 {
+    if let Some(meta) = read_cache_meta(repo_dir) {
+        let used = meta.last_used_ms.max(meta.last_fetched_ms);
+        if used > 0 {
+            return used;
+        }
+    }
+    std::fs::metadata(repo_dir)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|time| time.duration_since(SystemTime::UNIX_EPOCH).ok())
+        .map(|since| since.as_millis() as i64)
+        .unwrap_or(0)
+}
+
 fn parse_cleanup_days(raw: Option<String>) -> Option<i64> {
     let value = raw?.trim().parse::<i64>().ok()?;
     if !(0..=MAX_GIT_CACHE_CLEANUP_DAYS).contains(&value) {
@@ -145,9 +344,15 @@ fn parse_cache_ttl_secs(raw: Option<String>) -> Option<i64> {
     }
 }
 
-fn now_ms() -> i64 {
-    let now = SystemTime::now()
-        .duration_since(SystemTime::UNIX_EPOCH)
-        .unwrap_or_default();
-    now.as_millis() as i64
+fn parse_cache_max_mb(raw: Option<String>) -> Option<i64> {
+    let value = raw?.trim().parse::<i64>().ok()?;
+    if !(0..=MAX_GIT_CACHE_MAX_MB).contains(&value) {
+        None
+    } else {
+        Some(value)
+    }
 }
+
+#[cfg(test)]
+#[path = "tests/cache_cleanup.rs"]
+mod tests;
@@ -0,0 +1,283 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use git2::{IndexAddOption, Oid, Repository, Signature};
+use serde::Serialize;
+
+use super::central_repo::{ensure_central_repo_writable, is_shared_central_repo};
+use super::content_hash::hash_dir;
+use super::now_ms;
+use super::skill_drift::record_skill_manifest;
+use super::skill_lock::ensure_unlocked;
+use super::skill_metadata::refresh_skill_metadata;
+use super::skill_store::{SkillRecord, SkillStore};
+
+/// Set in the repo's git config so history is only ever written to a repo
+/// this app created, never to a user's own repository.
+const HISTORY_CONFIG_KEY: &str = "skillshub.history";
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SkillRevision {
+    pub commit: String,
+    pub message: String,
+    pub committed_at: i64,
+}
+
+/// Make `central_root` a git repository (once) and snapshot what is in it.
+pub fn ensure_history_repo(central_root: &Path) -> Result<()> {
+    if open_history_repo(central_root).is_some() {
+        return Ok(());
+    }
+    if central_root.join(".git").exists() {
+        // Someone else's repository; leave it alone.
+        return Ok(());
+    }
+    let repo = Repository::init(central_root)
+        .with_context(|| format!("init history repo in {:?}", central_root))?;
+    repo.config()?.set_bool(HISTORY_CONFIG_KEY, true)?;
+    commit_all(&repo, "Start central repo history")?;
+    Ok(())
+}
+
+pub(super) fn open_history_repo(central_root: &Path) -> Option<Repository> {
+    let repo = Repository::open(central_root).ok()?;
+    let ours = repo
+        .config()
+        .ok()?
+        .get_bool(HISTORY_CONFIG_KEY)
+        .unwrap_or(false);
+    // A skill dir may sit inside some other repo; only its own root counts.
+    let same_root = repo
+        .workdir()
+        .and_then(|w| w.canonicalize().ok())
+        .zip(central_root.canonicalize().ok())
+        .is_some_and(|(a, b)| a == b);
+    (ours && same_root).then_some(repo)
+}
+
+/// Record the central repo's current state after a change to the skill at
+/// `skill_path`. History is best effort: failures are logged, never raised.
+pub fn record_change(store: &SkillStore, skill_path: &Path, message: &str) {
+    if is_shared_central_repo(store) {
+        return;
+    }
+    let Some(root) = skill_path.parent() else {
+        return;
+    };
+    let Some(repo) = open_history_repo(root) else {
+        return;
+    };
+    if let Err(err) = commit_all(&repo, message) {
+        log::warn!("[central_history] commit {:?} failed: {:#}", message, err);
+    }
+}
+
+/// Hidden entries (staging dirs, `.git`) and skills that are git clones with
+/// their own history stay out of the central history.
+pub(super) fn is_untracked(root: &Path, relative: &Path) -> bool {
+    let Some(first) = relative.components().next() else {
+        return false;
+    };
+    let first = first.as_os_str();
+    first.to_string_lossy().starts_with('.') || root.join(first).join(".git").exists()
+}
+
+fn signature(repo: &Repository) -> Result<Signature<'static>> {
+    match repo.signature() {
+        Ok(sig) => Ok(sig.to_owned()),
+        Err(_) => Ok(Signature::now("Skills Hub", "skills-hub@localhost")?),
+    }
+}
+
+/// Commit every change in the work tree. Returns `None` when nothing changed.
+pub(super) fn commit_all(repo: &Repository, message: &str) -> Result<Option<Oid>> {
+    let root = repo
+        .workdir()
+        .ok_or_else(|| anyhow::anyhow!("history repo has no work tree"))?
+        .to_path_buf();
+    let mut index = repo.index()?;
+    let mut skip = |path: &Path, _: &[u8]| -> i32 { is_untracked(&root, path) as i32 };
+    index.add_all(["*"].iter(), IndexAddOption::DEFAULT, Some(&mut skip))?;
+    index.update_all(["*"].iter(), Some(&mut skip))?;
+    index.write()?;
+    let tree_id = index.write_tree()?;
+
+    let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+    if parent.as_ref().is_some_and(|p| p.tree_id() == tree_id) {
+        return Ok(None);
+    }
+    let tree = repo.find_tree(tree_id)?;
+    let sig = signature(repo)?;
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    let oid = repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)?;
+    Ok(Some(oid))
+}
+
+fn skill_location(skill: &SkillRecord) -> Result<(PathBuf, String)> {
+    let path = PathBuf::from(&skill.central_path);
+    let root = path
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("invalid central path"))?
+        .to_path_buf();
+    let name = path
+        .file_name()
+        .ok_or_else(|| anyhow::anyhow!("invalid central path"))?
+        .to_string_lossy()
+        .to_string();
+    Ok((root, name))
+}
+
+/// Commits that changed the skill's directory, newest first.
+pub fn get_skill_history(store: &SkillStore, skill_id: &str) -> Result<Vec<SkillRevision>> {
+    let skill = store
+        .get_skill_by_id(skill_id)?
+        .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+    let (root, name) = skill_location(&skill)?;
+    let Some(repo) = open_history_repo(&root) else {
+        return Ok(Vec::new());
+    };
+    history_of(&repo, &name)
+}
+
+fn history_of(repo: &Repository, name: &str) -> Result<Vec<SkillRevision>> {
+    let entry_id = |commit: &git2::Commit| -> Option<Oid> {
+        commit
+            .tree()
+            .ok()?
+            .get_path(Path::new(name))
+            .ok()
+            .map(|e| e.id())
+    };
+    let mut walk = repo.revwalk()?;
+    if walk.push_head().is_err() {
+        return Ok(Vec::new());
+    }
+    walk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+    let mut revisions = Vec::new();
+    for oid in walk {
+        let commit = repo.find_commit(oid?)?;
+        let here = entry_id(&commit);
+        let before = commit.parent(0).ok().and_then(|p| entry_id(&p));
+        if here.is_some() && here != before {
+            revisions.push(SkillRevision {
+                commit: commit.id().to_string(),
+                message: commit.summary().unwrap_or_default().to_string(),
+                committed_at: commit.time().seconds() * 1000,
+            });
+        }
+    }
+    Ok(revisions)
+}
+
+/// Put the skill's directory back to how it was at `commit`. The current
+/// state is committed first, so the restore can itself be undone. Locked
+/// skills are refused.
+pub fn restore_skill_revision(
+    store: &SkillStore,
+    skill_id: &str,
+    commit: &str,
+) -> Result<SkillRecord> {
+    ensure_central_repo_writable(store)?;
+    let skill = store
+        .get_skill_by_id(skill_id)?
+        .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+    ensure_unlocked(store, &skill)?;
+    let (root, name) = skill_location(&skill)?;
+    let repo =
+        open_history_repo(&root).ok_or_else(|| anyhow::anyhow!("no history for {}", skill.name))?;
+    let central_path = root.join(&name);
+    if central_path.join(".git").exists() {
+        anyhow::bail!("{} is a git clone; use its own history", skill.name);
+    }
+
+    let target = repo
+        .revparse_single(commit)
+        .and_then(|obj| obj.peel_to_commit())
+        .with_context(|| format!("unknown revision {}", commit))?;
+    let subtree = target
+        .tree()?
+        .get_path(Path::new(&name))
+        .ok()
+        .and_then(|entry| entry.to_object(&repo).ok())
+        .and_then(|obj| obj.into_tree().ok())
+        .ok_or_else(|| anyhow::anyhow!("{} did not exist at {}", skill.name, commit))?;
+
+    commit_all(&repo, &format!("Before restoring {}", skill.name))?;
+    if central_path.exists() {
+        std::fs::remove_dir_all(&central_path)
+            .with_context(|| format!("remove {:?}", central_path))?;
+    }
+    write_tree(&repo, &subtree, &central_path)?;
+
+    let content_hash = hash_dir(&central_path)?;
+    let short = &target.id().to_string()[..7];
+    commit_all(&repo, &format!("Restore {} to {}", skill.name, short))?;
+
+    let record = SkillRecord {
+        content_hash: Some(content_hash),
+        updated_at: now_ms(),
+        ..skill
+    };
+    store.upsert_skill(&record)?;
+    // The restored content is the new baseline, as after an update.
+    refresh_skill_metadata(store, &record.id, &central_path);
+    record_skill_manifest(store, &record.id, &central_path);
+    Ok(record)
+}
+
+pub(super) fn write_tree(repo: &Repository, tree: &git2::Tree, dest: &Path) -> Result<()> {
+    std::fs::create_dir_all(dest).with_context(|| format!("create dir {:?}", dest))?;
+    for entry in tree.iter() {
+        let name = entry
+            .name()
+            .ok_or_else(|| anyhow::anyhow!("non-UTF-8 name in history"))?;
+        let path = dest.join(name);
+        let object = entry.to_object(repo)?;
+        if let Some(subtree) = object.as_tree() {
+            write_tree(repo, subtree, &path)?;
+        } else if let Some(blob) = object.as_blob() {
+            if entry.filemode() == 0o120000 && write_symlink(blob.content(), &path)? {
+                continue;
+            }
+            std::fs::write(&path, blob.content()).with_context(|| format!("write {:?}", path))?;
+            #[cfg(unix)]
+            if entry.filemode() == 0o100755 {
+                use std::os::unix::fs::PermissionsExt;
+                std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Recreate a symlink blob (its content is the link's destination). Returns
+/// `false` where links cannot be made, and the caller writes the blob as a
+/// plain file the way git does with `core.symlinks` off.
+fn write_symlink(dest: &[u8], path: &Path) -> Result<bool> {
+    let dest =
+        Path::new(std::str::from_utf8(dest).context("non-UTF-8 symlink destination in history")?);
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(dest, path)
+            .with_context(|| format!("symlink {:?} -> {:?}", path, dest))?;
+        Ok(true)
+    }
+
+    #[cfg(windows)]
+    {
+        let resolved = path.parent().map(|p| p.join(dest));
+        let linked = if resolved.is_some_and(|p| p.is_dir()) {
+            std::os::windows::fs::symlink_dir(dest, path)
+        } else {
+            std::os::windows::fs::symlink_file(dest, path)
+        };
+        return Ok(linked.is_ok());
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    Ok(false)
+}
+
+#[cfg(test)]
+#[path = "tests/central_history.rs"]
+mod tests;
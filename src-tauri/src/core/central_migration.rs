@@ -0,0 +1,293 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use walkdir::WalkDir;
+
+use super::central_repo::CENTRAL_REPO_PATH_KEY;
+use super::errors::{coded_with, ErrorCode};
+use super::locale::{tr, trf, Msg};
+use super::now_ms;
+use super::skill_store::{SkillRecord, SkillStore};
+use super::storage_report::dir_usage;
+
+/// Name of the central repo's history, moved along with the skills.
+const HISTORY_DIR: &str = ".git";
+
+#[derive(Clone, Debug, Serialize)]
+pub struct PlannedMove {
+    /// `None` for the central repo's history.
+    pub skill_id: Option<String>,
+    pub name: String,
+    pub from: String,
+    pub to: String,
+    pub bytes: u64,
+}
+
+/// What moving the central repo to `to` would do, checked up front.
+#[derive(Clone, Debug, Serialize)]
+pub struct MigrationPlan {
+    pub from: String,
+    pub to: String,
+    pub moves: Vec<PlannedMove>,
+    pub total_bytes: u64,
+    /// Whether the content has to be copied because `to` is on another
+    /// file system; only then does free space matter.
+    pub needs_copy: bool,
+    pub available_bytes: Option<u64>,
+    /// Problems that stop the migration; nothing is moved while any remain.
+    pub blockers: Vec<String>,
+}
+
+/// Plan moving every skill stored directly in `from` to `to`. Skills kept
+/// elsewhere (adopted in place, shared) stay where they are.
+pub fn plan_central_migration(store: &SkillStore, from: &Path, to: &Path) -> Result<MigrationPlan> {
+    let mut blockers = Vec::new();
+    if !to.is_absolute() {
+        blockers.push(tr(Msg::MigrationPathNotAbsolute).to_string());
+    }
+    let mut moves = Vec::new();
+    for skill in store.list_skills()? {
+        let path = PathBuf::from(&skill.central_path);
+        if path.parent() != Some(from) {
+            continue;
+        }
+        let Some(file_name) = path.file_name() else {
+            continue;
+        };
+        if !path.exists() {
+            blockers.push(trf(
+                Msg::MigrationSourceMissing,
+                &[("path", &format!("{:?}", path))],
+            ));
+            continue;
+        }
+        moves.push(planned(
+            Some(skill.id.clone()),
+            &skill.name,
+            &path,
+            to.join(file_name),
+        ));
+    }
+    let history = from.join(HISTORY_DIR);
+    if history.is_dir() && !to.join(HISTORY_DIR).exists() {
+        moves.push(planned(None, "history", &history, to.join(HISTORY_DIR)));
+    }
+
+    for m in &moves {
+        if Path::new(&m.to).exists() {
+            blockers.push(trf(Msg::MigrationTargetExists, &[("path", &m.to)]));
+        }
+        if to.starts_with(&m.from) {
+            blockers.push(trf(Msg::MigrationIntoItself, &[("name", &m.name)]));
+        }
+    }
+
+    let total_bytes: u64 = moves.iter().map(|m| m.bytes).sum();
+    let probe = existing_ancestor(to);
+    let needs_copy = match &probe {
+        Some(probe) => !same_file_system(from, probe),
+        None => true,
+    };
+    let available_bytes = probe.as_deref().and_then(|p| fs2::available_space(p).ok());
+    if needs_copy {
+        if let Some(available) = available_bytes {
+            if available < total_bytes {
+                blockers.push(trf(
+                    Msg::MigrationNoSpace,
+                    &[
+                        ("needed", &total_bytes.to_string()),
+                        ("available", &available.to_string()),
+                    ],
+                ));
+            }
+        }
+    }
+    if let Some(probe) = &probe {
+        if let Err(err) = check_writable(probe) {
+            blockers.push(trf(
+                Msg::MigrationNotWritable,
+                &[
+                    ("path", &format!("{:?}", probe)),
+                    ("error", &format!("{:#}", err)),
+                ],
+            ));
+        }
+    }
+
+    Ok(MigrationPlan {
+        from: from.to_string_lossy().to_string(),
+        to: to.to_string_lossy().to_string(),
+        moves,
+        total_bytes,
+        needs_copy,
+        available_bytes,
+        blockers,
+    })
+}
+
+fn planned(skill_id: Option<String>, name: &str, from: &Path, to: PathBuf) -> PlannedMove {
+    PlannedMove {
+        skill_id,
+        name: name.to_string(),
+        from: from.to_string_lossy().to_string(),
+        to: to.to_string_lossy().to_string(),
+        bytes: dir_usage(from).bytes,
+    }
+}
+
+enum Done {
+    Renamed,
+    Copied,
+}
+
+/// Carry out `plan`, reporting `(moved, total)` after each step. Skills are
+/// renamed when possible and copied otherwise; originals of copies are only
+/// removed once everything is in place. If any step fails, what was moved
+/// goes back, records are restored and the error is returned.
+pub fn run_central_migration(
+    store: &SkillStore,
+    plan: &MigrationPlan,
+    progress: impl Fn(usize, usize),
+) -> Result<()> {
+    if !plan.blockers.is_empty() {
+        return Err(coded_with(
+            ErrorCode::MigrationBlocked,
+            plan.blockers.join("; "),
+            serde_json::json!({ "blockers": plan.blockers }),
+        ));
+    }
+    let to = Path::new(&plan.to);
+    std::fs::create_dir_all(to).with_context(|| format!("create {:?}", to))?;
+
+    let total = plan.moves.len();
+    let mut done: Vec<(&PlannedMove, Done)> = Vec::new();
+    for m in &plan.moves {
+        match move_one(m) {
+            Ok(how) => done.push((m, how)),
+            Err(err) => {
+                roll_back(&done);
+                return Err(err.context(trf(Msg::MigrationMoveFailed, &[("name", &m.name)])));
+            }
+        }
+        progress(done.len(), total);
+    }
+
+    let mut previous: Vec<SkillRecord> = Vec::new();
+    let updated = (|| -> Result<()> {
+        let now = now_ms();
+        for m in &plan.moves {
+            let Some(id) = &m.skill_id else {
+                continue;
+            };
+            let Some(skill) = store.get_skill_by_id(id)? else {
+                continue;
+            };
+            previous.push(skill.clone());
+            store.upsert_skill(&SkillRecord {
+                central_path: m.to.clone(),
+                updated_at: now,
+                ..skill
+            })?;
+        }
+        store.set_setting(CENTRAL_REPO_PATH_KEY, &plan.to)
+    })();
+    if let Err(err) = updated {
+        for skill in &previous {
+            if let Err(restore) = store.upsert_skill(skill) {
+                log::warn!(
+                    "[central_migration] restore {} failed: {:#}",
+                    skill.name,
+                    restore
+                );
+            }
+        }
+        roll_back(&done);
+        return Err(err.context(tr(Msg::MigrationRecordsFailed)));
+    }
+
+    for (m, how) in &done {
+        if matches!(how, Done::Copied) {
+            if let Err(err) = std::fs::remove_dir_all(&m.from) {
+                log::warn!("[central_migration] cleanup {} failed: {}", m.from, err);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn move_one(m: &PlannedMove) -> Result<Done> {
+    if Path::new(&m.to).exists() {
+        anyhow::bail!("target path already exists: {}", m.to);
+    }
+    if std::fs::rename(&m.from, &m.to).is_ok() {
+        return Ok(Done::Renamed);
+    }
+    if let Err(err) = copy_tree(Path::new(&m.from), Path::new(&m.to)) {
+        let _ = std::fs::remove_dir_all(&m.to);
+        return Err(err);
+    }
+    Ok(Done::Copied)
+}
+
+fn roll_back(done: &[(&PlannedMove, Done)]) {
+    for (m, how) in done.iter().rev() {
+        let undone = match how {
+            Done::Renamed => std::fs::rename(&m.to, &m.from),
+            Done::Copied => std::fs::remove_dir_all(&m.to),
+        };
+        if let Err(err) = undone {
+            log::warn!("[central_migration] roll back {} failed: {}", m.name, err);
+        }
+    }
+}
+
+/// Copy everything, `.git` included; symlinks stay symlinks.
+fn copy_tree(source: &Path, target: &Path) -> Result<()> {
+    for entry in WalkDir::new(source).follow_links(false) {
+        let entry = entry?;
+        let dest = target.join(entry.path().strip_prefix(source)?);
+        let file_type = entry.file_type();
+        if file_type.is_dir() {
+            std::fs::create_dir_all(&dest).with_context(|| format!("create dir {:?}", dest))?;
+        } else if file_type.is_symlink() {
+            let link = std::fs::read_link(entry.path())?;
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(&link, &dest).with_context(|| format!("link {:?}", dest))?;
+            #[cfg(windows)]
+            std::fs::copy(entry.path(), &dest)
+                .with_context(|| format!("copy {:?} ({:?})", dest, link))?;
+        } else {
+            std::fs::copy(entry.path(), &dest).with_context(|| format!("copy {:?}", dest))?;
+        }
+    }
+    Ok(())
+}
+
+fn existing_ancestor(path: &Path) -> Option<PathBuf> {
+    path.ancestors().find(|p| p.exists()).map(Path::to_path_buf)
+}
+
+#[cfg(unix)]
+fn same_file_system(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (std::fs::metadata(a), std::fs::metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn same_file_system(a: &Path, b: &Path) -> bool {
+    let root = |p: &Path| p.components().next().map(|c| c.as_os_str().to_owned());
+    root(a).is_some() && root(a) == root(b)
+}
+
+fn check_writable(dir: &Path) -> Result<()> {
+    tempfile::NamedTempFile::new_in(dir)?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "tests/central_migration.rs"]
+mod tests;
@@ -0,0 +1,455 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use git2::{
+    Cred, CredentialType, ErrorCode, FetchOptions, Oid, PushOptions, RemoteCallbacks, Repository,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::central_history::{commit_all, is_untracked, open_history_repo, write_tree};
+use super::central_repo::ensure_central_repo_writable;
+use super::content_hash::hash_dir;
+use super::errors;
+use super::now_ms;
+use super::remote_sync::resolve_key_path;
+use super::skill_store::{SkillRecord, SkillStore};
+
+pub const CENTRAL_REMOTE_KEY: &str = "central_remote";
+/// Name of the remote in the central repo's own git config.
+const REMOTE_NAME: &str = "backup";
+const DEFAULT_BRANCH: &str = "main";
+
+/// How to authenticate against the backup remote. Without explicit
+/// credentials, SSH URLs use the agent and HTTPS URLs the git credential
+/// helper.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RemoteAuth {
+    #[default]
+    None,
+    Token {
+        #[serde(default)]
+        username: Option<String>,
+        token: String,
+    },
+    SshAgent {
+        #[serde(default)]
+        username: Option<String>,
+    },
+    SshKey {
+        #[serde(default)]
+        username: Option<String>,
+        /// Defaults to the first of `~/.ssh/id_ed25519`, `id_rsa`, `id_ecdsa`.
+        #[serde(default)]
+        key_path: Option<String>,
+        #[serde(default)]
+        passphrase: Option<String>,
+    },
+}
+
+/// A private git repository the central repo is backed up to.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CentralRemote {
+    pub url: String,
+    #[serde(default)]
+    pub branch: Option<String>,
+    #[serde(default)]
+    pub auth: RemoteAuth,
+}
+
+/// What the settings UI may see: no secrets.
+#[derive(Clone, Debug, Serialize)]
+pub struct CentralRemoteSummary {
+    pub url: String,
+    pub branch: String,
+    pub auth_kind: String,
+    pub username: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct PullConflict {
+    pub name: String,
+    /// `both_changed`, `deleted_remotely`, `git_clone`, `unknown_type` or
+    /// `missing_locally`.
+    pub reason: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct PullResult {
+    /// Central repo commit after the pull; `None` when the remote is empty.
+    pub commit: Option<String>,
+    /// Skills that came from the remote and were added to the store.
+    pub added: Vec<String>,
+    /// Managed skills whose files the pull changed.
+    pub updated: Vec<String>,
+    /// Entries left as they were locally; see `PullConflict::reason`.
+    pub conflicts: Vec<PullConflict>,
+}
+
+fn read_remote(store: &SkillStore) -> Result<Option<CentralRemote>> {
+    match store.get_setting(CENTRAL_REMOTE_KEY)? {
+        Some(raw) if !raw.trim().is_empty() => {
+            let remote = serde_json::from_str(&raw).context("parse central remote setting")?;
+            Ok(Some(remote))
+        }
+        _ => Ok(None),
+    }
+}
+
+fn summarize(remote: &CentralRemote) -> CentralRemoteSummary {
+    let (auth_kind, username) = match &remote.auth {
+        RemoteAuth::None => ("none", None),
+        RemoteAuth::Token { username, .. } => ("token", username.clone()),
+        RemoteAuth::SshAgent { username } => ("ssh_agent", username.clone()),
+        RemoteAuth::SshKey { username, .. } => ("ssh_key", username.clone()),
+    };
+    CentralRemoteSummary {
+        url: remote.url.clone(),
+        branch: branch_of(remote).to_string(),
+        auth_kind: auth_kind.to_string(),
+        username,
+    }
+}
+
+fn branch_of(remote: &CentralRemote) -> &str {
+    remote
+        .branch
+        .as_deref()
+        .map(str::trim)
+        .filter(|b| !b.is_empty())
+        .unwrap_or(DEFAULT_BRANCH)
+}
+
+pub fn get_central_remote(store: &SkillStore) -> Result<Option<CentralRemoteSummary>> {
+    Ok(read_remote(store)?.as_ref().map(summarize))
+}
+
+/// Save (or with `None`, forget) the backup remote.
+pub fn set_central_remote(
+    store: &SkillStore,
+    remote: Option<CentralRemote>,
+) -> Result<Option<CentralRemoteSummary>> {
+    let Some(mut remote) = remote else {
+        store.delete_setting(CENTRAL_REMOTE_KEY)?;
+        return Ok(None);
+    };
+    remote.url = remote.url.trim().to_string();
+    if remote.url.is_empty() {
+        anyhow::bail!("remote URL is required");
+    }
+    if !git2::Reference::is_valid_name(&format!("refs/heads/{}", branch_of(&remote))) {
+        anyhow::bail!("invalid branch name: {}", branch_of(&remote));
+    }
+    if let RemoteAuth::Token { token, .. } = &remote.auth {
+        if token.trim().is_empty() {
+            anyhow::bail!("token is required for token auth");
+        }
+    }
+    store.set_setting(CENTRAL_REMOTE_KEY, &serde_json::to_string(&remote)?)?;
+    Ok(Some(summarize(&remote)))
+}
+
+fn configured(store: &SkillStore, central_root: &Path) -> Result<(CentralRemote, Repository)> {
+    ensure_central_repo_writable(store)?;
+    let remote = read_remote(store)?.ok_or_else(|| {
+        errors::coded(
+            errors::ErrorCode::CentralRemoteNotSet,
+            "no backup remote is configured",
+        )
+    })?;
+    let repo = open_history_repo(central_root)
+        .ok_or_else(|| anyhow::anyhow!("the central repo has no history to sync"))?;
+    Ok((remote, repo))
+}
+
+fn backup_remote<'r>(repo: &'r Repository, url: &str) -> Result<git2::Remote<'r>> {
+    match repo.find_remote(REMOTE_NAME) {
+        Ok(remote) if remote.url() == Some(url) => Ok(remote),
+        Ok(_) => {
+            repo.remote_set_url(REMOTE_NAME, url)?;
+            Ok(repo.find_remote(REMOTE_NAME)?)
+        }
+        Err(_) => Ok(repo.remote(REMOTE_NAME, url)?),
+    }
+}
+
+fn callbacks(auth: &RemoteAuth) -> RemoteCallbacks<'_> {
+    let mut callbacks = RemoteCallbacks::new();
+    // libgit2 keeps asking while credentials are rejected; one try each.
+    let mut attempts = 0;
+    callbacks.credentials(move |url, username_from_url, allowed| {
+        attempts += 1;
+        if attempts > 1 {
+            return Err(git2::Error::from_str("authentication failed"));
+        }
+        let ssh_user = |username: &Option<String>| {
+            username
+                .as_deref()
+                .or(username_from_url)
+                .unwrap_or("git")
+                .to_string()
+        };
+        match auth {
+            RemoteAuth::Token { username, token } => {
+                Cred::userpass_plaintext(username.as_deref().unwrap_or("x-access-token"), token)
+            }
+            RemoteAuth::SshAgent { username } => Cred::ssh_key_from_agent(&ssh_user(username)),
+            RemoteAuth::SshKey {
+                username,
+                key_path,
+                passphrase,
+            } => {
+                let key = resolve_key_path(key_path.as_deref())
+                    .map_err(|err| git2::Error::from_str(&err.to_string()))?;
+                Cred::ssh_key(
+                    &ssh_user(username),
+                    None,
+                    Path::new(&key),
+                    passphrase.as_deref(),
+                )
+            }
+            RemoteAuth::None if allowed.contains(CredentialType::SSH_KEY) => {
+                Cred::ssh_key_from_agent(&ssh_user(&None))
+            }
+            RemoteAuth::None => {
+                let config = git2::Config::open_default()?;
+                Cred::credential_helper(&config, url, username_from_url)
+            }
+        }
+    });
+    callbacks
+}
+
+/// Commit anything pending and push the central repo's history to the
+/// backup remote. Returns the pushed commit.
+pub fn push_central_repo(store: &SkillStore, central_root: &Path) -> Result<String> {
+    let (config, repo) = configured(store, central_root)?;
+    commit_all(&repo, "Snapshot before push")?;
+    let head = repo.head().context("the central repo has no commits yet")?;
+    let local_ref = head
+        .name()
+        .ok_or_else(|| anyhow::anyhow!("invalid HEAD reference"))?
+        .to_string();
+    let commit = head.peel_to_commit()?.id();
+
+    let mut remote = backup_remote(&repo, &config.url)?;
+    let refspec = format!("{}:refs/heads/{}", local_ref, branch_of(&config));
+    let mut rejected = None;
+    let pushed = {
+        let mut callbacks = callbacks(&config.auth);
+        callbacks.push_update_reference(|_, status| {
+            rejected = status.map(str::to_string);
+            Ok(())
+        });
+        let mut opts = PushOptions::new();
+        opts.remote_callbacks(callbacks);
+        remote.push(&[refspec.as_str()], Some(&mut opts))
+    };
+    match pushed {
+        Err(err) if err.code() == ErrorCode::NotFastForward => rejected = Some(err.to_string()),
+        other => other.with_context(|| format!("push to {}", config.url))?,
+    }
+    if let Some(reason) = rejected {
+        return Err(errors::coded(
+            errors::ErrorCode::CentralRemoteDiverged,
+            format!(
+                "the backup remote has changes this machine does not; pull first ({})",
+                reason
+            ),
+        ));
+    }
+    Ok(commit.to_string())
+}
+
+/// Fetch the backup remote and bring its skills into the central repo.
+///
+/// Each top-level skill directory is merged on its own: changes made only
+/// remotely are taken, and anything changed on both sides, deleted remotely
+/// or otherwise in doubt is left as it is locally and reported as a conflict.
+/// The store then gains records for new skills and fresh hashes for changed
+/// ones.
+pub fn pull_central_repo(store: &SkillStore, central_root: &Path) -> Result<PullResult> {
+    let (config, repo) = configured(store, central_root)?;
+    commit_all(&repo, "Snapshot before pull")?;
+    let branch = branch_of(&config).to_string();
+    {
+        let mut remote = backup_remote(&repo, &config.url)?;
+        let mut opts = FetchOptions::new();
+        opts.remote_callbacks(callbacks(&config.auth));
+        let refspec = format!("+refs/heads/{0}:refs/remotes/{1}/{0}", branch, REMOTE_NAME);
+        remote
+            .fetch(&[refspec.as_str()], Some(&mut opts), None)
+            .with_context(|| format!("fetch from {}", config.url))?;
+    }
+
+    let tracking = format!("refs/remotes/{}/{}", REMOTE_NAME, branch);
+    let Ok(theirs) = repo
+        .find_reference(&tracking)
+        .and_then(|r| r.peel_to_commit())
+    else {
+        return Ok(PullResult::default());
+    };
+    let ours = repo.head()?.peel_to_commit()?;
+    let mut result = PullResult {
+        commit: Some(ours.id().to_string()),
+        ..PullResult::default()
+    };
+    if ours.id() == theirs.id() || repo.graph_descendant_of(ours.id(), theirs.id())? {
+        return Ok(result);
+    }
+
+    let ours_tree = ours.tree()?;
+    let theirs_tree = theirs.tree()?;
+    let base_tree = repo
+        .merge_base(ours.id(), theirs.id())
+        .ok()
+        .and_then(|id| repo.find_commit(id).ok())
+        .and_then(|c| c.tree().ok());
+    let entry = |tree: Option<&git2::Tree>, name: &str| -> Option<Oid> {
+        tree.and_then(|t| t.get_name(name)).map(|e| e.id())
+    };
+    let names: BTreeSet<String> = ours_tree
+        .iter()
+        .chain(theirs_tree.iter())
+        .filter_map(|e| e.name().map(str::to_string))
+        .filter(|n| !n.starts_with('.'))
+        .collect();
+
+    let mut taken = Vec::new();
+    for name in names {
+        let o = entry(Some(&ours_tree), &name);
+        let t = entry(Some(&theirs_tree), &name);
+        let b = entry(base_tree.as_ref(), &name);
+        if t == o || t == b {
+            continue;
+        }
+        let reason = if o != b {
+            "both_changed"
+        } else if t.is_none() {
+            "deleted_remotely"
+        } else if is_untracked(central_root, Path::new(&name)) {
+            "git_clone"
+        } else {
+            taken.push(name);
+            continue;
+        };
+        result.conflicts.push(PullConflict {
+            name,
+            reason: reason.to_string(),
+        });
+    }
+
+    let mut builder = repo.treebuilder(Some(&ours_tree))?;
+    for name in &taken {
+        let their_entry = theirs_tree
+            .get_name(name)
+            .ok_or_else(|| anyhow::anyhow!("missing remote entry {}", name))?;
+        let path = central_root.join(name);
+        remove_path(&path)?;
+        let object = their_entry.to_object(&repo)?;
+        if let Some(tree) = object.as_tree() {
+            write_tree(&repo, tree, &path)?;
+        } else if let Some(blob) = object.as_blob() {
+            std::fs::write(&path, blob.content()).with_context(|| format!("write {:?}", path))?;
+        }
+        builder.insert(name, their_entry.id(), their_entry.filemode())?;
+    }
+    let tree = repo.find_tree(builder.write()?)?;
+    let mut index = repo.index()?;
+    index.read_tree(&tree)?;
+    index.write()?;
+    let sig = repo
+        .signature()
+        .map(|s| s.to_owned())
+        .or_else(|_| git2::Signature::now("Skills Hub", "skills-hub@localhost"))?;
+    let merged = repo.commit(
+        Some("HEAD"),
+        &sig,
+        &sig,
+        &format!("Merge {} from {}", branch, REMOTE_NAME),
+        &tree,
+        &[&ours, &theirs],
+    )?;
+    result.commit = Some(merged.to_string());
+
+    reconcile_store(store, central_root, &taken, &mut result)?;
+    Ok(result)
+}
+
+fn remove_path(path: &Path) -> Result<()> {
+    match std::fs::symlink_metadata(path) {
+        Ok(meta) if meta.is_dir() => {
+            std::fs::remove_dir_all(path).with_context(|| format!("remove {:?}", path))
+        }
+        Ok(_) => std::fs::remove_file(path).with_context(|| format!("remove {:?}", path)),
+        Err(_) => Ok(()),
+    }
+}
+
+fn reconcile_store(
+    store: &SkillStore,
+    central_root: &Path,
+    taken: &[String],
+    result: &mut PullResult,
+) -> Result<()> {
+    let skills = store.list_skills()?;
+    let now = now_ms();
+    for name in taken {
+        let path = central_root.join(name);
+        if !path.is_dir() {
+            continue;
+        }
+        let central_path = path.to_string_lossy().to_string();
+        let content_hash = hash_dir(&path).ok();
+        if let Some(skill) = skills.iter().find(|s| s.central_path == central_path) {
+            store.upsert_skill(&SkillRecord {
+                content_hash,
+                updated_at: now,
+                ..skill.clone()
+            })?;
+            result.updated.push(name.clone());
+        } else if path.join("SKILL.md").is_file() {
+            store.upsert_skill(&SkillRecord {
+                id: Uuid::new_v4().to_string(),
+                name: name.clone(),
+                source_type: "local".to_string(),
+                source_ref: None,
+                source_revision: None,
+                central_path,
+                content_hash,
+                created_at: now,
+                updated_at: now,
+                last_sync_at: None,
+                last_seen_at: now,
+                status: "ok".to_string(),
+                group_name: None,
+            })?;
+            result.added.push(name.clone());
+        } else {
+            // Agents and commands look alike on disk; let the user add them.
+            result.conflicts.push(PullConflict {
+                name: name.clone(),
+                reason: "unknown_type".to_string(),
+            });
+        }
+    }
+
+    // Records whose files exist neither here nor on the remote.
+    for skill in &skills {
+        let path = PathBuf::from(&skill.central_path);
+        if path.parent() != Some(central_root) || path.exists() {
+            continue;
+        }
+        result.conflicts.push(PullConflict {
+            name: skill.name.clone(),
+            reason: "missing_locally".to_string(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "tests/central_remote.rs"]
+mod tests;
@@ -2,29 +2,43 @@ use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use dirs::home_dir;
-use tauri::Manager;
+use uuid::Uuid;
 
-use super::skill_store::SkillStore;
+use super::app_paths::AppPaths;
+use super::cloud_folders::warn_if_cloud_folder;
+use super::errors::{coded, ErrorCode};
+use super::now_ms;
+use super::profiles::central_dir_name;
+use super::skill_events;
+use super::skill_store::{SkillRecord, SkillStore};
 
 const CENTRAL_DIR_NAME: &str = ".skillshub";
+pub const CENTRAL_REPO_PATH_KEY: &str = "central_repo_path";
+/// Set when the central repo is a read-only share maintained by someone else.
+pub const CENTRAL_REPO_SHARED_KEY: &str = "central_repo_shared";
+/// Source type of skills indexed from a shared central repo.
+pub const SHARED_SOURCE_TYPE: &str = "shared";
 
-pub fn resolve_central_repo_path<R: tauri::Runtime>(
-    app: &tauri::AppHandle<R>,
-    store: &SkillStore,
-) -> Result<PathBuf> {
-    if let Some(path) = store.get_setting("central_repo_path")? {
-        return Ok(PathBuf::from(path));
+/// The central repo of the active profile: the configured path, or a
+/// per-profile folder in the home directory.
+pub fn resolve_central_repo_path(paths: &AppPaths, store: &SkillStore) -> Result<PathBuf> {
+    if let Some(path) = store
+        .get_setting(CENTRAL_REPO_PATH_KEY)?
+        .filter(|p| !p.is_empty())
+    {
+        let path = PathBuf::from(path);
+        warn_if_cloud_folder(&path);
+        return Ok(path);
     }
 
+    let dir_name = central_dir_name(CENTRAL_DIR_NAME);
     if let Some(home) = home_dir() {
-        return Ok(home.join(CENTRAL_DIR_NAME));
+        let path = home.join(dir_name);
+        warn_if_cloud_folder(&path);
+        return Ok(path);
     }
 
-    let base = app
-        .path()
-        .app_data_dir()
-        .context("failed to resolve app data dir")?;
-    Ok(base.join(CENTRAL_DIR_NAME))
+    Ok(paths.data_dir.join(dir_name))
 }
 
 pub fn ensure_central_repo(path: &Path) -> Result<()> {
@@ -32,6 +46,128 @@ pub fn ensure_central_repo(path: &Path) -> Result<()> {
     Ok(())
 }
 
+pub fn is_shared_central_repo(store: &SkillStore) -> bool {
+    matches!(store.get_setting(CENTRAL_REPO_SHARED_KEY), Ok(Some(v)) if v == "1")
+}
+
+/// Fails with [`ErrorCode::CentralRepoReadOnly`] while a shared central repo is in
+/// use; installs, updates and deletes would write into someone else's share.
+pub fn ensure_central_repo_writable(store: &SkillStore) -> Result<()> {
+    if is_shared_central_repo(store) {
+        return Err(coded(
+            ErrorCode::CentralRepoReadOnly,
+            "the central repo is a read-only shared folder; skills are installed and updated by its maintainer",
+        ));
+    }
+    Ok(())
+}
+
+/// Central repo directory to install into, created when missing.
+pub fn writable_central_repo(paths: &AppPaths, store: &SkillStore) -> Result<PathBuf> {
+    ensure_central_repo_writable(store)?;
+    let path = resolve_central_repo_path(paths, store)?;
+    ensure_central_repo(&path)?;
+    if let Err(err) = super::central_history::ensure_history_repo(&path) {
+        log::warn!("[central_repo] history unavailable: {:#}", err);
+    }
+    Ok(path)
+}
+
+/// Point the central repo at a (typically read-only mounted) shared folder
+/// and index the skills in it. Nothing is written to the share.
+pub fn use_shared_central_repo(store: &SkillStore, path: &Path) -> Result<Vec<SkillRecord>> {
+    if !path.is_absolute() {
+        anyhow::bail!("shared repo path must be absolute");
+    }
+    if !path.is_dir() {
+        anyhow::bail!("shared repo not found: {:?}", path);
+    }
+    store.set_setting(CENTRAL_REPO_PATH_KEY, path.to_string_lossy().as_ref())?;
+    store.set_setting(CENTRAL_REPO_SHARED_KEY, "1")?;
+    refresh_shared_skills(store)
+}
+
+/// Go back to a private central repo at the default location. Skills indexed
+/// from the share are forgotten; copies already synced into tools stay.
+pub fn leave_shared_central_repo(store: &SkillStore) -> Result<()> {
+    for skill in store.list_skills()? {
+        if skill.source_type == SHARED_SOURCE_TYPE {
+            store.delete_skill(&skill.id)?;
+            skill_events::skill_deleted(&skill.id, &skill.name);
+        }
+    }
+    store.delete_setting(CENTRAL_REPO_SHARED_KEY)?;
+    store.delete_setting(CENTRAL_REPO_PATH_KEY)?;
+    Ok(())
+}
+
+/// Re-read the shared repo: every top-level folder with a SKILL.md becomes a
+/// managed skill, and skills the maintainer removed are dropped.
+pub fn refresh_shared_skills(store: &SkillStore) -> Result<Vec<SkillRecord>> {
+    if !is_shared_central_repo(store) {
+        return Ok(Vec::new());
+    }
+    let root = store
+        .get_setting(CENTRAL_REPO_PATH_KEY)?
+        .map(PathBuf::from)
+        .ok_or_else(|| anyhow::anyhow!("shared repo path not set"))?;
+    let mut dirs: Vec<PathBuf> = std::fs::read_dir(&root)
+        .with_context(|| format!("read shared repo {:?}", root))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.join("SKILL.md").is_file()
+                && !p
+                    .file_name()
+                    .is_some_and(|n| n.to_string_lossy().starts_with('.'))
+        })
+        .collect();
+    dirs.sort();
+
+    let now = now_ms();
+    let existing = store.list_skills()?;
+    let mut shared = Vec::new();
+    for dir in &dirs {
+        let central_path = dir.to_string_lossy().to_string();
+        let record = match existing.iter().find(|s| s.central_path == central_path) {
+            Some(skill) => SkillRecord {
+                last_seen_at: now,
+                ..skill.clone()
+            },
+            None => SkillRecord {
+                id: Uuid::new_v4().to_string(),
+                name: dir
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                source_type: SHARED_SOURCE_TYPE.to_string(),
+                source_ref: Some(root.to_string_lossy().to_string()),
+                source_revision: None,
+                central_path,
+                content_hash: None,
+                created_at: now,
+                updated_at: now,
+                last_sync_at: None,
+                last_seen_at: now,
+                status: "ok".to_string(),
+                group_name: None,
+            },
+        };
+        let is_new = !existing.iter().any(|s| s.id == record.id);
+        store.upsert_skill(&record)?;
+        if is_new {
+            skill_events::skill_installed(&record.id, &record.name);
+        }
+        shared.push(record);
+    }
+    for skill in existing {
+        if skill.source_type == SHARED_SOURCE_TYPE && !shared.iter().any(|s| s.id == skill.id) {
+            store.delete_skill(&skill.id)?;
+            skill_events::skill_deleted(&skill.id, &skill.name);
+        }
+    }
+    Ok(shared)
+}
+
 #[cfg(test)]
 #[path = "tests/central_repo.rs"]
 mod tests;
@@ -1,16 +1,36 @@
+use std::collections::{HashMap, VecDeque};
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
-use reqwest::blocking::Client;
 use serde::{Deserialize, Serialize};
 
+use super::app_paths::AppPaths;
+use super::content_hash::is_ignored;
+use super::errors::{coded, ErrorCode};
+use super::github_search::{github_send, rate_limit_error};
+use super::http_client::{self, http_client};
+use super::installer::{install_local_skill, InstallResult};
+use super::offline_cache::{self, RESPONSE_TTL};
+use super::registry_auth::{authorize, has_registry_auth, set_registry_auth, RegistryAuth};
+use super::skill_drift::record_skill_manifest;
+use super::skill_metadata::refresh_skill_metadata;
+use super::skill_store::{SkillRecord, SkillStore};
+use super::source_providers::SourceProvider;
+use super::temp_cleanup::operation_temp_dir;
+
 const CLAWHUB_BASE_URL: &str = "https://clawhub.ai";
 
 // ── Search ──────────────────────────────────────────────────────────
 
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct SearchResponse {
     results: Vec<SearchResultItem>,
+    #[serde(default)]
+    next_cursor: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -24,7 +44,7 @@ struct SearchResultItem {
     updated_at: Option<i64>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ClawHubSkill {
     pub slug: String,
@@ -33,46 +53,206 @@ pub struct ClawHubSkill {
     pub version: Option<String>,
     pub score: f64,
     pub updated_at: Option<i64>,
+    /// Only filled in by browse listings.
+    pub downloads: Option<u64>,
+    pub stars: Option<u64>,
 }
 
+/// Where a search page starts: an opaque cursor from the previous page when
+/// the server hands one out, otherwise a plain offset.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchPageRequest {
+    pub offset: Option<usize>,
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClawHubSearchPage {
+    pub results: Vec<ClawHubSkill>,
+    /// Pass back as `cursor` to load the next page.
+    pub next_cursor: Option<String>,
+    /// Pass back as `offset` when the server does not use cursors.
+    pub next_offset: Option<usize>,
+}
+
+const MAX_SEARCH_PAGE: usize = 50;
+
 pub fn search_clawhub(query: &str, limit: usize) -> Result<Vec<ClawHubSkill>> {
-    search_clawhub_inner(CLAWHUB_BASE_URL, query, limit)
+    Ok(search_clawhub_page(query, limit, &SearchPageRequest::default())?.results)
+}
+
+pub fn search_clawhub_page(
+    query: &str,
+    limit: usize,
+    page: &SearchPageRequest,
+) -> Result<ClawHubSearchPage> {
+    let key = format!("{}\n{}\n{:?}\n{:?}", query, limit, page.cursor, page.offset);
+    offline_cache::cached("clawhub-search", &key, RESPONSE_TTL, || {
+        search_clawhub_inner(CLAWHUB_BASE_URL, query, limit, page)
+    })
 }
 
-fn search_clawhub_inner(base_url: &str, query: &str, limit: usize) -> Result<Vec<ClawHubSkill>> {
-    let client = Client::new();
+fn search_clawhub_inner(
+    base_url: &str,
+    query: &str,
+    limit: usize,
+    page: &SearchPageRequest,
+) -> Result<ClawHubSearchPage> {
+    let client = http_client()?;
     let base_url = base_url.trim_end_matches('/');
-    let url = format!(
+    let limit = limit.clamp(1, MAX_SEARCH_PAGE);
+    let offset = page.offset.unwrap_or(0);
+    let mut url = format!(
         "{}/api/v1/search?q={}&limit={}",
         base_url,
         urlencoding::encode(query),
-        limit.clamp(1, 50)
+        limit
     );
+    match page.cursor.as_deref().filter(|c| !c.is_empty()) {
+        Some(cursor) => url.push_str(&format!("&cursor={}", urlencoding::encode(cursor))),
+        None if offset > 0 => url.push_str(&format!("&offset={}", offset)),
+        None => {}
+    }
 
-    let response = client
-        .get(url)
-        .header("User-Agent", "skills-hub")
-        .send()
+    let response = http_client::send(authorize(client.get(&url), &url)?)
         .context("ClawHub search request failed")?
         .error_for_status()
         .context("ClawHub search returned error")?;
 
     let result: SearchResponse = response.json().context("parse ClawHub search response")?;
+    // A full page without a cursor may have more behind it.
+    let next_offset = (result.next_cursor.is_none() && result.results.len() >= limit)
+        .then_some(offset + result.results.len());
+
+    Ok(ClawHubSearchPage {
+        results: result
+            .results
+            .into_iter()
+            .filter_map(|item| {
+                Some(ClawHubSkill {
+                    slug: item.slug?,
+                    display_name: item.display_name.unwrap_or_default(),
+                    summary: item.summary,
+                    version: item.version,
+                    score: item.score,
+                    updated_at: item.updated_at,
+                    downloads: None,
+                    stars: None,
+                })
+            })
+            .collect(),
+        next_cursor: result.next_cursor,
+        next_offset,
+    })
+}
 
-    Ok(result
-        .results
-        .into_iter()
-        .filter_map(|item| {
-            Some(ClawHubSkill {
-                slug: item.slug?,
-                display_name: item.display_name.unwrap_or_default(),
-                summary: item.summary,
-                version: item.version,
-                score: item.score,
-                updated_at: item.updated_at,
+// ── Browse ──────────────────────────────────────────────────────────
+
+/// Listing orders understood by ClawHub's browse API.
+pub const BROWSE_SORTS: &[&str] = &["trending", "newest", "updated", "downloads", "stars"];
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BrowseResponse {
+    #[serde(alias = "results", alias = "skills")]
+    items: Vec<BrowseItem>,
+    #[serde(default)]
+    next_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BrowseItem {
+    slug: Option<String>,
+    display_name: Option<String>,
+    summary: Option<String>,
+    #[serde(default)]
+    latest_version: Option<VersionInfo>,
+    #[serde(default)]
+    stats: Option<StatsInfo>,
+    updated_at: Option<i64>,
+}
+
+/// Trending / newest / ... skills, optionally within one category (tag), a
+/// page at a time. No search query needed.
+pub fn browse_clawhub(
+    category: Option<&str>,
+    sort: &str,
+    limit: usize,
+    page: &SearchPageRequest,
+) -> Result<ClawHubSearchPage> {
+    let key = format!(
+        "{:?}\n{}\n{}\n{:?}\n{:?}",
+        category, sort, limit, page.cursor, page.offset
+    );
+    offline_cache::cached("clawhub-browse", &key, RESPONSE_TTL, || {
+        browse_clawhub_inner(CLAWHUB_BASE_URL, category, sort, limit, page)
+    })
+}
+
+fn browse_clawhub_inner(
+    base_url: &str,
+    category: Option<&str>,
+    sort: &str,
+    limit: usize,
+    page: &SearchPageRequest,
+) -> Result<ClawHubSearchPage> {
+    if !BROWSE_SORTS.contains(&sort) {
+        anyhow::bail!(
+            "unknown sort: {} (expected one of {})",
+            sort,
+            BROWSE_SORTS.join(", ")
+        );
+    }
+    let client = http_client()?;
+    let limit = limit.clamp(1, MAX_SEARCH_PAGE);
+    let offset = page.offset.unwrap_or(0);
+    let mut url = format!(
+        "{}/api/v1/skills?sort={}&limit={}",
+        base_url.trim_end_matches('/'),
+        sort,
+        limit
+    );
+    if let Some(category) = category.map(str::trim).filter(|c| !c.is_empty()) {
+        url.push_str(&format!("&tag={}", urlencoding::encode(category)));
+    }
+    match page.cursor.as_deref().filter(|c| !c.is_empty()) {
+        Some(cursor) => url.push_str(&format!("&cursor={}", urlencoding::encode(cursor))),
+        None if offset > 0 => url.push_str(&format!("&offset={}", offset)),
+        None => {}
+    }
+
+    let response = http_client::send(authorize(client.get(&url), &url)?)
+        .context("ClawHub browse request failed")?
+        .error_for_status()
+        .context("ClawHub browse returned error")?;
+    let result: BrowseResponse = response.json().context("parse ClawHub browse response")?;
+    let next_offset = (result.next_cursor.is_none() && result.items.len() >= limit)
+        .then_some(offset + result.items.len());
+
+    Ok(ClawHubSearchPage {
+        results: result
+            .items
+            .into_iter()
+            .filter_map(|item| {
+                let slug = item.slug?;
+                Some(ClawHubSkill {
+                    display_name: item.display_name.unwrap_or_else(|| slug.clone()),
+                    slug,
+                    summary: item.summary,
+                    version: item.latest_version.map(|v| v.version),
+                    score: 0.0,
+                    updated_at: item.updated_at,
+                    downloads: item.stats.as_ref().and_then(|s| s.downloads),
+                    stars: item.stats.as_ref().and_then(|s| s.stars),
+                })
             })
-        })
-        .collect())
+            .collect(),
+        next_cursor: result.next_cursor,
+        next_offset,
+    })
 }
 
 // ── Get Skill Detail ────────────────────────────────────────────────
@@ -124,7 +304,7 @@ struct OwnerInfo {
     image: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ClawHubSkillDetail {
     pub slug: String,
@@ -146,18 +326,104 @@ pub struct ClawHubSkillDetail {
 }
 
 pub fn get_clawhub_skill(slug: &str) -> Result<ClawHubSkillDetail> {
-    get_clawhub_skill_inner(CLAWHUB_BASE_URL, slug)
+    if let Some(hit) = with_detail_cache(|cache| cache.get(slug, Instant::now())) {
+        return Ok(hit);
+    }
+    let detail = offline_cache::cached("clawhub-skill", slug, RESPONSE_TTL, || {
+        get_clawhub_skill_inner(CLAWHUB_BASE_URL, slug)
+    })?;
+    with_detail_cache(|cache| cache.insert(slug, detail.clone(), Instant::now()));
+    Ok(detail)
+}
+
+/// Latest published version of `slug`, always fetched fresh; the detail
+/// cache is refreshed with the answer.
+pub fn latest_clawhub_version(slug: &str) -> Result<Option<String>> {
+    let detail = get_clawhub_skill_inner(CLAWHUB_BASE_URL, slug)?;
+    let version = detail.version.clone();
+    with_detail_cache(|cache| cache.insert(slug, detail, Instant::now()));
+    Ok(version)
+}
+
+/// Drop a cached detail so the next lookup refetches (e.g. after install,
+/// when install counters and the latest version may have moved).
+pub fn invalidate_clawhub_skill(slug: &str) {
+    with_detail_cache(|cache| cache.remove(slug));
+}
+
+// ── Detail cache ─────────────────────────────────────────────────────
+
+const DETAIL_CACHE_CAPACITY: usize = 64;
+const DETAIL_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+static DETAIL_CACHE: OnceLock<Mutex<DetailCache>> = OnceLock::new();
+
+fn with_detail_cache<T>(f: impl FnOnce(&mut DetailCache) -> T) -> T {
+    let lock = DETAIL_CACHE
+        .get_or_init(|| Mutex::new(DetailCache::new(DETAIL_CACHE_CAPACITY, DETAIL_CACHE_TTL)));
+    let mut guard = lock.lock().unwrap_or_else(|e| e.into_inner());
+    f(&mut guard)
+}
+
+/// Small TTL + LRU cache keyed by slug. `order` holds slugs from least to most
+/// recently used; it stays tiny so linear scans are fine.
+struct DetailCache {
+    capacity: usize,
+    ttl: Duration,
+    entries: HashMap<String, (Instant, ClawHubSkillDetail)>,
+    order: VecDeque<String>,
+}
+
+impl DetailCache {
+    fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            capacity,
+            ttl,
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    fn get(&mut self, slug: &str, now: Instant) -> Option<ClawHubSkillDetail> {
+        let fresh = match self.entries.get(slug) {
+            Some((fetched_at, _)) => now.duration_since(*fetched_at) < self.ttl,
+            None => return None,
+        };
+        if !fresh {
+            self.remove(slug);
+            return None;
+        }
+        self.touch(slug);
+        self.entries.get(slug).map(|(_, detail)| detail.clone())
+    }
+
+    fn insert(&mut self, slug: &str, detail: ClawHubSkillDetail, now: Instant) {
+        self.entries.insert(slug.to_string(), (now, detail));
+        self.touch(slug);
+        while self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+
+    fn remove(&mut self, slug: &str) {
+        self.entries.remove(slug);
+        self.order.retain(|s| s != slug);
+    }
+
+    fn touch(&mut self, slug: &str) {
+        self.order.retain(|s| s != slug);
+        self.order.push_back(slug.to_string());
+    }
 }
 
 fn get_clawhub_skill_inner(base_url: &str, slug: &str) -> Result<ClawHubSkillDetail> {
-    let client = Client::new();
+    let client = http_client()?;
     let base_url = base_url.trim_end_matches('/');
     let url = format!("{}/api/v1/skills/{}", base_url, urlencoding::encode(slug));
 
-    let response = client
-        .get(url)
-        .header("User-Agent", "skills-hub")
-        .send()
+    let response = http_client::send(authorize(client.get(&url), &url)?)
         .context("ClawHub get skill request failed")?
         .error_for_status()
         .context("ClawHub get skill returned error")?;
@@ -221,43 +487,401 @@ pub struct SkillFileEntry {
     pub is_dir: bool,
 }
 
-pub fn get_github_tree(owner: &str, repo: &str) -> Result<Vec<SkillFileEntry>> {
-    let client = Client::new();
+#[derive(Debug, Deserialize)]
+struct GitHubRepoMeta {
+    default_branch: String,
+}
+
+/// Every file and directory of a GitHub repo at `git_ref` (branch, tag or
+/// commit), or at the repo's default branch when no ref is given.
+pub fn get_github_tree(
+    provider: &SourceProvider,
+    owner: &str,
+    repo: &str,
+    git_ref: Option<&str>,
+) -> Result<Vec<SkillFileEntry>> {
+    get_github_tree_inner(&provider.api_url, owner, repo, git_ref)
+}
+
+fn get_github_tree_inner(
+    base_url: &str,
+    owner: &str,
+    repo: &str,
+    git_ref: Option<&str>,
+) -> Result<Vec<SkillFileEntry>> {
+    let client = http_client()?;
+    let repo_url = format!(
+        "{}/repos/{}/{}",
+        base_url.trim_end_matches('/'),
+        urlencoding::encode(owner),
+        urlencoding::encode(repo)
+    );
+
+    let git_ref = match git_ref.map(str::trim).filter(|r| !r.is_empty()) {
+        Some(git_ref) => git_ref.to_string(),
+        None => {
+            let response = github_send(&client, &repo_url, "GitHub repo")?;
+            if let Some(err) = rate_limit_error(&response) {
+                return Err(err);
+            }
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                anyhow::bail!("repository not found on GitHub: {}/{}", owner, repo);
+            }
+            let meta: GitHubRepoMeta = response
+                .error_for_status()
+                .context("GitHub repo returned error")?
+                .json()
+                .context("parse GitHub repo response")?;
+            meta.default_branch
+        }
+    };
+
+    // Branch names may contain '/', which the trees endpoint takes as-is.
+    let encoded_ref = git_ref
+        .split('/')
+        .map(|segment| urlencoding::encode(segment).into_owned())
+        .collect::<Vec<_>>()
+        .join("/");
+    let url = format!("{}/git/trees/{}?recursive=1", repo_url, encoded_ref);
+    let response = github_send(&client, &url, "GitHub tree")?;
+    if let Some(err) = rate_limit_error(&response) {
+        return Err(err);
+    }
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        anyhow::bail!("ref '{}' not found in {}/{}", git_ref, owner, repo);
+    }
+    let result: GitHubTreeResponse = response
+        .error_for_status()
+        .context("GitHub tree returned error")?
+        .json()
+        .context("parse GitHub tree response")?;
+
+    Ok(result
+        .tree
+        .into_iter()
+        .map(|e| SkillFileEntry {
+            path: e.path,
+            is_dir: e.entry_type == "tree",
+        })
+        .collect())
+}
+
+/// Files larger than this are cut off; previews only need the start.
+const MAX_GITHUB_FILE_BYTES: usize = 512 * 1024;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitHubFile {
+    pub path: String,
+    /// The branch, tag or commit asked for; `None` is the default branch.
+    pub git_ref: Option<String>,
+    pub content: String,
+    pub truncated: bool,
+}
+
+/// One file of a GitHub repo, e.g. a skill's SKILL.md, without cloning.
+pub fn get_github_file(
+    provider: &SourceProvider,
+    owner: &str,
+    repo: &str,
+    path: &str,
+    git_ref: Option<&str>,
+) -> Result<GitHubFile> {
+    get_github_file_inner(&provider.api_url, owner, repo, path, git_ref)
+}
+
+fn get_github_file_inner(
+    base_url: &str,
+    owner: &str,
+    repo: &str,
+    path: &str,
+    git_ref: Option<&str>,
+) -> Result<GitHubFile> {
+    let path = path.trim().trim_matches('/');
+    if path.is_empty() {
+        anyhow::bail!("file path is required");
+    }
+    let encoded_path = path
+        .split('/')
+        .map(|segment| urlencoding::encode(segment).into_owned())
+        .collect::<Vec<_>>()
+        .join("/");
+    let mut url = format!(
+        "{}/repos/{}/{}/contents/{}",
+        base_url.trim_end_matches('/'),
+        urlencoding::encode(owner),
+        urlencoding::encode(repo),
+        encoded_path
+    );
+    let git_ref = git_ref.map(str::trim).filter(|r| !r.is_empty());
+    if let Some(git_ref) = git_ref {
+        url.push_str(&format!("?ref={}", urlencoding::encode(git_ref)));
+    }
+
+    // The raw media type returns the file itself instead of base64 JSON.
+    let request = http_client()?
+        .get(&url)
+        .header("Accept", "application/vnd.github.raw");
+    let response =
+        http_client::send(authorize(request, &url)?).context("GitHub file request failed")?;
+    if let Some(err) = rate_limit_error(&response) {
+        return Err(err);
+    }
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        anyhow::bail!("file not found on GitHub: {}/{}/{}", owner, repo, path);
+    }
+    let mut raw = Vec::new();
+    response
+        .error_for_status()
+        .context("GitHub file returned error")?
+        .take(MAX_GITHUB_FILE_BYTES as u64 + 1)
+        .read_to_end(&mut raw)
+        .context("read GitHub file")?;
+    let truncated = raw.len() > MAX_GITHUB_FILE_BYTES;
+    raw.truncate(MAX_GITHUB_FILE_BYTES);
+    Ok(GitHubFile {
+        path: path.to_string(),
+        git_ref: git_ref.map(str::to_string),
+        content: String::from_utf8_lossy(&raw).into_owned(),
+        truncated,
+    })
+}
+
+// ── Publish ─────────────────────────────────────────────────────────
 
-    // try main first, then master
-    for branch in &["main", "master"] {
-        let url = format!(
-            "https://api.github.com/repos/{}/{}/git/trees/{}?recursive=1",
-            urlencoding::encode(owner),
-            urlencoding::encode(repo),
-            branch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClawHubPublishResult {
+    pub slug: String,
+    pub version: String,
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+/// Save (or with `None`, clear) the API token used for ClawHub requests.
+/// It is stored as bearer credentials for the ClawHub origin.
+pub fn set_clawhub_token(store: &SkillStore, token: Option<String>) -> Result<bool> {
+    let auth = token
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .map(|token| RegistryAuth::Bearer { token });
+    set_registry_auth(store, CLAWHUB_BASE_URL, auth)?;
+    Ok(has_clawhub_token())
+}
+
+pub fn has_clawhub_token() -> bool {
+    has_registry_auth(CLAWHUB_BASE_URL)
+}
+
+/// Slug a skill is published under: the one it was installed from, or its
+/// name lowercased with anything but `[a-z0-9-]` turned into dashes.
+pub fn publish_slug(skill: &SkillRecord) -> String {
+    if let Some(slug) = skill
+        .source_ref
+        .as_deref()
+        .and_then(|r| r.strip_prefix("clawhub://"))
+    {
+        return slug.to_string();
+    }
+    let mut slug = String::new();
+    for c in skill.name.trim().to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_matches('-').to_string()
+}
+
+/// Zip the skill's central directory and upload it as `version`.
+pub fn publish_clawhub_skill(
+    skill: &SkillRecord,
+    version: &str,
+    changelog: &str,
+) -> Result<ClawHubPublishResult> {
+    if !has_clawhub_token() {
+        return Err(coded(
+            ErrorCode::ClawhubTokenRequired,
+            "set a ClawHub API token before publishing",
+        ));
+    }
+    let result = publish_inner(CLAWHUB_BASE_URL, skill, version, changelog)?;
+    invalidate_clawhub_skill(&result.slug);
+    Ok(result)
+}
+
+fn publish_inner(
+    base_url: &str,
+    skill: &SkillRecord,
+    version: &str,
+    changelog: &str,
+) -> Result<ClawHubPublishResult> {
+    let version = version.trim();
+    if !is_valid_version(version) {
+        anyhow::bail!("invalid version (expected e.g. 1.2.0): {}", version);
+    }
+    let slug = publish_slug(skill);
+    if slug.is_empty() {
+        anyhow::bail!(
+            "cannot derive a ClawHub slug from skill name: {}",
+            skill.name
         );
+    }
+    let dir = Path::new(&skill.central_path);
+    if !dir.join("SKILL.md").is_file() {
+        anyhow::bail!("SKILL.md not found in {}", skill.central_path);
+    }
+    let archive = zip_skill_dir(dir)?;
+
+    let boundary = format!("skillshub-{}", uuid::Uuid::new_v4().simple());
+    let mut body = Vec::with_capacity(archive.len() + 1024);
+    for (name, value) in [
+        ("slug", slug.as_str()),
+        ("version", version),
+        ("changelog", changelog.trim()),
+    ] {
+        write!(
+            body,
+            "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+            boundary, name, value
+        )?;
+    }
+    write!(
+        body,
+        "--{}\r\nContent-Disposition: form-data; name=\"archive\"; filename=\"{}.zip\"\r\nContent-Type: application/zip\r\n\r\n",
+        boundary, slug
+    )?;
+    body.extend_from_slice(&archive);
+    write!(body, "\r\n--{}--\r\n", boundary)?;
+
+    let url = format!("{}/api/v1/publish", base_url.trim_end_matches('/'));
+    let response = authorize(http_client()?.post(&url), &url)?
+        .header(
+            "Content-Type",
+            format!("multipart/form-data; boundary={}", boundary),
+        )
+        .body(body)
+        .send()
+        .context("ClawHub publish request failed")?;
+    let status = response.status();
+    if !status.is_success() {
+        let detail = response.text().unwrap_or_default();
+        anyhow::bail!("ClawHub publish returned {}: {}", status, detail.trim());
+    }
+    // Older servers answer with an empty body; fall back to what was sent.
+    let text = response.text().context("read ClawHub publish response")?;
+    Ok(
+        serde_json::from_str(&text).unwrap_or_else(|_| ClawHubPublishResult {
+            slug,
+            version: version.to_string(),
+            url: None,
+        }),
+    )
+}
 
-        let response = client
-            .get(&url)
-            .header("User-Agent", "skills-hub")
-            .send()
-            .context("GitHub tree request failed")?;
-
-        if response.status().is_success() {
-            let result: GitHubTreeResponse =
-                response.json().context("parse GitHub tree response")?;
-
-            let entries: Vec<SkillFileEntry> = result
-                .tree
-                .into_iter()
-                .map(|e| SkillFileEntry {
-                    path: e.path,
-                    is_dir: e.entry_type == "tree",
-                })
-                .collect();
+fn is_valid_version(version: &str) -> bool {
+    let core = version.split(['-', '+']).next().unwrap_or_default();
+    let parts: Vec<&str> = core.split('.').collect();
+    parts.len() == 3
+        && parts
+            .iter()
+            .all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()))
+}
 
-            return Ok(entries);
+fn zip_skill_dir(dir: &Path) -> Result<Vec<u8>> {
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    for entry in walkdir::WalkDir::new(dir)
+        .follow_links(false)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| !is_ignored(e))
+    {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
         }
-        // if not success, try next branch
+        let rel = entry
+            .path()
+            .strip_prefix(dir)
+            .with_context(|| format!("strip prefix {:?}", entry.path()))?
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let bytes =
+            std::fs::read(entry.path()).with_context(|| format!("read file {:?}", entry.path()))?;
+        zip.start_file(rel, options)?;
+        zip.write_all(&bytes)?;
     }
+    Ok(zip.finish().context("finish skill archive")?.into_inner())
+}
+
+// ── Readme ──────────────────────────────────────────────────────────
+
+/// Larger instruction files are cut off; the preview is for reading.
+const MAX_README_BYTES: usize = 256 * 1024;
 
-    anyhow::bail!("Could not fetch tree from GitHub (tried main and master branches)")
+#[derive(Clone, Debug, Serialize)]
+pub struct ClawHubReadme {
+    pub slug: String,
+    pub version: Option<String>,
+    /// Path of the file inside the skill archive, e.g. `SKILL.md`.
+    pub path: String,
+    pub content: String,
+    pub truncated: bool,
+}
+
+/// The SKILL.md (or README.md when there is none) of a ClawHub skill,
+/// read from its download archive without installing anything.
+pub fn get_clawhub_skill_readme(slug: &str, version: Option<&str>) -> Result<ClawHubReadme> {
+    get_readme_inner(CLAWHUB_BASE_URL, slug, version)
+}
+
+fn get_readme_inner(base_url: &str, slug: &str, version: Option<&str>) -> Result<ClawHubReadme> {
+    let bytes = download_archive(base_url, slug, version)?;
+    let mut archive =
+        zip::ZipArchive::new(std::io::Cursor::new(&bytes)).context("open zip archive")?;
+
+    // Prefer the shallowest SKILL.md; archives may wrap the skill in a folder.
+    let mut best: Option<(usize, usize, String)> = None;
+    for name in archive.file_names() {
+        if name.starts_with("__MACOSX") {
+            continue;
+        }
+        let file_name = name.rsplit('/').next().unwrap_or(name);
+        let rank = if file_name.eq_ignore_ascii_case("SKILL.md") {
+            0
+        } else if file_name.eq_ignore_ascii_case("README.md") {
+            1
+        } else {
+            continue;
+        };
+        let key = (rank, name.matches('/').count(), name.to_string());
+        if best.as_ref().map_or(true, |b| key < *b) {
+            best = Some(key);
+        }
+    }
+    let (_, _, path) = best.ok_or_else(|| anyhow::anyhow!("skill archive has no SKILL.md"))?;
+
+    let mut raw = Vec::new();
+    archive
+        .by_name(&path)
+        .context("read SKILL.md from archive")?
+        .take(MAX_README_BYTES as u64 + 1)
+        .read_to_end(&mut raw)
+        .context("read SKILL.md from archive")?;
+    let truncated = raw.len() > MAX_README_BYTES;
+    raw.truncate(MAX_README_BYTES);
+    Ok(ClawHubReadme {
+        slug: slug.to_string(),
+        version: version.map(str::to_string),
+        path,
+        content: String::from_utf8_lossy(&raw).into_owned(),
+        truncated,
+    })
 }
 
 // ── Download + Extract ──────────────────────────────────────────────
@@ -271,13 +895,67 @@ pub fn download_and_extract_clawhub_skill(
     download_and_extract_inner(CLAWHUB_BASE_URL, slug, version, target_dir)
 }
 
+/// Download `slug` and install it as a managed skill tracked as
+/// `clawhub://<slug>`. Without a version the latest one is pinned so later
+/// update checks can compare against it.
+pub fn install_clawhub_skill(
+    paths: &AppPaths,
+    store: &SkillStore,
+    slug: &str,
+    version: Option<String>,
+    name: Option<String>,
+) -> Result<InstallResult> {
+    let version = match version {
+        Some(version) => Some(version),
+        None => latest_clawhub_version(slug).ok().flatten(),
+    };
+    let temp_dir = operation_temp_dir("clawhub-install")?;
+    let extracted_path =
+        download_and_extract_clawhub_skill(slug, version.as_deref(), temp_dir.path())?;
+
+    let display_name = name.unwrap_or_else(|| slug.to_string());
+    let result = install_local_skill(paths, store, &extracted_path, Some(display_name))?;
+
+    // Fix source info: replace temp path with clawhub slug so the record
+    // remains valid after the temp dir is cleaned up.
+    if let Some(mut record) = store.get_skill_by_id(&result.skill_id)? {
+        record.source_type = "clawhub".to_string();
+        record.source_ref = Some(format!("clawhub://{}", slug));
+        record.source_revision = version;
+        store.upsert_skill(&record)?;
+        refresh_skill_metadata(store, &record.id, &result.central_path);
+        record_skill_manifest(store, &record.id, &result.central_path);
+    }
+    invalidate_clawhub_skill(slug);
+
+    // temp_dir is automatically cleaned up when dropped
+    Ok(result)
+}
+
 fn download_and_extract_inner(
     base_url: &str,
     slug: &str,
     version: Option<&str>,
     target_dir: &Path,
 ) -> Result<PathBuf> {
-    let client = Client::new();
+    let bytes = download_archive(base_url, slug, version)?;
+    let reader = std::io::Cursor::new(&bytes);
+    let mut archive = zip::ZipArchive::new(reader).context("open zip archive")?;
+
+    if !super::archive::is_safe_relative_path(slug) {
+        anyhow::bail!("invalid ClawHub slug: {}", slug);
+    }
+    let extract_dir = target_dir.join(slug);
+    // Hidden/special files are left out; entries escaping the dir are an error.
+    super::archive::extract_zip(&mut archive, &extract_dir, |name| {
+        name.starts_with("__MACOSX") || name.starts_with('.')
+    })?;
+
+    Ok(extract_dir)
+}
+
+fn download_archive(base_url: &str, slug: &str, version: Option<&str>) -> Result<Vec<u8>> {
+    let client = http_client()?;
     let base_url = base_url.trim_end_matches('/');
     let mut url = format!(
         "{}/api/v1/download?slug={}",
@@ -288,46 +966,15 @@ fn download_and_extract_inner(
         url.push_str(&format!("&version={}", urlencoding::encode(v)));
     }
 
-    let response = client
-        .get(&url)
-        .header("User-Agent", "skills-hub")
-        .send()
+    let response = http_client::send(authorize(client.get(&url), &url)?)
         .context("ClawHub download request failed")?
         .error_for_status()
         .context("ClawHub download returned error")?;
 
-    let bytes = response.bytes().context("read ClawHub download body")?;
-
-    let reader = std::io::Cursor::new(&bytes);
-    let mut archive = zip::ZipArchive::new(reader).context("open zip archive")?;
-
-    let extract_dir = target_dir.join(slug);
-    std::fs::create_dir_all(&extract_dir)
-        .with_context(|| format!("create extract dir {:?}", extract_dir))?;
-
-    for i in 0..archive.len() {
-        let mut file = archive.by_index(i).context("read zip entry")?;
-        let name = file.name().to_string();
-
-        // Skip directory entries and hidden/special files
-        if file.is_dir() || name.starts_with("__MACOSX") || name.starts_with('.') {
-            continue;
-        }
-
-        let out_path = extract_dir.join(&name);
-
-        // Ensure parent directories exist
-        if let Some(parent) = out_path.parent() {
-            std::fs::create_dir_all(parent)?;
-        }
-
-        let mut out_file = std::fs::File::create(&out_path)
-            .with_context(|| format!("create file {:?}", out_path))?;
-        std::io::copy(&mut file, &mut out_file)
-            .with_context(|| format!("write file {:?}", out_path))?;
-    }
-
-    Ok(extract_dir)
+    Ok(response
+        .bytes()
+        .context("read ClawHub download body")?
+        .to_vec())
 }
 
 #[cfg(test)]
@@ -0,0 +1,136 @@
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use super::skill_store::SkillStore;
+
+pub const CLOUD_FORCE_COPY_KEY: &str = "cloud_force_copy";
+
+/// A file sync service whose folders break links: the app sees placeholder
+/// files once content is evicted, and other machines get the links' targets
+/// rewritten or dropped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CloudProvider {
+    #[serde(rename = "icloud_drive")]
+    ICloudDrive,
+    Dropbox,
+    OneDrive,
+}
+
+impl CloudProvider {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ICloudDrive => "iCloud Drive",
+            Self::Dropbox => "Dropbox",
+            Self::OneDrive => "OneDrive",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct CloudFolderStatus {
+    pub path: String,
+    /// The service syncing `path`, if any.
+    pub provider: Option<CloudProvider>,
+    /// Skills under a cloud folder are copied instead of linked.
+    pub force_copy: bool,
+}
+
+/// The cloud service whose folder holds `path`, going by the folder names
+/// each client uses on macOS, Windows and Linux.
+pub fn detect_cloud_folder(path: &Path) -> Option<CloudProvider> {
+    if let Some(root) = std::env::var_os("OneDrive").filter(|root| !root.is_empty()) {
+        if path.starts_with(PathBuf::from(root)) {
+            return Some(CloudProvider::OneDrive);
+        }
+    }
+    let names: Vec<String> = path
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_string_lossy().to_string()),
+            _ => None,
+        })
+        .collect();
+    for (i, name) in names.iter().enumerate() {
+        let parent = i.checked_sub(1).map(|p| names[p].as_str());
+        if name == "com~apple~CloudDocs"
+            || name == "iCloud Drive"
+            || (name == "Mobile Documents" && parent == Some("Library"))
+            || (parent == Some("CloudStorage") && name.starts_with("iCloud"))
+        {
+            return Some(CloudProvider::ICloudDrive);
+        }
+        if name == "Dropbox"
+            || name.starts_with("Dropbox (")
+            || (parent == Some("CloudStorage") && name.starts_with("Dropbox"))
+        {
+            return Some(CloudProvider::Dropbox);
+        }
+        if name == "OneDrive" || name.starts_with("OneDrive - ") || name.starts_with("OneDrive-") {
+            return Some(CloudProvider::OneDrive);
+        }
+    }
+    None
+}
+
+static FORCE_COPY: AtomicBool = AtomicBool::new(false);
+
+/// The cloud service holding `source` when skills there must be copied
+/// rather than linked.
+pub fn cloud_force_copy_provider(source: &Path) -> Option<CloudProvider> {
+    if !FORCE_COPY.load(Ordering::Relaxed) {
+        return None;
+    }
+    detect_cloud_folder(source)
+}
+
+pub fn get_cloud_force_copy(store: &SkillStore) -> bool {
+    matches!(store.get_setting(CLOUD_FORCE_COPY_KEY), Ok(Some(v)) if v == "1")
+}
+
+/// Existing links change on the skills' next sync.
+pub fn set_cloud_force_copy(store: &SkillStore, enabled: bool) -> Result<bool> {
+    store.set_setting(CLOUD_FORCE_COPY_KEY, if enabled { "1" } else { "0" })?;
+    FORCE_COPY.store(enabled, Ordering::Relaxed);
+    Ok(enabled)
+}
+
+pub fn load_cloud_force_copy(store: &SkillStore) {
+    FORCE_COPY.store(get_cloud_force_copy(store), Ordering::Relaxed);
+}
+
+pub fn cloud_folder_status(store: &SkillStore, path: &Path) -> CloudFolderStatus {
+    CloudFolderStatus {
+        path: path.to_string_lossy().to_string(),
+        provider: detect_cloud_folder(path),
+        force_copy: get_cloud_force_copy(store),
+    }
+}
+
+static WARNED: OnceLock<Mutex<Option<PathBuf>>> = OnceLock::new();
+
+/// Log once per path that the central repo is inside a cloud folder.
+pub fn warn_if_cloud_folder(path: &Path) -> Option<CloudProvider> {
+    let provider = detect_cloud_folder(path)?;
+    let mut warned = WARNED
+        .get_or_init(|| Mutex::new(None))
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    if warned.as_deref() != Some(path) {
+        log::warn!(
+            "[central_repo] {:?} is synced by {}; links into it can break when files are evicted or synced to other machines",
+            path,
+            provider.label()
+        );
+        *warned = Some(path.to_path_buf());
+    }
+    Some(provider)
+}
+
+#[cfg(test)]
+#[path = "tests/cloud_folders.rs"]
+mod tests;
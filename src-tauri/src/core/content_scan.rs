@@ -0,0 +1,274 @@
+use std::path::Path;
+
+use serde::Serialize;
+use walkdir::WalkDir;
+
+use super::content_hash::is_ignored;
+
+/// Files larger than this are not read; skills are prose and small scripts.
+const MAX_SCANNED_FILE_BYTES: u64 = 1024 * 1024;
+/// Stop listing after this many findings; the skill needs review either way.
+const MAX_FINDINGS: usize = 50;
+const MAX_EXCERPT_CHARS: usize = 120;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingKind {
+    /// What looks like a credential committed with the skill.
+    Secret,
+    /// A downloaded script piped straight into a shell.
+    PipeToShell,
+    /// Text telling the agent to hide things from the user or leak data.
+    SuspiciousInstruction,
+}
+
+/// Something in a skill's files worth a look before it reaches an agent.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ContentFinding {
+    pub kind: FindingKind,
+    /// Stable identifier, e.g. `github_token`.
+    pub code: String,
+    /// Relative to the skill folder, with `/` separators.
+    pub file: String,
+    /// 1-based.
+    pub line: usize,
+    /// The line, shortened, with any secret masked.
+    pub excerpt: String,
+}
+
+/// Token prefixes of well-known services and the shortest token after them.
+const TOKEN_PREFIXES: &[(&str, &str, usize)] = &[
+    ("aws_access_key", "AKIA", 16),
+    ("github_token", "ghp_", 30),
+    ("github_token", "gho_", 30),
+    ("github_token", "ghu_", 30),
+    ("github_token", "ghs_", 30),
+    ("github_token", "github_pat_", 30),
+    ("slack_token", "xoxb-", 20),
+    ("slack_token", "xoxp-", 20),
+    ("anthropic_key", "sk-ant-", 30),
+    ("openai_key", "sk-proj-", 30),
+    ("openai_key", "sk-", 32),
+    ("google_api_key", "AIza", 35),
+];
+
+/// Assignments whose value is a credential, e.g. `API_KEY = "..."`.
+const SECRET_NAMES: &[&str] = &[
+    "api_key",
+    "apikey",
+    "api-key",
+    "secret_key",
+    "client_secret",
+    "access_token",
+    "auth_token",
+    "password",
+];
+
+/// Values that are obviously not real credentials.
+const PLACEHOLDER_HINTS: &[&str] = &[
+    "<",
+    "${",
+    "$(",
+    "{{",
+    "your",
+    "xxx",
+    "example",
+    "placeholder",
+    "changeme",
+    "...",
+    "env",
+];
+
+const SUSPICIOUS_PHRASES: &[(&str, &str)] = &[
+    ("ignore_instructions", "ignore previous instructions"),
+    ("ignore_instructions", "ignore all previous instructions"),
+    ("ignore_instructions", "ignore prior instructions"),
+    ("ignore_instructions", "disregard previous instructions"),
+    ("ignore_instructions", "disregard all prior instructions"),
+    ("hide_from_user", "do not tell the user"),
+    ("hide_from_user", "don't tell the user"),
+    ("hide_from_user", "without telling the user"),
+    ("hide_from_user", "without asking the user"),
+    ("hide_from_user", "hide this from the user"),
+    ("hide_from_user", "do not mention this"),
+    ("exfiltration", "exfiltrate"),
+    ("credential_access", "~/.ssh/id_"),
+    ("credential_access", "~/.aws/credentials"),
+    ("credential_access", ".git-credentials"),
+];
+
+/// Scan every text file in the skill at `dir`.
+pub fn scan_skill_dir(dir: &Path) -> Vec<ContentFinding> {
+    let mut findings = Vec::new();
+    for entry in WalkDir::new(dir)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|entry| !is_ignored(entry))
+        .filter_map(Result::ok)
+    {
+        let small = entry
+            .metadata()
+            .is_ok_and(|meta| meta.len() <= MAX_SCANNED_FILE_BYTES);
+        if !entry.file_type().is_file() || !small {
+            continue;
+        }
+        // Binary files are skipped.
+        let Ok(text) = std::fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        let file = entry
+            .path()
+            .strip_prefix(dir)
+            .unwrap_or(entry.path())
+            .to_string_lossy()
+            .replace('\\', "/");
+        scan_text(&file, &text, &mut findings);
+        if findings.len() >= MAX_FINDINGS {
+            findings.truncate(MAX_FINDINGS);
+            break;
+        }
+    }
+    findings
+}
+
+/// Findings in one file's `text`.
+pub fn scan_text(file: &str, text: &str, findings: &mut Vec<ContentFinding>) {
+    for (index, line) in text.lines().enumerate() {
+        let mut push = |kind, code: &str, excerpt: String| {
+            findings.push(ContentFinding {
+                kind,
+                code: code.to_string(),
+                file: file.to_string(),
+                line: index + 1,
+                excerpt,
+            })
+        };
+        if let Some((code, secret)) = find_secret(line) {
+            push(
+                FindingKind::Secret,
+                code,
+                excerpt(&line.replace(secret, &mask(secret))),
+            );
+        }
+        if pipes_to_shell(line) {
+            push(FindingKind::PipeToShell, "pipe_to_shell", excerpt(line));
+        }
+        let lower = line.to_ascii_lowercase();
+        if let Some((code, _)) = SUSPICIOUS_PHRASES
+            .iter()
+            .find(|(_, phrase)| lower.contains(phrase))
+        {
+            push(FindingKind::SuspiciousInstruction, code, excerpt(line));
+        }
+    }
+}
+
+fn is_token_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-'
+}
+
+/// The code and text of the first credential-looking token on `line`.
+fn find_secret(line: &str) -> Option<(&'static str, &str)> {
+    if line.contains("-----BEGIN") && line.contains("PRIVATE KEY-----") {
+        return Some(("private_key", line.trim()));
+    }
+    for (code, prefix, min_len) in TOKEN_PREFIXES {
+        let mut rest = line;
+        while let Some(pos) = rest.find(prefix) {
+            let start = line.len() - rest.len() + pos;
+            let preceded_by_token = line[..start].chars().next_back().is_some_and(is_token_char);
+            let token_len = line[start + prefix.len()..]
+                .find(|c: char| !is_token_char(c))
+                .unwrap_or(line.len() - start - prefix.len());
+            if !preceded_by_token && token_len >= *min_len {
+                return Some((code, &line[start..start + prefix.len() + token_len]));
+            }
+            rest = &rest[pos + prefix.len()..];
+        }
+    }
+    let lower = line.to_ascii_lowercase();
+    for name in SECRET_NAMES {
+        let Some(pos) = lower.find(name) else {
+            continue;
+        };
+        let after = line[pos + name.len()..]
+            .trim_start_matches(['"', '\''])
+            .trim_start();
+        let Some(value) = after
+            .strip_prefix('=')
+            .or_else(|| after.strip_prefix(':'))
+            .map(|v| v.trim().trim_matches(['"', '\'', ',', ';']))
+        else {
+            continue;
+        };
+        let value_lower = value.to_ascii_lowercase();
+        if value.len() >= 12
+            && !value.contains(char::is_whitespace)
+            && !PLACEHOLDER_HINTS
+                .iter()
+                .any(|hint| value_lower.contains(hint))
+        {
+            return Some(("credential_assignment", value));
+        }
+    }
+    None
+}
+
+/// `curl ... | sh`, `wget -O- ... | bash`, `bash <(curl ...)` and the
+/// PowerShell `iwr ... | iex` form.
+fn pipes_to_shell(line: &str) -> bool {
+    let lower = line.to_ascii_lowercase();
+    let fetches = [
+        "curl ",
+        "wget ",
+        "iwr ",
+        "invoke-webrequest",
+        "downloadstring",
+    ]
+    .iter()
+    .any(|cmd| lower.contains(cmd));
+    if !fetches {
+        return false;
+    }
+    if lower.contains("<(curl") || lower.contains("<(wget") {
+        return true;
+    }
+    lower.split('|').skip(1).any(|stage| {
+        let stage = stage.trim_start();
+        let stage = stage.strip_prefix("sudo ").unwrap_or(stage).trim_start();
+        [
+            "sh",
+            "bash",
+            "zsh",
+            "python",
+            "python3",
+            "iex",
+            "invoke-expression",
+        ]
+        .iter()
+        .any(|shell| {
+            stage == *shell
+                || stage
+                    .strip_prefix(shell)
+                    .is_some_and(|rest| rest.starts_with([' ', '-', ';', ')', '`']))
+        })
+    })
+}
+
+fn mask(secret: &str) -> String {
+    let shown: String = secret.chars().take(4).collect();
+    format!("{}…", shown)
+}
+
+fn excerpt(line: &str) -> String {
+    let line = line.trim();
+    if line.chars().count() <= MAX_EXCERPT_CHARS {
+        return line.to_string();
+    }
+    let cut: String = line.chars().take(MAX_EXCERPT_CHARS).collect();
+    format!("{}…", cut)
+}
+
+#[cfg(test)]
+#[path = "tests/content_scan.rs"]
+mod tests;
@@ -0,0 +1,186 @@
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use super::content_hash::is_ignored;
+use super::skill_store::SkillStore;
+use super::sync_engine::{sync_dir_copy_for_tool_then, sync_dir_for_tool_with_mode, SyncOutcome};
+use super::sync_modes::SyncModePreference;
+
+/// A skill refers to a sibling skill as `{{skill:<name>}}`; on sync the
+/// placeholder becomes the sibling's directory for the same tool.
+const REF_OPEN: &str = "{{skill:";
+const REF_CLOSE: &str = "}}";
+/// Larger files are assumed not to be hand-written docs and left alone.
+const MAX_REWRITE_BYTES: u64 = 1024 * 1024;
+
+/// Names of the skills referenced anywhere under `dir`, sorted.
+pub fn find_cross_refs(dir: &Path) -> Result<Vec<String>> {
+    let mut names = BTreeSet::new();
+    for (_, text) in text_files(dir)? {
+        names.extend(refs_in(&text).into_iter().map(str::to_string));
+    }
+    Ok(names.into_iter().collect())
+}
+
+/// Whether `text` holds a reference that sync rewrites in copies.
+pub fn has_cross_refs(text: &str) -> bool {
+    !refs_in(text).is_empty()
+}
+
+fn refs_in(text: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(REF_OPEN) {
+        let after = &rest[start + REF_OPEN.len()..];
+        let Some(end) = after.find(REF_CLOSE) else {
+            break;
+        };
+        let name = after[..end].trim();
+        if !name.is_empty() && !name.contains('\n') {
+            names.push(name);
+        }
+        rest = &after[end + REF_CLOSE.len()..];
+    }
+    names
+}
+
+/// Sync a skill into `target` for `tool_key`. Skills with cross-references
+/// are always copied, since a link would share one rewritten copy between
+/// tools. Returns warnings for references that could not be resolved for
+/// this tool.
+pub fn sync_with_cross_refs(
+    store: &SkillStore,
+    tool_key: &str,
+    source: &Path,
+    target: &Path,
+    overwrite: bool,
+) -> Result<(SyncOutcome, Vec<String>)> {
+    sync_with_cross_refs_in_mode(store, tool_key, source, target, overwrite, None)
+}
+
+/// [`sync_with_cross_refs`] in `mode` instead of the saved preference.
+pub fn sync_with_cross_refs_in_mode(
+    store: &SkillStore,
+    tool_key: &str,
+    source: &Path,
+    target: &Path,
+    overwrite: bool,
+    mode: Option<SyncModePreference>,
+) -> Result<(SyncOutcome, Vec<String>)> {
+    if find_cross_refs(source)?.is_empty() {
+        let outcome = sync_dir_for_tool_with_mode(tool_key, source, target, overwrite, mode)?;
+        return Ok((outcome, Vec::new()));
+    }
+    sync_copy_with_cross_refs(store, tool_key, source, target, overwrite)
+}
+
+/// Copy `source` to `target` for `tool_key` with its references rewritten.
+/// The rewrite happens before the copy is moved into place.
+pub fn sync_copy_with_cross_refs(
+    store: &SkillStore,
+    tool_key: &str,
+    source: &Path,
+    target: &Path,
+    overwrite: bool,
+) -> Result<(SyncOutcome, Vec<String>)> {
+    let mut warnings = Vec::new();
+    let outcome = sync_dir_copy_for_tool_then(tool_key, source, target, overwrite, |staged| {
+        warnings = rewrite_cross_refs(store, tool_key, staged)?;
+        Ok(())
+    })?;
+    Ok((outcome, warnings))
+}
+
+/// Replace every `{{skill:<name>}}` in the copied skill at `target_dir` with
+/// where `<name>` is synced for `tool_key`. A skill not synced to that tool
+/// resolves to its central copy; an unknown name is left as is.
+pub fn rewrite_cross_refs(
+    store: &SkillStore,
+    tool_key: &str,
+    target_dir: &Path,
+) -> Result<Vec<String>> {
+    let files = text_files(target_dir)?;
+    let names: BTreeSet<&str> = files.iter().flat_map(|(_, text)| refs_in(text)).collect();
+    if names.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let skills = store.list_skills()?;
+    let mut warnings = Vec::new();
+    let mut resolved: HashMap<&str, String> = HashMap::new();
+    for name in names {
+        let Some(skill) = skills.iter().find(|s| s.name == name) else {
+            warnings.push(format!("referenced skill is not managed: {}", name));
+            continue;
+        };
+        let path = match store.get_skill_target(&skill.id, tool_key)? {
+            Some(target) => target.target_path,
+            None => {
+                warnings.push(format!(
+                    "referenced skill {} is not synced to {}; using its central copy",
+                    name, tool_key
+                ));
+                skill.central_path.clone()
+            }
+        };
+        resolved.insert(name, path);
+    }
+
+    for (path, text) in &files {
+        let mut rewritten = String::with_capacity(text.len());
+        let mut rest = text.as_str();
+        while let Some(start) = rest.find(REF_OPEN) {
+            let after = &rest[start + REF_OPEN.len()..];
+            let Some(end) = after.find(REF_CLOSE) else {
+                break;
+            };
+            rewritten.push_str(&rest[..start]);
+            match resolved.get(after[..end].trim()) {
+                Some(resolved_path) => rewritten.push_str(resolved_path),
+                None => {
+                    rewritten.push_str(&rest[start..start + REF_OPEN.len() + end + REF_CLOSE.len()])
+                }
+            }
+            rest = &after[end + REF_CLOSE.len()..];
+        }
+        rewritten.push_str(rest);
+        if rewritten != *text {
+            std::fs::write(path, rewritten).with_context(|| format!("write {:?}", path))?;
+        }
+    }
+    Ok(warnings)
+}
+
+/// UTF-8 files under `dir` that mention a reference, with their contents.
+fn text_files(dir: &Path) -> Result<Vec<(std::path::PathBuf, String)>> {
+    let mut files = Vec::new();
+    for entry in walkdir::WalkDir::new(dir)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|e| !is_ignored(e))
+    {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        if entry
+            .metadata()
+            .map_or(true, |m| m.len() > MAX_REWRITE_BYTES)
+        {
+            continue;
+        }
+        let Ok(text) = std::fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        if text.contains(REF_OPEN) {
+            files.push((entry.path().to_path_buf(), text));
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+#[path = "tests/cross_refs.rs"]
+mod tests;
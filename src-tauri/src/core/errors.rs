@@ -0,0 +1,114 @@
+use std::fmt;
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// Failures the frontend handles with a dedicated flow. Everything else is
+/// [`ErrorCode::Other`] and only shown as a message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    Other,
+    /// A repo URL points at several skills; payload-less.
+    MultiSkills,
+    /// `{ "path" }` is in the way and was left alone. Sync commands add the
+    /// fields of [`super::target_conflict::TargetConflict`].
+    TargetExists,
+    /// `{ "tool" }` is not installed on this machine.
+    ToolNotInstalled,
+    GithubRateLimited,
+    /// `{ "reason" }` the folder is not a valid skill.
+    SkillInvalid,
+    SyncPlanStale,
+    CentralRemoteDiverged,
+    CentralRemoteNotSet,
+    CentralRepoReadOnly,
+    ClawhubTokenRequired,
+    /// `{ "tool" }` has no MCP config Skills Hub can write.
+    McpUnsupported,
+    /// `{ "tool", "kind" }`: the tool has no folder for this resource type.
+    ResourceUnsupported,
+    /// `{ "blockers" }` keep the central repo from moving.
+    MigrationBlocked,
+    /// `{ "skill_id", "name" }` is locked against updates and edits.
+    SkillLocked,
+    /// `{ "kind", "owner", "source" }` is on no allowlist; retry with the
+    /// command's confirm flag to install anyway.
+    UntrustedSource,
+    /// `{ "kind", "owner", "source" }` is on the denylist.
+    SourceDenied,
+    /// `{ "skill_id", "name" }` waits for `approve_skill` before it may be
+    /// synced.
+    SkillQuarantined,
+    /// `{ "name", "signature_status" }`: the package is unsigned or signed
+    /// by a key not in the trusted list; retry with the command's confirm
+    /// flag to install anyway.
+    UnverifiedPackage,
+}
+
+/// An error with a code the frontend can branch on. Raised through
+/// `anyhow`; commands find it again with `downcast_ref`, so added context
+/// does not hide it.
+#[derive(Debug)]
+pub struct CodedError {
+    pub code: ErrorCode,
+    pub message: String,
+    pub payload: Option<Value>,
+}
+
+impl fmt::Display for CodedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for CodedError {}
+
+pub fn coded(code: ErrorCode, message: impl Into<String>) -> anyhow::Error {
+    anyhow::Error::new(CodedError {
+        code,
+        message: message.into(),
+        payload: None,
+    })
+}
+
+pub fn coded_with(code: ErrorCode, message: impl Into<String>, payload: Value) -> anyhow::Error {
+    anyhow::Error::new(CodedError {
+        code,
+        message: message.into(),
+        payload: Some(payload),
+    })
+}
+
+/// `path` already exists and was not overwritten.
+pub fn target_exists(path: impl AsRef<std::path::Path>) -> anyhow::Error {
+    let path = path.as_ref().to_string_lossy().to_string();
+    coded_with(
+        ErrorCode::TargetExists,
+        format!("target already exists: {}", path),
+        serde_json::json!({ "path": path }),
+    )
+}
+
+pub fn tool_not_installed(tool: &str) -> anyhow::Error {
+    coded_with(
+        ErrorCode::ToolNotInstalled,
+        format!("{} is not installed", tool),
+        serde_json::json!({ "tool": tool }),
+    )
+}
+
+/// `tool` has no folder for `kind` resources.
+pub fn resource_unsupported(tool: &str, kind: &str) -> anyhow::Error {
+    coded_with(
+        ErrorCode::ResourceUnsupported,
+        format!("{} does not support {} resources", tool, kind),
+        serde_json::json!({ "tool": tool, "kind": kind }),
+    )
+}
+
+/// The coded error somewhere in `err`'s chain, if any.
+pub fn find_coded(err: &anyhow::Error) -> Option<&CodedError> {
+    err.chain()
+        .find_map(|cause| cause.downcast_ref::<CodedError>())
+}
@@ -0,0 +1,136 @@
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use super::content_hash::hash_dir;
+use super::skill_store::SkillStore;
+use super::tool_adapters::{
+    default_tool_adapters, is_tool_installed, resolve_default_path, scan_tool_dir,
+};
+
+/// One place an unmanaged skill was found.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ExistingCopy {
+    pub tool: String,
+    pub path: PathBuf,
+}
+
+/// Unmanaged skill folders with identical content.
+#[derive(Clone, Debug, Serialize)]
+pub struct ExistingSkill {
+    pub name: String,
+    pub content_hash: String,
+    pub copies: Vec<ExistingCopy>,
+    /// A managed skill with the same content; importing only links to it.
+    pub managed_skill_id: Option<String>,
+    /// Another group has the same name but different content.
+    pub name_conflict: bool,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ExistingSkillScan {
+    pub tools_scanned: usize,
+    pub skills: Vec<ExistingSkill>,
+}
+
+/// Find skill folders in installed tools' skills directories that Skills Hub
+/// does not track, grouped by content. Links are left to the symlink farm
+/// flow, since their content already lives somewhere else.
+pub fn scan_existing_skills(store: &SkillStore) -> Result<ExistingSkillScan> {
+    let mut dirs = Vec::new();
+    for adapter in default_tool_adapters() {
+        if is_tool_installed(&adapter)? {
+            let dir = resolve_default_path(&adapter)?;
+            dirs.push((adapter, dir));
+        }
+    }
+    let tools_scanned = dirs.len();
+
+    let managed_paths: HashSet<PathBuf> = store
+        .list_all_skill_target_paths()?
+        .into_iter()
+        .map(|(_, path)| PathBuf::from(path))
+        .collect();
+    let mut seen = HashSet::new();
+    let mut found = Vec::new();
+    for (adapter, dir) in &dirs {
+        for skill in scan_tool_dir(adapter, dir)? {
+            if skill.is_link || skill.name.starts_with('.') || managed_paths.contains(&skill.path) {
+                continue;
+            }
+            // Tools sharing a skills directory see the same folders.
+            if !seen.insert(skill.path.clone()) {
+                continue;
+            }
+            found.push((
+                skill.name,
+                ExistingCopy {
+                    tool: adapter.id.as_key().to_string(),
+                    path: skill.path,
+                },
+            ));
+        }
+    }
+
+    let managed: Vec<(String, String)> = store
+        .list_skills()?
+        .into_iter()
+        .filter_map(|s| s.content_hash.map(|hash| (hash, s.id)))
+        .collect();
+    Ok(ExistingSkillScan {
+        tools_scanned,
+        skills: group_by_content(found, &managed),
+    })
+}
+
+/// Group copies by content hash; a group is named after its first copy.
+/// `managed` pairs content hashes with managed skill ids.
+fn group_by_content(
+    found: Vec<(String, ExistingCopy)>,
+    managed: &[(String, String)],
+) -> Vec<ExistingSkill> {
+    let mut groups: BTreeMap<String, (String, Vec<ExistingCopy>)> = BTreeMap::new();
+    for (name, copy) in found {
+        let Ok(hash) = hash_dir(&copy.path) else {
+            log::warn!("[existing_skills] cannot hash {:?}", copy.path);
+            continue;
+        };
+        groups
+            .entry(hash)
+            .or_insert_with(|| (name, Vec::new()))
+            .1
+            .push(copy);
+    }
+
+    let mut names: BTreeMap<&str, usize> = BTreeMap::new();
+    for (name, _) in groups.values() {
+        *names.entry(name.as_str()).or_default() += 1;
+    }
+    let conflicts: HashSet<String> = names
+        .into_iter()
+        .filter(|(_, count)| *count > 1)
+        .map(|(name, _)| name.to_string())
+        .collect();
+
+    let mut skills: Vec<ExistingSkill> = groups
+        .into_iter()
+        .map(|(content_hash, (name, copies))| ExistingSkill {
+            managed_skill_id: managed
+                .iter()
+                .find(|(hash, _)| *hash == content_hash)
+                .map(|(_, id)| id.clone()),
+            name_conflict: conflicts.contains(&name),
+            name,
+            content_hash,
+            copies,
+        })
+        .collect();
+    skills.sort_by(|a, b| a.name.cmp(&b.name));
+    skills
+}
+
+#[cfg(test)]
+#[path = "tests/existing_skills.rs"]
+mod tests;
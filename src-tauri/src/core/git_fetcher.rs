@@ -5,7 +5,10 @@ use std::sync::OnceLock;
 use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
-use git2::{FetchOptions, Repository};
+use git2::{FetchOptions, ProxyOptions, Repository};
+
+use super::http_client::{self, HttpSettings};
+use super::locale::{tr, trf, Msg};
 
 pub fn clone_or_pull(repo_url: &str, dest: &Path, branch: Option<&str>) -> Result<String> {
     // Prefer the system `git` binary if available. It tends to work better on macOS
@@ -35,10 +38,7 @@ pub fn clone_or_pull(repo_url: &str, dest: &Path, branch: Option<&str>) -> Resul
                     err
                 );
                 if !allow_fallback {
-                    anyhow::bail!(
-                        "git 命令执行失败（为避免卡死，已停止并不再回退到内置 git）。请检查系统 git/网络/代理；或设置环境变量 SKILLS_HUB_ALLOW_LIBGIT2_FALLBACK=1 允许回退。\n{:#}",
-                        err
-                    );
+                    anyhow::bail!("{}\n{:#}", tr(Msg::GitCliFailed), err);
                 }
                 log::warn!(
                     "[git_fetcher] falling back to libgit2 (SKILLS_HUB_ALLOW_LIBGIT2_FALLBACK=1)"
@@ -54,7 +54,11 @@ pub fn clone_or_pull(repo_url: &str, dest: &Path, branch: Option<&str>) -> Resul
         fetch_origin(&repo)?;
         repo
     } else {
-        Repository::clone(repo_url, dest)
+        let mut opts = FetchOptions::new();
+        opts.proxy_options(proxy_options(repo_url));
+        git2::build::RepoBuilder::new()
+            .fetch_options(opts)
+            .clone(repo_url, dest)
             .with_context(|| format!("clone {} into {:?}", repo_url, dest))?
     };
 
@@ -159,9 +163,40 @@ fn git_cmd() -> Command {
     // Abort stalled HTTPS transfers (helps avoid "spinner forever" on bad networks).
     cmd.env("GIT_HTTP_LOW_SPEED_LIMIT", "1024")
         .env("GIT_HTTP_LOW_SPEED_TIME", "120");
+    cmd.envs(proxy_env(&http_client::current_settings()));
     cmd
 }
 
+/// Environment that sends HTTPS remotes through the app's proxy. It goes in
+/// `GIT_CONFIG_*` rather than `-c` so proxy credentials never show up in the
+/// process list; git reads NO_PROXY for the hosts that skip it.
+fn proxy_env(settings: &HttpSettings) -> Vec<(&'static str, String)> {
+    let Some(proxy) = settings.proxy_url.as_deref() else {
+        return Vec::new();
+    };
+    let mut env = vec![
+        ("GIT_CONFIG_COUNT", "1".to_string()),
+        ("GIT_CONFIG_KEY_0", "http.proxy".to_string()),
+        ("GIT_CONFIG_VALUE_0", proxy.to_string()),
+    ];
+    if let Some(no_proxy) = settings.no_proxy.as_deref() {
+        env.push(("NO_PROXY", no_proxy.to_string()));
+        env.push(("no_proxy", no_proxy.to_string()));
+    }
+    env
+}
+
+/// libgit2 proxy settings: the app's proxy when one applies to `repo_url`,
+/// otherwise whatever git config or the environment says.
+fn proxy_options(repo_url: &str) -> ProxyOptions<'static> {
+    let mut opts = ProxyOptions::new();
+    match http_client::proxy_for_url(repo_url) {
+        Some(proxy) => opts.url(&proxy),
+        None => opts.auto(),
+    };
+    opts
+}
+
 fn run_cmd_with_timeout(
     mut cmd: Command,
     timeout: Duration,
@@ -181,8 +216,8 @@ fn run_cmd_with_timeout(
                 .map(|out| String::from_utf8_lossy(&out.stderr).to_string())
                 .unwrap_or_default();
             anyhow::bail!(
-                "git 操作超时（{}s）。请检查网络/代理是否可访问 GitHub；也可设置环境变量 SKILLS_HUB_GIT_TIMEOUT_SECS 增大超时。\n{}",
-                timeout.as_secs(),
+                "{}\n{}",
+                trf(Msg::GitTimeout, &[("secs", &timeout.as_secs().to_string())]),
                 stderr.trim()
             );
         }
@@ -293,7 +328,7 @@ fn clone_or_pull_via_git_cli(repo_url: &str, dest: &Path, branch: Option<&str>)
             // But include useful context for debugging.
             let stderr = String::from_utf8_lossy(&out.stderr);
             if !stderr.trim().is_empty() {
-                eprintln!("[git_fetcher] checkout warning: {}", stderr);
+                log::warn!("checkout warning: {}", stderr);
             }
         }
     }
@@ -349,12 +384,12 @@ pub fn ls_remote_head(repo_url: &str, branch: Option<&str>) -> Result<String> {
     // Fallback: use libgit2 remote_ls (no checkout needed).
     let remote = git2::Remote::create_detached(repo_url)
         .with_context(|| format!("create detached remote for {}", repo_url))?;
-    // Need mutable for connect().
+    // Need mutable for connect_auth(); the connection must stay open while listing.
     let mut remote = remote;
-    remote
-        .connect(git2::Direction::Fetch)
+    let connection = remote
+        .connect_auth(git2::Direction::Fetch, None, Some(proxy_options(repo_url)))
         .with_context(|| format!("connect to {}", repo_url))?;
-    let heads = remote.list()?;
+    let heads = connection.list()?;
     for head in heads {
         if head.name() == ref_spec {
             return Ok(head.oid().to_string());
@@ -366,6 +401,7 @@ pub fn ls_remote_head(repo_url: &str, branch: Option<&str>) -> Result<String> {
 fn fetch_origin(repo: &Repository) -> Result<()> {
     let mut remote = repo.find_remote("origin")?;
     let mut opts = FetchOptions::new();
+    opts.proxy_options(proxy_options(remote.url().unwrap_or_default()));
     remote.fetch(
         &["refs/heads/*:refs/remotes/origin/*"],
         Some(&mut opts),
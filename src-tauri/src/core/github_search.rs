@@ -1,7 +1,88 @@
 use anyhow::{Context, Result};
-use reqwest::blocking::Client;
+use reqwest::blocking::{Client, Response};
 use serde::Deserialize;
 
+use super::errors::{coded, ErrorCode};
+use super::http_client::{self, http_client};
+use super::offline_cache::{self, RESPONSE_TTL};
+use super::registry_auth::{authorize, has_registry_auth, set_registry_auth, RegistryAuth};
+use super::skill_store::SkillStore;
+use super::source_providers::SourceProvider;
+
+pub const GITHUB_API_URL: &str = "https://api.github.com";
+
+/// Save (or with `None`, clear) a personal access token for the GitHub API.
+/// It is stored as bearer credentials for the API origin, so every request
+/// going through [`github_get`] uses it.
+pub fn set_github_token(store: &SkillStore, token: Option<String>) -> Result<bool> {
+    let auth = token
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .map(|token| RegistryAuth::Bearer { token });
+    set_registry_auth(store, GITHUB_API_URL, auth)?;
+    Ok(has_github_token())
+}
+
+pub fn has_github_token() -> bool {
+    has_registry_auth(GITHUB_API_URL)
+}
+
+/// GET a GitHub API URL with the stored token. `what` names the request in
+/// errors, e.g. "GitHub search".
+pub fn github_get(client: &Client, url: &str, what: &str) -> Result<Response> {
+    let response = github_send(client, url, what)?;
+    if let Some(err) = rate_limit_error(&response) {
+        return Err(err);
+    }
+    response
+        .error_for_status()
+        .with_context(|| format!("{} returned error", what))
+}
+
+/// Like [`github_get`] but leaves non-success statuses other than rate
+/// limiting to the caller.
+pub fn github_send(client: &Client, url: &str, what: &str) -> Result<Response> {
+    http_client::send(authorize(client.get(url), url)?)
+        .with_context(|| format!("{} request failed", what))
+}
+
+/// A `GithubRateLimited` error when GitHub refused the request for lack of
+/// quota, with the limit and when it resets.
+pub fn rate_limit_error(response: &Response) -> Option<anyhow::Error> {
+    let status = response.status().as_u16();
+    let header = |name: &str| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<i64>().ok())
+    };
+    if !(status == 403 || status == 429) || header("x-ratelimit-remaining") != Some(0) {
+        return None;
+    }
+    let limit = header("x-ratelimit-limit")
+        .map(|l| format!(" of {} requests/hour", l))
+        .unwrap_or_default();
+    let reset = header("x-ratelimit-reset")
+        .map(|at| {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs() as i64;
+            format!("; resets in {} min", ((at - now).max(0) + 59) / 60)
+        })
+        .unwrap_or_default();
+    let hint = if has_github_token() {
+        ""
+    } else {
+        ". Add a GitHub token to raise the limit"
+    };
+    Some(coded(
+        ErrorCode::GithubRateLimited,
+        format!("GitHub API quota used up{}{}{}", limit, reset, hint),
+    ))
+}
+
 #[derive(Debug, Deserialize)]
 struct SearchResponse {
     items: Vec<RepoItem>,
@@ -15,9 +96,13 @@ struct RepoItem {
     stargazers_count: u64,
     updated_at: String,
     clone_url: String,
+    #[serde(default)]
+    language: Option<String>,
+    #[serde(default)]
+    topics: Vec<String>,
 }
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, Deserialize)]
 pub struct RepoSummary {
     pub full_name: String,
     pub html_url: String,
@@ -25,48 +110,171 @@ pub struct RepoSummary {
     pub stars: u64,
     pub updated_at: String,
     pub clone_url: String,
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub topics: Vec<String>,
+}
+
+/// Optional narrowing of a repository search; empty fields are ignored.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepoSearchFilters {
+    pub topic: Option<String>,
+    pub language: Option<String>,
+    pub min_stars: Option<u64>,
+    /// `stars` or `updated`, newest / most starred first; default is best match.
+    pub sort: Option<String>,
+}
+
+pub fn search_github_repos(
+    provider: &SourceProvider,
+    query: &str,
+    limit: usize,
+    filters: &RepoSearchFilters,
+) -> Result<Vec<RepoSummary>> {
+    let key = format!("{}\n{}\n{}\n{:?}", provider.api_url, query, limit, filters);
+    offline_cache::cached("github-search", &key, RESPONSE_TTL, || {
+        search_github_repos_inner(&provider.api_url, query, limit, filters)
+    })
 }
 
-pub fn search_github_repos(query: &str, limit: usize) -> Result<Vec<RepoSummary>> {
-    search_github_repos_inner("https://api.github.com", query, limit)
+/// The `q` parameter: the free text followed by GitHub search qualifiers.
+fn build_search_query(query: &str, filters: &RepoSearchFilters) -> Result<String> {
+    let mut q = query.trim().to_string();
+    let mut qualify = |name: &str, value: &str| {
+        if !q.is_empty() {
+            q.push(' ');
+        }
+        q.push_str(&format!("{}:{}", name, value));
+    };
+    for (name, value) in [("topic", &filters.topic), ("language", &filters.language)] {
+        let Some(value) = value.as_deref().map(str::trim).filter(|v| !v.is_empty()) else {
+            continue;
+        };
+        if value.contains(char::is_whitespace) || value.contains(':') {
+            anyhow::bail!("invalid {} filter: {}", name, value);
+        }
+        qualify(name, value);
+    }
+    if let Some(min) = filters.min_stars.filter(|m| *m > 0) {
+        qualify("stars", &format!(">={}", min));
+    }
+    if q.is_empty() {
+        anyhow::bail!("enter a search term or a filter");
+    }
+    Ok(q)
 }
 
 fn search_github_repos_inner(
     base_url: &str,
     query: &str,
     limit: usize,
+    filters: &RepoSearchFilters,
 ) -> Result<Vec<RepoSummary>> {
-    let client = Client::new();
+    let client = http_client()?;
     let base_url = base_url.trim_end_matches('/');
-    let url = format!(
+    let mut url = format!(
         "{}/search/repositories?q={}&per_page={}",
         base_url,
-        urlencoding::encode(query),
+        urlencoding::encode(&build_search_query(query, filters)?),
         limit.clamp(1, 50)
     );
+    match filters.sort.as_deref().filter(|s| !s.is_empty()) {
+        Some(sort @ ("stars" | "updated")) => {
+            url.push_str(&format!("&sort={}&order=desc", sort));
+        }
+        Some(other) => anyhow::bail!("unsupported sort: {} (use stars or updated)", other),
+        None => {}
+    }
 
-    let response = client
-        .get(url)
-        .header("User-Agent", "skills-hub")
-        .send()
-        .context("GitHub search request failed")?
-        .error_for_status()
-        .context("GitHub search returned error")?;
+    let response = github_get(&client, &url, "GitHub search")?;
 
     let result: SearchResponse = response.json().context("parse GitHub response")?;
 
-    Ok(result
-        .items
-        .into_iter()
-        .map(|item| RepoSummary {
+    Ok(result.items.into_iter().map(RepoSummary::from).collect())
+}
+
+/// Most repos returned by one listing; GitHub pages hold at most 100.
+const MAX_LISTED_REPOS: usize = 300;
+const LIST_PAGE_SIZE: usize = 100;
+
+/// Repositories of a user or organization, most recently updated first.
+pub fn list_github_repos(
+    provider: &SourceProvider,
+    owner: &str,
+    limit: usize,
+) -> Result<Vec<RepoSummary>> {
+    let key = format!("{}\n{}\n{}", provider.api_url, owner, limit);
+    offline_cache::cached("github-repos", &key, RESPONSE_TTL, || {
+        list_github_repos_inner(&provider.api_url, owner, limit)
+    })
+}
+
+fn list_github_repos_inner(base_url: &str, owner: &str, limit: usize) -> Result<Vec<RepoSummary>> {
+    let owner = owner.trim();
+    if owner.is_empty()
+        || !owner
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+    {
+        anyhow::bail!("invalid GitHub user or organization: {}", owner);
+    }
+    let client = http_client()?;
+    let base_url = base_url.trim_end_matches('/');
+    let limit = limit.clamp(1, MAX_LISTED_REPOS);
+    let per_page = limit.min(LIST_PAGE_SIZE);
+
+    // The org endpoint also lists private repos the token can see; users
+    // are not orgs and answer 404 there.
+    let mut kind = "orgs";
+    let mut repos = Vec::new();
+    let mut page = 1;
+    while repos.len() < limit {
+        let url = format!(
+            "{}/{}/{}/repos?sort=updated&per_page={}&page={}",
+            base_url, kind, owner, per_page, page
+        );
+        let response = github_send(&client, &url, "GitHub repo list")?;
+        if let Some(err) = rate_limit_error(&response) {
+            return Err(err);
+        }
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            if kind == "orgs" {
+                kind = "users";
+                continue;
+            }
+            anyhow::bail!("GitHub user or organization not found: {}", owner);
+        }
+        let items: Vec<RepoItem> = response
+            .error_for_status()
+            .context("GitHub repo list returned error")?
+            .json()
+            .context("parse GitHub repo list")?;
+        let last_page = items.len() < per_page;
+        repos.extend(items.into_iter().map(RepoSummary::from));
+        if last_page {
+            break;
+        }
+        page += 1;
+    }
+    repos.truncate(limit);
+    Ok(repos)
+}
+
+impl From<RepoItem> for RepoSummary {
+    fn from(item: RepoItem) -> Self {
+        Self {
             full_name: item.full_name,
             html_url: item.html_url,
             description: item.description,
             stars: item.stargazers_count,
             updated_at: item.updated_at,
             clone_url: item.clone_url,
-        })
-        .collect())
+            language: item.language,
+            topics: item.topics,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::{Context, Result};
+use reqwest::blocking::Client;
+use serde::{Deserialize, Serialize};
+
+use super::github_search::{github_get, GITHUB_API_URL};
+use super::http_client::http_client;
+use super::skill_store::SkillStore;
+
+pub const GITHUB_ACCOUNT_KEY: &str = "github_account";
+
+/// Starred repos scanned per request; each one may cost a tree lookup.
+const MAX_STARRED_REPOS: usize = 200;
+
+#[derive(Debug, Deserialize)]
+struct StarredRepo {
+    full_name: String,
+    html_url: String,
+    description: Option<String>,
+    stargazers_count: u64,
+    clone_url: String,
+    default_branch: String,
+    pushed_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TreeResponse {
+    tree: Vec<TreeEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TreeEntry {
+    path: String,
+    #[serde(rename = "type")]
+    entry_type: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StarSuggestion {
+    pub full_name: String,
+    pub html_url: String,
+    pub description: Option<String>,
+    pub stars: u64,
+    pub clone_url: String,
+    /// Directories holding a SKILL.md, relative to the repo root ("" = root).
+    pub skill_paths: Vec<String>,
+    pub installed: bool,
+}
+
+pub fn get_github_account(store: &SkillStore) -> Result<Option<String>> {
+    store.get_setting(GITHUB_ACCOUNT_KEY)
+}
+
+pub fn set_github_account(store: &SkillStore, account: Option<&str>) -> Result<()> {
+    let account = account.map(str::trim).unwrap_or("");
+    if account.contains('/') || account.contains(char::is_whitespace) {
+        anyhow::bail!("invalid GitHub username: {}", account);
+    }
+    store.set_setting(GITHUB_ACCOUNT_KEY, account)
+}
+
+/// Starred repos of the linked account that contain at least one SKILL.md.
+pub fn list_star_suggestions(store: &SkillStore) -> Result<Vec<StarSuggestion>> {
+    let account = get_github_account(store)?
+        .filter(|a| !a.is_empty())
+        .ok_or_else(|| anyhow::anyhow!("no GitHub account linked"))?;
+    let installed: Vec<String> = store
+        .list_skills()?
+        .into_iter()
+        .filter_map(|s| s.source_ref)
+        .map(|r| normalize_repo_url(&r))
+        .collect();
+    list_star_suggestions_inner(GITHUB_API_URL, &account, &installed)
+}
+
+fn list_star_suggestions_inner(
+    base_url: &str,
+    account: &str,
+    installed: &[String],
+) -> Result<Vec<StarSuggestion>> {
+    let client = http_client()?;
+    let base_url = base_url.trim_end_matches('/');
+    let mut suggestions = Vec::new();
+    for repo in fetch_starred(&client, base_url, account)? {
+        let skill_paths = match cached_skill_paths(&repo) {
+            Some(paths) => paths,
+            None => match fetch_skill_paths(&client, base_url, &repo) {
+                Ok(paths) => {
+                    remember_skill_paths(&repo, &paths);
+                    paths
+                }
+                // Unreadable repos (empty, rate limited) are retried next time.
+                Err(_) => Vec::new(),
+            },
+        };
+        if skill_paths.is_empty() {
+            continue;
+        }
+        let url = normalize_repo_url(&repo.html_url);
+        suggestions.push(StarSuggestion {
+            installed: installed
+                .iter()
+                .any(|i| *i == url || i.starts_with(&format!("{}/", url))),
+            full_name: repo.full_name,
+            html_url: repo.html_url,
+            description: repo.description,
+            stars: repo.stargazers_count,
+            clone_url: repo.clone_url,
+            skill_paths,
+        });
+    }
+    Ok(suggestions)
+}
+
+fn fetch_starred(client: &Client, base_url: &str, account: &str) -> Result<Vec<StarredRepo>> {
+    let mut repos = Vec::new();
+    let mut page = 1;
+    while repos.len() < MAX_STARRED_REPOS {
+        let url = format!(
+            "{}/users/{}/starred?per_page=100&page={}",
+            base_url,
+            urlencoding::encode(account),
+            page
+        );
+        let batch: Vec<StarredRepo> = github_get(client, &url, "GitHub starred")?
+            .json()
+            .context("parse GitHub starred response")?;
+        let done = batch.len() < 100;
+        repos.extend(batch);
+        if done {
+            break;
+        }
+        page += 1;
+    }
+    repos.truncate(MAX_STARRED_REPOS);
+    Ok(repos)
+}
+
+fn fetch_skill_paths(client: &Client, base_url: &str, repo: &StarredRepo) -> Result<Vec<String>> {
+    let url = format!(
+        "{}/repos/{}/git/trees/{}?recursive=1",
+        base_url,
+        repo.full_name,
+        urlencoding::encode(&repo.default_branch)
+    );
+    let tree: TreeResponse = github_get(client, &url, "GitHub tree")?
+        .json()
+        .context("parse GitHub tree response")?;
+    Ok(skill_dirs(&tree.tree))
+}
+
+fn skill_dirs(entries: &[TreeEntry]) -> Vec<String> {
+    let mut dirs: Vec<String> = entries
+        .iter()
+        .filter(|e| e.entry_type == "blob")
+        .filter_map(|e| {
+            if e.path == "SKILL.md" {
+                Some(String::new())
+            } else {
+                e.path.strip_suffix("/SKILL.md").map(str::to_string)
+            }
+        })
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+// Tree lookups are keyed by repo and its last push, so a repo is re-checked
+// only after it changes.
+type TreeCache = HashMap<String, (Option<String>, Vec<String>)>;
+
+static TREE_CACHE: OnceLock<Mutex<TreeCache>> = OnceLock::new();
+
+fn tree_cache() -> &'static Mutex<TreeCache> {
+    TREE_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn cached_skill_paths(repo: &StarredRepo) -> Option<Vec<String>> {
+    let cache = tree_cache().lock().unwrap_or_else(|err| err.into_inner());
+    cache
+        .get(&repo.full_name)
+        .filter(|(pushed_at, _)| *pushed_at == repo.pushed_at)
+        .map(|(_, paths)| paths.clone())
+}
+
+fn remember_skill_paths(repo: &StarredRepo, paths: &[String]) {
+    let mut cache = tree_cache().lock().unwrap_or_else(|err| err.into_inner());
+    cache.insert(
+        repo.full_name.clone(),
+        (repo.pushed_at.clone(), paths.to_vec()),
+    );
+}
+
+fn normalize_repo_url(url: &str) -> String {
+    url.trim()
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+#[path = "tests/github_stars.rs"]
+mod tests;
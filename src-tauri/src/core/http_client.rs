@@ -0,0 +1,360 @@
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use reqwest::{Method, NoProxy, Proxy, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+
+use super::skill_store::SkillStore;
+
+pub const HTTP_SETTINGS_KEY: &str = "http_settings";
+const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
+const DEFAULT_TIMEOUT_SECS: u64 = 60;
+const DEFAULT_MAX_RETRIES: u32 = 2;
+const MAX_TIMEOUT_SECS: u64 = 600;
+const MAX_RETRIES: u32 = 5;
+const RETRY_BACKOFF_MS: u64 = 500;
+/// Longest `Retry-After` honoured; anything longer fails right away rather
+/// than stalling the UI.
+const MAX_RETRY_AFTER_SECS: u64 = 10;
+
+/// Network settings shared by every module that talks HTTP (ClawHub,
+/// GitHub, registries) and by git clones. `proxy_url` takes `http://`,
+/// `https://`, `socks5://` or `socks5h://`; when unset the usual
+/// `HTTPS_PROXY` style environment variables still apply.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HttpSettings {
+    pub proxy_url: Option<String>,
+    /// Comma-separated hosts that bypass the proxy, as in `NO_PROXY`.
+    pub no_proxy: Option<String>,
+    pub connect_timeout_secs: u64,
+    pub timeout_secs: u64,
+    /// Retries of a GET or HEAD after a dropped connection, timeout or
+    /// 429/502/503/504.
+    pub max_retries: u32,
+    /// Tunnel SSH to remote hosts through `proxy_url` with HTTP CONNECT.
+    pub ssh_via_proxy: bool,
+}
+
+impl Default for HttpSettings {
+    fn default() -> Self {
+        Self {
+            proxy_url: None,
+            no_proxy: None,
+            connect_timeout_secs: DEFAULT_CONNECT_TIMEOUT_SECS,
+            timeout_secs: DEFAULT_TIMEOUT_SECS,
+            max_retries: DEFAULT_MAX_RETRIES,
+            ssh_via_proxy: false,
+        }
+    }
+}
+
+struct HttpState {
+    settings: HttpSettings,
+    client: Option<Client>,
+}
+
+static STATE: OnceLock<Mutex<HttpState>> = OnceLock::new();
+
+fn state_cell() -> &'static Mutex<HttpState> {
+    STATE.get_or_init(|| {
+        Mutex::new(HttpState {
+            settings: HttpSettings::default(),
+            client: None,
+        })
+    })
+}
+
+pub fn user_agent() -> String {
+    format!("skills-hub/{}", env!("CARGO_PKG_VERSION"))
+}
+
+/// Build a client for `settings`; fails on a malformed proxy URL.
+pub fn build_client(settings: &HttpSettings) -> Result<Client> {
+    let mut builder = Client::builder()
+        .user_agent(user_agent())
+        .connect_timeout(Duration::from_secs(settings.connect_timeout_secs))
+        .timeout(Duration::from_secs(settings.timeout_secs));
+    if let Some(url) = settings.proxy_url.as_deref() {
+        let proxy = Proxy::all(url)
+            .with_context(|| format!("invalid proxy URL: {}", url))?
+            .no_proxy(settings.no_proxy.as_deref().and_then(NoProxy::from_string));
+        builder = builder.proxy(proxy);
+    }
+    builder.build().context("build HTTP client")
+}
+
+/// The shared client, built on first use from the current settings.
+/// Cloning a `Client` is cheap; it shares one connection pool.
+pub fn http_client() -> Result<Client> {
+    let mut state = state_cell().lock().unwrap_or_else(|err| err.into_inner());
+    if let Some(client) = &state.client {
+        return Ok(client.clone());
+    }
+    let client = match build_client(&state.settings) {
+        Ok(client) => client,
+        Err(err) => {
+            log::warn!("[http] {:#}; falling back to defaults", err);
+            build_client(&HttpSettings::default())?
+        }
+    };
+    state.client = Some(client.clone());
+    Ok(client)
+}
+
+pub fn current_settings() -> HttpSettings {
+    state_cell()
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .settings
+        .clone()
+}
+
+fn apply_settings(settings: HttpSettings) {
+    let mut state = state_cell().lock().unwrap_or_else(|err| err.into_inner());
+    state.settings = settings;
+    state.client = None;
+}
+
+fn normalize(mut settings: HttpSettings) -> Result<HttpSettings> {
+    let trimmed = |value: Option<String>| {
+        value
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    settings.proxy_url = trimmed(settings.proxy_url);
+    settings.no_proxy = trimmed(settings.no_proxy);
+    if let Some(url) = settings.proxy_url.as_deref() {
+        let scheme = url.split("://").next().unwrap_or_default();
+        if !url.contains("://") || !matches!(scheme, "http" | "https" | "socks5" | "socks5h") {
+            anyhow::bail!("proxy URL must start with http://, https://, socks5:// or socks5h://");
+        }
+    }
+    for (name, secs) in [
+        ("connect timeout", settings.connect_timeout_secs),
+        ("timeout", settings.timeout_secs),
+    ] {
+        if secs == 0 || secs > MAX_TIMEOUT_SECS {
+            anyhow::bail!(
+                "{} must be between 1 and {} seconds",
+                name,
+                MAX_TIMEOUT_SECS
+            );
+        }
+    }
+    if settings.max_retries > MAX_RETRIES {
+        anyhow::bail!("retries must be between 0 and {}", MAX_RETRIES);
+    }
+    if settings.ssh_via_proxy
+        && !settings
+            .proxy_url
+            .as_deref()
+            .is_some_and(|url| url.starts_with("http://"))
+    {
+        anyhow::bail!("SSH over a proxy needs an http:// proxy URL (HTTP CONNECT)");
+    }
+    Ok(settings)
+}
+
+pub fn get_http_settings(store: &SkillStore) -> HttpSettings {
+    store
+        .get_setting(HTTP_SETTINGS_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str::<HttpSettings>(&raw).ok())
+        .and_then(|settings| normalize(settings).ok())
+        .unwrap_or_default()
+}
+
+pub fn set_http_settings(store: &SkillStore, settings: HttpSettings) -> Result<HttpSettings> {
+    let settings = normalize(settings)?;
+    build_client(&settings)?;
+    store.set_setting(HTTP_SETTINGS_KEY, &serde_json::to_string(&settings)?)?;
+    apply_settings(settings.clone());
+    Ok(settings)
+}
+
+/// Load the saved settings into the process-wide client.
+pub fn load_http_settings(store: &SkillStore) {
+    apply_settings(get_http_settings(store));
+}
+
+/// Whether `host` matches an entry of a `NO_PROXY` style list: `*`, an
+/// exact host, or a domain suffix with or without the leading dot.
+pub fn bypasses_proxy(no_proxy: Option<&str>, host: &str) -> bool {
+    let host = host
+        .trim_matches(|c| c == '[' || c == ']')
+        .to_ascii_lowercase();
+    no_proxy.unwrap_or_default().split(',').any(|entry| {
+        let entry = entry.trim().trim_start_matches('.').to_ascii_lowercase();
+        !entry.is_empty()
+            && (entry == "*" || host == entry || host.ends_with(&format!(".{}", entry)))
+    })
+}
+
+/// The configured proxy for connections to `host`, unless `no_proxy`
+/// exempts it.
+pub fn proxy_for_host(host: &str) -> Option<String> {
+    let settings = current_settings();
+    let proxy = settings.proxy_url?;
+    (!bypasses_proxy(settings.no_proxy.as_deref(), host)).then_some(proxy)
+}
+
+/// Like [`proxy_for_host`] for a clone URL; scp-style `git@host:path`
+/// remotes go over SSH and get none.
+pub fn proxy_for_url(url: &str) -> Option<String> {
+    let parsed = Url::parse(url).ok()?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return None;
+    }
+    proxy_for_host(parsed.host_str()?)
+}
+
+/// Open a TCP stream to `host:port` through an HTTP proxy with `CONNECT`,
+/// sending the proxy URL's credentials as basic auth.
+pub fn http_connect_tunnel(
+    proxy_url: &str,
+    host: &str,
+    port: u16,
+    timeout: Duration,
+) -> Result<TcpStream> {
+    let proxy =
+        Url::parse(proxy_url).with_context(|| format!("invalid proxy URL: {}", proxy_url))?;
+    if proxy.scheme() != "http" {
+        anyhow::bail!("SSH over a proxy needs an http:// proxy URL (HTTP CONNECT)");
+    }
+    let proxy_host = proxy
+        .host_str()
+        .ok_or_else(|| anyhow::anyhow!("proxy URL has no host: {}", proxy_url))?;
+    let proxy_port = proxy.port_or_known_default().unwrap_or(80);
+    let addr = (proxy_host, proxy_port)
+        .to_socket_addrs()
+        .with_context(|| format!("resolve proxy {}:{}", proxy_host, proxy_port))?
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("no address found for proxy {}", proxy_host))?;
+    let mut stream = TcpStream::connect_timeout(&addr, timeout)
+        .with_context(|| format!("TCP connect to proxy {}:{}", proxy_host, proxy_port))?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+
+    let target = if host.contains(':') {
+        format!("[{}]:{}", host, port)
+    } else {
+        format!("{}:{}", host, port)
+    };
+    let mut request = format!("CONNECT {0} HTTP/1.1\r\nHost: {0}\r\n", target);
+    if !proxy.username().is_empty() {
+        let user = urlencoding::decode(proxy.username())?;
+        let password = urlencoding::decode(proxy.password().unwrap_or_default())?;
+        let credentials =
+            base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", user, password));
+        request.push_str(&format!("Proxy-Authorization: Basic {}\r\n", credentials));
+    }
+    request.push_str("\r\n");
+    stream
+        .write_all(request.as_bytes())
+        .context("send CONNECT to proxy")?;
+
+    // Read the reply a byte at a time so nothing after the headers (the
+    // SSH banner) is swallowed.
+    let mut head = Vec::new();
+    let mut byte = [0u8; 1];
+    while !head.ends_with(b"\r\n\r\n") {
+        if head.len() > 16 * 1024 {
+            anyhow::bail!("proxy reply to CONNECT is too long");
+        }
+        if stream.read(&mut byte).context("read proxy reply")? == 0 {
+            anyhow::bail!("proxy closed the connection during CONNECT");
+        }
+        head.push(byte[0]);
+    }
+    let head = String::from_utf8_lossy(&head);
+    let status_line = head.lines().next().unwrap_or_default().trim();
+    if status_line.split_whitespace().nth(1) != Some("200") {
+        anyhow::bail!("proxy refused CONNECT to {}: {}", target, status_line);
+    }
+    stream.set_read_timeout(None)?;
+    stream.set_write_timeout(None)?;
+    Ok(stream)
+}
+
+fn is_idempotent(request: &RequestBuilder) -> bool {
+    request
+        .try_clone()
+        .and_then(|clone| clone.build().ok())
+        .is_some_and(|built| matches!(*built.method(), Method::GET | Method::HEAD))
+}
+
+fn retryable_status(status: StatusCode) -> bool {
+    matches!(status.as_u16(), 429 | 502 | 503 | 504)
+}
+
+/// How long the server asked us to wait, if it said so in whole seconds.
+fn retry_after(response: &Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+/// Send `request`, retrying dropped connections, timeouts and overloaded
+/// responses under the current settings. Only GET and HEAD are retried: a
+/// POST may already have taken effect when its connection dropped. Requests
+/// whose body cannot be cloned (streams) are sent once too.
+pub fn send(request: RequestBuilder) -> reqwest::Result<Response> {
+    send_with_retries(request, current_settings().max_retries)
+}
+
+pub fn send_with_retries(request: RequestBuilder, max_retries: u32) -> reqwest::Result<Response> {
+    let max_retries = if is_idempotent(&request) {
+        max_retries
+    } else {
+        0
+    };
+    let mut retry = 0;
+    let mut pending = request;
+    loop {
+        let next = if retry < max_retries {
+            pending.try_clone()
+        } else {
+            None
+        };
+        let Some(next) = next else {
+            return pending.send();
+        };
+        let backoff = Duration::from_millis(RETRY_BACKOFF_MS << retry.min(8));
+        let delay = match pending.send() {
+            Ok(response) if retryable_status(response.status()) => match retry_after(&response) {
+                Some(wait) if wait.as_secs() > MAX_RETRY_AFTER_SECS => return Ok(response),
+                Some(wait) => wait,
+                None => backoff,
+            },
+            Ok(response) => return Ok(response),
+            Err(err) if err.is_connect() || err.is_timeout() => backoff,
+            Err(err) => return Err(err),
+        };
+        retry += 1;
+        log::warn!(
+            "[http] request failed; retry {}/{} in {:?}",
+            retry,
+            max_retries,
+            delay
+        );
+        std::thread::sleep(delay);
+        pending = next;
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/http_client.rs"]
+mod tests;
@@ -1,18 +1,34 @@
 use std::path::{Path, PathBuf};
-use std::sync::{Mutex, OnceLock};
 
 use anyhow::{Context, Result};
-use serde::{Deserialize, Serialize};
-use tauri::Manager;
+use serde::Serialize;
 use uuid::Uuid;
 
-use super::cache_cleanup::get_git_cache_ttl_secs;
-use super::central_repo::{ensure_central_repo, resolve_central_repo_path};
+use super::app_paths::AppPaths;
+use super::cache_cleanup::{
+    enforce_git_cache_cap, get_git_cache_ttl_secs, git_cache_root, lock_git_cache, read_cache_meta,
+    touch_cache_entry, write_cache_meta, RepoCacheMeta,
+};
+use super::central_history::record_change;
+use super::central_repo::{ensure_central_repo_writable, writable_central_repo};
 use super::content_hash::hash_dir;
+use super::content_scan::{scan_skill_dir, ContentFinding};
+use super::errors::{coded, coded_with, ErrorCode};
 use super::git_fetcher::{clone_or_pull, ls_remote_head};
+use super::locale::{tr, Msg};
+use super::now_ms;
+use super::quarantine::{install_status, status_after_update};
+use super::resource_types::{sync_resource_file, ResourceType};
+use super::skill_drift::record_skill_manifest;
+use super::skill_events;
+use super::skill_format::{tool_format, SkillFormat};
+use super::skill_lock::ensure_unlocked;
+use super::skill_metadata::refresh_skill_metadata;
 use super::skill_store::{SkillRecord, SkillStore};
+use super::source_providers::provider_for_url;
 use super::sync_engine::copy_dir_recursive;
-use super::sync_engine::sync_dir_copy_with_overwrite;
+use super::sync_engine::SyncMode;
+use super::sync_plan::mode_name;
 use super::tool_adapters::adapter_by_key;
 use super::tool_adapters::is_tool_installed;
 
@@ -21,10 +37,15 @@ pub struct InstallResult {
     pub name: String,
     pub central_path: PathBuf,
     pub content_hash: Option<String>,
+    /// Secrets, piped installers and suspicious instructions found in the
+    /// installed files; review them before syncing the skill to agents.
+    pub content_warnings: Vec<ContentFinding>,
+    /// `verified`, `unsigned` or `untrusted` for `.skillpkg` installs.
+    pub signature_status: Option<String>,
 }
 
-pub fn install_local_skill<R: tauri::Runtime>(
-    app: &tauri::AppHandle<R>,
+pub fn install_local_skill(
+    paths: &AppPaths,
     store: &SkillStore,
     source_path: &Path,
     name: Option<String>,
@@ -40,8 +61,7 @@ pub fn install_local_skill<R: tauri::Runtime>(
             .unwrap_or_else(|| "unnamed-skill".to_string())
     });
 
-    let central_dir = resolve_central_repo_path(app, store)?;
-    ensure_central_repo(&central_dir)?;
+    let central_dir = writable_central_repo(paths, store)?;
     let central_path = central_dir.join(&name);
 
     cleanup_orphan_central_path(&central_path, store)?;
@@ -64,13 +84,94 @@ pub fn install_local_skill<R: tauri::Runtime>(
         updated_at: now,
         last_sync_at: None,
         last_seen_at: now,
-        status: "ok".to_string(),
+        status: install_status(store),
+        group_name: None,
+    };
+
+    store.upsert_skill(&record)?;
+    refresh_skill_metadata(store, &record.id, &central_path);
+    record_skill_manifest(store, &record.id, &central_path);
+
+    record_change(store, &central_path, &format!("Install {}", record.name));
+    skill_events::skill_installed(&record.id, &record.name);
+
+    Ok(InstallResult {
+        content_warnings: scan_installed(&record.name, &central_path),
+        signature_status: None,
+        skill_id: record.id,
+        name: record.name,
+        central_path,
+        content_hash,
+    })
+}
+
+/// Install a single-file agent or command from `source_path` (a markdown
+/// file). It is kept as `<central>/<name>/<file>`; skills go through
+/// [`install_local_skill`].
+pub fn install_local_resource(
+    paths: &AppPaths,
+    store: &SkillStore,
+    kind: ResourceType,
+    source_path: &Path,
+    name: Option<String>,
+) -> Result<InstallResult> {
+    if kind == ResourceType::Skill {
+        return install_local_skill(paths, store, source_path, name);
+    }
+    if !source_path.is_file() {
+        anyhow::bail!("{} must be a single file: {:?}", kind.as_str(), source_path);
+    }
+    if !source_path.extension().is_some_and(|ext| ext == "md") {
+        anyhow::bail!(
+            "{} must be a markdown file: {:?}",
+            kind.as_str(),
+            source_path
+        );
+    }
+
+    let name = name.unwrap_or_else(|| {
+        source_path
+            .file_stem()
+            .map(|v| v.to_string_lossy().to_string())
+            .unwrap_or_else(|| format!("unnamed-{}", kind.as_str()))
+    });
+
+    let central_dir = writable_central_repo(paths, store)?;
+    let central_path = central_dir.join(&name);
+
+    cleanup_orphan_central_path(&central_path, store)?;
+
+    copy_file_into(source_path, &central_path)?;
+
+    let now = now_ms();
+    let content_hash = compute_content_hash(&central_path);
+
+    let record = SkillRecord {
+        id: Uuid::new_v4().to_string(),
+        name,
+        source_type: "local".to_string(),
+        source_ref: Some(source_path.to_string_lossy().to_string()),
+        source_revision: None,
+        central_path: central_path.to_string_lossy().to_string(),
+        content_hash: content_hash.clone(),
+        created_at: now,
+        updated_at: now,
+        last_sync_at: None,
+        last_seen_at: now,
+        status: install_status(store),
         group_name: None,
     };
 
     store.upsert_skill(&record)?;
+    store.set_skill_resource_type(&record.id, kind.as_str())?;
+    record_skill_manifest(store, &record.id, &central_path);
+
+    record_change(store, &central_path, &format!("Install {}", record.name));
+    skill_events::skill_installed(&record.id, &record.name);
 
     Ok(InstallResult {
+        content_warnings: scan_installed(&record.name, &central_path),
+        signature_status: None,
         skill_id: record.id,
         name: record.name,
         central_path,
@@ -78,8 +179,19 @@ pub fn install_local_skill<R: tauri::Runtime>(
     })
 }
 
-pub fn install_git_skill<R: tauri::Runtime>(
-    app: &tauri::AppHandle<R>,
+fn copy_file_into(source_path: &Path, dir: &Path) -> Result<()> {
+    let file_name = source_path
+        .file_name()
+        .ok_or_else(|| anyhow::anyhow!("invalid source file {:?}", source_path))?;
+    std::fs::create_dir_all(dir).with_context(|| format!("create dir {:?}", dir))?;
+    let dest = dir.join(file_name);
+    std::fs::copy(source_path, &dest)
+        .with_context(|| format!("copy {:?} -> {:?}", source_path, dest))?;
+    Ok(())
+}
+
+pub fn install_git_skill(
+    paths: &AppPaths,
     store: &SkillStore,
     repo_url: &str,
     name: Option<String>,
@@ -97,8 +209,7 @@ pub fn install_git_skill<R: tauri::Runtime>(
         }
     });
 
-    let central_dir = resolve_central_repo_path(app, store)?;
-    ensure_central_repo(&central_dir)?;
+    let central_dir = writable_central_repo(paths, store)?;
     let central_path = central_dir.join(&name);
 
     cleanup_orphan_central_path(&central_path, store)?;
@@ -106,7 +217,7 @@ pub fn install_git_skill<R: tauri::Runtime>(
     if let Some(subpath) = &parsed.subpath {
         // ── Subpath specified: clone to cache, then copy the subdirectory ──
         let (repo_dir, rev) =
-            clone_to_cache(app, store, &parsed.clone_url, parsed.branch.as_deref())?;
+            clone_to_cache(paths, store, &parsed.clone_url, parsed.branch.as_deref())?;
 
         let copy_src = {
             let sub_src = repo_dir.join(subpath);
@@ -133,12 +244,19 @@ pub fn install_git_skill<R: tauri::Runtime>(
             updated_at: now,
             last_sync_at: None,
             last_seen_at: now,
-            status: "ok".to_string(),
+            status: install_status(store),
             group_name: derive_group_name_from_clone_url(&parsed.clone_url),
         };
         store.upsert_skill(&record)?;
+        refresh_skill_metadata(store, &record.id, &central_path);
+        record_skill_manifest(store, &record.id, &central_path);
+
+        record_change(store, &central_path, &format!("Install {}", record.name));
+        skill_events::skill_installed(&record.id, &record.name);
 
         return Ok(InstallResult {
+            content_warnings: scan_installed(&record.name, &central_path),
+            signature_status: None,
             skill_id: record.id,
             name: record.name,
             central_path,
@@ -165,9 +283,7 @@ pub fn install_git_skill<R: tauri::Runtime>(
         if count >= 2 {
             // Clean up the cloned directory before bailing.
             let _ = std::fs::remove_dir_all(&central_path);
-            anyhow::bail!(
-                "MULTI_SKILLS|{}", "该仓库包含多个 Skills，请复制具体 Skill 文件夹链接（例如 GitHub 的 /tree/<branch>/skills/<name>），再导入。"
-            );
+            return Err(coded(ErrorCode::MultiSkills, tr(Msg::MultiSkills)));
         }
     }
 
@@ -186,13 +302,20 @@ pub fn install_git_skill<R: tauri::Runtime>(
         updated_at: now,
         last_sync_at: None,
         last_seen_at: now,
-        status: "ok".to_string(),
+        status: install_status(store),
         group_name: derive_group_name_from_clone_url(&parsed.clone_url),
     };
 
     store.upsert_skill(&record)?;
+    refresh_skill_metadata(store, &record.id, &central_path);
+    record_skill_manifest(store, &record.id, &central_path);
+
+    record_change(store, &central_path, &format!("Install {}", record.name));
+    skill_events::skill_installed(&record.id, &record.name);
 
     Ok(InstallResult {
+        content_warnings: scan_installed(&record.name, &central_path),
+        signature_status: None,
         skill_id: record.id,
         name: record.name,
         central_path,
@@ -200,19 +323,33 @@ pub fn install_git_skill<R: tauri::Runtime>(
     })
 }
 
+/// Scan a freshly installed skill, logging what needs review.
+pub(crate) fn scan_installed(name: &str, central_path: &Path) -> Vec<ContentFinding> {
+    let findings = scan_skill_dir(central_path);
+    if !findings.is_empty() {
+        log::warn!(
+            "[installer] {} has {} content warning(s) to review before syncing",
+            name,
+            findings.len()
+        );
+    }
+    findings
+}
+
 #[derive(Clone, Debug)]
-struct ParsedGitSource {
-    clone_url: String,
-    branch: Option<String>,
-    subpath: Option<String>,
+pub struct ParsedGitSource {
+    pub clone_url: String,
+    pub branch: Option<String>,
+    pub subpath: Option<String>,
 }
 
-fn parse_github_url(input: &str) -> ParsedGitSource {
+pub fn parse_github_url(input: &str) -> ParsedGitSource {
     // Supports:
     // - https://github.com/owner/repo
     // - https://github.com/owner/repo.git
     // - https://github.com/owner/repo/tree/<branch>/<path>
     // - https://github.com/owner/repo/blob/<branch>/<path>
+    // and the same forms on any configured GitHub Enterprise host.
     let trimmed = input.trim().trim_end_matches('/');
 
     // Convenience: allow GitHub shorthand inputs like `owner/repo` (and `owner/repo/tree/<branch>/...`).
@@ -230,16 +367,14 @@ fn parse_github_url(input: &str) -> ParsedGitSource {
     };
 
     let trimmed = normalized.trim_end_matches('/');
-    let gh_prefix = "https://github.com/";
-    if !trimmed.starts_with(gh_prefix) {
+    let Some((provider, rest)) = provider_for_url(trimmed) else {
         return ParsedGitSource {
             clone_url: trimmed.to_string(),
             branch: None,
             subpath: None,
         };
-    }
+    };
 
-    let rest = &trimmed[gh_prefix.len()..];
     let parts: Vec<&str> = rest.split('/').collect();
     if parts.len() < 2 {
         return ParsedGitSource {
@@ -254,7 +389,7 @@ fn parse_github_url(input: &str) -> ParsedGitSource {
     if let Some(stripped) = repo.strip_suffix(".git") {
         repo = stripped.to_string();
     }
-    let clone_url = format!("https://github.com/{}/{}.git", owner, repo);
+    let clone_url = provider.clone_url(owner, &repo);
 
     if parts.len() >= 4 && (parts[2] == "tree" || parts[2] == "blob") {
         let branch = Some(parts[3].to_string());
@@ -277,7 +412,7 @@ fn parse_github_url(input: &str) -> ParsedGitSource {
     }
 }
 
-fn looks_like_github_shorthand(input: &str) -> bool {
+pub(crate) fn looks_like_github_shorthand(input: &str) -> bool {
     if input.is_empty() {
         return false;
     }
@@ -322,13 +457,6 @@ fn looks_like_github_shorthand(input: &str) -> bool {
     }
 }
 
-fn now_ms() -> i64 {
-    let now = std::time::SystemTime::now()
-        .duration_since(std::time::SystemTime::UNIX_EPOCH)
-        .unwrap_or_default();
-    now.as_millis() as i64
-}
-
 fn derive_name_from_repo_url(repo_url: &str) -> String {
     let mut name = repo_url
         .split('/')
@@ -347,12 +475,8 @@ fn derive_name_from_repo_url(repo_url: &str) -> String {
 
 /// Derive `owner/repo` from a GitHub clone URL for auto-grouping.
 /// e.g. `https://github.com/analogjs/angular-skills.git` → `Some("analogjs/angular-skills")`
-fn derive_group_name_from_clone_url(clone_url: &str) -> Option<String> {
-    let gh_prefix = "https://github.com/";
-    if !clone_url.starts_with(gh_prefix) {
-        return None;
-    }
-    let rest = &clone_url[gh_prefix.len()..];
+pub fn derive_group_name_from_clone_url(clone_url: &str) -> Option<String> {
+    let (_, rest) = provider_for_url(clone_url)?;
     let parts: Vec<&str> = rest.split('/').collect();
     if parts.len() < 2 {
         return None;
@@ -374,11 +498,11 @@ fn derive_group_name_from_clone_url(clone_url: &str) -> Option<String> {
 ///   - Not currently supported with subpath (shouldn't happen in practice)
 fn build_source_ref_with_subpath(clone_url: &str, branch: Option<&str>, subpath: &str) -> String {
     let gh_suffix = ".git";
-    let gh_prefix = "https://github.com/";
+    let is_github = provider_for_url(clone_url).is_some();
 
     // For subpath ".", just return the repo URL (without .git suffix for cleanliness).
     if subpath == "." {
-        if clone_url.starts_with(gh_prefix) {
+        if is_github {
             return clone_url
                 .strip_suffix(gh_suffix)
                 .unwrap_or(clone_url)
@@ -388,7 +512,7 @@ fn build_source_ref_with_subpath(clone_url: &str, branch: Option<&str>, subpath:
     }
 
     // For GitHub URLs, build /tree/<branch>/<subpath> format.
-    if clone_url.starts_with(gh_prefix) {
+    if is_github {
         let base = clone_url.strip_suffix(gh_suffix).unwrap_or(clone_url);
         let branch = branch.unwrap_or("main");
         return format!("{}/tree/{}/{}", base, branch, subpath);
@@ -398,7 +522,7 @@ fn build_source_ref_with_subpath(clone_url: &str, branch: Option<&str>, subpath:
     clone_url.to_string()
 }
 
-fn compute_content_hash(path: &Path) -> Option<String> {
+pub(crate) fn compute_content_hash(path: &Path) -> Option<String> {
     if should_compute_content_hash() {
         hash_dir(path).ok()
     } else {
@@ -445,8 +569,11 @@ pub fn check_skill_updates(store: &SkillStore) -> Vec<SkillUpdateStatus> {
 
     skills
         .into_iter()
-        .filter(|s| s.source_type == "git" || s.source_type == "git-cloned")
+        .filter(|s| matches!(s.source_type.as_str(), "git" | "git-cloned" | "clawhub"))
         .map(|skill| {
+            if skill.source_type == "clawhub" {
+                return clawhub_update_status(skill, super::clawhub_api::latest_clawhub_version);
+            }
             let repo_url = match skill.source_ref.as_deref() {
                 Some(url) => url,
                 None => {
@@ -498,6 +625,49 @@ pub fn check_skill_updates(store: &SkillStore) -> Vec<SkillUpdateStatus> {
         .collect()
 }
 
+/// ClawHub skills record the installed version as `source_revision`; an
+/// update is available when the registry's latest version differs.
+fn clawhub_update_status(
+    skill: SkillRecord,
+    latest_version: impl FnOnce(&str) -> Result<Option<String>>,
+) -> SkillUpdateStatus {
+    let latest = skill
+        .source_ref
+        .as_deref()
+        .and_then(|r| r.strip_prefix("clawhub://"))
+        .ok_or_else(|| anyhow::anyhow!("missing clawhub slug in source_ref"))
+        .and_then(latest_version);
+    match latest {
+        Ok(latest) => SkillUpdateStatus {
+            has_update: match (&skill.source_revision, &latest) {
+                (Some(current), Some(latest)) => current != latest,
+                (None, Some(_)) => true,
+                (_, None) => false,
+            },
+            skill_id: skill.id,
+            name: skill.name,
+            current_rev: skill.source_revision,
+            remote_rev: latest,
+            error: None,
+        },
+        Err(err) => {
+            log::warn!(
+                "[installer] check_skill_updates: ClawHub lookup failed for {}: {:#}",
+                skill.name,
+                err
+            );
+            SkillUpdateStatus {
+                skill_id: skill.id,
+                name: skill.name,
+                has_update: false,
+                current_rev: skill.source_revision,
+                remote_rev: None,
+                error: Some(format!("{:#}", err)),
+            }
+        }
+    }
+}
+
 pub struct UpdateResult {
     pub skill_id: String,
     pub name: String,
@@ -508,14 +678,21 @@ pub struct UpdateResult {
     pub updated_targets: Vec<String>,
 }
 
-pub fn update_managed_skill_from_source<R: tauri::Runtime>(
-    app: &tauri::AppHandle<R>,
+/// Refresh a skill from its source. Locked skills are refused unless
+/// `force` is set.
+pub fn update_managed_skill_from_source(
+    paths: &AppPaths,
     store: &SkillStore,
     skill_id: &str,
+    force: bool,
 ) -> Result<UpdateResult> {
+    ensure_central_repo_writable(store)?;
     let record = store
         .get_skill_by_id(skill_id)?
         .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+    if !force {
+        ensure_unlocked(store, &record)?;
+    }
 
     let central_path = PathBuf::from(record.central_path.clone());
     if !central_path.exists() {
@@ -538,9 +715,7 @@ pub fn update_managed_skill_from_source<R: tauri::Runtime>(
         let _ = std::fs::remove_dir_all(&staging_dir);
     }
 
-    let mut new_revision: Option<String> = None;
-
-    if record.source_type == "git-cloned" {
+    let new_revision = if record.source_type == "git-cloned" {
         // ── Direct clone: just git pull in-place ──
         let repo_url = record
             .source_ref
@@ -550,71 +725,17 @@ pub fn update_managed_skill_from_source<R: tauri::Runtime>(
 
         let rev = clone_or_pull(&parsed.clone_url, &central_path, parsed.branch.as_deref())
             .with_context(|| format!("git pull {} in {:?}", parsed.clone_url, central_path))?;
-        new_revision = Some(rev);
         // No staging/swap needed — pull updated the central path directly.
-    } else if record.source_type == "git" {
-        let repo_url = record
-            .source_ref
-            .as_deref()
-            .ok_or_else(|| anyhow::anyhow!("missing source_ref for git skill"))?;
-        let parsed = parse_github_url(repo_url);
-
-        let (repo_dir, rev) =
-            clone_to_cache(app, store, &parsed.clone_url, parsed.branch.as_deref())?;
-        new_revision = Some(rev);
-
-        let copy_src = if let Some(subpath) = &parsed.subpath {
-            repo_dir.join(subpath)
-        } else {
-            repo_dir.clone()
-        };
-        if !copy_src.exists() {
-            anyhow::bail!("path not found in repo: {:?}", copy_src);
-        }
-
-        copy_dir_recursive(&copy_src, &staging_dir)
-            .with_context(|| format!("copy {:?} -> {:?}", copy_src, staging_dir))?;
-    } else if record.source_type == "local" {
-        let source = record
-            .source_ref
-            .as_deref()
-            .ok_or_else(|| anyhow::anyhow!("missing source_ref for local skill"))?;
-        let source_path = PathBuf::from(source);
-        if !source_path.exists() {
-            anyhow::bail!("source path not found: {:?}", source_path);
-        }
-        copy_dir_recursive(&source_path, &staging_dir)
-            .with_context(|| format!("copy {:?} -> {:?}", source_path, staging_dir))?;
-    } else if record.source_type == "clawhub" {
-        // ClawHub skills are downloaded as point-in-time snapshots.
-        // Re-download from ClawHub to update.
-        let slug = record
-            .source_ref
-            .as_deref()
-            .and_then(|r| r.strip_prefix("clawhub://"))
-            .ok_or_else(|| anyhow::anyhow!("missing clawhub slug in source_ref"))?;
-        let temp_dir = tempfile::tempdir().context("create temp dir for clawhub update")?;
-        let extracted =
-            super::clawhub_api::download_and_extract_clawhub_skill(slug, None, temp_dir.path())?;
-        copy_dir_recursive(&extracted, &staging_dir)
-            .with_context(|| format!("copy {:?} -> {:?}", extracted, staging_dir))?;
+        Some(rev)
     } else {
-        anyhow::bail!("unsupported source_type for update: {}", record.source_type);
-    }
+        stage_from_source(paths, store, &record, &staging_dir, false)?
+    };
 
     // Swap: remove old dir and rename staging into place (skip for git-cloned).
     if needs_staging {
         std::fs::remove_dir_all(&central_path)
             .with_context(|| format!("failed to remove old central dir {:?}", central_path))?;
-        if let Err(err) = std::fs::rename(&staging_dir, &central_path) {
-            // Fallback for cross-device rename: copy then delete staging.
-            copy_dir_recursive(&staging_dir, &central_path).with_context(|| {
-                format!("fallback copy {:?} -> {:?}", staging_dir, central_path)
-            })?;
-            let _ = std::fs::remove_dir_all(&staging_dir);
-            // Still surface original rename error in logs for troubleshooting.
-            eprintln!("[update] rename warning: {}", err);
-        }
+        move_staging_into_place(&staging_dir, &central_path)?;
     }
 
     let content_hash = compute_content_hash(&central_path);
@@ -632,13 +753,41 @@ pub fn update_managed_skill_from_source<R: tauri::Runtime>(
         updated_at: now,
         last_sync_at: record.last_sync_at,
         last_seen_at: now,
-        status: "ok".to_string(),
+        status: status_after_update(&record),
         group_name: record.group_name.clone(),
     };
     store.upsert_skill(&updated)?;
+    refresh_skill_metadata(store, skill_id, &central_path);
+    record_skill_manifest(store, skill_id, &central_path);
+
+    let updated_targets =
+        resync_copy_targets(store, skill_id, &central_path, content_hash.as_deref(), now)?;
+
+    record_change(store, &central_path, &format!("Update {}", record.name));
+    skill_events::skill_updated(&record.id, &record.name);
 
-    // If any targets are "copy", re-sync them so changes propagate. Symlinks update automatically.
+    Ok(UpdateResult {
+        skill_id: record.id,
+        name: record.name,
+        central_path,
+        content_hash,
+        source_revision: new_revision,
+        updated_targets,
+    })
+}
+
+/// Re-copy `skill_id`'s copy-mode targets from `central_path` after its
+/// content changed; linked targets follow on their own. Returns the tools
+/// that were refreshed.
+pub(crate) fn resync_copy_targets(
+    store: &SkillStore,
+    skill_id: &str,
+    central_path: &Path,
+    content_hash: Option<&str>,
+    now: i64,
+) -> Result<Vec<String>> {
     // Cursor 目前不支持软链/junction，因此无论历史 mode 如何，都需要强制 copy 回灌。
+    let kind = ResourceType::parse(&store.get_skill_resource_type(skill_id)?);
     let targets = store.list_skill_targets(skill_id)?;
     let mut updated_targets: Vec<String> = Vec::new();
     for t in targets {
@@ -648,10 +797,27 @@ pub fn update_managed_skill_from_source<R: tauri::Runtime>(
                 continue;
             }
         }
-        let force_copy = t.mode == "copy" || t.tool == "cursor";
+        // Agents and commands are never format-converted.
+        let force_copy = t.mode == "copy"
+            || t.tool == "cursor"
+            || (kind == ResourceType::Skill && tool_format(&t.tool) != SkillFormat::Skill);
         if force_copy {
             let target_path = PathBuf::from(&t.target_path);
-            let sync_res = sync_dir_copy_with_overwrite(&central_path, &target_path, true)?;
+            let sync_res = if kind == ResourceType::Skill {
+                let (sync_res, warnings) = super::cross_refs::sync_copy_with_cross_refs(
+                    store,
+                    &t.tool,
+                    central_path,
+                    &target_path,
+                    true,
+                )?;
+                for warning in warnings {
+                    log::warn!("{} ({}): {}", skill_id, t.tool, warning);
+                }
+                sync_res
+            } else {
+                sync_resource_file(&t.tool, central_path, &target_path, true, true)?
+            };
             let record = super::skill_store::SkillTargetRecord {
                 id: t.id.clone(),
                 skill_id: t.skill_id.clone(),
@@ -661,22 +827,297 @@ pub fn update_managed_skill_from_source<R: tauri::Runtime>(
                 status: "ok".to_string(),
                 last_error: None,
                 synced_at: Some(now),
+                content_hash: content_hash.map(str::to_string),
             };
             store.upsert_skill_target(&record)?;
+            skill_events::skill_synced(&record);
             updated_targets.push(t.tool.clone());
         }
     }
+    Ok(updated_targets)
+}
 
-    Ok(UpdateResult {
+#[derive(Clone, Debug, Serialize)]
+pub struct ReinstallResult {
+    pub skill_id: String,
+    pub name: String,
+    pub content_hash: String,
+    pub source_revision: Option<String>,
+    /// Whether the restored content hashes the same as before it broke.
+    /// Git sources follow their branch, so this can differ after upstream
+    /// commits.
+    pub hash_matches: bool,
+    pub resynced_targets: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+/// Rebuild a broken or missing central copy from the skill's recorded
+/// source, then re-sync every local target. Unlike an update, a ClawHub
+/// skill is restored at the version it was installed with.
+pub fn reinstall_skill_from_source(
+    paths: &AppPaths,
+    store: &SkillStore,
+    skill_id: &str,
+) -> Result<ReinstallResult> {
+    ensure_central_repo_writable(store)?;
+    let record = store
+        .get_skill_by_id(skill_id)?
+        .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+    let central_path = PathBuf::from(&record.central_path);
+    let central_parent = central_path
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("invalid central path"))?;
+    std::fs::create_dir_all(central_parent)
+        .with_context(|| format!("create dir {:?}", central_parent))?;
+
+    let staging_dir = central_parent.join(format!(".skills-hub-reinstall-{}", Uuid::new_v4()));
+    let staged = if record.source_type == "git-cloned" {
+        // The clone itself may be what is broken, so start from a fresh one.
+        let repo_url = record
+            .source_ref
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("missing source_ref for git-cloned skill"))?;
+        let parsed = parse_github_url(repo_url);
+        clone_or_pull(&parsed.clone_url, &staging_dir, parsed.branch.as_deref())
+            .with_context(|| format!("git clone {} into {:?}", parsed.clone_url, staging_dir))
+            .map(Some)
+    } else {
+        stage_from_source(paths, store, &record, &staging_dir, true)
+    };
+    let revision = match staged {
+        Ok(revision) => revision,
+        Err(err) => {
+            let _ = std::fs::remove_dir_all(&staging_dir);
+            return Err(err);
+        }
+    };
+
+    if std::fs::symlink_metadata(&central_path).is_ok() {
+        std::fs::remove_dir_all(&central_path)
+            .or_else(|_| std::fs::remove_file(&central_path))
+            .with_context(|| format!("failed to remove broken central dir {:?}", central_path))?;
+    }
+    move_staging_into_place(&staging_dir, &central_path)?;
+
+    let content_hash = hash_dir(&central_path)?;
+    let hash_matches = record
+        .content_hash
+        .as_deref()
+        .map_or(true, |old| old == content_hash);
+    let mut warnings = Vec::new();
+    if !hash_matches {
+        warnings.push("restored content differs from the last recorded hash".to_string());
+    }
+
+    let now = now_ms();
+    store.upsert_skill(&SkillRecord {
+        source_revision: revision.clone().or(record.source_revision.clone()),
+        content_hash: Some(content_hash.clone()),
+        updated_at: now,
+        last_seen_at: now,
+        status: status_after_update(&record),
+        ..record.clone()
+    })?;
+    refresh_skill_metadata(store, skill_id, &central_path);
+    record_skill_manifest(store, skill_id, &central_path);
+
+    let kind = ResourceType::parse(&store.get_skill_resource_type(skill_id)?);
+    let mut resynced_targets = Vec::new();
+    for t in store.list_skill_targets(skill_id)? {
+        let tool = t.tool.clone();
+        if let Some(reason) = local_target_skip_reason(store, &t.tool)? {
+            warnings.push(format!("{}: {}", t.tool, reason));
+            continue;
+        }
+        match resync_local_target(store, kind, &central_path, t, &content_hash, now) {
+            Ok(unresolved) => {
+                warnings.extend(unresolved.into_iter().map(|w| format!("{}: {}", tool, w)))
+            }
+            Err(err) => {
+                warnings.push(format!("{}: {:#}", tool, err));
+                continue;
+            }
+        }
+        resynced_targets.push(tool);
+    }
+
+    record_change(store, &central_path, &format!("Reinstall {}", record.name));
+    skill_events::skill_updated(&record.id, &record.name);
+
+    Ok(ReinstallResult {
         skill_id: record.id,
         name: record.name,
-        central_path,
         content_hash,
-        source_revision: new_revision,
-        updated_targets,
+        source_revision: revision,
+        hash_matches,
+        resynced_targets,
+        warnings,
     })
 }
 
+/// Sync a recorded local target again from `central_path` and mark its row
+/// as synced at `content_hash`. Returns cross-reference warnings.
+pub(crate) fn resync_local_target(
+    store: &SkillStore,
+    kind: ResourceType,
+    central_path: &Path,
+    t: super::skill_store::SkillTargetRecord,
+    content_hash: &str,
+    now: i64,
+) -> Result<Vec<String>> {
+    let target_path = PathBuf::from(&t.target_path);
+    let (outcome, unresolved) = if kind != ResourceType::Skill {
+        sync_resource_file(&t.tool, central_path, &target_path, true, t.mode == "copy")
+            .map(|outcome| (outcome, Vec::new()))?
+    } else if t.mode == "copy" {
+        super::cross_refs::sync_copy_with_cross_refs(
+            store,
+            &t.tool,
+            central_path,
+            &target_path,
+            true,
+        )?
+    } else {
+        super::cross_refs::sync_with_cross_refs(store, &t.tool, central_path, &target_path, true)?
+    };
+    let record = super::skill_store::SkillTargetRecord {
+        target_path: outcome.target_path.to_string_lossy().to_string(),
+        mode: mode_name(&outcome.mode_used).to_string(),
+        status: "ok".to_string(),
+        last_error: None,
+        synced_at: Some(now),
+        content_hash: Some(content_hash.to_string()),
+        ..t
+    };
+    store.upsert_skill_target(&record)?;
+    skill_events::skill_synced(&record);
+    Ok(unresolved)
+}
+
+/// Why a target row cannot be re-synced from this machine, if it cannot.
+pub(crate) fn local_target_skip_reason(
+    store: &SkillStore,
+    tool: &str,
+) -> Result<Option<&'static str>> {
+    if tool.starts_with("remote:") {
+        return Ok(Some("remote target, sync it again"));
+    }
+    if let Some(id) = super::target_variables::custom_target_id(tool) {
+        return Ok(match store.get_custom_target_by_id(id)? {
+            None => Some("custom target no longer exists"),
+            Some(ct) if ct.remote_host_id.is_some() => Some("remote target, sync it again"),
+            Some(_) => None,
+        });
+    }
+    Ok(match adapter_by_key(tool) {
+        Some(adapter) if !is_tool_installed(&adapter).unwrap_or(false) => {
+            Some("tool not installed")
+        }
+        _ => None,
+    })
+}
+
+fn move_staging_into_place(staging_dir: &Path, central_path: &Path) -> Result<()> {
+    if let Err(err) = std::fs::rename(staging_dir, central_path) {
+        // Fallback for cross-device rename: copy then delete staging.
+        copy_dir_recursive(staging_dir, central_path)
+            .with_context(|| format!("fallback copy {:?} -> {:?}", staging_dir, central_path))?;
+        let _ = std::fs::remove_dir_all(staging_dir);
+        // Still surface original rename error in logs for troubleshooting.
+        log::warn!("update: rename warning: {}", err);
+    }
+    Ok(())
+}
+
+/// Copy the skill's content from its recorded source into `staging_dir`.
+/// `pinned` re-fetches the recorded ClawHub version instead of the latest;
+/// git sources always follow their branch. Returns the fetched revision.
+fn stage_from_source(
+    paths: &AppPaths,
+    store: &SkillStore,
+    record: &SkillRecord,
+    staging_dir: &Path,
+    pinned: bool,
+) -> Result<Option<String>> {
+    let mut revision = None;
+    if record.source_type == "git" {
+        let repo_url = record
+            .source_ref
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("missing source_ref for git skill"))?;
+        let parsed = parse_github_url(repo_url);
+
+        let (repo_dir, rev) =
+            clone_to_cache(paths, store, &parsed.clone_url, parsed.branch.as_deref())?;
+        revision = Some(rev);
+
+        let copy_src = if let Some(subpath) = &parsed.subpath {
+            repo_dir.join(subpath)
+        } else {
+            repo_dir.clone()
+        };
+        if !copy_src.exists() {
+            anyhow::bail!("path not found in repo: {:?}", copy_src);
+        }
+
+        copy_dir_recursive(&copy_src, staging_dir)
+            .with_context(|| format!("copy {:?} -> {:?}", copy_src, staging_dir))?;
+    } else if record.source_type == "local" {
+        let source = record
+            .source_ref
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("missing source_ref for local skill"))?;
+        let source_path = PathBuf::from(source);
+        if !source_path.exists() {
+            anyhow::bail!("source path not found: {:?}", source_path);
+        }
+        if source_path.is_file() {
+            // A single-file agent or command.
+            copy_file_into(&source_path, staging_dir)?;
+        } else {
+            copy_dir_recursive(&source_path, staging_dir)
+                .with_context(|| format!("copy {:?} -> {:?}", source_path, staging_dir))?;
+        }
+    } else if record.source_type == "clawhub" {
+        // ClawHub skills are downloaded as point-in-time snapshots.
+        // Re-download the latest version, or the recorded one when pinned.
+        let slug = record
+            .source_ref
+            .as_deref()
+            .and_then(|r| r.strip_prefix("clawhub://"))
+            .ok_or_else(|| anyhow::anyhow!("missing clawhub slug in source_ref"))?;
+        let version = match (pinned, &record.source_revision) {
+            (true, Some(version)) => Some(version.clone()),
+            _ => super::clawhub_api::latest_clawhub_version(slug)?,
+        };
+        let temp_dir = super::temp_cleanup::operation_temp_dir("clawhub-update")?;
+        let extracted = super::clawhub_api::download_and_extract_clawhub_skill(
+            slug,
+            version.as_deref(),
+            temp_dir.path(),
+        )?;
+        revision = version;
+        copy_dir_recursive(&extracted, staging_dir)
+            .with_context(|| format!("copy {:?} -> {:?}", extracted, staging_dir))?;
+    } else if record.source_type == "skillpkg" {
+        let pkg = record
+            .source_ref
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("missing source_ref for skillpkg skill"))?;
+        let temp_dir = super::temp_cleanup::operation_temp_dir("skillpkg")?;
+        let (_, root, _) = super::skillpkg::extract_skillpkg(
+            Path::new(pkg),
+            temp_dir.path(),
+            &super::skillpkg::get_trusted_keys(store),
+        )?;
+        copy_dir_recursive(&root, staging_dir)
+            .with_context(|| format!("copy {:?} -> {:?}", root, staging_dir))?;
+    } else {
+        anyhow::bail!("unsupported source_type for update: {}", record.source_type);
+    }
+    Ok(revision)
+}
+
 #[derive(Clone, Debug, serde::Serialize)]
 pub struct GitSkillCandidate {
     pub name: String,
@@ -693,13 +1134,14 @@ pub struct LocalSkillCandidate {
     pub reason: Option<String>,
 }
 
-pub fn list_git_skills<R: tauri::Runtime>(
-    app: &tauri::AppHandle<R>,
+pub fn list_git_skills(
+    paths: &AppPaths,
     store: &SkillStore,
     repo_url: &str,
 ) -> Result<Vec<GitSkillCandidate>> {
     let parsed = parse_github_url(repo_url);
-    let (repo_dir, _rev) = clone_to_cache(app, store, &parsed.clone_url, parsed.branch.as_deref())?;
+    let (repo_dir, _rev) =
+        clone_to_cache(paths, store, &parsed.clone_url, parsed.branch.as_deref())?;
 
     let mut out: Vec<GitSkillCandidate> = Vec::new();
 
@@ -893,8 +1335,8 @@ pub fn list_local_skills(base_path: &Path) -> Result<Vec<LocalSkillCandidate>> {
     Ok(out)
 }
 
-pub fn install_git_skill_from_selection<R: tauri::Runtime>(
-    app: &tauri::AppHandle<R>,
+pub fn install_git_skill_from_selection(
+    paths: &AppPaths,
     store: &SkillStore,
     repo_url: &str,
     subpath: &str,
@@ -913,13 +1355,12 @@ pub fn install_git_skill_from_selection<R: tauri::Runtime>(
         }
     });
 
-    let central_dir = resolve_central_repo_path(app, store)?;
-    ensure_central_repo(&central_dir)?;
+    let central_dir = writable_central_repo(paths, store)?;
     let central_path = central_dir.join(&display_name);
     cleanup_orphan_central_path(&central_path, store)?;
 
     let (repo_dir, revision) =
-        clone_to_cache(app, store, &parsed.clone_url, parsed.branch.as_deref())?;
+        clone_to_cache(paths, store, &parsed.clone_url, parsed.branch.as_deref())?;
 
     let copy_src = if subpath == "." {
         repo_dir.clone()
@@ -951,12 +1392,19 @@ pub fn install_git_skill_from_selection<R: tauri::Runtime>(
         updated_at: now,
         last_sync_at: None,
         last_seen_at: now,
-        status: "ok".to_string(),
+        status: install_status(store),
         group_name: derive_group_name_from_clone_url(&parsed.clone_url),
     };
     store.upsert_skill(&record)?;
+    refresh_skill_metadata(store, &record.id, &central_path);
+    record_skill_manifest(store, &record.id, &central_path);
+
+    record_change(store, &central_path, &format!("Install {}", record.name));
+    skill_events::skill_installed(&record.id, &record.name);
 
     Ok(InstallResult {
+        content_warnings: scan_installed(&record.name, &central_path),
+        signature_status: None,
         skill_id: record.id,
         name: record.name,
         central_path,
@@ -964,8 +1412,8 @@ pub fn install_git_skill_from_selection<R: tauri::Runtime>(
     })
 }
 
-pub fn install_local_skill_from_selection<R: tauri::Runtime>(
-    app: &tauri::AppHandle<R>,
+pub fn install_local_skill_from_selection(
+    paths: &AppPaths,
     store: &SkillStore,
     base_path: &Path,
     subpath: &str,
@@ -986,92 +1434,92 @@ pub fn install_local_skill_from_selection<R: tauri::Runtime>(
 
     let skill_md = selected_dir.join("SKILL.md");
     if !skill_md.exists() {
-        anyhow::bail!("SKILL_INVALID|missing_skill_md");
+        return Err(skill_invalid("missing_skill_md"));
     }
-    let (parsed_name, _desc) = parse_skill_md_with_reason(&skill_md)
-        .map_err(|reason| anyhow::anyhow!("SKILL_INVALID|{}", reason))?;
+    let (parsed_name, _desc) = parse_skill_md_with_reason(&skill_md).map_err(skill_invalid)?;
 
     let display_name = name.unwrap_or(parsed_name);
 
-    install_local_skill(app, store, &selected_dir, Some(display_name))
-}
-
-#[derive(Clone, Debug, Serialize, Deserialize)]
-struct RepoCacheMeta {
-    last_fetched_ms: i64,
-    head: Option<String>,
+    install_local_skill(paths, store, &selected_dir, Some(display_name))
 }
 
-static GIT_CACHE_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
-
-fn clone_to_cache<R: tauri::Runtime>(
-    app: &tauri::AppHandle<R>,
+fn clone_to_cache(
+    paths: &AppPaths,
     store: &SkillStore,
     clone_url: &str,
     branch: Option<&str>,
 ) -> Result<(PathBuf, String)> {
     let started = std::time::Instant::now();
-    let cache_dir = app
-        .path()
-        .app_cache_dir()
-        .context("failed to resolve app cache dir")?;
-    let cache_root = cache_dir.join("skills-hub-git-cache");
+    let cache_root = git_cache_root(paths);
     std::fs::create_dir_all(&cache_root)
         .with_context(|| format!("failed to create cache dir {:?}", cache_root))?;
 
     let repo_dir = cache_root.join(repo_cache_key(clone_url, branch));
-    let meta_path = repo_dir.join(".skills-hub-cache.json");
-
-    let lock = GIT_CACHE_LOCK.get_or_init(|| Mutex::new(()));
-    let _guard = lock.lock().unwrap_or_else(|err| err.into_inner());
-
-    if repo_dir.join(".git").exists() {
-        if let Ok(meta) = std::fs::read_to_string(&meta_path) {
-            if let Ok(meta) = serde_json::from_str::<RepoCacheMeta>(&meta) {
-                if let Some(head) = meta.head {
-                    let ttl_ms = get_git_cache_ttl_secs(store).saturating_mul(1000);
-                    if ttl_ms > 0 && now_ms().saturating_sub(meta.last_fetched_ms) < ttl_ms {
-                        log::info!(
-                            "[installer] git cache hit (fresh) {}s url={} branch={:?} repo_dir={:?}",
-                            started.elapsed().as_secs_f32(),
-                            clone_url,
-                            branch,
-                            repo_dir
-                        );
-                        return Ok((repo_dir, head));
-                    }
+
+    let rev = {
+        let _guard = lock_git_cache();
+
+        if repo_dir.join(".git").exists() {
+            if let Some(RepoCacheMeta {
+                head: Some(head),
+                last_fetched_ms,
+                ..
+            }) = read_cache_meta(&repo_dir)
+            {
+                let ttl_ms = get_git_cache_ttl_secs(store).saturating_mul(1000);
+                if ttl_ms > 0 && now_ms().saturating_sub(last_fetched_ms) < ttl_ms {
+                    log::info!(
+                        "[installer] git cache hit (fresh) {}s url={} branch={:?} repo_dir={:?}",
+                        started.elapsed().as_secs_f32(),
+                        clone_url,
+                        branch,
+                        repo_dir
+                    );
+                    touch_cache_entry(&repo_dir);
+                    return Ok((repo_dir, head));
                 }
             }
         }
-    }
-
-    log::info!(
-        "[installer] git cache miss/stale; fetching {} url={} branch={:?} repo_dir={:?}",
-        started.elapsed().as_secs_f32(),
-        clone_url,
-        branch,
-        repo_dir
-    );
 
-    let rev = match clone_or_pull(clone_url, &repo_dir, branch) {
-        Ok(rev) => rev,
-        Err(err) => {
-            // If cache got corrupted, retry once from a clean state.
-            if repo_dir.exists() {
-                let _ = std::fs::remove_dir_all(&repo_dir);
+        log::info!(
+            "[installer] git cache miss/stale; fetching {} url={} branch={:?} repo_dir={:?}",
+            started.elapsed().as_secs_f32(),
+            clone_url,
+            branch,
+            repo_dir
+        );
+
+        // An existing clone is fetched in place rather than cloned again.
+        let rev = match clone_or_pull(clone_url, &repo_dir, branch) {
+            Ok(rev) => rev,
+            Err(err) => {
+                // If cache got corrupted, retry once from a clean state.
+                if repo_dir.exists() {
+                    let _ = std::fs::remove_dir_all(&repo_dir);
+                }
+                clone_or_pull(clone_url, &repo_dir, branch).with_context(|| format!("{:#}", err))?
             }
-            clone_or_pull(clone_url, &repo_dir, branch).with_context(|| format!("{:#}", err))?
-        }
+        };
+
+        let now = now_ms();
+        write_cache_meta(
+            &repo_dir,
+            &RepoCacheMeta {
+                last_fetched_ms: now,
+                last_used_ms: now,
+                head: Some(rev.clone()),
+                repo_url: Some(clone_url.to_string()),
+                branch: branch.map(str::to_string),
+            },
+        );
+        rev
     };
 
-    let _ = std::fs::write(
-        &meta_path,
-        serde_json::to_string(&RepoCacheMeta {
-            last_fetched_ms: now_ms(),
-            head: Some(rev.clone()),
-        })
-        .unwrap_or_else(|_| "{}".to_string()),
-    );
+    match enforce_git_cache_cap(paths, store, &repo_dir) {
+        Ok(0) => {}
+        Ok(n) => log::info!("[installer] evicted {} cached repos over the size cap", n),
+        Err(err) => log::warn!("[installer] git cache eviction failed: {:#}", err),
+    }
 
     log::info!(
         "[installer] git cache ready {}s url={} branch={:?} head={}",
@@ -1122,7 +1570,7 @@ fn cleanup_orphan_central_path(central_path: &Path, store: &SkillStore) -> Resul
 }
 
 /// Accept frontmatter delimiters: 3+ consecutive `-` or `*` (e.g. `---`, `***`, `------...------`).
-fn is_frontmatter_delimiter(line: &str) -> bool {
+pub(crate) fn is_frontmatter_delimiter(line: &str) -> bool {
     let trimmed = line.trim();
     if trimmed.len() < 3 {
         return false;
@@ -1135,6 +1583,14 @@ fn parse_skill_md(path: &Path) -> Option<(String, Option<String>)> {
     parse_skill_md_with_reason(path).ok()
 }
 
+fn skill_invalid(reason: &str) -> anyhow::Error {
+    coded_with(
+        ErrorCode::SkillInvalid,
+        format!("not a valid skill: {}", reason),
+        serde_json::json!({ "reason": reason }),
+    )
+}
+
 fn parse_skill_md_with_reason(path: &Path) -> Result<(String, Option<String>), &'static str> {
     let text = std::fs::read_to_string(path).map_err(|_| "read_failed")?;
     let mut lines = text.lines();
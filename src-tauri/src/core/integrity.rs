@@ -0,0 +1,234 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::central_history::record_change;
+use super::central_repo::ensure_central_repo_writable;
+use super::content_hash::hash_dir;
+use super::installer::{local_target_skip_reason, resync_local_target};
+use super::now_ms;
+use super::resource_types::ResourceType;
+use super::skill_store::{SkillRecord, SkillStore};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    /// The record's central directory is gone. Not repaired: reinstall or
+    /// delete the skill.
+    MissingDir,
+    /// Files were edited outside the app. Repair records the new hash.
+    HashMismatch,
+    /// A directory in the central repo that no record points at. Repair
+    /// adopts it as a local skill when it has a SKILL.md.
+    Orphan,
+    /// A local target that is missing or a dangling link. Repair syncs it
+    /// again.
+    BrokenTarget,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct IntegrityIssue {
+    pub kind: IssueKind,
+    pub skill_id: Option<String>,
+    pub name: String,
+    pub path: String,
+    /// Tool of a broken target.
+    pub tool: Option<String>,
+    pub repaired: bool,
+    /// Why a repair failed or was not attempted.
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct IntegrityReport {
+    pub checked: usize,
+    pub issues: Vec<IntegrityIssue>,
+}
+
+impl IntegrityIssue {
+    fn new(kind: IssueKind, skill: Option<&SkillRecord>, name: &str, path: &Path) -> Self {
+        Self {
+            kind,
+            skill_id: skill.map(|s| s.id.clone()),
+            name: name.to_string(),
+            path: path.to_string_lossy().to_string(),
+            tool: None,
+            repaired: false,
+            error: None,
+        }
+    }
+
+    fn settle(&mut self, outcome: Result<()>) {
+        match outcome {
+            Ok(()) => self.repaired = true,
+            Err(err) => self.error = Some(format!("{:#}", err)),
+        }
+    }
+}
+
+/// Check every skill record against the filesystem, and with `repair`, fix
+/// what can be fixed without going back to the skill's source.
+pub fn verify_central_repo(
+    store: &SkillStore,
+    central_root: &Path,
+    repair: bool,
+) -> Result<IntegrityReport> {
+    if repair {
+        ensure_central_repo_writable(store)?;
+    }
+    let skills = store.list_skills()?;
+    let mut issues = Vec::new();
+
+    for skill in &skills {
+        let central_path = PathBuf::from(&skill.central_path);
+        if !central_path.exists() {
+            issues.push(IntegrityIssue::new(
+                IssueKind::MissingDir,
+                Some(skill),
+                &skill.name,
+                &central_path,
+            ));
+            continue;
+        }
+
+        let current_hash = hash_dir(&central_path).ok();
+        if skill.content_hash.is_some()
+            && current_hash.is_some()
+            && skill.content_hash != current_hash
+        {
+            let mut issue = IntegrityIssue::new(
+                IssueKind::HashMismatch,
+                Some(skill),
+                &skill.name,
+                &central_path,
+            );
+            if repair {
+                issue.settle(rehash(store, skill, current_hash.clone()));
+            }
+            issues.push(issue);
+        }
+
+        let kind = ResourceType::parse(&store.get_skill_resource_type(&skill.id)?);
+        for target in store.list_skill_targets(&skill.id)? {
+            if local_target_skip_reason(store, &target.tool)?.is_some() {
+                continue;
+            }
+            let target_path = PathBuf::from(&target.target_path);
+            if target_is_intact(&target_path) {
+                continue;
+            }
+            let mut issue = IntegrityIssue::new(
+                IssueKind::BrokenTarget,
+                Some(skill),
+                &skill.name,
+                &target_path,
+            );
+            issue.tool = Some(target.tool.clone());
+            if repair {
+                let hash = current_hash.clone().unwrap_or_default();
+                issue.settle(
+                    resync_local_target(store, kind, &central_path, target, &hash, now_ms())
+                        .map(|_| ()),
+                );
+            }
+            issues.push(issue);
+        }
+    }
+
+    let known: HashSet<PathBuf> = skills
+        .iter()
+        .map(|s| PathBuf::from(&s.central_path))
+        .collect();
+    for dir in orphan_dirs(central_root, &known) {
+        let name = dir
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let mut issue = IntegrityIssue::new(IssueKind::Orphan, None, &name, &dir);
+        if repair {
+            match adopt(store, &dir, &name) {
+                Ok(id) => {
+                    issue.skill_id = Some(id);
+                    issue.repaired = true;
+                }
+                Err(err) => issue.error = Some(format!("{:#}", err)),
+            }
+        }
+        issues.push(issue);
+    }
+
+    Ok(IntegrityReport {
+        checked: skills.len(),
+        issues,
+    })
+}
+
+/// A target exists and, if it is a link, still leads somewhere.
+fn target_is_intact(path: &Path) -> bool {
+    std::fs::symlink_metadata(path).is_ok() && std::fs::metadata(path).is_ok()
+}
+
+/// Visible top-level directories of the central repo with no record.
+fn orphan_dirs(central_root: &Path, known: &HashSet<PathBuf>) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(central_root) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.is_dir()
+                && !known.contains(p)
+                && !p
+                    .file_name()
+                    .is_some_and(|n| n.to_string_lossy().starts_with('.'))
+        })
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+fn rehash(store: &SkillStore, skill: &SkillRecord, content_hash: Option<String>) -> Result<()> {
+    store.upsert_skill(&SkillRecord {
+        content_hash,
+        updated_at: now_ms(),
+        ..skill.clone()
+    })?;
+    record_change(
+        store,
+        Path::new(&skill.central_path),
+        &format!("Record manual edits to {}", skill.name),
+    );
+    Ok(())
+}
+
+fn adopt(store: &SkillStore, dir: &Path, name: &str) -> Result<String> {
+    if !dir.join("SKILL.md").is_file() {
+        anyhow::bail!("no SKILL.md; install it again to choose its type");
+    }
+    let now = now_ms();
+    let record = SkillRecord {
+        id: Uuid::new_v4().to_string(),
+        name: name.to_string(),
+        source_type: "local".to_string(),
+        source_ref: None,
+        source_revision: None,
+        central_path: dir.to_string_lossy().to_string(),
+        content_hash: hash_dir(dir).ok(),
+        created_at: now,
+        updated_at: now,
+        last_sync_at: None,
+        last_seen_at: now,
+        status: "ok".to_string(),
+        group_name: None,
+    };
+    store.upsert_skill(&record)?;
+    record_change(store, dir, &format!("Adopt {}", name));
+    Ok(record.id)
+}
+
+#[cfg(test)]
+#[path = "tests/integrity.rs"]
+mod tests;
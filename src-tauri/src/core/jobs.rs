@@ -0,0 +1,203 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::operations::{Operation, OperationHandle, OperationKind, OperationRegistry};
+
+/// Jobs running at once; later ones wait in the `queued` state.
+pub const MAX_RUNNING_JOBS: usize = 2;
+
+/// Log lines kept per job; the oldest go first.
+const MAX_JOB_LOG_LINES: usize = 200;
+
+/// An operation started through the queue, with what it logged and how it
+/// ended. Lives in the backend, so a reloaded window picks it up again with
+/// `list_jobs` / `get_job`.
+#[derive(Clone, Debug, Serialize)]
+pub struct Job {
+    #[serde(flatten)]
+    pub operation: Operation,
+    pub logs: Vec<String>,
+    /// What the work returned, once `done`.
+    pub result: Option<Value>,
+    /// The serialized error (code, message, payload), once `failed`.
+    pub error: Option<Value>,
+}
+
+#[derive(Default)]
+struct JobDetails {
+    logs: Vec<String>,
+    result: Option<Value>,
+    error: Option<Value>,
+}
+
+type Details = Arc<Mutex<HashMap<String, JobDetails>>>;
+
+type Work = Box<dyn FnOnce(&JobContext) + Send>;
+
+struct QueuedJob {
+    ctx: JobContext,
+    work: Work,
+}
+
+/// Runs long operations in the background (managed Tauri state). Commands
+/// enqueue and return the job id at once; progress goes through the
+/// [`OperationRegistry`] so `operations://updated` fires as usual.
+#[derive(Clone)]
+pub struct JobQueue {
+    ops: OperationRegistry,
+    details: Details,
+    sender: Sender<QueuedJob>,
+}
+
+impl JobQueue {
+    pub fn new(ops: OperationRegistry) -> Self {
+        Self::with_workers(ops, MAX_RUNNING_JOBS)
+    }
+
+    pub fn with_workers(ops: OperationRegistry, workers: usize) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let receiver = Arc::new(Mutex::new(receiver));
+        for index in 0..workers.max(1) {
+            let receiver = receiver.clone();
+            let spawned = std::thread::Builder::new()
+                .name(format!("skills-hub-job-{}", index))
+                .spawn(move || worker_loop(&receiver));
+            if let Err(err) = spawned {
+                log::warn!("failed to start job worker: {}", err);
+            }
+        }
+        Self {
+            ops,
+            details: Arc::new(Mutex::new(HashMap::new())),
+            sender,
+        }
+    }
+
+    /// Queue `work` and return its job id. The value or error it returns is
+    /// kept as JSON for `get_job`.
+    pub fn enqueue<T, E, F>(&self, kind: OperationKind, label: impl Into<String>, work: F) -> String
+    where
+        T: Serialize,
+        E: Display + Serialize,
+        F: FnOnce(&JobContext) -> Result<T, E> + Send + 'static,
+    {
+        let op = self.ops.start(kind, label);
+        let id = op.id().to_string();
+        {
+            let live: HashSet<String> = self.ops.list().into_iter().map(|op| op.id).collect();
+            let mut details = lock(&self.details);
+            details.retain(|id, _| live.contains(id));
+            details.insert(id.clone(), JobDetails::default());
+        }
+
+        let ctx = JobContext {
+            op,
+            details: self.details.clone(),
+        };
+        let work: Work = Box::new(move |ctx| {
+            let result = work(ctx);
+            // Store the outcome first so whoever reacts to the final
+            // transition finds it.
+            ctx.update(|details| match &result {
+                Ok(value) => {
+                    details.result = Some(serde_json::to_value(value).unwrap_or(Value::Null))
+                }
+                Err(err) => details.error = serde_json::to_value(err).ok(),
+            });
+            ctx.op.finish(&result);
+        });
+        if let Err(mpsc::SendError(job)) = self.sender.send(QueuedJob { ctx, work }) {
+            let err = "job queue is not running";
+            job.ctx.op.finish(&Err::<(), _>(err));
+        }
+        id
+    }
+
+    /// `None` for unknown ids and for operations that did not go through the
+    /// queue.
+    pub fn get(&self, id: &str) -> Option<Job> {
+        let operation = self.ops.get(id)?;
+        let details = lock(&self.details);
+        details.get(id).map(|details| to_job(operation, details))
+    }
+
+    /// Newest first.
+    pub fn list(&self) -> Vec<Job> {
+        let details = lock(&self.details);
+        self.ops
+            .list()
+            .into_iter()
+            .filter_map(|op| {
+                let found = details.get(&op.id)?;
+                Some(to_job(op, found))
+            })
+            .collect()
+    }
+}
+
+fn to_job(operation: Operation, details: &JobDetails) -> Job {
+    Job {
+        operation,
+        logs: details.logs.clone(),
+        result: details.result.clone(),
+        error: details.error.clone(),
+    }
+}
+
+fn worker_loop(receiver: &Mutex<Receiver<QueuedJob>>) {
+    loop {
+        let next = receiver
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .recv();
+        let Ok(QueuedJob { ctx, work }) = next else {
+            return;
+        };
+        ctx.op.running();
+        if catch_unwind(AssertUnwindSafe(|| work(&ctx))).is_err() {
+            ctx.op.finish(&Err::<(), _>("job panicked"));
+        }
+    }
+}
+
+/// Handed to a job's work for reporting progress and log lines.
+pub struct JobContext {
+    op: OperationHandle,
+    details: Details,
+}
+
+impl JobContext {
+    /// Report `done` of `total` steps finished.
+    pub fn progress(&self, done: usize, total: usize) {
+        self.op.progress(done, total);
+    }
+
+    pub fn log(&self, line: impl Into<String>) {
+        let line = line.into();
+        self.update(|details| {
+            details.logs.push(line);
+            let excess = details.logs.len().saturating_sub(MAX_JOB_LOG_LINES);
+            details.logs.drain(..excess);
+        });
+    }
+
+    fn update(&self, apply: impl FnOnce(&mut JobDetails)) {
+        if let Some(details) = lock(&self.details).get_mut(self.op.id()) {
+            apply(details);
+        }
+    }
+}
+
+fn lock(details: &Details) -> std::sync::MutexGuard<'_, HashMap<String, JobDetails>> {
+    details.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+#[cfg(test)]
+#[path = "tests/jobs.rs"]
+mod tests;
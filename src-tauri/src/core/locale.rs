@@ -0,0 +1,238 @@
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use super::skill_store::SkillStore;
+
+pub const LOCALE_KEY: &str = "locale";
+
+/// Language of backend messages; the codes match the frontend's i18n.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Locale {
+    #[default]
+    #[serde(rename = "en")]
+    En,
+    #[serde(rename = "zh-CN")]
+    ZhCn,
+    #[serde(rename = "zh-TW")]
+    ZhTw,
+}
+
+impl Locale {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim() {
+            "en" => Some(Self::En),
+            // Older frontends stored plain `zh`.
+            "zh-CN" | "zh" => Some(Self::ZhCn),
+            "zh-TW" => Some(Self::ZhTw),
+            _ => None,
+        }
+    }
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::ZhCn => "zh-CN",
+            Self::ZhTw => "zh-TW",
+        }
+    }
+}
+
+static LOCALE: OnceLock<Mutex<Locale>> = OnceLock::new();
+
+fn locale_cell() -> &'static Mutex<Locale> {
+    LOCALE.get_or_init(|| Mutex::new(Locale::default()))
+}
+
+pub fn current_locale() -> Locale {
+    *locale_cell().lock().unwrap_or_else(|err| err.into_inner())
+}
+
+fn apply_locale(locale: Locale) {
+    *locale_cell().lock().unwrap_or_else(|err| err.into_inner()) = locale;
+}
+
+pub fn get_locale(store: &SkillStore) -> Locale {
+    store
+        .get_setting(LOCALE_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| Locale::parse(&raw))
+        .unwrap_or_default()
+}
+
+pub fn set_locale(store: &SkillStore, raw: &str) -> Result<Locale> {
+    let Some(locale) = Locale::parse(raw) else {
+        anyhow::bail!("unsupported locale {:?}; expected en, zh-CN or zh-TW", raw);
+    };
+    store.set_setting(LOCALE_KEY, locale.code())?;
+    apply_locale(locale);
+    Ok(locale)
+}
+
+/// Apply the saved locale at startup.
+pub fn load_locale(store: &SkillStore) {
+    apply_locale(get_locale(store));
+}
+
+/// Backend messages shown to users. Placeholders are `{name}` and filled by
+/// [`trf`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Msg {
+    Details,
+    CloneTempDirOmitted,
+    GithubTls,
+    GithubAuth,
+    GithubNotFound,
+    GithubDns,
+    GithubTimeout,
+    GithubConnection,
+    GithubUnreachable,
+    GitCliFailed,
+    GitTimeout,
+    MultiSkills,
+    RemoteSshFailed,
+    DeleteCleanupFailed,
+    MigrationPathNotAbsolute,
+    MigrationSourceMissing,
+    MigrationTargetExists,
+    MigrationIntoItself,
+    MigrationNoSpace,
+    MigrationNotWritable,
+    MigrationMoveFailed,
+    MigrationRecordsFailed,
+}
+
+/// `msg` in `locale`.
+pub fn text(msg: Msg, locale: Locale) -> &'static str {
+    let [en, zh_cn, zh_tw] = catalog(msg);
+    match locale {
+        Locale::En => en,
+        Locale::ZhCn => zh_cn,
+        Locale::ZhTw => zh_tw,
+    }
+}
+
+/// `msg` in the current locale.
+pub fn tr(msg: Msg) -> &'static str {
+    text(msg, current_locale())
+}
+
+/// `msg` in the current locale with each `{name}` replaced.
+pub fn trf(msg: Msg, args: &[(&str, &str)]) -> String {
+    args.iter().fold(tr(msg).to_string(), |out, (name, value)| {
+        out.replace(&format!("{{{}}}", name), value)
+    })
+}
+
+fn catalog(msg: Msg) -> [&'static str; 3] {
+    match msg {
+        Msg::Details => ["Details: ", "详细：", "詳細："],
+        Msg::CloneTempDirOmitted => [
+            "(temp dir omitted)",
+            "(已省略临时目录)",
+            "(已省略暫存目錄)",
+        ],
+        Msg::GithubTls => [
+            "Could not fetch the repository from GitHub: TLS/certificate verification failed (macOS SecureTransport).\n\nSuggestions:\n- Check whether a network/proxy is intercepting HTTPS\n- On a corporate network you may need to install the company root certificate or use a trusted proxy\n- Check whether `git clone https://github.com/<owner>/<repo>` works in a terminal",
+            "无法从 GitHub 拉取仓库：TLS/证书校验失败（macOS SecureTransport）。\n\n建议：\n- 检查网络/代理是否拦截 HTTPS\n- 如在公司网络，可能需要安装公司根证书或使用可信代理\n- 也可在终端确认 `git clone https://github.com/<owner>/<repo>` 是否可用",
+            "無法從 GitHub 拉取儲存庫：TLS/憑證驗證失敗（macOS SecureTransport）。\n\n建議：\n- 檢查網路/代理是否攔截 HTTPS\n- 如在公司網路，可能需要安裝公司根憑證或使用可信代理\n- 也可在終端機確認 `git clone https://github.com/<owner>/<repo>` 是否可用",
+        ],
+        Msg::GithubAuth => [
+            "Cannot access this repository: it may be private, or you lack permission or need to authenticate.",
+            "无法访问该仓库：可能是私有仓库/权限不足/需要鉴权。",
+            "無法存取該儲存庫：可能是私有儲存庫/權限不足/需要驗證。",
+        ],
+        Msg::GithubNotFound => [
+            "The repository does not exist or you have no access (GitHub returned not found).",
+            "仓库不存在或无权限访问（GitHub 返回 not found）。",
+            "儲存庫不存在或無權限存取（GitHub 回傳 not found）。",
+        ],
+        Msg::GithubDns => [
+            "Could not resolve the GitHub host name (DNS). Check your network/proxy.",
+            "无法解析 GitHub 域名（DNS）。请检查网络/代理。",
+            "無法解析 GitHub 網域（DNS）。請檢查網路/代理。",
+        ],
+        Msg::GithubTimeout => [
+            "Connecting to GitHub timed out. Check your network/proxy.",
+            "连接 GitHub 超时。请检查网络/代理。",
+            "連線 GitHub 逾時。請檢查網路/代理。",
+        ],
+        Msg::GithubConnection => [
+            "Could not connect to GitHub (connection refused/reset). Check your network/proxy.",
+            "连接 GitHub 失败（连接被拒绝/重置）。请检查网络/代理。",
+            "連線 GitHub 失敗（連線被拒絕/重設）。請檢查網路/代理。",
+        ],
+        Msg::GithubUnreachable => [
+            "Could not fetch the repository from GitHub. Check your network/proxy, or try again later.",
+            "无法从 GitHub 拉取仓库。请检查网络/代理，或稍后重试。",
+            "無法從 GitHub 拉取儲存庫。請檢查網路/代理，或稍後重試。",
+        ],
+        Msg::GitCliFailed => [
+            "The git command failed (stopped without falling back to the built-in git to avoid hanging). Check the system git/network/proxy, or set SKILLS_HUB_ALLOW_LIBGIT2_FALLBACK=1 to allow the fallback.",
+            "git 命令执行失败（为避免卡死，已停止并不再回退到内置 git）。请检查系统 git/网络/代理；或设置环境变量 SKILLS_HUB_ALLOW_LIBGIT2_FALLBACK=1 允许回退。",
+            "git 指令執行失敗（為避免卡住，已停止且不再回退到內建 git）。請檢查系統 git/網路/代理；或設定環境變數 SKILLS_HUB_ALLOW_LIBGIT2_FALLBACK=1 允許回退。",
+        ],
+        Msg::GitTimeout => [
+            "The git operation timed out ({secs}s). Check that your network/proxy can reach GitHub, or raise the timeout with SKILLS_HUB_GIT_TIMEOUT_SECS.",
+            "git 操作超时（{secs}s）。请检查网络/代理是否可访问 GitHub；也可设置环境变量 SKILLS_HUB_GIT_TIMEOUT_SECS 增大超时。",
+            "git 操作逾時（{secs}s）。請檢查網路/代理是否可存取 GitHub；也可設定環境變數 SKILLS_HUB_GIT_TIMEOUT_SECS 增加逾時時間。",
+        ],
+        Msg::MultiSkills => [
+            "This repository contains several skills. Copy the link of one skill folder (e.g. GitHub's /tree/<branch>/skills/<name>) and import that.",
+            "该仓库包含多个 Skills，请复制具体 Skill 文件夹链接（例如 GitHub 的 /tree/<branch>/skills/<name>），再导入。",
+            "該儲存庫包含多個 Skills，請複製具體 Skill 資料夾連結（例如 GitHub 的 /tree/<branch>/skills/<name>），再匯入。",
+        ],
+        Msg::RemoteSshFailed => ["SSH connection failed", "SSH 连接失败", "SSH 連線失敗"],
+        Msg::DeleteCleanupFailed => [
+            "The managed record was deleted, but some folders could not be cleaned up:",
+            "已删除托管记录，但清理部分目录失败：",
+            "已刪除託管紀錄，但清理部分目錄失敗：",
+        ],
+        Msg::MigrationPathNotAbsolute => [
+            "storage path must be absolute",
+            "存储路径必须是绝对路径",
+            "儲存路徑必須是絕對路徑",
+        ],
+        Msg::MigrationSourceMissing => [
+            "central path not found: {path}",
+            "找不到中心仓库路径：{path}",
+            "找不到中央儲存庫路徑：{path}",
+        ],
+        Msg::MigrationTargetExists => [
+            "target path already exists: {path}",
+            "目标路径已存在：{path}",
+            "目標路徑已存在：{path}",
+        ],
+        Msg::MigrationIntoItself => [
+            "{name} cannot be moved into itself",
+            "{name} 不能移动到自身内部",
+            "{name} 不能移動到自身內部",
+        ],
+        Msg::MigrationNoSpace => [
+            "not enough space: {needed} bytes needed, {available} available",
+            "空间不足：需要 {needed} 字节，可用 {available} 字节",
+            "空間不足：需要 {needed} 位元組，可用 {available} 位元組",
+        ],
+        Msg::MigrationNotWritable => [
+            "{path} is not writable: {error}",
+            "{path} 不可写：{error}",
+            "{path} 無法寫入：{error}",
+        ],
+        Msg::MigrationMoveFailed => [
+            "move {name}; nothing was changed",
+            "移动 {name} 失败；未做任何更改",
+            "移動 {name} 失敗；未做任何變更",
+        ],
+        Msg::MigrationRecordsFailed => [
+            "update skill records; nothing was changed",
+            "更新 Skill 记录失败；未做任何更改",
+            "更新 Skill 紀錄失敗；未做任何變更",
+        ],
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/locale.rs"]
+mod tests;
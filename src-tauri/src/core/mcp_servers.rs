@@ -0,0 +1,454 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::errors::{coded_with, ErrorCode};
+use super::skill_store::SkillStore;
+
+pub const MCP_SERVERS_KEY: &str = "mcp_servers";
+/// Tool key -> names of the servers this app last wrote into its config.
+pub const MCP_SYNCED_KEY: &str = "mcp_synced";
+
+/// An MCP server kept centrally: either a local command or a remote URL.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpServer {
+    pub name: String,
+    #[serde(default)]
+    pub command: Option<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum ConfigFormat {
+    /// A JSON file with a top-level `mcpServers` object. `url_key` is what
+    /// the tool calls a remote server's URL.
+    Json {
+        url_key: &'static str,
+        http_type: bool,
+    },
+    /// Codex's `config.toml` with `[mcp_servers.<name>]` tables.
+    CodexToml,
+}
+
+/// Tool key, config file relative to home, format.
+const MCP_CONFIGS: &[(&str, &str, ConfigFormat)] = &[
+    (
+        "claude_code",
+        ".claude.json",
+        ConfigFormat::Json {
+            url_key: "url",
+            http_type: true,
+        },
+    ),
+    (
+        "cursor",
+        ".cursor/mcp.json",
+        ConfigFormat::Json {
+            url_key: "url",
+            http_type: false,
+        },
+    ),
+    (
+        "gemini_cli",
+        ".gemini/settings.json",
+        ConfigFormat::Json {
+            url_key: "httpUrl",
+            http_type: false,
+        },
+    ),
+    (
+        "windsurf",
+        ".codeium/windsurf/mcp_config.json",
+        ConfigFormat::Json {
+            url_key: "serverUrl",
+            http_type: false,
+        },
+    ),
+    ("codex", ".codex/config.toml", ConfigFormat::CodexToml),
+];
+
+#[derive(Clone, Debug, Serialize)]
+pub struct McpSyncResult {
+    pub tool: String,
+    pub config_path: String,
+    pub written: Vec<String>,
+    pub removed: Vec<String>,
+    /// Entries the user added by hand under the same name; left untouched
+    /// unless the sync is told to overwrite.
+    pub conflicts: Vec<String>,
+}
+
+/// Keys of the tools whose MCP config this app can write.
+pub fn mcp_tools() -> Vec<String> {
+    MCP_CONFIGS
+        .iter()
+        .map(|(key, _, _)| key.to_string())
+        .collect()
+}
+
+pub fn list_mcp_servers(store: &SkillStore) -> Vec<McpServer> {
+    store
+        .get_setting(MCP_SERVERS_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn write_servers(store: &SkillStore, servers: &[McpServer]) -> Result<()> {
+    let raw = serde_json::to_string(servers).context("serialize mcp servers")?;
+    store.set_setting(MCP_SERVERS_KEY, &raw)
+}
+
+/// Add `server`, replacing one with the same name. Tool configs change on
+/// their next sync.
+pub fn upsert_mcp_server(store: &SkillStore, server: McpServer) -> Result<Vec<McpServer>> {
+    let server = McpServer {
+        name: server.name.trim().to_string(),
+        command: server.command.filter(|c| !c.trim().is_empty()),
+        url: server.url.filter(|u| !u.trim().is_empty()),
+        ..server
+    };
+    if server.name.is_empty() {
+        anyhow::bail!("mcp server name is empty");
+    }
+    if server.command.is_some() == server.url.is_some() {
+        anyhow::bail!("mcp server {} needs either a command or a url", server.name);
+    }
+    let mut servers = list_mcp_servers(store);
+    match servers.iter_mut().find(|s| s.name == server.name) {
+        Some(existing) => *existing = server,
+        None => servers.push(server),
+    }
+    write_servers(store, &servers)?;
+    Ok(servers)
+}
+
+pub fn remove_mcp_server(store: &SkillStore, name: &str) -> Result<Vec<McpServer>> {
+    let mut servers = list_mcp_servers(store);
+    servers.retain(|s| s.name != name);
+    write_servers(store, &servers)?;
+    Ok(servers)
+}
+
+fn read_synced(store: &SkillStore) -> HashMap<String, BTreeSet<String>> {
+    store
+        .get_setting(MCP_SYNCED_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+/// Write every central server into `tool_key`'s MCP config and drop the ones
+/// this app wrote earlier that are gone now. Anything else in the file is
+/// kept as is.
+pub fn sync_mcp_servers(
+    store: &SkillStore,
+    tool_key: &str,
+    overwrite: bool,
+) -> Result<McpSyncResult> {
+    let (_, relative, format) = MCP_CONFIGS
+        .iter()
+        .find(|(key, _, _)| *key == tool_key)
+        .ok_or_else(|| {
+            coded_with(
+                ErrorCode::McpUnsupported,
+                format!("{} has no MCP config Skills Hub can write", tool_key),
+                serde_json::json!({ "tool": tool_key }),
+            )
+        })?;
+    let home = dirs::home_dir().context("failed to resolve home directory")?;
+    let path = home.join(relative);
+
+    let mut synced = read_synced(store);
+    let previous = synced.remove(tool_key).unwrap_or_default();
+    let servers = list_mcp_servers(store);
+    let mut result = sync_config_file(&path, *format, &servers, &previous, overwrite)?;
+    result.tool = tool_key.to_string();
+
+    let now_synced: BTreeSet<String> = result.written.iter().cloned().collect();
+    synced.insert(tool_key.to_string(), now_synced);
+    let raw = serde_json::to_string(&synced).context("serialize mcp sync state")?;
+    store.set_setting(MCP_SYNCED_KEY, &raw)?;
+    Ok(result)
+}
+
+fn sync_config_file(
+    path: &Path,
+    format: ConfigFormat,
+    servers: &[McpServer],
+    previous: &BTreeSet<String>,
+    overwrite: bool,
+) -> Result<McpSyncResult> {
+    let existing = match std::fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err).with_context(|| format!("read {:?}", path)),
+    };
+    let (updated, mut result) = match format {
+        ConfigFormat::Json { url_key, http_type } => {
+            merge_json(&existing, servers, previous, overwrite, |server| {
+                render_json(server, url_key, http_type)
+            })
+            .with_context(|| format!("merge {:?}", path))?
+        }
+        ConfigFormat::CodexToml => merge_codex_toml(&existing, servers, previous, overwrite),
+    };
+    result.config_path = path.to_string_lossy().to_string();
+    if updated != existing {
+        write_atomically(path, &updated)?;
+    }
+    Ok(result)
+}
+
+fn empty_result() -> McpSyncResult {
+    McpSyncResult {
+        tool: String::new(),
+        config_path: String::new(),
+        written: Vec::new(),
+        removed: Vec::new(),
+        conflicts: Vec::new(),
+    }
+}
+
+fn render_json(server: &McpServer, url_key: &str, http_type: bool) -> Value {
+    let mut entry = Map::new();
+    if let Some(url) = &server.url {
+        if http_type {
+            entry.insert("type".to_string(), Value::from("http"));
+        }
+        entry.insert(url_key.to_string(), Value::from(url.as_str()));
+    } else if let Some(command) = &server.command {
+        entry.insert("command".to_string(), Value::from(command.as_str()));
+        entry.insert("args".to_string(), Value::from(server.args.clone()));
+        if !server.env.is_empty() {
+            let env = server
+                .env
+                .iter()
+                .map(|(k, v)| (k.clone(), Value::from(v.as_str())))
+                .collect();
+            entry.insert("env".to_string(), Value::Object(env));
+        }
+    }
+    Value::Object(entry)
+}
+
+/// Merge into the `mcpServers` object of a JSON config. A file that is not a
+/// JSON object is refused rather than replaced.
+fn merge_json(
+    existing: &str,
+    servers: &[McpServer],
+    previous: &BTreeSet<String>,
+    overwrite: bool,
+    render: impl Fn(&McpServer) -> Value,
+) -> Result<(String, McpSyncResult)> {
+    let mut root: Value = if existing.trim().is_empty() {
+        Value::Object(Map::new())
+    } else {
+        serde_json::from_str(existing).context("config is not valid JSON")?
+    };
+    let Some(root_obj) = root.as_object_mut() else {
+        anyhow::bail!("config is not a JSON object");
+    };
+    let entries = root_obj
+        .entry("mcpServers")
+        .or_insert_with(|| Value::Object(Map::new()));
+    let Some(entries) = entries.as_object_mut() else {
+        anyhow::bail!("mcpServers is not a JSON object");
+    };
+
+    let mut result = empty_result();
+    for server in servers {
+        let rendered = render(server);
+        match entries.get(&server.name) {
+            Some(current) if *current == rendered => {}
+            Some(_) if !previous.contains(&server.name) && !overwrite => {
+                result.conflicts.push(server.name.clone());
+                continue;
+            }
+            _ => {
+                entries.insert(server.name.clone(), rendered);
+            }
+        }
+        result.written.push(server.name.clone());
+    }
+    for name in previous {
+        if !servers.iter().any(|s| &s.name == name) && entries.shift_remove(name).is_some() {
+            result.removed.push(name.clone());
+        }
+    }
+
+    let unchanged = if existing.trim().is_empty() {
+        entries.is_empty()
+    } else {
+        serde_json::from_str::<Value>(existing).ok().as_ref() == Some(&root)
+    };
+    if unchanged {
+        // Keep the user's formatting when there is nothing to write.
+        return Ok((existing.to_string(), result));
+    }
+    let mut out = serde_json::to_string_pretty(&root).context("serialize config")?;
+    out.push('\n');
+    Ok((out, result))
+}
+
+fn toml_key(name: &str) -> String {
+    if !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        name.to_string()
+    } else {
+        toml_string(name)
+    }
+}
+
+/// JSON string escapes are valid in TOML basic strings.
+fn toml_string(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| format!("\"{}\"", value))
+}
+
+fn render_codex_toml(server: &McpServer) -> String {
+    let table = format!("mcp_servers.{}", toml_key(&server.name));
+    let mut out = format!("[{}]\n", table);
+    if let Some(url) = &server.url {
+        out.push_str(&format!("url = {}\n", toml_string(url)));
+    } else if let Some(command) = &server.command {
+        out.push_str(&format!("command = {}\n", toml_string(command)));
+        let args: Vec<String> = server.args.iter().map(|a| toml_string(a)).collect();
+        out.push_str(&format!("args = [{}]\n", args.join(", ")));
+        if !server.env.is_empty() {
+            out.push_str(&format!("\n[{}.env]\n", table));
+            for (key, value) in &server.env {
+                out.push_str(&format!("{} = {}\n", toml_key(key), toml_string(value)));
+            }
+        }
+    }
+    out
+}
+
+/// The server a `[mcp_servers.<name>...]` header belongs to.
+fn codex_server_of(header: &str) -> Option<String> {
+    let inner = header.trim().strip_prefix('[')?.strip_suffix(']')?.trim();
+    let rest = inner.strip_prefix("mcp_servers.")?;
+    if let Some(quoted) = rest.strip_prefix('"') {
+        let end = quoted.find('"')?;
+        return serde_json::from_str(&format!("\"{}\"", &quoted[..end])).ok();
+    }
+    Some(rest.split('.').next()?.trim().to_string())
+}
+
+/// Split a TOML file into the text before the first table and one chunk per
+/// table, each tagged with the MCP server it configures (if any). Array
+/// tables (`[[...]]`) are never a server's, so they end its block too.
+fn codex_blocks(text: &str) -> Vec<(Option<String>, String)> {
+    let mut blocks: Vec<(Option<String>, String)> = vec![(None, String::new())];
+    for line in text.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("[[") {
+            blocks.push((None, String::new()));
+        } else if trimmed.starts_with('[') {
+            blocks.push((codex_server_of(trimmed), String::new()));
+        }
+        if let Some((_, body)) = blocks.last_mut() {
+            body.push_str(line);
+        }
+    }
+    blocks
+}
+
+/// Text-level merge, so comments and everything outside the managed
+/// `[mcp_servers.*]` tables survive untouched.
+fn merge_codex_toml(
+    existing: &str,
+    servers: &[McpServer],
+    previous: &BTreeSet<String>,
+    overwrite: bool,
+) -> (String, McpSyncResult) {
+    let blocks = codex_blocks(existing);
+    let current = |name: &str| -> String {
+        blocks
+            .iter()
+            .filter(|(owner, _)| owner.as_deref() == Some(name))
+            .map(|(_, body)| body.trim())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    };
+
+    let mut result = empty_result();
+    let mut replace: BTreeSet<String> = BTreeSet::new();
+    let mut appended = String::new();
+    for server in servers {
+        let rendered = render_codex_toml(server);
+        let existing_text = current(&server.name);
+        if existing_text == rendered.trim() {
+            result.written.push(server.name.clone());
+            continue;
+        }
+        if !existing_text.is_empty() && !previous.contains(&server.name) && !overwrite {
+            result.conflicts.push(server.name.clone());
+            continue;
+        }
+        replace.insert(server.name.clone());
+        appended.push_str(&rendered);
+        appended.push('\n');
+        result.written.push(server.name.clone());
+    }
+    for name in previous {
+        if !servers.iter().any(|s| &s.name == name) && !current(name).is_empty() {
+            replace.insert(name.clone());
+            result.removed.push(name.clone());
+        }
+    }
+    if replace.is_empty() {
+        return (existing.to_string(), result);
+    }
+
+    let mut out: String = blocks
+        .into_iter()
+        .filter(|(owner, _)| !owner.as_ref().is_some_and(|name| replace.contains(name)))
+        .map(|(_, body)| body)
+        .collect();
+    if !appended.is_empty() {
+        let trimmed_len = out.trim_end().len();
+        out.truncate(trimmed_len);
+        if !out.is_empty() {
+            out.push_str("\n\n");
+        }
+        out.push_str(appended.trim_end());
+        out.push('\n');
+    }
+    (out, result)
+}
+
+/// Write through a sibling temp file so a crash never leaves a half-written
+/// config behind.
+fn write_atomically(path: &Path, contents: &str) -> Result<()> {
+    let parent = path
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("invalid config path {:?}", path))?;
+    std::fs::create_dir_all(parent).with_context(|| format!("create dir {:?}", parent))?;
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let tmp: PathBuf = parent.join(format!(".{}.skills-hub-tmp", file_name));
+    std::fs::write(&tmp, contents).with_context(|| format!("write {:?}", tmp))?;
+    std::fs::rename(&tmp, path).with_context(|| format!("rename {:?} -> {:?}", tmp, path))?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "tests/mcp_servers.rs"]
+mod tests;
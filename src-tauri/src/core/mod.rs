@@ -1,14 +1,87 @@
+pub mod access_tokens;
+pub mod app_logs;
+pub mod app_paths;
+pub mod archive;
+pub mod backup;
 pub mod cache_cleanup;
+pub mod central_history;
+pub mod central_migration;
+pub mod central_remote;
 pub mod central_repo;
 pub mod clawhub_api;
+pub mod cloud_folders;
 pub mod content_hash;
+pub mod content_scan;
+pub mod cross_refs;
+pub mod errors;
+pub mod existing_skills;
 pub mod git_fetcher;
 pub mod github_search;
+pub mod github_stars;
+pub mod http_client;
 pub mod installer;
+pub mod integrity;
+pub mod jobs;
+pub mod locale;
+pub mod mcp_servers;
+pub mod multi_host_sync;
+pub mod offline_cache;
 pub mod onboarding;
+pub mod operations;
+pub mod orphan_targets;
+pub mod profiles;
+pub mod project_scan;
+pub mod project_targets;
+pub mod quarantine;
+pub mod registry_auth;
+pub mod remote_propagation;
+pub mod remote_schedule;
 pub mod remote_sync;
+pub mod resource_types;
+pub mod skill_authoring;
+pub mod skill_dependencies;
+pub mod skill_drift;
+pub mod skill_events;
+pub mod skill_files;
+pub mod skill_format;
+pub mod skill_freshness;
+pub mod skill_health;
+pub mod skill_ignore;
+pub mod skill_lock;
+pub mod skill_metadata;
 pub mod skill_store;
+pub mod skill_templates;
+pub mod skill_validation;
+pub mod skill_watcher;
+pub mod skillpkg;
+pub mod source_providers;
+pub mod source_trust;
+pub mod ssh_retry;
+pub mod startup;
+pub mod storage_report;
+pub mod symlink_migration;
 pub mod sync_engine;
+pub mod sync_modes;
+pub mod sync_plan;
+pub mod sync_topology;
+pub mod target_backup;
+pub mod target_conflict;
+pub mod target_naming;
+pub mod target_staleness;
+pub mod target_variables;
+pub mod target_verify;
 pub mod temp_cleanup;
 pub mod tool_adapters;
+pub mod tool_status;
+pub mod tool_versions;
+pub mod undo;
 pub mod update_checker;
+pub mod watch_settings;
+
+/// Milliseconds since the Unix epoch, the unit every stored timestamp uses.
+pub(crate) fn now_ms() -> i64 {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::SystemTime::UNIX_EPOCH)
+        .unwrap_or_default();
+    now.as_millis() as i64
+}
@@ -0,0 +1,154 @@
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use super::now_ms;
+use super::quarantine::is_quarantined;
+use super::remote_propagation::{record_remote_deployment, remote_skill_info};
+use super::remote_sync::{host_session, sync_all_skills_to_remote};
+use super::skill_events;
+use super::skill_store::{RemoteHostRecord, SkillRecord, SkillStore};
+
+/// Hosts synced at the same time; each one holds its own SSH session.
+pub const MAX_PARALLEL_HOSTS: usize = 4;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SkillSyncCell {
+    pub skill_id: String,
+    pub name: String,
+    /// `ok`, `skipped` (local copy missing) or `error`.
+    pub status: String,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HostSyncResult {
+    pub host_id: String,
+    pub label: String,
+    /// Set when the host could not be reached at all; `skills` is empty then.
+    pub error: Option<String>,
+    pub skills: Vec<SkillSyncCell>,
+}
+
+/// Push `skill_ids` to every host in `host_ids`, at most `max_parallel`
+/// hosts at a time. Results keep the order of `host_ids`.
+pub fn sync_skills_to_hosts(
+    store: &SkillStore,
+    host_ids: &[String],
+    skill_ids: &[String],
+    tool_keys: &[String],
+    max_parallel: usize,
+) -> Result<Vec<HostSyncResult>> {
+    let wanted: HashSet<&str> = skill_ids.iter().map(String::as_str).collect();
+    let skills: Vec<SkillRecord> = store
+        .list_skills()?
+        .into_iter()
+        .filter(|s| wanted.contains(s.id.as_str()) && !is_quarantined(s))
+        .collect();
+
+    let mut hosts = Vec::with_capacity(host_ids.len());
+    for host_id in host_ids {
+        let host = store
+            .get_remote_host_by_id(host_id)?
+            .ok_or_else(|| anyhow::anyhow!("remote host not found: {}", host_id))?;
+        hosts.push(host);
+    }
+
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<HostSyncResult>>> = Mutex::new(vec![None; hosts.len()]);
+    let workers = max_parallel.clamp(1, hosts.len().max(1));
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                let Some(host) = hosts.get(index) else {
+                    break;
+                };
+                let result = sync_host(store, host, &skills, tool_keys);
+                if let Ok(mut results) = results.lock() {
+                    results[index] = Some(result);
+                }
+            });
+        }
+    });
+
+    Ok(results
+        .into_inner()
+        .unwrap_or_default()
+        .into_iter()
+        .flatten()
+        .collect())
+}
+
+fn sync_host(
+    store: &SkillStore,
+    host: &RemoteHostRecord,
+    skills: &[SkillRecord],
+    tool_keys: &[String],
+) -> HostSyncResult {
+    let mut result = HostSyncResult {
+        host_id: host.id.clone(),
+        label: host.label.clone(),
+        error: None,
+        skills: Vec::new(),
+    };
+    store
+        .update_remote_host_sync_status(&host.id, "syncing", None)
+        .ok();
+
+    let sess = match host_session(host) {
+        Ok(sess) => sess,
+        Err(err) => {
+            store
+                .update_remote_host_sync_status(&host.id, "error", None)
+                .ok();
+            result.error = Some(format!("{:#}", err));
+            skill_events::remote_sync_finished(&host.id, &[], result.error.as_deref());
+            return result;
+        }
+    };
+
+    // One skill per call so each cell carries its own outcome.
+    for skill in skills {
+        let info = remote_skill_info(store, skill);
+        let cell = match sync_all_skills_to_remote(&sess, &[info], tool_keys) {
+            Ok(synced) if synced.is_empty() => cell(skill, "skipped", None),
+            Ok(_) => match record_remote_deployment(store, &host.id, skill, tool_keys, true) {
+                Ok(()) => cell(skill, "ok", None),
+                Err(err) => cell(skill, "error", Some(format!("{:#}", err))),
+            },
+            Err(err) => cell(skill, "error", Some(format!("{:#}", err))),
+        };
+        result.skills.push(cell);
+    }
+
+    let failed = result.skills.iter().any(|c| c.status == "error");
+    let status = if failed { "error" } else { "ok" };
+    store
+        .update_remote_host_sync_status(&host.id, status, Some(now_ms()))
+        .ok();
+    let synced: Vec<String> = result
+        .skills
+        .iter()
+        .filter(|c| c.status == "ok")
+        .map(|c| c.name.clone())
+        .collect();
+    skill_events::remote_sync_finished(&host.id, &synced, None);
+    result
+}
+
+fn cell(skill: &SkillRecord, status: &str, error: Option<String>) -> SkillSyncCell {
+    SkillSyncCell {
+        skill_id: skill.id.clone(),
+        name: skill.name.clone(),
+        status: status.to_string(),
+        error,
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/multi_host_sync.rs"]
+mod tests;
@@ -0,0 +1,138 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::now_ms;
+
+/// How long a search or detail response is served without asking the API.
+pub const RESPONSE_TTL: Duration = Duration::from_secs(10 * 60);
+/// Entries older than this are dropped at startup; until then they are the
+/// fallback when the API cannot be reached.
+pub const MAX_STALE_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry<T> {
+    fetched_at: i64,
+    value: T,
+}
+
+fn cache_root() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("skills-hub").join("offline-cache"))
+}
+
+/// Serve `namespace`/`key` from disk while younger than `ttl`, otherwise
+/// call `fetch` and store the answer. When `fetch` fails (offline, rate
+/// limited) an expired entry is returned instead of the error.
+pub fn cached<T: Serialize + DeserializeOwned>(
+    namespace: &str,
+    key: &str,
+    ttl: Duration,
+    fetch: impl FnOnce() -> Result<T>,
+) -> Result<T> {
+    match cache_root() {
+        Some(root) => cached_in(&root, namespace, key, ttl, now_ms(), fetch),
+        None => fetch(),
+    }
+}
+
+fn cached_in<T: Serialize + DeserializeOwned>(
+    root: &Path,
+    namespace: &str,
+    key: &str,
+    ttl: Duration,
+    now: i64,
+    fetch: impl FnOnce() -> Result<T>,
+) -> Result<T> {
+    let path = entry_path(root, namespace, key);
+    let stored: Option<CacheEntry<T>> = std::fs::read(&path)
+        .ok()
+        .and_then(|raw| serde_json::from_slice(&raw).ok());
+    let stored = match stored {
+        Some(entry) if now - entry.fetched_at < ttl.as_millis() as i64 => return Ok(entry.value),
+        other => other,
+    };
+
+    match fetch() {
+        Ok(value) => {
+            let entry = CacheEntry {
+                fetched_at: now,
+                value,
+            };
+            // A cache that cannot be written only costs the next lookup.
+            if let Err(err) = write_entry(&path, &entry) {
+                log::warn!("failed to write offline cache {:?}: {:#}", path, err);
+            }
+            Ok(entry.value)
+        }
+        Err(err) => match stored {
+            Some(entry) => {
+                log::warn!(
+                    "{} lookup failed, serving cached result: {:#}",
+                    namespace,
+                    err
+                );
+                Ok(entry.value)
+            }
+            None => Err(err),
+        },
+    }
+}
+
+/// Remove entries older than `max_age`; returns how many were removed.
+pub fn prune_offline_cache(max_age: Duration) -> Result<usize> {
+    match cache_root() {
+        Some(root) => prune_in(&root, max_age, now_ms()),
+        None => Ok(0),
+    }
+}
+
+fn prune_in(root: &Path, max_age: Duration, now: i64) -> Result<usize> {
+    let namespaces = match std::fs::read_dir(root) {
+        Ok(rd) => rd,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(err) => return Err(err).with_context(|| format!("read cache dir {:?}", root)),
+    };
+    let mut removed = 0;
+    for namespace in namespaces.flatten() {
+        let Ok(entries) = std::fs::read_dir(namespace.path()) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let fetched_at = std::fs::read(&path)
+                .ok()
+                .and_then(|raw| serde_json::from_slice::<CacheEntry<serde_json::Value>>(&raw).ok())
+                .map(|e| e.fetched_at);
+            // Unreadable entries are as good as expired.
+            let expired = fetched_at.map_or(true, |at| now - at >= max_age.as_millis() as i64);
+            if expired && std::fs::remove_file(&path).is_ok() {
+                removed += 1;
+            }
+        }
+    }
+    Ok(removed)
+}
+
+fn entry_path(root: &Path, namespace: &str, key: &str) -> PathBuf {
+    let digest = hex::encode(Sha256::digest(key.as_bytes()));
+    root.join(namespace).join(format!("{}.json", &digest[..32]))
+}
+
+fn write_entry<T: Serialize>(path: &Path, entry: &CacheEntry<T>) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).with_context(|| format!("create dir {:?}", parent))?;
+    }
+    // Write then rename so a concurrent reader never sees half a file.
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_vec(entry)?).with_context(|| format!("write {:?}", tmp))?;
+    std::fs::rename(&tmp, path).with_context(|| format!("rename {:?}", tmp))?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "tests/offline_cache.rs"]
+mod tests;
@@ -4,6 +4,7 @@ use std::path::{Path, PathBuf};
 use anyhow::Result;
 use serde::Serialize;
 
+use super::app_paths::AppPaths;
 use super::central_repo::resolve_central_repo_path;
 use super::content_hash::hash_dir;
 use super::skill_store::SkillStore;
@@ -33,13 +34,10 @@ pub struct OnboardingPlan {
     pub groups: Vec<OnboardingGroup>,
 }
 
-pub fn build_onboarding_plan<R: tauri::Runtime>(
-    app: &tauri::AppHandle<R>,
-    store: &SkillStore,
-) -> Result<OnboardingPlan> {
+pub fn build_onboarding_plan(paths: &AppPaths, store: &SkillStore) -> Result<OnboardingPlan> {
     let home =
         dirs::home_dir().ok_or_else(|| anyhow::anyhow!("failed to resolve home directory"))?;
-    let central = resolve_central_repo_path(app, store)?;
+    let central = resolve_central_repo_path(paths, store)?;
     let managed_targets = store
         .list_all_skill_target_paths()
         .unwrap_or_default()
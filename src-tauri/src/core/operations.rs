@@ -0,0 +1,224 @@
+use std::fmt::Display;
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use uuid::Uuid;
+
+use super::now_ms;
+
+pub const EVENT_OPERATION_UPDATED: &str = "operations://updated";
+
+/// Finished operations kept around for `list_operations`; active ones are
+/// never dropped.
+const MAX_FINISHED_OPS: usize = 50;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OperationKind {
+    Install,
+    Sync,
+    Update,
+    RemotePush,
+    Publish,
+    Migrate,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum OperationStatus {
+    Queued,
+    /// `progress` is a percentage once the operation knows how far along it is.
+    Running {
+        progress: Option<u8>,
+    },
+    Done,
+    Failed {
+        error: String,
+    },
+}
+
+impl OperationStatus {
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Done | Self::Failed { .. })
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Operation {
+    pub id: String,
+    pub kind: OperationKind,
+    /// What is being worked on, e.g. a repo URL or `skill -> tool`.
+    pub label: String,
+    pub status: OperationStatus,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+type Notify = Arc<dyn Fn(&Operation) + Send + Sync>;
+
+/// Every long operation (installs, syncs, updates, remote pushes, publishes)
+/// registers
+/// here so the frontend can list them and follow transitions through
+/// `operations://updated` (managed Tauri state).
+#[derive(Clone)]
+pub struct OperationRegistry {
+    ops: Arc<Mutex<Vec<Operation>>>,
+    notify: Option<Notify>,
+}
+
+impl OperationRegistry {
+    /// Registry that only records; nothing is emitted.
+    pub fn new() -> Self {
+        Self {
+            ops: Arc::new(Mutex::new(Vec::new())),
+            notify: None,
+        }
+    }
+
+    pub fn with_notify(notify: impl Fn(&Operation) + Send + Sync + 'static) -> Self {
+        Self {
+            notify: Some(Arc::new(notify)),
+            ..Self::new()
+        }
+    }
+
+    pub fn for_app(app: AppHandle) -> Self {
+        Self::with_notify(move |op| {
+            if let Err(err) = app.emit(EVENT_OPERATION_UPDATED, op) {
+                log::warn!("failed to emit {}: {}", EVENT_OPERATION_UPDATED, err);
+            }
+        })
+    }
+
+    /// Register a new operation in the `queued` state.
+    pub fn start(&self, kind: OperationKind, label: impl Into<String>) -> OperationHandle {
+        let now = now_ms();
+        let op = Operation {
+            id: Uuid::new_v4().to_string(),
+            kind,
+            label: label.into(),
+            status: OperationStatus::Queued,
+            created_at: now,
+            updated_at: now,
+        };
+        let id = op.id.clone();
+        {
+            let mut ops = self.lock();
+            ops.push(op.clone());
+            prune(&mut ops);
+        }
+        self.emit(&op);
+        OperationHandle {
+            registry: self.clone(),
+            id,
+        }
+    }
+
+    pub fn get(&self, id: &str) -> Option<Operation> {
+        self.lock().iter().find(|op| op.id == id).cloned()
+    }
+
+    /// Newest first.
+    pub fn list(&self) -> Vec<Operation> {
+        let mut ops = self.lock().clone();
+        ops.reverse();
+        ops
+    }
+
+    /// Move an operation to `status`. Finished operations stay finished.
+    fn transition(&self, id: &str, status: OperationStatus) {
+        let updated = {
+            let mut ops = self.lock();
+            let Some(op) = ops.iter_mut().find(|op| op.id == id) else {
+                return;
+            };
+            if op.status.is_finished() || op.status == status {
+                return;
+            }
+            op.status = status;
+            op.updated_at = now_ms();
+            op.clone()
+        };
+        self.emit(&updated);
+    }
+
+    fn emit(&self, op: &Operation) {
+        if let Some(notify) = &self.notify {
+            notify(op);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Operation>> {
+        self.ops.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl Default for OperationRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn prune(ops: &mut Vec<Operation>) {
+    let finished = ops.iter().filter(|op| op.status.is_finished()).count();
+    let mut excess = finished.saturating_sub(MAX_FINISHED_OPS);
+    ops.retain(|op| {
+        if excess > 0 && op.status.is_finished() {
+            excess -= 1;
+            return false;
+        }
+        true
+    });
+}
+
+/// Reports the progress of one registered operation.
+#[derive(Clone)]
+pub struct OperationHandle {
+    registry: OperationRegistry,
+    id: String,
+}
+
+impl OperationHandle {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn running(&self) {
+        self.registry
+            .transition(&self.id, OperationStatus::Running { progress: None });
+    }
+
+    /// Report `done` of `total` steps finished.
+    pub fn progress(&self, done: usize, total: usize) {
+        let percent = (done.min(total) * 100).checked_div(total).unwrap_or(100) as u8;
+        self.registry.transition(
+            &self.id,
+            OperationStatus::Running {
+                progress: Some(percent),
+            },
+        );
+    }
+
+    pub fn finish<T, E: Display>(&self, result: &Result<T, E>) {
+        let status = match result {
+            Ok(_) => OperationStatus::Done,
+            Err(err) => OperationStatus::Failed {
+                error: format!("{:#}", err),
+            },
+        };
+        self.registry.transition(&self.id, status);
+    }
+
+    /// Mark the operation running, run `work`, then record how it ended.
+    pub fn run<T, E: Display>(self, work: impl FnOnce(&Self) -> Result<T, E>) -> Result<T, E> {
+        self.running();
+        let result = work(&self);
+        self.finish(&result);
+        result
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/operations.rs"]
+mod tests;
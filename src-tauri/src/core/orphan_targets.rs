@@ -0,0 +1,161 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use super::skill_store::SkillStore;
+use super::target_variables::custom_target_id;
+use super::tool_adapters::{default_tool_adapters, is_tool_installed, resolve_default_path};
+
+/// A link in a tool's skills directory whose destination is gone, e.g. a
+/// deleted skill or a central repo that moved.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct DanglingLink {
+    pub tool: String,
+    pub path: String,
+    pub link_target: String,
+}
+
+/// A target record whose path no longer exists on disk.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct MissingTarget {
+    pub skill_id: String,
+    pub skill_name: String,
+    pub tool: String,
+    pub target_path: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct OrphanReport {
+    pub dangling_links: Vec<DanglingLink>,
+    pub missing_targets: Vec<MissingTarget>,
+}
+
+pub fn find_orphan_targets(store: &SkillStore) -> Result<OrphanReport> {
+    let mut dirs = Vec::new();
+    for adapter in default_tool_adapters() {
+        if is_tool_installed(&adapter)? {
+            dirs.push((
+                adapter.id.as_key().to_string(),
+                resolve_default_path(&adapter)?,
+            ));
+        }
+    }
+    Ok(OrphanReport {
+        dangling_links: find_dangling_links(&dirs)?,
+        missing_targets: find_missing_targets(store)?,
+    })
+}
+
+/// Dangling links directly inside each `(tool, skills dir)`. Tools sharing
+/// a directory report each link once.
+fn find_dangling_links(dirs: &[(String, PathBuf)]) -> Result<Vec<DanglingLink>> {
+    let mut seen = HashSet::new();
+    let mut links = Vec::new();
+    for (tool, dir) in dirs {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !is_dangling_link(&path) || !seen.insert(path.clone()) {
+                continue;
+            }
+            let link_target = std::fs::read_link(&path).unwrap_or_default();
+            links.push(DanglingLink {
+                tool: tool.clone(),
+                path: path.to_string_lossy().to_string(),
+                link_target: link_target.to_string_lossy().to_string(),
+            });
+        }
+    }
+    links.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(links)
+}
+
+/// Local target records whose path is gone. Remote targets cannot be
+/// checked from here and are left out.
+fn find_missing_targets(store: &SkillStore) -> Result<Vec<MissingTarget>> {
+    let mut missing = Vec::new();
+    for skill in store.list_skills()? {
+        for target in store.list_skill_targets(&skill.id)? {
+            if is_remote_target(store, &target.tool)? {
+                continue;
+            }
+            if std::fs::symlink_metadata(&target.target_path).is_ok() {
+                continue;
+            }
+            missing.push(MissingTarget {
+                skill_id: skill.id.clone(),
+                skill_name: skill.name.clone(),
+                tool: target.tool,
+                target_path: target.target_path,
+            });
+        }
+    }
+    Ok(missing)
+}
+
+/// Remove the dangling links found by [`find_orphan_targets`], or only
+/// those in `paths`. Each link is checked again first, so a path that was
+/// repaired meanwhile is left alone. Returns how many were removed.
+pub fn remove_dangling_links(store: &SkillStore, paths: Option<&[String]>) -> Result<usize> {
+    let mut removed = 0;
+    for link in find_orphan_targets(store)?.dangling_links {
+        if paths.is_some_and(|paths| !paths.contains(&link.path)) {
+            continue;
+        }
+        let path = Path::new(&link.path);
+        if !is_dangling_link(path) {
+            continue;
+        }
+        // Junctions are removed as directories on Windows.
+        std::fs::remove_file(path)
+            .or_else(|_| std::fs::remove_dir(path))
+            .with_context(|| format!("remove link {:?}", path))?;
+        removed += 1;
+    }
+    Ok(removed)
+}
+
+/// Delete the records found by [`find_orphan_targets`] whose path is still
+/// missing, or only those for `target_paths`. Returns how many were deleted.
+pub fn forget_missing_targets(
+    store: &SkillStore,
+    target_paths: Option<&[String]>,
+) -> Result<usize> {
+    let mut forgotten = 0;
+    for target in find_missing_targets(store)? {
+        if target_paths.is_some_and(|paths| !paths.contains(&target.target_path)) {
+            continue;
+        }
+        store.delete_skill_target(&target.skill_id, &target.tool)?;
+        forgotten += 1;
+    }
+    Ok(forgotten)
+}
+
+fn is_dangling_link(path: &Path) -> bool {
+    let is_link = std::fs::symlink_metadata(path)
+        .map(|meta| meta.file_type().is_symlink())
+        .unwrap_or(false)
+        || std::fs::read_link(path).is_ok();
+    is_link && !path.exists()
+}
+
+fn is_remote_target(store: &SkillStore, tool: &str) -> Result<bool> {
+    if tool.starts_with("remote:") {
+        return Ok(true);
+    }
+    if let Some(id) = custom_target_id(tool) {
+        if let Some(ct) = store.get_custom_target_by_id(id)? {
+            return Ok(ct.remote_host_id.is_some());
+        }
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+#[path = "tests/orphan_targets.rs"]
+mod tests;
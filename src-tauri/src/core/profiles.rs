@@ -0,0 +1,209 @@
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// Which profiles exist and which one the next launch opens. Kept next to
+/// the databases because settings live inside each profile's database.
+const PROFILES_FILE: &str = "profiles.json";
+const PROFILES_DIR: &str = "profiles";
+pub const DEFAULT_PROFILE_ID: &str = "default";
+const DEFAULT_PROFILE_NAME: &str = "Default";
+
+/// A storage profile: its own database (skills, targets, settings) and its
+/// own central repo.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profile {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ProfilesFile {
+    #[serde(default)]
+    active: Option<String>,
+    #[serde(default)]
+    profiles: Vec<Profile>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ProfileList {
+    /// The profile this process runs with.
+    pub active: String,
+    /// The profile the next launch opens.
+    pub next: String,
+    pub profiles: Vec<Profile>,
+}
+
+static ACTIVE: OnceLock<String> = OnceLock::new();
+
+/// Profile the running app was started with.
+pub fn active_profile_id() -> &'static str {
+    ACTIVE
+        .get()
+        .map(String::as_str)
+        .unwrap_or(DEFAULT_PROFILE_ID)
+}
+
+/// Default central repo folder name for the active profile.
+pub fn central_dir_name(base: &str) -> String {
+    match active_profile_id() {
+        DEFAULT_PROFILE_ID => base.to_string(),
+        id => format!("{}-{}", base, id),
+    }
+}
+
+/// Database of profile `id`. The default profile keeps the original
+/// database so existing installs carry on unchanged.
+pub fn profile_db_path(default_db_path: &Path, id: &str) -> PathBuf {
+    if id == DEFAULT_PROFILE_ID {
+        return default_db_path.to_path_buf();
+    }
+    let app_dir = default_db_path.parent().unwrap_or(Path::new("."));
+    let file_name = default_db_path
+        .file_name()
+        .unwrap_or_else(|| std::ffi::OsStr::new("skills_hub.db"));
+    app_dir.join(PROFILES_DIR).join(id).join(file_name)
+}
+
+/// Pick the profile for this run and return its database path. Called once
+/// at startup; a profile that has since been removed falls back to default.
+pub fn activate_profile(default_db_path: &Path) -> Result<PathBuf> {
+    let app_dir = app_dir(default_db_path);
+    let file = read_profiles(app_dir);
+    let id = file
+        .active
+        .filter(|id| id == DEFAULT_PROFILE_ID || file.profiles.iter().any(|p| &p.id == id))
+        .unwrap_or_else(|| DEFAULT_PROFILE_ID.to_string());
+    let db_path = profile_db_path(default_db_path, &id);
+    if let Some(parent) = db_path.parent() {
+        std::fs::create_dir_all(parent).with_context(|| format!("create {:?}", parent))?;
+    }
+    let _ = ACTIVE.set(id);
+    Ok(db_path)
+}
+
+pub fn list_profiles(default_db_path: &Path) -> ProfileList {
+    let file = read_profiles(app_dir(default_db_path));
+    let mut profiles = vec![Profile {
+        id: DEFAULT_PROFILE_ID.to_string(),
+        name: DEFAULT_PROFILE_NAME.to_string(),
+    }];
+    profiles.extend(file.profiles);
+    ProfileList {
+        active: active_profile_id().to_string(),
+        next: file
+            .active
+            .unwrap_or_else(|| DEFAULT_PROFILE_ID.to_string()),
+        profiles,
+    }
+}
+
+pub fn create_profile(default_db_path: &Path, name: &str) -> Result<Profile> {
+    let name = name.trim();
+    if name.is_empty() {
+        anyhow::bail!("profile name is required");
+    }
+    let app_dir = app_dir(default_db_path);
+    let mut file = read_profiles(app_dir);
+    if name.eq_ignore_ascii_case(DEFAULT_PROFILE_NAME)
+        || file
+            .profiles
+            .iter()
+            .any(|p| p.name.eq_ignore_ascii_case(name))
+    {
+        anyhow::bail!("a profile named {} already exists", name);
+    }
+    let base = slug(name);
+    let taken = |id: &str| id == DEFAULT_PROFILE_ID || file.profiles.iter().any(|p| p.id == id);
+    let mut id = base.clone();
+    let mut n = 2;
+    while taken(&id) {
+        id = format!("{}-{}", base, n);
+        n += 1;
+    }
+    let profile = Profile {
+        id,
+        name: name.to_string(),
+    };
+    file.profiles.push(profile.clone());
+    write_profiles(app_dir, &file)?;
+    Ok(profile)
+}
+
+/// Open profile `id` on the next launch.
+pub fn set_next_profile(default_db_path: &Path, id: &str) -> Result<()> {
+    let app_dir = app_dir(default_db_path);
+    let mut file = read_profiles(app_dir);
+    if id != DEFAULT_PROFILE_ID && !file.profiles.iter().any(|p| p.id == id) {
+        anyhow::bail!("profile not found: {}", id);
+    }
+    file.active = Some(id.to_string());
+    write_profiles(app_dir, &file)
+}
+
+/// Remove a profile and its database. Its central repo is left on disk.
+pub fn delete_profile(default_db_path: &Path, id: &str) -> Result<()> {
+    if id == DEFAULT_PROFILE_ID {
+        anyhow::bail!("the default profile cannot be deleted");
+    }
+    if id == active_profile_id() {
+        anyhow::bail!("switch to another profile before deleting this one");
+    }
+    let app_dir = app_dir(default_db_path);
+    let mut file = read_profiles(app_dir);
+    let before = file.profiles.len();
+    file.profiles.retain(|p| p.id != id);
+    if file.profiles.len() == before {
+        anyhow::bail!("profile not found: {}", id);
+    }
+    if file.active.as_deref() == Some(id) {
+        file.active = None;
+    }
+    write_profiles(app_dir, &file)?;
+    let dir = app_dir.join(PROFILES_DIR).join(id);
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir).with_context(|| format!("remove {:?}", dir))?;
+    }
+    Ok(())
+}
+
+fn app_dir(default_db_path: &Path) -> &Path {
+    default_db_path.parent().unwrap_or(Path::new("."))
+}
+
+fn read_profiles(app_dir: &Path) -> ProfilesFile {
+    std::fs::read_to_string(app_dir.join(PROFILES_FILE))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn write_profiles(app_dir: &Path, file: &ProfilesFile) -> Result<()> {
+    let path = app_dir.join(PROFILES_FILE);
+    let raw = serde_json::to_string_pretty(file)?;
+    std::fs::write(&path, raw).with_context(|| format!("write {:?}", path))
+}
+
+/// Lowercase ASCII letters, digits and dashes; used in file names.
+fn slug(name: &str) -> String {
+    let mut out = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            out.push(c.to_ascii_lowercase());
+        } else if !out.ends_with('-') && !out.is_empty() {
+            out.push('-');
+        }
+    }
+    let out = out.trim_end_matches('-').to_string();
+    if out.is_empty() {
+        "profile".to_string()
+    } else {
+        out
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/profiles.rs"]
+mod tests;
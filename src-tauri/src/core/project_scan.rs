@@ -0,0 +1,171 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use super::skill_store::SkillStore;
+use super::tool_adapters::default_tool_adapters;
+
+pub const WORKSPACE_ROOTS_KEY: &str = "workspace_roots";
+pub const DETECTED_PROJECTS_KEY: &str = "detected_projects";
+pub const EVENT_PROJECTS_UPDATED: &str = "projects://updated";
+
+/// How deep below a workspace root projects are looked for.
+const MAX_SCAN_DEPTH: usize = 4;
+const RESCAN_INTERVAL: Duration = Duration::from_secs(30 * 60);
+/// Directories never worth descending into.
+const SKIP_DIRS: &[&str] = &["node_modules", "target", "vendor", "dist", "build"];
+
+/// A directory holding per-project agent config (e.g. `.claude/`).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DetectedProject {
+    pub path: String,
+    /// Marker directories found in it, e.g. `.claude`, `.cursor`.
+    pub markers: Vec<String>,
+}
+
+/// Config directory names that mark a project, taken from the tool adapters'
+/// skills dirs (`.claude/skills` -> `.claude`). Shared `.config` is skipped.
+pub fn project_markers() -> Vec<String> {
+    let markers: BTreeSet<String> = default_tool_adapters()
+        .iter()
+        .filter_map(|a| a.relative_skills_dir.split('/').next())
+        .filter(|first| first.starts_with('.') && *first != ".config")
+        .map(str::to_string)
+        .collect();
+    markers.into_iter().collect()
+}
+
+pub fn get_workspace_roots(store: &SkillStore) -> Vec<String> {
+    read_json(store, WORKSPACE_ROOTS_KEY)
+}
+
+pub fn set_workspace_roots(store: &SkillStore, roots: &[String]) -> Result<Vec<String>> {
+    let mut cleaned: Vec<String> = Vec::new();
+    for root in roots {
+        let root = root.trim().trim_end_matches(['/', '\\']);
+        if root.is_empty() {
+            continue;
+        }
+        if !Path::new(root).is_absolute() {
+            anyhow::bail!("workspace root must be absolute: {}", root);
+        }
+        if !cleaned.iter().any(|r| r == root) {
+            cleaned.push(root.to_string());
+        }
+    }
+    store.set_setting(WORKSPACE_ROOTS_KEY, &serde_json::to_string(&cleaned)?)?;
+    Ok(cleaned)
+}
+
+pub fn list_detected_projects(store: &SkillStore) -> Vec<DetectedProject> {
+    read_json(store, DETECTED_PROJECTS_KEY)
+}
+
+/// Walk `roots` (the configured workspace roots when empty) and store the
+/// projects found as the current candidate list.
+pub fn scan_and_store(store: &SkillStore, roots: &[String]) -> Result<Vec<DetectedProject>> {
+    let roots = if roots.is_empty() {
+        get_workspace_roots(store)
+    } else {
+        roots.to_vec()
+    };
+    let projects = scan_projects(&roots, &project_markers());
+    let raw = serde_json::to_string(&projects).context("serialize detected projects")?;
+    store.set_setting(DETECTED_PROJECTS_KEY, &raw)?;
+    Ok(projects)
+}
+
+/// Find directories under `roots` that contain one of `markers`. A detected
+/// project is not searched further, and the home directory (whose global
+/// `.claude` etc. are not project config) is never reported.
+pub fn scan_projects(roots: &[String], markers: &[String]) -> Vec<DetectedProject> {
+    let home = dirs::home_dir();
+    let mut found: Vec<DetectedProject> = Vec::new();
+    for root in roots {
+        let mut walker = walkdir::WalkDir::new(root)
+            .max_depth(MAX_SCAN_DEPTH)
+            .follow_links(false)
+            .into_iter();
+        while let Some(entry) = walker.next() {
+            let Ok(entry) = entry else {
+                continue;
+            };
+            if !entry.file_type().is_dir() {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy();
+            if entry.depth() > 0 && (name.starts_with('.') || SKIP_DIRS.contains(&name.as_ref())) {
+                walker.skip_current_dir();
+                continue;
+            }
+            let path = entry.path();
+            if home.as_deref() == Some(path) {
+                continue;
+            }
+            let present = markers_in(path, markers);
+            if present.is_empty() {
+                continue;
+            }
+            let path = path.to_string_lossy().to_string();
+            if !found.iter().any(|p| p.path == path) {
+                found.push(DetectedProject {
+                    path,
+                    markers: present,
+                });
+            }
+            walker.skip_current_dir();
+        }
+    }
+    found.sort_by(|a, b| a.path.cmp(&b.path));
+    found
+}
+
+fn markers_in(dir: &Path, markers: &[String]) -> Vec<String> {
+    markers
+        .iter()
+        .filter(|m| dir.join(m.as_str()).is_dir())
+        .cloned()
+        .collect()
+}
+
+/// Rescan the configured workspace roots now and then, emitting
+/// `projects://updated` when the candidate list changes.
+pub fn spawn_project_scanner(app: AppHandle, store: SkillStore) {
+    let spawned = std::thread::Builder::new()
+        .name("project-scanner".to_string())
+        .spawn(move || loop {
+            if !get_workspace_roots(&store).is_empty() {
+                let before = list_detected_projects(&store);
+                match scan_and_store(&store, &[]) {
+                    Ok(after) if after != before => {
+                        if let Err(err) = app.emit(EVENT_PROJECTS_UPDATED, &after) {
+                            log::warn!("failed to emit {}: {}", EVENT_PROJECTS_UPDATED, err);
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(err) => log::warn!("project scan failed: {:#}", err),
+                }
+            }
+            std::thread::sleep(RESCAN_INTERVAL);
+        });
+    if let Err(err) = spawned {
+        log::warn!("failed to start project scanner: {}", err);
+    }
+}
+
+fn read_json<T: serde::de::DeserializeOwned + Default>(store: &SkillStore, key: &str) -> T {
+    store
+        .get_setting(key)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+#[path = "tests/project_scan.rs"]
+mod tests;
@@ -0,0 +1,208 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::cross_refs::sync_with_cross_refs;
+use super::errors::{find_coded, ErrorCode};
+use super::now_ms;
+use super::quarantine::ensure_approved;
+use super::skill_store::{ProjectTargetRecord, SkillStore};
+use super::sync_engine::SyncMode;
+use super::sync_plan::mode_name;
+use super::target_conflict::target_conflict;
+use super::target_naming::target_name_for;
+use super::tool_adapters::{adapter_by_key, ToolAdapter};
+
+/// A skill synced into one project, with how it compares to the central copy.
+#[derive(Clone, Debug, Serialize)]
+pub struct ProjectTargetStatus {
+    pub id: String,
+    pub skill_id: String,
+    pub project_path: String,
+    pub tool: String,
+    pub target_path: String,
+    pub mode: String,
+    pub synced_at: i64,
+    /// `ok`, `stale` (a copy older than the central skill), `missing` (the
+    /// synced dir is gone) or `project_missing` (the project itself is gone).
+    pub status: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ProjectSyncResult {
+    pub target: ProjectTargetStatus,
+    /// Cross-references that could not be resolved.
+    pub warnings: Vec<String>,
+}
+
+/// `<project>/<tool skills dir>`, e.g. `<project>/.claude/skills`. Tools whose
+/// skills live under the shared `~/.config` have no per-project directory.
+pub fn project_skills_dir(adapter: &ToolAdapter, project: &Path) -> Result<PathBuf> {
+    let relative = adapter.relative_skills_dir;
+    if relative.starts_with(".config/") {
+        anyhow::bail!(
+            "{} has no project-level skills directory",
+            adapter.display_name
+        );
+    }
+    Ok(project.join(relative))
+}
+
+/// Sync a managed skill into `project_path` for `tool_key` and remember it.
+pub fn sync_skill_to_project(
+    store: &SkillStore,
+    skill_id: &str,
+    project_path: &str,
+    tool_key: &str,
+    overwrite: bool,
+) -> Result<ProjectSyncResult> {
+    let skill = store
+        .get_skill_by_id(skill_id)?
+        .ok_or_else(|| anyhow::anyhow!("skill not found: {}", skill_id))?;
+    ensure_approved(&skill)?;
+    let adapter =
+        adapter_by_key(tool_key).ok_or_else(|| anyhow::anyhow!("unknown tool: {}", tool_key))?;
+    let project = normalize_project_path(project_path)?;
+    let skills_dir = project_skills_dir(&adapter, &project)?;
+    std::fs::create_dir_all(&skills_dir).with_context(|| format!("create dir {:?}", skills_dir))?;
+
+    let target = skills_dir.join(target_name_for(store, &skill));
+    let (outcome, warnings) = sync_with_cross_refs(
+        store,
+        tool_key,
+        Path::new(&skill.central_path),
+        &target,
+        overwrite,
+    )
+    .map_err(|err| {
+        if find_coded(&err).is_some_and(|coded| coded.code == ErrorCode::TargetExists) {
+            target_conflict(&target, Path::new(&skill.central_path))
+        } else {
+            err
+        }
+    })?;
+
+    let record = ProjectTargetRecord {
+        id: Uuid::new_v4().to_string(),
+        skill_id: skill.id.clone(),
+        project_path: project.to_string_lossy().to_string(),
+        tool: tool_key.to_string(),
+        target_path: outcome.target_path.to_string_lossy().to_string(),
+        mode: mode_name(&outcome.mode_used).to_string(),
+        content_hash: skill.content_hash.clone(),
+        synced_at: now_ms(),
+    };
+    store.upsert_project_target(&record)?;
+    // The upsert keeps the existing id on a re-sync, so read it back.
+    let saved = store
+        .list_project_targets_for_skill(&skill.id)?
+        .into_iter()
+        .find(|t| t.project_path == record.project_path && t.tool == record.tool)
+        .unwrap_or(record);
+    Ok(ProjectSyncResult {
+        target: to_status(&saved, skill.content_hash.as_deref()),
+        warnings,
+    })
+}
+
+/// Remove a project target's synced dir and forget it.
+pub fn unsync_project_target(store: &SkillStore, target_id: &str) -> Result<()> {
+    let target = store
+        .list_project_targets()?
+        .into_iter()
+        .find(|t| t.id == target_id)
+        .ok_or_else(|| anyhow::anyhow!("project target not found: {}", target_id))?;
+    remove_target_path(Path::new(&target.target_path))?;
+    store.delete_project_target(&target.id)
+}
+
+/// Every project target (only `skill_id`'s when given) with its drift status.
+pub fn list_project_targets(
+    store: &SkillStore,
+    skill_id: Option<&str>,
+) -> Result<Vec<ProjectTargetStatus>> {
+    let targets = match skill_id {
+        Some(id) => store.list_project_targets_for_skill(id)?,
+        None => store.list_project_targets()?,
+    };
+    let skills = store.list_skills()?;
+    Ok(targets
+        .iter()
+        .map(|t| {
+            let hash = skills
+                .iter()
+                .find(|s| s.id == t.skill_id)
+                .and_then(|s| s.content_hash.as_deref());
+            to_status(t, hash)
+        })
+        .collect())
+}
+
+/// Forget targets whose project directory no longer exists. Returns how many
+/// were dropped; there is nothing left on disk to remove.
+pub fn cleanup_missing_projects(store: &SkillStore) -> Result<usize> {
+    let mut removed = 0;
+    for target in store.list_project_targets()? {
+        if !Path::new(&target.project_path).is_dir() {
+            store.delete_project_target(&target.id)?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+fn to_status(target: &ProjectTargetRecord, central_hash: Option<&str>) -> ProjectTargetStatus {
+    let status = if !Path::new(&target.project_path).is_dir() {
+        "project_missing"
+    } else if std::fs::symlink_metadata(&target.target_path).is_err() {
+        "missing"
+    } else if target.mode == "copy"
+        && central_hash.is_some()
+        && target.content_hash.as_deref() != central_hash
+    {
+        "stale"
+    } else {
+        "ok"
+    };
+    ProjectTargetStatus {
+        id: target.id.clone(),
+        skill_id: target.skill_id.clone(),
+        project_path: target.project_path.clone(),
+        tool: target.tool.clone(),
+        target_path: target.target_path.clone(),
+        mode: target.mode.clone(),
+        synced_at: target.synced_at,
+        status: status.to_string(),
+    }
+}
+
+fn normalize_project_path(raw: &str) -> Result<PathBuf> {
+    let trimmed = raw.trim().trim_end_matches(['/', '\\']);
+    let path = Path::new(trimmed);
+    if trimmed.is_empty() || !path.is_absolute() {
+        anyhow::bail!("project path must be absolute: {}", raw);
+    }
+    if !path.is_dir() {
+        anyhow::bail!("project directory does not exist: {}", raw);
+    }
+    Ok(path.to_path_buf())
+}
+
+fn remove_target_path(path: &Path) -> Result<()> {
+    let Ok(meta) = std::fs::symlink_metadata(path) else {
+        return Ok(());
+    };
+    // A symlink (even to a directory) is removed as a link.
+    if meta.is_dir() {
+        std::fs::remove_dir_all(path)
+    } else {
+        std::fs::remove_file(path)
+    }
+    .with_context(|| format!("remove {:?}", path))
+}
+
+#[cfg(test)]
+#[path = "tests/project_targets.rs"]
+mod tests;
@@ -0,0 +1,72 @@
+use anyhow::Result;
+
+use super::errors::{coded_with, ErrorCode};
+use super::skill_store::{SkillRecord, SkillStore};
+
+pub const QUARANTINE_KEY: &str = "quarantine_new_installs";
+/// `skills.status` of an installed skill waiting for [`approve_skill`].
+pub const QUARANTINED_STATUS: &str = "quarantined";
+
+pub fn get_quarantine_new_installs(store: &SkillStore) -> bool {
+    matches!(store.get_setting(QUARANTINE_KEY), Ok(Some(v)) if v == "1")
+}
+
+/// Skills installed while this is on stay out of every tool until approved.
+pub fn set_quarantine_new_installs(store: &SkillStore, enabled: bool) -> Result<bool> {
+    store.set_setting(QUARANTINE_KEY, if enabled { "1" } else { "0" })?;
+    Ok(enabled)
+}
+
+/// The status a newly installed skill is recorded with.
+pub fn install_status(store: &SkillStore) -> String {
+    if get_quarantine_new_installs(store) {
+        QUARANTINED_STATUS.to_string()
+    } else {
+        "ok".to_string()
+    }
+}
+
+/// The status after an update: a quarantined skill stays quarantined.
+pub fn status_after_update(record: &SkillRecord) -> String {
+    if is_quarantined(record) {
+        QUARANTINED_STATUS.to_string()
+    } else {
+        "ok".to_string()
+    }
+}
+
+pub fn is_quarantined(record: &SkillRecord) -> bool {
+    record.status == QUARANTINED_STATUS
+}
+
+/// Fails with [`ErrorCode::SkillQuarantined`] until `skill` is approved.
+pub fn ensure_approved(skill: &SkillRecord) -> Result<()> {
+    if !is_quarantined(skill) {
+        return Ok(());
+    }
+    Err(coded_with(
+        ErrorCode::SkillQuarantined,
+        format!("{} is quarantined; approve it before syncing", skill.name),
+        serde_json::json!({ "skill_id": skill.id, "name": skill.name }),
+    ))
+}
+
+/// Release a quarantined skill so it can be synced.
+pub fn approve_skill(store: &SkillStore, skill_id: &str) -> Result<()> {
+    let skill = store
+        .get_skill_by_id(skill_id)?
+        .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+    if !is_quarantined(&skill) {
+        return Ok(());
+    }
+    store.upsert_skill(&SkillRecord {
+        status: "ok".to_string(),
+        ..skill
+    })?;
+    log::info!("[quarantine] approved {}", skill_id);
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "tests/quarantine.rs"]
+mod tests;
@@ -0,0 +1,436 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::{Context, Result};
+use reqwest::blocking::RequestBuilder;
+use serde::{Deserialize, Serialize};
+
+use super::http_client::{self, http_client};
+use super::now_ms;
+use super::skill_store::SkillStore;
+
+pub const REGISTRY_AUTH_KEY: &str = "registry_auth";
+
+/// Access tokens this close to expiry are refreshed before use.
+const REFRESH_MARGIN_MS: i64 = 60 * 1000;
+const DEVICE_CODE_GRANT: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+/// Credentials for one registry origin (`https://host[:port]`).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RegistryAuth {
+    Basic {
+        username: String,
+        password: String,
+    },
+    Bearer {
+        token: String,
+    },
+    /// OIDC device-code login; tokens are filled in by the device flow and
+    /// refreshed in the background of requests.
+    Oidc {
+        device_authorization_endpoint: String,
+        token_endpoint: String,
+        client_id: String,
+        #[serde(default)]
+        scope: Option<String>,
+        #[serde(default)]
+        access_token: Option<String>,
+        #[serde(default)]
+        refresh_token: Option<String>,
+        #[serde(default)]
+        expires_at: Option<i64>,
+    },
+}
+
+/// What the settings UI may see: no secrets.
+#[derive(Clone, Debug, Serialize)]
+pub struct RegistryAuthSummary {
+    pub origin: String,
+    pub kind: String,
+    pub username: Option<String>,
+    pub signed_in: bool,
+    pub expires_at: Option<i64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DeviceLogin {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    #[serde(default)]
+    pub verification_uri_complete: Option<String>,
+    pub expires_in: u64,
+    #[serde(default = "default_poll_interval")]
+    pub interval: u64,
+}
+
+fn default_poll_interval() -> u64 {
+    5
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DevicePollStatus {
+    Pending,
+    SlowDown,
+    Complete,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: Option<String>,
+    refresh_token: Option<String>,
+    expires_in: Option<i64>,
+    error: Option<String>,
+    error_description: Option<String>,
+}
+
+type AuthMap = HashMap<String, RegistryAuth>;
+
+struct AuthState {
+    /// Where refreshed tokens are written back; set by `load_registry_auth`.
+    store: Option<SkillStore>,
+    entries: AuthMap,
+}
+
+static STATE: OnceLock<Mutex<AuthState>> = OnceLock::new();
+
+fn state() -> std::sync::MutexGuard<'static, AuthState> {
+    STATE
+        .get_or_init(|| {
+            Mutex::new(AuthState {
+                store: None,
+                entries: HashMap::new(),
+            })
+        })
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+}
+
+/// `scheme://host[:port]` of `url`, the key credentials are stored under.
+pub fn registry_origin(url: &str) -> Option<String> {
+    let parsed = reqwest::Url::parse(url.trim()).ok()?;
+    let host = parsed.host_str()?;
+    Some(match parsed.port() {
+        Some(port) => format!("{}://{}:{}", parsed.scheme(), host, port),
+        None => format!("{}://{}", parsed.scheme(), host),
+    })
+}
+
+fn read_auth_map(store: &SkillStore) -> AuthMap {
+    store
+        .get_setting(REGISTRY_AUTH_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn write_auth_map(store: &SkillStore, map: &AuthMap) -> Result<()> {
+    let raw = serde_json::to_string(map).context("serialize registry auth")?;
+    store.set_setting(REGISTRY_AUTH_KEY, &raw)
+}
+
+/// Load saved credentials into the process-wide table used by registry clients.
+pub fn load_registry_auth(store: &SkillStore) {
+    let mut state = state();
+    state.entries = read_auth_map(store);
+    state.store = Some(store.clone());
+}
+
+pub fn list_registry_auth(store: &SkillStore) -> Vec<RegistryAuthSummary> {
+    let mut summaries: Vec<RegistryAuthSummary> = read_auth_map(store)
+        .into_iter()
+        .map(|(origin, auth)| summarize(origin, &auth))
+        .collect();
+    summaries.sort_by(|a, b| a.origin.cmp(&b.origin));
+    summaries
+}
+
+fn summarize(origin: String, auth: &RegistryAuth) -> RegistryAuthSummary {
+    match auth {
+        RegistryAuth::Basic { username, .. } => RegistryAuthSummary {
+            origin,
+            kind: "basic".to_string(),
+            username: Some(username.clone()),
+            signed_in: true,
+            expires_at: None,
+        },
+        RegistryAuth::Bearer { .. } => RegistryAuthSummary {
+            origin,
+            kind: "bearer".to_string(),
+            username: None,
+            signed_in: true,
+            expires_at: None,
+        },
+        RegistryAuth::Oidc {
+            access_token,
+            expires_at,
+            ..
+        } => RegistryAuthSummary {
+            origin,
+            kind: "oidc".to_string(),
+            username: None,
+            signed_in: access_token.is_some(),
+            expires_at: *expires_at,
+        },
+    }
+}
+
+/// Save (or with `None`, remove) credentials for the registry at `url`.
+pub fn set_registry_auth(
+    store: &SkillStore,
+    url: &str,
+    auth: Option<RegistryAuth>,
+) -> Result<Vec<RegistryAuthSummary>> {
+    let origin =
+        registry_origin(url).ok_or_else(|| anyhow::anyhow!("invalid registry URL: {}", url))?;
+    if let Some(auth) = &auth {
+        validate(auth)?;
+    }
+    let mut map = read_auth_map(store);
+    match auth.clone() {
+        Some(auth) => map.insert(origin.clone(), auth),
+        None => map.remove(&origin),
+    };
+    write_auth_map(store, &map)?;
+    // Only this origin changes in memory; other registries stay as loaded.
+    match auth {
+        Some(auth) => state().entries.insert(origin, auth),
+        None => state().entries.remove(&origin),
+    };
+    Ok(list_registry_auth(store))
+}
+
+fn validate(auth: &RegistryAuth) -> Result<()> {
+    match auth {
+        RegistryAuth::Basic { username, .. } if username.trim().is_empty() => {
+            anyhow::bail!("username is required for basic auth")
+        }
+        RegistryAuth::Bearer { token } if token.trim().is_empty() => {
+            anyhow::bail!("token is required for bearer auth")
+        }
+        RegistryAuth::Oidc {
+            device_authorization_endpoint,
+            token_endpoint,
+            client_id,
+            ..
+        } => {
+            if client_id.trim().is_empty() {
+                anyhow::bail!("client id is required for OIDC");
+            }
+            for endpoint in [device_authorization_endpoint, token_endpoint] {
+                if registry_origin(endpoint).is_none() {
+                    anyhow::bail!("invalid OIDC endpoint: {}", endpoint);
+                }
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Whether credentials are saved for `url`'s origin.
+pub fn has_registry_auth(url: &str) -> bool {
+    registry_origin(url).is_some_and(|origin| state().entries.contains_key(&origin))
+}
+
+/// Attach the credentials saved for `url`'s origin, refreshing an expiring
+/// OIDC token first. Requests to unknown origins go out unchanged.
+pub fn authorize(request: RequestBuilder, url: &str) -> Result<RequestBuilder> {
+    let Some(origin) = registry_origin(url) else {
+        return Ok(request);
+    };
+    let auth = state().entries.get(&origin).cloned();
+    Ok(match auth {
+        None => request,
+        Some(RegistryAuth::Basic { username, password }) => {
+            request.basic_auth(username, Some(password))
+        }
+        Some(RegistryAuth::Bearer { token }) => request.bearer_auth(token),
+        Some(auth @ RegistryAuth::Oidc { .. }) => {
+            request.bearer_auth(fresh_access_token(&origin, auth, now_ms())?)
+        }
+    })
+}
+
+fn fresh_access_token(origin: &str, auth: RegistryAuth, now: i64) -> Result<String> {
+    let RegistryAuth::Oidc {
+        token_endpoint,
+        client_id,
+        access_token,
+        refresh_token,
+        expires_at,
+        ..
+    } = &auth
+    else {
+        anyhow::bail!("not an OIDC registry: {}", origin);
+    };
+    let expiring = expires_at.is_some_and(|at| at - REFRESH_MARGIN_MS <= now);
+    match (access_token, refresh_token) {
+        (Some(token), _) if !expiring => Ok(token.clone()),
+        (_, Some(refresh)) => {
+            let tokens = request_tokens(
+                token_endpoint,
+                &[
+                    ("grant_type", "refresh_token"),
+                    ("refresh_token", refresh),
+                    ("client_id", client_id),
+                ],
+            )?;
+            let updated = with_tokens(auth.clone(), tokens, now)?;
+            let token = match &updated {
+                RegistryAuth::Oidc { access_token, .. } => access_token.clone(),
+                _ => None,
+            };
+            remember_refreshed(origin, updated);
+            token.ok_or_else(|| anyhow::anyhow!("token refresh returned no access token"))
+        }
+        _ => anyhow::bail!("not signed in to registry {}", origin),
+    }
+}
+
+fn remember_refreshed(origin: &str, auth: RegistryAuth) {
+    let mut state = state();
+    state.entries.insert(origin.to_string(), auth);
+    if let Some(store) = state.store.clone() {
+        if let Err(err) = write_auth_map(&store, &state.entries) {
+            log::warn!("failed to save refreshed registry token: {:#}", err);
+        }
+    }
+}
+
+fn oidc_config(store: &SkillStore, url: &str) -> Result<(String, RegistryAuth)> {
+    let origin =
+        registry_origin(url).ok_or_else(|| anyhow::anyhow!("invalid registry URL: {}", url))?;
+    match read_auth_map(store).remove(&origin) {
+        Some(auth @ RegistryAuth::Oidc { .. }) => Ok((origin, auth)),
+        _ => anyhow::bail!("no OIDC configuration for {}", origin),
+    }
+}
+
+/// Begin an OIDC device-code login; the user visits `verification_uri` and
+/// enters `user_code`, then `poll_device_login` is called every `interval`s.
+pub fn start_device_login(store: &SkillStore, url: &str) -> Result<DeviceLogin> {
+    let (_, auth) = oidc_config(store, url)?;
+    let RegistryAuth::Oidc {
+        device_authorization_endpoint,
+        client_id,
+        scope,
+        ..
+    } = auth
+    else {
+        unreachable!("oidc_config only returns OIDC entries");
+    };
+    let mut form = vec![("client_id", client_id.as_str())];
+    if let Some(scope) = scope.as_deref() {
+        form.push(("scope", scope));
+    }
+    http_client::send(
+        http_client()?
+            .post(&device_authorization_endpoint)
+            .form(&form),
+    )
+    .context("device authorization request failed")?
+    .error_for_status()
+    .context("device authorization returned error")?
+    .json()
+    .context("parse device authorization response")
+}
+
+/// Check whether the user finished the device login; on success the tokens
+/// are saved for the registry.
+pub fn poll_device_login(
+    store: &SkillStore,
+    url: &str,
+    device_code: &str,
+) -> Result<DevicePollStatus> {
+    let (origin, auth) = oidc_config(store, url)?;
+    let (token_endpoint, client_id) = match &auth {
+        RegistryAuth::Oidc {
+            token_endpoint,
+            client_id,
+            ..
+        } => (token_endpoint.clone(), client_id.clone()),
+        _ => unreachable!("oidc_config only returns OIDC entries"),
+    };
+    let tokens = request_tokens(
+        &token_endpoint,
+        &[
+            ("grant_type", DEVICE_CODE_GRANT),
+            ("device_code", device_code),
+            ("client_id", &client_id),
+        ],
+    )?;
+    match tokens.error.as_deref() {
+        Some("authorization_pending") => return Ok(DevicePollStatus::Pending),
+        Some("slow_down") => return Ok(DevicePollStatus::SlowDown),
+        _ => {}
+    }
+    let updated = with_tokens(auth, tokens, now_ms())?;
+    let mut map = read_auth_map(store);
+    map.insert(origin.clone(), updated.clone());
+    write_auth_map(store, &map)?;
+    state().entries.insert(origin, updated);
+    Ok(DevicePollStatus::Complete)
+}
+
+/// POST to a token endpoint. OAuth error bodies come back with status 400,
+/// so they are parsed rather than turned into transport errors.
+fn request_tokens(endpoint: &str, form: &[(&str, &str)]) -> Result<TokenResponse> {
+    let response = http_client::send(
+        http_client()?
+            .post(endpoint)
+            .header("Accept", "application/json")
+            .form(form),
+    )
+    .context("token request failed")?;
+    let status = response.status();
+    let tokens: TokenResponse = response
+        .json()
+        .with_context(|| format!("parse token response (HTTP {})", status))?;
+    Ok(tokens)
+}
+
+fn with_tokens(auth: RegistryAuth, tokens: TokenResponse, now: i64) -> Result<RegistryAuth> {
+    if let Some(error) = tokens.error {
+        anyhow::bail!(
+            "token request failed: {}{}",
+            error,
+            tokens
+                .error_description
+                .map(|d| format!(" ({})", d))
+                .unwrap_or_default()
+        );
+    }
+    let RegistryAuth::Oidc {
+        device_authorization_endpoint,
+        token_endpoint,
+        client_id,
+        scope,
+        refresh_token,
+        ..
+    } = auth
+    else {
+        anyhow::bail!("not an OIDC registry");
+    };
+    Ok(RegistryAuth::Oidc {
+        device_authorization_endpoint,
+        token_endpoint,
+        client_id,
+        scope,
+        access_token: Some(
+            tokens
+                .access_token
+                .ok_or_else(|| anyhow::anyhow!("token response has no access token"))?,
+        ),
+        // Providers may omit the refresh token on refresh; keep the old one.
+        refresh_token: tokens.refresh_token.or(refresh_token),
+        expires_at: tokens.expires_in.map(|secs| now + secs * 1000),
+    })
+}
+
+#[cfg(test)]
+#[path = "tests/registry_auth.rs"]
+mod tests;
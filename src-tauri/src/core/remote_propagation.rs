@@ -0,0 +1,177 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::now_ms;
+use super::quarantine::is_quarantined;
+use super::remote_sync::{host_session, sync_all_skills_to_remote, RemoteSkillInfo};
+use super::skill_store::{RemoteDeploymentRecord, SkillRecord, SkillStore, SkillTargetRecord};
+use super::target_naming::target_name_for;
+use super::tool_adapters::adapter_by_key;
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct RemotePropagationReport {
+    /// Host ids that received the updated content.
+    pub pushed: Vec<String>,
+    /// Host ids marked stale (propagation disabled or push failed).
+    pub stale: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+pub fn remote_skill_info(store: &SkillStore, skill: &SkillRecord) -> RemoteSkillInfo {
+    RemoteSkillInfo {
+        name: skill.name.clone(),
+        target_name: target_name_for(store, skill),
+        local_path: PathBuf::from(&skill.central_path),
+        source_type: skill.source_type.clone(),
+        source_ref: skill.source_ref.clone(),
+    }
+}
+
+/// `skill_targets` tool key for a tool linked on a remote host.
+pub fn remote_target_key(host_id: &str, tool_key: &str) -> String {
+    format!("remote:{}:{}", host_id, tool_key)
+}
+
+/// `(host_id, tool_key)` from a `remote:<hostId>:<tool>` key.
+pub fn parse_remote_target_key(tool: &str) -> Option<(&str, &str)> {
+    tool.strip_prefix("remote:")?.split_once(':')
+}
+
+/// Remember that `skill` now lives on `host_id`. With `merge`, `tool_keys` are
+/// added to the ones already linked instead of replacing them. Each tool link
+/// is also tracked as a `remote:<hostId>:<tool>` target.
+pub fn record_remote_deployment(
+    store: &SkillStore,
+    host_id: &str,
+    skill: &SkillRecord,
+    tool_keys: &[String],
+    merge: bool,
+) -> Result<()> {
+    let mut keys: Vec<String> = if merge {
+        store
+            .get_remote_deployment(host_id, &skill.id)?
+            .map(|d| d.tool_keys)
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+    for key in tool_keys {
+        if !keys.contains(key) {
+            keys.push(key.clone());
+        }
+    }
+    let now = now_ms();
+    store.upsert_remote_deployment(&RemoteDeploymentRecord {
+        host_id: host_id.to_string(),
+        skill_id: skill.id.clone(),
+        tool_keys: keys,
+        content_hash: skill.content_hash.clone(),
+        synced_at: now,
+        status: "ok".to_string(),
+    })?;
+
+    let target_name = target_name_for(store, skill);
+    for key in tool_keys {
+        let Some(adapter) = adapter_by_key(key) else {
+            continue;
+        };
+        let tool = remote_target_key(host_id, key);
+        let id = store
+            .get_skill_target(&skill.id, &tool)?
+            .map(|t| t.id)
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        store.upsert_skill_target(&SkillTargetRecord {
+            id,
+            skill_id: skill.id.clone(),
+            tool,
+            target_path: format!("~/{}/{}", adapter.relative_skills_dir, target_name),
+            mode: "symlink".to_string(),
+            status: "ok".to_string(),
+            last_error: None,
+            synced_at: Some(now),
+            content_hash: skill.content_hash.clone(),
+        })?;
+    }
+    Ok(())
+}
+
+/// Drop one tool from a deployment; the remote central copy is still there.
+pub fn forget_remote_tool(
+    store: &SkillStore,
+    host_id: &str,
+    skill_id: &str,
+    tool_key: &str,
+) -> Result<()> {
+    if let Some(mut deployment) = store.get_remote_deployment(host_id, skill_id)? {
+        deployment.tool_keys.retain(|k| k != tool_key);
+        store.upsert_remote_deployment(&deployment)?;
+    }
+    store.delete_skill_target(skill_id, &remote_target_key(host_id, tool_key))?;
+    Ok(())
+}
+
+/// After a local update, push the skill to every host that has it when the
+/// skill opts in to propagation; otherwise mark those deployments stale.
+pub fn propagate_skill_update(
+    store: &SkillStore,
+    skill_id: &str,
+) -> Result<RemotePropagationReport> {
+    let mut report = RemotePropagationReport::default();
+    let Some(skill) = store.get_skill_by_id(skill_id)? else {
+        return Ok(report);
+    };
+    if is_quarantined(&skill) {
+        return Ok(report);
+    }
+    let deployments = store.list_remote_deployments_for_skill(skill_id)?;
+    if deployments.is_empty() {
+        return Ok(report);
+    }
+
+    let propagate = store.get_skill_propagate_to_remotes(skill_id)?;
+    for deployment in deployments {
+        if deployment.content_hash.is_some() && deployment.content_hash == skill.content_hash {
+            continue;
+        }
+        if propagate {
+            match push_to_host(store, &skill, &deployment) {
+                Ok(()) => {
+                    report.pushed.push(deployment.host_id.clone());
+                    continue;
+                }
+                Err(err) => report
+                    .errors
+                    .push(format!("{}: {:#}", deployment.host_id, err)),
+            }
+        }
+        report.stale.push(deployment.host_id.clone());
+        mark_stale(store, deployment)?;
+    }
+    Ok(report)
+}
+
+fn push_to_host(
+    store: &SkillStore,
+    skill: &SkillRecord,
+    deployment: &RemoteDeploymentRecord,
+) -> Result<()> {
+    let host = store
+        .get_remote_host_by_id(&deployment.host_id)?
+        .ok_or_else(|| anyhow::anyhow!("remote host not found"))?;
+    let sess = host_session(&host)?;
+    let info = remote_skill_info(store, skill);
+    sync_all_skills_to_remote(&sess, &[info], &deployment.tool_keys)?;
+    record_remote_deployment(store, &host.id, skill, &deployment.tool_keys, false)
+}
+
+fn mark_stale(store: &SkillStore, mut deployment: RemoteDeploymentRecord) -> Result<()> {
+    deployment.status = "stale".to_string();
+    store.upsert_remote_deployment(&deployment)
+}
+
+#[cfg(test)]
+#[path = "tests/remote_propagation.rs"]
+mod tests;
@@ -0,0 +1,198 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use super::multi_host_sync::{sync_skills_to_hosts, HostSyncResult, MAX_PARALLEL_HOSTS};
+use super::now_ms;
+use super::skill_store::SkillStore;
+
+pub const REMOTE_SCHEDULE_KEY: &str = "remote_sync_schedule";
+pub const REMOTE_SCHEDULE_LAST_RUN_KEY: &str = "remote_sync_schedule_last_run";
+
+pub const EVENT_SCHEDULED_SYNC_STARTED: &str = "remote-schedule://started";
+pub const EVENT_SCHEDULED_SYNC_FINISHED: &str = "remote-schedule://finished";
+
+const MIN_INTERVAL_MINUTES: u32 = 5;
+const MAX_INTERVAL_MINUTES: u32 = 7 * 24 * 60;
+/// How often the scheduler wakes up to check whether a run is due.
+const TICK: Duration = Duration::from_secs(60);
+
+/// Which hosts are kept current in the background, and when.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteSyncSchedule {
+    pub enabled: bool,
+    pub host_ids: Vec<String>,
+    /// Tools to link on each host; empty pushes the central copy only.
+    pub tool_keys: Vec<String>,
+    /// 0 disables interval runs.
+    pub interval_minutes: u32,
+    pub run_at_start: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScheduledSyncRun {
+    /// `startup`, `interval` or `manual`.
+    pub trigger: String,
+    pub started_at: i64,
+    pub finished_at: i64,
+    pub error: Option<String>,
+    pub hosts: Vec<HostSyncResult>,
+}
+
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+pub fn get_schedule(store: &SkillStore) -> RemoteSyncSchedule {
+    store
+        .get_setting(REMOTE_SCHEDULE_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+pub fn set_schedule(
+    store: &SkillStore,
+    schedule: RemoteSyncSchedule,
+) -> Result<RemoteSyncSchedule> {
+    if schedule.interval_minutes != 0
+        && !(MIN_INTERVAL_MINUTES..=MAX_INTERVAL_MINUTES).contains(&schedule.interval_minutes)
+    {
+        anyhow::bail!(
+            "sync interval must be 0 or between {} and {} minutes",
+            MIN_INTERVAL_MINUTES,
+            MAX_INTERVAL_MINUTES
+        );
+    }
+    for host_id in &schedule.host_ids {
+        if store.get_remote_host_by_id(host_id)?.is_none() {
+            anyhow::bail!("remote host not found: {}", host_id);
+        }
+    }
+    let raw = serde_json::to_string(&schedule).context("serialize sync schedule")?;
+    store.set_setting(REMOTE_SCHEDULE_KEY, &raw)?;
+    Ok(schedule)
+}
+
+pub fn last_run(store: &SkillStore) -> Option<ScheduledSyncRun> {
+    store
+        .get_setting(REMOTE_SCHEDULE_LAST_RUN_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+}
+
+/// Whether an interval run is due at `now` given when the last one started.
+pub fn interval_run_due(
+    schedule: &RemoteSyncSchedule,
+    last_started: Option<i64>,
+    now: i64,
+) -> bool {
+    if !schedule.enabled || schedule.interval_minutes == 0 || schedule.host_ids.is_empty() {
+        return false;
+    }
+    let interval_ms = schedule.interval_minutes as i64 * 60 * 1000;
+    last_started.map_or(true, |started| now - started >= interval_ms)
+}
+
+/// Push every managed skill to the scheduled hosts and store the result as
+/// the last run. Returns `None` when a run is already in progress;
+/// `on_start` is only called once this run has the slot.
+pub fn run_scheduled_sync(
+    store: &SkillStore,
+    trigger: &str,
+    on_start: impl FnOnce(),
+) -> Result<Option<ScheduledSyncRun>> {
+    if RUNNING.swap(true, Ordering::SeqCst) {
+        return Ok(None);
+    }
+    on_start();
+    let result = run_locked(store, trigger);
+    RUNNING.store(false, Ordering::SeqCst);
+    result.map(Some)
+}
+
+fn run_locked(store: &SkillStore, trigger: &str) -> Result<ScheduledSyncRun> {
+    let schedule = get_schedule(store);
+    let started_at = now_ms();
+    let outcome = store.list_skills().and_then(|skills| {
+        let skill_ids: Vec<String> = skills.into_iter().map(|s| s.id).collect();
+        sync_skills_to_hosts(
+            store,
+            &schedule.host_ids,
+            &skill_ids,
+            &schedule.tool_keys,
+            MAX_PARALLEL_HOSTS,
+        )
+    });
+    let (hosts, error) = match outcome {
+        Ok(hosts) => (hosts, None),
+        Err(err) => (Vec::new(), Some(format!("{:#}", err))),
+    };
+    let run = ScheduledSyncRun {
+        trigger: trigger.to_string(),
+        started_at,
+        finished_at: now_ms(),
+        error,
+        hosts,
+    };
+    let raw = serde_json::to_string(&run).context("serialize sync run")?;
+    store.set_setting(REMOTE_SCHEDULE_LAST_RUN_KEY, &raw)?;
+    Ok(run)
+}
+
+/// Background thread that runs the schedule: once at start when asked to,
+/// then whenever the interval has elapsed. Settings are re-read every tick,
+/// so schedule changes apply without a restart.
+pub fn spawn_remote_scheduler(app: AppHandle, store: SkillStore) {
+    let spawned = std::thread::Builder::new()
+        .name("remote-sync-scheduler".to_string())
+        .spawn(move || {
+            let schedule = get_schedule(&store);
+            if schedule.enabled && schedule.run_at_start && !schedule.host_ids.is_empty() {
+                run_and_emit(&app, &store, "startup");
+            }
+            loop {
+                std::thread::sleep(TICK);
+                let schedule = get_schedule(&store);
+                let last_started = last_run(&store).map(|r| r.started_at);
+                if interval_run_due(&schedule, last_started, now_ms()) {
+                    run_and_emit(&app, &store, "interval");
+                }
+            }
+        });
+    if let Err(err) = spawned {
+        log::warn!("failed to start remote sync scheduler: {}", err);
+    }
+}
+
+pub fn run_and_emit(
+    app: &AppHandle,
+    store: &SkillStore,
+    trigger: &str,
+) -> Option<ScheduledSyncRun> {
+    let started = || emit(app, EVENT_SCHEDULED_SYNC_STARTED, &trigger);
+    match run_scheduled_sync(store, trigger, started) {
+        Ok(Some(run)) => {
+            emit(app, EVENT_SCHEDULED_SYNC_FINISHED, &run);
+            Some(run)
+        }
+        Ok(None) => None,
+        Err(err) => {
+            log::warn!("scheduled remote sync failed: {:#}", err);
+            None
+        }
+    }
+}
+
+fn emit<S: Serialize + Clone>(app: &AppHandle, event: &str, payload: &S) {
+    if let Err(err) = app.emit(event, payload) {
+        log::warn!("failed to emit {}: {}", event, err);
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/remote_schedule.rs"]
+mod tests;
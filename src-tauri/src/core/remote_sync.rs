@@ -1,10 +1,20 @@
+use std::collections::HashMap;
 use std::io::Read;
 use std::net::{TcpStream, ToSocketAddrs};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 use ssh2::Session;
 
+use super::http_client;
+use super::skill_ignore::SkillIgnore;
+use super::skill_store::RemoteHostRecord;
+use super::source_providers::provider_for_url;
+use super::ssh_retry::with_retry;
 use super::tool_adapters::default_tool_adapters;
 
 // ── Data types ──────────────────────────────────────────────────────────
@@ -13,6 +23,9 @@ use super::tool_adapters::default_tool_adapters;
 /// Carries git source info so we can use `git clone` on the VM when possible.
 pub struct RemoteSkillInfo {
     pub name: String,
+    /// Directory name for tool links, rendered from the naming template.
+    /// The remote central copy always uses `name`.
+    pub target_name: String,
     pub local_path: PathBuf,
     pub source_type: String,
     /// For git-cloned: the clone URL (e.g. "https://github.com/owner/repo")
@@ -29,16 +42,26 @@ pub fn create_ssh_session(
     auth_method: &str,
     key_path: Option<&str>,
 ) -> Result<Session> {
-    // Resolve address and connect with a 15-second timeout to avoid hanging the UI.
-    let addr = format!("{}:{}", host, port);
-    let sock_addr = addr
-        .to_socket_addrs()
-        .with_context(|| format!("resolve address {}", addr))?
-        .next()
-        .ok_or_else(|| anyhow::anyhow!("no address found for {}", addr))?;
-
-    let tcp = TcpStream::connect_timeout(&sock_addr, std::time::Duration::from_secs(15))
-        .with_context(|| format!("TCP connect to {}:{}", host, port))?;
+    // Connect with a 15-second timeout to avoid hanging the UI.
+    let connect_timeout = Duration::from_secs(15);
+    let proxy = http_client::current_settings()
+        .ssh_via_proxy
+        .then(|| http_client::proxy_for_host(host))
+        .flatten();
+    let tcp = match proxy {
+        Some(proxy) => http_client::http_connect_tunnel(&proxy, host, port, connect_timeout)
+            .with_context(|| format!("connect to {}:{} through proxy", host, port))?,
+        None => {
+            let addr = format!("{}:{}", host, port);
+            let sock_addr = addr
+                .to_socket_addrs()
+                .with_context(|| format!("resolve address {}", addr))?
+                .next()
+                .ok_or_else(|| anyhow::anyhow!("no address found for {}", addr))?;
+            TcpStream::connect_timeout(&sock_addr, connect_timeout)
+                .with_context(|| format!("TCP connect to {}:{}", host, port))?
+        }
+    };
 
     let mut sess = Session::new().context("create SSH session")?;
     // Set a 30-second timeout for all SSH operations (handshake, auth, exec, etc.).
@@ -66,6 +89,111 @@ pub fn create_ssh_session(
     Ok(sess)
 }
 
+// ── SSH session pool ────────────────────────────────────────────────────
+
+/// Sessions idle longer than this are dropped instead of reused.
+const SESSION_IDLE_TTL: Duration = Duration::from_secs(5 * 60);
+/// Interval (seconds) at which libssh2 sends keepalive messages.
+const SESSION_KEEPALIVE_SECS: u32 = 30;
+
+struct PooledSession {
+    /// Connection parameters the session was opened with; a host edit invalidates it.
+    fingerprint: String,
+    sess: Session,
+    last_used: Instant,
+}
+
+static SESSION_POOL: OnceLock<Mutex<HashMap<String, PooledSession>>> = OnceLock::new();
+
+fn session_pool() -> &'static Mutex<HashMap<String, PooledSession>> {
+    SESSION_POOL.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Get an SSH session for a saved remote host, reusing a pooled connection when
+/// it is still alive. Falls back to opening a fresh session otherwise.
+pub fn pooled_session(
+    host_id: &str,
+    host: &str,
+    port: u16,
+    username: &str,
+    auth_method: &str,
+    key_path: Option<&str>,
+) -> Result<Session> {
+    let fingerprint = format!(
+        "{}|{}|{}|{}|{}",
+        host,
+        port,
+        username,
+        auth_method,
+        key_path.unwrap_or("")
+    );
+
+    {
+        let mut pool = session_pool().lock().unwrap_or_else(|err| err.into_inner());
+        prune_idle_sessions(&mut pool, Instant::now());
+        if let Some(entry) = pool.get_mut(host_id) {
+            if entry.fingerprint == fingerprint && entry.sess.keepalive_send().is_ok() {
+                entry.last_used = Instant::now();
+                return Ok(entry.sess.clone());
+            }
+            pool.remove(host_id);
+        }
+    }
+
+    let sess = with_retry(&format!("connect to {}", host), |_| {
+        create_ssh_session(host, port, username, auth_method, key_path)
+    })?;
+    sess.set_keepalive(true, SESSION_KEEPALIVE_SECS);
+
+    let mut pool = session_pool().lock().unwrap_or_else(|err| err.into_inner());
+    pool.insert(
+        host_id.to_string(),
+        PooledSession {
+            fingerprint,
+            sess: sess.clone(),
+            last_used: Instant::now(),
+        },
+    );
+    Ok(sess)
+}
+
+/// Pooled session for a saved host record.
+pub fn host_session(host: &RemoteHostRecord) -> Result<Session> {
+    pooled_session(
+        &host.id,
+        &host.host,
+        host.port as u16,
+        &host.username,
+        &host.auth_method,
+        host.key_path.as_deref(),
+    )
+}
+
+/// A new, unpooled session for a saved host, for work that runs next to the
+/// pooled one. libssh2 serializes every call on a session, so parallel
+/// uploads need a connection each to actually overlap.
+pub fn fresh_host_session(host: &RemoteHostRecord) -> Result<Session> {
+    with_retry(&format!("connect to {}", host.host), |_| {
+        create_ssh_session(
+            &host.host,
+            host.port as u16,
+            &host.username,
+            &host.auth_method,
+            host.key_path.as_deref(),
+        )
+    })
+}
+
+/// Drop the pooled session for a host (e.g. after the host was edited or deleted).
+pub fn evict_session(host_id: &str) {
+    let mut pool = session_pool().lock().unwrap_or_else(|err| err.into_inner());
+    pool.remove(host_id);
+}
+
+fn prune_idle_sessions(pool: &mut HashMap<String, PooledSession>, now: Instant) {
+    pool.retain(|_, entry| now.saturating_duration_since(entry.last_used) < SESSION_IDLE_TTL);
+}
+
 /// Test SSH connection. Returns Ok(()) on success.
 pub fn test_connection(
     host: &str,
@@ -79,10 +207,70 @@ pub fn test_connection(
     Ok(output.trim().to_string())
 }
 
+/// Snapshot from [`check_host_health`], stored on the host record as JSON.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RemoteHostHealth {
+    pub checked_at: i64,
+    pub reachable: bool,
+    /// Round trip of a no-op command over the established session.
+    pub latency_ms: Option<u64>,
+    pub os: Option<String>,
+    pub arch: Option<String>,
+    pub home: Option<String>,
+    pub free_disk_bytes: Option<u64>,
+    pub skillshub_writable: bool,
+    pub error: Option<String>,
+}
+
+// One round trip: OS, arch, home, free KiB under $HOME, and a write probe.
+const HEALTH_PROBE: &str = r#"uname -s; uname -m; echo "$HOME"; echo "$(df -Pk "$HOME" 2>/dev/null | awk 'NR==2 {print $4}')"; mkdir -p "$HOME/.skillshub" 2>/dev/null && t="$HOME/.skillshub/.write-test-$$" && touch "$t" 2>/dev/null && rm -f "$t" && echo writable || echo readonly"#;
+
+/// Probe a saved host. Never fails: connection errors end up in `error`.
+pub fn check_host_health(host: &RemoteHostRecord, checked_at: i64) -> RemoteHostHealth {
+    let mut health = RemoteHostHealth {
+        checked_at,
+        ..Default::default()
+    };
+    let probe = host_session(host).and_then(|sess| {
+        let started = Instant::now();
+        ssh_exec(&sess, "true")?;
+        let latency = started.elapsed().as_millis() as u64;
+        Ok((latency, ssh_exec(&sess, HEALTH_PROBE)?))
+    });
+    match probe {
+        Ok((latency, output)) => {
+            health.reachable = true;
+            health.latency_ms = Some(latency);
+            parse_health_probe(&output, &mut health);
+        }
+        Err(err) => health.error = Some(format!("{:#}", err)),
+    }
+    health
+}
+
+fn parse_health_probe(output: &str, health: &mut RemoteHostHealth) {
+    let mut lines = output.lines().map(str::trim);
+    let mut next = || lines.next().filter(|l| !l.is_empty()).map(str::to_string);
+    health.os = next();
+    health.arch = next();
+    health.home = next();
+    health.free_disk_bytes = next()
+        .and_then(|kb| kb.parse::<u64>().ok())
+        .map(|kb| kb * 1024);
+    health.skillshub_writable = next().as_deref() == Some("writable");
+}
+
 // ── Remote command execution ────────────────────────────────────────────
 
-/// Execute a command on the remote host and return stdout.
+/// Execute a command on the remote host and return stdout. Transport
+/// failures are retried on a fresh channel; non-zero exits are not.
 pub fn ssh_exec(sess: &Session, command: &str) -> Result<String> {
+    with_retry(&format!("exec '{}'", command), |_| {
+        ssh_exec_once(sess, command)
+    })
+}
+
+fn ssh_exec_once(sess: &Session, command: &str) -> Result<String> {
     let mut channel = sess.channel_session().context("open SSH channel")?;
     channel
         .exec(command)
@@ -114,8 +302,22 @@ pub fn ssh_exec(sess: &Session, command: &str) -> Result<String> {
 
 // ── SFTP directory upload ───────────────────────────────────────────────
 
+/// Counts reported by [`sftp_upload_dir`].
+#[derive(Clone, Debug, Default)]
+pub struct UploadStats {
+    pub uploaded: usize,
+    pub skipped: usize,
+}
+
 /// Recursively upload a local directory to a remote path via SFTP.
-pub fn sftp_upload_dir(sess: &Session, local_path: &Path, remote_path: &str) -> Result<()> {
+/// Files whose remote size and mtime already match the local file are skipped,
+/// so re-syncing an unchanged skill only costs one `stat` per file.
+/// Entries excluded by the skill's `.skillignore` and the built-ins are not uploaded.
+pub fn sftp_upload_dir(
+    sess: &Session,
+    local_path: &Path,
+    remote_path: &str,
+) -> Result<UploadStats> {
     // Validate local path exists BEFORE creating remote directories
     if !local_path.exists() {
         anyhow::bail!(
@@ -124,15 +326,17 @@ pub fn sftp_upload_dir(sess: &Session, local_path: &Path, remote_path: &str) ->
         );
     }
 
-    let sftp = sess.sftp().context("open SFTP session")?;
+    let mut sftp = sess.sftp().context("open SFTP session")?;
+    let mut stats = UploadStats::default();
 
     // Ensure remote base directory exists
     sftp_mkdir_p(&sftp, remote_path)?;
 
+    let ignore = SkillIgnore::load(local_path);
     for entry in walkdir::WalkDir::new(local_path)
         .follow_links(false)
         .into_iter()
-        .filter_entry(|e| e.file_name() != ".git")
+        .filter_entry(|e| !is_upload_excluded(local_path, &ignore, e))
     {
         let entry = entry?;
         let relative = entry
@@ -150,6 +354,22 @@ pub fn sftp_upload_dir(sess: &Session, local_path: &Path, remote_path: &str) ->
         if entry.file_type().is_dir() {
             sftp_mkdir_p(&sftp, &remote_target)?;
         } else if entry.file_type().is_file() {
+            let local_meta = entry
+                .metadata()
+                .with_context(|| format!("stat local file {:?}", entry.path()))?;
+            let local_mtime = local_meta
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_secs());
+
+            if let Ok(remote_stat) = sftp.stat(Path::new(&remote_target)) {
+                if remote_file_is_current(local_meta.len(), local_mtime, &remote_stat) {
+                    stats.skipped += 1;
+                    continue;
+                }
+            }
+
             let content = std::fs::read(entry.path())
                 .with_context(|| format!("read local file {:?}", entry.path()))?;
 
@@ -161,15 +381,169 @@ pub fn sftp_upload_dir(sess: &Session, local_path: &Path, remote_path: &str) ->
                 }
             }
 
-            let mut remote_file = sftp
-                .create(Path::new(&remote_target))
-                .with_context(|| format!("create remote file {}", remote_target))?;
-            std::io::Write::write_all(&mut remote_file, &content)
-                .with_context(|| format!("write remote file {}", remote_target))?;
+            // Retry just this file on a reopened SFTP channel; files already
+            // written in this batch are not sent again.
+            with_retry(&format!("upload {}", remote_target), |retry| {
+                if retry > 0 {
+                    sftp = sess.sftp().context("reopen SFTP session")?;
+                }
+                let mut remote_file = sftp
+                    .create(Path::new(&remote_target))
+                    .with_context(|| format!("create remote file {}", remote_target))?;
+                std::io::Write::write_all(&mut remote_file, &content)
+                    .with_context(|| format!("write remote file {}", remote_target))
+            })?;
+
+            // Mirror the local mtime so the next sync can skip this file.
+            if let Some(mtime) = local_mtime {
+                let _ = sftp.setstat(
+                    Path::new(&remote_target),
+                    ssh2::FileStat {
+                        size: None,
+                        uid: None,
+                        gid: None,
+                        perm: None,
+                        atime: Some(mtime),
+                        mtime: Some(mtime),
+                    },
+                );
+            }
+            stats.uploaded += 1;
         }
     }
 
-    Ok(())
+    Ok(stats)
+}
+
+// ── Tar-over-SSH upload ─────────────────────────────────────────────────
+
+/// Above this many files a single compressed tar stream beats per-file SFTP round-trips.
+const TAR_UPLOAD_MIN_FILES: usize = 200;
+
+/// Upload a local directory, picking the transport by file count: SFTP (with
+/// unchanged-file skipping) for small skills, a gzipped tar piped into
+/// `tar xzf -` for large ones. Falls back to SFTP if the remote lacks `tar`.
+pub fn upload_dir(sess: &Session, local_path: &Path, remote_path: &str) -> Result<UploadStats> {
+    let file_count = count_upload_files(local_path);
+    if file_count >= TAR_UPLOAD_MIN_FILES {
+        match tar_upload_dir(sess, local_path, remote_path) {
+            Ok(stats) => return Ok(stats),
+            Err(e) => log::warn!("tar upload failed, falling back to SFTP: {:#}", e),
+        }
+    }
+    sftp_upload_dir(sess, local_path, remote_path)
+}
+
+/// Stream the directory as a gzipped tar into `tar xzf - -C <remote_path>`.
+/// Every file is sent; tar preserves mtimes so later SFTP syncs can still skip them.
+pub fn tar_upload_dir(sess: &Session, local_path: &Path, remote_path: &str) -> Result<UploadStats> {
+    let (archive, file_count) = build_tar_gz(local_path)?;
+
+    let command = format!(
+        "mkdir -p '{}' && tar xzf - -C '{}'",
+        remote_path, remote_path
+    );
+    let mut channel = sess.channel_session().context("open SSH channel")?;
+    channel
+        .exec(&command)
+        .with_context(|| format!("exec: {}", command))?;
+    std::io::Write::write_all(&mut channel, &archive).context("stream tar archive")?;
+    channel.send_eof().context("send EOF to remote tar")?;
+
+    let mut output = String::new();
+    channel.read_to_string(&mut output).ok();
+    let mut stderr_buf = String::new();
+    channel.stderr().read_to_string(&mut stderr_buf).ok();
+    channel.wait_close().ok();
+
+    let exit = channel.exit_status().unwrap_or(-1);
+    if exit != 0 {
+        anyhow::bail!(
+            "remote tar extract exited with code {}: {}",
+            exit,
+            stderr_buf.trim()
+        );
+    }
+
+    Ok(UploadStats {
+        uploaded: file_count,
+        skipped: 0,
+    })
+}
+
+fn upload_entries(local_path: &Path) -> impl Iterator<Item = walkdir::DirEntry> + '_ {
+    let ignore = SkillIgnore::load(local_path);
+    walkdir::WalkDir::new(local_path)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(move |e| !is_upload_excluded(local_path, &ignore, e))
+        .filter_map(|e| e.ok())
+}
+
+/// Uploads leave out the same entries as local copies (see [`SkillIgnore`]).
+fn is_upload_excluded(root: &Path, ignore: &SkillIgnore, entry: &walkdir::DirEntry) -> bool {
+    let relative = entry.path().strip_prefix(root).unwrap_or(entry.path());
+    ignore.is_excluded(relative, entry.file_type().is_dir())
+}
+
+fn count_upload_files(local_path: &Path) -> usize {
+    upload_entries(local_path)
+        .filter(|e| e.file_type().is_file())
+        .count()
+}
+
+/// Build an in-memory `.tar.gz` of `local_path` with paths relative to it.
+/// Mirrors the SFTP walker: excluded entries and symlinks are left out.
+fn build_tar_gz(local_path: &Path) -> Result<(Vec<u8>, usize)> {
+    if !local_path.exists() {
+        anyhow::bail!(
+            "local source directory does not exist: {}",
+            local_path.display()
+        );
+    }
+
+    let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+    let mut builder = tar::Builder::new(encoder);
+    builder.follow_symlinks(false);
+    let mut file_count = 0;
+
+    for entry in upload_entries(local_path) {
+        let relative = entry
+            .path()
+            .strip_prefix(local_path)
+            .context("strip prefix")?;
+        if relative.as_os_str().is_empty() {
+            continue;
+        }
+        if entry.file_type().is_dir() {
+            builder
+                .append_dir(relative, entry.path())
+                .with_context(|| format!("add dir {:?} to tar", entry.path()))?;
+        } else if entry.file_type().is_file() {
+            builder
+                .append_path_with_name(entry.path(), relative)
+                .with_context(|| format!("add file {:?} to tar", entry.path()))?;
+            file_count += 1;
+        }
+    }
+
+    let encoder = builder.into_inner().context("finish tar archive")?;
+    let archive = encoder.finish().context("finish gzip stream")?;
+    Ok((archive, file_count))
+}
+
+/// A remote file is considered up to date when both size and mtime (seconds) match.
+fn remote_file_is_current(
+    local_size: u64,
+    local_mtime: Option<u64>,
+    remote: &ssh2::FileStat,
+) -> bool {
+    match (local_mtime, remote.mtime, remote.size) {
+        (Some(local), Some(remote_mtime), Some(remote_size)) => {
+            remote_size == local_size && remote_mtime == local
+        }
+        _ => false,
+    }
 }
 
 /// Create remote directory recursively, ignoring "already exists" errors.
@@ -282,11 +656,11 @@ pub fn remove_remote_symlink(sess: &Session, target: &str) -> Result<()> {
 }
 
 /// Unsync a single skill from a specific tool on the remote host.
-/// Removes the symlink at ~/<tool_skills_dir>/<skill_name>.
+/// Removes the symlink at ~/<tool_skills_dir>/<target_name>.
 /// Does NOT remove the central copy (~/.skillshub/<name>).
 pub fn unsync_skill_from_remote_tool(
     sess: &Session,
-    skill_name: &str,
+    target_name: &str,
     tool_key: &str,
 ) -> Result<()> {
     let adapter = default_tool_adapters()
@@ -296,34 +670,49 @@ pub fn unsync_skill_from_remote_tool(
 
     let home = ssh_exec(sess, "echo $HOME")?;
     let home = home.trim();
-    let abs_tool = format!("{}/{}/{}", home, adapter.relative_skills_dir, skill_name);
+    let abs_tool = format!("{}/{}/{}", home, adapter.relative_skills_dir, target_name);
 
     remove_remote_symlink(sess, &abs_tool)?;
     Ok(())
 }
 
 /// Remove a skill completely from a remote host.
-/// 1. Remove symlinks from ALL installed tools
+/// 1. Remove symlinks (named `target_name`) from ALL installed tools
 /// 2. Remove the central copy (~/.skillshub/<name>)
-pub fn remove_skill_from_remote(sess: &Session, skill_name: &str) -> Result<()> {
-    println!(
-        "[remote_sync] remove_skill_from_remote: skill={}",
-        skill_name
+pub fn remove_skill_from_remote(sess: &Session, skill_name: &str, target_name: &str) -> Result<()> {
+    remove_skill_from_remote_tools(sess, skill_name, target_name, &[])
+}
+
+/// Like [`remove_skill_from_remote`], but only clears the tool symlinks for
+/// `tool_keys` (all tools when empty) before removing the central copy.
+pub fn remove_skill_from_remote_tools(
+    sess: &Session,
+    skill_name: &str,
+    target_name: &str,
+    tool_keys: &[String],
+) -> Result<()> {
+    log::debug!(
+        "remove_skill_from_remote: skill={} tools={:?}",
+        skill_name,
+        tool_keys
     );
     let home = ssh_exec(sess, "echo $HOME")?;
     let home = home.trim();
 
-    // Remove symlinks from all tools (best-effort)
+    // Remove symlinks from tools (best-effort)
     let adapters = default_tool_adapters();
-    for adapter in &adapters {
-        let abs_tool = format!("{}/{}/{}", home, adapter.relative_skills_dir, skill_name);
-        println!("[remote_sync]   removing tool symlink: {}", abs_tool);
+    for adapter in adapters
+        .iter()
+        .filter(|a| tool_keys.is_empty() || tool_keys.iter().any(|k| k == a.id.as_key()))
+    {
+        let abs_tool = format!("{}/{}/{}", home, adapter.relative_skills_dir, target_name);
+        log::debug!("removing tool symlink: {}", abs_tool);
         let _ = ssh_exec(sess, &format!("rm -rf '{}'", abs_tool));
     }
 
     // Remove central copy
     let abs_central = format!("{}/.skillshub/{}", home, skill_name);
-    println!("[remote_sync]   removing central: {}", abs_central);
+    log::debug!("removing central: {}", abs_central);
     ssh_exec(sess, &format!("rm -rf '{}'", abs_central))?;
 
     Ok(())
@@ -334,9 +723,10 @@ pub fn remove_skill_from_remote(sess: &Session, skill_name: &str) -> Result<()>
 /// Clone or pull a git repo on the remote host via SSH.
 /// Returns Ok(()) on success.
 fn clone_or_pull_on_remote(sess: &Session, clone_url: &str, remote_path: &str) -> Result<()> {
-    println!(
-        "[remote_sync] clone_or_pull_on_remote: url={} path={}",
-        clone_url, remote_path
+    log::debug!(
+        "clone_or_pull_on_remote: url={} path={}",
+        clone_url,
+        remote_path
     );
     let check = format!(
         "if [ -d '{path}/.git' ]; then echo EXISTS; else echo MISSING; fi",
@@ -345,7 +735,7 @@ fn clone_or_pull_on_remote(sess: &Session, clone_url: &str, remote_path: &str) -
     let status = ssh_exec(sess, &check)?;
 
     if status.trim() == "EXISTS" {
-        println!("[remote_sync]   git repo exists, pulling...");
+        log::debug!("git repo exists, pulling...");
         ssh_exec(
             sess,
             &format!(
@@ -354,7 +744,7 @@ fn clone_or_pull_on_remote(sess: &Session, clone_url: &str, remote_path: &str) -
             ),
         )?;
     } else {
-        println!("[remote_sync]   cloning fresh...");
+        log::debug!("cloning fresh...");
         ssh_exec(sess, &format!("rm -rf '{}'", remote_path))?;
         ssh_exec(
             sess,
@@ -362,35 +752,31 @@ fn clone_or_pull_on_remote(sess: &Session, clone_url: &str, remote_path: &str) -
         )?;
     }
 
-    println!("[remote_sync]   clone_or_pull done");
+    log::debug!("clone_or_pull done");
     Ok(())
 }
 
 /// Ensure a skill exists in the remote central repo (~/.skillshub/<name>).
-/// Uses `git clone` for git-sourced skills, SFTP upload for others.
+/// Uses `git clone` for git-sourced skills, SFTP or tar upload for others.
 pub fn ensure_skill_on_remote(sess: &Session, info: &RemoteSkillInfo, home: &str) -> Result<()> {
     let abs_central = format!("{}/.skillshub/{}", home, info.name);
-    println!(
-        "[remote_sync] ensure_skill_on_remote: name={} source_type={} source_ref={:?}",
-        info.name, info.source_type, info.source_ref
+    log::debug!(
+        "ensure_skill_on_remote: name={} source_type={} source_ref={:?}",
+        info.name,
+        info.source_type,
+        info.source_ref
     );
 
     // For git-sourced skills, try git clone on the VM
     if let Some(url) = info.source_ref.as_ref() {
         if info.source_type == "git-cloned" || info.source_type == "git" {
             let (clone_url, subpath) = parse_remote_git_url(url);
-            println!(
-                "[remote_sync]   parsed: clone_url={} subpath={:?}",
-                clone_url, subpath
-            );
+            log::debug!("parsed: clone_url={} subpath={:?}", clone_url, subpath);
 
             if let Some(sub) = subpath {
                 let repo_key = simple_hash(&clone_url);
                 let repo_cache = format!("{}/.skillshub/.repos/{}", home, repo_key);
-                println!(
-                    "[remote_sync]   subpath mode: repo_cache={} sub={}",
-                    repo_cache, sub
-                );
+                log::debug!("subpath mode: repo_cache={} sub={}", repo_cache, sub);
                 clone_or_pull_on_remote(sess, &clone_url, &repo_cache)?;
 
                 let source = format!("{}/{}", repo_cache, sub);
@@ -401,15 +787,15 @@ pub fn ensure_skill_on_remote(sess: &Session, info: &RemoteSkillInfo, home: &str
                 let _ = ssh_exec(sess, &format!("rm -rf '{}'", abs_central));
                 create_remote_symlink(sess, &source, &abs_central)?;
             } else {
-                println!("[remote_sync]   direct clone mode");
+                log::debug!("direct clone mode");
                 clone_or_pull_on_remote(sess, &clone_url, &abs_central)?;
             }
             return Ok(());
         }
     }
 
-    // Fallback: SFTP upload
-    println!("[remote_sync]   SFTP upload fallback");
+    // Fallback: SFTP / tar upload
+    log::debug!("upload fallback");
     if !info.local_path.exists() {
         anyhow::bail!(
             "local source directory does not exist: {}",
@@ -417,7 +803,12 @@ pub fn ensure_skill_on_remote(sess: &Session, info: &RemoteSkillInfo, home: &str
         );
     }
     ssh_exec(sess, &format!("mkdir -p '{}'", abs_central))?;
-    sftp_upload_dir(sess, &info.local_path, &abs_central)?;
+    let stats = upload_dir(sess, &info.local_path, &abs_central)?;
+    log::debug!(
+        "uploaded {} files, skipped {} unchanged",
+        stats.uploaded,
+        stats.skipped
+    );
     Ok(())
 }
 
@@ -425,13 +816,10 @@ pub fn ensure_skill_on_remote(sess: &Session, info: &RemoteSkillInfo, home: &str
 /// Handles GitHub tree URLs like "https://github.com/owner/repo/tree/branch/path".
 fn parse_remote_git_url(url: &str) -> (String, Option<String>) {
     let trimmed = url.trim().trim_end_matches('/');
-    let gh_prefix = "https://github.com/";
-
-    if !trimmed.starts_with(gh_prefix) {
+    let Some((provider, rest)) = provider_for_url(trimmed) else {
         return (normalize_clone_url(trimmed), None);
-    }
+    };
 
-    let rest = &trimmed[gh_prefix.len()..];
     let parts: Vec<&str> = rest.split('/').collect();
     if parts.len() < 2 {
         return (normalize_clone_url(trimmed), None);
@@ -439,7 +827,7 @@ fn parse_remote_git_url(url: &str) -> (String, Option<String>) {
 
     let owner = parts[0];
     let repo = parts[1].trim_end_matches(".git");
-    let clone_url = format!("https://github.com/{}/{}.git", owner, repo);
+    let clone_url = provider.clone_url(owner, repo);
 
     // Check for /tree/<branch>/<subpath> or /blob/<branch>/<subpath>
     if parts.len() >= 5 && (parts[2] == "tree" || parts[2] == "blob") {
@@ -470,7 +858,7 @@ fn normalize_clone_url(url: &str) -> String {
         return trimmed.to_string();
     }
     // For GitHub URLs, add .git suffix
-    if trimmed.starts_with("https://github.com/") {
+    if provider_for_url(trimmed).is_some() {
         return format!("{}.git", trimmed);
     }
     trimmed.to_string()
@@ -497,12 +885,20 @@ pub fn sync_skill_to_remote_tool(
 
     // Create symlink from central to tool dir
     let abs_central = format!("{}/.skillshub/{}", home, info.name);
-    let abs_tool = format!("{}/{}/{}", home, adapter.relative_skills_dir, info.name);
+    let abs_tool = format!(
+        "{}/{}/{}",
+        home, adapter.relative_skills_dir, info.target_name
+    );
     create_remote_symlink(sess, &abs_central, &abs_tool)?;
 
     Ok(())
 }
 
+/// Skills uploaded at the same time within one host sync. The first worker
+/// uses the caller's session and each other one opens its own connection,
+/// since uploads sharing one session only take turns.
+pub const MAX_PARALLEL_UPLOADS: usize = 4;
+
 /// Sync all managed skills to a remote host.
 /// Uses git clone for git-sourced skills, SFTP for others.
 /// Creates symlinks for detected tools.
@@ -512,46 +908,74 @@ pub fn sync_all_skills_to_remote(
     skills: &[RemoteSkillInfo],
     tool_keys: &[String],
 ) -> Result<Vec<String>> {
-    let mut synced = Vec::new();
-    let mut errors: Vec<String> = Vec::new();
+    sync_all_skills_to_remote_with_progress(sess, None, skills, tool_keys, &|_, _| {})
+}
 
+/// Like [`sync_all_skills_to_remote`], calling `on_progress(done, total)`
+/// after each skill, from whichever upload worker finished it. With
+/// `open_session`, up to [`MAX_PARALLEL_UPLOADS`] workers upload at once,
+/// each on its own session; without it, skills go one after another. A
+/// worker whose session fails to open leaves its share to the others.
+pub fn sync_all_skills_to_remote_with_progress(
+    sess: &Session,
+    open_session: Option<&(dyn Fn() -> Result<Session> + Sync)>,
+    skills: &[RemoteSkillInfo],
+    tool_keys: &[String],
+    on_progress: &(dyn Fn(usize, usize) + Sync),
+) -> Result<Vec<String>> {
     let home = ssh_exec(sess, "echo $HOME")?;
     let home = home.trim().to_string();
 
     // Ensure remote central repo directory exists
     ssh_exec(sess, &format!("mkdir -p '{}/.skillshub'", home))?;
 
-    let adapters = default_tool_adapters();
-
-    for info in skills {
-        // Skip skills whose local source is missing (only relevant for SFTP path)
-        if info.source_type != "git-cloned" && !info.local_path.exists() {
-            eprintln!(
-                "[remote_sync] skipping '{}': local path does not exist: {}",
-                info.name,
-                info.local_path.display()
-            );
-            continue;
-        }
-
-        // Ensure skill is on remote (git clone or SFTP)
-        if let Err(e) = ensure_skill_on_remote(sess, info, &home) {
-            errors.push(format!("{}: {:#}", info.name, e));
-            continue;
+    // Skills sharing a remote repo cache go through the same lane so two
+    // workers never clone/pull the same checkout at once.
+    let lanes = upload_lanes(skills);
+    let outcomes: Mutex<Vec<(bool, Vec<String>)>> =
+        Mutex::new((0..skills.len()).map(|_| (false, Vec::new())).collect());
+    let next = AtomicUsize::new(0);
+    let finished = AtomicUsize::new(0);
+    let workers = match open_session {
+        Some(_) => MAX_PARALLEL_UPLOADS.clamp(1, lanes.len().max(1)),
+        None => 1,
+    };
+    let run_worker = |sess: &Session| loop {
+        let lane = next.fetch_add(1, Ordering::SeqCst);
+        let Some(indices) = lanes.get(lane) else {
+            break;
+        };
+        for &index in indices {
+            let outcome = sync_one_skill(sess, &skills[index], tool_keys, &home);
+            if let Ok(mut outcomes) = outcomes.lock() {
+                outcomes[index] = outcome;
+            }
+            on_progress(finished.fetch_add(1, Ordering::SeqCst) + 1, skills.len());
         }
-
-        // Create symlinks for each tool
-        let abs_central = format!("{}/.skillshub/{}", home, info.name);
-        for tool_key in tool_keys {
-            if let Some(adapter) = adapters.iter().find(|a| a.id.as_key() == tool_key) {
-                let abs_tool = format!("{}/{}/{}", home, adapter.relative_skills_dir, info.name);
-                if let Err(e) = create_remote_symlink(sess, &abs_central, &abs_tool) {
-                    errors.push(format!("{} -> {}: {:#}", info.name, tool_key, e));
+    };
+    std::thread::scope(|scope| {
+        for _ in 1..workers {
+            scope.spawn(|| {
+                let Some(open_session) = open_session else {
+                    return;
+                };
+                match open_session() {
+                    Ok(own) => run_worker(&own),
+                    Err(err) => log::warn!("[remote_sync] extra upload session: {:#}", err),
                 }
-            }
+            });
         }
+        run_worker(sess);
+    });
 
-        synced.push(info.name.clone());
+    let mut synced = Vec::new();
+    let mut errors: Vec<String> = Vec::new();
+    let outcomes = outcomes.into_inner().unwrap_or_default();
+    for (info, (ok, errs)) in skills.iter().zip(outcomes) {
+        if ok {
+            synced.push(info.name.clone());
+        }
+        errors.extend(errs);
     }
 
     if !errors.is_empty() && synced.is_empty() {
@@ -565,6 +989,72 @@ pub fn sync_all_skills_to_remote(
     Ok(synced)
 }
 
+/// Upload one skill and link it into `tool_keys`. Returns whether the skill
+/// landed on the host (tool link failures do not undo that) and any errors.
+fn sync_one_skill(
+    sess: &Session,
+    info: &RemoteSkillInfo,
+    tool_keys: &[String],
+    home: &str,
+) -> (bool, Vec<String>) {
+    // Skip skills whose local source is missing (only relevant for SFTP path)
+    if info.source_type != "git-cloned" && !info.local_path.exists() {
+        log::warn!(
+            "skipping '{}': local path does not exist: {}",
+            info.name,
+            info.local_path.display()
+        );
+        return (false, Vec::new());
+    }
+
+    // Ensure skill is on remote (git clone or SFTP)
+    if let Err(e) = ensure_skill_on_remote(sess, info, home) {
+        return (false, vec![format!("{}: {:#}", info.name, e)]);
+    }
+
+    // Create symlinks for each tool
+    let mut errors = Vec::new();
+    let abs_central = format!("{}/.skillshub/{}", home, info.name);
+    let adapters = default_tool_adapters();
+    for tool_key in tool_keys {
+        if let Some(adapter) = adapters.iter().find(|a| a.id.as_key() == tool_key) {
+            let abs_tool = format!(
+                "{}/{}/{}",
+                home, adapter.relative_skills_dir, info.target_name
+            );
+            if let Err(e) = create_remote_symlink(sess, &abs_central, &abs_tool) {
+                errors.push(format!("{} -> {}: {:#}", info.name, tool_key, e));
+            }
+        }
+    }
+    (true, errors)
+}
+
+/// Group skill indices into lanes that may run concurrently. Git skills
+/// living in the same repo share a lane; everything else gets its own.
+fn upload_lanes(skills: &[RemoteSkillInfo]) -> Vec<Vec<usize>> {
+    let mut lanes: Vec<Vec<usize>> = Vec::new();
+    let mut repo_lane: HashMap<String, usize> = HashMap::new();
+    for (index, info) in skills.iter().enumerate() {
+        let repo = info
+            .source_ref
+            .as_deref()
+            .filter(|_| info.source_type == "git-cloned" || info.source_type == "git")
+            .map(|url| parse_remote_git_url(url).0);
+        match repo {
+            Some(repo) => match repo_lane.get(&repo) {
+                Some(&lane) => lanes[lane].push(index),
+                None => {
+                    repo_lane.insert(repo, lanes.len());
+                    lanes.push(vec![index]);
+                }
+            },
+            None => lanes.push(vec![index]),
+        }
+    }
+    lanes
+}
+
 // ── Remote skill listing ────────────────────────────────────────────────
 
 /// List skill names that exist on the remote host under ~/.skillshub/.
@@ -580,12 +1070,13 @@ pub fn list_remote_skills(sess: &Session) -> Result<Vec<String>> {
 }
 
 /// Check which tool×skill symlinks actually exist on the remote host.
-/// Given the list of skill names present in ~/.skillshub/ and the list of
-/// installed tool keys, builds a single batch SSH command to test all
-/// combinations and returns `(tool_key, skill_name, linked)` triples.
+/// Given `(skill_name, target_name)` pairs for the skills present in
+/// ~/.skillshub/ and the list of installed tool keys, builds a single batch
+/// SSH command to test all combinations and returns
+/// `(tool_key, skill_name, linked)` triples.
 pub fn list_remote_skill_links(
     sess: &Session,
-    skills: &[String],
+    skills: &[(String, String)],
     installed_tool_keys: &[String],
 ) -> Result<Vec<(String, String, bool)>> {
     if skills.is_empty() || installed_tool_keys.is_empty() {
@@ -607,10 +1098,10 @@ pub fn list_remote_skill_links(
     let mut checks: Vec<String> = Vec::new();
     for adapter in &relevant {
         let key = adapter.id.as_key();
-        for skill in skills {
+        for (skill, target_name) in skills {
             checks.push(format!(
                 "test -e ~/{}/{} && echo 'Y|{}|{}' || echo 'N|{}|{}'",
-                adapter.relative_skills_dir, skill, key, skill, key, skill
+                adapter.relative_skills_dir, target_name, key, skill, key, skill
             ));
         }
     }
@@ -635,9 +1126,64 @@ pub fn list_remote_skill_links(
     Ok(results)
 }
 
+/// Largest remote file returned for preview.
+const MAX_REMOTE_PREVIEW_BYTES: u64 = 1024 * 1024;
+
+/// Read a file from the remote central copy (`~/.skillshub/<skill>`) over
+/// SFTP. `relative_path` defaults to SKILL.md and may not leave the skill dir.
+pub fn read_remote_skill_file(
+    sess: &Session,
+    skill_name: &str,
+    relative_path: Option<&str>,
+) -> Result<String> {
+    let relative = relative_path
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .unwrap_or("SKILL.md");
+    validate_relative_path(skill_name)?;
+    validate_relative_path(relative)?;
+
+    let home = ssh_exec(sess, "echo $HOME")?;
+    let path = format!("{}/.skillshub/{}/{}", home.trim(), skill_name, relative);
+    with_retry(&format!("read remote file {}", path), |_| {
+        let sftp = sess.sftp().context("open SFTP session")?;
+        let stat = sftp
+            .stat(Path::new(&path))
+            .with_context(|| format!("remote file not found: {}", path))?;
+        if stat.is_dir() {
+            anyhow::bail!("remote path is a directory: {}", path);
+        }
+        if stat.size.unwrap_or(0) > MAX_REMOTE_PREVIEW_BYTES {
+            anyhow::bail!(
+                "remote file too large to preview ({} bytes): {}",
+                stat.size.unwrap_or(0),
+                path
+            );
+        }
+        let mut file = sftp
+            .open(Path::new(&path))
+            .with_context(|| format!("open remote file {}", path))?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)
+            .with_context(|| format!("read remote file {}", path))?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    })
+}
+
+fn validate_relative_path(path: &str) -> Result<()> {
+    let invalid = path.is_empty()
+        || path.starts_with('/')
+        || path.contains('\\')
+        || path.split('/').any(|part| part.is_empty() || part == "..");
+    if invalid {
+        anyhow::bail!("invalid remote path: {}", path);
+    }
+    Ok(())
+}
+
 // ── Helpers ─────────────────────────────────────────────────────────────
 
-fn resolve_key_path(key_path: Option<&str>) -> Result<String> {
+pub(super) fn resolve_key_path(key_path: Option<&str>) -> Result<String> {
     if let Some(kp) = key_path {
         if !kp.is_empty() {
             let expanded = if let Some(stripped) = kp.strip_prefix("~/") {
@@ -664,36 +1210,5 @@ fn resolve_key_path(key_path: Option<&str>) -> Result<String> {
 }
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn resolve_key_path_explicit() {
-        // Should return the explicit path when provided
-        let result = resolve_key_path(Some("/tmp/my_key"));
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), "/tmp/my_key");
-    }
-
-    #[test]
-    fn resolve_key_path_empty_falls_back() {
-        // Empty string should fall back to default key search
-        let result = resolve_key_path(Some(""));
-        // Either finds a key or errors; both are valid behaviors
-        assert!(result.is_ok() || result.is_err());
-    }
-
-    #[test]
-    fn resolve_key_path_none_falls_back() {
-        let result = resolve_key_path(None);
-        assert!(result.is_ok() || result.is_err());
-    }
-
-    #[test]
-    fn resolve_key_path_tilde_expansion() {
-        let result = resolve_key_path(Some("~/.ssh/id_rsa"));
-        assert!(result.is_ok());
-        let path = result.unwrap();
-        assert!(!path.starts_with("~"), "tilde should be expanded");
-    }
-}
+#[path = "tests/remote_sync.rs"]
+mod tests;
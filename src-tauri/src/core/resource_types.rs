@@ -0,0 +1,134 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::sync_engine::{sync_file_with_overwrite, SyncOutcome};
+use super::tool_adapters::{default_tool_adapters, resolve_default_path, ToolAdapter};
+
+/// What a managed entry is. Skills are directories; agents and commands are
+/// a single markdown file, kept in its own directory in the central repo and
+/// synced as that file.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceType {
+    #[default]
+    Skill,
+    Agent,
+    Command,
+}
+
+impl ResourceType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResourceType::Skill => "skill",
+            ResourceType::Agent => "agent",
+            ResourceType::Command => "command",
+        }
+    }
+
+    /// Unknown values read back from the DB are treated as skills.
+    pub fn parse(raw: &str) -> Self {
+        match raw {
+            "agent" => ResourceType::Agent,
+            "command" => ResourceType::Command,
+            _ => ResourceType::Skill,
+        }
+    }
+}
+
+/// Tool key, agents dir, commands dir; relative to home. Tools not listed
+/// only take skills.
+const RESOURCE_DIRS: &[(&str, Option<&str>, Option<&str>)] = &[
+    (
+        "claude_code",
+        Some(".claude/agents"),
+        Some(".claude/commands"),
+    ),
+    (
+        "opencode",
+        Some(".config/opencode/agent"),
+        Some(".config/opencode/command"),
+    ),
+    ("codex", None, Some(".codex/prompts")),
+    ("cursor", None, Some(".cursor/commands")),
+    ("droid", Some(".factory/droids"), Some(".factory/commands")),
+];
+
+fn relative_resource_dir(tool_key: &str, kind: ResourceType) -> Option<&'static str> {
+    let (_, agents, commands) = RESOURCE_DIRS.iter().find(|(key, _, _)| *key == tool_key)?;
+    match kind {
+        ResourceType::Skill => None,
+        ResourceType::Agent => *agents,
+        ResourceType::Command => *commands,
+    }
+}
+
+/// Where `adapter` keeps resources of `kind`, or `None` if it has no such
+/// directory. Skills follow the tool's skills dir, including overrides.
+pub fn resource_dir(adapter: &ToolAdapter, kind: ResourceType) -> Result<Option<PathBuf>> {
+    if kind == ResourceType::Skill {
+        return resolve_default_path(adapter).map(Some);
+    }
+    let Some(relative) = relative_resource_dir(adapter.id.as_key(), kind) else {
+        return Ok(None);
+    };
+    let home = dirs::home_dir().context("failed to resolve home directory")?;
+    Ok(Some(home.join(relative)))
+}
+
+/// Keys of the tools that can receive resources of `kind`.
+pub fn tools_supporting(kind: ResourceType) -> Vec<String> {
+    default_tool_adapters()
+        .into_iter()
+        .filter(|a| {
+            kind == ResourceType::Skill || relative_resource_dir(a.id.as_key(), kind).is_some()
+        })
+        .map(|a| a.id.as_key().to_string())
+        .collect()
+}
+
+/// The markdown file of a single-file resource stored at `central_path`.
+pub fn resource_file(central_path: &Path) -> Result<PathBuf> {
+    let mut files = Vec::new();
+    for entry in
+        std::fs::read_dir(central_path).with_context(|| format!("read dir {:?}", central_path))?
+    {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "md") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    files
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("no markdown file in {:?}", central_path))
+}
+
+/// Target path for a resource named `name` in `dir`, keeping the stored
+/// file's extension.
+pub fn resource_target(dir: &Path, name: &str, file: &Path) -> PathBuf {
+    match file.extension() {
+        Some(ext) => dir.join(format!("{}.{}", name, ext.to_string_lossy())),
+        None => dir.join(name),
+    }
+}
+
+/// Link or copy the resource stored at `central_path` to `target`. Cursor
+/// does not follow symlinks, so it always gets a copy.
+pub fn sync_resource_file(
+    tool_key: &str,
+    central_path: &Path,
+    target: &Path,
+    overwrite: bool,
+    force_copy: bool,
+) -> Result<SyncOutcome> {
+    let source = resource_file(central_path)?;
+    let force_copy = force_copy || tool_key.eq_ignore_ascii_case("cursor");
+    sync_file_with_overwrite(&source, target, overwrite, force_copy)
+}
+
+#[cfg(test)]
+#[path = "tests/resource_types.rs"]
+mod tests;
@@ -0,0 +1,316 @@
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::app_paths::AppPaths;
+use super::central_history::record_change;
+use super::central_repo::{ensure_central_repo_writable, writable_central_repo};
+use super::errors::target_exists;
+use super::installer::{
+    compute_content_hash, is_frontmatter_delimiter, resync_copy_targets, InstallResult,
+};
+use super::now_ms;
+use super::skill_drift::record_skill_manifest;
+use super::skill_events;
+use super::skill_lock::ensure_unlocked;
+use super::skill_metadata::refresh_skill_metadata;
+use super::skill_store::{SkillRecord, SkillStore};
+use super::skill_templates::{render_template, user_templates_dir, TemplateVars, DEFAULT_TEMPLATE};
+use super::sync_engine::{copy_dir_recursive, sibling_path};
+
+/// Source type of skills written in Skills Hub rather than installed.
+pub const AUTHORED_SOURCE_TYPE: &str = "authored";
+
+const MAX_NAME_LEN: usize = 64;
+
+/// Create `<central>/<name>` from a built-in or user template (see
+/// `skill_templates`) and register it as an authored skill. Fails if a skill
+/// with that name is already managed or the folder exists.
+pub fn create_skill(
+    paths: &AppPaths,
+    store: &SkillStore,
+    name: &str,
+    description: Option<&str>,
+    template: Option<&str>,
+    author: Option<&str>,
+) -> Result<InstallResult> {
+    validate_skill_name(name)?;
+    if store.list_skills()?.iter().any(|s| s.name == name) {
+        anyhow::bail!("a skill named {} already exists", name);
+    }
+
+    let central_path = writable_central_repo(paths, store)?.join(name);
+    if std::fs::symlink_metadata(&central_path).is_ok() {
+        return Err(target_exists(&central_path));
+    }
+    render_template(
+        &user_templates_dir(paths, store)?,
+        template.unwrap_or(DEFAULT_TEMPLATE),
+        &TemplateVars::new(name, description, author),
+        &central_path,
+    )?;
+    register_authored(store, name, central_path, &format!("Create {}", name))
+}
+
+/// Copy a managed skill's central folder to `<central>/<new_name>` as an
+/// authored skill, so it can be edited without touching the original or
+/// being overwritten by its updates. The copy's frontmatter `name` is
+/// changed to `new_name`.
+pub fn duplicate_skill(
+    paths: &AppPaths,
+    store: &SkillStore,
+    skill_id: &str,
+    new_name: &str,
+) -> Result<InstallResult> {
+    validate_skill_name(new_name)?;
+    let source = store
+        .get_skill_by_id(skill_id)?
+        .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+    if store.list_skills()?.iter().any(|s| s.name == new_name) {
+        anyhow::bail!("a skill named {} already exists", new_name);
+    }
+    let source_path = PathBuf::from(&source.central_path);
+    if !source_path.is_dir() {
+        anyhow::bail!("central copy is missing: {}", source.central_path);
+    }
+
+    let central_path = writable_central_repo(paths, store)?.join(new_name);
+    if std::fs::symlink_metadata(&central_path).is_ok() {
+        return Err(target_exists(&central_path));
+    }
+    let copied = copy_dir_recursive(&source_path, &central_path)
+        .and_then(|()| rename_in_frontmatter(&central_path.join("SKILL.md"), new_name));
+    if let Err(err) = copied {
+        let _ = std::fs::remove_dir_all(&central_path);
+        return Err(err);
+    }
+
+    let resource_type = store.get_skill_resource_type(&source.id)?;
+    let result = register_authored(
+        store,
+        new_name,
+        central_path,
+        &format!("Duplicate {} as {}", source.name, new_name),
+    )?;
+    store.set_skill_resource_type(&result.skill_id, &resource_type)?;
+    Ok(result)
+}
+
+/// Record the skill folder at `central_path` as a new authored skill and
+/// commit it to the central history with `message`. The folder is removed
+/// again if the record cannot be saved.
+fn register_authored(
+    store: &SkillStore,
+    name: &str,
+    central_path: PathBuf,
+    message: &str,
+) -> Result<InstallResult> {
+    let now = now_ms();
+    let content_hash = compute_content_hash(&central_path);
+    let record = SkillRecord {
+        id: Uuid::new_v4().to_string(),
+        name: name.to_string(),
+        source_type: AUTHORED_SOURCE_TYPE.to_string(),
+        source_ref: None,
+        source_revision: None,
+        central_path: central_path.to_string_lossy().to_string(),
+        content_hash: content_hash.clone(),
+        created_at: now,
+        updated_at: now,
+        last_sync_at: None,
+        last_seen_at: now,
+        status: "ok".to_string(),
+        group_name: None,
+    };
+    if let Err(err) = store.upsert_skill(&record) {
+        let _ = std::fs::remove_dir_all(&central_path);
+        return Err(err);
+    }
+    refresh_skill_metadata(store, &record.id, &central_path);
+    record_skill_manifest(store, &record.id, &central_path);
+    record_change(store, &central_path, message);
+    skill_events::skill_installed(&record.id, &record.name);
+
+    // New and duplicated skills come from the user; nothing to scan.
+    Ok(InstallResult {
+        skill_id: record.id,
+        name: record.name,
+        central_path,
+        content_hash,
+        content_warnings: Vec::new(),
+        signature_status: None,
+    })
+}
+
+/// Point the `name:` line of a SKILL.md frontmatter at `name`. Files
+/// without SKILL.md or without a `name:` line are left as they are.
+fn rename_in_frontmatter(skill_md: &Path, name: &str) -> Result<()> {
+    let Ok(text) = std::fs::read_to_string(skill_md) else {
+        return Ok(());
+    };
+    if let Some(renamed) = with_frontmatter_name(&text, name) {
+        write_file_atomic(skill_md, &renamed)?;
+    }
+    Ok(())
+}
+
+fn with_frontmatter_name(text: &str, name: &str) -> Option<String> {
+    let mut lines = text.split_inclusive('\n');
+    let first = lines.next()?;
+    if !is_frontmatter_delimiter(first) {
+        return None;
+    }
+    let mut out = String::from(first);
+    let mut renamed = false;
+    let mut in_frontmatter = true;
+    for line in lines {
+        if in_frontmatter && is_frontmatter_delimiter(line) {
+            in_frontmatter = false;
+        } else if in_frontmatter && !renamed && line.starts_with("name:") {
+            let ending = if line.ends_with("\r\n") { "\r\n" } else { "\n" };
+            out.push_str(&format!("name: {}{}", name, ending));
+            renamed = true;
+            continue;
+        }
+        out.push_str(line);
+    }
+    renamed.then_some(out)
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SkillWriteResult {
+    pub skill_id: String,
+    pub relative_path: String,
+    pub content_hash: Option<String>,
+    /// Copy-mode targets that were refreshed with the new content.
+    pub updated_targets: Vec<String>,
+}
+
+/// Replace (or create) `relative_path` inside a skill's central folder.
+/// The previous state is committed to the central history first, so the
+/// edit can be rolled back; copy-mode targets are re-copied afterwards.
+pub fn write_skill_content(
+    store: &SkillStore,
+    skill_id: &str,
+    relative_path: &str,
+    content: &str,
+) -> Result<SkillWriteResult> {
+    ensure_central_repo_writable(store)?;
+    let skill = store
+        .get_skill_by_id(skill_id)?
+        .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+    ensure_unlocked(store, &skill)?;
+    let central_path = PathBuf::from(&skill.central_path);
+    if !central_path.is_dir() {
+        anyhow::bail!("central copy is missing: {}", skill.central_path);
+    }
+    let path = resolve_skill_file(&central_path, relative_path)?;
+
+    let label = format!("{}/{}", skill.name, relative_path);
+    record_change(store, &central_path, &format!("Before editing {}", label));
+    write_file_atomic(&path, content)?;
+
+    let now = now_ms();
+    let content_hash = compute_content_hash(&central_path);
+    store.upsert_skill(&SkillRecord {
+        content_hash: content_hash.clone(),
+        updated_at: now,
+        ..skill.clone()
+    })?;
+    refresh_skill_metadata(store, skill_id, &central_path);
+    let updated_targets =
+        resync_copy_targets(store, skill_id, &central_path, content_hash.as_deref(), now)?;
+    record_change(store, &central_path, &format!("Edit {}", label));
+    skill_events::skill_updated(&skill.id, &skill.name);
+
+    Ok(SkillWriteResult {
+        skill_id: skill.id,
+        relative_path: relative_path.to_string(),
+        content_hash,
+        updated_targets,
+    })
+}
+
+/// `relative` inside `skill_dir`. Absolute paths, `..`, `.git` and links
+/// that lead out of the folder are refused.
+pub(crate) fn resolve_skill_file(skill_dir: &Path, relative: &str) -> Result<PathBuf> {
+    let rel = Path::new(relative);
+    let mut parts = 0;
+    for component in rel.components() {
+        match component {
+            Component::Normal(part) if part != ".git" => parts += 1,
+            Component::CurDir => {}
+            _ => anyhow::bail!("path must stay inside the skill folder: {}", relative),
+        }
+    }
+    if parts == 0 {
+        anyhow::bail!("path must name a file inside the skill folder");
+    }
+    let path = skill_dir.join(rel);
+
+    // The deepest part that exists decides where a link would lead.
+    let root = skill_dir
+        .canonicalize()
+        .with_context(|| format!("resolve {:?}", skill_dir))?;
+    let mut existing = path.as_path();
+    while std::fs::symlink_metadata(existing).is_err() {
+        existing = existing.parent().unwrap_or(skill_dir);
+    }
+    let resolved = existing
+        .canonicalize()
+        .with_context(|| format!("resolve {:?}", existing))?;
+    if !resolved.starts_with(&root) {
+        anyhow::bail!("path must stay inside the skill folder: {}", relative);
+    }
+    if path.is_dir() {
+        anyhow::bail!("{} is a directory", relative);
+    }
+    Ok(path)
+}
+
+/// Write through a sibling file and rename it over `path`, keeping the
+/// permissions of the file it replaces.
+fn write_file_atomic(path: &Path, content: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).with_context(|| format!("create dir {:?}", parent))?;
+    }
+    let staged = sibling_path(path, "edit");
+    let result = (|| {
+        std::fs::write(&staged, content).with_context(|| format!("write {:?}", staged))?;
+        if let Ok(meta) = std::fs::metadata(path) {
+            std::fs::set_permissions(&staged, meta.permissions())
+                .with_context(|| format!("set permissions on {:?}", staged))?;
+        }
+        std::fs::rename(&staged, path).with_context(|| format!("replace {:?}", path))
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&staged);
+    }
+    result
+}
+
+/// Skill names follow the Agent Skills rules: lowercase letters, digits and
+/// single hyphens, at most 64 characters.
+pub fn validate_skill_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        && !name.starts_with('-')
+        && !name.ends_with('-')
+        && !name.contains("--");
+    if !valid {
+        anyhow::bail!(
+            "invalid skill name {:?}: use lowercase letters, digits and hyphens",
+            name
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "tests/skill_authoring.rs"]
+mod tests;
@@ -0,0 +1,241 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use super::app_paths::AppPaths;
+use super::clawhub_api::install_clawhub_skill;
+use super::installer::{
+    install_git_skill, looks_like_github_shorthand, parse_github_url, InstallResult,
+};
+use super::skill_store::{SkillRecord, SkillStore};
+use super::source_trust::{check_clawhub_source, check_git_source};
+
+const CLAWHUB_PREFIX: &str = "clawhub://";
+
+/// Where a declared dependency can be installed from.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DependencySource {
+    Clawhub { slug: String },
+    Git { url: String },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SkillDependency {
+    /// As written in SKILL.md.
+    pub spec: String,
+    /// `None` when the spec is neither a ClawHub slug nor a git URL.
+    pub source: Option<DependencySource>,
+    /// The managed skill that satisfies it, if one is installed.
+    pub installed_skill_id: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SkillDependent {
+    pub skill_id: String,
+    pub name: String,
+    pub spec: String,
+}
+
+/// `clawhub://<slug>` (or `clawhub:<slug>`), a git URL, or a GitHub
+/// `owner/repo[/tree/<branch>/<path>]` shorthand.
+pub fn parse_dependency(spec: &str) -> Option<DependencySource> {
+    let spec = spec.trim();
+    if let Some(slug) = spec
+        .strip_prefix(CLAWHUB_PREFIX)
+        .or_else(|| spec.strip_prefix("clawhub:"))
+    {
+        let slug = slug.trim_matches('/');
+        return (!slug.is_empty()).then(|| DependencySource::Clawhub {
+            slug: slug.to_string(),
+        });
+    }
+    let is_url = spec.contains("://") || spec.starts_with("git@");
+    (is_url || looks_like_github_shorthand(spec)).then(|| DependencySource::Git {
+        url: spec.to_string(),
+    })
+}
+
+/// The installed skill that `source` refers to.
+fn find_installed(skills: &[SkillRecord], source: &DependencySource) -> Option<String> {
+    skills
+        .iter()
+        .find(|skill| satisfies(skill, source))
+        .map(|skill| skill.id.clone())
+}
+
+fn satisfies(skill: &SkillRecord, source: &DependencySource) -> bool {
+    let Some(source_ref) = skill.source_ref.as_deref() else {
+        return false;
+    };
+    match source {
+        DependencySource::Clawhub { slug } => {
+            source_ref.strip_prefix(CLAWHUB_PREFIX) == Some(slug.as_str())
+        }
+        DependencySource::Git { url } => git_identity(url) == git_identity(source_ref),
+    }
+}
+
+/// Repository and folder of a git source; the branch does not matter.
+fn git_identity(url: &str) -> (String, String) {
+    let parsed = parse_github_url(url);
+    let repo = parsed
+        .clone_url
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .to_ascii_lowercase();
+    let subpath = parsed
+        .subpath
+        .unwrap_or_default()
+        .trim_matches('/')
+        .to_string();
+    (repo, subpath)
+}
+
+/// Match `specs` against installed skills and store them as the
+/// dependency edges of `skill_id`. Edges other skills left unresolved are
+/// linked to `skill_id` when it satisfies them.
+pub fn record_dependencies(
+    store: &SkillStore,
+    skill_id: &str,
+    specs: &[String],
+) -> Result<Vec<SkillDependency>> {
+    let skills = store.list_skills()?;
+    let deps = resolve(&skills, specs, skill_id);
+    let edges: Vec<(String, Option<String>)> = deps
+        .iter()
+        .map(|dep| (dep.spec.clone(), dep.installed_skill_id.clone()))
+        .collect();
+    store.set_skill_dependencies(skill_id, &edges)?;
+
+    if let Some(skill) = skills.iter().find(|s| s.id == skill_id) {
+        for (dependent, spec) in store.list_unresolved_dependencies()? {
+            let matches = parse_dependency(&spec).is_some_and(|source| satisfies(skill, &source));
+            if dependent != skill_id && matches {
+                store.set_dependency_target(&dependent, &spec, Some(skill_id))?;
+            }
+        }
+    }
+    Ok(deps)
+}
+
+/// A skill never satisfies its own dependencies.
+fn resolve(skills: &[SkillRecord], specs: &[String], skill_id: &str) -> Vec<SkillDependency> {
+    let others: Vec<SkillRecord> = skills
+        .iter()
+        .filter(|s| s.id != skill_id)
+        .cloned()
+        .collect();
+    let mut deps: Vec<SkillDependency> = Vec::new();
+    for spec in specs {
+        if deps.iter().any(|d| &d.spec == spec) {
+            continue;
+        }
+        let source = parse_dependency(spec);
+        deps.push(SkillDependency {
+            spec: spec.clone(),
+            installed_skill_id: source
+                .as_ref()
+                .and_then(|source| find_installed(&others, source)),
+            source,
+        });
+    }
+    deps
+}
+
+/// Declared dependencies of `skill_id`, resolved against what is
+/// installed now.
+pub fn skill_dependencies(store: &SkillStore, skill_id: &str) -> Result<Vec<SkillDependency>> {
+    let specs: Vec<String> = store
+        .list_skill_dependencies(skill_id)?
+        .into_iter()
+        .map(|(spec, _)| spec)
+        .collect();
+    Ok(resolve(&store.list_skills()?, &specs, skill_id))
+}
+
+/// Dependencies of `skill_id` that are not installed and can be.
+pub fn missing_dependencies(store: &SkillStore, skill_id: &str) -> Result<Vec<SkillDependency>> {
+    Ok(skill_dependencies(store, skill_id)?
+        .into_iter()
+        .filter(|dep| dep.installed_skill_id.is_none() && dep.source.is_some())
+        .collect())
+}
+
+/// Managed skills that declare `skill_id` as a dependency.
+pub fn skill_dependents(store: &SkillStore, skill_id: &str) -> Result<Vec<SkillDependent>> {
+    let mut dependents = Vec::new();
+    for (dependent, spec) in store.list_skill_dependents(skill_id)? {
+        if let Some(skill) = store.get_skill_by_id(&dependent)? {
+            dependents.push(SkillDependent {
+                skill_id: skill.id,
+                name: skill.name,
+                spec,
+            });
+        }
+    }
+    Ok(dependents)
+}
+
+/// What `install_missing_dependencies` did.
+#[derive(Default)]
+pub struct DependencyInstall {
+    pub installed: Vec<InstallResult>,
+    /// `(spec, error)` for dependencies that could not be installed.
+    pub failed: Vec<(String, String)>,
+}
+
+/// Install the missing dependencies of `skill_id`, then theirs in turn.
+/// `only` limits the direct dependencies to the specs the user confirmed.
+/// A failed dependency is reported and does not stop the others.
+pub fn install_missing_dependencies(
+    paths: &AppPaths,
+    store: &SkillStore,
+    skill_id: &str,
+    only: Option<&[String]>,
+) -> Result<DependencyInstall> {
+    let mut report = DependencyInstall::default();
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut pending = vec![skill_id.to_string()];
+    while let Some(current) = pending.pop() {
+        for dep in missing_dependencies(store, &current)? {
+            let confirmed = match only {
+                Some(only) if current == skill_id => only.contains(&dep.spec),
+                _ => true,
+            };
+            if !confirmed || !seen.insert(dep.spec.clone()) {
+                continue;
+            }
+            // Something installed earlier in this run may satisfy it now.
+            let still_missing = missing_dependencies(store, &current)?
+                .iter()
+                .any(|d| d.spec == dep.spec);
+            if !still_missing {
+                continue;
+            }
+            // The user confirmed these specs, so only denylisted sources stop them.
+            let result = match dep.source {
+                Some(DependencySource::Clawhub { ref slug }) => {
+                    check_clawhub_source(store, slug, true)
+                        .and_then(|_| install_clawhub_skill(paths, store, slug, None, None))
+                }
+                Some(DependencySource::Git { ref url }) => check_git_source(store, url, true)
+                    .and_then(|_| install_git_skill(paths, store, url, None)),
+                None => continue,
+            };
+            match result {
+                Ok(result) => {
+                    pending.push(result.skill_id.clone());
+                    report.installed.push(result);
+                }
+                Err(err) => report.failed.push((dep.spec, format!("{:#}", err))),
+            }
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+#[path = "tests/skill_dependencies.rs"]
+mod tests;
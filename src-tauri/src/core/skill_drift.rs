@@ -0,0 +1,195 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+
+use super::content_hash::is_ignored;
+use super::cross_refs::has_cross_refs;
+use super::installer::local_target_skip_reason;
+use super::now_ms;
+use super::resource_types::ResourceType;
+use super::skill_store::SkillStore;
+
+/// Relative path (with `/` separators) to the SHA-256 of the file.
+pub type FileManifest = BTreeMap<String, String>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DriftChange {
+    Added,
+    Removed,
+    Modified,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct FileDrift {
+    pub path: String,
+    pub change: DriftChange,
+}
+
+/// A copy-mode target whose files differ from the central copy.
+#[derive(Clone, Debug, Serialize)]
+pub struct TargetDrift {
+    pub tool: String,
+    pub target_path: String,
+    /// Set when the target is a project target.
+    pub project_path: Option<String>,
+    pub files: Vec<FileDrift>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SkillDrift {
+    pub skill_id: String,
+    pub name: String,
+    /// False for skills installed before manifests were recorded; their
+    /// central edits cannot be told apart and `central` is empty.
+    pub has_baseline: bool,
+    /// Files edited in the central copy since it was installed or updated.
+    pub central: Vec<FileDrift>,
+    pub targets: Vec<TargetDrift>,
+}
+
+/// Hash every file under `dir`, skipping what content hashes skip.
+pub fn build_manifest(dir: &Path) -> Result<FileManifest> {
+    let mut manifest = FileManifest::new();
+    for entry in WalkDir::new(dir)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|entry| !is_ignored(entry))
+    {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(dir)
+            .with_context(|| format!("strip prefix {:?}", entry.path()))?;
+        let bytes =
+            std::fs::read(entry.path()).with_context(|| format!("read file {:?}", entry.path()))?;
+        manifest.insert(
+            relative.to_string_lossy().replace('\\', "/"),
+            hex::encode(Sha256::digest(&bytes)),
+        );
+    }
+    Ok(manifest)
+}
+
+/// Store `dir`'s manifest as the skill's baseline. Called when content
+/// arrives from the source; best effort, failures are logged.
+pub fn record_skill_manifest(store: &SkillStore, skill_id: &str, dir: &Path) {
+    let saved = build_manifest(dir).and_then(|manifest| {
+        let raw = serde_json::to_string(&manifest)?;
+        store.set_skill_manifest_json(skill_id, &raw, now_ms())
+    });
+    if let Err(err) = saved {
+        log::warn!("[skill_drift] manifest for {} failed: {:#}", skill_id, err);
+    }
+}
+
+/// Files in `current` that were added, removed or changed against `base`,
+/// sorted by path.
+pub fn diff_manifests(base: &FileManifest, current: &FileManifest) -> Vec<FileDrift> {
+    let mut drift: Vec<FileDrift> = current
+        .iter()
+        .filter_map(|(path, hash)| {
+            let change = match base.get(path) {
+                None => DriftChange::Added,
+                Some(old) if old != hash => DriftChange::Modified,
+                Some(_) => return None,
+            };
+            Some(FileDrift {
+                path: path.clone(),
+                change,
+            })
+        })
+        .chain(
+            base.keys()
+                .filter(|path| !current.contains_key(*path))
+                .map(|path| FileDrift {
+                    path: path.clone(),
+                    change: DriftChange::Removed,
+                }),
+        )
+        .collect();
+    drift.sort_by(|a, b| a.path.cmp(&b.path));
+    drift
+}
+
+/// Compare the central copy with its baseline, and each local copy-mode
+/// target with the central copy. Files with `{{skill:...}}` references are
+/// rewritten on copy, so their target copies are never reported.
+pub fn detect_skill_drift(store: &SkillStore, skill_id: &str) -> Result<SkillDrift> {
+    let skill = store
+        .get_skill_by_id(skill_id)?
+        .ok_or_else(|| anyhow::anyhow!("skill not found: {}", skill_id))?;
+    let central_path = Path::new(&skill.central_path);
+    let central = build_manifest(central_path)?;
+
+    let baseline: Option<FileManifest> = store
+        .get_skill_manifest_json(skill_id)?
+        .and_then(|raw| serde_json::from_str(&raw).ok());
+
+    let mut targets = Vec::new();
+    // Resource files are converted per tool, so only skill folders compare.
+    if ResourceType::parse(&store.get_skill_resource_type(skill_id)?) == ResourceType::Skill {
+        let local = store
+            .list_skill_targets(skill_id)?
+            .into_iter()
+            .filter(|target| target.mode == "copy")
+            .filter(|target| {
+                local_target_skip_reason(store, &target.tool)
+                    .ok()
+                    .flatten()
+                    .is_none()
+            })
+            .map(|target| (target.tool, target.target_path, None));
+        let project = store
+            .list_project_targets_for_skill(skill_id)?
+            .into_iter()
+            .filter(|target| target.mode == "copy")
+            .map(|target| (target.tool, target.target_path, Some(target.project_path)));
+        for (tool, target_path, project_path) in local.chain(project) {
+            let path = Path::new(&target_path);
+            if !path.is_dir() {
+                continue;
+            }
+            let files: Vec<FileDrift> = diff_manifests(&central, &build_manifest(path)?)
+                .into_iter()
+                .filter(|file| {
+                    file.change != DriftChange::Modified
+                        || !rewritten_on_copy(&central_path.join(&file.path))
+                })
+                .collect();
+            if !files.is_empty() {
+                targets.push(TargetDrift {
+                    tool,
+                    target_path,
+                    project_path,
+                    files,
+                });
+            }
+        }
+    }
+
+    Ok(SkillDrift {
+        skill_id: skill.id,
+        name: skill.name,
+        has_baseline: baseline.is_some(),
+        central: baseline
+            .map(|base| diff_manifests(&base, &central))
+            .unwrap_or_default(),
+        targets,
+    })
+}
+
+fn rewritten_on_copy(central_file: &Path) -> bool {
+    std::fs::read_to_string(central_file).is_ok_and(|text| has_cross_refs(&text))
+}
+
+#[cfg(test)]
+#[path = "tests/skill_drift.rs"]
+mod tests;
@@ -0,0 +1,146 @@
+use std::sync::{Mutex, OnceLock};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use super::skill_store::SkillTargetRecord;
+
+pub const EVENT_SKILL_INSTALLED: &str = "skill:installed";
+pub const EVENT_SKILL_UPDATED: &str = "skill:updated";
+pub const EVENT_SKILL_SYNCED: &str = "skill:synced";
+pub const EVENT_SKILL_DELETED: &str = "skill:deleted";
+pub const EVENT_TARGET_BROKEN: &str = "target:broken";
+pub const EVENT_REMOTE_SYNC_FINISHED: &str = "remote:sync-finished";
+
+/// Payload of `skill:installed`, `skill:updated` and `skill:deleted`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SkillChanged {
+    pub skill_id: String,
+    pub name: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SkillSynced {
+    pub skill_id: String,
+    pub tool: String,
+    pub target_path: String,
+    pub mode: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TargetBroken {
+    pub skill_id: String,
+    pub tool: String,
+    pub target_path: String,
+    /// A `target_verify` state such as `missing` or `dangling`.
+    pub state: String,
+    pub detail: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct RemoteSyncFinished {
+    pub host_id: String,
+    /// Names of the skills that reached the host.
+    pub synced: Vec<String>,
+    pub error: Option<String>,
+}
+
+type Sink = Box<dyn Fn(&str, serde_json::Value) + Send + Sync>;
+
+static SINK: OnceLock<Mutex<Option<Sink>>> = OnceLock::new();
+
+fn sink_cell() -> &'static Mutex<Option<Sink>> {
+    SINK.get_or_init(|| Mutex::new(None))
+}
+
+/// Send lifecycle events to the windows of `app`. Until this is called
+/// (and in the headless CLI) they go nowhere.
+pub fn attach(app: AppHandle) {
+    set_sink(Some(Box::new(move |event, payload| {
+        if let Err(err) = app.emit(event, payload) {
+            log::warn!("failed to emit {}: {}", event, err);
+        }
+    })));
+}
+
+fn set_sink(sink: Option<Sink>) {
+    if let Ok(mut cell) = sink_cell().lock() {
+        *cell = sink;
+    }
+}
+
+fn emit<S: Serialize>(event: &str, payload: &S) {
+    let Ok(cell) = sink_cell().lock() else {
+        return;
+    };
+    let Some(sink) = cell.as_ref() else {
+        return;
+    };
+    match serde_json::to_value(payload) {
+        Ok(value) => sink(event, value),
+        Err(err) => log::warn!("failed to serialize {}: {}", event, err),
+    }
+}
+
+fn skill_changed(event: &str, skill_id: &str, name: &str) {
+    emit(
+        event,
+        &SkillChanged {
+            skill_id: skill_id.to_string(),
+            name: name.to_string(),
+        },
+    );
+}
+
+pub fn skill_installed(skill_id: &str, name: &str) {
+    skill_changed(EVENT_SKILL_INSTALLED, skill_id, name);
+}
+
+pub fn skill_updated(skill_id: &str, name: &str) {
+    skill_changed(EVENT_SKILL_UPDATED, skill_id, name);
+}
+
+pub fn skill_deleted(skill_id: &str, name: &str) {
+    skill_changed(EVENT_SKILL_DELETED, skill_id, name);
+}
+
+/// `target` was just written for its skill.
+pub fn skill_synced(target: &SkillTargetRecord) {
+    emit(
+        EVENT_SKILL_SYNCED,
+        &SkillSynced {
+            skill_id: target.skill_id.clone(),
+            tool: target.tool.clone(),
+            target_path: target.target_path.clone(),
+            mode: target.mode.clone(),
+        },
+    );
+}
+
+pub fn target_broken(target: &SkillTargetRecord, state: &str, detail: Option<&str>) {
+    emit(
+        EVENT_TARGET_BROKEN,
+        &TargetBroken {
+            skill_id: target.skill_id.clone(),
+            tool: target.tool.clone(),
+            target_path: target.target_path.clone(),
+            state: state.to_string(),
+            detail: detail.map(str::to_string),
+        },
+    );
+}
+
+pub fn remote_sync_finished(host_id: &str, synced: &[String], error: Option<&str>) {
+    emit(
+        EVENT_REMOTE_SYNC_FINISHED,
+        &RemoteSyncFinished {
+            host_id: host_id.to_string(),
+            synced: synced.to_vec(),
+            error: error.map(str::to_string),
+        },
+    );
+}
+
+#[cfg(test)]
+#[path = "tests/skill_events.rs"]
+mod tests;
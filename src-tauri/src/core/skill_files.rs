@@ -0,0 +1,137 @@
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use walkdir::WalkDir;
+
+use super::content_hash::is_ignored;
+use super::skill_authoring::resolve_skill_file;
+use super::skill_store::SkillStore;
+
+/// Files larger than this are cut off; previews only need the start.
+pub const MAX_PREVIEW_BYTES: u64 = 512 * 1024;
+/// A NUL byte in this much of the start marks a file as binary.
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+/// Keeps the tree usable when a skill vendors a large folder.
+const MAX_LISTED_ENTRIES: usize = 2000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SkillFileEntry {
+    /// Relative to the skill folder, `/`-separated.
+    pub path: String,
+    pub is_dir: bool,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillFileContent {
+    pub path: String,
+    pub size: u64,
+    /// `None` for binary files.
+    pub content: Option<String>,
+    pub binary: bool,
+    pub truncated: bool,
+}
+
+fn skill_dir(store: &SkillStore, skill_id: &str) -> Result<PathBuf> {
+    let skill = store
+        .get_skill_by_id(skill_id)?
+        .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+    Ok(PathBuf::from(skill.central_path))
+}
+
+pub fn list_skill_files(store: &SkillStore, skill_id: &str) -> Result<Vec<SkillFileEntry>> {
+    list_dir(&skill_dir(store, skill_id)?)
+}
+
+pub fn read_skill_file(
+    store: &SkillStore,
+    skill_id: &str,
+    relative_path: &str,
+) -> Result<SkillFileContent> {
+    let dir = skill_dir(store, skill_id)?;
+    let path = resolve_skill_file(&dir, relative_path)?;
+    read_preview(&path, relative_path)
+}
+
+/// Everything under `dir` in path order, directories included. Links are
+/// listed but not followed.
+fn list_dir(dir: &Path) -> Result<Vec<SkillFileEntry>> {
+    if !dir.is_dir() {
+        anyhow::bail!("skill folder not found: {:?}", dir);
+    }
+    let mut entries = Vec::new();
+    for entry in WalkDir::new(dir)
+        .follow_links(false)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| !is_ignored(e))
+    {
+        let entry = entry.with_context(|| format!("list {:?}", dir))?;
+        if entries.len() == MAX_LISTED_ENTRIES {
+            log::warn!(
+                "[skill_files] {:?} has more than {} entries",
+                dir,
+                MAX_LISTED_ENTRIES
+            );
+            break;
+        }
+        let relative = entry.path().strip_prefix(dir).unwrap_or(entry.path());
+        let path = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let is_dir = entry.file_type().is_dir();
+        let size = if is_dir {
+            0
+        } else {
+            entry.metadata().map(|m| m.len()).unwrap_or(0)
+        };
+        entries.push(SkillFileEntry { path, is_dir, size });
+    }
+    Ok(entries)
+}
+
+fn read_preview(path: &Path, relative_path: &str) -> Result<SkillFileContent> {
+    let file = std::fs::File::open(path).with_context(|| format!("open {:?}", path))?;
+    let size = file
+        .metadata()
+        .with_context(|| format!("stat {:?}", path))?
+        .len();
+    let mut bytes = Vec::new();
+    file.take(MAX_PREVIEW_BYTES)
+        .read_to_end(&mut bytes)
+        .with_context(|| format!("read {:?}", path))?;
+    let truncated = size > bytes.len() as u64;
+    let content = decode_text(&bytes, truncated);
+    Ok(SkillFileContent {
+        path: relative_path.to_string(),
+        size,
+        binary: content.is_none(),
+        content,
+        truncated,
+    })
+}
+
+/// UTF-8 text, or `None` for binary data. A character split by the size
+/// cap is dropped rather than counted as invalid.
+fn decode_text(bytes: &[u8], truncated: bool) -> Option<String> {
+    let sniff = &bytes[..bytes.len().min(BINARY_SNIFF_BYTES)];
+    if sniff.contains(&0) {
+        return None;
+    }
+    match std::str::from_utf8(bytes) {
+        Ok(text) => Some(text.to_string()),
+        Err(err) if truncated && err.error_len().is_none() => {
+            Some(String::from_utf8_lossy(&bytes[..err.valid_up_to()]).into_owned())
+        }
+        Err(_) => None,
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/skill_files.rs"]
+mod tests;
@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::installer::is_frontmatter_delimiter;
+use super::skill_store::SkillStore;
+use super::skill_validation::frontmatter_fields;
+use super::tool_adapters::adapter_by_key;
+
+pub const TOOL_FORMATS_KEY: &str = "tool_formats";
+
+/// The layout a tool expects a synced skill in. Anything but `Skill` is
+/// produced by converting a copy, so such tools never get a symlink.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkillFormat {
+    /// `SKILL.md` with `name` / `description` frontmatter, as authored.
+    #[default]
+    Skill,
+    /// A Cursor folder rule: `RULE.md` with `description` / `alwaysApply`.
+    CursorRule,
+    /// A Codex custom prompt: `PROMPT.md` with a `description`.
+    CodexPrompt,
+}
+
+#[derive(Default)]
+struct FormatTables {
+    /// Chosen by the user in settings.
+    chosen: HashMap<String, SkillFormat>,
+    /// Declared by adapters in the adapter config file.
+    declared: HashMap<String, SkillFormat>,
+}
+
+static FORMATS: OnceLock<Mutex<FormatTables>> = OnceLock::new();
+
+fn tables() -> std::sync::MutexGuard<'static, FormatTables> {
+    FORMATS
+        .get_or_init(|| Mutex::new(FormatTables::default()))
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+}
+
+/// What `tool_key` gets synced as: the user's choice, else what its adapter
+/// declares, else a plain skill.
+pub fn tool_format(tool_key: &str) -> SkillFormat {
+    let tables = tables();
+    tables
+        .chosen
+        .get(tool_key)
+        .or_else(|| tables.declared.get(tool_key))
+        .copied()
+        .unwrap_or_default()
+}
+
+pub fn get_tool_formats(store: &SkillStore) -> HashMap<String, SkillFormat> {
+    store
+        .get_setting(TOOL_FORMATS_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+/// Choose the format `tool_key` is synced as; `None` goes back to the
+/// adapter's default. Existing targets change on their next sync.
+pub fn set_tool_format(
+    store: &SkillStore,
+    tool_key: &str,
+    format: Option<SkillFormat>,
+) -> Result<HashMap<String, SkillFormat>> {
+    if adapter_by_key(tool_key).is_none() {
+        anyhow::bail!("unknown tool: {}", tool_key);
+    }
+    let mut formats = get_tool_formats(store);
+    match format {
+        Some(format) => formats.insert(tool_key.to_string(), format),
+        None => formats.remove(tool_key),
+    };
+    let raw = serde_json::to_string(&formats).context("serialize tool formats")?;
+    store.set_setting(TOOL_FORMATS_KEY, &raw)?;
+    tables().chosen = formats.clone();
+    Ok(formats)
+}
+
+/// Load the user's choices into the table sync uses.
+pub fn load_tool_formats(store: &SkillStore) {
+    tables().chosen = get_tool_formats(store);
+}
+
+/// Formats declared by configured adapters; replaces earlier declarations.
+pub fn set_declared_formats(declared: HashMap<String, SkillFormat>) {
+    tables().declared = declared;
+}
+
+/// Rewrite the skill copied to `dir` into `format`. `Skill` leaves it as is.
+pub fn convert_skill_dir(format: SkillFormat, dir: &Path) -> Result<()> {
+    let (file_name, frontmatter): (&str, fn(&Frontmatter) -> Vec<String>) = match format {
+        SkillFormat::Skill => return Ok(()),
+        SkillFormat::CursorRule => ("RULE.md", |fm| {
+            vec![
+                format!("description: {}", yaml_string(&fm.description())),
+                "alwaysApply: false".to_string(),
+            ]
+        }),
+        SkillFormat::CodexPrompt => ("PROMPT.md", |fm| {
+            vec![format!("description: {}", yaml_string(&fm.description()))]
+        }),
+    };
+    let skill_md = dir.join("SKILL.md");
+    let text =
+        std::fs::read_to_string(&skill_md).with_context(|| format!("read {:?}", skill_md))?;
+    let (fm, body) = split_frontmatter(&text);
+
+    let mut out = String::from("---\n");
+    for line in frontmatter(&fm) {
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out.push_str("---\n");
+    out.push_str(body.trim_start_matches(['\r', '\n']));
+    let converted = dir.join(file_name);
+    std::fs::write(&converted, out).with_context(|| format!("write {:?}", converted))?;
+    std::fs::remove_file(&skill_md).with_context(|| format!("remove {:?}", skill_md))?;
+    Ok(())
+}
+
+#[derive(Default)]
+struct Frontmatter {
+    name: Option<String>,
+    description: Option<String>,
+}
+
+impl Frontmatter {
+    /// Tools show this to decide when to apply the skill; fall back to the
+    /// name so it is never empty.
+    fn description(&self) -> String {
+        self.description
+            .clone()
+            .or_else(|| self.name.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// `name` / `description` from a leading `---` block, and the rest of the file.
+fn split_frontmatter(text: &str) -> (Frontmatter, &str) {
+    let mut fm = Frontmatter::default();
+    let Some(fields) = frontmatter_fields(text) else {
+        return (fm, text);
+    };
+    for (_, key, value) in fields {
+        match key.as_str() {
+            "name" => fm.name = Some(value),
+            "description" => fm.description = Some(value),
+            _ => {}
+        }
+    }
+    // The block is closed, so the body starts after the second delimiter.
+    let mut offset = 0;
+    for (index, line) in text.split_inclusive('\n').enumerate() {
+        offset += line.len();
+        if index > 0 && is_frontmatter_delimiter(line) {
+            break;
+        }
+    }
+    (fm, &text[offset..])
+}
+
+fn yaml_string(value: &str) -> String {
+    format!(
+        "\"{}\"",
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    )
+}
+
+#[cfg(test)]
+#[path = "tests/skill_format.rs"]
+mod tests;
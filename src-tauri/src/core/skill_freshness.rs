@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use reqwest::blocking::Client;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use super::github_search::{github_get, GITHUB_API_URL};
+use super::http_client::http_client;
+use super::installer::{derive_group_name_from_clone_url, parse_github_url};
+use super::now_ms;
+use super::skill_store::{SkillRecord, SkillStore};
+use super::source_providers::{provider_for_url, GITHUB_PROVIDER_ID};
+
+pub const SKILL_FRESHNESS_KEY: &str = "skill_freshness";
+pub const EVENT_FRESHNESS_UPDATED: &str = "freshness://updated";
+
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+/// No upstream commit for this long and a skill counts as stale / abandoned.
+const STALE_AFTER_DAYS: i64 = 180;
+const ABANDONED_AFTER_DAYS: i64 = 365;
+/// A skill's upstream is looked up again at most once per this period.
+const RECHECK_AFTER_MS: i64 = DAY_MS;
+const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FreshnessLevel {
+    Active,
+    Stale,
+    Abandoned,
+    Unknown,
+}
+
+/// Upstream activity for one git-sourced skill, for the freshness badge.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SkillFreshness {
+    pub skill_id: String,
+    pub name: String,
+    /// Date of the newest upstream commit touching the skill (ms).
+    pub last_commit_at: Option<i64>,
+    pub checked_at: i64,
+    pub level: FreshnessLevel,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+struct FreshnessRecord {
+    last_commit_at: Option<i64>,
+    checked_at: i64,
+    error: Option<String>,
+}
+
+pub fn freshness_level(last_commit_at: Option<i64>, now: i64) -> FreshnessLevel {
+    let Some(last) = last_commit_at else {
+        return FreshnessLevel::Unknown;
+    };
+    let idle_days = (now - last) / DAY_MS;
+    if idle_days >= ABANDONED_AFTER_DAYS {
+        FreshnessLevel::Abandoned
+    } else if idle_days >= STALE_AFTER_DAYS {
+        FreshnessLevel::Stale
+    } else {
+        FreshnessLevel::Active
+    }
+}
+
+/// Last recorded upstream activity of every git-sourced skill; skills not
+/// checked yet are left out.
+pub fn list_skill_freshness(store: &SkillStore) -> Result<Vec<SkillFreshness>> {
+    let records = read_records(store);
+    let now = now_ms();
+    Ok(git_skills(store)?
+        .into_iter()
+        .filter_map(|skill| {
+            let record = records.get(&skill.id)?;
+            Some(to_freshness(&skill, record, now))
+        })
+        .collect())
+}
+
+/// Look up the upstream of skills not checked within the last day (all of
+/// them with `force`) and store the results.
+pub fn refresh_skill_freshness(store: &SkillStore, force: bool) -> Result<Vec<SkillFreshness>> {
+    refresh_inner(store, GITHUB_API_URL, force)
+}
+
+fn refresh_inner(store: &SkillStore, api_url: &str, force: bool) -> Result<Vec<SkillFreshness>> {
+    let client = http_client()?;
+    let skills = git_skills(store)?;
+    let mut records = read_records(store);
+    records.retain(|id, _| skills.iter().any(|s| &s.id == id));
+
+    let now = now_ms();
+    for skill in &skills {
+        let due = records
+            .get(&skill.id)
+            .map_or(true, |r| now - r.checked_at >= RECHECK_AFTER_MS);
+        if !force && !due {
+            continue;
+        }
+        let source_ref = skill.source_ref.as_deref().unwrap_or_default();
+        let record = match last_commit_at(&client, api_url, source_ref) {
+            Ok(last) => FreshnessRecord {
+                last_commit_at: Some(last),
+                checked_at: now,
+                error: None,
+            },
+            // Keep the last known date so one failed lookup does not hide it.
+            Err(err) => FreshnessRecord {
+                last_commit_at: records.get(&skill.id).and_then(|r| r.last_commit_at),
+                checked_at: now,
+                error: Some(format!("{:#}", err)),
+            },
+        };
+        records.insert(skill.id.clone(), record);
+    }
+
+    let raw = serde_json::to_string(&records).context("serialize skill freshness")?;
+    store.set_setting(SKILL_FRESHNESS_KEY, &raw)?;
+    Ok(skills
+        .iter()
+        .filter_map(|skill| Some(to_freshness(skill, records.get(&skill.id)?, now)))
+        .collect())
+}
+
+#[derive(Debug, Deserialize)]
+struct CommitItem {
+    commit: CommitInfo,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommitInfo {
+    committer: CommitSignature,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommitSignature {
+    date: String,
+}
+
+/// Newest commit on the skill's branch and subpath, via one GitHub API call.
+/// `api_url` is used for github.com; Enterprise hosts use their own API.
+fn last_commit_at(client: &Client, api_url: &str, source_ref: &str) -> Result<i64> {
+    let parsed = parse_github_url(source_ref);
+    let repo = derive_group_name_from_clone_url(&parsed.clone_url).ok_or_else(|| {
+        anyhow::anyhow!("upstream activity is only tracked for GitHub repositories")
+    })?;
+    let api_url = match provider_for_url(&parsed.clone_url) {
+        Some((provider, _)) if provider.id != GITHUB_PROVIDER_ID => provider.api_url,
+        _ => api_url.to_string(),
+    };
+    let mut url = format!(
+        "{}/repos/{}/commits?per_page=1",
+        api_url.trim_end_matches('/'),
+        repo
+    );
+    if let Some(branch) = &parsed.branch {
+        url.push_str(&format!("&sha={}", urlencoding::encode(branch)));
+    }
+    if let Some(subpath) = &parsed.subpath {
+        url.push_str(&format!("&path={}", urlencoding::encode(subpath)));
+    }
+
+    let commits: Vec<CommitItem> = github_get(client, &url, "GitHub commits")?
+        .json()
+        .context("parse GitHub commits response")?;
+    let commit = commits
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("no upstream commits found"))?;
+    parse_github_time(&commit.commit.committer.date)
+        .ok_or_else(|| anyhow::anyhow!("invalid commit date: {}", commit.commit.committer.date))
+}
+
+/// `2024-05-01T12:30:00Z` -> unix ms. GitHub always reports UTC.
+pub fn parse_github_time(raw: &str) -> Option<i64> {
+    let raw = raw.strip_suffix('Z')?;
+    let (date, time) = raw.split_once('T')?;
+    let mut date = date.splitn(3, '-').map(|p| p.parse::<i64>().ok());
+    let (year, month, day) = (date.next()??, date.next()??, date.next()??);
+    let mut time = time.splitn(3, ':').map(|p| p.parse::<i64>().ok());
+    let (hour, minute, second) = (time.next()??, time.next()??, time.next()??);
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    // Days since the epoch for a proleptic Gregorian date.
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+    Some(((days * 24 + hour) * 60 + minute) * 60_000 + second * 1000)
+}
+
+/// Check upstream activity in the background and emit `freshness://updated`
+/// when something changed.
+pub fn spawn_freshness_checker(app: AppHandle, store: SkillStore) {
+    let spawned = std::thread::Builder::new()
+        .name("skill-freshness".to_string())
+        .spawn(move || loop {
+            let before = list_skill_freshness(&store).unwrap_or_default();
+            match refresh_skill_freshness(&store, false) {
+                Ok(after) if !same_activity(&before, &after) => {
+                    if let Err(err) = app.emit(EVENT_FRESHNESS_UPDATED, &after) {
+                        log::warn!("failed to emit {}: {}", EVENT_FRESHNESS_UPDATED, err);
+                    }
+                }
+                Ok(_) => {}
+                Err(err) => log::warn!("skill freshness check failed: {:#}", err),
+            }
+            std::thread::sleep(CHECK_INTERVAL);
+        });
+    if let Err(err) = spawned {
+        log::warn!("failed to start skill freshness checker: {}", err);
+    }
+}
+
+fn same_activity(before: &[SkillFreshness], after: &[SkillFreshness]) -> bool {
+    before.len() == after.len()
+        && before.iter().zip(after).all(|(a, b)| {
+            a.skill_id == b.skill_id && a.last_commit_at == b.last_commit_at && a.level == b.level
+        })
+}
+
+fn git_skills(store: &SkillStore) -> Result<Vec<SkillRecord>> {
+    Ok(store
+        .list_skills()?
+        .into_iter()
+        .filter(|s| s.source_type == "git" || s.source_type == "git-cloned")
+        .filter(|s| s.source_ref.is_some())
+        .collect())
+}
+
+fn to_freshness(skill: &SkillRecord, record: &FreshnessRecord, now: i64) -> SkillFreshness {
+    SkillFreshness {
+        skill_id: skill.id.clone(),
+        name: skill.name.clone(),
+        last_commit_at: record.last_commit_at,
+        checked_at: record.checked_at,
+        level: freshness_level(record.last_commit_at, now),
+        error: record.error.clone(),
+    }
+}
+
+fn read_records(store: &SkillStore) -> HashMap<String, FreshnessRecord> {
+    store
+        .get_setting(SKILL_FRESHNESS_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+#[path = "tests/skill_freshness.rs"]
+mod tests;
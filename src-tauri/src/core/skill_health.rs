@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use super::content_hash::hash_dir;
+use super::installer::{check_skill_updates, local_target_skip_reason, SkillUpdateStatus};
+use super::resource_types::ResourceType;
+use super::skill_drift::detect_skill_drift;
+use super::skill_store::{SkillRecord, SkillStore};
+use super::storage_report::dir_usage;
+use super::target_verify::{check_target, link_source, TargetState};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TargetHealth {
+    Ok,
+    /// Missing, a dangling link, or replaced by something else.
+    Broken,
+    /// A copy made from older content than the central copy holds now.
+    Stale,
+    /// Remote targets and tools that are not installed are not checked.
+    Skipped,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TargetHealthStatus {
+    pub tool: String,
+    pub target_path: String,
+    pub mode: String,
+    pub state: TargetHealth,
+    pub detail: Option<String>,
+}
+
+/// Everything the health dashboard shows for one skill.
+#[derive(Clone, Debug, Serialize)]
+pub struct SkillHealth {
+    pub skill_id: String,
+    pub name: String,
+    pub source_type: String,
+    pub targets: Vec<TargetHealthStatus>,
+    /// `None` for sources without updates, or when sources were not checked.
+    pub has_update: Option<bool>,
+    /// Whether the git repo, registry or local folder the skill came from
+    /// answered; `None` when not checked.
+    pub source_reachable: Option<bool>,
+    pub source_error: Option<String>,
+    /// The central copy was edited since it was installed or updated.
+    pub central_drifted: bool,
+    /// Tools whose copies no longer match the central copy.
+    pub drifted_targets: Vec<String>,
+    pub bytes: u64,
+    pub files: u64,
+}
+
+/// Health of every managed skill. `check_sources` asks each git repo and
+/// registry for its latest version, which needs the network.
+pub fn skills_health(store: &SkillStore, check_sources: bool) -> Result<Vec<SkillHealth>> {
+    let mut updates: HashMap<String, SkillUpdateStatus> = if check_sources {
+        check_skill_updates(store)
+            .into_iter()
+            .map(|status| (status.skill_id.clone(), status))
+            .collect()
+    } else {
+        HashMap::new()
+    };
+
+    let mut report = Vec::new();
+    for skill in store.list_skills()? {
+        let update = updates.remove(&skill.id);
+        report.push(skill_health(store, skill, update, check_sources)?);
+    }
+    Ok(report)
+}
+
+fn skill_health(
+    store: &SkillStore,
+    skill: SkillRecord,
+    update: Option<SkillUpdateStatus>,
+    check_sources: bool,
+) -> Result<SkillHealth> {
+    let central_path = PathBuf::from(&skill.central_path);
+    let kind = ResourceType::parse(&store.get_skill_resource_type(&skill.id)?);
+    let link_source = link_source(kind, &central_path);
+    let current_hash = hash_dir(&central_path).ok().or(skill.content_hash.clone());
+
+    let mut targets = Vec::new();
+    for target in store.list_skill_targets(&skill.id)? {
+        let (state, detail) = match local_target_skip_reason(store, &target.tool)? {
+            Some(reason) => (TargetHealth::Skipped, Some(reason.to_string())),
+            None => {
+                let (state, detail) = check_target(&target, &link_source, current_hash.as_deref());
+                let health = match state {
+                    TargetState::Ok => TargetHealth::Ok,
+                    TargetState::Stale => TargetHealth::Stale,
+                    TargetState::Missing | TargetState::Dangling | TargetState::Replaced => {
+                        TargetHealth::Broken
+                    }
+                };
+                (health, detail)
+            }
+        };
+        targets.push(TargetHealthStatus {
+            tool: target.tool,
+            target_path: target.target_path,
+            mode: target.mode,
+            state,
+            detail,
+        });
+    }
+
+    let (has_update, source_reachable, source_error) = match update {
+        Some(update) => (
+            Some(update.has_update),
+            Some(update.error.is_none()),
+            update.error,
+        ),
+        None if check_sources && skill.source_type == "local" => {
+            let reachable = skill
+                .source_ref
+                .as_deref()
+                .is_some_and(|source| Path::new(source).exists());
+            let error = (!reachable).then(|| "source folder not found".to_string());
+            (None, Some(reachable), error)
+        }
+        None => (None, None, None),
+    };
+
+    // A missing central copy already shows as broken targets.
+    let drift = detect_skill_drift(store, &skill.id).ok();
+    let usage = dir_usage(&central_path);
+    Ok(SkillHealth {
+        skill_id: skill.id,
+        name: skill.name,
+        source_type: skill.source_type,
+        targets,
+        has_update,
+        source_reachable,
+        source_error,
+        central_drifted: drift.as_ref().is_some_and(|d| !d.central.is_empty()),
+        drifted_targets: drift
+            .map(|d| d.targets.into_iter().map(|t| t.tool).collect())
+            .unwrap_or_default(),
+        bytes: usage.bytes,
+        files: usage.files,
+    })
+}
+
+#[cfg(test)]
+#[path = "tests/skill_health.rs"]
+mod tests;
@@ -0,0 +1,151 @@
+use std::path::{Component, Path};
+
+use super::watch_settings::{parts_match, segment_matches};
+
+/// Per-skill exclude list, read from the skill's root folder.
+pub const SKILLIGNORE_FILE: &str = ".skillignore";
+
+/// Never copied into tool directories or uploaded, whatever `.skillignore` says.
+const BUILTIN_EXCLUDES: [&str; 3] = [".git", "node_modules", ".DS_Store"];
+
+/// What to leave out when a skill is copied or uploaded: the built-in
+/// excludes plus the rules of the skill's `.skillignore`.
+///
+/// `.skillignore` uses a subset of the gitignore syntax: one pattern per
+/// line, `#` comments, `!` to re-include, a trailing `/` for folders only and
+/// a leading or inner `/` to anchor the pattern at the skill root. `*` and
+/// `?` match within a name, `**` matches any number of folders. A pattern
+/// without a slash matches a name at any depth.
+#[derive(Clone, Debug, Default)]
+pub struct SkillIgnore {
+    builtin: bool,
+    rules: Vec<Rule>,
+}
+
+#[derive(Clone, Debug)]
+struct Rule {
+    segments: Vec<String>,
+    anchored: bool,
+    dir_only: bool,
+    negate: bool,
+}
+
+impl SkillIgnore {
+    /// Built-in excludes plus `root/.skillignore` when it exists.
+    pub fn load(root: &Path) -> Self {
+        let text = std::fs::read_to_string(root.join(SKILLIGNORE_FILE)).unwrap_or_default();
+        Self::parse(&text)
+    }
+
+    /// Built-in excludes plus the rules in `text`.
+    pub fn parse(text: &str) -> Self {
+        let rules = text.lines().filter_map(Rule::parse).collect();
+        Self {
+            builtin: true,
+            rules,
+        }
+    }
+
+    /// Whether `.skillignore` rules (not just the built-ins) apply.
+    pub fn has_rules(&self) -> bool {
+        !self.rules.is_empty()
+    }
+
+    /// Whether `relative` (a path inside the skill) is left out. Children of
+    /// an excluded folder are not checked separately, so walkers should
+    /// prune excluded folders rather than test every entry.
+    pub fn is_excluded(&self, relative: &Path, is_dir: bool) -> bool {
+        let parts: Vec<String> = relative
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(name) => Some(name.to_string_lossy().to_string()),
+                _ => None,
+            })
+            .collect();
+        let Some(name) = parts.last() else {
+            return false;
+        };
+        if self.builtin && BUILTIN_EXCLUDES.contains(&name.as_str()) {
+            return true;
+        }
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.matches(&parts, is_dir))
+            .is_some_and(|rule| !rule.negate)
+    }
+
+    /// Whether a `.skillignore` rule leaves out anything under `root`. A
+    /// link would expose those files, so such skills are synced as copies.
+    pub fn excludes_any(&self, root: &Path) -> bool {
+        if !self.has_rules() {
+            return false;
+        }
+        let rules_only = Self {
+            builtin: false,
+            rules: self.rules.clone(),
+        };
+        walkdir::WalkDir::new(root)
+            .follow_links(false)
+            .min_depth(1)
+            .into_iter()
+            .filter_entry(|e| !BUILTIN_EXCLUDES.contains(&e.file_name().to_string_lossy().as_ref()))
+            .filter_map(|e| e.ok())
+            .any(|e| {
+                e.path()
+                    .strip_prefix(root)
+                    .is_ok_and(|relative| rules_only.is_excluded(relative, e.file_type().is_dir()))
+            })
+    }
+}
+
+impl Rule {
+    fn parse(line: &str) -> Option<Self> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let (negate, line) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let (dir_only, line) = match line.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let anchored = line.contains('/');
+        let segments: Vec<String> = line
+            .trim_start_matches('/')
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect();
+        if segments.is_empty() {
+            return None;
+        }
+        Some(Self {
+            segments,
+            anchored,
+            dir_only,
+            negate,
+        })
+    }
+
+    fn matches(&self, parts: &[String], is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+        if self.anchored {
+            let pattern: Vec<&str> = self.segments.iter().map(String::as_str).collect();
+            let parts: Vec<&str> = parts.iter().map(String::as_str).collect();
+            return parts_match(&pattern, &parts);
+        }
+        parts.last().is_some_and(|name| {
+            self.segments.len() == 1 && segment_matches(&self.segments[0], name)
+        })
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/skill_ignore.rs"]
+mod tests;
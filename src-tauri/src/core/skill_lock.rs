@@ -0,0 +1,35 @@
+use anyhow::Result;
+
+use super::errors::{coded_with, ErrorCode};
+use super::skill_store::{SkillRecord, SkillStore};
+
+/// Lock or unlock a skill. A locked skill keeps its central copy as is:
+/// updating it from its source needs `force`, update-all skips it, and
+/// in-app edits are refused.
+pub fn set_skill_locked(store: &SkillStore, skill_id: &str, locked: bool) -> Result<bool> {
+    if store.get_skill_by_id(skill_id)?.is_none() {
+        anyhow::bail!("skill not found");
+    }
+    store.set_skill_locked(skill_id, locked)?;
+    Ok(locked)
+}
+
+pub fn is_skill_locked(store: &SkillStore, skill_id: &str) -> bool {
+    store.get_skill_locked(skill_id).unwrap_or(false)
+}
+
+/// Fails with [`ErrorCode::SkillLocked`] when `skill` is locked.
+pub fn ensure_unlocked(store: &SkillStore, skill: &SkillRecord) -> Result<()> {
+    if !is_skill_locked(store, &skill.id) {
+        return Ok(());
+    }
+    Err(coded_with(
+        ErrorCode::SkillLocked,
+        format!("{} is locked", skill.name),
+        serde_json::json!({ "skill_id": skill.id, "name": skill.name }),
+    ))
+}
+
+#[cfg(test)]
+#[path = "tests/skill_lock.rs"]
+mod tests;
@@ -0,0 +1,110 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::skill_dependencies::record_dependencies;
+use super::skill_store::SkillStore;
+use super::skill_validation::frontmatter_fields;
+
+/// What a skill says about itself in its SKILL.md frontmatter.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SkillMetadata {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub version: Option<String>,
+    pub tags: Vec<String>,
+    /// Other skills this one needs, as ClawHub slugs or git URLs (see
+    /// `skill_dependencies`).
+    pub dependencies: Vec<String>,
+}
+
+/// Parse `<dir>/SKILL.md`. `None` when there is no readable frontmatter.
+pub fn read_skill_metadata(dir: &Path) -> Option<SkillMetadata> {
+    let text = std::fs::read_to_string(dir.join("SKILL.md")).ok()?;
+    parse_metadata(&text)
+}
+
+fn parse_metadata(text: &str) -> Option<SkillMetadata> {
+    let fields = frontmatter_fields(text)?;
+    let field = |key: &str| {
+        fields
+            .iter()
+            .find(|(_, k, _)| k == key)
+            .map(|(_, _, value)| value.as_str())
+            .filter(|value| !value.is_empty())
+    };
+    // The Agent Skills spec keeps extra keys under `metadata:`.
+    let nested = |key: &str| {
+        field("metadata")?.lines().find_map(|line| {
+            let (k, v) = line.split_once(':')?;
+            let v = v.trim().trim_matches(['"', '\'']);
+            (k.trim() == key && !v.is_empty()).then(|| v.to_string())
+        })
+    };
+    Some(SkillMetadata {
+        name: field("name").map(str::to_string),
+        description: field("description").map(str::to_string),
+        version: field("version")
+            .map(str::to_string)
+            .or_else(|| nested("version")),
+        tags: field("tags").map(parse_list).unwrap_or_default(),
+        dependencies: field("dependencies").map(parse_list).unwrap_or_default(),
+    })
+}
+
+/// `[a, b]`, `a, b` or a `- a` list.
+fn parse_list(value: &str) -> Vec<String> {
+    let inner = value
+        .strip_prefix('[')
+        .and_then(|v| v.strip_suffix(']'))
+        .unwrap_or(value);
+    inner
+        .split([',', '\n'])
+        .map(|item| {
+            item.trim()
+                .trim_start_matches("- ")
+                .trim()
+                .trim_matches(['"', '\''])
+                .to_string()
+        })
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// Re-read a skill's frontmatter after its content changed and store it,
+/// along with its dependency edges. Best effort: failures are logged, never
+/// raised.
+pub fn refresh_skill_metadata(store: &SkillStore, skill_id: &str, dir: &Path) {
+    let meta = read_skill_metadata(dir);
+    let raw = meta
+        .as_ref()
+        .and_then(|meta| serde_json::to_string(meta).ok());
+    if let Err(err) = store.set_skill_metadata_json(skill_id, raw.as_deref()) {
+        log::warn!("[skill_metadata] save for {} failed: {:#}", skill_id, err);
+    }
+    let specs = meta.map(|meta| meta.dependencies).unwrap_or_default();
+    if let Err(err) = record_dependencies(store, skill_id, &specs) {
+        log::warn!(
+            "[skill_metadata] dependencies of {} failed: {:#}",
+            skill_id,
+            err
+        );
+    }
+}
+
+/// Stored metadata, or parsed from `dir` for skills recorded before
+/// metadata was kept.
+pub fn skill_metadata(store: &SkillStore, skill_id: &str, dir: &Path) -> SkillMetadata {
+    store
+        .get_skill_metadata_json(skill_id)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .or_else(|| read_skill_metadata(dir))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+#[path = "tests/skill_metadata.rs"]
+mod tests;
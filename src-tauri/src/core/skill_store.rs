@@ -2,13 +2,12 @@ use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use rusqlite::{params, Connection};
-use tauri::Manager;
 
 const DB_FILE_NAME: &str = "skills_hub.db";
 const LEGACY_APP_IDENTIFIERS: &[&str] = &["com.tauri.dev", "com.tauri.dev.skillshub"];
 
 // Schema versioning: bump when making changes and add a migration step.
-const SCHEMA_VERSION: i32 = 5;
+const SCHEMA_VERSION: i32 = 15;
 
 // Minimal schema for MVP: skills, skill_targets, settings, discovered_skills(optional).
 const SCHEMA_V1: &str = r#"
@@ -94,6 +93,95 @@ const SCHEMA_V5: &str = r#"
 ALTER TABLE skills ADD COLUMN group_name TEXT NULL;
 "#;
 
+// Remote tool syncs are not tracked in skill_targets; remote_deployments
+// remembers which hosts hold a copy of each skill and at which content hash.
+const SCHEMA_V6: &str = r#"
+ALTER TABLE skills ADD COLUMN propagate_to_remotes INTEGER NOT NULL DEFAULT 0;
+
+CREATE TABLE IF NOT EXISTS remote_deployments (
+    host_id TEXT NOT NULL,
+    skill_id TEXT NOT NULL,
+    tool_keys TEXT NOT NULL DEFAULT '[]',
+    content_hash TEXT NULL,
+    synced_at INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'ok',
+    PRIMARY KEY(host_id, skill_id),
+    FOREIGN KEY(skill_id) REFERENCES skills(id) ON DELETE CASCADE,
+    FOREIGN KEY(host_id) REFERENCES remote_hosts(id) ON DELETE CASCADE
+);
+"#;
+
+const SCHEMA_V7: &str = r#"
+ALTER TABLE remote_hosts ADD COLUMN group_name TEXT NULL;
+"#;
+
+// Content hash the target was last written from, so copy-mode targets can be
+// flagged stale once the central copy moves on.
+const SCHEMA_V8: &str = r#"
+ALTER TABLE skill_targets ADD COLUMN content_hash TEXT NULL;
+"#;
+
+const SCHEMA_V9: &str = r#"
+ALTER TABLE remote_hosts ADD COLUMN health_json TEXT NULL;
+"#;
+
+// Skills synced into one project's tool dir (e.g. `<project>/.claude/skills`)
+// rather than the tool's global one.
+const SCHEMA_V10: &str = r#"
+CREATE TABLE IF NOT EXISTS project_targets (
+    id TEXT PRIMARY KEY,
+    skill_id TEXT NOT NULL,
+    project_path TEXT NOT NULL,
+    tool TEXT NOT NULL,
+    target_path TEXT NOT NULL,
+    mode TEXT NOT NULL,
+    content_hash TEXT NULL,
+    synced_at INTEGER NOT NULL,
+    UNIQUE(skill_id, project_path, tool),
+    FOREIGN KEY(skill_id) REFERENCES skills(id) ON DELETE CASCADE
+);
+"#;
+
+// What a managed entry is: a skill directory, or a single-file agent or
+// command (see `resource_types`).
+const SCHEMA_V11: &str = r#"
+ALTER TABLE skills ADD COLUMN resource_type TEXT NOT NULL DEFAULT 'skill';
+"#;
+
+// SKILL.md frontmatter (see `skill_metadata`), refreshed when content changes.
+const SCHEMA_V12: &str = r#"
+ALTER TABLE skills ADD COLUMN metadata_json TEXT NULL;
+"#;
+
+// Dependencies a skill declares in SKILL.md (see `skill_dependencies`);
+// `dependency_id` is the installed skill that satisfies `spec`, if any.
+const SCHEMA_V13: &str = r#"
+CREATE TABLE IF NOT EXISTS skill_dependencies (
+    skill_id TEXT NOT NULL,
+    spec TEXT NOT NULL,
+    dependency_id TEXT NULL,
+    PRIMARY KEY(skill_id, spec),
+    FOREIGN KEY(skill_id) REFERENCES skills(id) ON DELETE CASCADE,
+    FOREIGN KEY(dependency_id) REFERENCES skills(id) ON DELETE SET NULL
+);
+"#;
+
+// Per-file hashes of each skill as installed or last updated from its
+// source (see `skill_drift`); later edits to the central copy show as drift.
+const SCHEMA_V14: &str = r#"
+CREATE TABLE IF NOT EXISTS skill_manifests (
+    skill_id TEXT PRIMARY KEY,
+    manifest_json TEXT NOT NULL,
+    recorded_at INTEGER NOT NULL,
+    FOREIGN KEY(skill_id) REFERENCES skills(id) ON DELETE CASCADE
+);
+"#;
+
+// Locked skills are left alone by updates and edits (see `skill_lock`).
+const SCHEMA_V15: &str = r#"
+ALTER TABLE skills ADD COLUMN locked INTEGER NOT NULL DEFAULT 0;
+"#;
+
 #[derive(Clone, Debug)]
 pub struct SkillStore {
     db_path: PathBuf,
@@ -126,6 +214,7 @@ pub struct SkillTargetRecord {
     pub status: String,
     pub last_error: Option<String>,
     pub synced_at: Option<i64>,
+    pub content_hash: Option<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -141,6 +230,32 @@ pub struct RemoteHostRecord {
     pub updated_at: i64,
     pub last_sync_at: Option<i64>,
     pub status: String,
+    pub group_name: Option<String>,
+    /// Last health check result, written only by `update_remote_host_health`.
+    pub health_json: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct RemoteDeploymentRecord {
+    pub host_id: String,
+    pub skill_id: String,
+    pub tool_keys: Vec<String>,
+    pub content_hash: Option<String>,
+    pub synced_at: i64,
+    /// `ok` or `stale` (the skill changed locally after this deployment).
+    pub status: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct ProjectTargetRecord {
+    pub id: String,
+    pub skill_id: String,
+    pub project_path: String,
+    pub tool: String,
+    pub target_path: String,
+    pub mode: String,
+    pub content_hash: Option<String>,
+    pub synced_at: i64,
 }
 
 #[derive(Clone, Debug)]
@@ -172,22 +287,147 @@ impl SkillStore {
                 conn.execute_batch(SCHEMA_V2)?;
                 conn.execute_batch(SCHEMA_V3)?;
                 conn.execute_batch(SCHEMA_V5)?;
+                conn.execute_batch(SCHEMA_V6)?;
+                conn.execute_batch(SCHEMA_V7)?;
+                conn.execute_batch(SCHEMA_V8)?;
+                conn.execute_batch(SCHEMA_V9)?;
+                conn.execute_batch(SCHEMA_V10)?;
+                conn.execute_batch(SCHEMA_V11)?;
+                conn.execute_batch(SCHEMA_V12)?;
+                conn.execute_batch(SCHEMA_V13)?;
+                conn.execute_batch(SCHEMA_V14)?;
+                conn.execute_batch(SCHEMA_V15)?;
                 conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
             } else if user_version == 1 {
                 conn.execute_batch(SCHEMA_V2)?;
                 conn.execute_batch(SCHEMA_V3)?;
                 conn.execute_batch(SCHEMA_V5)?;
+                conn.execute_batch(SCHEMA_V6)?;
+                conn.execute_batch(SCHEMA_V7)?;
+                conn.execute_batch(SCHEMA_V8)?;
+                conn.execute_batch(SCHEMA_V9)?;
+                conn.execute_batch(SCHEMA_V10)?;
+                conn.execute_batch(SCHEMA_V11)?;
+                conn.execute_batch(SCHEMA_V12)?;
+                conn.execute_batch(SCHEMA_V13)?;
+                conn.execute_batch(SCHEMA_V14)?;
+                conn.execute_batch(SCHEMA_V15)?;
                 conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
             } else if user_version == 2 {
                 conn.execute_batch(SCHEMA_V3)?;
                 conn.execute_batch(SCHEMA_V5)?;
+                conn.execute_batch(SCHEMA_V6)?;
+                conn.execute_batch(SCHEMA_V7)?;
+                conn.execute_batch(SCHEMA_V8)?;
+                conn.execute_batch(SCHEMA_V9)?;
+                conn.execute_batch(SCHEMA_V10)?;
+                conn.execute_batch(SCHEMA_V11)?;
+                conn.execute_batch(SCHEMA_V12)?;
+                conn.execute_batch(SCHEMA_V13)?;
+                conn.execute_batch(SCHEMA_V14)?;
+                conn.execute_batch(SCHEMA_V15)?;
                 conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
             } else if user_version == 3 {
                 conn.execute_batch(SCHEMA_V4)?;
                 conn.execute_batch(SCHEMA_V5)?;
+                conn.execute_batch(SCHEMA_V6)?;
+                conn.execute_batch(SCHEMA_V7)?;
+                conn.execute_batch(SCHEMA_V8)?;
+                conn.execute_batch(SCHEMA_V9)?;
+                conn.execute_batch(SCHEMA_V10)?;
+                conn.execute_batch(SCHEMA_V11)?;
+                conn.execute_batch(SCHEMA_V12)?;
+                conn.execute_batch(SCHEMA_V13)?;
+                conn.execute_batch(SCHEMA_V14)?;
+                conn.execute_batch(SCHEMA_V15)?;
                 conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
             } else if user_version == 4 {
                 conn.execute_batch(SCHEMA_V5)?;
+                conn.execute_batch(SCHEMA_V6)?;
+                conn.execute_batch(SCHEMA_V7)?;
+                conn.execute_batch(SCHEMA_V8)?;
+                conn.execute_batch(SCHEMA_V9)?;
+                conn.execute_batch(SCHEMA_V10)?;
+                conn.execute_batch(SCHEMA_V11)?;
+                conn.execute_batch(SCHEMA_V12)?;
+                conn.execute_batch(SCHEMA_V13)?;
+                conn.execute_batch(SCHEMA_V14)?;
+                conn.execute_batch(SCHEMA_V15)?;
+                conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+            } else if user_version == 5 {
+                conn.execute_batch(SCHEMA_V6)?;
+                conn.execute_batch(SCHEMA_V7)?;
+                conn.execute_batch(SCHEMA_V8)?;
+                conn.execute_batch(SCHEMA_V9)?;
+                conn.execute_batch(SCHEMA_V10)?;
+                conn.execute_batch(SCHEMA_V11)?;
+                conn.execute_batch(SCHEMA_V12)?;
+                conn.execute_batch(SCHEMA_V13)?;
+                conn.execute_batch(SCHEMA_V14)?;
+                conn.execute_batch(SCHEMA_V15)?;
+                conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+            } else if user_version == 6 {
+                conn.execute_batch(SCHEMA_V7)?;
+                conn.execute_batch(SCHEMA_V8)?;
+                conn.execute_batch(SCHEMA_V9)?;
+                conn.execute_batch(SCHEMA_V10)?;
+                conn.execute_batch(SCHEMA_V11)?;
+                conn.execute_batch(SCHEMA_V12)?;
+                conn.execute_batch(SCHEMA_V13)?;
+                conn.execute_batch(SCHEMA_V14)?;
+                conn.execute_batch(SCHEMA_V15)?;
+                conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+            } else if user_version == 7 {
+                conn.execute_batch(SCHEMA_V8)?;
+                conn.execute_batch(SCHEMA_V9)?;
+                conn.execute_batch(SCHEMA_V10)?;
+                conn.execute_batch(SCHEMA_V11)?;
+                conn.execute_batch(SCHEMA_V12)?;
+                conn.execute_batch(SCHEMA_V13)?;
+                conn.execute_batch(SCHEMA_V14)?;
+                conn.execute_batch(SCHEMA_V15)?;
+                conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+            } else if user_version == 8 {
+                conn.execute_batch(SCHEMA_V9)?;
+                conn.execute_batch(SCHEMA_V10)?;
+                conn.execute_batch(SCHEMA_V11)?;
+                conn.execute_batch(SCHEMA_V12)?;
+                conn.execute_batch(SCHEMA_V13)?;
+                conn.execute_batch(SCHEMA_V14)?;
+                conn.execute_batch(SCHEMA_V15)?;
+                conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+            } else if user_version == 9 {
+                conn.execute_batch(SCHEMA_V10)?;
+                conn.execute_batch(SCHEMA_V11)?;
+                conn.execute_batch(SCHEMA_V12)?;
+                conn.execute_batch(SCHEMA_V13)?;
+                conn.execute_batch(SCHEMA_V14)?;
+                conn.execute_batch(SCHEMA_V15)?;
+                conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+            } else if user_version == 10 {
+                conn.execute_batch(SCHEMA_V11)?;
+                conn.execute_batch(SCHEMA_V12)?;
+                conn.execute_batch(SCHEMA_V13)?;
+                conn.execute_batch(SCHEMA_V14)?;
+                conn.execute_batch(SCHEMA_V15)?;
+                conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+            } else if user_version == 11 {
+                conn.execute_batch(SCHEMA_V12)?;
+                conn.execute_batch(SCHEMA_V13)?;
+                conn.execute_batch(SCHEMA_V14)?;
+                conn.execute_batch(SCHEMA_V15)?;
+                conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+            } else if user_version == 12 {
+                conn.execute_batch(SCHEMA_V13)?;
+                conn.execute_batch(SCHEMA_V14)?;
+                conn.execute_batch(SCHEMA_V15)?;
+                conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+            } else if user_version == 13 {
+                conn.execute_batch(SCHEMA_V14)?;
+                conn.execute_batch(SCHEMA_V15)?;
+                conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+            } else if user_version == 14 {
+                conn.execute_batch(SCHEMA_V15)?;
                 conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
             } else if user_version > SCHEMA_VERSION {
                 anyhow::bail!(
@@ -220,11 +460,7 @@ impl SkillStore {
 /// Extract `owner/repo` from a GitHub URL (e.g. `https://github.com/analogjs/angular-skills.git?...`).
 fn derive_group_from_source_ref(source_ref: &str) -> Option<String> {
     let url = source_ref.split('?').next().unwrap_or(source_ref);
-    let gh = "https://github.com/";
-    if !url.starts_with(gh) {
-        return None;
-    }
-    let rest = &url[gh.len()..];
+    let (_, rest) = super::source_providers::provider_for_url(url)?;
     let parts: Vec<&str> = rest.split('/').collect();
     if parts.len() < 2 {
         return None;
@@ -268,6 +504,13 @@ impl SkillStore {
         })
     }
 
+    pub fn delete_setting(&self, key: &str) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute("DELETE FROM settings WHERE key = ?1", params![key])?;
+            Ok(())
+        })
+    }
+
     #[allow(dead_code)]
     pub fn set_onboarding_completed(&self, completed: bool) -> Result<()> {
         self.set_setting(
@@ -323,16 +566,17 @@ impl SkillStore {
         self.with_conn(|conn| {
             conn.execute(
                 "INSERT INTO skill_targets (
-          id, skill_id, tool, target_path, mode, status, last_error, synced_at
+          id, skill_id, tool, target_path, mode, status, last_error, synced_at, content_hash
         ) VALUES (
-          ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8
+          ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9
         )
         ON CONFLICT(skill_id, tool) DO UPDATE SET
           target_path = excluded.target_path,
           mode = excluded.mode,
           status = excluded.status,
           last_error = excluded.last_error,
-          synced_at = excluded.synced_at",
+          synced_at = excluded.synced_at,
+          content_hash = excluded.content_hash",
                 params![
                     record.id,
                     record.skill_id,
@@ -341,7 +585,8 @@ impl SkillStore {
                     record.mode,
                     record.status,
                     record.last_error,
-                    record.synced_at
+                    record.synced_at,
+                    record.content_hash
                 ],
             )?;
             Ok(())
@@ -424,7 +669,8 @@ impl SkillStore {
     pub fn list_skill_targets(&self, skill_id: &str) -> Result<Vec<SkillTargetRecord>> {
         self.with_conn(|conn| {
             let mut stmt = conn.prepare(
-                "SELECT id, skill_id, tool, target_path, mode, status, last_error, synced_at
+                "SELECT id, skill_id, tool, target_path, mode, status, last_error, synced_at,
+                content_hash
          FROM skill_targets
          WHERE skill_id = ?1
          ORDER BY tool ASC",
@@ -439,6 +685,7 @@ impl SkillStore {
                     status: row.get(5)?,
                     last_error: row.get(6)?,
                     synced_at: row.get(7)?,
+                    content_hash: row.get(8)?,
                 })
             })?;
 
@@ -473,7 +720,8 @@ impl SkillStore {
     ) -> Result<Option<SkillTargetRecord>> {
         self.with_conn(|conn| {
             let mut stmt = conn.prepare(
-                "SELECT id, skill_id, tool, target_path, mode, status, last_error, synced_at
+                "SELECT id, skill_id, tool, target_path, mode, status, last_error, synced_at,
+                content_hash
          FROM skill_targets
          WHERE skill_id = ?1 AND tool = ?2",
             )?;
@@ -488,6 +736,7 @@ impl SkillStore {
                     status: row.get(5)?,
                     last_error: row.get(6)?,
                     synced_at: row.get(7)?,
+                    content_hash: row.get(8)?,
                 }))
             } else {
                 Ok(None)
@@ -515,6 +764,222 @@ impl SkillStore {
         })
     }
 
+    pub fn get_skill_propagate_to_remotes(&self, skill_id: &str) -> Result<bool> {
+        self.with_conn(|conn| {
+            let value: Option<i64> = conn
+                .query_row(
+                    "SELECT propagate_to_remotes FROM skills WHERE id = ?1",
+                    params![skill_id],
+                    |row| row.get(0),
+                )
+                .ok();
+            Ok(value.unwrap_or(0) != 0)
+        })
+    }
+
+    pub fn set_skill_propagate_to_remotes(&self, skill_id: &str, enabled: bool) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "UPDATE skills SET propagate_to_remotes = ?1 WHERE id = ?2",
+                params![enabled as i64, skill_id],
+            )?;
+            Ok(())
+        })
+    }
+
+    pub fn get_skill_locked(&self, skill_id: &str) -> Result<bool> {
+        self.with_conn(|conn| {
+            let value: Option<i64> = conn
+                .query_row(
+                    "SELECT locked FROM skills WHERE id = ?1",
+                    params![skill_id],
+                    |row| row.get(0),
+                )
+                .ok();
+            Ok(value.unwrap_or(0) != 0)
+        })
+    }
+
+    pub fn set_skill_locked(&self, skill_id: &str, locked: bool) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "UPDATE skills SET locked = ?1 WHERE id = ?2",
+                params![locked as i64, skill_id],
+            )?;
+            Ok(())
+        })
+    }
+
+    pub fn get_skill_resource_type(&self, skill_id: &str) -> Result<String> {
+        self.with_conn(|conn| {
+            let value: Option<String> = conn
+                .query_row(
+                    "SELECT resource_type FROM skills WHERE id = ?1",
+                    params![skill_id],
+                    |row| row.get(0),
+                )
+                .ok();
+            Ok(value.unwrap_or_else(|| "skill".to_string()))
+        })
+    }
+
+    pub fn set_skill_resource_type(&self, skill_id: &str, resource_type: &str) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "UPDATE skills SET resource_type = ?1 WHERE id = ?2",
+                params![resource_type, skill_id],
+            )?;
+            Ok(())
+        })
+    }
+
+    pub fn get_skill_metadata_json(&self, skill_id: &str) -> Result<Option<String>> {
+        self.with_conn(|conn| {
+            let value: Option<Option<String>> = conn
+                .query_row(
+                    "SELECT metadata_json FROM skills WHERE id = ?1",
+                    params![skill_id],
+                    |row| row.get(0),
+                )
+                .ok();
+            Ok(value.flatten())
+        })
+    }
+
+    pub fn set_skill_metadata_json(
+        &self,
+        skill_id: &str,
+        metadata_json: Option<&str>,
+    ) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "UPDATE skills SET metadata_json = ?1 WHERE id = ?2",
+                params![metadata_json, skill_id],
+            )?;
+            Ok(())
+        })
+    }
+
+    pub fn get_skill_manifest_json(&self, skill_id: &str) -> Result<Option<String>> {
+        self.with_conn(|conn| {
+            let value: Option<String> = conn
+                .query_row(
+                    "SELECT manifest_json FROM skill_manifests WHERE skill_id = ?1",
+                    params![skill_id],
+                    |row| row.get(0),
+                )
+                .ok();
+            Ok(value)
+        })
+    }
+
+    pub fn set_skill_manifest_json(
+        &self,
+        skill_id: &str,
+        manifest_json: &str,
+        recorded_at: i64,
+    ) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "INSERT INTO skill_manifests (skill_id, manifest_json, recorded_at)
+                 VALUES (?1, ?2, ?3)
+                 ON CONFLICT(skill_id) DO UPDATE SET
+                   manifest_json = excluded.manifest_json,
+                   recorded_at = excluded.recorded_at",
+                params![skill_id, manifest_json, recorded_at],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Replace the dependency edges of `skill_id` with `(spec, dependency_id)`.
+    pub fn set_skill_dependencies(
+        &self,
+        skill_id: &str,
+        edges: &[(String, Option<String>)],
+    ) -> Result<()> {
+        self.with_conn(|conn| {
+            let tx = conn.unchecked_transaction()?;
+            tx.execute(
+                "DELETE FROM skill_dependencies WHERE skill_id = ?1",
+                params![skill_id],
+            )?;
+            for (spec, dependency_id) in edges {
+                tx.execute(
+                    "INSERT OR REPLACE INTO skill_dependencies (skill_id, spec, dependency_id)
+                     VALUES (?1, ?2, ?3)",
+                    params![skill_id, spec, dependency_id],
+                )?;
+            }
+            tx.commit()?;
+            Ok(())
+        })
+    }
+
+    /// `(spec, dependency_id)` edges declared by `skill_id`.
+    pub fn list_skill_dependencies(&self, skill_id: &str) -> Result<Vec<(String, Option<String>)>> {
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT spec, dependency_id FROM skill_dependencies
+                 WHERE skill_id = ?1 ORDER BY spec ASC",
+            )?;
+            let rows = stmt.query_map(params![skill_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            let mut items = Vec::new();
+            for row in rows {
+                items.push(row?);
+            }
+            Ok(items)
+        })
+    }
+
+    /// `(skill_id, spec)` edges that point at `dependency_id`.
+    pub fn list_skill_dependents(&self, dependency_id: &str) -> Result<Vec<(String, String)>> {
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT skill_id, spec FROM skill_dependencies
+                 WHERE dependency_id = ?1 ORDER BY skill_id ASC",
+            )?;
+            let rows =
+                stmt.query_map(params![dependency_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            let mut items = Vec::new();
+            for row in rows {
+                items.push(row?);
+            }
+            Ok(items)
+        })
+    }
+
+    /// `(skill_id, spec)` edges no installed skill satisfies yet.
+    pub fn list_unresolved_dependencies(&self) -> Result<Vec<(String, String)>> {
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT skill_id, spec FROM skill_dependencies WHERE dependency_id IS NULL",
+            )?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            let mut items = Vec::new();
+            for row in rows {
+                items.push(row?);
+            }
+            Ok(items)
+        })
+    }
+
+    pub fn set_dependency_target(
+        &self,
+        skill_id: &str,
+        spec: &str,
+        dependency_id: Option<&str>,
+    ) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "UPDATE skill_dependencies SET dependency_id = ?1
+                 WHERE skill_id = ?2 AND spec = ?3",
+                params![dependency_id, skill_id, spec],
+            )?;
+            Ok(())
+        })
+    }
+
     // ── Custom Target CRUD ──────────────────────────────────────────────
 
     pub fn upsert_custom_target(&self, record: &CustomTargetRecord) -> Result<()> {
@@ -588,16 +1053,98 @@ impl SkillStore {
     pub fn delete_custom_target(&self, id: &str) -> Result<()> {
         self.with_conn(|conn| {
             conn.execute("DELETE FROM custom_targets WHERE id = ?1", params![id])?;
-            // Also clean up any skill_targets that reference this custom target.
+            // Also clean up any skill_targets that reference this custom target,
+            // including the per-root rows of templated targets (`custom:<id>@<root>`).
             let tool_key = format!("custom:{}", id);
             conn.execute(
-                "DELETE FROM skill_targets WHERE tool = ?1",
-                params![tool_key],
+                "DELETE FROM skill_targets WHERE tool = ?1 OR substr(tool, 1, length(?2)) = ?2",
+                params![tool_key, format!("{}@", tool_key)],
+            )?;
+            Ok(())
+        })
+    }
+
+    // ── Project targets ─────────────────────────────────────────────────
+
+    pub fn upsert_project_target(&self, record: &ProjectTargetRecord) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "INSERT INTO project_targets (
+                    id, skill_id, project_path, tool, target_path, mode, content_hash, synced_at
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                ON CONFLICT(skill_id, project_path, tool) DO UPDATE SET
+                    target_path = excluded.target_path,
+                    mode = excluded.mode,
+                    content_hash = excluded.content_hash,
+                    synced_at = excluded.synced_at",
+                params![
+                    record.id,
+                    record.skill_id,
+                    record.project_path,
+                    record.tool,
+                    record.target_path,
+                    record.mode,
+                    record.content_hash,
+                    record.synced_at,
+                ],
             )?;
             Ok(())
         })
     }
 
+    pub fn list_project_targets(&self) -> Result<Vec<ProjectTargetRecord>> {
+        self.query_project_targets("ORDER BY project_path ASC, tool ASC", params![])
+    }
+
+    pub fn list_project_targets_for_skill(
+        &self,
+        skill_id: &str,
+    ) -> Result<Vec<ProjectTargetRecord>> {
+        self.query_project_targets(
+            "WHERE skill_id = ?1 ORDER BY project_path ASC, tool ASC",
+            params![skill_id],
+        )
+    }
+
+    fn query_project_targets(
+        &self,
+        clause: &str,
+        args: &[&dyn rusqlite::ToSql],
+    ) -> Result<Vec<ProjectTargetRecord>> {
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare(&format!(
+                "SELECT id, skill_id, project_path, tool, target_path, mode, content_hash,
+                        synced_at
+                 FROM project_targets {}",
+                clause
+            ))?;
+            let rows = stmt.query_map(args, |row| {
+                Ok(ProjectTargetRecord {
+                    id: row.get(0)?,
+                    skill_id: row.get(1)?,
+                    project_path: row.get(2)?,
+                    tool: row.get(3)?,
+                    target_path: row.get(4)?,
+                    mode: row.get(5)?,
+                    content_hash: row.get(6)?,
+                    synced_at: row.get(7)?,
+                })
+            })?;
+            let mut items = Vec::new();
+            for row in rows {
+                items.push(row?);
+            }
+            Ok(items)
+        })
+    }
+
+    pub fn delete_project_target(&self, id: &str) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute("DELETE FROM project_targets WHERE id = ?1", params![id])?;
+            Ok(())
+        })
+    }
+
     // ── Remote Host CRUD ────────────────────────────────────────────────
 
     pub fn upsert_remote_host(&self, record: &RemoteHostRecord) -> Result<()> {
@@ -605,8 +1152,8 @@ impl SkillStore {
             conn.execute(
                 "INSERT INTO remote_hosts (
                     id, label, host, port, username, auth_method, key_path,
-                    created_at, updated_at, last_sync_at, status
-                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+                    created_at, updated_at, last_sync_at, status, group_name
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
                 ON CONFLICT(id) DO UPDATE SET
                     label = excluded.label,
                     host = excluded.host,
@@ -615,7 +1162,8 @@ impl SkillStore {
                     auth_method = excluded.auth_method,
                     key_path = excluded.key_path,
                     updated_at = excluded.updated_at,
-                    status = excluded.status",
+                    status = excluded.status,
+                    group_name = excluded.group_name",
                 params![
                     record.id,
                     record.label,
@@ -628,6 +1176,7 @@ impl SkillStore {
                     record.updated_at,
                     record.last_sync_at,
                     record.status,
+                    record.group_name,
                 ],
             )?;
             Ok(())
@@ -638,7 +1187,7 @@ impl SkillStore {
         self.with_conn(|conn| {
             let mut stmt = conn.prepare(
                 "SELECT id, label, host, port, username, auth_method, key_path,
-                        created_at, updated_at, last_sync_at, status
+                        created_at, updated_at, last_sync_at, status, group_name, health_json
                  FROM remote_hosts
                  ORDER BY label ASC",
             )?;
@@ -655,6 +1204,8 @@ impl SkillStore {
                     updated_at: row.get(8)?,
                     last_sync_at: row.get(9)?,
                     status: row.get(10)?,
+                    group_name: row.get(11)?,
+                    health_json: row.get(12)?,
                 })
             })?;
             let mut items = Vec::new();
@@ -669,7 +1220,7 @@ impl SkillStore {
         self.with_conn(|conn| {
             let mut stmt = conn.prepare(
                 "SELECT id, label, host, port, username, auth_method, key_path,
-                        created_at, updated_at, last_sync_at, status
+                        created_at, updated_at, last_sync_at, status, group_name, health_json
                  FROM remote_hosts
                  WHERE id = ?1
                  LIMIT 1",
@@ -688,6 +1239,8 @@ impl SkillStore {
                     updated_at: row.get(8)?,
                     last_sync_at: row.get(9)?,
                     status: row.get(10)?,
+                    group_name: row.get(11)?,
+                    health_json: row.get(12)?,
                 }))
             } else {
                 Ok(None)
@@ -695,13 +1248,143 @@ impl SkillStore {
         })
     }
 
+    pub fn update_remote_host_health(&self, host_id: &str, health_json: &str) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "UPDATE remote_hosts SET health_json = ?1 WHERE id = ?2",
+                params![health_json, host_id],
+            )?;
+            Ok(())
+        })
+    }
+
+    pub fn update_remote_host_group(&self, host_id: &str, group_name: Option<&str>) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "UPDATE remote_hosts SET group_name = ?1 WHERE id = ?2",
+                params![group_name, host_id],
+            )?;
+            Ok(())
+        })
+    }
+
+    pub fn list_remote_hosts_in_group(&self, group_name: &str) -> Result<Vec<RemoteHostRecord>> {
+        Ok(self
+            .list_remote_hosts()?
+            .into_iter()
+            .filter(|h| h.group_name.as_deref() == Some(group_name))
+            .collect())
+    }
+
     pub fn delete_remote_host(&self, host_id: &str) -> Result<()> {
         self.with_conn(|conn| {
             conn.execute("DELETE FROM remote_hosts WHERE id = ?1", params![host_id])?;
+            // Remote tool targets are keyed `remote:<hostId>:<tool>`.
+            conn.execute(
+                "DELETE FROM skill_targets WHERE substr(tool, 1, length(?1)) = ?1",
+                params![format!("remote:{}:", host_id)],
+            )?;
+            Ok(())
+        })
+    }
+
+    // ── Remote deployments ──────────────────────────────────────────────
+
+    pub fn upsert_remote_deployment(&self, record: &RemoteDeploymentRecord) -> Result<()> {
+        let tool_keys = serde_json::to_string(&record.tool_keys)?;
+        self.with_conn(|conn| {
+            conn.execute(
+                "INSERT INTO remote_deployments (
+                    host_id, skill_id, tool_keys, content_hash, synced_at, status
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                ON CONFLICT(host_id, skill_id) DO UPDATE SET
+                    tool_keys = excluded.tool_keys,
+                    content_hash = excluded.content_hash,
+                    synced_at = excluded.synced_at,
+                    status = excluded.status",
+                params![
+                    record.host_id,
+                    record.skill_id,
+                    tool_keys,
+                    record.content_hash,
+                    record.synced_at,
+                    record.status,
+                ],
+            )?;
             Ok(())
         })
     }
 
+    pub fn list_remote_deployments_for_skill(
+        &self,
+        skill_id: &str,
+    ) -> Result<Vec<RemoteDeploymentRecord>> {
+        self.query_remote_deployments("WHERE skill_id = ?1 ORDER BY host_id ASC", skill_id)
+    }
+
+    pub fn list_remote_deployments_for_host(
+        &self,
+        host_id: &str,
+    ) -> Result<Vec<RemoteDeploymentRecord>> {
+        self.query_remote_deployments("WHERE host_id = ?1 ORDER BY skill_id ASC", host_id)
+    }
+
+    pub fn get_remote_deployment(
+        &self,
+        host_id: &str,
+        skill_id: &str,
+    ) -> Result<Option<RemoteDeploymentRecord>> {
+        Ok(self
+            .list_remote_deployments_for_skill(skill_id)?
+            .into_iter()
+            .find(|d| d.host_id == host_id))
+    }
+
+    pub fn delete_remote_deployment(&self, host_id: &str, skill_id: &str) -> Result<()> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "DELETE FROM remote_deployments WHERE host_id = ?1 AND skill_id = ?2",
+                params![host_id, skill_id],
+            )?;
+            conn.execute(
+                "DELETE FROM skill_targets
+                 WHERE skill_id = ?1 AND substr(tool, 1, length(?2)) = ?2",
+                params![skill_id, format!("remote:{}:", host_id)],
+            )?;
+            Ok(())
+        })
+    }
+
+    fn query_remote_deployments(
+        &self,
+        filter: &str,
+        arg: &str,
+    ) -> Result<Vec<RemoteDeploymentRecord>> {
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare(&format!(
+                "SELECT host_id, skill_id, tool_keys, content_hash, synced_at, status
+                 FROM remote_deployments {}",
+                filter
+            ))?;
+            let rows = stmt.query_map(params![arg], |row| {
+                let tool_keys: String = row.get(2)?;
+                Ok(RemoteDeploymentRecord {
+                    host_id: row.get(0)?,
+                    skill_id: row.get(1)?,
+                    tool_keys: serde_json::from_str(&tool_keys).unwrap_or_default(),
+                    content_hash: row.get(3)?,
+                    synced_at: row.get(4)?,
+                    status: row.get(5)?,
+                })
+            })?;
+            let mut items = Vec::new();
+            for row in rows {
+                items.push(row?);
+            }
+            Ok(items)
+        })
+    }
+
     pub fn update_remote_host_sync_status(
         &self,
         host_id: &str,
@@ -733,12 +1416,9 @@ impl SkillStore {
     }
 }
 
-pub fn default_db_path<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Result<PathBuf> {
-    let app_dir = app
-        .path()
-        .app_data_dir()
-        .context("failed to resolve app data dir")?;
-    std::fs::create_dir_all(&app_dir)
+pub fn default_db_path(paths: &AppPaths) -> Result<PathBuf> {
+    let app_dir = &paths.data_dir;
+    std::fs::create_dir_all(app_dir)
         .with_context(|| format!("failed to create app data dir {:?}", app_dir))?;
     Ok(app_dir.join(DB_FILE_NAME))
 }
@@ -819,3 +1499,4 @@ fn db_has_any_skills(db_path: &Path) -> Result<bool> {
 #[cfg(test)]
 #[path = "tests/skill_store.rs"]
 mod tests;
+use super::app_paths::AppPaths;
@@ -0,0 +1,274 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use super::app_paths::AppPaths;
+use super::installer::is_frontmatter_delimiter;
+use super::skill_store::SkillStore;
+use super::sync_engine::copy_dir_recursive;
+
+pub const DEFAULT_TEMPLATE: &str = "basic";
+/// Overrides where user templates are read from.
+pub const TEMPLATES_DIR_KEY: &str = "skill_templates_dir";
+const TEMPLATES_DIR_NAME: &str = "templates";
+/// Text files larger than this are copied without substitution.
+const MAX_RENDERED_BYTES: u64 = 1024 * 1024;
+
+const BASIC_TEMPLATE: &str = "---
+name: {{name}}
+description: {{description}}
+---
+
+# {{title}}
+
+## When to use
+
+- TODO: situations where this skill applies
+
+## Instructions
+
+1. TODO: first step
+2. TODO: next step
+
+## Examples
+
+TODO: a short example of the expected input and result.
+";
+
+const MINIMAL_TEMPLATE: &str = "---
+name: {{name}}
+description: {{description}}
+---
+
+# {{title}}
+
+TODO: instructions.
+";
+
+/// `(id, description, SKILL.md)` of the templates shipped with the app.
+const BUILTIN_TEMPLATES: &[(&str, &str, &str)] = &[
+    (
+        "basic",
+        "SKILL.md with when-to-use, instructions and examples sections",
+        BASIC_TEMPLATE,
+    ),
+    ("minimal", "SKILL.md with a title only", MINIMAL_TEMPLATE),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SkillTemplate {
+    pub id: String,
+    pub description: Option<String>,
+    /// User templates live in [`TemplateList::user_dir`] and shadow
+    /// built-ins with the same id.
+    pub builtin: bool,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TemplateList {
+    pub templates: Vec<SkillTemplate>,
+    /// Each folder in here with a SKILL.md is a template.
+    pub user_dir: String,
+}
+
+/// Values for the `{{name}}`, `{{title}}`, `{{description}}` and
+/// `{{author}}` placeholders.
+pub struct TemplateVars {
+    pub name: String,
+    pub description: String,
+    pub author: String,
+}
+
+impl TemplateVars {
+    /// Missing values get defaults; the author falls back to git's
+    /// `user.name`.
+    pub fn new(name: &str, description: Option<&str>, author: Option<&str>) -> Self {
+        let description = description
+            .map(|d| d.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|d| !d.is_empty())
+            .unwrap_or_else(|| format!("Describe what {} does and when to use it.", name));
+        let author = author
+            .map(str::trim)
+            .filter(|a| !a.is_empty())
+            .map(str::to_string)
+            .or_else(git_user_name)
+            .unwrap_or_default();
+        TemplateVars {
+            name: name.to_string(),
+            description,
+            author,
+        }
+    }
+}
+
+fn git_user_name() -> Option<String> {
+    git2::Config::open_default()
+        .ok()?
+        .get_string("user.name")
+        .ok()
+        .filter(|name| !name.trim().is_empty())
+}
+
+/// The configured user templates folder, or `templates` in the app data
+/// folder.
+pub fn user_templates_dir(paths: &AppPaths, store: &SkillStore) -> Result<PathBuf> {
+    if let Some(path) = store
+        .get_setting(TEMPLATES_DIR_KEY)?
+        .filter(|p| !p.is_empty())
+    {
+        return Ok(PathBuf::from(path));
+    }
+    Ok(paths.data_dir.join(TEMPLATES_DIR_NAME))
+}
+
+pub fn list_skill_templates(paths: &AppPaths, store: &SkillStore) -> Result<TemplateList> {
+    let user_dir = user_templates_dir(paths, store)?;
+    Ok(TemplateList {
+        templates: templates_in(&user_dir),
+        user_dir: user_dir.to_string_lossy().to_string(),
+    })
+}
+
+/// Built-ins followed by the user templates in `user_dir`, by id.
+fn templates_in(user_dir: &Path) -> Vec<SkillTemplate> {
+    let mut user: Vec<SkillTemplate> = std::fs::read_dir(user_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().join("SKILL.md").is_file())
+        .map(|entry| SkillTemplate {
+            id: entry.file_name().to_string_lossy().to_string(),
+            description: None,
+            builtin: false,
+        })
+        .collect();
+    user.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let mut templates: Vec<SkillTemplate> = BUILTIN_TEMPLATES
+        .iter()
+        .filter(|(id, _, _)| !user.iter().any(|t| t.id == *id))
+        .map(|(id, description, _)| SkillTemplate {
+            id: id.to_string(),
+            description: Some(description.to_string()),
+            builtin: true,
+        })
+        .collect();
+    templates.extend(user);
+    templates
+}
+
+/// Create the skill folder `dest` from template `id`, filling in `vars`.
+/// `dest` is removed again if anything fails.
+pub fn render_template(user_dir: &Path, id: &str, vars: &TemplateVars, dest: &Path) -> Result<()> {
+    let user_template = user_dir.join(id);
+    let is_user = !id.is_empty()
+        && !id.contains(['/', '\\'])
+        && !id.starts_with('.')
+        && user_template.join("SKILL.md").is_file();
+    let result = if is_user {
+        copy_dir_recursive(&user_template, dest).and_then(|()| render_files(dest, vars))
+    } else {
+        let (_, _, text) = BUILTIN_TEMPLATES
+            .iter()
+            .find(|(builtin, _, _)| *builtin == id)
+            .ok_or_else(|| anyhow::anyhow!("unknown skill template: {}", id))?;
+        std::fs::create_dir_all(dest)
+            .with_context(|| format!("create {:?}", dest))
+            .and_then(|()| {
+                let skill_md = dest.join("SKILL.md");
+                std::fs::write(&skill_md, substitute(text, vars))
+                    .with_context(|| format!("write {:?}", skill_md))
+            })
+    };
+    if result.is_err() {
+        let _ = std::fs::remove_dir_all(dest);
+    }
+    result
+}
+
+/// Fill placeholders in every text file under `dir`. Binary and very large
+/// files are left alone.
+fn render_files(dir: &Path, vars: &TemplateVars) -> Result<()> {
+    for entry in walkdir::WalkDir::new(dir).follow_links(false) {
+        let entry = entry?;
+        let small = entry
+            .metadata()
+            .is_ok_and(|m| m.is_file() && m.len() <= MAX_RENDERED_BYTES);
+        if !small {
+            continue;
+        }
+        let Ok(text) = std::fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        let rendered = substitute(&text, vars);
+        if rendered != text {
+            std::fs::write(entry.path(), rendered)
+                .with_context(|| format!("write {:?}", entry.path()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Replace placeholders in `text`. Inside a leading frontmatter block the
+/// values are quoted when plain YAML would misread them, so templates
+/// should not quote placeholders themselves.
+fn substitute(text: &str, vars: &TemplateVars) -> String {
+    let title = title_case(&vars.name);
+    let values = [
+        ("{{name}}", vars.name.as_str()),
+        ("{{title}}", title.as_str()),
+        ("{{description}}", vars.description.as_str()),
+        ("{{author}}", vars.author.as_str()),
+    ];
+    let mut out = String::with_capacity(text.len());
+    let mut in_frontmatter = false;
+    for (index, line) in text.split_inclusive('\n').enumerate() {
+        if is_frontmatter_delimiter(line) {
+            in_frontmatter = index == 0;
+        }
+        let mut line = line.to_string();
+        for (placeholder, value) in values {
+            if line.contains(placeholder) {
+                let value = if in_frontmatter {
+                    yaml_scalar(value)
+                } else {
+                    value.to_string()
+                };
+                line = line.replace(placeholder, &value);
+            }
+        }
+        out.push_str(&line);
+    }
+    out
+}
+
+/// Quote a frontmatter value when plain YAML would misread it.
+fn yaml_scalar(value: &str) -> String {
+    let plain = !value.contains(": ")
+        && !value.contains(" #")
+        && !value.starts_with(|c: char| "!&*?|>'\"%@`{}[],#-".contains(c));
+    if plain {
+        value.to_string()
+    } else {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+fn title_case(name: &str) -> String {
+    name.split('-')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+#[path = "tests/skill_templates.rs"]
+mod tests;
@@ -0,0 +1,340 @@
+use std::path::{Component, Path};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use super::content_hash::is_ignored;
+use super::installer::is_frontmatter_delimiter;
+use super::skill_authoring::validate_skill_name;
+use super::skill_store::SkillStore;
+
+/// Agent Skills limits; tools truncate or reject longer values.
+pub const MAX_DESCRIPTION_LEN: usize = 1024;
+/// Longer instructions should be split into referenced files.
+pub const MAX_SKILL_MD_LINES: usize = 500;
+pub const MAX_SKILL_BYTES: u64 = 10 * 1024 * 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueSeverity {
+    /// Tools will not load the skill.
+    Error,
+    /// The skill loads but may misbehave or not travel well.
+    Warning,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SkillIssue {
+    pub severity: IssueSeverity,
+    /// Stable identifier, e.g. `missing_description`.
+    pub code: String,
+    pub message: String,
+    /// 1-based line in SKILL.md, when the issue is tied to one.
+    pub line: Option<usize>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SkillValidation {
+    /// No errors; warnings do not count.
+    pub valid: bool,
+    pub issues: Vec<SkillIssue>,
+}
+
+pub fn validate_skill(store: &SkillStore, skill_id: &str) -> Result<SkillValidation> {
+    let skill = store
+        .get_skill_by_id(skill_id)?
+        .ok_or_else(|| anyhow::anyhow!("skill not found"))?;
+    Ok(validate_skill_dir(Path::new(&skill.central_path)))
+}
+
+/// Check the skill folder at `dir`: SKILL.md frontmatter, size, and the
+/// files it links to.
+pub fn validate_skill_dir(dir: &Path) -> SkillValidation {
+    let mut issues = Vec::new();
+    let skill_md = dir.join("SKILL.md");
+    match std::fs::read_to_string(&skill_md) {
+        Ok(text) => check_skill_md(dir, &text, &mut issues),
+        Err(_) => issues.push(error(
+            "missing_skill_md",
+            "SKILL.md is missing or unreadable",
+        )),
+    }
+    let size = dir_size(dir);
+    if size > MAX_SKILL_BYTES {
+        issues.push(warning(
+            "large_skill",
+            format!(
+                "skill folder is {} MB; tools copy all of it",
+                size / (1024 * 1024)
+            ),
+        ));
+    }
+    SkillValidation {
+        valid: !issues.iter().any(|i| i.severity == IssueSeverity::Error),
+        issues,
+    }
+}
+
+/// Warnings to show after an install. Agents and commands have no SKILL.md
+/// and are not checked.
+pub fn install_warnings(dir: &Path) -> Vec<SkillIssue> {
+    if !dir.join("SKILL.md").is_file() {
+        return Vec::new();
+    }
+    validate_skill_dir(dir).issues
+}
+
+fn check_skill_md(dir: &Path, text: &str, issues: &mut Vec<SkillIssue>) {
+    let Some(fields) = frontmatter_fields(text) else {
+        issues.push(error(
+            "invalid_frontmatter",
+            "SKILL.md must start with a --- frontmatter block",
+        ));
+        return;
+    };
+    let field = |key: &str| {
+        fields
+            .iter()
+            .find(|(_, k, _)| k == key)
+            .map(|(line, _, value)| (*line, value.as_str()))
+    };
+
+    match field("name") {
+        None | Some((_, "")) => issues.push(error("missing_name", "frontmatter has no name")),
+        Some((line, name)) => {
+            if validate_skill_name(name).is_err() {
+                issues.push(warning_at(
+                    "invalid_name",
+                    format!(
+                        "name {:?} should use lowercase letters, digits and hyphens",
+                        name
+                    ),
+                    line,
+                ));
+            }
+            let folder = dir.file_name().map(|f| f.to_string_lossy().to_string());
+            if folder.as_deref().is_some_and(|folder| folder != name) {
+                issues.push(warning_at(
+                    "name_mismatch",
+                    format!("name {:?} differs from the folder name", name),
+                    line,
+                ));
+            }
+        }
+    }
+    match field("description") {
+        None | Some((_, "")) => issues.push(error(
+            "missing_description",
+            "frontmatter has no description; tools use it to decide when to load the skill",
+        )),
+        Some((line, description)) if description.chars().count() > MAX_DESCRIPTION_LEN => issues
+            .push(warning_at(
+                "description_too_long",
+                format!(
+                    "description is longer than {} characters",
+                    MAX_DESCRIPTION_LEN
+                ),
+                line,
+            )),
+        Some(_) => {}
+    }
+
+    let lines = text.lines().count();
+    if lines > MAX_SKILL_MD_LINES {
+        issues.push(warning(
+            "skill_md_too_long",
+            format!(
+                "SKILL.md has {} lines; move details into referenced files",
+                lines
+            ),
+        ));
+    }
+
+    for (index, line) in text.lines().enumerate() {
+        let line_no = index + 1;
+        let mut absolute_reported = false;
+        for link in links_in(line) {
+            if is_absolute(link) {
+                issues.push(warning_at(
+                    "absolute_path",
+                    format!("link to absolute path {}", link),
+                    line_no,
+                ));
+                absolute_reported = true;
+            } else if escapes(link) {
+                issues.push(warning_at(
+                    "reference_outside_skill",
+                    format!("{} is outside the skill folder", link),
+                    line_no,
+                ));
+            } else if std::fs::symlink_metadata(dir.join(link)).is_err() {
+                issues.push(warning_at(
+                    "missing_reference",
+                    format!("referenced file {} does not exist", link),
+                    line_no,
+                ));
+            }
+        }
+        if !absolute_reported && mentions_home_path(line) {
+            issues.push(warning_at(
+                "absolute_path",
+                "mentions a path in a home directory",
+                line_no,
+            ));
+        }
+    }
+}
+
+/// Top-level `key: value` pairs of the leading frontmatter block with their
+/// line numbers, or `None` when there is no closed block. Block scalars
+/// (`>` / `|`) and nested lines become part of their key's value.
+pub(crate) fn frontmatter_fields(text: &str) -> Option<Vec<(usize, String, String)>> {
+    let mut lines = text.lines().enumerate();
+    if !lines
+        .next()
+        .is_some_and(|(_, line)| is_frontmatter_delimiter(line))
+    {
+        return None;
+    }
+    let mut fields: Vec<(usize, String, String)> = Vec::new();
+    // Whether continuation lines join with spaces (folded) or newlines.
+    let mut fold = true;
+    for (index, line) in lines {
+        if is_frontmatter_delimiter(line) {
+            return Some(fields);
+        }
+        let nested = line.starts_with([' ', '\t', '-']) || line.trim().is_empty();
+        if nested {
+            if let Some((_, _, value)) = fields.last_mut() {
+                let part = line.trim();
+                if !part.is_empty() {
+                    let sep = if fold { " " } else { "\n" };
+                    if !value.is_empty() {
+                        value.push_str(sep);
+                    }
+                    value.push_str(part);
+                }
+            }
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        let block = value.len() <= 2 && value.starts_with(['>', '|']);
+        // Plain and `>` values fold; `|` and nested lists keep their lines.
+        fold = if block {
+            value.starts_with('>')
+        } else {
+            !value.is_empty()
+        };
+        let value = if block { String::new() } else { unquote(value) };
+        fields.push((index + 1, key.trim().to_string(), value));
+    }
+    None
+}
+
+fn unquote(value: &str) -> String {
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|v| v.strip_suffix(quote))
+        {
+            return inner.replace("\\\"", "\"");
+        }
+    }
+    value.to_string()
+}
+
+/// Local targets of markdown links and images on `line`. URLs, anchors and
+/// `{{skill:...}}` references are left out.
+fn links_in(line: &str) -> Vec<&str> {
+    let mut links = Vec::new();
+    let mut rest = line;
+    while let Some(start) = rest.find("](") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find(')') else {
+            break;
+        };
+        let target = after[..end].trim();
+        // Drop an optional title and angle brackets, then the anchor.
+        let target = target.split_once(' ').map_or(target, |(t, _)| t);
+        let target = target.trim_start_matches('<').trim_end_matches('>');
+        let target = target.split(['#', '?']).next().unwrap_or("");
+        let external =
+            target.contains("://") || target.starts_with("mailto:") || target.starts_with("{{");
+        if !target.is_empty() && !external {
+            links.push(target);
+        }
+        rest = &after[end + 1..];
+    }
+    links
+}
+
+fn is_absolute(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    path.starts_with(['/', '\\', '~'])
+        || (bytes.len() > 2
+            && bytes[0].is_ascii_alphabetic()
+            && bytes[1] == b':'
+            && matches!(bytes[2], b'/' | b'\\'))
+}
+
+fn escapes(path: &str) -> bool {
+    let mut depth = 0i32;
+    for component in Path::new(path).components() {
+        match component {
+            Component::ParentDir => depth -= 1,
+            Component::Normal(_) => depth += 1,
+            _ => {}
+        }
+        if depth < 0 {
+            return true;
+        }
+    }
+    false
+}
+
+/// A path under someone's home directory only works on their machine.
+fn mentions_home_path(line: &str) -> bool {
+    ["/Users/", "/home/", ":\\Users\\", ":/Users/"]
+        .iter()
+        .any(|marker| line.contains(marker))
+}
+
+fn dir_size(dir: &Path) -> u64 {
+    walkdir::WalkDir::new(dir)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|e| !is_ignored(e))
+        .flatten()
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+fn error(code: &str, message: impl Into<String>) -> SkillIssue {
+    issue(IssueSeverity::Error, code, message.into(), None)
+}
+
+fn warning(code: &str, message: impl Into<String>) -> SkillIssue {
+    issue(IssueSeverity::Warning, code, message.into(), None)
+}
+
+fn warning_at(code: &str, message: impl Into<String>, line: usize) -> SkillIssue {
+    issue(IssueSeverity::Warning, code, message.into(), Some(line))
+}
+
+fn issue(severity: IssueSeverity, code: &str, message: String, line: Option<usize>) -> SkillIssue {
+    SkillIssue {
+        severity,
+        code: code.to_string(),
+        message,
+        line,
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/skill_validation.rs"]
+mod tests;
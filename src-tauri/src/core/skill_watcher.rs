@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use notify::{RecursiveMode, Watcher};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use super::app_paths::AppPaths;
+use super::central_repo::resolve_central_repo_path;
+use super::content_hash::hash_dir;
+use super::installer::{local_target_skip_reason, resync_local_target};
+use super::now_ms;
+use super::resource_types::ResourceType;
+use super::skill_events;
+use super::skill_ignore::SkillIgnore;
+use super::skill_metadata::refresh_skill_metadata;
+use super::skill_store::{SkillRecord, SkillStore};
+use super::watch_settings::{get_watch_settings, WatchSettings};
+
+pub const EVENT_SKILL_RESYNCED: &str = "skill-watcher://resynced";
+
+/// How often pending changes are checked against their debounce.
+const TICK: Duration = Duration::from_millis(200);
+/// How often the central repo path and the skill list are re-read.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TargetResyncError {
+    pub tool: String,
+    pub error: String,
+}
+
+/// Copy-mode targets refreshed after a skill changed in the central repo.
+#[derive(Clone, Debug, Serialize)]
+pub struct WatchResync {
+    pub skill_id: String,
+    pub name: String,
+    pub tools: Vec<String>,
+    pub failed: Vec<TargetResyncError>,
+}
+
+/// Watch the central repo and re-copy edited skills to their copy-mode
+/// local targets once changes settle, emitting `skill-watcher://resynced`.
+/// Symlinked targets need nothing; skills kept outside the central repo are
+/// not watched.
+pub fn spawn_skill_watcher(app: AppHandle, store: SkillStore) {
+    let spawned = std::thread::Builder::new()
+        .name("skill-watcher".to_string())
+        .spawn(move || {
+            if let Err(err) = watch_loop(&app, &store) {
+                log::warn!("skill watcher stopped: {:#}", err);
+            }
+        });
+    if let Err(err) = spawned {
+        log::warn!("failed to start skill watcher: {}", err);
+    }
+}
+
+fn watch_loop(app: &AppHandle, store: &SkillStore) -> Result<()> {
+    let paths = app.state::<AppPaths>().inner().clone();
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    let mut root: Option<PathBuf> = None;
+    let mut skills: HashMap<PathBuf, (SkillRecord, WatchSettings)> = HashMap::new();
+    let mut debouncer = Debouncer::default();
+    let mut refreshed_at: Option<Instant> = None;
+
+    loop {
+        let refresh_due = match refreshed_at {
+            Some(at) => at.elapsed() >= REFRESH_INTERVAL,
+            None => true,
+        };
+        if refresh_due {
+            refreshed_at = Some(Instant::now());
+            let current = resolve_central_repo_path(&paths, store).ok();
+            if current != root {
+                if let Some(old) = &root {
+                    let _ = watcher.unwatch(old);
+                }
+                root = current.filter(|p| p.is_dir());
+                if let Some(new) = &root {
+                    if let Err(err) = watcher.watch(new, RecursiveMode::Recursive) {
+                        log::warn!("[skill_watcher] cannot watch {:?}: {}", new, err);
+                        root = None;
+                    }
+                }
+            }
+            skills = store
+                .list_skills()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|s| {
+                    let settings = get_watch_settings(store, &s.id);
+                    (PathBuf::from(&s.central_path), (s, settings))
+                })
+                .collect();
+        }
+
+        match rx.recv_timeout(TICK) {
+            Ok(Ok(event)) => {
+                if let Some(root) = &root {
+                    for path in &event.paths {
+                        if let Some(dir) = changed_skill_dir(root, path, &skills) {
+                            debouncer.touch(dir, Instant::now());
+                        }
+                    }
+                }
+            }
+            Ok(Err(err)) => log::warn!("[skill_watcher] watch error: {}", err),
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => return Ok(()),
+        }
+
+        for dir in debouncer.due(Instant::now(), |dir| skills.get(dir).map(|(_, s)| s)) {
+            let Some((skill, _)) = skills.get(&dir) else {
+                continue;
+            };
+            match resync_copy_targets(store, skill) {
+                Ok(result) if !result.tools.is_empty() || !result.failed.is_empty() => {
+                    if let Err(err) = app.emit(EVENT_SKILL_RESYNCED, &result) {
+                        log::warn!("failed to emit {}: {}", EVENT_SKILL_RESYNCED, err);
+                    }
+                }
+                Ok(_) => {}
+                Err(err) => log::warn!("[skill_watcher] re-sync {} failed: {:#}", skill.name, err),
+            }
+        }
+    }
+}
+
+/// The managed skill folder a change at `path` belongs to, unless the
+/// change is excluded by the skill's watch settings or `.skillignore`.
+fn changed_skill_dir(
+    root: &Path,
+    path: &Path,
+    skills: &HashMap<PathBuf, (SkillRecord, WatchSettings)>,
+) -> Option<PathBuf> {
+    let relative = path.strip_prefix(root).ok()?;
+    let first = relative.components().next()?;
+    let dir = root.join(first);
+    let (_, settings) = skills.get(&dir)?;
+    let inner = relative.strip_prefix(first).ok()?;
+    let inner_str = inner.to_string_lossy().replace('\\', "/");
+    if settings.is_excluded(&inner_str) {
+        return None;
+    }
+    if SkillIgnore::load(&dir).is_excluded(inner, path.is_dir()) {
+        return None;
+    }
+    Some(dir)
+}
+
+/// Re-copy `skill` to its copy-mode local targets that were made from
+/// other content. The skill's recorded hash follows the edit.
+pub fn resync_copy_targets(store: &SkillStore, skill: &SkillRecord) -> Result<WatchResync> {
+    let mut result = WatchResync {
+        skill_id: skill.id.clone(),
+        name: skill.name.clone(),
+        tools: Vec::new(),
+        failed: Vec::new(),
+    };
+    let central_path = PathBuf::from(&skill.central_path);
+    if !central_path.exists() {
+        return Ok(result);
+    }
+    let hash = hash_dir(&central_path)?;
+    if skill.content_hash.as_deref() != Some(hash.as_str()) {
+        let mut updated = store
+            .get_skill_by_id(&skill.id)?
+            .unwrap_or_else(|| skill.clone());
+        updated.content_hash = Some(hash.clone());
+        updated.updated_at = now_ms();
+        store.upsert_skill(&updated)?;
+        refresh_skill_metadata(store, &skill.id, &central_path);
+        skill_events::skill_updated(&skill.id, &skill.name);
+    }
+
+    let kind = ResourceType::parse(&store.get_skill_resource_type(&skill.id)?);
+    for target in store.list_skill_targets(&skill.id)? {
+        if target.mode != "copy" || target.content_hash.as_deref() == Some(hash.as_str()) {
+            continue;
+        }
+        if local_target_skip_reason(store, &target.tool)?.is_some() {
+            continue;
+        }
+        let tool = target.tool.clone();
+        match resync_local_target(store, kind, &central_path, target, &hash, now_ms()) {
+            Ok(_) => result.tools.push(tool),
+            Err(err) => result.failed.push(TargetResyncError {
+                tool,
+                error: format!("{:#}", err),
+            }),
+        }
+    }
+    Ok(result)
+}
+
+/// Holds changed skill folders until they have been quiet for their
+/// debounce and their per-minute budget allows another re-sync.
+#[derive(Default)]
+struct Debouncer {
+    pending: HashMap<PathBuf, Instant>,
+    recent: HashMap<PathBuf, Vec<Instant>>,
+}
+
+impl Debouncer {
+    fn touch(&mut self, dir: PathBuf, now: Instant) {
+        self.pending.insert(dir, now);
+    }
+
+    fn due<'a>(
+        &mut self,
+        now: Instant,
+        settings: impl Fn(&Path) -> Option<&'a WatchSettings>,
+    ) -> Vec<PathBuf> {
+        let mut due = Vec::new();
+        let default = WatchSettings::default();
+        for (dir, last) in &self.pending {
+            let settings = settings(dir).unwrap_or(&default);
+            if now.duration_since(*last) < Duration::from_millis(settings.debounce_ms) {
+                continue;
+            }
+            let recent = self.recent.entry(dir.clone()).or_default();
+            recent.retain(|at| now.duration_since(*at) < RATE_WINDOW);
+            let limit = settings.max_events_per_minute as usize;
+            if limit > 0 && recent.len() >= limit {
+                continue;
+            }
+            recent.push(now);
+            due.push(dir.clone());
+        }
+        for dir in &due {
+            self.pending.remove(dir);
+        }
+        due
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/skill_watcher.rs"]
+mod tests;
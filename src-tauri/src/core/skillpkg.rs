@@ -0,0 +1,409 @@
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::app_paths::AppPaths;
+use super::archive::is_safe_relative_path;
+use super::content_hash::{hash_dir, is_ignored};
+use super::errors::{coded_with, ErrorCode};
+use super::installer::{install_local_skill, InstallResult};
+use super::now_ms;
+use super::skill_drift::record_skill_manifest;
+use super::skill_metadata::refresh_skill_metadata;
+use super::skill_store::{SkillRecord, SkillStore};
+use super::temp_cleanup::operation_temp_dir;
+
+pub const SKILLPKG_FORMAT_VERSION: u32 = 1;
+pub const SKILLPKG_TRUSTED_KEYS_KEY: &str = "skillpkg_trusted_keys";
+
+const MANIFEST_NAME: &str = "manifest.json";
+const CONTENT_PREFIX: &str = "skill/";
+
+// Caps on what a package may make us read into memory. Sizes in the
+// manifest come from the package itself, so they are checked against these
+// before anything is read.
+const MAX_MANIFEST_BYTES: u64 = 4 << 20;
+const MAX_FILE_BYTES: u64 = 64 << 20;
+const MAX_TOTAL_BYTES: u64 = 256 << 20;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SkillPkgSource {
+    pub source_type: String,
+    pub source_ref: Option<String>,
+    pub source_revision: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SkillPkgFile {
+    /// Path relative to the skill root, always `/`-separated.
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+/// `manifest.json` at the root of a `.skillpkg` zip; skill files live under `skill/`.
+///
+/// `signature` is a minisign signature over the manifest serialized without
+/// it, which is exactly the `manifest.json` of an unsigned package, so
+/// `minisign -Sm manifest.json` on the extracted file produces a valid one.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SkillPkgManifest {
+    pub format_version: u32,
+    pub name: String,
+    pub version: Option<String>,
+    pub created_at: i64,
+    pub source: SkillPkgSource,
+    pub content_hash: String,
+    pub files: Vec<SkillPkgFile>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SkillPkgInspection {
+    pub manifest: SkillPkgManifest,
+    /// `unsigned`, `verified` or `untrusted` (signed by a key not in the trusted list).
+    pub signature_status: String,
+}
+
+pub fn get_trusted_keys(store: &SkillStore) -> Vec<String> {
+    store
+        .get_setting(SKILLPKG_TRUSTED_KEYS_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str::<Vec<String>>(&raw).ok())
+        .unwrap_or_default()
+}
+
+pub fn set_trusted_keys(store: &SkillStore, keys: Vec<String>) -> Result<Vec<String>> {
+    let keys: Vec<String> = keys
+        .into_iter()
+        .map(|k| k.trim().to_string())
+        .filter(|k| !k.is_empty())
+        .collect();
+    for key in &keys {
+        minisign_verify::PublicKey::from_base64(key)
+            .map_err(|e| anyhow::anyhow!("invalid minisign public key {}: {}", key, e))?;
+    }
+    store.set_setting(SKILLPKG_TRUSTED_KEYS_KEY, &serde_json::to_string(&keys)?)?;
+    Ok(keys)
+}
+
+pub fn build_manifest(skill: &SkillRecord, dir: &Path) -> Result<SkillPkgManifest> {
+    let mut files = Vec::new();
+    for entry in walkdir::WalkDir::new(dir)
+        .follow_links(false)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| !is_ignored(e))
+    {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let bytes =
+            std::fs::read(entry.path()).with_context(|| format!("read file {:?}", entry.path()))?;
+        files.push(SkillPkgFile {
+            path: relative_path(dir, entry.path())?,
+            size: bytes.len() as u64,
+            sha256: hex::encode(Sha256::digest(&bytes)),
+        });
+    }
+
+    Ok(SkillPkgManifest {
+        format_version: SKILLPKG_FORMAT_VERSION,
+        name: skill.name.clone(),
+        version: skill.source_revision.clone(),
+        created_at: now_ms(),
+        source: SkillPkgSource {
+            source_type: skill.source_type.clone(),
+            source_ref: skill.source_ref.clone(),
+            source_revision: skill.source_revision.clone(),
+        },
+        content_hash: hash_dir(dir)?,
+        files,
+        signature: None,
+    })
+}
+
+/// Write `skill` as a `.skillpkg` archive at `out_path`.
+pub fn export_skillpkg(skill: &SkillRecord, out_path: &Path) -> Result<SkillPkgManifest> {
+    let dir = Path::new(&skill.central_path);
+    if !dir.exists() {
+        anyhow::bail!("skill directory not found: {}", skill.central_path);
+    }
+    let manifest = build_manifest(skill, dir)?;
+    let contents = manifest
+        .files
+        .iter()
+        .map(|f| {
+            std::fs::read(dir.join(&f.path))
+                .with_context(|| format!("read file {:?}", dir.join(&f.path)))
+                .map(|bytes| (f.path.clone(), bytes))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    write_archive(out_path, &manifest, &contents)?;
+    Ok(manifest)
+}
+
+pub fn signing_payload(manifest: &SkillPkgManifest) -> Result<Vec<u8>> {
+    let mut unsigned = manifest.clone();
+    unsigned.signature = None;
+    Ok(serde_json::to_vec_pretty(&unsigned)?)
+}
+
+/// Check the manifest signature against the trusted minisign keys.
+/// A signature that does not verify against the key it names is an error;
+/// a signature from an unknown key is reported as `untrusted`.
+pub fn verify_signature(manifest: &SkillPkgManifest, trusted_keys: &[String]) -> Result<String> {
+    let Some(sig_text) = manifest.signature.as_deref() else {
+        return Ok("unsigned".to_string());
+    };
+    let signature = minisign_verify::Signature::decode(sig_text)
+        .map_err(|e| anyhow::anyhow!("malformed package signature: {}", e))?;
+    let payload = signing_payload(manifest)?;
+
+    for key in trusted_keys {
+        let Ok(pk) = minisign_verify::PublicKey::from_base64(key) else {
+            continue;
+        };
+        match pk.verify(&payload, &signature, false) {
+            Ok(()) => return Ok("verified".to_string()),
+            Err(minisign_verify::Error::UnexpectedKeyId) => continue,
+            Err(e) => anyhow::bail!("package signature is invalid: {}", e),
+        }
+    }
+    Ok("untrusted".to_string())
+}
+
+pub fn read_manifest(pkg_path: &Path) -> Result<SkillPkgManifest> {
+    let mut archive = open_archive(pkg_path)?;
+    read_manifest_from(&mut archive)
+}
+
+pub fn inspect_skillpkg(store: &SkillStore, pkg_path: &Path) -> Result<SkillPkgInspection> {
+    let manifest = read_manifest(pkg_path)?;
+    let signature_status = verify_signature(&manifest, &get_trusted_keys(store))?;
+    Ok(SkillPkgInspection {
+        manifest,
+        signature_status,
+    })
+}
+
+/// Store an externally produced minisign signature in the package manifest.
+pub fn attach_signature(pkg_path: &Path, signature: &str) -> Result<SkillPkgManifest> {
+    let mut archive = open_archive(pkg_path)?;
+    let mut manifest = read_manifest_from(&mut archive)?;
+    let contents = read_contents(&mut archive, &manifest)?;
+    drop(archive);
+
+    minisign_verify::Signature::decode(signature)
+        .map_err(|e| anyhow::anyhow!("malformed signature: {}", e))?;
+    manifest.signature = Some(signature.trim().to_string());
+
+    let tmp = pkg_path.with_extension("skillpkg.tmp");
+    write_archive(&tmp, &manifest, &contents)?;
+    std::fs::rename(&tmp, pkg_path).with_context(|| format!("replace package {:?}", pkg_path))?;
+    Ok(manifest)
+}
+
+/// Unpack the package into `dest`, checking every file against the manifest
+/// hash tree. Returns the manifest, the extracted skill root and the
+/// signature status from [`verify_signature`].
+pub fn extract_skillpkg(
+    pkg_path: &Path,
+    dest: &Path,
+    trusted_keys: &[String],
+) -> Result<(SkillPkgManifest, PathBuf, String)> {
+    let mut archive = open_archive(pkg_path)?;
+    let manifest = read_manifest_from(&mut archive)?;
+    let signature_status = verify_signature(&manifest, trusted_keys)?;
+    let contents = read_contents(&mut archive, &manifest)?;
+
+    let root = dest.join(&manifest.name);
+    for (path, bytes) in contents {
+        let out = root.join(&path);
+        if let Some(parent) = out.parent() {
+            std::fs::create_dir_all(parent).with_context(|| format!("create dir {:?}", parent))?;
+        }
+        std::fs::write(&out, bytes).with_context(|| format!("write file {:?}", out))?;
+    }
+    std::fs::create_dir_all(&root).with_context(|| format!("create dir {:?}", root))?;
+    Ok((manifest, root, signature_status))
+}
+
+/// Install a `.skillpkg` into the central repo, keeping its recorded provenance.
+/// Git-sourced packages stay updatable from git; everything else is tracked
+/// as `skillpkg` pointing at the archive. Packages that are not `verified`
+/// fail with [`ErrorCode::UnverifiedPackage`] unless `confirmed`.
+pub fn install_skillpkg(
+    paths: &AppPaths,
+    store: &SkillStore,
+    pkg_path: &Path,
+    name: Option<String>,
+    confirmed: bool,
+) -> Result<InstallResult> {
+    let temp_dir = operation_temp_dir("skillpkg")?;
+    let (manifest, root, signature_status) =
+        extract_skillpkg(pkg_path, temp_dir.path(), &get_trusted_keys(store))?;
+    if signature_status != "verified" && !confirmed {
+        return Err(coded_with(
+            ErrorCode::UnverifiedPackage,
+            format!("package {} is {}", manifest.name, signature_status),
+            serde_json::json!({ "name": manifest.name, "signature_status": signature_status }),
+        ));
+    }
+
+    let mut result = install_local_skill(paths, store, &root, Some(name.unwrap_or(manifest.name)))?;
+    result.signature_status = Some(signature_status);
+
+    if let Some(mut record) = store.get_skill_by_id(&result.skill_id)? {
+        if manifest.source.source_type == "git" && manifest.source.source_ref.is_some() {
+            record.source_type = manifest.source.source_type;
+            record.source_ref = manifest.source.source_ref;
+            record.source_revision = manifest.source.source_revision;
+        } else {
+            record.source_type = "skillpkg".to_string();
+            record.source_ref = Some(pkg_path.to_string_lossy().to_string());
+            record.source_revision = manifest.version;
+        }
+        store.upsert_skill(&record)?;
+        refresh_skill_metadata(store, &record.id, &result.central_path);
+        record_skill_manifest(store, &record.id, &result.central_path);
+    }
+    Ok(result)
+}
+
+fn open_archive(pkg_path: &Path) -> Result<zip::ZipArchive<std::fs::File>> {
+    let file =
+        std::fs::File::open(pkg_path).with_context(|| format!("open package {:?}", pkg_path))?;
+    zip::ZipArchive::new(file).with_context(|| format!("read package {:?}", pkg_path))
+}
+
+fn read_manifest_from<R: Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+) -> Result<SkillPkgManifest> {
+    let mut raw = String::new();
+    archive
+        .by_name(MANIFEST_NAME)
+        .context("package has no manifest.json")?
+        .take(MAX_MANIFEST_BYTES + 1)
+        .read_to_string(&mut raw)
+        .context("read manifest.json")?;
+    if raw.len() as u64 > MAX_MANIFEST_BYTES {
+        anyhow::bail!("package manifest.json is too large");
+    }
+    let manifest: SkillPkgManifest = serde_json::from_str(&raw).context("parse manifest.json")?;
+    let name = manifest.name.trim();
+    if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\']) {
+        anyhow::bail!("package has an invalid skill name: {:?}", manifest.name);
+    }
+    if manifest.format_version > SKILLPKG_FORMAT_VERSION {
+        anyhow::bail!(
+            "unsupported skillpkg format version {} (max {})",
+            manifest.format_version,
+            SKILLPKG_FORMAT_VERSION
+        );
+    }
+    Ok(manifest)
+}
+
+/// Read every file listed in the manifest and check size + sha256.
+/// Archive entries that the manifest does not list are rejected, and so are
+/// packages whose files exceed the size caps.
+fn read_contents<R: Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    manifest: &SkillPkgManifest,
+) -> Result<Vec<(String, Vec<u8>)>> {
+    for i in 0..archive.len() {
+        let entry = archive.by_index(i).context("read package entry")?;
+        let name = entry.name().to_string();
+        if entry.is_dir() || name == MANIFEST_NAME {
+            continue;
+        }
+        let listed = name
+            .strip_prefix(CONTENT_PREFIX)
+            .map(|rel| manifest.files.iter().any(|f| f.path == rel))
+            .unwrap_or(false);
+        if !listed {
+            anyhow::bail!("package contains unlisted file: {}", name);
+        }
+    }
+
+    let mut total: u64 = 0;
+    for file in &manifest.files {
+        if file.size > MAX_FILE_BYTES {
+            anyhow::bail!("package file {} is too large", file.path);
+        }
+        total = total.saturating_add(file.size);
+    }
+    if total > MAX_TOTAL_BYTES {
+        anyhow::bail!("package is too large to install");
+    }
+
+    let mut contents = Vec::new();
+    for file in &manifest.files {
+        if !is_safe_relative_path(&file.path) {
+            anyhow::bail!("package file has unsafe path: {}", file.path);
+        }
+        let entry = archive
+            .by_name(&format!("{}{}", CONTENT_PREFIX, file.path))
+            .with_context(|| format!("package is missing {}", file.path))?;
+        // Stop one byte past the listed size so a longer entry fails below.
+        let mut bytes = Vec::new();
+        entry
+            .take(file.size.saturating_add(1))
+            .read_to_end(&mut bytes)
+            .with_context(|| format!("read {}", file.path))?;
+        if bytes.len() as u64 != file.size || hex::encode(Sha256::digest(&bytes)) != file.sha256 {
+            anyhow::bail!(
+                "package file {} does not match its manifest hash",
+                file.path
+            );
+        }
+        contents.push((file.path.clone(), bytes));
+    }
+    Ok(contents)
+}
+
+fn write_archive(
+    out_path: &Path,
+    manifest: &SkillPkgManifest,
+    contents: &[(String, Vec<u8>)],
+) -> Result<()> {
+    if let Some(parent) = out_path.parent() {
+        std::fs::create_dir_all(parent).with_context(|| format!("create dir {:?}", parent))?;
+    }
+    let file = std::fs::File::create(out_path)
+        .with_context(|| format!("create package {:?}", out_path))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    zip.start_file(MANIFEST_NAME, options)?;
+    zip.write_all(&serde_json::to_vec_pretty(manifest)?)?;
+    for (path, bytes) in contents {
+        zip.start_file(format!("{}{}", CONTENT_PREFIX, path), options)?;
+        zip.write_all(bytes)?;
+    }
+    zip.finish().context("finish package archive")?;
+    Ok(())
+}
+
+fn relative_path(root: &Path, path: &Path) -> Result<String> {
+    let rel = path
+        .strip_prefix(root)
+        .with_context(|| format!("strip prefix {:?}", path))?;
+    Ok(rel
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/"))
+}
+
+#[cfg(test)]
+#[path = "tests/skillpkg.rs"]
+mod tests;
@@ -0,0 +1,203 @@
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::github_search::GITHUB_API_URL;
+use super::skill_store::SkillStore;
+
+pub const SOURCE_PROVIDERS_KEY: &str = "source_providers";
+pub const GITHUB_PROVIDER_ID: &str = "github";
+const GITHUB_WEB_URL: &str = "https://github.com";
+
+/// A GitHub-compatible host skills are searched, browsed and installed from:
+/// github.com itself or a GitHub Enterprise instance.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceProvider {
+    pub id: String,
+    pub name: String,
+    /// REST API root, e.g. `https://ghe.example.com/api/v3`.
+    pub api_url: String,
+    /// Where repos are browsed and cloned, e.g. `https://ghe.example.com`.
+    pub web_url: String,
+}
+
+impl SourceProvider {
+    pub fn github() -> Self {
+        Self {
+            id: GITHUB_PROVIDER_ID.to_string(),
+            name: "GitHub".to_string(),
+            api_url: GITHUB_API_URL.to_string(),
+            web_url: GITHUB_WEB_URL.to_string(),
+        }
+    }
+
+    /// The `owner/repo[/...]` part of `url` when it points at this host.
+    pub fn repo_path<'a>(&self, url: &'a str) -> Option<&'a str> {
+        let rest = url.get(self.web_url.len()..)?;
+        if !url[..self.web_url.len()].eq_ignore_ascii_case(&self.web_url) {
+            return None;
+        }
+        rest.strip_prefix('/').filter(|r| !r.is_empty())
+    }
+
+    pub fn clone_url(&self, owner: &str, repo: &str) -> String {
+        format!("{}/{}/{}.git", self.web_url, owner, repo)
+    }
+}
+
+static CUSTOM: OnceLock<Mutex<Vec<SourceProvider>>> = OnceLock::new();
+
+fn custom_cell() -> &'static Mutex<Vec<SourceProvider>> {
+    CUSTOM.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+fn read_custom(store: &SkillStore) -> Vec<SourceProvider> {
+    store
+        .get_setting(SOURCE_PROVIDERS_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn write_custom(store: &SkillStore, providers: Vec<SourceProvider>) -> Result<()> {
+    let raw = serde_json::to_string(&providers).context("serialize source providers")?;
+    store.set_setting(SOURCE_PROVIDERS_KEY, &raw)?;
+    *custom_cell().lock().unwrap_or_else(|err| err.into_inner()) = providers;
+    Ok(())
+}
+
+/// Load saved providers into the process-wide table URL parsing uses.
+pub fn load_source_providers(store: &SkillStore) {
+    *custom_cell().lock().unwrap_or_else(|err| err.into_inner()) = read_custom(store);
+}
+
+/// GitHub first, then the configured Enterprise hosts.
+pub fn all_providers() -> Vec<SourceProvider> {
+    let mut providers = vec![SourceProvider::github()];
+    providers.extend(
+        custom_cell()
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .iter()
+            .cloned(),
+    );
+    providers
+}
+
+pub fn list_source_providers(store: &SkillStore) -> Vec<SourceProvider> {
+    let mut providers = vec![SourceProvider::github()];
+    providers.extend(read_custom(store));
+    providers
+}
+
+/// Add or replace (by id) a provider.
+pub fn save_source_provider(
+    store: &SkillStore,
+    provider: SourceProvider,
+) -> Result<SourceProvider> {
+    let provider = validate(provider)?;
+    let mut providers = read_custom(store);
+    if let Some(clash) = providers
+        .iter()
+        .find(|p| p.id != provider.id && p.web_url.eq_ignore_ascii_case(&provider.web_url))
+    {
+        anyhow::bail!(
+            "{} is already configured as '{}'",
+            provider.web_url,
+            clash.id
+        );
+    }
+    match providers.iter_mut().find(|p| p.id == provider.id) {
+        Some(existing) => *existing = provider.clone(),
+        None => providers.push(provider.clone()),
+    }
+    write_custom(store, providers)?;
+    Ok(provider)
+}
+
+/// Remove a provider; returns whether it existed. GitHub itself stays.
+pub fn delete_source_provider(store: &SkillStore, id: &str) -> Result<bool> {
+    let mut providers = read_custom(store);
+    let before = providers.len();
+    providers.retain(|p| p.id != id);
+    let removed = providers.len() != before;
+    if removed {
+        write_custom(store, providers)?;
+    }
+    Ok(removed)
+}
+
+/// The provider with `id`, GitHub when none is given.
+pub fn resolve_provider(id: Option<&str>) -> Result<SourceProvider> {
+    let id = id.map(str::trim).filter(|id| !id.is_empty());
+    let Some(id) = id else {
+        return Ok(SourceProvider::github());
+    };
+    all_providers()
+        .into_iter()
+        .find(|p| p.id == id)
+        .ok_or_else(|| anyhow::anyhow!("unknown source provider: {}", id))
+}
+
+/// The provider hosting `url` and the `owner/repo[/...]` rest of it.
+pub fn provider_for_url(url: &str) -> Option<(SourceProvider, &str)> {
+    match_provider(all_providers(), url)
+}
+
+fn match_provider(providers: Vec<SourceProvider>, url: &str) -> Option<(SourceProvider, &str)> {
+    providers
+        .into_iter()
+        .find_map(|p| p.repo_path(url).map(|rest| (p, rest)))
+}
+
+fn validate(provider: SourceProvider) -> Result<SourceProvider> {
+    let id = provider.id.trim().to_ascii_lowercase();
+    if id.is_empty()
+        || !id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        anyhow::bail!("provider id may only contain letters, digits, '-' and '_'");
+    }
+    if id == GITHUB_PROVIDER_ID {
+        anyhow::bail!("'{}' is the built-in GitHub provider", GITHUB_PROVIDER_ID);
+    }
+    let api_url = normalize_url(&provider.api_url, "API URL")?;
+    let web_url = normalize_url(&provider.web_url, "web URL")?;
+    if web_url.eq_ignore_ascii_case(GITHUB_WEB_URL) {
+        anyhow::bail!("github.com is already available as the built-in provider");
+    }
+    let name = match provider.name.trim() {
+        "" => reqwest::Url::parse(&web_url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_string))
+            .unwrap_or_else(|| id.clone()),
+        name => name.to_string(),
+    };
+    Ok(SourceProvider {
+        id,
+        name,
+        api_url,
+        web_url,
+    })
+}
+
+fn normalize_url(raw: &str, what: &str) -> Result<String> {
+    let trimmed = raw.trim().trim_end_matches('/');
+    let parsed =
+        reqwest::Url::parse(trimmed).with_context(|| format!("invalid {}: {}", what, raw))?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        anyhow::bail!("{} must be an http(s) URL: {}", what, raw);
+    }
+    if parsed.query().is_some() || parsed.fragment().is_some() {
+        anyhow::bail!("{} must not have a query or fragment: {}", what, raw);
+    }
+    Ok(trimmed.to_string())
+}
+
+#[cfg(test)]
+#[path = "tests/source_providers.rs"]
+mod tests;
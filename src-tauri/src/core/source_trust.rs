@@ -0,0 +1,214 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::clawhub_api::get_clawhub_skill;
+use super::errors::{coded_with, ErrorCode};
+use super::installer::parse_github_url;
+use super::skill_store::SkillStore;
+
+pub const SOURCE_TRUST_KEY: &str = "source_trust_policy";
+
+/// Which git owners and ClawHub publishers installs may come from. Owner
+/// entries are `owner` (any host) or `host/owner`; matching ignores case.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SourceTrustPolicy {
+    /// Sources on no allowlist need an explicit confirm. Denylisted ones
+    /// are refused either way.
+    pub require_allowlisted: bool,
+    pub allowed_owners: Vec<String>,
+    pub denied_owners: Vec<String>,
+    pub allowed_publishers: Vec<String>,
+    pub denied_publishers: Vec<String>,
+}
+
+impl SourceTrustPolicy {
+    fn is_empty(&self) -> bool {
+        !self.require_allowlisted
+            && self.denied_owners.is_empty()
+            && self.denied_publishers.is_empty()
+    }
+}
+
+/// Where an install comes from, as far as the policy is concerned.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InstallSource {
+    /// A git repo; `owner` is `None` for URLs without an owner segment.
+    Git { host: String, owner: Option<String> },
+    /// A ClawHub skill; `publisher` is `None` when ClawHub did not say.
+    Clawhub { publisher: Option<String> },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceTrust {
+    Trusted,
+    Untrusted,
+    Denied,
+}
+
+pub fn get_source_trust_policy(store: &SkillStore) -> SourceTrustPolicy {
+    store
+        .get_setting(SOURCE_TRUST_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+pub fn set_source_trust_policy(
+    store: &SkillStore,
+    policy: SourceTrustPolicy,
+) -> Result<SourceTrustPolicy> {
+    let clean = |list: Vec<String>| -> Vec<String> {
+        let mut out: Vec<String> = Vec::new();
+        for entry in list {
+            let entry = entry.trim().trim_matches('/').to_ascii_lowercase();
+            if !entry.is_empty() && !out.contains(&entry) {
+                out.push(entry);
+            }
+        }
+        out
+    };
+    let policy = SourceTrustPolicy {
+        require_allowlisted: policy.require_allowlisted,
+        allowed_owners: clean(policy.allowed_owners),
+        denied_owners: clean(policy.denied_owners),
+        allowed_publishers: clean(policy.allowed_publishers),
+        denied_publishers: clean(policy.denied_publishers),
+    };
+    let raw = serde_json::to_string(&policy).context("serialize source trust policy")?;
+    store.set_setting(SOURCE_TRUST_KEY, &raw)?;
+    Ok(policy)
+}
+
+/// The host and owner of a git URL, GitHub shorthand or `git@host:owner/repo`.
+pub fn git_source(url: &str) -> InstallSource {
+    let clone_url = parse_github_url(url).clone_url;
+    let (host, path) = if let Ok(parsed) = reqwest::Url::parse(&clone_url) {
+        (
+            parsed.host_str().unwrap_or_default().to_string(),
+            parsed.path().to_string(),
+        )
+    } else if let Some((user_host, path)) = clone_url.split_once(':') {
+        let host = user_host.rsplit('@').next().unwrap_or(user_host);
+        (host.to_string(), path.to_string())
+    } else {
+        (String::new(), clone_url.clone())
+    };
+    let mut segments = path.trim_matches('/').split('/');
+    let owner = match (segments.next(), segments.next()) {
+        (Some(owner), Some(_)) if !owner.is_empty() => Some(owner.to_ascii_lowercase()),
+        _ => None,
+    };
+    InstallSource::Git {
+        host: host.to_ascii_lowercase(),
+        owner,
+    }
+}
+
+fn owner_listed(list: &[String], host: &str, owner: &str) -> bool {
+    list.iter().any(|entry| {
+        let entry = entry.to_ascii_lowercase();
+        match entry.rsplit_once('/') {
+            Some((entry_host, entry_owner)) => entry_host == host && entry_owner == owner,
+            None => entry == owner,
+        }
+    })
+}
+
+fn publisher_listed(list: &[String], publisher: &str) -> bool {
+    list.iter()
+        .any(|entry| entry.eq_ignore_ascii_case(publisher))
+}
+
+pub fn evaluate(policy: &SourceTrustPolicy, source: &InstallSource) -> SourceTrust {
+    let (denied, allowed) = match source {
+        InstallSource::Git {
+            host,
+            owner: Some(owner),
+        } => (
+            owner_listed(&policy.denied_owners, host, owner),
+            owner_listed(&policy.allowed_owners, host, owner),
+        ),
+        InstallSource::Clawhub {
+            publisher: Some(publisher),
+        } => (
+            publisher_listed(&policy.denied_publishers, publisher),
+            publisher_listed(&policy.allowed_publishers, publisher),
+        ),
+        _ => (false, false),
+    };
+    if denied {
+        SourceTrust::Denied
+    } else if allowed || !policy.require_allowlisted {
+        SourceTrust::Trusted
+    } else {
+        SourceTrust::Untrusted
+    }
+}
+
+fn describe(source: &InstallSource) -> (&'static str, Option<String>) {
+    match source {
+        InstallSource::Git { host, owner } => (
+            "git",
+            owner.as_ref().map(|owner| format!("{}/{}", host, owner)),
+        ),
+        InstallSource::Clawhub { publisher } => ("clawhub", publisher.clone()),
+    }
+}
+
+/// Refuse denylisted sources, and unlisted ones unless `confirmed`. Both
+/// errors carry `{ "kind", "owner", "source" }`.
+pub fn check_source(
+    store: &SkillStore,
+    source: &InstallSource,
+    raw: &str,
+    confirmed: bool,
+) -> Result<()> {
+    let policy = get_source_trust_policy(store);
+    let trust = evaluate(&policy, source);
+    let (kind, owner) = describe(source);
+    let payload = serde_json::json!({ "kind": kind, "owner": owner, "source": raw });
+    let who = owner.as_deref().unwrap_or("an unknown owner");
+    match trust {
+        SourceTrust::Trusted => Ok(()),
+        SourceTrust::Untrusted if confirmed => Ok(()),
+        SourceTrust::Untrusted => Err(coded_with(
+            ErrorCode::UntrustedSource,
+            format!("{} is from {}, which is not on the allowlist", raw, who),
+            payload,
+        )),
+        SourceTrust::Denied => Err(coded_with(
+            ErrorCode::SourceDenied,
+            format!("{} is from {}, which is on the denylist", raw, who),
+            payload,
+        )),
+    }
+}
+
+pub fn check_git_source(store: &SkillStore, url: &str, confirmed: bool) -> Result<()> {
+    check_source(store, &git_source(url), url, confirmed)
+}
+
+/// Looks up the publisher only when the policy could act on it.
+pub fn check_clawhub_source(store: &SkillStore, slug: &str, confirmed: bool) -> Result<()> {
+    if get_source_trust_policy(store).is_empty() {
+        return Ok(());
+    }
+    let publisher = get_clawhub_skill(slug)
+        .ok()
+        .and_then(|detail| detail.owner_handle)
+        .map(|handle| handle.to_ascii_lowercase());
+    let raw = format!("clawhub://{}", slug);
+    check_source(
+        store,
+        &InstallSource::Clawhub { publisher },
+        &raw,
+        confirmed,
+    )
+}
+
+#[cfg(test)]
+#[path = "tests/source_trust.rs"]
+mod tests;
@@ -0,0 +1,183 @@
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use super::skill_store::SkillStore;
+
+pub const REMOTE_RETRY_ATTEMPTS_KEY: &str = "remote_retry_attempts";
+pub const REMOTE_RETRY_BACKOFF_MS_KEY: &str = "remote_retry_backoff_ms";
+const DEFAULT_RETRY_ATTEMPTS: u32 = 3;
+const DEFAULT_RETRY_BACKOFF_MS: u64 = 500;
+const MAX_RETRY_ATTEMPTS: u32 = 10;
+const MAX_RETRY_BACKOFF_MS: u64 = 30_000;
+
+// libssh2 session errors that mean the transport hiccupped, not that the
+// request itself is wrong.
+const LIBSSH2_ERROR_SOCKET_SEND: i32 = -7;
+const LIBSSH2_ERROR_TIMEOUT: i32 = -9;
+const LIBSSH2_ERROR_SOCKET_DISCONNECT: i32 = -13;
+const LIBSSH2_ERROR_SOCKET_TIMEOUT: i32 = -30;
+const LIBSSH2_ERROR_EAGAIN: i32 = -37;
+const LIBSSH2_ERROR_SOCKET_RECV: i32 = -43;
+
+/// How often remote operations are retried after a transient failure.
+/// `attempts` counts retries, so 0 disables retrying.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    pub attempts: u32,
+    pub backoff_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: DEFAULT_RETRY_ATTEMPTS,
+            backoff_ms: DEFAULT_RETRY_BACKOFF_MS,
+        }
+    }
+}
+
+static POLICY: OnceLock<Mutex<RetryPolicy>> = OnceLock::new();
+
+fn policy_cell() -> &'static Mutex<RetryPolicy> {
+    POLICY.get_or_init(|| Mutex::new(RetryPolicy::default()))
+}
+
+pub fn current_policy() -> RetryPolicy {
+    *policy_cell().lock().unwrap_or_else(|err| err.into_inner())
+}
+
+fn apply_policy(policy: RetryPolicy) {
+    *policy_cell().lock().unwrap_or_else(|err| err.into_inner()) = policy;
+}
+
+pub fn get_retry_policy(store: &SkillStore) -> RetryPolicy {
+    let read = |key| {
+        store
+            .get_setting(key)
+            .ok()
+            .flatten()
+            .and_then(|raw| raw.trim().parse::<u64>().ok())
+    };
+    let defaults = RetryPolicy::default();
+    RetryPolicy {
+        attempts: read(REMOTE_RETRY_ATTEMPTS_KEY)
+            .map(|v| v.min(MAX_RETRY_ATTEMPTS as u64) as u32)
+            .unwrap_or(defaults.attempts),
+        backoff_ms: read(REMOTE_RETRY_BACKOFF_MS_KEY)
+            .map(|v| v.min(MAX_RETRY_BACKOFF_MS))
+            .unwrap_or(defaults.backoff_ms),
+    }
+}
+
+pub fn set_retry_policy(store: &SkillStore, policy: RetryPolicy) -> Result<RetryPolicy> {
+    if policy.attempts > MAX_RETRY_ATTEMPTS {
+        anyhow::bail!(
+            "retry attempts must be between 0 and {}",
+            MAX_RETRY_ATTEMPTS
+        );
+    }
+    if policy.backoff_ms > MAX_RETRY_BACKOFF_MS {
+        anyhow::bail!(
+            "retry backoff must be between 0 and {} ms",
+            MAX_RETRY_BACKOFF_MS
+        );
+    }
+    store.set_setting(REMOTE_RETRY_ATTEMPTS_KEY, &policy.attempts.to_string())?;
+    store.set_setting(REMOTE_RETRY_BACKOFF_MS_KEY, &policy.backoff_ms.to_string())?;
+    apply_policy(policy);
+    Ok(policy)
+}
+
+/// Load the saved policy into the process-wide one used by `remote_sync`.
+pub fn load_retry_policy(store: &SkillStore) {
+    apply_policy(get_retry_policy(store));
+}
+
+/// Exponential backoff: `backoff_ms`, then twice that, and so on, capped.
+pub fn backoff_delay(policy: &RetryPolicy, retry: u32) -> Duration {
+    let factor = 1u64 << retry.min(16);
+    Duration::from_millis(
+        policy
+            .backoff_ms
+            .saturating_mul(factor)
+            .min(MAX_RETRY_BACKOFF_MS),
+    )
+}
+
+/// Whether `err` looks like a dropped or stalled connection worth retrying.
+/// Auth failures and non-zero remote exit codes are not.
+pub fn is_transient(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        if let Some(io) = cause.downcast_ref::<std::io::Error>() {
+            // SFTP file handles report libssh2 failures as io errors.
+            let inner = io.get_ref().and_then(|e| e.downcast_ref::<ssh2::Error>());
+            return inner.is_some_and(ssh_error_is_transient)
+                || matches!(
+                    io.kind(),
+                    std::io::ErrorKind::ConnectionReset
+                        | std::io::ErrorKind::ConnectionAborted
+                        | std::io::ErrorKind::BrokenPipe
+                        | std::io::ErrorKind::TimedOut
+                        | std::io::ErrorKind::UnexpectedEof
+                        | std::io::ErrorKind::WouldBlock
+                );
+        }
+        cause
+            .downcast_ref::<ssh2::Error>()
+            .is_some_and(ssh_error_is_transient)
+    })
+}
+
+fn ssh_error_is_transient(err: &ssh2::Error) -> bool {
+    matches!(
+        err.code(),
+        ssh2::ErrorCode::Session(
+            LIBSSH2_ERROR_SOCKET_SEND
+                | LIBSSH2_ERROR_TIMEOUT
+                | LIBSSH2_ERROR_SOCKET_DISCONNECT
+                | LIBSSH2_ERROR_SOCKET_TIMEOUT
+                | LIBSSH2_ERROR_EAGAIN
+                | LIBSSH2_ERROR_SOCKET_RECV
+        )
+    )
+}
+
+/// Run `op` under the current policy. `op` receives the retry number (0 on
+/// the first try) so it can reopen channels before trying again.
+pub fn with_retry<T>(what: &str, op: impl FnMut(u32) -> Result<T>) -> Result<T> {
+    retry_with(&current_policy(), what, op)
+}
+
+pub fn retry_with<T>(
+    policy: &RetryPolicy,
+    what: &str,
+    mut op: impl FnMut(u32) -> Result<T>,
+) -> Result<T> {
+    let mut retry = 0;
+    loop {
+        match op(retry) {
+            Ok(value) => return Ok(value),
+            Err(err) if retry < policy.attempts && is_transient(&err) => {
+                let delay = backoff_delay(policy, retry);
+                log::warn!(
+                    "[remote_sync] {} failed ({:#}); retry {}/{} in {:?}",
+                    what,
+                    err,
+                    retry + 1,
+                    policy.attempts,
+                    delay
+                );
+                std::thread::sleep(delay);
+                retry += 1;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/ssh_retry.rs"]
+mod tests;
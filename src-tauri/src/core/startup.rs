@@ -0,0 +1,166 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use super::app_paths::AppPaths;
+use super::skill_store::SkillStore;
+use super::tool_adapters::{default_tool_adapters, is_tool_installed};
+
+pub const EVENT_TOOLS_DETECTED: &str = "startup://tools-detected";
+pub const EVENT_CLEANUP_DONE: &str = "startup://cleanup-done";
+pub const EVENT_READY: &str = "startup://ready";
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct StartupStatus {
+    pub store_ready: bool,
+    pub tools_detected: bool,
+    pub cleanup_done: bool,
+    /// True once every background phase has finished.
+    pub ready: bool,
+    pub installed_tools: Vec<String>,
+    pub removed_temp_dirs: usize,
+    pub removed_cache_dirs: usize,
+}
+
+/// Managed state shared between the deferred startup tasks and the
+/// `get_startup_status` command (the splash screen polls it on mount and then
+/// listens for the `startup://*` events).
+#[derive(Default)]
+pub struct StartupState {
+    inner: Mutex<StartupStatus>,
+}
+
+impl StartupState {
+    pub fn snapshot(&self) -> StartupStatus {
+        self.lock().clone()
+    }
+
+    pub fn mark_store_ready(&self) {
+        self.update(|s| s.store_ready = true);
+    }
+
+    pub fn set_installed_tools(&self, installed: Vec<String>) -> StartupStatus {
+        self.update(|s| {
+            s.installed_tools = installed;
+            s.tools_detected = true;
+        })
+    }
+
+    pub fn set_cleanup_result(&self, removed_temp: usize, removed_cache: usize) -> StartupStatus {
+        self.update(|s| {
+            s.removed_temp_dirs = removed_temp;
+            s.removed_cache_dirs = removed_cache;
+            s.cleanup_done = true;
+        })
+    }
+
+    fn update(&self, f: impl FnOnce(&mut StartupStatus)) -> StartupStatus {
+        let mut guard = self.lock();
+        f(&mut guard);
+        guard.ready = guard.store_ready && guard.tools_detected && guard.cleanup_done;
+        guard.clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, StartupStatus> {
+        // A poisoned lock only means a phase panicked mid-update; the flags are still usable.
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+pub fn detect_installed_tools() -> Vec<String> {
+    let mut installed: Vec<String> = default_tool_adapters()
+        .iter()
+        .filter(|adapter| is_tool_installed(adapter).unwrap_or(false))
+        .map(|adapter| adapter.id.as_key().to_string())
+        .collect();
+    installed.dedup();
+    installed
+}
+
+/// Run the startup work that does not need to block the first window:
+/// tool detection, shared repo indexing and temp/cache cleanup. Tool
+/// detection and cleanup emit an event when done.
+pub fn spawn_deferred_startup(app: AppHandle, store: SkillStore) {
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<StartupState>();
+
+        let installed = tauri::async_runtime::spawn_blocking(detect_installed_tools)
+            .await
+            .unwrap_or_default();
+        let status = state.set_installed_tools(installed);
+        emit_phase(&app, EVENT_TOOLS_DETECTED, &status);
+
+        let shared_store = store.clone();
+        let _ = tauri::async_runtime::spawn_blocking(move || {
+            // Pick up skills the share's maintainer added or removed.
+            if let Err(err) = super::central_repo::refresh_shared_skills(&shared_store) {
+                log::warn!("shared central repo refresh failed: {:#}", err);
+            }
+        })
+        .await;
+
+        let paths = app.state::<AppPaths>().inner().clone();
+        let (removed_temp, removed_cache) =
+            tauri::async_runtime::spawn_blocking(move || run_cleanup(&paths, &store))
+                .await
+                .unwrap_or((0, 0));
+        let status = state.set_cleanup_result(removed_temp, removed_cache);
+        emit_phase(&app, EVENT_CLEANUP_DONE, &status);
+
+        if status.ready {
+            emit_phase(&app, EVENT_READY, &status);
+        }
+    });
+}
+
+fn run_cleanup(paths: &AppPaths, store: &SkillStore) -> (usize, usize) {
+    // Best-effort cleanup of our own old git temp directories.
+    // Safety:
+    // - Only deletes directories that match prefix `skills-hub-git-*`
+    // - And contain our marker file `.skills-hub-git-temp`
+    // - And are older than the max age.
+    let mut removed_temp =
+        super::temp_cleanup::cleanup_old_git_temp_dirs(paths, Duration::from_secs(24 * 60 * 60))
+            .unwrap_or(0);
+    // Operation temp dirs whose owning process is gone (crash, force quit).
+    removed_temp +=
+        super::temp_cleanup::cleanup_orphaned_temp_dirs(Duration::from_secs(24 * 60 * 60))
+            .unwrap_or(0);
+    if removed_temp > 0 {
+        log::info!("cleaned up {} old git temp dirs", removed_temp);
+    }
+
+    match super::project_targets::cleanup_missing_projects(store) {
+        Ok(0) => {}
+        Ok(n) => log::info!("forgot {} project targets of deleted projects", n),
+        Err(err) => log::warn!("project target cleanup failed: {:#}", err),
+    }
+
+    if let Err(err) = super::offline_cache::prune_offline_cache(super::offline_cache::MAX_STALE_AGE)
+    {
+        log::warn!("offline cache cleanup failed: {:#}", err);
+    }
+
+    let cleanup_days = super::cache_cleanup::get_git_cache_cleanup_days(store);
+    let max_age =
+        (cleanup_days > 0).then(|| Duration::from_secs(cleanup_days as u64 * 24 * 60 * 60));
+    let max_bytes = super::cache_cleanup::git_cache_max_bytes(store);
+    let removed_cache =
+        super::cache_cleanup::cleanup_git_cache_dirs(paths, max_age, max_bytes).unwrap_or(0);
+    if removed_cache > 0 {
+        log::info!("cleaned up {} git cache dirs", removed_cache);
+    }
+    (removed_temp, removed_cache)
+}
+
+fn emit_phase(app: &AppHandle, event: &str, status: &StartupStatus) {
+    if let Err(err) = app.emit(event, status) {
+        log::warn!("failed to emit {}: {}", event, err);
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/startup.rs"]
+mod tests;
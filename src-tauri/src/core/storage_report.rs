@@ -0,0 +1,92 @@
+use std::path::Path;
+
+use anyhow::Result;
+use serde::Serialize;
+use walkdir::WalkDir;
+
+use super::skill_store::SkillStore;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct DirUsage {
+    pub bytes: u64,
+    pub files: u64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SkillUsage {
+    pub skill_id: String,
+    pub name: String,
+    pub central_path: String,
+    pub bytes: u64,
+    pub files: u64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct StorageReport {
+    pub central_path: String,
+    /// Everything under the central repo, history included.
+    pub total_bytes: u64,
+    /// Managed skills, largest first.
+    pub skills: Vec<SkillUsage>,
+    /// The central repo's own git history (`.git`).
+    pub history_bytes: u64,
+    pub git_cache_bytes: u64,
+    /// Content held for undo after a delete or overwrite.
+    pub trash_bytes: u64,
+}
+
+/// Size and file count of everything under `path`. Symlinks count as
+/// themselves, never as what they point to; a missing path is empty.
+pub fn dir_usage(path: &Path) -> DirUsage {
+    let mut usage = DirUsage::default();
+    for entry in WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        if entry.file_type().is_dir() {
+            continue;
+        }
+        if let Ok(meta) = entry.metadata() {
+            usage.bytes += meta.len();
+            usage.files += 1;
+        }
+    }
+    usage
+}
+
+pub fn build_storage_report(
+    store: &SkillStore,
+    central_root: &Path,
+    git_cache_root: &Path,
+    trash_root: &Path,
+) -> Result<StorageReport> {
+    let mut skills: Vec<SkillUsage> = store
+        .list_skills()?
+        .into_iter()
+        .map(|skill| {
+            let usage = dir_usage(Path::new(&skill.central_path));
+            SkillUsage {
+                skill_id: skill.id,
+                name: skill.name,
+                central_path: skill.central_path,
+                bytes: usage.bytes,
+                files: usage.files,
+            }
+        })
+        .collect();
+    skills.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.name.cmp(&b.name)));
+
+    Ok(StorageReport {
+        central_path: central_root.to_string_lossy().to_string(),
+        total_bytes: dir_usage(central_root).bytes,
+        skills,
+        history_bytes: dir_usage(&central_root.join(".git")).bytes,
+        git_cache_bytes: dir_usage(git_cache_root).bytes,
+        trash_bytes: dir_usage(trash_root).bytes,
+    })
+}
+
+#[cfg(test)]
+#[path = "tests/storage_report.rs"]
+mod tests;
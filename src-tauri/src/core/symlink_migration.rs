@@ -0,0 +1,201 @@
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::app_paths::AppPaths;
+use super::central_repo::resolve_central_repo_path;
+use super::content_hash::hash_dir;
+use super::installer::{install_local_skill, scan_installed, InstallResult};
+use super::now_ms;
+use super::skill_drift::record_skill_manifest;
+use super::skill_metadata::refresh_skill_metadata;
+use super::skill_store::{SkillRecord, SkillStore, SkillTargetRecord};
+use super::sync_engine::{sync_dir_for_tool_with_overwrite, SyncMode};
+use super::sync_plan::mode_name;
+use super::tool_adapters::{default_tool_adapters, scan_tool_dir};
+
+/// Source type for skills adopted in place: `central_path` is the user's own
+/// directory, so it must never be deleted or rewritten by Skills Hub.
+pub const LINKED_SOURCE_TYPE: &str = "linked";
+
+#[derive(Clone, Debug, Serialize)]
+pub struct FarmLink {
+    pub tool: String,
+    pub link_path: PathBuf,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct FarmSkill {
+    pub name: String,
+    pub source_dir: PathBuf,
+    pub links: Vec<FarmLink>,
+}
+
+/// Hand-maintained symlinks whose sources share one parent directory
+/// (typically a dotfiles checkout).
+#[derive(Clone, Debug, Serialize)]
+pub struct SymlinkFarm {
+    pub root: PathBuf,
+    pub skills: Vec<FarmSkill>,
+}
+
+pub fn detect_symlink_farms(paths: &AppPaths, store: &SkillStore) -> Result<Vec<SymlinkFarm>> {
+    let home =
+        dirs::home_dir().ok_or_else(|| anyhow::anyhow!("failed to resolve home directory"))?;
+    let central = resolve_central_repo_path(paths, store)?;
+    let managed: HashSet<PathBuf> = store
+        .list_skills()?
+        .into_iter()
+        .map(|s| canonical(Path::new(&s.central_path)))
+        .collect();
+    detect_symlink_farms_in_home(&home, &central, &managed)
+}
+
+fn detect_symlink_farms_in_home(
+    home: &Path,
+    central: &Path,
+    managed_sources: &HashSet<PathBuf>,
+) -> Result<Vec<SymlinkFarm>> {
+    let central = canonical(central);
+    let mut by_source: BTreeMap<PathBuf, FarmSkill> = BTreeMap::new();
+
+    for adapter in default_tool_adapters() {
+        if !home.join(adapter.relative_detect_dir).exists() {
+            continue;
+        }
+        let dir = home.join(adapter.relative_skills_dir);
+        for detected in scan_tool_dir(&adapter, &dir)? {
+            if !detected.is_link {
+                continue;
+            }
+            // Resolve relative and chained links to the real directory.
+            let Ok(source) = std::fs::canonicalize(&detected.path) else {
+                continue;
+            };
+            if source.starts_with(&central) || managed_sources.contains(&source) {
+                continue;
+            }
+            let entry = by_source
+                .entry(source.clone())
+                .or_insert_with(|| FarmSkill {
+                    name: source
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_else(|| detected.name.clone()),
+                    source_dir: source.clone(),
+                    links: Vec::new(),
+                });
+            entry.links.push(FarmLink {
+                tool: adapter.id.as_key().to_string(),
+                link_path: detected.path,
+            });
+        }
+    }
+
+    let mut farms: BTreeMap<PathBuf, Vec<FarmSkill>> = BTreeMap::new();
+    for skill in by_source.into_values() {
+        let root = skill
+            .source_dir
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| skill.source_dir.clone());
+        farms.entry(root).or_default().push(skill);
+    }
+    Ok(farms
+        .into_iter()
+        .map(|(root, skills)| SymlinkFarm { root, skills })
+        .collect())
+}
+
+/// Take over a hand-made symlink skill.
+///
+/// By default the skill is registered in place (`linked`), leaving files where
+/// they are. With `copy_into_central` the directory is copied into the central
+/// repo and every existing link is re-pointed there; the original folder is
+/// left untouched either way. Existing links become tracked targets.
+pub fn adopt_farm_skill(
+    paths: &AppPaths,
+    store: &SkillStore,
+    skill: &FarmSkill,
+    copy_into_central: bool,
+) -> Result<InstallResult> {
+    if !skill.source_dir.is_dir() {
+        anyhow::bail!("source directory not found: {:?}", skill.source_dir);
+    }
+
+    let result = if copy_into_central {
+        install_local_skill(paths, store, &skill.source_dir, Some(skill.name.clone()))?
+    } else {
+        register_linked_skill(store, skill)?
+    };
+
+    for link in &skill.links {
+        let (target_path, mode) = if copy_into_central {
+            let outcome = sync_dir_for_tool_with_overwrite(
+                &link.tool,
+                &result.central_path,
+                &link.link_path,
+                true,
+            )
+            .with_context(|| format!("re-point {:?}", link.link_path))?;
+            (outcome.target_path, mode_name(&outcome.mode_used))
+        } else {
+            (link.link_path.clone(), "symlink")
+        };
+        store.upsert_skill_target(&SkillTargetRecord {
+            id: Uuid::new_v4().to_string(),
+            skill_id: result.skill_id.clone(),
+            tool: link.tool.clone(),
+            target_path: target_path.to_string_lossy().to_string(),
+            mode: mode.to_string(),
+            status: "ok".to_string(),
+            last_error: None,
+            synced_at: Some(now_ms()),
+            content_hash: result.content_hash.clone(),
+        })?;
+    }
+    Ok(result)
+}
+
+fn register_linked_skill(store: &SkillStore, skill: &FarmSkill) -> Result<InstallResult> {
+    let now = now_ms();
+    let content_hash = hash_dir(&skill.source_dir).ok();
+    let source = skill.source_dir.to_string_lossy().to_string();
+    let record = SkillRecord {
+        id: Uuid::new_v4().to_string(),
+        name: skill.name.clone(),
+        source_type: LINKED_SOURCE_TYPE.to_string(),
+        source_ref: Some(source.clone()),
+        source_revision: None,
+        central_path: source,
+        content_hash: content_hash.clone(),
+        created_at: now,
+        updated_at: now,
+        last_sync_at: Some(now),
+        last_seen_at: now,
+        status: "ok".to_string(),
+        group_name: None,
+    };
+    store.upsert_skill(&record)?;
+    refresh_skill_metadata(store, &record.id, &skill.source_dir);
+    record_skill_manifest(store, &record.id, &skill.source_dir);
+    Ok(InstallResult {
+        content_warnings: scan_installed(&record.name, &skill.source_dir),
+        signature_status: None,
+        skill_id: record.id,
+        name: record.name,
+        central_path: skill.source_dir.clone(),
+        content_hash,
+    })
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+#[path = "tests/symlink_migration.rs"]
+mod tests;
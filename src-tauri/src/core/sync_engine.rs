@@ -1,6 +1,13 @@
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
+use uuid::Uuid;
+
+use super::cloud_folders::cloud_force_copy_provider;
+use super::errors::target_exists;
+use super::skill_format::{convert_skill_dir, tool_format, SkillFormat};
+use super::skill_ignore::SkillIgnore;
+use super::sync_modes::{preferred_mode, SyncModePreference};
 
 #[allow(dead_code)]
 #[derive(Clone, Debug)]
@@ -16,45 +23,81 @@ pub struct SyncOutcome {
     pub mode_used: SyncMode,
     pub target_path: PathBuf,
     pub replaced: bool,
+    /// Why `mode_used` is not a plain symlink, when it is not.
+    pub mode_reason: Option<String>,
 }
 
+/// Link `target` to `source`, falling back to a copy. Skills whose
+/// `.skillignore` leaves something out are always copied, since a link would
+/// expose the excluded files. On Windows a symlink is only tried when the
+/// process may create one (see [`symlinks_available`]); otherwise the folder
+/// gets a junction.
 pub fn sync_dir_hybrid(source: &Path, target: &Path) -> Result<SyncOutcome> {
     if target.exists() {
         if is_same_link(target, source) {
-            return Ok(SyncOutcome {
-                mode_used: SyncMode::Symlink,
-                target_path: target.to_path_buf(),
-                replaced: false,
-            });
+            return Ok(outcome(SyncMode::Symlink, target, None));
         }
-        anyhow::bail!("target already exists: {:?}", target);
+        return Err(target_exists(target));
     }
 
     ensure_parent_dir(target)?;
 
-    if try_link_dir(source, target).is_ok() {
-        return Ok(SyncOutcome {
-            mode_used: SyncMode::Symlink,
-            target_path: target.to_path_buf(),
-            replaced: false,
-        });
+    let ignore = SkillIgnore::load(source);
+    if ignore.excludes_any(source) {
+        copy_into_place(target, |staged| copy_dir_filtered(source, staged, &ignore))?;
+        return Ok(outcome(
+            SyncMode::Copy,
+            target,
+            Some(".skillignore excludes files a link would expose".to_string()),
+        ));
+    }
+
+    let mut reasons = Vec::new();
+    if !symlinks_available() {
+        reasons.push("symlinks need Developer Mode or administrator rights".to_string());
+    } else {
+        match try_link_dir(source, target) {
+            Ok(()) => return Ok(outcome(SyncMode::Symlink, target, None)),
+            Err(err) => reasons.push(format!("{:#}", err)),
+        }
     }
 
     #[cfg(windows)]
-    if try_junction(source, target).is_ok() {
-        return Ok(SyncOutcome {
-            mode_used: SyncMode::Junction,
-            target_path: target.to_path_buf(),
-            replaced: false,
-        });
+    match try_junction(source, target) {
+        Ok(()) => return Ok(outcome(SyncMode::Junction, target, reasons.pop())),
+        Err(err) => reasons.push(format!("{:#}", err)),
     }
 
-    copy_dir_recursive(source, target)?;
-    Ok(SyncOutcome {
-        mode_used: SyncMode::Copy,
+    copy_into_place(target, |staged| copy_dir_filtered(source, staged, &ignore))?;
+    Ok(outcome(SyncMode::Copy, target, Some(reasons.join("; "))))
+}
+
+fn outcome(mode_used: SyncMode, target: &Path, mode_reason: Option<String>) -> SyncOutcome {
+    SyncOutcome {
+        mode_used,
         target_path: target.to_path_buf(),
         replaced: false,
-    })
+        mode_reason,
+    }
+}
+
+/// Whether this process can create symlinks. Always true off Windows; on
+/// Windows that takes Developer Mode or an elevated process, so it is probed
+/// once by creating a link in the temp dir.
+pub fn symlinks_available() -> bool {
+    #[cfg(windows)]
+    {
+        static AVAILABLE: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+        *AVAILABLE.get_or_init(|| {
+            let probe = std::env::temp_dir().join(format!("skills-hub-link-{}", Uuid::new_v4()));
+            let created = std::os::windows::fs::symlink_dir(std::env::temp_dir(), &probe).is_ok();
+            let _ = std::fs::remove_dir(&probe);
+            created
+        })
+    }
+
+    #[cfg(not(windows))]
+    true
 }
 
 pub fn sync_dir_hybrid_with_overwrite(
@@ -65,11 +108,7 @@ pub fn sync_dir_hybrid_with_overwrite(
     let mut did_replace = false;
     if std::fs::symlink_metadata(target).is_ok() {
         if is_same_link(target, source) {
-            return Ok(SyncOutcome {
-                mode_used: SyncMode::Symlink,
-                target_path: target.to_path_buf(),
-                replaced: false,
-            });
+            return Ok(outcome(SyncMode::Symlink, target, None));
         }
 
         if overwrite {
@@ -77,7 +116,7 @@ pub fn sync_dir_hybrid_with_overwrite(
                 .with_context(|| format!("remove existing target {:?}", target))?;
             did_replace = true;
         } else {
-            anyhow::bail!("target already exists: {:?}", target);
+            return Err(target_exists(target));
         }
     }
 
@@ -88,43 +127,209 @@ pub fn sync_dir_hybrid_with_overwrite(
     })
 }
 
-pub fn sync_dir_copy_with_overwrite(
+/// Copy `source` to `target`, letting `finish` adjust the staged copy. The
+/// copy is written next to the target and swapped in once complete (see
+/// [`copy_into_place`]), so an interrupted sync never leaves a half-written
+/// skill behind.
+fn copy_with_overwrite(
+    source: &Path,
+    target: &Path,
+    overwrite: bool,
+    finish: impl FnOnce(&Path) -> Result<()>,
+) -> Result<SyncOutcome> {
+    if std::fs::symlink_metadata(target).is_ok() && !overwrite {
+        return Err(target_exists(target));
+    }
+    let did_replace = copy_into_place(target, |staged| {
+        copy_dir_recursive(source, staged)?;
+        finish(staged)
+    })?;
+
+    Ok(SyncOutcome {
+        mode_used: SyncMode::Copy,
+        target_path: target.to_path_buf(),
+        replaced: did_replace,
+        mode_reason: None,
+    })
+}
+
+/// Sync following the saved mode preference for this skill and tool (see
+/// [`preferred_mode`]).
+pub fn sync_dir_for_tool_with_overwrite(
+    tool_key: &str,
+    source: &Path,
+    target: &Path,
+    overwrite: bool,
+) -> Result<SyncOutcome> {
+    sync_dir_for_tool_with_mode(tool_key, source, target, overwrite, None)
+}
+
+/// Sync in `mode`, or the saved preference when `None`.
+pub fn sync_dir_for_tool_with_mode(
+    tool_key: &str,
+    source: &Path,
+    target: &Path,
+    overwrite: bool,
+    mode: Option<SyncModePreference>,
+) -> Result<SyncOutcome> {
+    let cloud = mode
+        .is_none()
+        .then(|| cloud_force_copy_provider(source))
+        .flatten();
+    let copy_reason = match mode.unwrap_or_else(|| preferred_mode(tool_key, source)) {
+        _ if cloud.is_some() => cloud.map(|provider| {
+            format!(
+                "the central repo is in {}, which breaks links",
+                provider.label()
+            )
+        }),
+        SyncModePreference::Copy => Some("copy mode is preferred".to_string()),
+        SyncModePreference::Link => None,
+        // Cursor 目前不支持软链/junction：强制使用 copy，避免同步后在 Cursor 内不可用。
+        SyncModePreference::Auto if tool_key.eq_ignore_ascii_case("cursor") => {
+            Some("Cursor does not load linked skills".to_string())
+        }
+        SyncModePreference::Auto => None,
+    };
+    // Tools wanting another format need a converted copy whatever the mode.
+    let format = tool_format(tool_key);
+    let copy_reason = if format != SkillFormat::Skill {
+        Some(format!("{} reads skills in another format", tool_key))
+    } else {
+        copy_reason
+    };
+    if let Some(reason) = copy_reason {
+        let mut outcome = sync_dir_copy_for_tool(tool_key, source, target, overwrite)?;
+        outcome.mode_reason = Some(reason);
+        return Ok(outcome);
+    }
+    sync_dir_hybrid_with_overwrite(source, target, overwrite)
+}
+
+/// Copy-mode sync followed by the transform stage: the copy is converted into
+/// the format `tool_key` expects (see [`SkillFormat`]).
+pub fn sync_dir_copy_for_tool(
+    tool_key: &str,
+    source: &Path,
+    target: &Path,
+    overwrite: bool,
+) -> Result<SyncOutcome> {
+    sync_dir_copy_for_tool_then(tool_key, source, target, overwrite, |_| Ok(()))
+}
+
+/// [`sync_dir_copy_for_tool`], running `finish` on the converted copy before
+/// it is moved into place so the tool only ever sees the finished result.
+pub(crate) fn sync_dir_copy_for_tool_then(
+    tool_key: &str,
+    source: &Path,
+    target: &Path,
+    overwrite: bool,
+    finish: impl FnOnce(&Path) -> Result<()>,
+) -> Result<SyncOutcome> {
+    copy_with_overwrite(source, target, overwrite, |staged| {
+        convert_skill_dir(tool_format(tool_key), staged)
+            .with_context(|| format!("convert skill for {}", tool_key))?;
+        finish(staged)
+    })
+}
+
+/// Sync a single-file resource (an agent or command): a file symlink, or a
+/// copy when `force_copy` is set or linking is not possible.
+pub fn sync_file_with_overwrite(
     source: &Path,
     target: &Path,
     overwrite: bool,
+    force_copy: bool,
 ) -> Result<SyncOutcome> {
     let mut did_replace = false;
     if std::fs::symlink_metadata(target).is_ok() {
+        if !force_copy && is_same_link(target, source) {
+            return Ok(outcome(SyncMode::Symlink, target, None));
+        }
         if overwrite {
             remove_path_any(target)
                 .with_context(|| format!("remove existing target {:?}", target))?;
             did_replace = true;
         } else {
-            anyhow::bail!("target already exists: {:?}", target);
+            return Err(target_exists(target));
         }
     }
 
     ensure_parent_dir(target)?;
-    copy_dir_recursive(source, target)?;
-
+    // Files have no junction equivalent; without symlinks they are copied.
+    let reason = if force_copy {
+        "copy mode is preferred".to_string()
+    } else if !symlinks_available() {
+        "symlinks need Developer Mode or administrator rights".to_string()
+    } else {
+        match try_link_file(source, target) {
+            Ok(()) => {
+                return Ok(SyncOutcome {
+                    replaced: did_replace,
+                    ..outcome(SyncMode::Symlink, target, None)
+                })
+            }
+            Err(err) => format!("{:#}", err),
+        }
+    };
+    std::fs::copy(source, target)
+        .with_context(|| format!("copy file {:?} -> {:?}", source, target))?;
     Ok(SyncOutcome {
-        mode_used: SyncMode::Copy,
-        target_path: target.to_path_buf(),
         replaced: did_replace,
+        ..outcome(SyncMode::Copy, target, Some(reason))
     })
 }
 
-pub fn sync_dir_for_tool_with_overwrite(
-    tool_key: &str,
-    source: &Path,
+/// Build `target` in a hidden sibling folder with `fill`, then move it into
+/// place. Whatever was at `target` is moved aside first and removed once
+/// the new content is in; if anything fails, it is left as it was. Returns
+/// whether something was replaced.
+pub(crate) fn copy_into_place(
     target: &Path,
-    overwrite: bool,
-) -> Result<SyncOutcome> {
-    // Cursor 目前不支持软链/junction：强制使用 copy，避免同步后在 Cursor 内不可用。
-    if tool_key.eq_ignore_ascii_case("cursor") {
-        return sync_dir_copy_with_overwrite(source, target, overwrite);
+    fill: impl FnOnce(&Path) -> Result<()>,
+) -> Result<bool> {
+    ensure_parent_dir(target)?;
+    let staged = sibling_path(target, "staged");
+    if let Err(err) = fill(&staged) {
+        let _ = remove_path_any(&staged);
+        return Err(err);
     }
-    sync_dir_hybrid_with_overwrite(source, target, overwrite)
+    swap_into_place(&staged, target)
+}
+
+/// Move `staged` to `target` with renames, replacing what is there. On
+/// failure `staged` is removed and the old target restored.
+pub(crate) fn swap_into_place(staged: &Path, target: &Path) -> Result<bool> {
+    let previous = sibling_path(target, "previous");
+    let replaced = std::fs::symlink_metadata(target).is_ok();
+    if replaced {
+        if let Err(err) = std::fs::rename(target, &previous) {
+            let _ = remove_path_any(staged);
+            return Err(err).with_context(|| format!("move aside {:?}", target));
+        }
+    }
+    if let Err(err) = std::fs::rename(staged, target) {
+        let _ = remove_path_any(staged);
+        if replaced {
+            let _ = std::fs::rename(&previous, target);
+        }
+        return Err(err).with_context(|| format!("move {:?} into place", target));
+    }
+    if replaced {
+        if let Err(err) = remove_path_any(&previous) {
+            log::warn!("[sync_engine] remove {:?} failed: {:#}", previous, err);
+        }
+    }
+    Ok(replaced)
+}
+
+/// `.<name>.<purpose>-<uuid>` next to `path`.
+pub(crate) fn sibling_path(path: &Path, purpose: &str) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.{}-{}", name, purpose, Uuid::new_v4()))
 }
 
 fn ensure_parent_dir(path: &Path) -> Result<()> {
@@ -134,7 +339,7 @@ fn ensure_parent_dir(path: &Path) -> Result<()> {
     Ok(())
 }
 
-fn remove_path_any(path: &Path) -> Result<()> {
+pub(crate) fn remove_path_any(path: &Path) -> Result<()> {
     let meta = match std::fs::symlink_metadata(path) {
         Ok(meta) => meta,
         Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
@@ -181,6 +386,25 @@ fn try_link_dir(source: &Path, target: &Path) -> Result<()> {
     anyhow::bail!("symlink not supported on this platform");
 }
 
+fn try_link_file(source: &Path, target: &Path) -> Result<()> {
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(source, target)
+            .with_context(|| format!("symlink {:?} -> {:?}", target, source))?;
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    {
+        std::os::windows::fs::symlink_file(source, target)
+            .with_context(|| format!("symlink {:?} -> {:?}", target, source))?;
+        return Ok(());
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    anyhow::bail!("symlink not supported on this platform");
+}
+
 #[cfg(windows)]
 fn try_junction(source: &Path, target: &Path) -> Result<()> {
     junction::create(source, target)
@@ -188,11 +412,18 @@ fn try_junction(source: &Path, target: &Path) -> Result<()> {
     Ok(())
 }
 
-fn should_skip_copy(entry: &walkdir::DirEntry) -> bool {
-    entry.file_name() == ".git"
+/// Copy a skill folder, leaving out what its `.skillignore` and the built-in
+/// excludes (`.git`, `node_modules`, `.DS_Store`) name.
+pub fn copy_dir_recursive(source: &Path, target: &Path) -> Result<()> {
+    copy_dir_filtered(source, target, &SkillIgnore::load(source))
 }
 
-pub fn copy_dir_recursive(source: &Path, target: &Path) -> Result<()> {
+/// Copy `source` to `target`, skipping entries `ignore` excludes. Pass
+/// `SkillIgnore::default()` to copy everything. File and folder permissions
+/// are kept, so bundled scripts stay executable. Relative symlinks that stay
+/// inside `source` are copied as symlinks; others are skipped with a warning,
+/// since they would point somewhere else from the target.
+pub fn copy_dir_filtered(source: &Path, target: &Path, ignore: &SkillIgnore) -> Result<()> {
     let profile = std::env::var("SKILLS_HUB_PROFILE_IO")
         .ok()
         .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
@@ -200,22 +431,29 @@ pub fn copy_dir_recursive(source: &Path, target: &Path) -> Result<()> {
     let started = std::time::Instant::now();
     let mut copied_files: u64 = 0;
     let mut copied_bytes: u64 = 0;
+    // Applied last so a read-only folder can still be filled.
+    let mut dir_permissions = Vec::new();
 
     for entry in walkdir::WalkDir::new(source)
         .follow_links(false)
         .into_iter()
-        .filter_entry(|entry| !should_skip_copy(entry))
+        .filter_entry(|entry| {
+            let relative = entry.path().strip_prefix(source).unwrap_or(entry.path());
+            !ignore.is_excluded(relative, entry.file_type().is_dir())
+        })
     {
         let entry = entry?;
-        if should_skip_copy(&entry) {
-            continue;
-        }
         let relative = entry.path().strip_prefix(source)?;
         let target_path = target.join(relative);
 
         if entry.file_type().is_dir() {
             std::fs::create_dir_all(&target_path)
                 .with_context(|| format!("create dir {:?}", target_path))?;
+            if let Ok(meta) = entry.metadata() {
+                dir_permissions.push((target_path, meta.permissions()));
+            }
+        } else if entry.file_type().is_symlink() {
+            copy_symlink(source, entry.path(), &target_path)?;
         } else if entry.file_type().is_file() {
             if let Some(parent) = target_path.parent() {
                 std::fs::create_dir_all(parent)?;
@@ -228,6 +466,10 @@ pub fn copy_dir_recursive(source: &Path, target: &Path) -> Result<()> {
             }
         }
     }
+    for (path, permissions) in dir_permissions.into_iter().rev() {
+        std::fs::set_permissions(&path, permissions)
+            .with_context(|| format!("set permissions on {:?}", path))?;
+    }
     if profile {
         log::info!(
             "[sync_engine] copy_dir_recursive {} files, {} bytes in {}s (src={:?} dst={:?})",
@@ -241,6 +483,71 @@ pub fn copy_dir_recursive(source: &Path, target: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Recreate the symlink at `link` (inside `root`) at `target` when it is
+/// relative and resolves inside `root`.
+fn copy_symlink(root: &Path, link: &Path, target: &Path) -> Result<()> {
+    let dest = std::fs::read_link(link).with_context(|| format!("read link {:?}", link))?;
+    let parent = link
+        .parent()
+        .and_then(|p| p.strip_prefix(root).ok())
+        .unwrap_or(Path::new(""));
+    if !stays_inside(parent, &dest) {
+        log::warn!(
+            "[sync_engine] skipped symlink {:?} -> {:?}: it points outside the skill",
+            link,
+            dest
+        );
+        return Ok(());
+    }
+    ensure_parent_dir(target)?;
+
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(&dest, target)
+        .with_context(|| format!("symlink {:?} -> {:?}", target, dest))?;
+
+    #[cfg(windows)]
+    {
+        let linked = if link.is_dir() {
+            std::os::windows::fs::symlink_dir(&dest, target)
+        } else {
+            std::os::windows::fs::symlink_file(&dest, target)
+        };
+        // Without the symlink privilege, a linked file is copied instead.
+        if linked.is_err() && link.is_file() {
+            std::fs::copy(link, target)
+                .with_context(|| format!("copy file {:?} -> {:?}", link, target))?;
+        } else {
+            linked.with_context(|| format!("symlink {:?} -> {:?}", target, dest))?;
+        }
+    }
+    Ok(())
+}
+
+/// Whether `dest`, relative to `parent` (itself relative to the skill root),
+/// stays inside the skill.
+fn stays_inside(parent: &Path, dest: &Path) -> bool {
+    use std::path::Component;
+
+    let mut depth = parent
+        .components()
+        .filter(|c| matches!(c, Component::Normal(_)))
+        .count();
+    for component in dest.components() {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if depth == 0 {
+                    return false;
+                }
+                depth -= 1;
+            }
+            Component::RootDir | Component::Prefix(_) => return false,
+        }
+    }
+    true
+}
+
 #[cfg(test)]
 #[path = "tests/sync_engine.rs"]
 mod tests;
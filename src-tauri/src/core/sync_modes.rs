@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::skill_store::SkillStore;
+use super::tool_adapters::adapter_by_key;
+
+pub const SYNC_MODE_PREFS_KEY: &str = "sync_mode_prefs";
+
+/// How a skill should land in a tool's directory.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncModePreference {
+    /// Link when possible, copy otherwise; tools known to mishandle links
+    /// (Cursor) get copies.
+    #[default]
+    Auto,
+    /// Link even for tools that get copies by default. Skills that need
+    /// converting, rewritten cross-references or excluded files are still
+    /// copied.
+    Link,
+    /// Always copy.
+    Copy,
+}
+
+/// Saved preferences. A skill's preference overrides its tool's.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncModePrefs {
+    #[serde(default)]
+    pub tools: HashMap<String, SyncModePreference>,
+    /// Keyed by skill id.
+    #[serde(default)]
+    pub skills: HashMap<String, SyncModePreference>,
+}
+
+#[derive(Default)]
+struct ModeTables {
+    tools: HashMap<String, SyncModePreference>,
+    /// Skill preferences keyed by central path, which is all sync sees.
+    skills: HashMap<PathBuf, SyncModePreference>,
+}
+
+static MODES: OnceLock<Mutex<ModeTables>> = OnceLock::new();
+
+fn tables() -> std::sync::MutexGuard<'static, ModeTables> {
+    MODES
+        .get_or_init(|| Mutex::new(ModeTables::default()))
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+}
+
+/// The preference for syncing the skill at `source` to `tool_key`: the
+/// skill's, else the tool's, else `Auto`.
+pub fn preferred_mode(tool_key: &str, source: &Path) -> SyncModePreference {
+    let tables = tables();
+    tables
+        .skills
+        .get(source)
+        .or_else(|| tables.tools.get(tool_key))
+        .copied()
+        .unwrap_or_default()
+}
+
+pub fn get_sync_mode_prefs(store: &SkillStore) -> SyncModePrefs {
+    store
+        .get_setting(SYNC_MODE_PREFS_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+/// Set how skills are synced to `tool_key`; `None` goes back to `Auto`.
+/// Existing targets change on their next sync.
+pub fn set_tool_sync_mode(
+    store: &SkillStore,
+    tool_key: &str,
+    mode: Option<SyncModePreference>,
+) -> Result<SyncModePrefs> {
+    if adapter_by_key(tool_key).is_none() {
+        anyhow::bail!("unknown tool: {}", tool_key);
+    }
+    let mut prefs = get_sync_mode_prefs(store);
+    match mode {
+        Some(mode) => prefs.tools.insert(tool_key.to_string(), mode),
+        None => prefs.tools.remove(tool_key),
+    };
+    save(store, prefs)
+}
+
+/// Set how one skill is synced to every tool; `None` follows the tools'
+/// preferences again.
+pub fn set_skill_sync_mode(
+    store: &SkillStore,
+    skill_id: &str,
+    mode: Option<SyncModePreference>,
+) -> Result<SyncModePrefs> {
+    if store.get_skill_by_id(skill_id)?.is_none() {
+        anyhow::bail!("skill not found: {}", skill_id);
+    }
+    let mut prefs = get_sync_mode_prefs(store);
+    match mode {
+        Some(mode) => prefs.skills.insert(skill_id.to_string(), mode),
+        None => prefs.skills.remove(skill_id),
+    };
+    save(store, prefs)
+}
+
+fn save(store: &SkillStore, mut prefs: SyncModePrefs) -> Result<SyncModePrefs> {
+    // Drop preferences of skills that no longer exist.
+    let skill_ids: Vec<String> = store.list_skills()?.into_iter().map(|s| s.id).collect();
+    prefs.skills.retain(|id, _| skill_ids.contains(id));
+    let raw = serde_json::to_string(&prefs).context("serialize sync mode preferences")?;
+    store.set_setting(SYNC_MODE_PREFS_KEY, &raw)?;
+    load_sync_mode_prefs(store);
+    Ok(prefs)
+}
+
+/// Load the saved preferences into the table sync uses. Run again when
+/// skills move, since skill preferences are looked up by central path.
+pub fn load_sync_mode_prefs(store: &SkillStore) {
+    let prefs = get_sync_mode_prefs(store);
+    let skills = store
+        .list_skills()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|s| {
+            prefs
+                .skills
+                .get(&s.id)
+                .map(|mode| (PathBuf::from(s.central_path), *mode))
+        })
+        .collect();
+    let mut tables = tables();
+    tables.tools = prefs.tools;
+    tables.skills = skills;
+}
+
+#[cfg(test)]
+#[path = "tests/sync_modes.rs"]
+mod tests;
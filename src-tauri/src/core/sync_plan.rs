@@ -0,0 +1,218 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+
+use super::cross_refs::sync_with_cross_refs;
+use super::errors::{coded, ErrorCode};
+use super::skill_store::SkillStore;
+use super::sync_engine::{remove_path_any, sibling_path, swap_into_place, SyncMode, SyncOutcome};
+use super::temp_cleanup::operation_temp_dir;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncAction {
+    /// Nothing at the target yet.
+    Create,
+    /// The target already is what the sync would produce.
+    Unchanged,
+    /// Something else is at the target; syncing needs overwrite.
+    Replace,
+}
+
+/// What syncing a skill folder to one target would do, worked out without
+/// touching the target.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncPlan {
+    pub tool: String,
+    pub source: String,
+    pub target: String,
+    /// `symlink`, `junction` or `copy`.
+    pub mode: String,
+    pub action: SyncAction,
+    /// Files, relative to the target, that would appear, change or go away.
+    pub added: Vec<String>,
+    pub overwritten: Vec<String>,
+    pub deleted: Vec<String>,
+    pub unchanged: usize,
+    /// Where the link currently at the target points.
+    pub existing_link: Option<String>,
+    /// The target as it was when planned; applying refuses if it changed.
+    pub target_fingerprint: Option<String>,
+}
+
+/// Plan syncing `source` to `target` for `tool`. The content is staged in a
+/// temporary folder exactly as a sync would write it (link or copy, format
+/// conversion, cross-references) and compared with what is at `target`.
+pub fn plan_sync(store: &SkillStore, tool: &str, source: &Path, target: &Path) -> Result<SyncPlan> {
+    let staging = operation_temp_dir("sync-plan")?;
+    let staged = staging.path().join("skill");
+    let outcome = sync_with_cross_refs(store, tool, source, &staged, false)?.0;
+    let is_link = !matches!(outcome.mode_used, SyncMode::Copy);
+
+    let existing = std::fs::symlink_metadata(target).ok();
+    let existing_link = std::fs::read_link(target).ok();
+    let mut plan = SyncPlan {
+        tool: tool.to_string(),
+        source: source.to_string_lossy().to_string(),
+        target: target.to_string_lossy().to_string(),
+        mode: mode_name(&outcome.mode_used).to_string(),
+        action: SyncAction::Create,
+        added: Vec::new(),
+        overwritten: Vec::new(),
+        deleted: Vec::new(),
+        unchanged: 0,
+        existing_link: existing_link
+            .as_ref()
+            .map(|p| p.to_string_lossy().to_string()),
+        target_fingerprint: fingerprint(target)?,
+    };
+
+    let new_files = list_files(if is_link { source } else { &staged })?;
+    let old_files = if existing.is_some() {
+        list_files(target).unwrap_or_default()
+    } else {
+        BTreeMap::new()
+    };
+    for (path, hash) in &new_files {
+        match old_files.get(path) {
+            None => plan.added.push(path.clone()),
+            Some(old) if old != hash => plan.overwritten.push(path.clone()),
+            Some(_) => plan.unchanged += 1,
+        }
+    }
+    plan.deleted = old_files
+        .keys()
+        .filter(|path| !new_files.contains_key(*path))
+        .cloned()
+        .collect();
+
+    plan.action = match existing {
+        None => SyncAction::Create,
+        Some(_) if is_link && existing_link.as_deref() == Some(source) => SyncAction::Unchanged,
+        Some(meta)
+            if !is_link
+                && !meta.file_type().is_symlink()
+                && plan.added.is_empty()
+                && plan.overwritten.is_empty()
+                && plan.deleted.is_empty() =>
+        {
+            SyncAction::Unchanged
+        }
+        Some(_) => SyncAction::Replace,
+    };
+    Ok(plan)
+}
+
+/// Fails with [`ErrorCode::SyncPlanStale`] when the target changed after `plan`
+/// was made, so an overwrite is never decided on outdated information.
+pub fn ensure_plan_current(plan: &SyncPlan) -> Result<()> {
+    if fingerprint(Path::new(&plan.target))? != plan.target_fingerprint {
+        return Err(coded(
+            ErrorCode::SyncPlanStale,
+            format!(
+                "{} changed since the plan was made; plan again",
+                plan.target
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Carry out `plan` atomically: the new content is staged next to the
+/// target and swapped in with renames, so the target is either the old
+/// content or the new one, never a half-written mix. Callers check
+/// [`ensure_plan_current`] first.
+pub fn execute_sync_plan(store: &SkillStore, plan: &SyncPlan) -> Result<SyncOutcome> {
+    let source = Path::new(&plan.source);
+    let target = Path::new(&plan.target);
+    if plan.action == SyncAction::Unchanged {
+        return Ok(SyncOutcome {
+            mode_used: mode_from_name(&plan.mode),
+            target_path: target.to_path_buf(),
+            replaced: false,
+            mode_reason: None,
+        });
+    }
+    let staged = sibling_path(target, "staged");
+    let outcome = match sync_with_cross_refs(store, &plan.tool, source, &staged, false) {
+        Ok((outcome, _)) => outcome,
+        Err(err) => {
+            let _ = remove_path_any(&staged);
+            return Err(err);
+        }
+    };
+    let replaced = swap_into_place(&staged, target)?;
+    Ok(SyncOutcome {
+        mode_used: outcome.mode_used,
+        target_path: target.to_path_buf(),
+        replaced,
+        mode_reason: outcome.mode_reason,
+    })
+}
+
+/// Identifies what is at `path`: where a link points, or the content of a
+/// folder or file. `None` when nothing is there.
+fn fingerprint(path: &Path) -> Result<Option<String>> {
+    let Ok(meta) = std::fs::symlink_metadata(path) else {
+        return Ok(None);
+    };
+    if meta.file_type().is_symlink() {
+        let link = std::fs::read_link(path).with_context(|| format!("read link {:?}", path))?;
+        return Ok(Some(format!("link:{}", link.to_string_lossy())));
+    }
+    let mut hasher = Sha256::new();
+    for (file, hash) in list_files(path)? {
+        hasher.update(file.as_bytes());
+        hasher.update(hash.as_bytes());
+    }
+    Ok(Some(hex::encode(hasher.finalize())))
+}
+
+/// Every file under `root` (following a link at the root) with a hash of
+/// its content, keyed by `/`-separated relative path.
+fn list_files(root: &Path) -> Result<BTreeMap<String, String>> {
+    let mut files = BTreeMap::new();
+    for entry in WalkDir::new(root).follow_links(false) {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(root)?;
+        let key = if relative.as_os_str().is_empty() {
+            entry.file_name().to_string_lossy().to_string()
+        } else {
+            relative.to_string_lossy().replace('\\', "/")
+        };
+        let bytes =
+            std::fs::read(entry.path()).with_context(|| format!("read {:?}", entry.path()))?;
+        files.insert(key, hex::encode(Sha256::digest(&bytes)));
+    }
+    Ok(files)
+}
+
+/// How `mode` is named in plans, results and stored target rows.
+pub(crate) fn mode_name(mode: &SyncMode) -> &'static str {
+    match mode {
+        SyncMode::Auto => "auto",
+        SyncMode::Symlink => "symlink",
+        SyncMode::Junction => "junction",
+        SyncMode::Copy => "copy",
+    }
+}
+
+fn mode_from_name(name: &str) -> SyncMode {
+    match name {
+        "symlink" => SyncMode::Symlink,
+        "junction" => SyncMode::Junction,
+        "copy" => SyncMode::Copy,
+        _ => SyncMode::Auto,
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/sync_plan.rs"]
+mod tests;
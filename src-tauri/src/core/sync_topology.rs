@@ -0,0 +1,163 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use super::remote_propagation::parse_remote_target_key;
+use super::skill_store::SkillStore;
+use super::target_variables::custom_target_id;
+use super::tool_adapters::adapter_by_key;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TopologyNode {
+    /// Namespaced id: `skill:<id>`, `tool:<key>`, `custom:<id>` or `remote:<id>`.
+    pub id: String,
+    /// One of `skill`, `tool`, `custom_target`, `remote_host`.
+    pub kind: String,
+    pub label: String,
+    /// Filesystem path (skills, custom targets) or `user@host:port` (remote hosts).
+    pub location: Option<String>,
+    /// For remote custom targets, the id of the remote host node they live on.
+    pub parent: Option<String>,
+    pub status: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TopologyEdge {
+    pub from: String,
+    pub to: String,
+    pub mode: String,
+    pub status: String,
+    /// `ok`, `missing`, `broken_link`, `mismatch` or `unknown` (remote, not probed).
+    pub health: String,
+    pub target_path: String,
+    pub synced_at: Option<i64>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SyncTopology {
+    pub nodes: Vec<TopologyNode>,
+    pub edges: Vec<TopologyEdge>,
+}
+
+/// Build a graph of where every managed skill is deployed.
+/// Only local paths are probed for health; remote targets report `unknown`
+/// so building the map never opens an SSH session.
+pub fn build_sync_topology(store: &SkillStore) -> Result<SyncTopology> {
+    let mut nodes: Vec<TopologyNode> = Vec::new();
+    let mut edges: Vec<TopologyEdge> = Vec::new();
+    let mut seen: HashSet<String> = HashSet::new();
+
+    for host in store.list_remote_hosts()? {
+        let id = format!("remote:{}", host.id);
+        seen.insert(id.clone());
+        nodes.push(TopologyNode {
+            id,
+            kind: "remote_host".to_string(),
+            label: host.label,
+            location: Some(format!("{}@{}:{}", host.username, host.host, host.port)),
+            parent: None,
+            status: Some(host.status),
+        });
+    }
+
+    let custom_targets = store.list_custom_targets()?;
+    for ct in &custom_targets {
+        let id = format!("custom:{}", ct.id);
+        seen.insert(id.clone());
+        nodes.push(TopologyNode {
+            id,
+            kind: "custom_target".to_string(),
+            label: ct.label.clone(),
+            location: Some(ct.path.clone()),
+            parent: ct.remote_host_id.as_ref().map(|h| format!("remote:{}", h)),
+            status: None,
+        });
+    }
+
+    for skill in store.list_skills()? {
+        let skill_node = format!("skill:{}", skill.id);
+        nodes.push(TopologyNode {
+            id: skill_node.clone(),
+            kind: "skill".to_string(),
+            label: skill.name.clone(),
+            location: Some(skill.central_path.clone()),
+            parent: None,
+            status: Some(skill.status.clone()),
+        });
+
+        for target in store.list_skill_targets(&skill.id)? {
+            let remote_host = parse_remote_target_key(&target.tool).map(|(host, _)| host);
+            let to = if let Some(host_id) = remote_host {
+                format!("remote:{}", host_id)
+            } else if let Some(ct_id) = custom_target_id(&target.tool) {
+                format!("custom:{}", ct_id)
+            } else {
+                format!("tool:{}", target.tool)
+            };
+
+            if seen.insert(to.clone()) {
+                // Tool nodes are created lazily so the map only shows tools that hold skills.
+                let label = adapter_by_key(&target.tool)
+                    .map(|a| a.display_name.to_string())
+                    .unwrap_or_else(|| target.tool.clone());
+                nodes.push(TopologyNode {
+                    id: to.clone(),
+                    kind: "tool".to_string(),
+                    label,
+                    location: None,
+                    parent: None,
+                    status: None,
+                });
+            }
+
+            let is_remote = remote_host.is_some()
+                || custom_target_id(&target.tool)
+                    .and_then(|ct_id| custom_targets.iter().find(|ct| ct.id == ct_id))
+                    .map(|ct| ct.remote_host_id.is_some())
+                    .unwrap_or(false);
+            let health = if is_remote {
+                "unknown"
+            } else {
+                local_target_health(Path::new(&skill.central_path), &target.target_path)
+            };
+
+            edges.push(TopologyEdge {
+                from: skill_node.clone(),
+                to,
+                mode: target.mode,
+                status: target.status,
+                health: health.to_string(),
+                target_path: target.target_path,
+                synced_at: target.synced_at,
+                last_error: target.last_error,
+            });
+        }
+    }
+
+    Ok(SyncTopology { nodes, edges })
+}
+
+fn local_target_health(central_path: &Path, target_path: &str) -> &'static str {
+    let target = Path::new(target_path);
+    let meta = match std::fs::symlink_metadata(target) {
+        Ok(meta) => meta,
+        Err(_) => return "missing",
+    };
+    if meta.file_type().is_symlink() {
+        if !target.exists() {
+            return "broken_link";
+        }
+        return match std::fs::read_link(target) {
+            Ok(link) if link == central_path => "ok",
+            _ => "mismatch",
+        };
+    }
+    "ok"
+}
+
+#[cfg(test)]
+#[path = "tests/sync_topology.rs"]
+mod tests;
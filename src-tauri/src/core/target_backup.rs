@@ -0,0 +1,160 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::errors::target_exists;
+use super::now_ms;
+use super::skill_ignore::SkillIgnore;
+use super::skill_store::SkillStore;
+use super::sync_engine::{copy_dir_filtered, copy_into_place, remove_path_any};
+
+/// Folder next to the database holding replaced target content.
+pub const BACKUP_DIR_NAME: &str = "overwritten-targets";
+const META_FILE: &str = "backup.json";
+const CONTENT_NAME: &str = "content";
+
+/// Content that was at a tool's target path before a sync overwrote it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OverwrittenTarget {
+    pub id: String,
+    pub original_path: String,
+    /// Whether a single file (an agent or command) was replaced.
+    pub is_file: bool,
+    pub created_at: i64,
+}
+
+pub fn backup_root(store: &SkillStore) -> PathBuf {
+    store
+        .db_path()
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(std::env::temp_dir)
+        .join(BACKUP_DIR_NAME)
+}
+
+/// Copy the folder or file at `target` into the backup area before a sync
+/// overwrites it. Links and targets Skills Hub made itself are not kept:
+/// their content lives in the central repo.
+pub fn backup_target(store: &SkillStore, target: &Path) -> Result<Option<OverwrittenTarget>> {
+    let meta = match std::fs::symlink_metadata(target) {
+        Ok(meta) => meta,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err).with_context(|| format!("stat {:?}", target)),
+    };
+    if meta.file_type().is_symlink() || std::fs::read_link(target).is_ok() {
+        return Ok(None);
+    }
+    let target_str = target.to_string_lossy().to_string();
+    let managed = store
+        .list_all_skill_target_paths()?
+        .into_iter()
+        .any(|(_, path)| path == target_str);
+    if managed {
+        return Ok(None);
+    }
+
+    let created_at = now_ms();
+    let entry = OverwrittenTarget {
+        id: format!("{}-{}", created_at, Uuid::new_v4()),
+        original_path: target_str,
+        is_file: meta.is_file(),
+        created_at,
+    };
+    let dir = backup_root(store).join(&entry.id);
+    std::fs::create_dir_all(&dir).with_context(|| format!("create backup dir {:?}", dir))?;
+    let result = (|| {
+        copy_content(target, &dir.join(CONTENT_NAME), entry.is_file)?;
+        let raw = serde_json::to_string_pretty(&entry).context("serialize backup")?;
+        std::fs::write(dir.join(META_FILE), raw).context("write backup metadata")
+    })();
+    if let Err(err) = result {
+        let _ = std::fs::remove_dir_all(&dir);
+        return Err(err).with_context(|| format!("back up {:?}", target));
+    }
+    log::info!("[target_backup] kept {:?} as {}", target, entry.id);
+    Ok(Some(entry))
+}
+
+/// Backups, newest first. Unreadable entries are skipped.
+pub fn list_overwritten_targets(store: &SkillStore) -> Result<Vec<OverwrittenTarget>> {
+    let root = backup_root(store);
+    let Ok(entries) = std::fs::read_dir(&root) else {
+        return Ok(Vec::new());
+    };
+    let mut items: Vec<OverwrittenTarget> = entries
+        .flatten()
+        .filter_map(|entry| read_meta(&entry.path()).ok())
+        .collect();
+    items.sort_by_key(|item| std::cmp::Reverse(item.created_at));
+    Ok(items)
+}
+
+/// Put backup `id` back where it came from and drop the backup. Whatever is
+/// there now is replaced only with `overwrite` (fails with
+/// `TargetExists` otherwise) and is backed up in turn. Target rows
+/// for that path are forgotten, since it no longer holds the synced skill.
+pub fn restore_overwritten_target(
+    store: &SkillStore,
+    id: &str,
+    overwrite: bool,
+) -> Result<OverwrittenTarget> {
+    let dir = backup_dir(store, id)?;
+    let entry = read_meta(&dir)?;
+    let target = PathBuf::from(&entry.original_path);
+    if std::fs::symlink_metadata(&target).is_ok() {
+        if !overwrite {
+            return Err(target_exists(&target));
+        }
+        backup_target(store, &target)?;
+    }
+    let content = dir.join(CONTENT_NAME);
+    copy_into_place(&target, |staged| {
+        copy_content(&content, staged, entry.is_file)
+    })?;
+
+    for skill in store.list_skills()? {
+        for row in store.list_skill_targets(&skill.id)? {
+            if row.target_path == entry.original_path {
+                store.delete_skill_target(&skill.id, &row.tool)?;
+            }
+        }
+    }
+    remove_path_any(&dir)?;
+    Ok(entry)
+}
+
+pub fn delete_overwritten_target(store: &SkillStore, id: &str) -> Result<()> {
+    remove_path_any(&backup_dir(store, id)?)
+}
+
+fn backup_dir(store: &SkillStore, id: &str) -> Result<PathBuf> {
+    if id.is_empty() || id.contains(['/', '\\']) || id.starts_with('.') {
+        anyhow::bail!("invalid backup id: {}", id);
+    }
+    let dir = backup_root(store).join(id);
+    if !dir.join(META_FILE).is_file() {
+        anyhow::bail!("backup not found: {}", id);
+    }
+    Ok(dir)
+}
+
+fn read_meta(dir: &Path) -> Result<OverwrittenTarget> {
+    let raw = std::fs::read_to_string(dir.join(META_FILE))
+        .with_context(|| format!("read backup metadata in {:?}", dir))?;
+    serde_json::from_str(&raw).context("parse backup metadata")
+}
+
+/// Nothing is excluded: a backup keeps everything that was there.
+fn copy_content(from: &Path, to: &Path, is_file: bool) -> Result<()> {
+    if is_file {
+        std::fs::copy(from, to).with_context(|| format!("copy {:?} -> {:?}", from, to))?;
+        return Ok(());
+    }
+    copy_dir_filtered(from, to, &SkillIgnore::default())
+}
+
+#[cfg(test)]
+#[path = "tests/target_backup.rs"]
+mod tests;
@@ -0,0 +1,87 @@
+use std::path::Path;
+use std::time::SystemTime;
+
+use serde::Serialize;
+
+use super::content_hash::hash_dir;
+use super::errors::{coded_with, ErrorCode};
+use super::storage_report::dir_usage;
+
+/// What sits at a sync target that was not overwritten. Sent as the
+/// `TARGET_EXISTS` payload so the UI can tell an existing copy of the same
+/// skill from content that genuinely conflicts.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TargetConflict {
+    pub path: String,
+    pub is_symlink: bool,
+    /// Where the symlink points, as stored in the link.
+    pub link_target: Option<String>,
+    /// The symlink resolves to the source being synced.
+    pub points_to_source: bool,
+    pub is_dir: bool,
+    /// Size of the content, following a top-level symlink.
+    pub bytes: u64,
+    pub modified_ms: Option<i64>,
+    /// The content hashes the same as the source; `None` when either side
+    /// could not be read.
+    pub content_matches: Option<bool>,
+}
+
+/// Describe `target`, comparing it with `source`.
+pub fn inspect_target(target: &Path, source: &Path) -> TargetConflict {
+    let link_meta = std::fs::symlink_metadata(target).ok();
+    let is_symlink = link_meta
+        .as_ref()
+        .is_some_and(|meta| meta.file_type().is_symlink());
+    let link_target = if is_symlink {
+        std::fs::read_link(target)
+            .ok()
+            .map(|link| link.to_string_lossy().to_string())
+    } else {
+        None
+    };
+    let resolved = std::fs::canonicalize(target).ok();
+    let points_to_source =
+        is_symlink && resolved.is_some() && resolved == std::fs::canonicalize(source).ok();
+    let content_meta = std::fs::metadata(target).ok().or(link_meta);
+
+    let content_matches = match (&resolved, hash_dir(source)) {
+        (Some(resolved), Ok(source_hash)) => {
+            hash_dir(resolved).ok().map(|hash| hash == source_hash)
+        }
+        _ => None,
+    };
+
+    TargetConflict {
+        path: target.to_string_lossy().to_string(),
+        is_symlink,
+        link_target,
+        points_to_source,
+        is_dir: content_meta.as_ref().is_some_and(|meta| meta.is_dir()),
+        bytes: resolved
+            .as_deref()
+            .map(|path| dir_usage(path).bytes)
+            .unwrap_or(0),
+        modified_ms: content_meta
+            .and_then(|meta| meta.modified().ok())
+            .and_then(|time| time.duration_since(SystemTime::UNIX_EPOCH).ok())
+            .map(|since| since.as_millis() as i64),
+        content_matches,
+    }
+}
+
+/// `target` already exists and was not overwritten; the payload describes
+/// it against `source`.
+pub fn target_conflict(target: &Path, source: &Path) -> anyhow::Error {
+    let conflict = inspect_target(target, source);
+    coded_with(
+        ErrorCode::TargetExists,
+        format!("target already exists: {}", conflict.path),
+        serde_json::to_value(&conflict)
+            .unwrap_or_else(|_| serde_json::json!({ "path": conflict.path })),
+    )
+}
+
+#[cfg(test)]
+#[path = "tests/target_conflict.rs"]
+mod tests;
@@ -0,0 +1,217 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use super::errors::target_exists;
+use super::remote_propagation::parse_remote_target_key;
+use super::skill_store::{SkillRecord, SkillStore};
+use super::target_variables::custom_target_id;
+
+pub const TARGET_NAME_TEMPLATE_KEY: &str = "target_name_template";
+pub const DEFAULT_TARGET_NAME_TEMPLATE: &str = "{name}";
+
+const PLACEHOLDERS: &[&str] = &["name", "source", "group"];
+const MODIFIERS: &[&str] = &["kebab", "lower"];
+
+/// A tracked target whose directory name no longer matches the current template.
+#[derive(Clone, Debug, Serialize)]
+pub struct TargetRename {
+    pub skill_id: String,
+    pub tool: String,
+    pub from: String,
+    pub to: String,
+    /// Set when the target lives on a remote host (remote custom targets).
+    pub remote_host_id: Option<String>,
+}
+
+pub fn get_target_name_template(store: &SkillStore) -> String {
+    store
+        .get_setting(TARGET_NAME_TEMPLATE_KEY)
+        .ok()
+        .flatten()
+        .filter(|raw| validate_template(raw).is_ok())
+        .unwrap_or_else(|| DEFAULT_TARGET_NAME_TEMPLATE.to_string())
+}
+
+pub fn set_target_name_template(store: &SkillStore, template: &str) -> Result<String> {
+    let template = template.trim();
+    validate_template(template)?;
+    store.set_setting(TARGET_NAME_TEMPLATE_KEY, template)?;
+    Ok(template.to_string())
+}
+
+/// Directory name to use for `skill` in tool / custom target directories.
+pub fn target_name_for(store: &SkillStore, skill: &SkillRecord) -> String {
+    render_target_name(&get_target_name_template(store), skill)
+}
+
+/// Templates use `{placeholder}` or `{placeholder:modifier}` tokens, e.g.
+/// `{source}-{name}` or `{name:kebab}`. `{name}` is required so two skills
+/// never collapse onto the same directory.
+pub fn validate_template(template: &str) -> Result<()> {
+    if template.is_empty() {
+        anyhow::bail!("naming template cannot be empty");
+    }
+    if template.contains('/') || template.contains('\\') {
+        anyhow::bail!("naming template cannot contain path separators");
+    }
+    let mut has_name = false;
+    for token in tokens(template)? {
+        let (key, modifier) = split_token(token);
+        if !PLACEHOLDERS.contains(&key) {
+            anyhow::bail!("unknown placeholder {{{}}}", key);
+        }
+        if let Some(m) = modifier {
+            if !MODIFIERS.contains(&m) {
+                anyhow::bail!("unknown modifier :{} in {{{}}}", m, token);
+            }
+        }
+        has_name |= key == "name";
+    }
+    if !has_name {
+        anyhow::bail!("naming template must include {{name}}");
+    }
+    Ok(())
+}
+
+pub fn render_target_name(template: &str, skill: &SkillRecord) -> String {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        let (key, modifier) = split_token(&rest[start + 1..start + len]);
+        let value = match key {
+            "name" => skill.name.as_str(),
+            "source" => skill.source_type.as_str(),
+            "group" => skill.group_name.as_deref().unwrap_or("default"),
+            _ => "",
+        };
+        out.push_str(&match modifier {
+            Some("kebab") => to_kebab(value),
+            Some("lower") => value.to_lowercase(),
+            _ => value.to_string(),
+        });
+        rest = &rest[start + len + 1..];
+    }
+    out.push_str(rest);
+
+    let cleaned = out.replace(['/', '\\'], "-").trim().to_string();
+    if cleaned.is_empty() || cleaned == "." || cleaned == ".." {
+        return skill.name.clone();
+    }
+    cleaned
+}
+
+/// List tracked targets whose last path component differs from what the
+/// current template produces. Remote tool links (`remote:` targets) are
+/// skipped; they pick up the new name on their next sync.
+pub fn plan_target_renames(store: &SkillStore) -> Result<Vec<TargetRename>> {
+    let template = get_target_name_template(store);
+    let custom_targets = store.list_custom_targets()?;
+    let mut plan = Vec::new();
+
+    for skill in store.list_skills()? {
+        let wanted = render_target_name(&template, &skill);
+        for target in store.list_skill_targets(&skill.id)? {
+            if parse_remote_target_key(&target.tool).is_some() {
+                continue;
+            }
+            let remote_host_id = custom_target_id(&target.tool)
+                .and_then(|id| custom_targets.iter().find(|ct| ct.id == id))
+                .and_then(|ct| ct.remote_host_id.clone());
+
+            let to = if remote_host_id.is_some() {
+                match target.target_path.rsplit_once('/') {
+                    Some((parent, current)) if current != wanted => {
+                        format!("{}/{}", parent, wanted)
+                    }
+                    _ => continue,
+                }
+            } else {
+                let path = Path::new(&target.target_path);
+                let current = path.file_name().map(|n| n.to_string_lossy().to_string());
+                match (path.parent(), current) {
+                    (Some(parent), Some(current)) if current != wanted => {
+                        parent.join(&wanted).to_string_lossy().to_string()
+                    }
+                    _ => continue,
+                }
+            };
+
+            plan.push(TargetRename {
+                skill_id: skill.id.clone(),
+                tool: target.tool,
+                from: target.target_path,
+                to,
+                remote_host_id,
+            });
+        }
+    }
+    Ok(plan)
+}
+
+/// Move a local target to its new name and update the tracked path.
+pub fn apply_local_rename(store: &SkillStore, rename: &TargetRename) -> Result<()> {
+    let to = Path::new(&rename.to);
+    if std::fs::symlink_metadata(to).is_ok() {
+        return Err(target_exists(to));
+    }
+    std::fs::rename(&rename.from, to)
+        .with_context(|| format!("rename {} -> {}", rename.from, rename.to))?;
+    record_renamed_target(store, rename)
+}
+
+/// Point the `skill_targets` row at the renamed path.
+pub fn record_renamed_target(store: &SkillStore, rename: &TargetRename) -> Result<()> {
+    if let Some(mut record) = store.get_skill_target(&rename.skill_id, &rename.tool)? {
+        record.target_path = rename.to.clone();
+        store.upsert_skill_target(&record)?;
+    }
+    Ok(())
+}
+
+fn tokens(template: &str) -> Result<Vec<&str>> {
+    let mut found = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        if rest[..start].contains('}') {
+            anyhow::bail!("unmatched '}}' in naming template");
+        }
+        let len = rest[start..]
+            .find('}')
+            .ok_or_else(|| anyhow::anyhow!("unclosed placeholder in naming template"))?;
+        found.push(&rest[start + 1..start + len]);
+        rest = &rest[start + len + 1..];
+    }
+    if rest.contains('}') {
+        anyhow::bail!("unmatched '}}' in naming template");
+    }
+    Ok(found)
+}
+
+fn split_token(token: &str) -> (&str, Option<&str>) {
+    match token.split_once(':') {
+        Some((key, modifier)) => (key.trim(), Some(modifier.trim())),
+        None => (token.trim(), None),
+    }
+}
+
+fn to_kebab(value: &str) -> String {
+    let mut out = String::new();
+    for ch in value.chars() {
+        if ch.is_alphanumeric() {
+            out.extend(ch.to_lowercase());
+        } else if !out.ends_with('-') {
+            out.push('-');
+        }
+    }
+    out.trim_matches('-').to_string()
+}
+
+#[cfg(test)]
+#[path = "tests/target_naming.rs"]
+mod tests;
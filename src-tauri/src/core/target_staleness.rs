@@ -0,0 +1,44 @@
+use super::remote_propagation::parse_remote_target_key;
+use super::skill_store::{CustomTargetRecord, RemoteDeploymentRecord, SkillTargetRecord};
+use super::target_variables::custom_target_id;
+
+/// Whether a deployment lags behind `current_hash`. Symlinked local targets
+/// always follow the central copy, so only copy-mode targets and remote
+/// deployments can go stale. Unknown hashes (rows synced before hashes were
+/// recorded) are never reported as stale.
+pub fn target_is_stale(
+    target: &SkillTargetRecord,
+    current_hash: Option<&str>,
+    custom_targets: &[CustomTargetRecord],
+    deployments: &[RemoteDeploymentRecord],
+) -> bool {
+    let remote_host = match parse_remote_target_key(&target.tool) {
+        Some((host_id, _)) => Some(host_id),
+        None => custom_target_id(&target.tool)
+            .and_then(|id| custom_targets.iter().find(|ct| ct.id == id))
+            .and_then(|ct| ct.remote_host_id.as_deref()),
+    };
+    if let Some(host_id) = remote_host {
+        // Remote tool links and custom targets point at the host's central copy.
+        return deployments
+            .iter()
+            .find(|d| d.host_id == host_id)
+            .is_some_and(|d| deployment_is_stale(d, current_hash));
+    }
+    target.mode == "copy" && hash_differs(target.content_hash.as_deref(), current_hash)
+}
+
+pub fn deployment_is_stale(
+    deployment: &RemoteDeploymentRecord,
+    current_hash: Option<&str>,
+) -> bool {
+    deployment.status == "stale" || hash_differs(deployment.content_hash.as_deref(), current_hash)
+}
+
+fn hash_differs(synced: Option<&str>, current: Option<&str>) -> bool {
+    matches!((synced, current), (Some(a), Some(b)) if a != b)
+}
+
+#[cfg(test)]
+#[path = "tests/target_staleness.rs"]
+mod tests;
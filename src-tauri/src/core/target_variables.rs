@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+
+use super::skill_store::SkillStore;
+
+pub const RECENT_PROJECTS_KEY: &str = "recent_projects";
+pub const PROJECT_VARIABLE: &str = "project";
+const MAX_RECENT_PROJECTS: usize = 10;
+
+/// Variable names (`{project}` -> `project`) used in a custom target path,
+/// in order of first appearance.
+pub fn path_variables(template: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start + 1..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 1..start + 1 + len];
+        if is_variable_name(name) && !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+        rest = &rest[start + 1 + len + 1..];
+    }
+    names
+}
+
+fn is_variable_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Fill in every `{variable}` of a custom target path. Missing or empty
+/// values are an error, as are values that try to climb out with `..`.
+pub fn expand_target_path(template: &str, values: &HashMap<String, String>) -> Result<String> {
+    let mut expanded = template.to_string();
+    for name in path_variables(template) {
+        let value = values
+            .get(&name)
+            .map(|v| v.trim().trim_end_matches(['/', '\\']))
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("missing value for {{{}}}", name))?;
+        if value.split(['/', '\\']).any(|part| part == "..") {
+            anyhow::bail!("invalid value for {{{}}}: {}", name, value);
+        }
+        expanded = expanded.replace(&format!("{{{}}}", name), value);
+    }
+    Ok(expanded)
+}
+
+/// Tool key recorded in `skill_targets`. Templated targets get one row per
+/// expanded root so the same skill can live in several projects.
+pub fn custom_target_key(target_id: &str, expanded_root: Option<&str>) -> String {
+    match expanded_root {
+        Some(root) => format!("custom:{}@{}", target_id, root),
+        None => format!("custom:{}", target_id),
+    }
+}
+
+/// Custom target id from a `custom:<id>` or `custom:<id>@<root>` tool key.
+pub fn custom_target_id(tool: &str) -> Option<&str> {
+    let rest = tool.strip_prefix("custom:")?;
+    Some(rest.split_once('@').map_or(rest, |(id, _)| id))
+}
+
+pub fn list_recent_projects(store: &SkillStore) -> Vec<String> {
+    store
+        .get_setting(RECENT_PROJECTS_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+/// Move `project` to the front of the recent list, keeping the newest few.
+pub fn remember_project(store: &SkillStore, project: &str) -> Result<Vec<String>> {
+    let project = project.trim().trim_end_matches(['/', '\\']);
+    let mut recent = list_recent_projects(store);
+    recent.retain(|p| p != project);
+    recent.insert(0, project.to_string());
+    recent.truncate(MAX_RECENT_PROJECTS);
+    store.set_setting(RECENT_PROJECTS_KEY, &serde_json::to_string(&recent)?)?;
+    Ok(recent)
+}
+
+pub fn forget_project(store: &SkillStore, project: &str) -> Result<Vec<String>> {
+    let mut recent = list_recent_projects(store);
+    recent.retain(|p| p != project);
+    store.set_setting(RECENT_PROJECTS_KEY, &serde_json::to_string(&recent)?)?;
+    Ok(recent)
+}
+
+#[cfg(test)]
+#[path = "tests/target_variables.rs"]
+mod tests;
@@ -0,0 +1,203 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use super::content_hash::hash_dir;
+use super::installer::{local_target_skip_reason, resync_local_target};
+use super::now_ms;
+use super::resource_types::{resource_file, ResourceType};
+use super::skill_events;
+use super::skill_store::{SkillStore, SkillTargetRecord};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TargetState {
+    Ok,
+    /// Nothing at the target path.
+    Missing,
+    /// A link whose destination is gone.
+    Dangling,
+    /// A link or folder that no longer leads to the skill's central copy.
+    Replaced,
+    /// A copy made from older content than the central copy holds now.
+    Stale,
+}
+
+impl TargetState {
+    /// Value stored in `skill_targets.status`.
+    fn status(self) -> &'static str {
+        match self {
+            TargetState::Ok => "ok",
+            TargetState::Missing => "missing",
+            TargetState::Dangling => "dangling",
+            TargetState::Replaced => "replaced",
+            TargetState::Stale => "stale",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TargetCheck {
+    pub skill_id: String,
+    pub skill_name: String,
+    pub tool: String,
+    pub target_path: String,
+    pub mode: String,
+    pub state: TargetState,
+    /// Why the target is broken; also stored as the row's `last_error`.
+    pub detail: Option<String>,
+    pub repaired: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TargetVerifyReport {
+    pub checked: usize,
+    /// Remote targets and targets of tools that are not installed.
+    pub skipped: usize,
+    /// Targets that are not ok; healthy ones are only counted.
+    pub problems: Vec<TargetCheck>,
+}
+
+/// Check every local target row: links must still lead to the skill's
+/// central copy and copies must come from its current content. Each row's
+/// `status`/`last_error` is updated to match. With `repair`, broken targets
+/// are synced again in the same pass.
+pub fn verify_all_targets(store: &SkillStore, repair: bool) -> Result<TargetVerifyReport> {
+    let mut report = TargetVerifyReport {
+        checked: 0,
+        skipped: 0,
+        problems: Vec::new(),
+    };
+    for skill in store.list_skills()? {
+        let targets = store.list_skill_targets(&skill.id)?;
+        if targets.is_empty() {
+            continue;
+        }
+        let central_path = PathBuf::from(&skill.central_path);
+        let current_hash = hash_dir(&central_path).ok().or(skill.content_hash.clone());
+        let kind = ResourceType::parse(&store.get_skill_resource_type(&skill.id)?);
+        let link_source = link_source(kind, &central_path);
+
+        for target in targets {
+            if let Some(reason) = local_target_skip_reason(store, &target.tool)? {
+                report.skipped += 1;
+                log::debug!(
+                    "[target_verify] skip {} / {}: {}",
+                    skill.name,
+                    target.tool,
+                    reason
+                );
+                continue;
+            }
+            report.checked += 1;
+            let (state, detail) = check_target(&target, &link_source, current_hash.as_deref());
+            if state == TargetState::Ok {
+                if target.status != "ok" || target.last_error.is_some() {
+                    store.upsert_skill_target(&SkillTargetRecord {
+                        status: "ok".to_string(),
+                        last_error: None,
+                        ..target
+                    })?;
+                }
+                continue;
+            }
+
+            let mut check = TargetCheck {
+                skill_id: skill.id.clone(),
+                skill_name: skill.name.clone(),
+                tool: target.tool.clone(),
+                target_path: target.target_path.clone(),
+                mode: target.mode.clone(),
+                state,
+                detail: detail.clone(),
+                repaired: false,
+                error: None,
+            };
+            let marked = SkillTargetRecord {
+                status: state.status().to_string(),
+                last_error: detail,
+                ..target.clone()
+            };
+            store.upsert_skill_target(&marked)?;
+            skill_events::target_broken(&marked, state.status(), marked.last_error.as_deref());
+            if repair {
+                if !central_path.exists() {
+                    check.error = Some("central copy is missing".to_string());
+                } else {
+                    let hash = current_hash.clone().unwrap_or_default();
+                    match resync_local_target(store, kind, &central_path, marked, &hash, now_ms()) {
+                        Ok(_) => check.repaired = true,
+                        Err(err) => {
+                            let message = format!("{:#}", err);
+                            store.upsert_skill_target(&SkillTargetRecord {
+                                status: "error".to_string(),
+                                last_error: Some(message.clone()),
+                                ..target
+                            })?;
+                            check.error = Some(message);
+                        }
+                    }
+                }
+            }
+            report.problems.push(check);
+        }
+    }
+    Ok(report)
+}
+
+/// What links to a skill point at: agents and commands link to their single
+/// file, skills to the folder.
+pub(crate) fn link_source(kind: ResourceType, central_path: &Path) -> PathBuf {
+    if kind == ResourceType::Skill {
+        central_path.to_path_buf()
+    } else {
+        resource_file(central_path).unwrap_or_else(|_| central_path.to_path_buf())
+    }
+}
+
+pub(crate) fn check_target(
+    target: &SkillTargetRecord,
+    link_source: &Path,
+    current_hash: Option<&str>,
+) -> (TargetState, Option<String>) {
+    let path = Path::new(&target.target_path);
+    let Ok(meta) = std::fs::symlink_metadata(path) else {
+        return (TargetState::Missing, Some("target not found".to_string()));
+    };
+    if target.mode == "copy" {
+        if meta.file_type().is_symlink() {
+            return (
+                TargetState::Replaced,
+                Some("expected a copy, found a link".to_string()),
+            );
+        }
+        return match (target.content_hash.as_deref(), current_hash) {
+            (Some(synced), Some(current)) if synced != current => (
+                TargetState::Stale,
+                Some("central copy changed since the last sync".to_string()),
+            ),
+            _ => (TargetState::Ok, None),
+        };
+    }
+
+    let Ok(resolved) = std::fs::canonicalize(path) else {
+        return (
+            TargetState::Dangling,
+            Some("link destination not found".to_string()),
+        );
+    };
+    let expected = std::fs::canonicalize(link_source).unwrap_or(link_source.to_path_buf());
+    if resolved != expected {
+        return (
+            TargetState::Replaced,
+            Some(format!("resolves to {}", resolved.display())),
+        );
+    }
+    (TargetState::Ok, None)
+}
+
+#[cfg(test)]
+#[path = "tests/target_verify.rs"]
+mod tests;
@@ -1,11 +1,138 @@
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 use std::time::{Duration, SystemTime};
 
 use anyhow::{Context, Result};
-use tauri::Manager;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::app_paths::AppPaths;
+use super::now_ms;
 
 const TEMP_PREFIX: &str = "skills-hub-git-";
 const TEMP_MARKER: &str = ".skills-hub-git-temp";
+const OWNER_MARKER: &str = ".skills-hub-owner.json";
+
+/// Who created an operation temp dir. `instance` tells this run of the app
+/// apart from an earlier one that happened to get the same PID.
+#[derive(Debug, Serialize, Deserialize)]
+struct TempOwner {
+    pid: u32,
+    instance: String,
+    purpose: String,
+    created_at: i64,
+}
+
+/// A scratch directory for one operation, removed when dropped. Dirs left
+/// behind by a crash are reaped by [`cleanup_orphaned_temp_dirs`].
+#[derive(Debug)]
+pub struct OperationTempDir {
+    path: PathBuf,
+}
+
+impl OperationTempDir {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for OperationTempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+pub fn temp_root() -> PathBuf {
+    std::env::temp_dir().join("skills-hub-tmp")
+}
+
+/// Create `<temp root>/<purpose>-<uuid>` owned by this process.
+pub fn operation_temp_dir(purpose: &str) -> Result<OperationTempDir> {
+    operation_temp_dir_in(&temp_root(), purpose)
+}
+
+fn operation_temp_dir_in(root: &Path, purpose: &str) -> Result<OperationTempDir> {
+    let path = root.join(format!("{}-{}", purpose, Uuid::new_v4()));
+    std::fs::create_dir_all(&path).with_context(|| format!("create temp dir {:?}", path))?;
+    let dir = OperationTempDir { path };
+    let owner = TempOwner {
+        pid: std::process::id(),
+        instance: instance_id().to_string(),
+        purpose: purpose.to_string(),
+        created_at: now_ms(),
+    };
+    let marker = dir.path.join(OWNER_MARKER);
+    std::fs::write(&marker, serde_json::to_vec(&owner)?)
+        .with_context(|| format!("failed to write marker {:?}", marker))?;
+    Ok(dir)
+}
+
+/// Remove operation temp dirs whose owner is gone. Dirs of this run are never
+/// touched; a dir whose PID is still alive (another instance, or a reused
+/// PID) is only removed once older than `max_age`.
+pub fn cleanup_orphaned_temp_dirs(max_age: Duration) -> Result<usize> {
+    cleanup_orphaned_temp_dirs_in(&temp_root(), max_age)
+}
+
+fn cleanup_orphaned_temp_dirs_in(root: &Path, max_age: Duration) -> Result<usize> {
+    let rd = match std::fs::read_dir(root) {
+        Ok(v) => v,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(err) => return Err(err).with_context(|| format!("read temp dir {:?}", root)),
+    };
+    let max_age_ms = max_age.as_millis() as i64;
+    let mut removed = 0usize;
+    for entry in rd.flatten() {
+        let path = entry.path();
+        // Safety: only delete directories carrying an owner marker we wrote.
+        let Some(owner) = std::fs::read(path.join(OWNER_MARKER))
+            .ok()
+            .and_then(|raw| serde_json::from_slice::<TempOwner>(&raw).ok())
+        else {
+            continue;
+        };
+        if owner.instance == instance_id() {
+            continue;
+        }
+        if process_alive(owner.pid) && now_ms() - owner.created_at < max_age_ms {
+            continue;
+        }
+        if std::fs::remove_dir_all(&path).is_ok() {
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+fn instance_id() -> &'static str {
+    static INSTANCE: OnceLock<String> = OnceLock::new();
+    INSTANCE.get_or_init(|| Uuid::new_v4().to_string())
+}
+
+/// Best effort; when it cannot be determined the process counts as alive.
+#[cfg(target_os = "linux")]
+fn process_alive(pid: u32) -> bool {
+    Path::new("/proc").join(pid.to_string()).exists()
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn process_alive(pid: u32) -> bool {
+    std::process::Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map_or(true, |status| status.success())
+}
+
+#[cfg(windows)]
+fn process_alive(pid: u32) -> bool {
+    std::process::Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {}", pid), "/NH"])
+        .output()
+        .map_or(true, |out| {
+            String::from_utf8_lossy(&out.stdout).contains(&pid.to_string())
+        })
+}
 
 #[allow(dead_code)]
 pub fn mark_temp_dir(dir: &Path) -> Result<()> {
@@ -18,16 +145,8 @@ pub fn mark_temp_dir(dir: &Path) -> Result<()> {
     Ok(())
 }
 
-pub fn cleanup_old_git_temp_dirs<R: tauri::Runtime>(
-    app: &tauri::AppHandle<R>,
-    max_age: Duration,
-) -> Result<usize> {
-    let cache_dir = app
-        .path()
-        .app_cache_dir()
-        .context("failed to resolve app cache dir")?;
-
-    cleanup_old_git_temp_dirs_in(&cache_dir, max_age)
+pub fn cleanup_old_git_temp_dirs(paths: &AppPaths, max_age: Duration) -> Result<usize> {
+    cleanup_old_git_temp_dirs_in(&paths.cache_dir, max_age)
 }
 
 fn cleanup_old_git_temp_dirs_in(cache_dir: &Path, max_age: Duration) -> Result<usize> {
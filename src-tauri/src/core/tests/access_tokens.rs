@@ -0,0 +1,54 @@
+use crate::core::skill_store::SkillStore;
+
+use super::{authorize, create_access_token, list_access_tokens, revoke_access_token, Scope};
+
+fn make_store() -> (tempfile::TempDir, SkillStore) {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    (dir, store)
+}
+
+#[test]
+fn open_until_the_first_token_exists() {
+    let (_dir, store) = make_store();
+    assert!(authorize(&store, None, &Scope::ALL).is_ok());
+
+    create_access_token(&store, "ci", &[Scope::Read]).unwrap();
+    let err = authorize(&store, None, &[Scope::Read]).unwrap_err();
+    assert!(err.to_string().contains("access token is required"));
+}
+
+#[test]
+fn tokens_grant_only_their_scopes() {
+    let (_dir, store) = make_store();
+    let (token, secret) =
+        create_access_token(&store, "ci", &[Scope::Read, Scope::Sync, Scope::Read]).unwrap();
+    assert_eq!(token.scopes, vec![Scope::Read, Scope::Sync]);
+    assert!(!token.secret_sha256.contains(&secret));
+
+    assert!(authorize(&store, Some(&secret), &[Scope::Read, Scope::Sync]).is_ok());
+    let err = authorize(&store, Some(&secret), &[Scope::Install, Scope::Remote]).unwrap_err();
+    assert_eq!(err.to_string(), "token ci lacks the install, remote scope");
+    assert!(authorize(&store, Some("shub_wrong"), &[Scope::Read]).is_err());
+}
+
+#[test]
+fn revoked_tokens_stop_working() {
+    let (_dir, store) = make_store();
+    let (_, keep) = create_access_token(&store, "admin", &Scope::ALL).unwrap();
+    let (ci, secret) = create_access_token(&store, "ci", &[Scope::Read]).unwrap();
+    assert!(create_access_token(&store, "ci", &[Scope::Read]).is_err());
+
+    assert_eq!(revoke_access_token(&store, &ci.id).unwrap().name, "ci");
+    assert_eq!(list_access_tokens(&store).len(), 1);
+    assert!(authorize(&store, Some(&secret), &[Scope::Read]).is_err());
+    assert!(authorize(&store, Some(&keep), &[Scope::Read]).is_ok());
+    assert!(revoke_access_token(&store, "ci").is_err());
+}
+
+#[test]
+fn parses_scope_names() {
+    assert_eq!(Scope::parse("remote").unwrap(), Scope::Remote);
+    assert!(Scope::parse("admin").is_err());
+}
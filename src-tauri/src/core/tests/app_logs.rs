@@ -0,0 +1,97 @@
+use std::fs;
+
+use log::{Level, LevelFilter};
+
+use crate::core::skill_store::SkillStore;
+
+use super::{get_log_level, parse_log, read_recent_logs, set_log_level, LogEntry};
+
+fn entry(time: &str, level: &str, message: &str) -> LogEntry {
+    LogEntry {
+        timestamp: format!("2024-05-01 {}", time),
+        level: level.to_string(),
+        target: "skills_hub_lib::core::installer".to_string(),
+        message: message.to_string(),
+    }
+}
+
+#[test]
+fn parses_records_and_folds_continuation_lines() {
+    let text = "stray line before any record\n\
+[2024-05-01][10:00:00][skills_hub_lib::core::installer][INFO] cloning repo\n\
+[2024-05-01][10:00:01][skills_hub_lib::core::installer][ERROR] clone failed: boom\n\
+caused by: [network] timeout\n";
+    assert_eq!(
+        parse_log(text),
+        vec![
+            entry("10:00:00", "info", "cloning repo"),
+            entry(
+                "10:00:01",
+                "error",
+                "clone failed: boom\ncaused by: [network] timeout"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn recent_logs_span_rotated_files_and_filter_by_level() {
+    let dir = tempfile::tempdir().unwrap();
+    let record = |time: &str, level: &str, message: &str| {
+        format!(
+            "[2024-05-01][{}][skills_hub_lib::core::installer][{}] {}\n",
+            time, level, message
+        )
+    };
+    fs::write(
+        dir.path().join("skills-hub_2024-05-01_09-00-00.log"),
+        record("09:00:00", "WARN", "oldest"),
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("skills-hub_2024-05-01_09-30-00.log"),
+        record("09:30:00", "INFO", "older") + &record("09:31:00", "DEBUG", "noise"),
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("skills-hub.log"),
+        record("10:00:00", "ERROR", "newest"),
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("other.log"),
+        record("11:00:00", "ERROR", "x"),
+    )
+    .unwrap();
+
+    let all = read_recent_logs(dir.path(), 10, Level::Trace).unwrap();
+    let messages: Vec<&str> = all.iter().map(|e| e.message.as_str()).collect();
+    assert_eq!(messages, vec!["oldest", "older", "noise", "newest"]);
+
+    let tail = read_recent_logs(dir.path(), 2, Level::Info).unwrap();
+    let messages: Vec<&str> = tail.iter().map(|e| e.message.as_str()).collect();
+    assert_eq!(messages, vec!["older", "newest"]);
+
+    let missing = dir.path().join("missing");
+    assert!(read_recent_logs(&missing, 10, Level::Info)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn log_level_setting_round_trips_and_rejects_unknown_levels() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+
+    assert_eq!(get_log_level(&store), LevelFilter::Info);
+    assert_eq!(set_log_level(&store, "Debug").unwrap(), LevelFilter::Debug);
+    assert_eq!(
+        store.get_setting("log_level").unwrap().as_deref(),
+        Some("debug")
+    );
+    assert_eq!(get_log_level(&store), LevelFilter::Debug);
+    assert!(set_log_level(&store, "loud").is_err());
+    assert_eq!(get_log_level(&store), LevelFilter::Debug);
+    set_log_level(&store, "info").unwrap();
+}
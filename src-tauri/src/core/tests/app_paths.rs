@@ -0,0 +1,25 @@
+use super::*;
+
+#[test]
+fn identifier_matches_the_tauri_config() {
+    let config: serde_json::Value =
+        serde_json::from_str(include_str!("../../../tauri.conf.json")).unwrap();
+    assert_eq!(config["identifier"].as_str(), Some(APP_IDENTIFIER));
+}
+
+#[test]
+fn resolves_folders_without_a_running_app() {
+    let paths = AppPaths::for_identifier(APP_IDENTIFIER).unwrap();
+    assert_eq!(
+        paths.data_dir,
+        dirs::data_dir().unwrap().join(APP_IDENTIFIER)
+    );
+    assert_eq!(
+        paths.cache_dir,
+        dirs::cache_dir().unwrap().join(APP_IDENTIFIER)
+    );
+    assert_eq!(
+        paths.config_dir,
+        dirs::config_dir().unwrap().join(APP_IDENTIFIER)
+    );
+}
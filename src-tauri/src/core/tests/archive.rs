@@ -0,0 +1,76 @@
+use std::io::Write;
+
+use super::*;
+
+fn zip_with(entries: &[(&str, &[u8])], symlink: Option<(&str, &str)>) -> Vec<u8> {
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = zip::write::SimpleFileOptions::default();
+    for (name, body) in entries {
+        zip.start_file(*name, options).unwrap();
+        zip.write_all(body).unwrap();
+    }
+    if let Some((name, target)) = symlink {
+        zip.add_symlink(name, target, options).unwrap();
+    }
+    zip.finish().unwrap().into_inner()
+}
+
+#[test]
+fn rejects_paths_leaving_the_dir() {
+    assert!(is_safe_relative_path("SKILL.md"));
+    assert!(is_safe_relative_path("refs/a.md"));
+    assert!(!is_safe_relative_path("../evil"));
+    assert!(!is_safe_relative_path("refs/../../evil"));
+    assert!(!is_safe_relative_path("..\\evil"));
+    assert!(!is_safe_relative_path("/etc/passwd"));
+    assert!(!is_safe_relative_path("./a"));
+    assert!(!is_safe_relative_path(""));
+}
+
+#[test]
+fn extracts_regular_files_and_skips_symlinks() {
+    let dir = tempfile::tempdir().unwrap();
+    let dest = dir.path().join("out");
+    let bytes = zip_with(
+        &[
+            ("SKILL.md", b"# ok"),
+            ("refs/a.md", b"a"),
+            (".hidden", b"h"),
+        ],
+        Some(("link", "/etc/passwd")),
+    );
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes)).unwrap();
+    let written = extract_zip(&mut archive, &dest, |name| name.starts_with('.')).unwrap();
+    assert_eq!(written, 2);
+    assert_eq!(std::fs::read(dest.join("refs/a.md")).unwrap(), b"a");
+    assert!(!dest.join(".hidden").exists());
+    assert!(std::fs::symlink_metadata(dest.join("link")).is_err());
+}
+
+#[test]
+fn zip_slip_entries_are_an_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let dest = dir.path().join("out");
+    let bytes = zip_with(&[("SKILL.md", b"# ok"), ("../escaped.txt", b"x")], None);
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes)).unwrap();
+    assert!(extract_zip(&mut archive, &dest, |_| false).is_err());
+    assert!(!dir.path().join("escaped.txt").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn symlinked_dirs_inside_dest_do_not_redirect_writes() {
+    let dir = tempfile::tempdir().unwrap();
+    let dest = dir.path().join("out");
+    let outside = dir.path().join("outside");
+    std::fs::create_dir_all(&dest).unwrap();
+    std::fs::create_dir_all(&outside).unwrap();
+    std::os::unix::fs::symlink(&outside, dest.join("refs")).unwrap();
+
+    assert!(safe_entry_path(&dest, "refs/a.md").is_err());
+    assert!(safe_entry_path(&dest, "refs/deep/er/a.md").is_err());
+    assert!(!outside.join("deep").exists());
+    assert!(safe_entry_path(&dest, "SKILL.md").is_ok());
+    assert!(safe_entry_path(&dest, "new/dir/a.md").is_ok());
+    assert!(dest.join("new/dir").is_dir());
+}
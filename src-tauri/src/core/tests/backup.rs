@@ -0,0 +1,136 @@
+use std::io::Write;
+
+use crate::core::skill_store::{SkillRecord, SkillStore};
+
+use super::*;
+
+fn file_entry(path: &str, bytes: &[u8]) -> BackupFile {
+    BackupFile {
+        path: path.to_string(),
+        size: bytes.len() as u64,
+        sha256: hex::encode(Sha256::digest(bytes)),
+    }
+}
+
+fn db_bytes(dir: &Path) -> Vec<u8> {
+    let db_path = dir.join("source.db");
+    let store = SkillStore::new(db_path.clone());
+    store.ensure_schema().unwrap();
+    store
+        .upsert_skill(&SkillRecord {
+            id: "s1".to_string(),
+            name: "demo".to_string(),
+            source_type: "local".to_string(),
+            source_ref: None,
+            source_revision: None,
+            central_path: "/central/demo".to_string(),
+            content_hash: None,
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: "ok".to_string(),
+            group_name: None,
+        })
+        .unwrap();
+    std::fs::read(db_path).unwrap()
+}
+
+/// Write a backup whose manifest describes `listed` but whose archive holds
+/// `stored` (so tests can tamper with either side).
+fn write_backup(out: &Path, db: &[u8], listed: &[(&str, &[u8])], stored: &[(&str, &[u8])]) {
+    let manifest = BackupManifest {
+        format_version: BACKUP_FORMAT_VERSION,
+        created_at: 42,
+        db_sha256: hex::encode(Sha256::digest(db)),
+        files: listed.iter().map(|(p, b)| file_entry(p, b)).collect(),
+    };
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(out).unwrap());
+    let options = zip::write::SimpleFileOptions::default();
+    zip.start_file(MANIFEST_NAME, options).unwrap();
+    zip.write_all(&serde_json::to_vec(&manifest).unwrap())
+        .unwrap();
+    zip.start_file(DB_ENTRY, options).unwrap();
+    zip.write_all(db).unwrap();
+    for (path, bytes) in stored {
+        zip.start_file(format!("{}{}", CONTENT_PREFIX, path), options)
+            .unwrap();
+        zip.write_all(bytes).unwrap();
+    }
+    zip.finish().unwrap();
+}
+
+#[test]
+fn intact_backup_verifies() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = db_bytes(dir.path());
+    let files: Vec<(&str, &[u8])> = vec![
+        ("demo/SKILL.md", b"# demo"),
+        ("demo/refs/a.md", b"a"),
+        ("other/SKILL.md", b"# other"),
+    ];
+    let out = dir.path().join("backup.zip");
+    write_backup(&out, &db, &files, &files);
+
+    let report = verify_backup(&out).unwrap();
+    assert!(report.ok, "{:?}", report.problems);
+    assert_eq!(report.created_at, 42);
+    assert_eq!(report.files_checked, 3);
+    assert_eq!(report.files_sampled, 3);
+    assert_eq!(report.skill_count, Some(1));
+}
+
+#[test]
+fn tampered_files_and_database_are_reported() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = db_bytes(dir.path());
+    let out = dir.path().join("backup.zip");
+    write_backup(
+        &out,
+        &db,
+        &[("demo/SKILL.md", b"# demo"), ("demo/gone.md", b"x")],
+        &[("demo/SKILL.md", b"# edited"), ("extra.md", b"y")],
+    );
+    let report = verify_backup(&out).unwrap();
+    assert!(!report.ok);
+    assert_eq!(report.files_checked, 0);
+    assert_eq!(report.skill_count, Some(1));
+    let problems = report.problems.join("\n");
+    assert!(problems.contains("demo/SKILL.md does not match"));
+    assert!(problems.contains("missing central/demo/gone.md"));
+    assert!(problems.contains("unlisted file in backup: central/extra.md"));
+
+    // A database that is not SQLite fails even with a matching hash.
+    write_backup(&out, b"not a database", &[], &[]);
+    let report = verify_backup(&out).unwrap();
+    assert_eq!(report.skill_count, None);
+    assert!(report.problems[0].starts_with("database:"));
+
+    std::fs::write(&out, b"not a zip").unwrap();
+    assert!(verify_backup(&out).is_err());
+}
+
+#[test]
+fn created_backup_verifies() {
+    let dir = tempfile::tempdir().unwrap();
+    db_bytes(dir.path());
+    let store = SkillStore::new(dir.path().join("source.db"));
+    let central = dir.path().join("central");
+    std::fs::create_dir_all(central.join("demo/refs")).unwrap();
+    std::fs::create_dir_all(central.join(".git")).unwrap();
+    std::fs::write(central.join("demo/SKILL.md"), "# demo").unwrap();
+    std::fs::write(central.join("demo/refs/a.md"), "a").unwrap();
+    std::fs::write(central.join(".git/HEAD"), "ref: refs/heads/main").unwrap();
+
+    let out = dir.path().join("out/backup.zip");
+    let manifest = create_backup(&store, &central, &out).unwrap();
+    let paths: Vec<&str> = manifest.files.iter().map(|f| f.path.as_str()).collect();
+    assert_eq!(paths, ["demo/SKILL.md", "demo/refs/a.md"]);
+    assert!(!out.with_extension("partial").exists());
+
+    let report = verify_backup(&out).unwrap();
+    assert!(report.ok, "{:?}", report.problems);
+    assert_eq!(report.created_at, manifest.created_at);
+    assert_eq!(report.files_checked, 2);
+    assert_eq!(report.skill_count, Some(1));
+}
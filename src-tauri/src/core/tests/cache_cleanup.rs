@@ -0,0 +1,169 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::core::skill_store::SkillStore;
+
+use super::{
+    build_git_cache_report, cleanup_git_cache_dirs_in, evict_git_cache_entry, get_git_cache_max_mb,
+    git_cache_max_bytes, now_ms, read_cache_meta, set_git_cache_max_mb, touch_cache_entry,
+    write_cache_meta, RepoCacheMeta, CACHE_DIR_NAME, DEFAULT_GIT_CACHE_MAX_MB,
+};
+
+fn cached_repo(cache_dir: &Path, name: &str, bytes: usize, last_used_ms: i64) -> PathBuf {
+    let repo = cache_dir.join(CACHE_DIR_NAME).join(name);
+    fs::create_dir_all(repo.join(".git")).unwrap();
+    fs::write(repo.join("blob"), vec![0u8; bytes]).unwrap();
+    write_cache_meta(
+        &repo,
+        &RepoCacheMeta {
+            last_fetched_ms: last_used_ms,
+            last_used_ms,
+            head: None,
+            repo_url: None,
+            branch: None,
+        },
+    );
+    repo
+}
+
+#[test]
+fn evicts_least_recently_used_until_under_cap() {
+    let dir = tempfile::tempdir().unwrap();
+    let now = now_ms();
+    let oldest = cached_repo(dir.path(), "oldest", 4000, now - 3000);
+    let middle = cached_repo(dir.path(), "middle", 4000, now - 2000);
+    let newest = cached_repo(dir.path(), "newest", 4000, now - 1000);
+
+    let removed = cleanup_git_cache_dirs_in(dir.path(), None, Some(9000), None).unwrap();
+    assert_eq!(removed, 1);
+    assert!(!oldest.exists());
+    assert!(middle.exists());
+    assert!(newest.exists());
+}
+
+#[test]
+fn cap_never_evicts_the_kept_clone() {
+    let dir = tempfile::tempdir().unwrap();
+    let now = now_ms();
+    let kept = cached_repo(dir.path(), "kept", 4000, now - 3000);
+    let other = cached_repo(dir.path(), "other", 4000, now - 1000);
+
+    let removed = cleanup_git_cache_dirs_in(dir.path(), None, Some(5000), Some(&kept)).unwrap();
+    assert_eq!(removed, 1);
+    assert!(kept.exists());
+    assert!(!other.exists());
+}
+
+#[test]
+fn age_cleanup_uses_last_use_not_last_fetch() {
+    let dir = tempfile::tempdir().unwrap();
+    let day_ms = 24 * 60 * 60 * 1000;
+    let now = now_ms();
+    let stale = cached_repo(dir.path(), "stale", 10, now - 10 * day_ms);
+    let reused = cached_repo(dir.path(), "reused", 10, now - 10 * day_ms);
+    touch_cache_entry(&reused);
+    assert!(read_cache_meta(&reused).unwrap().last_used_ms >= now);
+    let not_a_clone = dir.path().join(CACHE_DIR_NAME).join("partial");
+    fs::create_dir_all(&not_a_clone).unwrap();
+
+    let max_age = Some(Duration::from_secs(7 * 24 * 60 * 60));
+    let removed = cleanup_git_cache_dirs_in(dir.path(), max_age, None, None).unwrap();
+    assert_eq!(removed, 1);
+    assert!(!stale.exists());
+    assert!(reused.exists());
+    assert!(not_a_clone.exists());
+
+    let removed = cleanup_git_cache_dirs_in(dir.path(), Some(Duration::ZERO), None, None).unwrap();
+    assert_eq!(removed, 1);
+    assert!(!reused.exists());
+
+    let missing = dir.path().join("missing");
+    assert_eq!(
+        cleanup_git_cache_dirs_in(&missing, Some(Duration::ZERO), None, None).unwrap(),
+        0
+    );
+}
+
+#[test]
+fn max_mb_setting_validates_and_zero_disables_cap() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+
+    assert_eq!(get_git_cache_max_mb(&store), DEFAULT_GIT_CACHE_MAX_MB);
+    assert_eq!(set_git_cache_max_mb(&store, 512).unwrap(), 512);
+    assert_eq!(git_cache_max_bytes(&store), Some(512 * 1024 * 1024));
+    assert!(set_git_cache_max_mb(&store, -1).is_err());
+    assert_eq!(get_git_cache_max_mb(&store), 512);
+    set_git_cache_max_mb(&store, 0).unwrap();
+    assert_eq!(git_cache_max_bytes(&store), None);
+
+    store.set_setting("git_cache_max_mb", "lots").unwrap();
+    assert_eq!(get_git_cache_max_mb(&store), DEFAULT_GIT_CACHE_MAX_MB);
+}
+
+#[test]
+fn report_lists_clones_largest_first() {
+    let dir = tempfile::tempdir().unwrap();
+    let now = now_ms();
+    let small = cached_repo(dir.path(), "small", 100, now - 1000);
+    let big = cached_repo(dir.path(), "big", 5000, now - 2000);
+    let mut meta = read_cache_meta(&big).unwrap();
+    meta.repo_url = Some("https://github.com/acme/big.git".to_string());
+    meta.branch = Some("main".to_string());
+    write_cache_meta(&big, &meta);
+    fs::create_dir_all(dir.path().join(CACHE_DIR_NAME).join("partial")).unwrap();
+
+    let root = dir.path().join(CACHE_DIR_NAME);
+    let report = build_git_cache_report(&root, Some(1 << 20)).unwrap();
+    let keys: Vec<&str> = report.entries.iter().map(|e| e.key.as_str()).collect();
+    assert_eq!(keys, vec!["big", "small"]);
+    assert_eq!(
+        report.entries[0].repo_url.as_deref(),
+        Some("https://github.com/acme/big.git")
+    );
+    assert_eq!(report.entries[0].branch.as_deref(), Some("main"));
+    assert_eq!(report.entries[0].last_used_ms, now - 2000);
+    assert!(report.entries[1].repo_url.is_none());
+    assert_eq!(
+        report.total_bytes,
+        report.entries.iter().map(|e| e.bytes).sum::<u64>()
+    );
+    assert_eq!(report.max_bytes, Some(1 << 20));
+    assert!(small.exists());
+
+    let missing = build_git_cache_report(&dir.path().join("missing"), None).unwrap();
+    assert!(missing.entries.is_empty());
+}
+
+#[test]
+fn evicts_every_branch_of_one_repo() {
+    let dir = tempfile::tempdir().unwrap();
+    let now = now_ms();
+    let tag = |path: &Path, url: &str| {
+        let mut meta = read_cache_meta(path).unwrap();
+        meta.repo_url = Some(url.to_string());
+        write_cache_meta(path, &meta);
+    };
+    let main = cached_repo(dir.path(), "main", 10, now);
+    let dev = cached_repo(dir.path(), "dev", 10, now);
+    let other = cached_repo(dir.path(), "other", 10, now);
+    tag(&main, "https://github.com/acme/huge.git");
+    tag(&dev, "https://github.com/acme/huge");
+    tag(&other, "https://github.com/acme/small.git");
+
+    let root = dir.path().join(CACHE_DIR_NAME);
+    assert_eq!(
+        evict_git_cache_entry(&root, "https://github.com/acme/huge/").unwrap(),
+        2
+    );
+    assert!(!main.exists());
+    assert!(!dev.exists());
+    assert!(other.exists());
+    assert_eq!(
+        evict_git_cache_entry(&root, "https://github.com/acme/huge").unwrap(),
+        0
+    );
+    assert!(evict_git_cache_entry(&root, "  ").is_err());
+}
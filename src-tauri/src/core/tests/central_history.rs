@@ -0,0 +1,111 @@
+use std::fs;
+
+use super::*;
+
+fn store_with_skill(central: &Path) -> (tempfile::TempDir, SkillStore) {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    store
+        .upsert_skill(&SkillRecord {
+            id: "s1".to_string(),
+            name: "pdf".to_string(),
+            source_type: "local".to_string(),
+            source_ref: None,
+            source_revision: None,
+            central_path: central.join("pdf").to_string_lossy().to_string(),
+            content_hash: None,
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: "ok".to_string(),
+            group_name: None,
+        })
+        .unwrap();
+    (dir, store)
+}
+
+#[test]
+fn records_history_and_restores_a_revision() {
+    let central = tempfile::tempdir().unwrap();
+    let skill = central.path().join("pdf");
+    fs::create_dir_all(&skill).unwrap();
+    fs::write(skill.join("SKILL.md"), "v1").unwrap();
+    fs::create_dir_all(central.path().join("cloned/.git")).unwrap();
+    fs::create_dir_all(central.path().join(".skills-hub-update-x")).unwrap();
+    fs::write(central.path().join(".skills-hub-update-x/tmp"), "x").unwrap();
+    ensure_history_repo(central.path()).unwrap();
+    let (_db, store) = store_with_skill(central.path());
+
+    fs::write(skill.join("SKILL.md"), "v2").unwrap();
+    fs::write(skill.join("extra.md"), "new").unwrap();
+    record_change(&store, &skill, "Update pdf");
+    // Nothing changed: no empty commit.
+    record_change(&store, &skill, "Update pdf");
+
+    let history = get_skill_history(&store, "s1").unwrap();
+    let messages: Vec<_> = history.iter().map(|r| r.message.as_str()).collect();
+    assert_eq!(messages, vec!["Update pdf", "Start central repo history"]);
+
+    let repo = open_history_repo(central.path()).unwrap();
+    let head = repo.head().unwrap().peel_to_tree().unwrap();
+    assert!(head.get_path(Path::new("cloned")).is_err());
+    assert!(head.get_path(Path::new(".skills-hub-update-x")).is_err());
+
+    let restored = restore_skill_revision(&store, "s1", &history[1].commit).unwrap();
+    assert_eq!(fs::read_to_string(skill.join("SKILL.md")).unwrap(), "v1");
+    assert!(!skill.join("extra.md").exists());
+    assert_eq!(restored.content_hash, Some(hash_dir(&skill).unwrap()));
+    assert_eq!(
+        get_skill_history(&store, "s1").unwrap()[0].message,
+        format!("Restore pdf to {}", &history[1].commit[..7])
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn restore_brings_back_links_and_a_fresh_baseline() {
+    let central = tempfile::tempdir().unwrap();
+    let skill = central.path().join("pdf");
+    fs::create_dir_all(&skill).unwrap();
+    fs::write(skill.join("SKILL.md"), "v1").unwrap();
+    std::os::unix::fs::symlink("SKILL.md", skill.join("README.md")).unwrap();
+    ensure_history_repo(central.path()).unwrap();
+    let (_db, store) = store_with_skill(central.path());
+
+    fs::remove_file(skill.join("README.md")).unwrap();
+    fs::write(skill.join("SKILL.md"), "v2").unwrap();
+    record_change(&store, &skill, "Update pdf");
+    let history = get_skill_history(&store, "s1").unwrap();
+
+    store.set_skill_locked("s1", true).unwrap();
+    assert!(restore_skill_revision(&store, "s1", &history[1].commit).is_err());
+    assert_eq!(fs::read_to_string(skill.join("SKILL.md")).unwrap(), "v2");
+    store.set_skill_locked("s1", false).unwrap();
+    assert!(store.get_skill_manifest_json("s1").unwrap().is_none());
+
+    restore_skill_revision(&store, "s1", &history[1].commit).unwrap();
+    assert_eq!(
+        fs::read_link(skill.join("README.md")).unwrap(),
+        Path::new("SKILL.md")
+    );
+    let manifest = store.get_skill_manifest_json("s1").unwrap().unwrap();
+    assert!(manifest.contains("SKILL.md"));
+}
+
+#[test]
+fn leaves_foreign_repositories_alone() {
+    let central = tempfile::tempdir().unwrap();
+    let skill = central.path().join("pdf");
+    fs::create_dir_all(&skill).unwrap();
+    fs::write(skill.join("SKILL.md"), "v1").unwrap();
+    let foreign = Repository::init(central.path()).unwrap();
+    let (_db, store) = store_with_skill(central.path());
+
+    ensure_history_repo(central.path()).unwrap();
+    record_change(&store, &skill, "Update pdf");
+    assert!(foreign.head().is_err());
+    assert!(get_skill_history(&store, "s1").unwrap().is_empty());
+    assert!(restore_skill_revision(&store, "s1", "HEAD").is_err());
+}
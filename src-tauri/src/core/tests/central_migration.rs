@@ -0,0 +1,112 @@
+use std::fs;
+
+use super::*;
+use crate::core::errors::{find_coded, ErrorCode};
+
+fn record(id: &str, central_path: &Path) -> SkillRecord {
+    SkillRecord {
+        id: id.to_string(),
+        name: id.to_string(),
+        source_type: "local".to_string(),
+        source_ref: None,
+        source_revision: None,
+        central_path: central_path.to_string_lossy().to_string(),
+        content_hash: None,
+        created_at: 1,
+        updated_at: 1,
+        last_sync_at: None,
+        last_seen_at: 1,
+        status: "ok".to_string(),
+        group_name: None,
+    }
+}
+
+fn setup() -> (tempfile::TempDir, SkillStore, PathBuf) {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    let old = dir.path().join("old");
+    for id in ["a", "b"] {
+        fs::create_dir_all(old.join(id)).unwrap();
+        fs::write(old.join(id).join("SKILL.md"), id).unwrap();
+        store.upsert_skill(&record(id, &old.join(id))).unwrap();
+    }
+    fs::create_dir_all(old.join(".git")).unwrap();
+    fs::write(old.join(".git/HEAD"), "ref").unwrap();
+    // Adopted in place elsewhere; never moved.
+    let linked = dir.path().join("mine/linked");
+    fs::create_dir_all(&linked).unwrap();
+    store.upsert_skill(&record("linked", &linked)).unwrap();
+    (dir, store, old)
+}
+
+#[test]
+fn moves_skills_and_history() {
+    let (dir, store, old) = setup();
+    let new = dir.path().join("new");
+    let plan = plan_central_migration(&store, &old, &new).unwrap();
+    assert!(plan.blockers.is_empty(), "{:?}", plan.blockers);
+    assert_eq!(plan.moves.len(), 3);
+    assert_eq!(plan.total_bytes, 5);
+
+    let steps = std::cell::RefCell::new(Vec::new());
+    run_central_migration(&store, &plan, |done, total| {
+        steps.borrow_mut().push((done, total))
+    })
+    .unwrap();
+    assert_eq!(steps.into_inner(), vec![(1, 3), (2, 3), (3, 3)]);
+    assert_eq!(fs::read_to_string(new.join("a/SKILL.md")).unwrap(), "a");
+    assert!(new.join(".git/HEAD").exists());
+    assert!(!old.join("a").exists());
+    let a = store.get_skill_by_id("a").unwrap().unwrap();
+    assert_eq!(a.central_path, new.join("a").to_string_lossy());
+    let linked = store.get_skill_by_id("linked").unwrap().unwrap();
+    assert_eq!(
+        linked.central_path,
+        dir.path().join("mine/linked").to_string_lossy()
+    );
+    assert_eq!(
+        store.get_setting(CENTRAL_REPO_PATH_KEY).unwrap().as_deref(),
+        Some(new.to_string_lossy().as_ref())
+    );
+}
+
+#[test]
+fn collisions_block_before_anything_moves() {
+    let (dir, store, old) = setup();
+    let new = dir.path().join("new");
+    fs::create_dir_all(new.join("b")).unwrap();
+    let plan = plan_central_migration(&store, &old, &new).unwrap();
+    assert_eq!(plan.blockers.len(), 1);
+    assert!(plan.blockers[0].contains("already exists"));
+    let err = run_central_migration(&store, &plan, |_, _| {}).unwrap_err();
+    assert_eq!(
+        find_coded(&err).map(|e| e.code),
+        Some(ErrorCode::MigrationBlocked)
+    );
+    assert!(old.join("a").exists());
+
+    let inside = plan_central_migration(&store, &old, &old.join("a/sub")).unwrap();
+    assert!(inside.blockers.iter().any(|b| b.contains("into itself")));
+}
+
+#[test]
+fn a_failed_step_rolls_back_moved_skills() {
+    let (dir, store, old) = setup();
+    let new = dir.path().join("new");
+    let plan = plan_central_migration(&store, &old, &new).unwrap();
+    // Something else takes the second skill's place after planning.
+    let second = Path::new(&plan.moves[1].to);
+    fs::create_dir_all(second).unwrap();
+    fs::write(second.join("other"), "x").unwrap();
+
+    assert!(run_central_migration(&store, &plan, |_, _| {}).is_err());
+    assert_eq!(fs::read_to_string(old.join("a/SKILL.md")).unwrap(), "a");
+    assert_eq!(fs::read_to_string(old.join("b/SKILL.md")).unwrap(), "b");
+    assert!(old.join(".git/HEAD").exists());
+    assert!(!new.join(&plan.moves[0].name).exists());
+    assert_eq!(fs::read_to_string(second.join("other")).unwrap(), "x");
+    let a = store.get_skill_by_id("a").unwrap().unwrap();
+    assert_eq!(a.central_path, old.join("a").to_string_lossy());
+    assert!(store.get_setting(CENTRAL_REPO_PATH_KEY).unwrap().is_none());
+}
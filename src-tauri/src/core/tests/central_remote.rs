@@ -0,0 +1,142 @@
+use std::fs;
+
+use super::*;
+use crate::core::central_history::{ensure_history_repo, record_change};
+use crate::core::errors::{find_coded, ErrorCode};
+
+struct Machine {
+    central: tempfile::TempDir,
+    _db: tempfile::TempDir,
+    store: SkillStore,
+}
+
+fn machine(remote: &Path, skills: &[(&str, &str)]) -> Machine {
+    let central = tempfile::tempdir().unwrap();
+    for (name, body) in skills {
+        fs::create_dir_all(central.path().join(name)).unwrap();
+        fs::write(central.path().join(name).join("SKILL.md"), body).unwrap();
+    }
+    ensure_history_repo(central.path()).unwrap();
+    let db = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(db.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    set_central_remote(
+        &store,
+        Some(CentralRemote {
+            url: remote.to_string_lossy().to_string(),
+            branch: None,
+            auth: RemoteAuth::None,
+        }),
+    )
+    .unwrap();
+    Machine {
+        central,
+        _db: db,
+        store,
+    }
+}
+
+fn edit(m: &Machine, name: &str, body: &str) {
+    let path = m.central.path().join(name);
+    fs::write(path.join("SKILL.md"), body).unwrap();
+    record_change(&m.store, &path, &format!("Update {}", name));
+}
+
+fn read(m: &Machine, name: &str) -> String {
+    fs::read_to_string(m.central.path().join(name).join("SKILL.md")).unwrap()
+}
+
+#[test]
+fn pull_adds_remote_skills_and_flags_conflicts() {
+    let remote = tempfile::tempdir().unwrap();
+    Repository::init_bare(remote.path()).unwrap();
+    let a = machine(remote.path(), &[("pdf", "v1")]);
+    let b = machine(remote.path(), &[("notes", "n1")]);
+
+    // Nothing pushed yet.
+    let empty = pull_central_repo(&b.store, b.central.path()).unwrap();
+    assert!(empty.commit.is_none());
+
+    push_central_repo(&a.store, a.central.path()).unwrap();
+    let pulled = pull_central_repo(&b.store, b.central.path()).unwrap();
+    assert_eq!(pulled.added, vec!["pdf".to_string()]);
+    assert!(pulled.conflicts.is_empty());
+    assert_eq!(read(&b, "pdf"), "v1");
+    assert_eq!(read(&b, "notes"), "n1");
+    let skills = b.store.list_skills().unwrap();
+    assert_eq!(skills.len(), 1);
+    assert_eq!(
+        skills[0].central_path,
+        b.central.path().join("pdf").to_string_lossy()
+    );
+    push_central_repo(&b.store, b.central.path()).unwrap();
+
+    // Both machines change pdf; the second to push has to pull first.
+    edit(&a, "pdf", "from a");
+    edit(&b, "pdf", "from b");
+    push_central_repo(&b.store, b.central.path()).unwrap();
+    let err = push_central_repo(&a.store, a.central.path()).unwrap_err();
+    assert_eq!(
+        find_coded(&err).map(|e| e.code),
+        Some(ErrorCode::CentralRemoteDiverged)
+    );
+
+    let pulled = pull_central_repo(&a.store, a.central.path()).unwrap();
+    assert_eq!(pulled.added, vec!["notes".to_string()]);
+    assert_eq!(
+        pulled.conflicts,
+        vec![PullConflict {
+            name: "pdf".to_string(),
+            reason: "both_changed".to_string(),
+        }]
+    );
+    assert_eq!(read(&a, "pdf"), "from a");
+    assert_eq!(read(&a, "notes"), "n1");
+    push_central_repo(&a.store, a.central.path()).unwrap();
+
+    // A change made only remotely is taken as is.
+    edit(&a, "notes", "n2");
+    push_central_repo(&a.store, a.central.path()).unwrap();
+    let pulled = pull_central_repo(&b.store, b.central.path()).unwrap();
+    assert!(pulled.conflicts.is_empty());
+    assert_eq!(read(&b, "notes"), "n2");
+    assert_eq!(read(&b, "pdf"), "from a");
+}
+
+#[test]
+fn settings_summary_hides_the_token() {
+    let db = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(db.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    assert!(get_central_remote(&store).unwrap().is_none());
+    assert!(set_central_remote(
+        &store,
+        Some(CentralRemote {
+            url: "https://example.com/me/skills.git".to_string(),
+            branch: Some("bad..name".to_string()),
+            auth: RemoteAuth::None,
+        }),
+    )
+    .is_err());
+
+    let summary = set_central_remote(
+        &store,
+        Some(CentralRemote {
+            url: " https://example.com/me/skills.git ".to_string(),
+            branch: None,
+            auth: RemoteAuth::Token {
+                username: Some("me".to_string()),
+                token: "secret".to_string(),
+            },
+        }),
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(summary.url, "https://example.com/me/skills.git");
+    assert_eq!(summary.branch, "main");
+    assert_eq!(summary.auth_kind, "token");
+    assert!(!serde_json::to_string(&summary).unwrap().contains("secret"));
+
+    set_central_remote(&store, None).unwrap();
+    assert!(get_central_remote(&store).unwrap().is_none());
+}
@@ -1,6 +1,12 @@
 use std::path::PathBuf;
 
-use crate::core::central_repo::{ensure_central_repo, resolve_central_repo_path};
+use crate::core::app_paths::AppPaths;
+use crate::core::central_repo::{
+    ensure_central_repo, ensure_central_repo_writable, leave_shared_central_repo,
+    refresh_shared_skills, resolve_central_repo_path, use_shared_central_repo,
+    writable_central_repo, SHARED_SOURCE_TYPE,
+};
+use crate::core::errors::{find_coded, ErrorCode};
 use crate::core::skill_store::SkillStore;
 
 fn make_store() -> (tempfile::TempDir, SkillStore) {
@@ -10,16 +16,26 @@ fn make_store() -> (tempfile::TempDir, SkillStore) {
     (dir, store)
 }
 
+fn temp_paths() -> (tempfile::TempDir, AppPaths) {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let paths = AppPaths {
+        data_dir: dir.path().join("data"),
+        cache_dir: dir.path().join("cache"),
+        config_dir: dir.path().join("config"),
+    };
+    (dir, paths)
+}
+
 #[test]
 fn resolve_uses_setting_when_present() {
     let (dir, store) = make_store();
-    let app = tauri::test::mock_app();
+    let (_paths_dir, paths) = temp_paths();
     let expected = dir.path().join("central");
     store
         .set_setting("central_repo_path", expected.to_string_lossy().as_ref())
         .unwrap();
 
-    let got = resolve_central_repo_path(app.handle(), &store).unwrap();
+    let got = resolve_central_repo_path(&paths, &store).unwrap();
     assert_eq!(got, expected);
 }
 
@@ -31,3 +47,40 @@ fn ensure_central_repo_creates_dir() {
     ensure_central_repo(&p).unwrap();
     assert!(p.exists());
 }
+
+#[test]
+fn shared_repo_is_indexed_and_read_only() {
+    let (dir, store) = make_store();
+    let (_paths_dir, paths) = temp_paths();
+    let share = dir.path().join("share");
+    for name in ["pdf", "docx", ".hidden"] {
+        std::fs::create_dir_all(share.join(name)).unwrap();
+        std::fs::write(share.join(name).join("SKILL.md"), "# skill").unwrap();
+    }
+    std::fs::create_dir_all(share.join("notes")).unwrap();
+
+    let skills = use_shared_central_repo(&store, &share).unwrap();
+    let names: Vec<&str> = skills.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["docx", "pdf"]);
+    assert!(skills.iter().all(|s| s.source_type == SHARED_SOURCE_TYPE));
+    assert_eq!(resolve_central_repo_path(&paths, &store).unwrap(), share);
+
+    let err = writable_central_repo(&paths, &store).unwrap_err();
+    assert_eq!(
+        find_coded(&err).map(|e| e.code),
+        Some(ErrorCode::CentralRepoReadOnly)
+    );
+
+    // Maintainer removed a skill: it disappears, the other keeps its id.
+    let pdf_id = skills.iter().find(|s| s.name == "pdf").unwrap().id.clone();
+    std::fs::remove_dir_all(share.join("docx")).unwrap();
+    let skills = refresh_shared_skills(&store).unwrap();
+    assert_eq!(skills.len(), 1);
+    assert_eq!(skills[0].id, pdf_id);
+    assert_eq!(store.list_skills().unwrap().len(), 1);
+
+    leave_shared_central_repo(&store).unwrap();
+    assert!(store.list_skills().unwrap().is_empty());
+    assert_ne!(resolve_central_repo_path(&paths, &store).unwrap(), share);
+    assert!(ensure_central_repo_writable(&store).is_ok());
+}
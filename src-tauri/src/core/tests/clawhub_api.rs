@@ -93,6 +93,8 @@ fn search_filters_out_null_slugs() {
                 version: item.version,
                 score: item.score,
                 updated_at: item.updated_at,
+                downloads: None,
+                stars: None,
             })
         })
         .collect();
@@ -100,3 +102,331 @@ fn search_filters_out_null_slugs() {
     assert_eq!(results.len(), 1);
     assert_eq!(results[0].slug, "valid");
 }
+
+fn make_detail(slug: &str) -> ClawHubSkillDetail {
+    ClawHubSkillDetail {
+        slug: slug.to_string(),
+        display_name: slug.to_string(),
+        summary: None,
+        version: None,
+        changelog: None,
+        owner_handle: None,
+        owner_name: None,
+        owner_image: None,
+        github_url: None,
+        downloads: None,
+        stars: None,
+        installs_current: None,
+        installs_all_time: None,
+        tags: None,
+        created_at: None,
+        updated_at: None,
+    }
+}
+
+#[test]
+fn detail_cache_expires_after_ttl() {
+    let mut cache = DetailCache::new(4, Duration::from_secs(60));
+    let t0 = Instant::now();
+    cache.insert("a", make_detail("a"), t0);
+
+    assert!(cache.get("a", t0 + Duration::from_secs(30)).is_some());
+    assert!(cache.get("a", t0 + Duration::from_secs(61)).is_none());
+    assert!(cache.entries.is_empty());
+}
+
+#[test]
+fn detail_cache_evicts_least_recently_used() {
+    let mut cache = DetailCache::new(2, Duration::from_secs(60));
+    let t0 = Instant::now();
+    cache.insert("a", make_detail("a"), t0);
+    cache.insert("b", make_detail("b"), t0);
+    // Touch "a" so "b" becomes the eviction candidate.
+    assert!(cache.get("a", t0).is_some());
+    cache.insert("c", make_detail("c"), t0);
+
+    assert!(cache.get("a", t0).is_some());
+    assert!(cache.get("b", t0).is_none());
+    assert!(cache.get("c", t0).is_some());
+
+    cache.remove("a");
+    assert!(cache.get("a", t0).is_none());
+}
+
+#[test]
+fn search_pages_by_offset_then_cursor() {
+    let mut server = mockito::Server::new();
+    let item = r#"{"score": 1.0, "slug": "a", "displayName": "A"}"#;
+    let first = server
+        .mock("GET", "/api/v1/search")
+        .match_query(mockito::Matcher::AllOf(vec![
+            mockito::Matcher::UrlEncoded("q".into(), "pdf".into()),
+            mockito::Matcher::UrlEncoded("limit".into(), "1".into()),
+            mockito::Matcher::UrlEncoded("offset".into(), "3".into()),
+        ]))
+        .with_body(format!(r#"{{"results": [{}]}}"#, item))
+        .create();
+    let page = search_clawhub_inner(
+        &server.url(),
+        "pdf",
+        1,
+        &SearchPageRequest {
+            offset: Some(3),
+            cursor: None,
+        },
+    )
+    .unwrap();
+    first.assert();
+    assert_eq!(page.results.len(), 1);
+    assert_eq!(page.next_offset, Some(4));
+    assert_eq!(page.next_cursor, None);
+
+    let second = server
+        .mock("GET", "/api/v1/search")
+        .match_query(mockito::Matcher::UrlEncoded("cursor".into(), "abc=".into()))
+        .with_body(r#"{"results": [], "nextCursor": "def"}"#)
+        .create();
+    let page = search_clawhub_inner(
+        &server.url(),
+        "pdf",
+        1,
+        &SearchPageRequest {
+            offset: Some(3),
+            cursor: Some("abc=".to_string()),
+        },
+    )
+    .unwrap();
+    second.assert();
+    assert!(page.results.is_empty());
+    assert_eq!(page.next_cursor.as_deref(), Some("def"));
+    assert_eq!(page.next_offset, None);
+}
+
+fn publishable_skill(dir: &std::path::Path, source_ref: Option<&str>) -> SkillRecord {
+    let central = dir.join("My Skill");
+    std::fs::create_dir_all(central.join("refs")).unwrap();
+    std::fs::create_dir_all(central.join(".git")).unwrap();
+    std::fs::write(central.join("SKILL.md"), "# My Skill").unwrap();
+    std::fs::write(central.join("refs/a.md"), "a").unwrap();
+    std::fs::write(central.join(".git/HEAD"), "ref").unwrap();
+    SkillRecord {
+        id: "s1".to_string(),
+        name: "My Skill!".to_string(),
+        source_type: "local".to_string(),
+        source_ref: source_ref.map(str::to_string),
+        source_revision: None,
+        central_path: central.to_string_lossy().to_string(),
+        content_hash: None,
+        created_at: 1,
+        updated_at: 1,
+        last_sync_at: None,
+        last_seen_at: 1,
+        status: "ok".to_string(),
+        group_name: None,
+    }
+}
+
+#[test]
+fn publish_slug_prefers_installed_slug() {
+    let dir = tempfile::tempdir().unwrap();
+    assert_eq!(
+        publish_slug(&publishable_skill(dir.path(), None)),
+        "my-skill"
+    );
+    let installed = publishable_skill(dir.path(), Some("clawhub://finviz-crawler"));
+    assert_eq!(publish_slug(&installed), "finviz-crawler");
+    assert!(is_valid_version("1.2.0"));
+    assert!(is_valid_version("1.2.0-beta.1"));
+    assert!(!is_valid_version("1.2"));
+    assert!(!is_valid_version("v1.2.0"));
+}
+
+#[test]
+fn publish_uploads_zipped_skill_as_multipart() {
+    let dir = tempfile::tempdir().unwrap();
+    let skill = publishable_skill(dir.path(), None);
+    let mut server = mockito::Server::new();
+    let mock = server
+        .mock("POST", "/api/v1/publish")
+        .match_header(
+            "content-type",
+            mockito::Matcher::Regex("^multipart/form-data; boundary=".to_string()),
+        )
+        .match_body(mockito::Matcher::AllOf(vec![
+            mockito::Matcher::Regex("name=\"slug\"\r\n\r\nmy-skill\r\n".to_string()),
+            mockito::Matcher::Regex("name=\"version\"\r\n\r\n1.0.0\r\n".to_string()),
+            mockito::Matcher::Regex("Fixed typos".to_string()),
+            mockito::Matcher::Regex("filename=\"my-skill.zip\"".to_string()),
+        ]))
+        .with_body(
+            r#"{"slug": "my-skill", "version": "1.0.0", "url": "https://clawhub.ai/s/my-skill"}"#,
+        )
+        .create();
+
+    let result = publish_inner(&server.url(), &skill, "1.0.0", "Fixed typos").unwrap();
+    mock.assert();
+    assert_eq!(result.slug, "my-skill");
+    assert_eq!(result.url.as_deref(), Some("https://clawhub.ai/s/my-skill"));
+
+    let archive = zip_skill_dir(std::path::Path::new(&skill.central_path)).unwrap();
+    let archive = zip::ZipArchive::new(std::io::Cursor::new(archive)).unwrap();
+    let mut names: Vec<&str> = archive.file_names().collect();
+    names.sort();
+    assert_eq!(names, vec!["SKILL.md", "refs/a.md"]);
+
+    assert!(publish_inner(&server.url(), &skill, "latest", "").is_err());
+}
+
+#[test]
+fn browse_lists_by_sort_and_category() {
+    let mut server = mockito::Server::new();
+    let mock = server
+        .mock("GET", "/api/v1/skills")
+        .match_query(mockito::Matcher::AllOf(vec![
+            mockito::Matcher::UrlEncoded("sort".into(), "trending".into()),
+            mockito::Matcher::UrlEncoded("limit".into(), "10".into()),
+            mockito::Matcher::UrlEncoded("tag".into(), "data & finance".into()),
+        ]))
+        .with_body(
+            r#"{
+                "items": [
+                    {"slug": "finviz-crawler", "displayName": "Finviz",
+                     "latestVersion": {"version": "2.0.0"},
+                     "stats": {"downloads": 120, "stars": 7}, "updatedAt": 5},
+                    {"slug": null}
+                ],
+                "nextCursor": "c2"
+            }"#,
+        )
+        .create();
+
+    let page = browse_clawhub_inner(
+        &server.url(),
+        Some("data & finance"),
+        "trending",
+        10,
+        &SearchPageRequest::default(),
+    )
+    .unwrap();
+    mock.assert();
+    assert_eq!(page.results.len(), 1);
+    let skill = &page.results[0];
+    assert_eq!(skill.version.as_deref(), Some("2.0.0"));
+    assert_eq!(skill.downloads, Some(120));
+    assert_eq!(skill.stars, Some(7));
+    assert_eq!(page.next_cursor.as_deref(), Some("c2"));
+
+    let err = browse_clawhub_inner(
+        &server.url(),
+        None,
+        "random",
+        10,
+        &SearchPageRequest::default(),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("unknown sort"));
+}
+
+#[test]
+fn readme_prefers_shallowest_skill_md() {
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = zip::write::SimpleFileOptions::default();
+    for (name, body) in [
+        ("pdf/README.md", "readme"),
+        ("pdf/examples/SKILL.md", "nested"),
+        ("pdf/SKILL.md", "# PDF\nUse pdftotext."),
+    ] {
+        zip.start_file(name, options).unwrap();
+        zip.write_all(body.as_bytes()).unwrap();
+    }
+    let archive = zip.finish().unwrap().into_inner();
+
+    let mut server = mockito::Server::new();
+    let mock = server
+        .mock("GET", "/api/v1/download")
+        .match_query(mockito::Matcher::AllOf(vec![
+            mockito::Matcher::UrlEncoded("slug".into(), "pdf".into()),
+            mockito::Matcher::UrlEncoded("version".into(), "1.2.0".into()),
+        ]))
+        .with_body(archive)
+        .create();
+
+    let readme = get_readme_inner(&server.url(), "pdf", Some("1.2.0")).unwrap();
+    mock.assert();
+    assert_eq!(readme.path, "pdf/SKILL.md");
+    assert_eq!(readme.content, "# PDF\nUse pdftotext.");
+    assert!(!readme.truncated);
+}
+
+#[test]
+fn github_file_is_fetched_raw_at_ref() {
+    let mut server = mockito::Server::new();
+    let mock = server
+        .mock(
+            "GET",
+            "/repos/acme/skills/contents/skills/pdf%20tools/SKILL.md",
+        )
+        .match_query(mockito::Matcher::UrlEncoded("ref".into(), "v1.0".into()))
+        .match_header("accept", "application/vnd.github.raw")
+        .with_body("# PDF tools")
+        .create();
+    let _missing = server
+        .mock("GET", "/repos/acme/skills/contents/nope.md")
+        .with_status(404)
+        .create();
+
+    let file = get_github_file_inner(
+        &server.url(),
+        "acme",
+        "skills",
+        "/skills/pdf tools/SKILL.md",
+        Some("v1.0"),
+    )
+    .unwrap();
+    mock.assert();
+    assert_eq!(file.path, "skills/pdf tools/SKILL.md");
+    assert_eq!(file.content, "# PDF tools");
+    assert!(!file.truncated);
+
+    let err = get_github_file_inner(&server.url(), "acme", "skills", "nope.md", None).unwrap_err();
+    assert!(err.to_string().contains("file not found"));
+}
+
+#[test]
+fn github_tree_uses_default_branch_or_given_ref() {
+    let mut server = mockito::Server::new();
+    let tree =
+        r#"{"tree":[{"path":"skills","type":"tree"},{"path":"skills/SKILL.md","type":"blob"}]}"#;
+    let meta = server
+        .mock("GET", "/repos/acme/skills")
+        .with_body(r#"{"default_branch":"trunk"}"#)
+        .create();
+    let trunk = server
+        .mock("GET", "/repos/acme/skills/git/trees/trunk")
+        .match_query(mockito::Matcher::UrlEncoded("recursive".into(), "1".into()))
+        .with_body(tree)
+        .create();
+    let tagged = server
+        .mock("GET", "/repos/acme/skills/git/trees/release/v2")
+        .match_query(mockito::Matcher::Any)
+        .with_body(tree)
+        .create();
+    let _missing = server
+        .mock("GET", "/repos/acme/skills/git/trees/nope")
+        .match_query(mockito::Matcher::Any)
+        .with_status(404)
+        .create();
+
+    let entries = get_github_tree_inner(&server.url(), "acme", "skills", None).unwrap();
+    meta.assert();
+    trunk.assert();
+    assert_eq!(entries.len(), 2);
+    assert!(entries[0].is_dir);
+    assert!(!entries[1].is_dir);
+
+    get_github_tree_inner(&server.url(), "acme", "skills", Some("release/v2")).unwrap();
+    tagged.assert();
+
+    let err = get_github_tree_inner(&server.url(), "acme", "skills", Some("nope")).unwrap_err();
+    assert!(err.to_string().contains("ref 'nope' not found"));
+}
@@ -0,0 +1,70 @@
+use std::path::Path;
+
+use crate::core::skill_store::SkillStore;
+
+use super::{
+    cloud_folder_status, cloud_force_copy_provider, detect_cloud_folder, get_cloud_force_copy,
+    set_cloud_force_copy, CloudProvider,
+};
+
+#[test]
+fn detects_cloud_folders_by_client_folder_names() {
+    let cases = [
+        (
+            "/Users/me/Library/Mobile Documents/com~apple~CloudDocs/skills",
+            Some(CloudProvider::ICloudDrive),
+        ),
+        (
+            "/Users/me/Library/CloudStorage/iCloudDrive/skills",
+            Some(CloudProvider::ICloudDrive),
+        ),
+        ("/Users/me/Dropbox/.skillshub", Some(CloudProvider::Dropbox)),
+        (
+            "/Users/me/Dropbox (Acme)/.skillshub",
+            Some(CloudProvider::Dropbox),
+        ),
+        (
+            "/Users/me/Library/CloudStorage/Dropbox-Personal/skills",
+            Some(CloudProvider::Dropbox),
+        ),
+        (
+            "/Users/me/Library/CloudStorage/OneDrive-Acme/skills",
+            Some(CloudProvider::OneDrive),
+        ),
+        (
+            "/home/me/OneDrive - Acme/skills",
+            Some(CloudProvider::OneDrive),
+        ),
+        ("/home/me/.skillshub", None),
+        ("/home/me/Dropboxes/skills", None),
+        ("/home/me/Library/Mobile Documents Backup/skills", None),
+    ];
+    for (path, expected) in cases {
+        assert_eq!(detect_cloud_folder(Path::new(path)), expected, "{}", path);
+    }
+}
+
+#[test]
+fn force_copy_setting_persists_and_applies_to_cloud_paths_only() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    let cloud = Path::new("/Users/me/Dropbox/.skillshub/demo");
+
+    assert!(!get_cloud_force_copy(&store));
+    assert_eq!(cloud_force_copy_provider(cloud), None);
+
+    assert!(set_cloud_force_copy(&store, true).unwrap());
+    assert!(get_cloud_force_copy(&store));
+    assert_eq!(
+        cloud_force_copy_provider(cloud),
+        Some(CloudProvider::Dropbox)
+    );
+    assert_eq!(cloud_force_copy_provider(dir.path()), None);
+    let status = cloud_folder_status(&store, cloud);
+    assert_eq!(status.provider, Some(CloudProvider::Dropbox));
+    assert!(status.force_copy);
+
+    set_cloud_force_copy(&store, false).unwrap();
+    assert_eq!(cloud_force_copy_provider(cloud), None);
+}
@@ -0,0 +1,88 @@
+use crate::core::skill_store::{SkillRecord, SkillStore, SkillTargetRecord};
+use crate::core::sync_engine::SyncMode;
+
+use super::*;
+
+fn skill(dir: &Path, name: &str) -> SkillRecord {
+    SkillRecord {
+        id: name.to_string(),
+        name: name.to_string(),
+        source_type: "local".to_string(),
+        source_ref: None,
+        source_revision: None,
+        central_path: dir.join("central").join(name).to_string_lossy().to_string(),
+        content_hash: None,
+        created_at: 1,
+        updated_at: 1,
+        last_sync_at: None,
+        last_seen_at: 1,
+        status: "ok".to_string(),
+        group_name: None,
+    }
+}
+
+#[test]
+fn finds_references_once_and_ignores_unterminated_ones() {
+    assert_eq!(
+        refs_in("see {{skill:pdf}} and {{skill: xlsx }} then {{skill:pdf}}"),
+        vec!["pdf", "xlsx", "pdf"]
+    );
+    assert!(refs_in("{{skill:}} {{skill:open").is_empty());
+
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("SKILL.md"), "use {{skill:b}}").unwrap();
+    std::fs::create_dir_all(dir.path().join("refs")).unwrap();
+    std::fs::write(dir.path().join("refs/more.md"), "{{skill:a}} {{skill:b}}").unwrap();
+    assert_eq!(find_cross_refs(dir.path()).unwrap(), vec!["a", "b"]);
+}
+
+#[test]
+fn sync_rewrites_references_to_the_tool_paths() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    for name in ["a", "b", "d"] {
+        store.upsert_skill(&skill(dir.path(), name)).unwrap();
+    }
+    store
+        .upsert_skill_target(&SkillTargetRecord {
+            id: "t1".to_string(),
+            skill_id: "b".to_string(),
+            tool: "claude_code".to_string(),
+            target_path: "/tools/claude/b".to_string(),
+            mode: "symlink".to_string(),
+            status: "ok".to_string(),
+            last_error: None,
+            synced_at: Some(1),
+            content_hash: None,
+        })
+        .unwrap();
+
+    let source = dir.path().join("central/a");
+    std::fs::create_dir_all(&source).unwrap();
+    std::fs::write(
+        source.join("SKILL.md"),
+        "Run {{skill:b}}/run.sh, read {{skill:d}}/SKILL.md, skip {{skill:c}}.",
+    )
+    .unwrap();
+
+    let target = dir.path().join("tool/a");
+    let (outcome, warnings) =
+        sync_with_cross_refs(&store, "claude_code", &source, &target, false).unwrap();
+    assert!(matches!(outcome.mode_used, SyncMode::Copy));
+    let d_central = skill(dir.path(), "d").central_path;
+    assert_eq!(
+        std::fs::read_to_string(target.join("SKILL.md")).unwrap(),
+        format!(
+            "Run /tools/claude/b/run.sh, read {}/SKILL.md, skip {{{{skill:c}}}}.",
+            d_central
+        )
+    );
+    assert_eq!(warnings.len(), 2);
+    assert!(warnings.iter().any(|w| w.contains("not managed: c")));
+    assert!(warnings.iter().any(|w| w.contains("d is not synced")));
+    // The central copy keeps its placeholders.
+    assert!(std::fs::read_to_string(source.join("SKILL.md"))
+        .unwrap()
+        .contains("{{skill:b}}"));
+}
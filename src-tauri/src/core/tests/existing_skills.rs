@@ -0,0 +1,40 @@
+use std::fs;
+
+use super::*;
+
+fn copy(root: &std::path::Path, tool: &str, name: &str, body: &str) -> (String, ExistingCopy) {
+    let path = root.join(tool).join(name);
+    fs::create_dir_all(&path).unwrap();
+    fs::write(path.join("SKILL.md"), body).unwrap();
+    (
+        name.to_string(),
+        ExistingCopy {
+            tool: tool.to_string(),
+            path,
+        },
+    )
+}
+
+#[test]
+fn groups_copies_by_content_and_flags_conflicts() {
+    let dir = tempfile::tempdir().unwrap();
+    let found = vec![
+        copy(dir.path(), "codex", "demo", "same"),
+        copy(dir.path(), "cursor", "demo", "same"),
+        copy(dir.path(), "goose", "demo", "edited"),
+        copy(dir.path(), "goose", "other", "other"),
+    ];
+    let other_hash = hash_dir(&found[3].1.path).unwrap();
+    let managed = vec![(other_hash, "s1".to_string())];
+
+    let skills = group_by_content(found, &managed);
+    assert_eq!(skills.len(), 3);
+    let demo: Vec<_> = skills.iter().filter(|s| s.name == "demo").collect();
+    assert_eq!(demo.len(), 2);
+    assert!(demo.iter().all(|s| s.name_conflict));
+    assert!(demo.iter().any(|s| s.copies.len() == 2));
+
+    let other = skills.iter().find(|s| s.name == "other").unwrap();
+    assert!(!other.name_conflict);
+    assert_eq!(other.managed_skill_id.as_deref(), Some("s1"));
+}
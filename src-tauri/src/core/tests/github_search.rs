@@ -1,6 +1,7 @@
 use mockito::Matcher;
 
-use super::search_github_repos_inner;
+use super::{list_github_repos_inner, search_github_repos_inner, RepoSearchFilters};
+use crate::core::errors::{find_coded, ErrorCode};
 
 fn json_one_repo() -> String {
     r#"{
@@ -33,7 +34,8 @@ fn limit_is_clamped() {
         .with_body(json_one_repo())
         .create();
 
-    let out = search_github_repos_inner(&server.url(), "hello", 0).unwrap();
+    let out = search_github_repos_inner(&server.url(), "hello", 0, &RepoSearchFilters::default())
+        .unwrap();
     assert_eq!(out.len(), 1);
 
     let _m2 = server
@@ -47,7 +49,8 @@ fn limit_is_clamped() {
         .with_body(json_one_repo())
         .create();
 
-    let _ = search_github_repos_inner(&server.url(), "hello", 999).unwrap();
+    let _ = search_github_repos_inner(&server.url(), "hello", 999, &RepoSearchFilters::default())
+        .unwrap();
 }
 
 #[test]
@@ -64,7 +67,8 @@ fn maps_fields() {
         .with_body(json_one_repo())
         .create();
 
-    let out = search_github_repos_inner(&server.url(), "x", 2).unwrap();
+    let out =
+        search_github_repos_inner(&server.url(), "x", 2, &RepoSearchFilters::default()).unwrap();
     assert_eq!(out[0].full_name, "o/r");
     assert_eq!(out[0].stars, 123);
 }
@@ -78,7 +82,118 @@ fn http_error_has_context() {
         .with_body("oops")
         .create();
 
-    let err = search_github_repos_inner(&server.url(), "x", 2).unwrap_err();
+    let err = search_github_repos_inner(&server.url(), "x", 2, &RepoSearchFilters::default())
+        .unwrap_err();
     let msg = format!("{:#}", err);
     assert!(msg.contains("GitHub search returned error"), "{msg}");
 }
+
+#[test]
+fn rate_limit_errors_report_quota_and_reset() {
+    let mut server = mockito::Server::new();
+    let reset = std::time::SystemTime::now()
+        .duration_since(std::time::SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        + 600;
+    let _m = server
+        .mock("GET", "/search/repositories")
+        .match_query(Matcher::Any)
+        .with_status(403)
+        .with_header("x-ratelimit-limit", "60")
+        .with_header("x-ratelimit-remaining", "0")
+        .with_header("x-ratelimit-reset", &reset.to_string())
+        .with_body(r#"{"message": "API rate limit exceeded"}"#)
+        .create();
+
+    let err = search_github_repos_inner(&server.url(), "skills", 5, &RepoSearchFilters::default())
+        .unwrap_err();
+    let msg = err.to_string();
+    assert_eq!(
+        find_coded(&err).map(|e| e.code),
+        Some(ErrorCode::GithubRateLimited)
+    );
+    assert!(msg.contains("60 requests/hour"));
+    assert!(msg.contains("resets in 10 min"));
+}
+
+#[test]
+fn filters_become_qualifiers_and_sort() {
+    let mut server = mockito::Server::new();
+    let _m = server
+        .mock("GET", "/search/repositories")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded(
+                "q".into(),
+                "skills topic:claude-skills language:Python stars:>=50".into(),
+            ),
+            Matcher::UrlEncoded("sort".into(), "stars".into()),
+            Matcher::UrlEncoded("order".into(), "desc".into()),
+        ]))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"items": [{"full_name": "o/r", "html_url": "h", "description": null,
+                "stargazers_count": 70, "updated_at": "u", "clone_url": "c",
+                "language": "Python", "topics": ["claude-skills"]}]}"#,
+        )
+        .create();
+
+    let filters = RepoSearchFilters {
+        topic: Some("claude-skills".to_string()),
+        language: Some(" Python ".to_string()),
+        min_stars: Some(50),
+        sort: Some("stars".to_string()),
+    };
+    let out = search_github_repos_inner(&server.url(), "skills", 5, &filters).unwrap();
+    assert_eq!(out[0].language.as_deref(), Some("Python"));
+    assert_eq!(out[0].topics, vec!["claude-skills"]);
+
+    let bad_sort = RepoSearchFilters {
+        sort: Some("forks".to_string()),
+        ..filters.clone()
+    };
+    assert!(search_github_repos_inner(&server.url(), "skills", 5, &bad_sort).is_err());
+    let bad_topic = RepoSearchFilters {
+        topic: Some("a b".to_string()),
+        ..filters
+    };
+    assert!(search_github_repos_inner(&server.url(), "skills", 5, &bad_topic).is_err());
+}
+
+#[test]
+fn lists_org_repos_and_falls_back_to_user_repos() {
+    let mut server = mockito::Server::new();
+    let item = |name: &str| {
+        format!(
+            r#"{{"full_name": "{0}", "html_url": "h", "description": null,
+                "stargazers_count": 1, "updated_at": "u", "clone_url": "c"}}"#,
+            name
+        )
+    };
+    let _org = server
+        .mock("GET", "/orgs/acme/repos")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("sort".into(), "updated".into()),
+            Matcher::UrlEncoded("per_page".into(), "2".into()),
+            Matcher::UrlEncoded("page".into(), "1".into()),
+        ]))
+        .with_body(format!("[{},{}]", item("acme/a"), item("acme/b")))
+        .create();
+    let _not_org = server
+        .mock("GET", "/orgs/jane/repos")
+        .match_query(Matcher::Any)
+        .with_status(404)
+        .create();
+    let _user = server
+        .mock("GET", "/users/jane/repos")
+        .match_query(Matcher::Any)
+        .with_body(format!("[{}]", item("jane/x")))
+        .create();
+
+    let out = list_github_repos_inner(&server.url(), "acme", 2).unwrap();
+    assert_eq!(out.len(), 2);
+    let out = list_github_repos_inner(&server.url(), "jane", 10).unwrap();
+    assert_eq!(out[0].full_name, "jane/x");
+    assert!(list_github_repos_inner(&server.url(), "../x", 10).is_err());
+}
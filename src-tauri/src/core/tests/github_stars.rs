@@ -0,0 +1,85 @@
+use super::{list_star_suggestions_inner, set_github_account};
+
+fn starred_json(pushed_at: &str) -> String {
+    format!(
+        r#"[
+  {{
+    "full_name": "o/skills",
+    "html_url": "https://github.com/o/skills",
+    "description": "d",
+    "stargazers_count": 7,
+    "clone_url": "https://github.com/o/skills.git",
+    "default_branch": "main",
+    "pushed_at": "{pushed_at}"
+  }},
+  {{
+    "full_name": "o/plain",
+    "html_url": "https://github.com/o/plain",
+    "description": null,
+    "stargazers_count": 1,
+    "clone_url": "https://github.com/o/plain.git",
+    "default_branch": "master",
+    "pushed_at": "{pushed_at}"
+  }}
+]"#
+    )
+}
+
+const SKILLS_TREE: &str = r#"{"tree": [
+  {"path": "README.md", "type": "blob"},
+  {"path": "skills", "type": "tree"},
+  {"path": "skills/b/SKILL.md", "type": "blob"},
+  {"path": "skills/a/SKILL.md", "type": "blob"}
+]}"#;
+
+#[test]
+fn filters_starred_repos_and_caches_trees() {
+    let mut server = mockito::Server::new();
+    let _starred = server
+        .mock("GET", "/users/me/starred")
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(starred_json("2026-01-01T00:00:00Z"))
+        .create();
+    let skills_tree = server
+        .mock("GET", "/repos/o/skills/git/trees/main")
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_body(SKILLS_TREE)
+        .expect(1)
+        .create();
+    let _plain_tree = server
+        .mock("GET", "/repos/o/plain/git/trees/master")
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_body(r#"{"tree": [{"path": "README.md", "type": "blob"}]}"#)
+        .create();
+
+    let installed = vec!["https://github.com/o/skills/tree/main/skills/a".to_string()];
+    let out = list_star_suggestions_inner(&server.url(), "me", &installed).unwrap();
+    assert_eq!(out.len(), 1);
+    assert_eq!(out[0].full_name, "o/skills");
+    assert_eq!(out[0].skill_paths, vec!["skills/a", "skills/b"]);
+    assert!(out[0].installed);
+
+    // Unchanged pushed_at: served from the cache.
+    let out = list_star_suggestions_inner(&server.url(), "me", &[]).unwrap();
+    assert_eq!(out.len(), 1);
+    assert!(!out[0].installed);
+    skills_tree.assert();
+}
+
+#[test]
+fn account_setting_rejects_paths() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = crate::core::skill_store::SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+
+    set_github_account(&store, Some(" octocat ")).unwrap();
+    assert_eq!(
+        super::get_github_account(&store).unwrap().as_deref(),
+        Some("octocat")
+    );
+    assert!(set_github_account(&store, Some("a/b")).is_err());
+}
@@ -0,0 +1,226 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::time::Duration;
+
+use crate::core::skill_store::SkillStore;
+
+use super::{
+    build_client, bypasses_proxy, get_http_settings, http_connect_tunnel, normalize,
+    send_with_retries, set_http_settings, user_agent, HttpSettings,
+};
+
+fn make_store() -> (tempfile::TempDir, SkillStore) {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().expect("ensure_schema");
+    (dir, store)
+}
+
+#[test]
+fn settings_round_trip_and_default() {
+    let (_dir, store) = make_store();
+    assert_eq!(get_http_settings(&store), HttpSettings::default());
+
+    // No proxy here: the client is process-wide and other tests use it.
+    let saved = set_http_settings(
+        &store,
+        HttpSettings {
+            no_proxy: Some("  ".to_string()),
+            timeout_secs: 45,
+            ..HttpSettings::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(saved.no_proxy, None);
+    assert_eq!(get_http_settings(&store), saved);
+    set_http_settings(&store, HttpSettings::default()).unwrap();
+}
+
+#[test]
+fn accepts_http_and_socks_proxies() {
+    for url in [
+        "http://proxy.local:8080",
+        "https://user:pw@proxy.local",
+        "socks5://127.0.0.1:1080",
+        "socks5h://proxy.local:1080",
+    ] {
+        let settings = normalize(HttpSettings {
+            proxy_url: Some(format!(" {} ", url)),
+            no_proxy: Some("localhost,.internal".to_string()),
+            ..HttpSettings::default()
+        })
+        .unwrap();
+        assert_eq!(settings.proxy_url.as_deref(), Some(url));
+        build_client(&settings).unwrap();
+    }
+}
+
+#[test]
+fn rejects_bad_settings() {
+    let (_dir, store) = make_store();
+    let bad = [
+        HttpSettings {
+            proxy_url: Some("proxy.local:8080".to_string()),
+            ..HttpSettings::default()
+        },
+        HttpSettings {
+            proxy_url: Some("ftp://proxy.local".to_string()),
+            ..HttpSettings::default()
+        },
+        HttpSettings {
+            timeout_secs: 0,
+            ..HttpSettings::default()
+        },
+        HttpSettings {
+            max_retries: 99,
+            ..HttpSettings::default()
+        },
+    ];
+    for settings in bad {
+        assert!(set_http_settings(&store, settings).is_err());
+    }
+    assert_eq!(get_http_settings(&store), HttpSettings::default());
+}
+
+#[test]
+fn retries_overloaded_responses() {
+    let mut server = mockito::Server::new();
+    let busy = server
+        .mock("GET", "/busy")
+        .with_status(503)
+        .with_header("retry-after", "0")
+        .expect(2)
+        .create();
+    let client = build_client(&HttpSettings::default()).unwrap();
+    let url = format!("{}/busy", server.url());
+
+    let response = send_with_retries(client.get(&url), 1).unwrap();
+    assert_eq!(response.status().as_u16(), 503);
+    busy.assert();
+
+    let missing = server
+        .mock("GET", "/missing")
+        .with_status(404)
+        .expect(1)
+        .create();
+    let url = format!("{}/missing", server.url());
+    let response = send_with_retries(client.get(&url), 3).unwrap();
+    assert_eq!(response.status().as_u16(), 404);
+    missing.assert();
+}
+
+#[test]
+fn never_retries_posts() {
+    let mut server = mockito::Server::new();
+    let busy = server
+        .mock("POST", "/token")
+        .with_status(503)
+        .with_header("retry-after", "0")
+        .expect(1)
+        .create();
+    let client = build_client(&HttpSettings::default()).unwrap();
+    let request = client
+        .post(format!("{}/token", server.url()))
+        .form(&[("grant_type", "device_code")]);
+
+    let response = send_with_retries(request, 3).unwrap();
+    assert_eq!(response.status().as_u16(), 503);
+    busy.assert();
+}
+
+#[test]
+fn sends_versioned_user_agent() {
+    let mut server = mockito::Server::new();
+    let mock = server
+        .mock("GET", "/ua")
+        .match_header("user-agent", user_agent().as_str())
+        .with_status(200)
+        .create();
+    let client = build_client(&HttpSettings::default()).unwrap();
+    let response = send_with_retries(client.get(format!("{}/ua", server.url())), 0).unwrap();
+    assert!(response.status().is_success());
+    mock.assert();
+    assert!(user_agent().starts_with("skills-hub/"));
+}
+
+#[test]
+fn matches_no_proxy_entries() {
+    let list = Some("localhost, .corp.example,10.0.0.1");
+    assert!(bypasses_proxy(list, "localhost"));
+    assert!(bypasses_proxy(list, "git.corp.example"));
+    assert!(bypasses_proxy(list, "corp.example"));
+    assert!(bypasses_proxy(list, "10.0.0.1"));
+    assert!(!bypasses_proxy(list, "github.com"));
+    assert!(!bypasses_proxy(list, "notcorp.example"));
+    assert!(bypasses_proxy(Some("*"), "github.com"));
+    assert!(!bypasses_proxy(None, "github.com"));
+}
+
+#[test]
+fn ssh_via_proxy_needs_an_http_proxy() {
+    let with = |proxy_url: Option<&str>| HttpSettings {
+        proxy_url: proxy_url.map(str::to_string),
+        ssh_via_proxy: true,
+        ..HttpSettings::default()
+    };
+    assert!(normalize(with(None)).is_err());
+    assert!(normalize(with(Some("socks5://127.0.0.1:1080"))).is_err());
+    assert!(normalize(with(Some("http://proxy.local:3128"))).is_ok());
+}
+
+/// A one-shot proxy that answers the CONNECT with `reply`, then writes an
+/// SSH banner. Returns the request it received.
+fn fake_proxy(reply: &'static str) -> (String, std::thread::JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://user:p%40ss@{}", listener.local_addr().unwrap());
+    let handle = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut request = String::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            request.push_str(&line);
+            if line == "\r\n" || line.is_empty() {
+                break;
+            }
+        }
+        // The client may already have hung up after a refusal.
+        let _ = stream.write_all(reply.as_bytes());
+        let _ = stream.write_all(b"SSH-2.0-fake\r\n");
+        request
+    });
+    (url, handle)
+}
+
+#[test]
+fn tunnels_through_http_connect() {
+    let (url, proxy) = fake_proxy("HTTP/1.1 200 Connection established\r\n\r\n");
+    let mut stream =
+        http_connect_tunnel(&url, "ssh.example", 2222, Duration::from_secs(5)).unwrap();
+    let mut banner = [0u8; 14];
+    stream.read_exact(&mut banner).unwrap();
+    assert_eq!(&banner, b"SSH-2.0-fake\r\n");
+
+    let request = proxy.join().unwrap();
+    assert!(request.starts_with("CONNECT ssh.example:2222 HTTP/1.1\r\n"));
+    // base64("user:p@ss")
+    assert!(request.contains("Proxy-Authorization: Basic dXNlcjpwQHNz\r\n"));
+}
+
+#[test]
+fn reports_refused_connect() {
+    let (url, proxy) = fake_proxy("HTTP/1.1 407 Proxy Authentication Required\r\n\r\n");
+    let err = http_connect_tunnel(&url, "ssh.example", 22, Duration::from_secs(5)).unwrap_err();
+    assert!(format!("{:#}", err).contains("407"));
+    proxy.join().unwrap();
+
+    let err = http_connect_tunnel(
+        "socks5://127.0.0.1:1",
+        "ssh.example",
+        22,
+        Duration::from_secs(1),
+    )
+    .unwrap_err();
+    assert!(format!("{:#}", err).contains("http://"));
+}
@@ -1,6 +1,8 @@
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::core::app_paths::AppPaths;
+use crate::core::errors::{find_coded, ErrorCode};
 use crate::core::skill_store::{SkillStore, SkillTargetRecord};
 
 fn make_store() -> (tempfile::TempDir, SkillStore) {
@@ -10,6 +12,16 @@ fn make_store() -> (tempfile::TempDir, SkillStore) {
     (dir, store)
 }
 
+fn temp_paths() -> (tempfile::TempDir, AppPaths) {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let paths = AppPaths {
+        data_dir: dir.path().join("data"),
+        cache_dir: dir.path().join("cache"),
+        config_dir: dir.path().join("config"),
+    };
+    (dir, paths)
+}
+
 fn set_central_path(store: &SkillStore, central: &Path) {
     store
         .set_setting("central_repo_path", central.to_string_lossy().as_ref())
@@ -145,7 +157,7 @@ fn parses_skill_md_with_variant_delimiters() {
 
 #[test]
 fn install_cleans_orphan_dir() {
-    let app = tauri::test::mock_app();
+    let (_paths_dir, paths) = temp_paths();
     let (_dir, store) = make_store();
 
     let central_root = tempfile::tempdir().unwrap();
@@ -167,7 +179,7 @@ fn install_cleans_orphan_dir() {
 
     // Install should succeed by cleaning the orphan dir first
     let res = super::install_local_skill(
-        app.handle(),
+        &paths,
         &store,
         source.path(),
         Some("orphan-test".to_string()),
@@ -183,7 +195,7 @@ fn install_cleans_orphan_dir() {
     let source2 = tempfile::tempdir().unwrap();
     fs::write(source2.path().join("SKILL.md"), b"---\nname: x\n---\n").unwrap();
     let err = match super::install_local_skill(
-        app.handle(),
+        &paths,
         &store,
         source2.path(),
         Some("orphan-test".to_string()),
@@ -196,7 +208,7 @@ fn install_cleans_orphan_dir() {
 
 #[test]
 fn installs_local_skill_and_updates_from_source() {
-    let app = tauri::test::mock_app();
+    let (_paths_dir, paths) = temp_paths();
     let (_dir, store) = make_store();
 
     let central_root = tempfile::tempdir().unwrap();
@@ -206,13 +218,8 @@ fn installs_local_skill_and_updates_from_source() {
     fs::write(source.path().join("SKILL.md"), b"---\nname: x\n---\n").unwrap();
     fs::write(source.path().join("a.txt"), b"v1").unwrap();
 
-    let res = super::install_local_skill(
-        app.handle(),
-        &store,
-        source.path(),
-        Some("local1".to_string()),
-    )
-    .unwrap();
+    let res = super::install_local_skill(&paths, &store, source.path(), Some("local1".to_string()))
+        .unwrap();
     assert!(res.central_path.exists());
 
     let skill = store.get_skill_by_id(&res.skill_id).unwrap().unwrap();
@@ -230,11 +237,12 @@ fn installs_local_skill_and_updates_from_source() {
         status: "ok".to_string(),
         last_error: None,
         synced_at: None,
+        content_hash: None,
     };
     store.upsert_skill_target(&t).unwrap();
 
     fs::write(source.path().join("a.txt"), b"v2").unwrap();
-    let up = super::update_managed_skill_from_source(app.handle(), &store, &res.skill_id).unwrap();
+    let up = super::update_managed_skill_from_source(&paths, &store, &res.skill_id, false).unwrap();
     assert_eq!(up.skill_id, res.skill_id);
     assert!(up.updated_targets.contains(&"unknown_tool".to_string()));
     assert!(PathBuf::from(
@@ -251,21 +259,18 @@ fn installs_local_skill_and_updates_from_source() {
     );
     assert_eq!(fs::read(target.join("a.txt")).unwrap(), b"v2");
 
-    let err = match super::install_local_skill(
-        app.handle(),
-        &store,
-        source.path(),
-        Some("local1".to_string()),
-    ) {
-        Ok(_) => panic!("expected error"),
-        Err(e) => e,
-    };
+    let err =
+        match super::install_local_skill(&paths, &store, source.path(), Some("local1".to_string()))
+        {
+            Ok(_) => panic!("expected error"),
+            Err(e) => e,
+        };
     assert!(format!("{:#}", err).contains("skill already exists"));
 }
 
 #[test]
 fn lists_and_installs_git_skills_without_network() {
-    let app = tauri::test::mock_app();
+    let (_paths_dir, paths) = temp_paths();
     let (_dir, store) = make_store();
     let central_root = tempfile::tempdir().unwrap();
     set_central_path(&store, central_root.path());
@@ -281,18 +286,14 @@ fn lists_and_installs_git_skills_without_network() {
     let repo = init_git_repo(repo_dir.path());
     commit_all(&repo, "add skills");
 
-    let candidates = super::list_git_skills(
-        app.handle(),
-        &store,
-        repo_dir.path().to_string_lossy().as_ref(),
-    )
-    .unwrap();
+    let candidates =
+        super::list_git_skills(&paths, &store, repo_dir.path().to_string_lossy().as_ref()).unwrap();
     let subpaths: Vec<String> = candidates.into_iter().map(|c| c.subpath).collect();
     assert!(subpaths.contains(&".".to_string()));
     assert!(subpaths.iter().any(|s| s.ends_with("skills/a")));
 
     let res = super::install_git_skill_from_selection(
-        app.handle(),
+        &paths,
         &store,
         repo_dir.path().to_string_lossy().as_ref(),
         "skills/a",
@@ -304,7 +305,7 @@ fn lists_and_installs_git_skills_without_network() {
 
 #[test]
 fn install_git_skill_errors_on_multi_skills_repo_root() {
-    let app = tauri::test::mock_app();
+    let (_paths_dir, paths) = temp_paths();
     let (_dir, store) = make_store();
     let central_root = tempfile::tempdir().unwrap();
     set_central_path(&store, central_root.path());
@@ -326,7 +327,7 @@ fn install_git_skill_errors_on_multi_skills_repo_root() {
     commit_all(&repo, "multi skills");
 
     let err = match super::install_git_skill(
-        app.handle(),
+        &paths,
         &store,
         repo_dir.path().to_string_lossy().as_ref(),
         None,
@@ -334,7 +335,10 @@ fn install_git_skill_errors_on_multi_skills_repo_root() {
         Ok(_) => panic!("expected error"),
         Err(e) => e,
     };
-    assert!(format!("{:#}", err).contains("MULTI_SKILLS|"));
+    assert_eq!(
+        find_coded(&err).map(|e| e.code),
+        Some(ErrorCode::MultiSkills)
+    );
 }
 
 #[test]
@@ -373,7 +377,7 @@ fn lists_local_skills_with_invalid_entries() {
 
 #[test]
 fn install_local_selection_validates_skill_md() {
-    let app = tauri::test::mock_app();
+    let (_paths_dir, paths) = temp_paths();
     let (_dir, store) = make_store();
 
     let central_root = tempfile::tempdir().unwrap();
@@ -388,20 +392,15 @@ fn install_local_selection_validates_skill_md() {
     )
     .unwrap();
 
-    let res = super::install_local_skill_from_selection(
-        app.handle(),
-        &store,
-        base.path(),
-        "skills/a",
-        None,
-    )
-    .unwrap();
+    let res =
+        super::install_local_skill_from_selection(&paths, &store, base.path(), "skills/a", None)
+            .unwrap();
     assert!(res.central_path.exists());
     let skill = store.get_skill_by_id(&res.skill_id).unwrap().unwrap();
     assert_eq!(skill.name, "Local A");
 
     let err = match super::install_local_skill_from_selection(
-        app.handle(),
+        &paths,
         &store,
         base.path(),
         "skills/b",
@@ -410,5 +409,94 @@ fn install_local_selection_validates_skill_md() {
         Ok(_) => panic!("expected error"),
         Err(e) => e,
     };
-    assert!(format!("{:#}", err).contains("SKILL_INVALID|missing_skill_md"));
+    let coded = find_coded(&err).unwrap();
+    assert_eq!(coded.code, ErrorCode::SkillInvalid);
+    assert_eq!(
+        coded.payload,
+        Some(serde_json::json!({ "reason": "missing_skill_md" }))
+    );
+}
+
+#[test]
+fn clawhub_update_status_compares_recorded_version() {
+    let skill = |revision: Option<&str>| crate::core::skill_store::SkillRecord {
+        id: "s1".to_string(),
+        name: "finviz".to_string(),
+        source_type: "clawhub".to_string(),
+        source_ref: Some("clawhub://finviz-crawler".to_string()),
+        source_revision: revision.map(str::to_string),
+        central_path: "/tmp/central/finviz".to_string(),
+        content_hash: None,
+        created_at: 1,
+        updated_at: 1,
+        last_sync_at: None,
+        last_seen_at: 1,
+        status: "ok".to_string(),
+        group_name: None,
+    };
+    let latest = |slug: &str| {
+        assert_eq!(slug, "finviz-crawler");
+        Ok(Some("2.0.0".to_string()))
+    };
+
+    let status = super::clawhub_update_status(skill(Some("1.0.0")), latest);
+    assert!(status.has_update);
+    assert_eq!(status.remote_rev.as_deref(), Some("2.0.0"));
+    assert!(!super::clawhub_update_status(skill(Some("2.0.0")), latest).has_update);
+    // Installed before versions were recorded: offer the update.
+    assert!(super::clawhub_update_status(skill(None), latest).has_update);
+
+    let failed =
+        super::clawhub_update_status(skill(Some("1.0.0")), |_| Err(anyhow::anyhow!("offline")));
+    assert!(!failed.has_update);
+    assert_eq!(failed.error.as_deref(), Some("offline"));
+}
+
+#[test]
+fn reinstall_restores_missing_central_copy_and_targets() {
+    let (_paths_dir, paths) = temp_paths();
+    let (_dir, store) = make_store();
+    let central_root = tempfile::tempdir().unwrap();
+    set_central_path(&store, central_root.path());
+
+    let source = tempfile::tempdir().unwrap();
+    fs::write(source.path().join("SKILL.md"), b"---\nname: x\n---\n").unwrap();
+    fs::write(source.path().join("a.txt"), b"v1").unwrap();
+    let res = super::install_local_skill(&paths, &store, source.path(), Some("local1".to_string()))
+        .unwrap();
+    let installed = store.get_skill_by_id(&res.skill_id).unwrap().unwrap();
+
+    let target_root = tempfile::tempdir().unwrap();
+    let target = target_root.path().join("target");
+    for (id, tool) in [("t1", "unknown_tool"), ("t2", "remote:h1:claude_code")] {
+        store
+            .upsert_skill_target(&SkillTargetRecord {
+                id: id.to_string(),
+                skill_id: res.skill_id.clone(),
+                tool: tool.to_string(),
+                target_path: target.to_string_lossy().to_string(),
+                mode: "copy".to_string(),
+                status: "ok".to_string(),
+                last_error: None,
+                synced_at: None,
+                content_hash: None,
+            })
+            .unwrap();
+    }
+
+    fs::remove_dir_all(&res.central_path).unwrap();
+    let out = super::reinstall_skill_from_source(&paths, &store, &res.skill_id).unwrap();
+    assert_eq!(fs::read(res.central_path.join("a.txt")).unwrap(), b"v1");
+    assert_eq!(fs::read(target.join("a.txt")).unwrap(), b"v1");
+    assert!(out.hash_matches);
+    assert_eq!(Some(out.content_hash.clone()), installed.content_hash);
+    assert_eq!(out.resynced_targets, vec!["unknown_tool".to_string()]);
+    assert_eq!(out.warnings.len(), 1);
+    assert!(out.warnings[0].starts_with("remote:h1:claude_code: remote target"));
+
+    // The source moved on since install: restored, but flagged.
+    fs::write(source.path().join("a.txt"), b"v2").unwrap();
+    let out = super::reinstall_skill_from_source(&paths, &store, &res.skill_id).unwrap();
+    assert!(!out.hash_matches);
+    assert_eq!(fs::read(target.join("a.txt")).unwrap(), b"v2");
 }
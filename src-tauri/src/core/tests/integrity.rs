@@ -0,0 +1,117 @@
+use std::fs;
+
+use super::*;
+use crate::core::skill_store::SkillTargetRecord;
+
+fn record(id: &str, name: &str, central_path: &Path, content_hash: Option<String>) -> SkillRecord {
+    SkillRecord {
+        id: id.to_string(),
+        name: name.to_string(),
+        source_type: "local".to_string(),
+        source_ref: None,
+        source_revision: None,
+        central_path: central_path.to_string_lossy().to_string(),
+        content_hash,
+        created_at: 1,
+        updated_at: 1,
+        last_sync_at: None,
+        last_seen_at: 1,
+        status: "ok".to_string(),
+        group_name: None,
+    }
+}
+
+fn skill_dir(central: &Path, name: &str, body: &str) -> PathBuf {
+    let dir = central.join(name);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("SKILL.md"), body).unwrap();
+    dir
+}
+
+#[test]
+fn finds_and_repairs_drift() {
+    let dir = tempfile::tempdir().unwrap();
+    let central = dir.path().join("central");
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+
+    let ok = skill_dir(&central, "ok", "fine");
+    store
+        .upsert_skill(&record("s1", "ok", &ok, hash_dir(&ok).ok()))
+        .unwrap();
+    let edited = skill_dir(&central, "edited", "before");
+    store
+        .upsert_skill(&record("s2", "edited", &edited, hash_dir(&edited).ok()))
+        .unwrap();
+    fs::write(edited.join("SKILL.md"), "after").unwrap();
+    store
+        .upsert_skill(&record("s3", "gone", &central.join("gone"), None))
+        .unwrap();
+    skill_dir(&central, "stray", "hello");
+    fs::create_dir_all(central.join("no-skill-md")).unwrap();
+    fs::create_dir_all(central.join(".git")).unwrap();
+    store
+        .upsert_skill_target(&SkillTargetRecord {
+            id: "t1".to_string(),
+            skill_id: "s1".to_string(),
+            tool: "other_tool".to_string(),
+            target_path: dir
+                .path()
+                .join("missing-target")
+                .to_string_lossy()
+                .to_string(),
+            mode: "copy".to_string(),
+            status: "ok".to_string(),
+            last_error: None,
+            synced_at: None,
+            content_hash: None,
+        })
+        .unwrap();
+
+    let report = verify_central_repo(&store, &central, false).unwrap();
+    assert_eq!(report.checked, 3);
+    let mut found: Vec<_> = report
+        .issues
+        .iter()
+        .map(|i| (i.kind, i.name.as_str(), i.repaired))
+        .collect();
+    found.sort_by_key(|(_, name, _)| *name);
+    assert_eq!(
+        found,
+        vec![
+            (IssueKind::HashMismatch, "edited", false),
+            (IssueKind::MissingDir, "gone", false),
+            (IssueKind::Orphan, "no-skill-md", false),
+            (IssueKind::BrokenTarget, "ok", false),
+            (IssueKind::Orphan, "stray", false),
+        ]
+    );
+
+    store.delete_skill_target("s1", "other_tool").unwrap();
+    let report = verify_central_repo(&store, &central, true).unwrap();
+    let mut repaired: Vec<_> = report
+        .issues
+        .iter()
+        .map(|i| (i.kind, i.name.as_str(), i.repaired))
+        .collect();
+    repaired.sort_by_key(|(_, name, _)| *name);
+    assert_eq!(
+        repaired,
+        vec![
+            (IssueKind::HashMismatch, "edited", true),
+            (IssueKind::MissingDir, "gone", false),
+            (IssueKind::Orphan, "no-skill-md", false),
+            (IssueKind::Orphan, "stray", true),
+        ]
+    );
+    let edited = store.get_skill_by_id("s2").unwrap().unwrap();
+    assert_eq!(edited.content_hash, hash_dir(&central.join("edited")).ok());
+    assert!(store
+        .list_skills()
+        .unwrap()
+        .iter()
+        .any(|s| s.name == "stray" && s.source_type == "local"));
+
+    let report = verify_central_repo(&store, &central, false).unwrap();
+    assert_eq!(report.issues.len(), 2);
+}
@@ -0,0 +1,131 @@
+use std::time::{Duration, Instant};
+
+use super::*;
+use crate::core::operations::OperationStatus;
+
+fn wait_finished(queue: &JobQueue, id: &str) -> Job {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    loop {
+        let job = queue.get(id).expect("job");
+        if job.operation.status.is_finished() {
+            return job;
+        }
+        assert!(Instant::now() < deadline, "job {} did not finish", id);
+        std::thread::sleep(Duration::from_millis(5));
+    }
+}
+
+#[derive(Serialize)]
+struct FakeError {
+    code: &'static str,
+    message: &'static str,
+}
+
+impl Display for FakeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.message)
+    }
+}
+
+#[test]
+fn job_keeps_logs_and_result_and_only_queued_operations_are_listed() {
+    let ops = OperationRegistry::new();
+    let queue = JobQueue::new(ops.clone());
+    ops.start(OperationKind::Sync, "not a job");
+
+    let id = queue.enqueue(OperationKind::Install, "repo", |ctx| {
+        ctx.log("cloning");
+        ctx.progress(1, 2);
+        ctx.log("copying");
+        Ok::<_, String>(serde_json::json!({ "name": "pdf" }))
+    });
+    let job = wait_finished(&queue, &id);
+
+    assert_eq!(job.operation.status, OperationStatus::Done);
+    assert_eq!(job.logs, vec!["cloning", "copying"]);
+    assert_eq!(job.result, Some(serde_json::json!({ "name": "pdf" })));
+    assert_eq!(job.error, None);
+    let listed: Vec<String> = queue.list().into_iter().map(|j| j.operation.id).collect();
+    assert_eq!(listed, vec![id]);
+}
+
+#[test]
+fn failed_and_panicking_jobs_are_recorded_and_the_worker_survives() {
+    let queue = JobQueue::with_workers(OperationRegistry::new(), 1);
+
+    let failed = queue.enqueue(OperationKind::RemotePush, "host-1", |_| {
+        Err::<(), _>(FakeError {
+            code: "TOOL_NOT_INSTALLED",
+            message: "cursor is not installed",
+        })
+    });
+    let panicked = queue.enqueue(OperationKind::Update, "all", |_| -> Result<(), String> {
+        panic!("boom")
+    });
+    let after = queue.enqueue(OperationKind::Update, "next", |_| Ok::<_, String>(1));
+
+    let job = wait_finished(&queue, &failed);
+    assert_eq!(
+        job.operation.status,
+        OperationStatus::Failed {
+            error: "cursor is not installed".to_string()
+        }
+    );
+    assert_eq!(
+        job.error.unwrap()["code"],
+        serde_json::json!("TOOL_NOT_INSTALLED")
+    );
+    assert_eq!(
+        wait_finished(&queue, &panicked).operation.status,
+        OperationStatus::Failed {
+            error: "job panicked".to_string()
+        }
+    );
+    assert_eq!(
+        wait_finished(&queue, &after).result,
+        Some(serde_json::json!(1))
+    );
+}
+
+#[test]
+fn jobs_wait_queued_for_a_free_worker() {
+    let queue = JobQueue::with_workers(OperationRegistry::new(), 1);
+    let (release, gate) = mpsc::channel::<()>();
+
+    let first = queue.enqueue(OperationKind::Install, "first", move |_| {
+        gate.recv().ok();
+        Ok::<_, String>(())
+    });
+    let second = queue.enqueue(OperationKind::Install, "second", |_| Ok::<_, String>(()));
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while queue.get(&first).unwrap().operation.status == OperationStatus::Queued {
+        assert!(Instant::now() < deadline);
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    assert_eq!(
+        queue.get(&second).unwrap().operation.status,
+        OperationStatus::Queued
+    );
+
+    release.send(()).unwrap();
+    wait_finished(&queue, &first);
+    assert_eq!(
+        wait_finished(&queue, &second).operation.status,
+        OperationStatus::Done
+    );
+}
+
+#[test]
+fn log_keeps_only_the_latest_lines() {
+    let queue = JobQueue::new(OperationRegistry::new());
+    let id = queue.enqueue(OperationKind::Sync, "chatty", |ctx| {
+        for i in 0..MAX_JOB_LOG_LINES + 3 {
+            ctx.log(format!("line {}", i));
+        }
+        Ok::<_, String>(())
+    });
+    let logs = wait_finished(&queue, &id).logs;
+    assert_eq!(logs.len(), MAX_JOB_LOG_LINES);
+    assert_eq!(logs[0], "line 3");
+}
@@ -0,0 +1,113 @@
+use crate::core::skill_store::SkillStore;
+
+use super::{get_locale, set_locale, text, trf, Locale, Msg, LOCALE_KEY};
+
+// Every `Msg`. The match below stops compiling when a variant is added, as a
+// reminder to list it here too.
+const ALL_MESSAGES: [Msg; 22] = [
+    Msg::Details,
+    Msg::CloneTempDirOmitted,
+    Msg::GithubTls,
+    Msg::GithubAuth,
+    Msg::GithubNotFound,
+    Msg::GithubDns,
+    Msg::GithubTimeout,
+    Msg::GithubConnection,
+    Msg::GithubUnreachable,
+    Msg::GitCliFailed,
+    Msg::GitTimeout,
+    Msg::MultiSkills,
+    Msg::RemoteSshFailed,
+    Msg::DeleteCleanupFailed,
+    Msg::MigrationPathNotAbsolute,
+    Msg::MigrationSourceMissing,
+    Msg::MigrationTargetExists,
+    Msg::MigrationIntoItself,
+    Msg::MigrationNoSpace,
+    Msg::MigrationNotWritable,
+    Msg::MigrationMoveFailed,
+    Msg::MigrationRecordsFailed,
+];
+
+fn placeholders(text: &str) -> Vec<&str> {
+    let mut names: Vec<&str> = text
+        .split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+        .collect();
+    names.sort_unstable();
+    names
+}
+
+#[test]
+fn every_message_has_all_three_languages() {
+    for msg in ALL_MESSAGES {
+        match msg {
+            Msg::Details
+            | Msg::CloneTempDirOmitted
+            | Msg::GithubTls
+            | Msg::GithubAuth
+            | Msg::GithubNotFound
+            | Msg::GithubDns
+            | Msg::GithubTimeout
+            | Msg::GithubConnection
+            | Msg::GithubUnreachable
+            | Msg::GitCliFailed
+            | Msg::GitTimeout
+            | Msg::MultiSkills
+            | Msg::RemoteSshFailed
+            | Msg::DeleteCleanupFailed
+            | Msg::MigrationPathNotAbsolute
+            | Msg::MigrationSourceMissing
+            | Msg::MigrationTargetExists
+            | Msg::MigrationIntoItself
+            | Msg::MigrationNoSpace
+            | Msg::MigrationNotWritable
+            | Msg::MigrationMoveFailed
+            | Msg::MigrationRecordsFailed => {}
+        }
+        let en = text(msg, Locale::En);
+        for locale in [Locale::En, Locale::ZhCn, Locale::ZhTw] {
+            let translated = text(msg, locale);
+            assert!(!translated.trim().is_empty(), "{:?} in {:?}", msg, locale);
+            assert_eq!(
+                placeholders(translated),
+                placeholders(en),
+                "{:?} in {:?}",
+                msg,
+                locale
+            );
+        }
+    }
+    assert!(text(Msg::GithubTimeout, Locale::En).starts_with("Connecting to GitHub timed out"));
+    assert!(text(Msg::GithubTimeout, Locale::ZhTw).contains("逾時"));
+}
+
+#[test]
+fn trf_fills_named_placeholders() {
+    // The process-wide locale defaults to English.
+    assert_eq!(
+        trf(
+            Msg::MigrationNoSpace,
+            &[("needed", "10"), ("available", "4")]
+        ),
+        "not enough space: 10 bytes needed, 4 available"
+    );
+}
+
+#[test]
+fn locale_setting_accepts_frontend_codes() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+
+    assert_eq!(get_locale(&store), Locale::En);
+    store.set_setting(LOCALE_KEY, "zh").unwrap();
+    assert_eq!(get_locale(&store), Locale::ZhCn);
+    store.set_setting(LOCALE_KEY, "zh-TW").unwrap();
+    assert_eq!(get_locale(&store), Locale::ZhTw);
+    // Rejected before anything is saved or applied.
+    assert!(set_locale(&store, "fr").is_err());
+    assert_eq!(get_locale(&store), Locale::ZhTw);
+    assert_eq!(Locale::ZhTw.code(), "zh-TW");
+}
@@ -0,0 +1,160 @@
+use super::*;
+
+fn stdio(name: &str) -> McpServer {
+    McpServer {
+        name: name.to_string(),
+        command: Some("npx".to_string()),
+        args: vec!["-y".to_string(), format!("@mcp/{}", name)],
+        env: BTreeMap::from([("TOKEN".to_string(), "x".to_string())]),
+        url: None,
+    }
+}
+
+fn remote(name: &str) -> McpServer {
+    McpServer {
+        name: name.to_string(),
+        command: None,
+        args: Vec::new(),
+        env: BTreeMap::new(),
+        url: Some("https://mcp.example.com/mcp".to_string()),
+    }
+}
+
+fn names(list: &[&str]) -> BTreeSet<String> {
+    list.iter().map(|n| n.to_string()).collect()
+}
+
+fn claude_json(server: &McpServer) -> Value {
+    render_json(server, "url", true)
+}
+
+#[test]
+fn upsert_validates_and_replaces_by_name() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+
+    assert!(upsert_mcp_server(
+        &store,
+        McpServer {
+            url: None,
+            ..remote("a")
+        }
+    )
+    .is_err());
+    let both = McpServer {
+        command: Some("x".to_string()),
+        ..remote("a")
+    };
+    assert!(upsert_mcp_server(&store, both).is_err());
+
+    upsert_mcp_server(&store, stdio("fs")).unwrap();
+    upsert_mcp_server(&store, remote("docs")).unwrap();
+    let servers = upsert_mcp_server(&store, remote("fs")).unwrap();
+    assert_eq!(servers.len(), 2);
+    assert_eq!(servers[0], remote("fs"));
+    assert_eq!(
+        remove_mcp_server(&store, "fs").unwrap(),
+        vec![remote("docs")]
+    );
+}
+
+#[test]
+fn json_merge_keeps_other_settings_and_user_entries() {
+    let existing = r#"{"theme":"dark","mcpServers":{"mine":{"command":"me"},"fs":{"command":"other"},"old":{"command":"gone"}}}"#;
+    let servers = vec![stdio("fs"), remote("docs")];
+
+    let (out, result) =
+        merge_json(existing, &servers, &names(&["old"]), false, claude_json).unwrap();
+    assert_eq!(result.written, vec!["docs"]);
+    assert_eq!(result.conflicts, vec!["fs"]);
+    assert_eq!(result.removed, vec!["old"]);
+    let root: Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(root["theme"], "dark");
+    assert_eq!(root["mcpServers"]["mine"]["command"], "me");
+    assert_eq!(root["mcpServers"]["fs"]["command"], "other");
+    assert_eq!(root["mcpServers"]["docs"]["type"], "http");
+    assert!(root["mcpServers"].get("old").is_none());
+
+    let (out, result) = merge_json(&out, &servers, &names(&["docs"]), true, claude_json).unwrap();
+    assert_eq!(result.written, vec!["fs", "docs"]);
+    let root: Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(root["mcpServers"]["fs"]["env"]["TOKEN"], "x");
+
+    // A second run changes nothing and keeps the file byte for byte.
+    let (again, _) =
+        merge_json(&out, &servers, &names(&["fs", "docs"]), false, claude_json).unwrap();
+    assert_eq!(again, out);
+
+    assert!(merge_json("[1]", &servers, &BTreeSet::new(), false, claude_json).is_err());
+    assert!(merge_json("{oops", &servers, &BTreeSet::new(), false, claude_json).is_err());
+    let (untouched, _) = merge_json("", &[], &BTreeSet::new(), false, claude_json).unwrap();
+    assert_eq!(untouched, "");
+}
+
+#[test]
+fn json_merge_keeps_key_order() {
+    let existing = r#"{"zeta":1,"mcpServers":{"b":{"command":"b"},"old":{"command":"x"},"a":{"command":"a"}},"alpha":2}"#;
+    let (out, _) = merge_json(
+        existing,
+        &[stdio("new")],
+        &names(&["old"]),
+        false,
+        claude_json,
+    )
+    .unwrap();
+    let root: Value = serde_json::from_str(&out).unwrap();
+    let keys: Vec<&str> = root
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect();
+    assert_eq!(keys, ["zeta", "mcpServers", "alpha"]);
+    let servers: Vec<&str> = root["mcpServers"]
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect();
+    assert_eq!(servers, ["b", "a", "new"]);
+}
+
+#[test]
+fn codex_toml_merge_replaces_only_managed_tables() {
+    let existing = "model = \"o3\" # keep me\n\n[mcp_servers.mine]\ncommand = \"me\"\n\n[mcp_servers.old]\ncommand = \"gone\"\n\n[mcp_servers.old.env]\nA = \"1\"\n\n[profiles.fast]\nmodel = \"mini\"\n";
+    let servers = vec![stdio("fs"), remote("my docs")];
+
+    let (out, result) = merge_codex_toml(existing, &servers, &names(&["old"]), false);
+    assert_eq!(result.written, vec!["fs", "my docs"]);
+    assert_eq!(result.removed, vec!["old"]);
+    assert!(out.starts_with("model = \"o3\" # keep me\n\n[mcp_servers.mine]\n"));
+    assert!(out.contains("[profiles.fast]\nmodel = \"mini\"\n"));
+    assert!(!out.contains("mcp_servers.old"));
+    assert!(out.contains(
+        "[mcp_servers.fs]\ncommand = \"npx\"\nargs = [\"-y\", \"@mcp/fs\"]\n\n[mcp_servers.fs.env]\nTOKEN = \"x\"\n"
+    ));
+    assert!(out.ends_with("[mcp_servers.\"my docs\"]\nurl = \"https://mcp.example.com/mcp\"\n"));
+
+    let (again, _) = merge_codex_toml(&out, &servers, &names(&["fs", "my docs"]), false);
+    assert_eq!(again, out);
+
+    let (_, result) = merge_codex_toml(existing, &[stdio("mine")], &BTreeSet::new(), false);
+    assert_eq!(result.conflicts, vec!["mine"]);
+}
+
+#[test]
+fn codex_toml_merge_keeps_array_tables_after_a_server() {
+    let existing = "[mcp_servers.fs]\ncommand = \"npx\"\nargs = [\"-y\", \"@mcp/fs\"]\n\n[mcp_servers.fs.env]\nTOKEN = \"x\"\n\n[[hooks.pre]]\nrun = \"lint\"\n";
+    let servers = vec![stdio("fs")];
+
+    let (out, result) = merge_codex_toml(existing, &servers, &names(&["fs"]), false);
+    assert_eq!(result.written, vec!["fs"]);
+    assert!(result.conflicts.is_empty());
+    assert_eq!(out, existing);
+
+    let (out, result) = merge_codex_toml(existing, &[], &names(&["fs"]), false);
+    assert_eq!(result.removed, vec!["fs"]);
+    assert!(!out.contains("mcp_servers.fs"));
+    assert!(out.contains("[[hooks.pre]]\nrun = \"lint\"\n"));
+}
@@ -0,0 +1,92 @@
+use crate::core::skill_store::{RemoteHostRecord, SkillRecord, SkillStore};
+
+use super::sync_skills_to_hosts;
+
+fn make_store() -> (tempfile::TempDir, SkillStore) {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    (dir, store)
+}
+
+// Port 1 on loopback refuses connections immediately.
+fn add_unreachable_host(store: &SkillStore, id: &str, group: Option<&str>) {
+    store
+        .upsert_remote_host(&RemoteHostRecord {
+            id: id.to_string(),
+            label: id.to_uppercase(),
+            host: "127.0.0.1".to_string(),
+            port: 1,
+            username: "me".to_string(),
+            auth_method: "key".to_string(),
+            key_path: None,
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            status: "idle".to_string(),
+            group_name: group.map(str::to_string),
+            health_json: None,
+        })
+        .unwrap();
+}
+
+#[test]
+fn host_groups_round_trip() {
+    let (_dir, store) = make_store();
+    add_unreachable_host(&store, "h1", Some("lab"));
+    add_unreachable_host(&store, "h2", None);
+
+    store.update_remote_host_group("h2", Some("lab")).unwrap();
+    store.update_remote_host_group("h1", None).unwrap();
+
+    let lab = store.list_remote_hosts_in_group("lab").unwrap();
+    assert_eq!(lab.len(), 1);
+    assert_eq!(lab[0].id, "h2");
+    assert_eq!(
+        store
+            .get_remote_host_by_id("h1")
+            .unwrap()
+            .unwrap()
+            .group_name,
+        None
+    );
+}
+
+#[test]
+fn unreachable_hosts_report_errors_in_input_order() {
+    let (_dir, store) = make_store();
+    for id in ["h1", "h2", "h3"] {
+        add_unreachable_host(&store, id, None);
+    }
+    store
+        .upsert_skill(&SkillRecord {
+            id: "s1".to_string(),
+            name: "demo".to_string(),
+            source_type: "local".to_string(),
+            source_ref: None,
+            source_revision: None,
+            central_path: "/tmp/demo".to_string(),
+            content_hash: None,
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: "ok".to_string(),
+            group_name: None,
+        })
+        .unwrap();
+
+    let hosts = vec!["h3".to_string(), "h1".to_string(), "h2".to_string()];
+    let results = sync_skills_to_hosts(&store, &hosts, &["s1".to_string()], &[], 2).unwrap();
+    let ids: Vec<&str> = results.iter().map(|r| r.host_id.as_str()).collect();
+    assert_eq!(ids, vec!["h3", "h1", "h2"]);
+    assert!(results
+        .iter()
+        .all(|r| r.error.is_some() && r.skills.is_empty()));
+    assert_eq!(
+        store.get_remote_host_by_id("h1").unwrap().unwrap().status,
+        "error"
+    );
+
+    assert!(sync_skills_to_hosts(&store, &["nope".to_string()], &[], &[], 2).is_err());
+}
@@ -0,0 +1,55 @@
+use std::cell::Cell;
+
+use super::*;
+
+const TTL: Duration = Duration::from_secs(60);
+
+#[test]
+fn serves_fresh_entries_and_falls_back_to_stale_ones() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    let calls = Cell::new(0);
+    let fetch = |value: &str| {
+        calls.set(calls.get() + 1);
+        Ok(vec![value.to_string()])
+    };
+
+    let first = cached_in(root, "search", "q=pdf", TTL, 1_000, || fetch("a")).unwrap();
+    assert_eq!(first, vec!["a"]);
+    // Within the TTL the API is not asked again.
+    let hit = cached_in(root, "search", "q=pdf", TTL, 30_000, || fetch("b")).unwrap();
+    assert_eq!(hit, vec!["a"]);
+    assert_eq!(calls.get(), 1);
+    // Other keys and namespaces are separate entries.
+    let other = cached_in(root, "detail", "q=pdf", TTL, 30_000, || fetch("c")).unwrap();
+    assert_eq!(other, vec!["c"]);
+
+    // Expired and the API fails: the old answer is still served.
+    let offline = cached_in::<Vec<String>>(root, "search", "q=pdf", TTL, 120_000, || {
+        anyhow::bail!("network down")
+    })
+    .unwrap();
+    assert_eq!(offline, vec!["a"]);
+    // Expired and the API answers: the entry is refreshed.
+    let refreshed = cached_in(root, "search", "q=pdf", TTL, 120_000, || fetch("d")).unwrap();
+    assert_eq!(refreshed, vec!["d"]);
+
+    let missing = cached_in::<Vec<String>>(root, "search", "q=xlsx", TTL, 0, || {
+        anyhow::bail!("network down")
+    });
+    assert!(missing.is_err());
+}
+
+#[test]
+fn prune_drops_old_and_unreadable_entries() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    cached_in(root, "search", "old", TTL, 0, || Ok(1)).unwrap();
+    cached_in(root, "search", "new", TTL, 90_000, || Ok(2)).unwrap();
+    std::fs::write(root.join("search").join("junk.json"), b"{").unwrap();
+
+    assert_eq!(prune_in(root, Duration::from_secs(60), 100_000).unwrap(), 2);
+    assert!(entry_path(root, "search", "new").exists());
+    assert!(!entry_path(root, "search", "old").exists());
+    assert_eq!(prune_in(&root.join("missing"), TTL, 0).unwrap(), 0);
+}
@@ -0,0 +1,68 @@
+use super::*;
+
+#[test]
+fn run_moves_through_queued_running_done() {
+    let seen: Arc<Mutex<Vec<OperationStatus>>> = Arc::new(Mutex::new(Vec::new()));
+    let sink = seen.clone();
+    let registry = OperationRegistry::with_notify(move |op| {
+        sink.lock().unwrap().push(op.status.clone());
+    });
+
+    let op = registry.start(OperationKind::Install, "https://example.com/repo.git");
+    let id = op.id.clone();
+    assert_eq!(registry.get(&id).unwrap().status, OperationStatus::Queued);
+    let value = op
+        .run(|op| {
+            op.progress(1, 4);
+            op.progress(1, 4);
+            Ok::<_, String>(7)
+        })
+        .unwrap();
+    assert_eq!(value, 7);
+
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec![
+            OperationStatus::Queued,
+            OperationStatus::Running { progress: None },
+            OperationStatus::Running { progress: Some(25) },
+            OperationStatus::Done,
+        ]
+    );
+    assert_eq!(registry.get(&id).unwrap().status, OperationStatus::Done);
+}
+
+#[test]
+fn failed_operations_keep_their_error_and_stay_finished() {
+    let registry = OperationRegistry::new();
+    let op = registry.start(OperationKind::RemotePush, "host-1");
+    let handle = op.clone();
+    let result = op.run(|_| Err::<(), _>(anyhow::anyhow!("boom").context("push skills")));
+    assert!(result.is_err());
+
+    handle.progress(3, 3);
+    let status = registry.get(&handle.id).unwrap().status;
+    assert_eq!(
+        status,
+        OperationStatus::Failed {
+            error: "push skills: boom".to_string()
+        }
+    );
+}
+
+#[test]
+fn list_is_newest_first_and_prunes_only_finished() {
+    let registry = OperationRegistry::new();
+    let active = registry.start(OperationKind::Sync, "active");
+    for i in 0..MAX_FINISHED_OPS + 5 {
+        let op = registry.start(OperationKind::Update, format!("op-{}", i));
+        op.finish(&Ok::<_, String>(()));
+    }
+    let last = registry.start(OperationKind::Sync, "last");
+
+    let ops = registry.list();
+    assert_eq!(ops.len(), MAX_FINISHED_OPS + 2);
+    assert_eq!(ops[0].id, last.id);
+    assert!(ops.iter().any(|op| op.id == active.id));
+    assert!(!ops.iter().any(|op| op.label == "op-0"));
+}
@@ -0,0 +1,88 @@
+use std::fs;
+
+use super::*;
+use crate::core::skill_store::{SkillRecord, SkillTargetRecord};
+
+#[cfg(unix)]
+#[test]
+fn finds_dangling_links_once_per_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    let skills = dir.path().join("skills");
+    fs::create_dir_all(skills.join("real")).unwrap();
+    std::os::unix::fs::symlink(skills.join("real"), skills.join("alive")).unwrap();
+    std::os::unix::fs::symlink(dir.path().join("gone"), skills.join("dead")).unwrap();
+
+    let dirs = vec![
+        ("codex".to_string(), skills.clone()),
+        ("goose".to_string(), skills.clone()),
+    ];
+    let links = find_dangling_links(&dirs).unwrap();
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].tool, "codex");
+    assert!(links[0].path.ends_with("dead"));
+    assert!(links[0].link_target.ends_with("gone"));
+}
+
+#[test]
+fn forgets_records_whose_target_is_gone() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    store
+        .upsert_skill(&SkillRecord {
+            id: "s1".to_string(),
+            name: "demo".to_string(),
+            source_type: "local".to_string(),
+            source_ref: None,
+            source_revision: None,
+            central_path: dir
+                .path()
+                .join("central/demo")
+                .to_string_lossy()
+                .to_string(),
+            content_hash: None,
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: "ok".to_string(),
+            group_name: None,
+        })
+        .unwrap();
+    let present = dir.path().join("codex/demo");
+    fs::create_dir_all(&present).unwrap();
+    for (tool, path) in [
+        ("codex", present.clone()),
+        ("cursor", dir.path().join("cursor/demo")),
+        ("remote:h1:codex", dir.path().join("remote/demo")),
+    ] {
+        store
+            .upsert_skill_target(&SkillTargetRecord {
+                id: format!("t-{}", tool),
+                skill_id: "s1".to_string(),
+                tool: tool.to_string(),
+                target_path: path.to_string_lossy().to_string(),
+                mode: "copy".to_string(),
+                status: "ok".to_string(),
+                last_error: None,
+                synced_at: None,
+                content_hash: None,
+            })
+            .unwrap();
+    }
+
+    let missing = find_missing_targets(&store).unwrap();
+    assert_eq!(missing.len(), 1);
+    assert_eq!(missing[0].tool, "cursor");
+
+    assert_eq!(forget_missing_targets(&store, Some(&[])).unwrap(), 0);
+    assert_eq!(forget_missing_targets(&store, None).unwrap(), 1);
+    let tools: Vec<String> = store
+        .list_skill_targets("s1")
+        .unwrap()
+        .into_iter()
+        .map(|t| t.tool)
+        .collect();
+    assert_eq!(tools.len(), 2);
+    assert!(!tools.contains(&"cursor".to_string()));
+}
@@ -0,0 +1,44 @@
+use super::*;
+
+#[test]
+fn profiles_get_their_own_database() {
+    let dir = tempfile::tempdir().unwrap();
+    let default_db = dir.path().join("skills_hub.db");
+    assert_eq!(profile_db_path(&default_db, DEFAULT_PROFILE_ID), default_db);
+
+    let work = create_profile(&default_db, "  Work Stuff ").unwrap();
+    assert_eq!(work.id, "work-stuff");
+    assert_eq!(work.name, "Work Stuff");
+    assert_eq!(
+        profile_db_path(&default_db, &work.id),
+        dir.path().join("profiles/work-stuff/skills_hub.db")
+    );
+    assert!(create_profile(&default_db, "work stuff").is_err());
+    assert!(create_profile(&default_db, "default").is_err());
+    assert_eq!(
+        create_profile(&default_db, "Work-Stuff!").unwrap().id,
+        "work-stuff-2"
+    );
+    assert_eq!(create_profile(&default_db, "研究").unwrap().id, "profile");
+
+    set_next_profile(&default_db, &work.id).unwrap();
+    assert!(set_next_profile(&default_db, "nope").is_err());
+    let list = list_profiles(&default_db);
+    assert_eq!(list.active, DEFAULT_PROFILE_ID);
+    assert_eq!(list.next, "work-stuff");
+    let ids: Vec<_> = list.profiles.iter().map(|p| p.id.as_str()).collect();
+    assert_eq!(
+        ids,
+        vec!["default", "work-stuff", "work-stuff-2", "profile"]
+    );
+
+    let work_db = profile_db_path(&default_db, &work.id);
+    std::fs::create_dir_all(work_db.parent().unwrap()).unwrap();
+    std::fs::write(&work_db, "").unwrap();
+    assert!(delete_profile(&default_db, DEFAULT_PROFILE_ID).is_err());
+    delete_profile(&default_db, &work.id).unwrap();
+    assert!(!work_db.exists());
+    let list = list_profiles(&default_db);
+    assert_eq!(list.next, DEFAULT_PROFILE_ID);
+    assert_eq!(list.profiles.len(), 3);
+}
@@ -0,0 +1,62 @@
+use crate::core::skill_store::SkillStore;
+
+use super::{
+    get_workspace_roots, list_detected_projects, project_markers, scan_and_store, scan_projects,
+    set_workspace_roots,
+};
+
+fn markers() -> Vec<String> {
+    vec![".claude".to_string(), ".cursor".to_string()]
+}
+
+#[test]
+fn markers_come_from_tool_adapters() {
+    let markers = project_markers();
+    assert!(markers.contains(&".claude".to_string()));
+    assert!(markers.contains(&".cursor".to_string()));
+    assert!(!markers.contains(&".config".to_string()));
+}
+
+#[test]
+fn finds_projects_and_skips_vendored_dirs() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::create_dir_all(root.join("app/.claude")).unwrap();
+    std::fs::create_dir_all(root.join("app/.cursor")).unwrap();
+    // Nested inside a detected project: not reported separately.
+    std::fs::create_dir_all(root.join("app/packages/web/.claude")).unwrap();
+    std::fs::create_dir_all(root.join("group/lib/.cursor")).unwrap();
+    std::fs::create_dir_all(root.join("app2/node_modules/dep/.claude")).unwrap();
+    std::fs::create_dir_all(root.join("plain/src")).unwrap();
+
+    let roots = vec![root.to_string_lossy().to_string()];
+    let found = scan_projects(&roots, &markers());
+    let paths: Vec<_> = found.iter().map(|p| p.path.clone()).collect();
+    assert_eq!(
+        paths,
+        vec![
+            root.join("app").to_string_lossy().to_string(),
+            root.join("group/lib").to_string_lossy().to_string(),
+        ]
+    );
+    assert_eq!(found[0].markers, vec![".claude", ".cursor"]);
+}
+
+#[test]
+fn scan_uses_saved_roots_and_stores_result() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    let workspace = dir.path().join("ws");
+    std::fs::create_dir_all(workspace.join("proj/.claude")).unwrap();
+
+    assert!(set_workspace_roots(&store, &["relative/path".to_string()]).is_err());
+    let root = workspace.to_string_lossy().to_string();
+    let saved = set_workspace_roots(&store, &[format!("{}/", root), root.clone()]).unwrap();
+    assert_eq!(saved, vec![root.clone()]);
+    assert_eq!(get_workspace_roots(&store), vec![root]);
+
+    let found = scan_and_store(&store, &[]).unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(list_detected_projects(&store), found);
+}
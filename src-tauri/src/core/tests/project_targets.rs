@@ -0,0 +1,74 @@
+use crate::core::skill_store::{SkillRecord, SkillStore};
+
+use super::*;
+
+fn setup() -> (tempfile::TempDir, SkillStore, PathBuf) {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    let central = dir.path().join("central/demo");
+    std::fs::create_dir_all(&central).unwrap();
+    std::fs::write(central.join("SKILL.md"), "# demo").unwrap();
+    store
+        .upsert_skill(&SkillRecord {
+            id: "s1".to_string(),
+            name: "demo".to_string(),
+            source_type: "local".to_string(),
+            source_ref: None,
+            source_revision: None,
+            central_path: central.to_string_lossy().to_string(),
+            content_hash: Some("h1".to_string()),
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: "ok".to_string(),
+            group_name: None,
+        })
+        .unwrap();
+    let project = dir.path().join("work/app");
+    std::fs::create_dir_all(&project).unwrap();
+    (dir, store, project)
+}
+
+#[test]
+fn syncs_into_the_project_tool_dir_and_tracks_drift() {
+    let (_dir, store, project) = setup();
+    let project_str = project.to_string_lossy().to_string();
+
+    let result = sync_skill_to_project(&store, "s1", &project_str, "claude_code", false).unwrap();
+    let target = project.join(".claude/skills/demo");
+    assert_eq!(Path::new(&result.target.target_path), target);
+    assert!(target.join("SKILL.md").exists());
+    assert_eq!(result.target.status, "ok");
+
+    // Re-syncing updates the same record.
+    let again = sync_skill_to_project(&store, "s1", &project_str, "claude_code", true).unwrap();
+    assert_eq!(again.target.id, result.target.id);
+    assert_eq!(list_project_targets(&store, Some("s1")).unwrap().len(), 1);
+
+    remove_target_path(&target).unwrap();
+    assert_eq!(
+        list_project_targets(&store, None).unwrap()[0].status,
+        "missing"
+    );
+
+    unsync_project_target(&store, &result.target.id).unwrap();
+    assert!(list_project_targets(&store, None).unwrap().is_empty());
+
+    assert!(sync_skill_to_project(&store, "s1", "relative/app", "claude_code", false).is_err());
+    assert!(sync_skill_to_project(&store, "nope", &project_str, "claude_code", false).is_err());
+}
+
+#[test]
+fn deleted_projects_are_reported_and_cleaned_up() {
+    let (_dir, store, project) = setup();
+    let project_str = project.to_string_lossy().to_string();
+    sync_skill_to_project(&store, "s1", &project_str, "cursor", false).unwrap();
+
+    std::fs::remove_dir_all(&project).unwrap();
+    let listed = list_project_targets(&store, None).unwrap();
+    assert_eq!(listed[0].status, "project_missing");
+    assert_eq!(cleanup_missing_projects(&store).unwrap(), 1);
+    assert!(store.list_project_targets().unwrap().is_empty());
+}
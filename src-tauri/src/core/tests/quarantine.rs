@@ -0,0 +1,65 @@
+use crate::core::errors::{find_coded, ErrorCode};
+use crate::core::skill_store::{SkillRecord, SkillStore};
+
+use super::{
+    approve_skill, ensure_approved, get_quarantine_new_installs, install_status,
+    set_quarantine_new_installs, status_after_update, QUARANTINED_STATUS,
+};
+
+fn record(status: &str) -> SkillRecord {
+    SkillRecord {
+        id: "s1".to_string(),
+        name: "demo".to_string(),
+        source_type: "git".to_string(),
+        source_ref: Some("https://github.com/o/r".to_string()),
+        source_revision: None,
+        central_path: "/tmp/demo".to_string(),
+        content_hash: None,
+        created_at: 1,
+        updated_at: 1,
+        last_sync_at: None,
+        last_seen_at: 1,
+        status: status.to_string(),
+        group_name: None,
+    }
+}
+
+#[test]
+fn new_installs_are_quarantined_only_when_the_policy_is_on() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+
+    assert!(!get_quarantine_new_installs(&store));
+    assert_eq!(install_status(&store), "ok");
+    assert!(set_quarantine_new_installs(&store, true).unwrap());
+    assert_eq!(install_status(&store), QUARANTINED_STATUS);
+
+    assert_eq!(
+        status_after_update(&record(QUARANTINED_STATUS)),
+        QUARANTINED_STATUS
+    );
+    assert_eq!(status_after_update(&record("ok")), "ok");
+}
+
+#[test]
+fn quarantined_skills_cannot_sync_until_approved() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    store.upsert_skill(&record(QUARANTINED_STATUS)).unwrap();
+
+    let skill = store.get_skill_by_id("s1").unwrap().unwrap();
+    let err = ensure_approved(&skill).unwrap_err();
+    let coded = find_coded(&err).unwrap();
+    assert_eq!(coded.code, ErrorCode::SkillQuarantined);
+    assert_eq!(coded.payload.as_ref().unwrap()["skill_id"], "s1");
+
+    approve_skill(&store, "s1").unwrap();
+    let skill = store.get_skill_by_id("s1").unwrap().unwrap();
+    assert_eq!(skill.status, "ok");
+    ensure_approved(&skill).unwrap();
+    // Approving twice is harmless.
+    approve_skill(&store, "s1").unwrap();
+    assert!(approve_skill(&store, "missing").is_err());
+}
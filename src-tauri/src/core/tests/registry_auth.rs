@@ -0,0 +1,154 @@
+use mockito::Matcher;
+
+use crate::core::skill_store::SkillStore;
+
+use super::{
+    authorize, list_registry_auth, poll_device_login, registry_origin, set_registry_auth,
+    start_device_login, DevicePollStatus, RegistryAuth,
+};
+
+fn make_store() -> (tempfile::TempDir, SkillStore) {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    (dir, store)
+}
+
+fn oidc(server: &mockito::Server) -> RegistryAuth {
+    RegistryAuth::Oidc {
+        device_authorization_endpoint: format!("{}/device", server.url()),
+        token_endpoint: format!("{}/token", server.url()),
+        client_id: "skills-hub".to_string(),
+        scope: Some("read".to_string()),
+        access_token: None,
+        refresh_token: None,
+        expires_at: None,
+    }
+}
+
+#[test]
+fn origins_ignore_path_and_keep_port() {
+    assert_eq!(
+        registry_origin("https://reg.example.com/api/v1/search?q=x").as_deref(),
+        Some("https://reg.example.com")
+    );
+    assert_eq!(
+        registry_origin("http://127.0.0.1:8080/x").as_deref(),
+        Some("http://127.0.0.1:8080")
+    );
+    assert_eq!(registry_origin("not a url"), None);
+}
+
+#[test]
+fn basic_and_bearer_credentials_are_attached() {
+    let (_dir, store) = make_store();
+    let mut server = mockito::Server::new();
+    let url = format!("{}/api/v1/search", server.url());
+    let mock = server
+        .mock("GET", "/api/v1/search")
+        .match_header("authorization", "Basic dXNlcjpwYXNz")
+        .with_status(200)
+        .create();
+
+    set_registry_auth(
+        &store,
+        &url,
+        Some(RegistryAuth::Basic {
+            username: "user".to_string(),
+            password: "pass".to_string(),
+        }),
+    )
+    .unwrap();
+    let client = reqwest::blocking::Client::new();
+    let status = authorize(client.get(&url), &url).unwrap().send().unwrap();
+    assert_eq!(status.status(), 200);
+    mock.assert();
+
+    let mock = server
+        .mock("GET", "/api/v1/search")
+        .match_header("authorization", "Bearer t0k")
+        .with_status(200)
+        .create();
+    let summaries = set_registry_auth(
+        &store,
+        &url,
+        Some(RegistryAuth::Bearer {
+            token: "t0k".to_string(),
+        }),
+    )
+    .unwrap();
+    assert_eq!(summaries.len(), 1);
+    assert_eq!(summaries[0].kind, "bearer");
+    authorize(client.get(&url), &url).unwrap().send().unwrap();
+    mock.assert();
+
+    set_registry_auth(&store, &url, None).unwrap();
+    assert!(list_registry_auth(&store).is_empty());
+}
+
+#[test]
+fn device_flow_saves_tokens_and_refreshes_them() {
+    let (_dir, store) = make_store();
+    let mut server = mockito::Server::new();
+    let registry = format!("{}/api", server.url());
+    set_registry_auth(&store, &registry, Some(oidc(&server))).unwrap();
+
+    let _device = server
+        .mock("POST", "/device")
+        .with_status(200)
+        .with_body(
+            r#"{"device_code":"dc","user_code":"ABCD","verification_uri":"https://sso/activate","expires_in":600}"#,
+        )
+        .create();
+    let login = start_device_login(&store, &registry).unwrap();
+    assert_eq!(login.user_code, "ABCD");
+    assert_eq!(login.interval, 5);
+
+    let pending = server
+        .mock("POST", "/token")
+        .match_body(Matcher::Regex("device_code=dc".to_string()))
+        .with_status(400)
+        .with_body(r#"{"error":"authorization_pending"}"#)
+        .create();
+    assert_eq!(
+        poll_device_login(&store, &registry, "dc").unwrap(),
+        DevicePollStatus::Pending
+    );
+    pending.remove();
+
+    // Issued already expired so the next request has to refresh it.
+    let _granted = server
+        .mock("POST", "/token")
+        .match_body(Matcher::Regex("device_code=dc".to_string()))
+        .with_status(200)
+        .with_body(r#"{"access_token":"old","refresh_token":"r1","expires_in":0}"#)
+        .create();
+    assert_eq!(
+        poll_device_login(&store, &registry, "dc").unwrap(),
+        DevicePollStatus::Complete
+    );
+    assert!(list_registry_auth(&store)[0].signed_in);
+
+    let refresh = server
+        .mock("POST", "/token")
+        .match_body(Matcher::Regex("grant_type=refresh_token".to_string()))
+        .with_status(200)
+        .with_body(r#"{"access_token":"new","expires_in":3600}"#)
+        .expect(1)
+        .create();
+    let api = server
+        .mock("GET", "/api")
+        .match_header("authorization", "Bearer new")
+        .with_status(200)
+        .expect(2)
+        .create();
+    let client = reqwest::blocking::Client::new();
+    for _ in 0..2 {
+        authorize(client.get(&registry), &registry)
+            .unwrap()
+            .send()
+            .unwrap();
+    }
+    refresh.assert();
+    api.assert();
+}
@@ -0,0 +1,121 @@
+use crate::core::skill_store::{RemoteHostRecord, SkillRecord, SkillStore};
+
+use super::{
+    forget_remote_tool, parse_remote_target_key, propagate_skill_update, record_remote_deployment,
+    remote_target_key,
+};
+
+fn make_store() -> (tempfile::TempDir, SkillStore) {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    store
+        .upsert_remote_host(&RemoteHostRecord {
+            id: "h1".to_string(),
+            label: "VM".to_string(),
+            host: "10.0.0.1".to_string(),
+            port: 22,
+            username: "me".to_string(),
+            auth_method: "key".to_string(),
+            key_path: None,
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            status: "idle".to_string(),
+            group_name: None,
+            health_json: None,
+        })
+        .unwrap();
+    (dir, store)
+}
+
+fn make_skill(store: &SkillStore, hash: &str) -> SkillRecord {
+    let record = SkillRecord {
+        id: "s1".to_string(),
+        name: "demo".to_string(),
+        source_type: "local".to_string(),
+        source_ref: None,
+        source_revision: None,
+        central_path: "/tmp/demo".to_string(),
+        content_hash: Some(hash.to_string()),
+        created_at: 1,
+        updated_at: 1,
+        last_sync_at: None,
+        last_seen_at: 1,
+        status: "ok".to_string(),
+        group_name: None,
+    };
+    store.upsert_skill(&record).unwrap();
+    record
+}
+
+#[test]
+fn records_and_merges_tool_keys() {
+    let (_dir, store) = make_store();
+    let skill = make_skill(&store, "h-1");
+
+    record_remote_deployment(&store, "h1", &skill, &["claude_code".to_string()], true).unwrap();
+    record_remote_deployment(
+        &store,
+        "h1",
+        &skill,
+        &["codex".to_string(), "claude_code".to_string()],
+        true,
+    )
+    .unwrap();
+    let deployment = store.get_remote_deployment("h1", "s1").unwrap().unwrap();
+    assert_eq!(deployment.tool_keys, vec!["claude_code", "codex"]);
+    assert_eq!(deployment.content_hash.as_deref(), Some("h-1"));
+
+    forget_remote_tool(&store, "h1", "s1", "claude_code").unwrap();
+    let deployment = store.get_remote_deployment("h1", "s1").unwrap().unwrap();
+    assert_eq!(deployment.tool_keys, vec!["codex"]);
+}
+
+#[test]
+fn tool_links_are_tracked_as_remote_targets() {
+    let (_dir, store) = make_store();
+    let skill = make_skill(&store, "h-1");
+    let key = remote_target_key("h1", "codex");
+    assert_eq!(parse_remote_target_key(&key), Some(("h1", "codex")));
+    assert_eq!(parse_remote_target_key("custom:ct1"), None);
+
+    record_remote_deployment(&store, "h1", &skill, &["codex".to_string()], true).unwrap();
+    let target = store.get_skill_target("s1", &key).unwrap().unwrap();
+    assert_eq!(target.target_path, "~/.codex/skills/demo");
+    assert_eq!(target.content_hash.as_deref(), Some("h-1"));
+
+    forget_remote_tool(&store, "h1", "s1", "codex").unwrap();
+    assert!(store.get_skill_target("s1", &key).unwrap().is_none());
+
+    record_remote_deployment(&store, "h1", &skill, &["codex".to_string()], true).unwrap();
+    store.delete_remote_deployment("h1", "s1").unwrap();
+    assert!(store.list_skill_targets("s1").unwrap().is_empty());
+
+    record_remote_deployment(&store, "h1", &skill, &["codex".to_string()], true).unwrap();
+    store.delete_remote_host("h1").unwrap();
+    assert!(store.list_skill_targets("s1").unwrap().is_empty());
+}
+
+#[test]
+fn update_without_propagation_marks_hosts_stale() {
+    let (_dir, store) = make_store();
+    let skill = make_skill(&store, "h-1");
+    record_remote_deployment(&store, "h1", &skill, &["codex".to_string()], false).unwrap();
+    assert!(!store.get_skill_propagate_to_remotes("s1").unwrap());
+
+    // Unchanged content: nothing to do.
+    let report = propagate_skill_update(&store, "s1").unwrap();
+    assert!(report.stale.is_empty() && report.pushed.is_empty());
+
+    make_skill(&store, "h-2");
+    let report = propagate_skill_update(&store, "s1").unwrap();
+    assert_eq!(report.stale, vec!["h1"]);
+    assert!(report.pushed.is_empty());
+    let deployment = store.get_remote_deployment("h1", "s1").unwrap().unwrap();
+    assert_eq!(deployment.status, "stale");
+    assert_eq!(deployment.content_hash.as_deref(), Some("h-1"));
+
+    store.set_skill_propagate_to_remotes("s1", true).unwrap();
+    assert!(store.get_skill_propagate_to_remotes("s1").unwrap());
+}
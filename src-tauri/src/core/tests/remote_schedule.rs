@@ -0,0 +1,93 @@
+use crate::core::skill_store::{RemoteHostRecord, SkillStore};
+
+use super::{
+    get_schedule, interval_run_due, last_run, run_scheduled_sync, set_schedule, RemoteSyncSchedule,
+};
+
+fn make_store() -> (tempfile::TempDir, SkillStore) {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    (dir, store)
+}
+
+// Port 1 on loopback refuses connections immediately.
+fn add_unreachable_host(store: &SkillStore, id: &str) {
+    store
+        .upsert_remote_host(&RemoteHostRecord {
+            id: id.to_string(),
+            label: id.to_uppercase(),
+            host: "127.0.0.1".to_string(),
+            port: 1,
+            username: "me".to_string(),
+            auth_method: "key".to_string(),
+            key_path: None,
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            status: "idle".to_string(),
+            group_name: None,
+            health_json: None,
+        })
+        .unwrap();
+}
+
+fn schedule(host_ids: &[&str], interval_minutes: u32) -> RemoteSyncSchedule {
+    RemoteSyncSchedule {
+        enabled: true,
+        host_ids: host_ids.iter().map(|h| h.to_string()).collect(),
+        tool_keys: Vec::new(),
+        interval_minutes,
+        run_at_start: false,
+    }
+}
+
+#[test]
+fn schedule_round_trips_and_validates() {
+    let (_dir, store) = make_store();
+    add_unreachable_host(&store, "h1");
+
+    assert_eq!(get_schedule(&store), RemoteSyncSchedule::default());
+    set_schedule(&store, schedule(&["h1"], 30)).unwrap();
+    assert_eq!(get_schedule(&store), schedule(&["h1"], 30));
+
+    assert!(set_schedule(&store, schedule(&["h1"], 1)).is_err());
+    assert!(set_schedule(&store, schedule(&["missing"], 30)).is_err());
+}
+
+#[test]
+fn interval_runs_are_due_after_the_interval() {
+    let minute = 60 * 1000;
+    let s = schedule(&["h1"], 10);
+    assert!(interval_run_due(&s, None, 0));
+    assert!(!interval_run_due(&s, Some(0), 9 * minute));
+    assert!(interval_run_due(&s, Some(0), 10 * minute));
+
+    assert!(!interval_run_due(&schedule(&["h1"], 0), None, 0));
+    assert!(!interval_run_due(&schedule(&[], 10), None, 0));
+    let disabled = RemoteSyncSchedule {
+        enabled: false,
+        ..s
+    };
+    assert!(!interval_run_due(&disabled, None, 0));
+}
+
+#[test]
+fn run_records_per_host_results() {
+    let (_dir, store) = make_store();
+    add_unreachable_host(&store, "h1");
+    set_schedule(&store, schedule(&["h1"], 30)).unwrap();
+
+    let mut started = false;
+    let run = run_scheduled_sync(&store, "manual", || started = true)
+        .unwrap()
+        .unwrap();
+    assert!(started);
+    assert_eq!(run.trigger, "manual");
+    assert_eq!(run.hosts.len(), 1);
+    assert!(run.hosts[0].error.is_some());
+
+    let stored = last_run(&store).unwrap();
+    assert_eq!(stored.started_at, run.started_at);
+    assert_eq!(stored.hosts[0].host_id, "h1");
+}
@@ -0,0 +1,48 @@
+use std::fs;
+
+use super::*;
+use crate::core::sync_engine::SyncMode;
+use crate::core::tool_adapters::adapter_by_key;
+
+#[test]
+fn resource_dirs_exist_only_for_tools_that_take_them() {
+    let claude = adapter_by_key("claude_code").unwrap();
+    let agents = resource_dir(&claude, ResourceType::Agent).unwrap().unwrap();
+    assert!(agents.ends_with(".claude/agents"));
+    assert_eq!(
+        resource_dir(&claude, ResourceType::Skill).unwrap(),
+        Some(resolve_default_path(&claude).unwrap())
+    );
+
+    let codex = adapter_by_key("codex").unwrap();
+    assert!(resource_dir(&codex, ResourceType::Agent).unwrap().is_none());
+    assert!(tools_supporting(ResourceType::Command).contains(&"codex".to_string()));
+    assert!(!tools_supporting(ResourceType::Agent).contains(&"codex".to_string()));
+    assert_eq!(ResourceType::parse("mystery"), ResourceType::Skill);
+}
+
+#[test]
+fn syncs_resource_file_as_link_or_copy() {
+    let central = tempfile::tempdir().unwrap();
+    fs::write(central.path().join("review.md"), "# Review\n").unwrap();
+    fs::write(central.path().join("notes.txt"), "ignored").unwrap();
+    let source = resource_file(central.path()).unwrap();
+    assert_eq!(source, central.path().join("review.md"));
+
+    let tool_dir = tempfile::tempdir().unwrap();
+    let target = resource_target(tool_dir.path(), "code-review", &source);
+    assert_eq!(target, tool_dir.path().join("code-review.md"));
+
+    let out = sync_resource_file("claude_code", central.path(), &target, false, false).unwrap();
+    assert!(matches!(out.mode_used, SyncMode::Symlink | SyncMode::Copy));
+    assert_eq!(fs::read_to_string(&target).unwrap(), "# Review\n");
+    assert!(sync_resource_file("claude_code", central.path(), &target, false, true).is_err());
+
+    let out = sync_resource_file("cursor", central.path(), &target, true, false).unwrap();
+    assert!(matches!(out.mode_used, SyncMode::Copy));
+    assert!(out.replaced);
+    assert!(fs::read_link(&target).is_err());
+
+    let empty = tempfile::tempdir().unwrap();
+    assert!(resource_file(empty.path()).is_err());
+}
@@ -0,0 +1,127 @@
+use super::*;
+use crate::core::central_history::{ensure_history_repo, get_skill_history};
+
+#[test]
+fn validates_skill_names() {
+    for name in ["demo", "pdf-tools", "v2-notes"] {
+        assert!(validate_skill_name(name).is_ok(), "{}", name);
+    }
+    for name in ["", "Demo", "-demo", "demo-", "a--b", "a/b", ".."] {
+        assert!(validate_skill_name(name).is_err(), "{}", name);
+    }
+    assert!(validate_skill_name(&"a".repeat(65)).is_err());
+}
+
+#[test]
+fn resolves_files_inside_the_skill_only() {
+    let dir = tempfile::tempdir().unwrap();
+    let skill = dir.path().join("demo");
+    std::fs::create_dir_all(skill.join("scripts")).unwrap();
+
+    assert_eq!(
+        resolve_skill_file(&skill, "SKILL.md").unwrap(),
+        skill.join("SKILL.md")
+    );
+    assert!(resolve_skill_file(&skill, "refs/new.md").is_ok());
+    for bad in [
+        "",
+        ".",
+        "../other/SKILL.md",
+        "a/../../x",
+        ".git/config",
+        "scripts",
+    ] {
+        assert!(resolve_skill_file(&skill, bad).is_err(), "{}", bad);
+    }
+    let abs = dir.path().join("x.md");
+    assert!(resolve_skill_file(&skill, &abs.to_string_lossy()).is_err());
+
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(dir.path(), skill.join("out")).unwrap();
+        assert!(resolve_skill_file(&skill, "out/x.md").is_err());
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn atomic_write_keeps_permissions() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let script = dir.path().join("run.sh");
+    std::fs::write(&script, "old").unwrap();
+    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    write_file_atomic(&script, "new").unwrap();
+    assert_eq!(std::fs::read_to_string(&script).unwrap(), "new");
+    let mode = std::fs::metadata(&script).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o755);
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn write_updates_hash_and_keeps_the_previous_version() {
+    let central = tempfile::tempdir().unwrap();
+    let skill_dir = central.path().join("demo");
+    std::fs::create_dir_all(&skill_dir).unwrap();
+    std::fs::write(skill_dir.join("SKILL.md"), "v1").unwrap();
+    ensure_history_repo(central.path()).unwrap();
+    // An edit made outside the app is snapshotted before being replaced.
+    std::fs::write(skill_dir.join("SKILL.md"), "v1 edited").unwrap();
+
+    let db = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(db.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    store
+        .upsert_skill(&SkillRecord {
+            id: "s1".to_string(),
+            name: "demo".to_string(),
+            source_type: AUTHORED_SOURCE_TYPE.to_string(),
+            source_ref: None,
+            source_revision: None,
+            central_path: skill_dir.to_string_lossy().to_string(),
+            content_hash: None,
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: "ok".to_string(),
+            group_name: None,
+        })
+        .unwrap();
+
+    let result = write_skill_content(&store, "s1", "SKILL.md", "v2").unwrap();
+    assert_eq!(
+        std::fs::read_to_string(skill_dir.join("SKILL.md")).unwrap(),
+        "v2"
+    );
+    let stored = store.get_skill_by_id("s1").unwrap().unwrap();
+    assert!(stored.content_hash.is_some());
+    assert_eq!(stored.content_hash, result.content_hash);
+
+    let messages: Vec<String> = get_skill_history(&store, "s1")
+        .unwrap()
+        .into_iter()
+        .map(|rev| rev.message)
+        .collect();
+    assert_eq!(messages[0], "Edit demo/SKILL.md");
+    assert_eq!(messages[1], "Before editing demo/SKILL.md");
+
+    assert!(write_skill_content(&store, "s1", "../escape.md", "x").is_err());
+}
+
+#[test]
+fn duplicate_renames_only_the_frontmatter_name() {
+    let text = "---\nname: pdf\ndescription: x\n---\n\nname: pdf is mentioned here\n";
+    assert_eq!(
+        with_frontmatter_name(text, "pdf-custom").unwrap(),
+        "---\nname: pdf-custom\ndescription: x\n---\n\nname: pdf is mentioned here\n"
+    );
+    assert_eq!(
+        with_frontmatter_name("---\r\nname: a\r\n---\r\n", "b").unwrap(),
+        "---\r\nname: b\r\n---\r\n"
+    );
+    assert!(with_frontmatter_name("# no frontmatter\nname: a\n", "b").is_none());
+    assert!(with_frontmatter_name("---\ndescription: x\n---\nname: a\n", "b").is_none());
+}
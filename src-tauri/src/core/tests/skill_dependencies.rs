@@ -0,0 +1,80 @@
+use super::*;
+
+fn skill(id: &str, source_ref: &str) -> SkillRecord {
+    SkillRecord {
+        id: id.to_string(),
+        name: id.to_string(),
+        source_type: "git".to_string(),
+        source_ref: Some(source_ref.to_string()),
+        source_revision: None,
+        central_path: format!("/central/{}", id),
+        content_hash: None,
+        created_at: 1,
+        updated_at: 1,
+        last_sync_at: None,
+        last_seen_at: 1,
+        status: "ok".to_string(),
+        group_name: None,
+    }
+}
+
+#[test]
+fn parses_clawhub_and_git_specs() {
+    assert_eq!(
+        parse_dependency("clawhub://pdf-tools"),
+        Some(DependencySource::Clawhub {
+            slug: "pdf-tools".to_string()
+        })
+    );
+    assert_eq!(
+        parse_dependency(" clawhub:pdf-tools "),
+        parse_dependency("clawhub://pdf-tools")
+    );
+    for spec in [
+        "https://github.com/acme/skills/tree/main/pdf",
+        "git@github.com:acme/skills.git",
+        "acme/skills",
+    ] {
+        assert!(
+            matches!(parse_dependency(spec), Some(DependencySource::Git { .. })),
+            "{}",
+            spec
+        );
+    }
+    for spec in ["", "pdf-tools", "./local", "clawhub://"] {
+        assert_eq!(parse_dependency(spec), None, "{}", spec);
+    }
+}
+
+#[test]
+fn matches_installed_skills_by_source() {
+    let skills = vec![
+        skill("a", "https://github.com/Acme/Skills/tree/dev/pdf"),
+        skill("b", "clawhub://notes"),
+        skill("c", "https://github.com/acme/other.git"),
+    ];
+    let specs: Vec<String> = [
+        "acme/skills/tree/main/pdf",
+        "clawhub://notes",
+        "https://github.com/acme/other",
+        "acme/skills/tree/main/docx",
+        "just-a-name",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+
+    let deps = resolve(&skills, &specs, "x");
+    let installed: Vec<Option<&str>> = deps
+        .iter()
+        .map(|d| d.installed_skill_id.as_deref())
+        .collect();
+    assert_eq!(installed, vec![Some("a"), Some("b"), Some("c"), None, None]);
+    assert!(deps[4].source.is_none());
+
+    // A skill does not satisfy its own dependency.
+    assert_eq!(
+        resolve(&skills, &specs[1..2], "b")[0].installed_skill_id,
+        None
+    );
+}
@@ -0,0 +1,180 @@
+use std::fs;
+use std::path::Path;
+
+use crate::core::skill_store::{ProjectTargetRecord, SkillRecord, SkillStore, SkillTargetRecord};
+
+use super::{
+    build_manifest, detect_skill_drift, diff_manifests, record_skill_manifest, DriftChange,
+    FileDrift,
+};
+
+fn make_store(dir: &Path) -> SkillStore {
+    let store = SkillStore::new(dir.join("test.db"));
+    store.ensure_schema().unwrap();
+    store
+}
+
+fn write_skill(root: &Path) {
+    fs::create_dir_all(root.join("scripts")).unwrap();
+    fs::write(root.join("SKILL.md"), "# pdf\n").unwrap();
+    fs::write(root.join("scripts/run.sh"), "echo hi\n").unwrap();
+}
+
+fn add_skill(store: &SkillStore, central: &Path) {
+    store
+        .upsert_skill(&SkillRecord {
+            id: "s1".to_string(),
+            name: "pdf".to_string(),
+            source_type: "local".to_string(),
+            source_ref: None,
+            source_revision: None,
+            central_path: central.to_string_lossy().to_string(),
+            content_hash: None,
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: "ok".to_string(),
+            group_name: None,
+        })
+        .unwrap();
+}
+
+fn drift(path: &str, change: DriftChange) -> FileDrift {
+    FileDrift {
+        path: path.to_string(),
+        change,
+    }
+}
+
+#[test]
+fn manifest_skips_ignored_files_and_diffs_by_path() {
+    let dir = tempfile::tempdir().unwrap();
+    write_skill(dir.path());
+    fs::create_dir_all(dir.path().join(".git")).unwrap();
+    fs::write(dir.path().join(".git/HEAD"), "ref").unwrap();
+    fs::write(dir.path().join(".DS_Store"), "x").unwrap();
+
+    let base = build_manifest(dir.path()).unwrap();
+    let keys: Vec<&str> = base.keys().map(String::as_str).collect();
+    assert_eq!(keys, vec!["SKILL.md", "scripts/run.sh"]);
+
+    fs::write(dir.path().join("SKILL.md"), "# pdf v2\n").unwrap();
+    fs::remove_file(dir.path().join("scripts/run.sh")).unwrap();
+    fs::write(dir.path().join("notes.md"), "new").unwrap();
+    let current = build_manifest(dir.path()).unwrap();
+    assert_eq!(
+        diff_manifests(&base, &current),
+        vec![
+            drift("SKILL.md", DriftChange::Modified),
+            drift("notes.md", DriftChange::Added),
+            drift("scripts/run.sh", DriftChange::Removed),
+        ]
+    );
+    assert!(diff_manifests(&current, &current).is_empty());
+}
+
+#[test]
+fn reports_central_edits_and_copy_target_drift() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = make_store(dir.path());
+    let central = dir.path().join("central/pdf");
+    write_skill(&central);
+    add_skill(&store, &central);
+
+    let report = detect_skill_drift(&store, "s1").unwrap();
+    assert!(!report.has_baseline);
+    assert!(report.central.is_empty());
+
+    record_skill_manifest(&store, "s1", &central);
+    fs::write(central.join("SKILL.md"), "# pdf, edited by hand\n").unwrap();
+
+    let copy = dir.path().join("tool/pdf");
+    write_skill(&copy);
+    fs::write(copy.join("scripts/run.sh"), "echo changed\n").unwrap();
+    let linked = dir.path().join("other/pdf");
+    fs::create_dir_all(&linked).unwrap();
+    for (tool, path, mode) in [
+        ("copy_tool", &copy, "copy"),
+        ("link_tool", &linked, "symlink"),
+    ] {
+        store
+            .upsert_skill_target(&SkillTargetRecord {
+                id: tool.to_string(),
+                skill_id: "s1".to_string(),
+                tool: tool.to_string(),
+                target_path: path.to_string_lossy().to_string(),
+                mode: mode.to_string(),
+                status: "ok".to_string(),
+                last_error: None,
+                synced_at: Some(1),
+                content_hash: None,
+            })
+            .unwrap();
+    }
+
+    let project = dir.path().join("project");
+    let project_copy = project.join(".claude/skills/pdf");
+    fs::create_dir_all(project_copy.parent().unwrap()).unwrap();
+    write_skill(&project_copy);
+    fs::write(project_copy.join("SKILL.md"), "# pdf, edited by hand\n").unwrap();
+    store
+        .upsert_project_target(&ProjectTargetRecord {
+            id: "p1".to_string(),
+            skill_id: "s1".to_string(),
+            project_path: project.to_string_lossy().to_string(),
+            tool: "claude_code".to_string(),
+            target_path: project_copy.to_string_lossy().to_string(),
+            mode: "copy".to_string(),
+            content_hash: None,
+            synced_at: 1,
+        })
+        .unwrap();
+
+    let report = detect_skill_drift(&store, "s1").unwrap();
+    assert!(report.has_baseline);
+    assert_eq!(
+        report.central,
+        vec![drift("SKILL.md", DriftChange::Modified)]
+    );
+    assert_eq!(report.targets.len(), 1);
+    assert_eq!(report.targets[0].tool, "copy_tool");
+    assert_eq!(report.targets[0].project_path, None);
+    assert_eq!(
+        report.targets[0].files,
+        vec![
+            drift("SKILL.md", DriftChange::Modified),
+            drift("scripts/run.sh", DriftChange::Modified),
+        ]
+    );
+    assert!(detect_skill_drift(&store, "missing").is_err());
+}
+
+#[test]
+fn files_with_cross_refs_are_not_reported_in_targets() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = make_store(dir.path());
+    let central = dir.path().join("central/pdf");
+    write_skill(&central);
+    fs::write(central.join("SKILL.md"), "See {{skill:docx}}.\n").unwrap();
+    add_skill(&store, &central);
+
+    let copy = dir.path().join("tool/pdf");
+    write_skill(&copy);
+    fs::write(copy.join("SKILL.md"), "See /home/me/.claude/skills/docx.\n").unwrap();
+    store
+        .upsert_skill_target(&SkillTargetRecord {
+            id: "t1".to_string(),
+            skill_id: "s1".to_string(),
+            tool: "copy_tool".to_string(),
+            target_path: copy.to_string_lossy().to_string(),
+            mode: "copy".to_string(),
+            status: "ok".to_string(),
+            last_error: None,
+            synced_at: Some(1),
+            content_hash: None,
+        })
+        .unwrap();
+
+    assert!(detect_skill_drift(&store, "s1").unwrap().targets.is_empty());
+}
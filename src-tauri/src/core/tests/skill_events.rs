@@ -0,0 +1,53 @@
+use std::sync::{Arc, Mutex};
+
+use super::*;
+
+#[test]
+fn events_reach_the_sink_with_typed_payloads() {
+    let seen: Arc<Mutex<Vec<(String, serde_json::Value)>>> = Arc::default();
+    let captured = seen.clone();
+    set_sink(Some(Box::new(move |event, payload| {
+        captured.lock().unwrap().push((event.to_string(), payload));
+    })));
+
+    skill_installed("events-test", "pdf");
+    let target = SkillTargetRecord {
+        id: "t1".to_string(),
+        skill_id: "events-test".to_string(),
+        tool: "cursor".to_string(),
+        target_path: "/tmp/cursor/pdf".to_string(),
+        mode: "copy".to_string(),
+        status: "ok".to_string(),
+        last_error: None,
+        synced_at: Some(1),
+        content_hash: None,
+    };
+    skill_synced(&target);
+    target_broken(&target, "missing", Some("target not found"));
+    remote_sync_finished("events-host", &["pdf".to_string()], None);
+    set_sink(None);
+    skill_deleted("events-test", "pdf");
+
+    // Other tests may emit while the sink is attached.
+    let seen: Vec<(String, serde_json::Value)> = seen
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(_, p)| p["skill_id"] == "events-test" || p["host_id"] == "events-host")
+        .cloned()
+        .collect();
+    let names: Vec<&str> = seen.iter().map(|(e, _)| e.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            EVENT_SKILL_INSTALLED,
+            EVENT_SKILL_SYNCED,
+            EVENT_TARGET_BROKEN,
+            EVENT_REMOTE_SYNC_FINISHED,
+        ]
+    );
+    assert_eq!(seen[0].1["name"], "pdf");
+    assert_eq!(seen[1].1["mode"], "copy");
+    assert_eq!(seen[2].1["state"], "missing");
+    assert_eq!(seen[3].1["synced"], serde_json::json!(["pdf"]));
+}
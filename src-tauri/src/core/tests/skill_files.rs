@@ -0,0 +1,55 @@
+use super::*;
+
+#[test]
+fn lists_files_in_path_order_without_ignored_entries() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("SKILL.md"), "---\nname: x\n---\n").unwrap();
+    std::fs::create_dir_all(dir.path().join("scripts")).unwrap();
+    std::fs::write(dir.path().join("scripts/run.sh"), "echo hi").unwrap();
+    std::fs::create_dir_all(dir.path().join(".git")).unwrap();
+    std::fs::write(dir.path().join(".git/HEAD"), "ref").unwrap();
+
+    let entries = list_dir(dir.path()).unwrap();
+    let paths: Vec<&str> = entries.iter().map(|e| e.path.as_str()).collect();
+    assert_eq!(paths, vec!["SKILL.md", "scripts", "scripts/run.sh"]);
+    assert!(entries[1].is_dir);
+    assert_eq!(entries[2].size, 7);
+}
+
+#[test]
+fn reads_text_and_flags_binary_and_large_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let text = dir.path().join("notes.md");
+    std::fs::write(&text, "héllo").unwrap();
+    let preview = read_preview(&text, "notes.md").unwrap();
+    assert_eq!(preview.content.as_deref(), Some("héllo"));
+    assert!(!preview.binary && !preview.truncated);
+
+    let image = dir.path().join("logo.png");
+    std::fs::write(&image, [0x89, b'P', b'N', b'G', 0, 0, 1]).unwrap();
+    let preview = read_preview(&image, "logo.png").unwrap();
+    assert!(preview.binary);
+    assert_eq!(preview.content, None);
+    assert_eq!(preview.size, 7);
+
+    // A multi-byte character cut by the cap is dropped, not binary.
+    let mut big = "a".repeat(MAX_PREVIEW_BYTES as usize - 1);
+    big.push('é');
+    let large = dir.path().join("large.md");
+    std::fs::write(&large, &big).unwrap();
+    let preview = read_preview(&large, "large.md").unwrap();
+    assert!(preview.truncated && !preview.binary);
+    assert_eq!(
+        preview.content.unwrap().len(),
+        MAX_PREVIEW_BYTES as usize - 1
+    );
+}
+
+#[test]
+fn rejects_paths_outside_the_skill() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("SKILL.md"), "x").unwrap();
+    assert!(resolve_skill_file(dir.path(), "../secret").is_err());
+    assert!(resolve_skill_file(dir.path(), "/etc/passwd").is_err());
+    assert!(resolve_skill_file(dir.path(), "SKILL.md").is_ok());
+}
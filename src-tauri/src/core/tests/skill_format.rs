@@ -0,0 +1,58 @@
+use super::*;
+
+fn skill_dir(content: &str) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("SKILL.md"), content).unwrap();
+    std::fs::write(dir.path().join("helper.py"), "print(1)").unwrap();
+    dir
+}
+
+const SKILL: &str =
+    "---\nname: pdf\ndescription: Work with \"PDF\" files\nlicense: MIT\n---\n\n# PDF\n\nSteps.\n";
+
+#[test]
+fn converts_to_cursor_rule() {
+    let dir = skill_dir(SKILL);
+    convert_skill_dir(SkillFormat::CursorRule, dir.path()).unwrap();
+    assert!(!dir.path().join("SKILL.md").exists());
+    assert!(dir.path().join("helper.py").exists());
+    let rule = std::fs::read_to_string(dir.path().join("RULE.md")).unwrap();
+    assert_eq!(
+        rule,
+        "---\ndescription: \"Work with \\\"PDF\\\" files\"\nalwaysApply: false\n---\n# PDF\n\nSteps.\n"
+    );
+}
+
+#[test]
+fn converts_to_codex_prompt_and_leaves_skills_alone() {
+    let dir = skill_dir("# No frontmatter\n");
+    convert_skill_dir(SkillFormat::CodexPrompt, dir.path()).unwrap();
+    let prompt = std::fs::read_to_string(dir.path().join("PROMPT.md")).unwrap();
+    assert_eq!(prompt, "---\ndescription: \"\"\n---\n# No frontmatter\n");
+
+    let dir = skill_dir(SKILL);
+    convert_skill_dir(SkillFormat::Skill, dir.path()).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("SKILL.md")).unwrap(),
+        SKILL
+    );
+
+    let empty = tempfile::tempdir().unwrap();
+    assert!(convert_skill_dir(SkillFormat::CursorRule, empty.path()).is_err());
+}
+
+#[test]
+fn reads_block_scalar_descriptions() {
+    let dir = skill_dir("---\nname: pdf\ndescription: >\n  Work with PDF\n  files\n---\n# PDF\n");
+    convert_skill_dir(SkillFormat::CodexPrompt, dir.path()).unwrap();
+    let prompt = std::fs::read_to_string(dir.path().join("PROMPT.md")).unwrap();
+    assert_eq!(
+        prompt,
+        "---\ndescription: \"Work with PDF files\"\n---\n# PDF\n"
+    );
+
+    let dir = skill_dir("---\nname: pdf\ndescription: |\n  Line one\n  Line two\n---\n# PDF\n");
+    convert_skill_dir(SkillFormat::CursorRule, dir.path()).unwrap();
+    let rule = std::fs::read_to_string(dir.path().join("RULE.md")).unwrap();
+    assert!(rule.starts_with("---\ndescription: \"Line one\\nLine two\"\n"));
+}
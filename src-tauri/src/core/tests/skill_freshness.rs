@@ -0,0 +1,95 @@
+use crate::core::skill_store::{SkillRecord, SkillStore};
+
+use super::*;
+
+fn skill(id: &str, source_type: &str, source_ref: Option<&str>) -> SkillRecord {
+    SkillRecord {
+        id: id.to_string(),
+        name: id.to_string(),
+        source_type: source_type.to_string(),
+        source_ref: source_ref.map(str::to_string),
+        source_revision: None,
+        central_path: format!("/tmp/central/{}", id),
+        content_hash: None,
+        created_at: 1,
+        updated_at: 1,
+        last_sync_at: None,
+        last_seen_at: 1,
+        status: "ok".to_string(),
+        group_name: None,
+    }
+}
+
+#[test]
+fn parses_github_times_and_grades_idle_time() {
+    assert_eq!(parse_github_time("1970-01-01T00:00:00Z"), Some(0));
+    assert_eq!(
+        parse_github_time("2024-03-01T12:30:15Z"),
+        Some(1_709_296_215_000)
+    );
+    assert_eq!(parse_github_time("2024-03-01 12:30:15"), None);
+    assert_eq!(parse_github_time("2024-13-01T00:00:00Z"), None);
+
+    let now = 1_000 * DAY_MS;
+    assert_eq!(freshness_level(None, now), FreshnessLevel::Unknown);
+    assert_eq!(
+        freshness_level(Some(now - 10 * DAY_MS), now),
+        FreshnessLevel::Active
+    );
+    assert_eq!(
+        freshness_level(Some(now - STALE_AFTER_DAYS * DAY_MS), now),
+        FreshnessLevel::Stale
+    );
+    assert_eq!(
+        freshness_level(Some(now - ABANDONED_AFTER_DAYS * DAY_MS), now),
+        FreshnessLevel::Abandoned
+    );
+}
+
+#[test]
+fn refresh_records_last_commit_per_skill_subpath() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    store
+        .upsert_skill(&skill(
+            "pdf",
+            "git",
+            Some("https://github.com/acme/skills/tree/main/skills/pdf"),
+        ))
+        .unwrap();
+    store
+        .upsert_skill(&skill(
+            "mirror",
+            "git",
+            Some("https://git.example.com/x.git"),
+        ))
+        .unwrap();
+    store.upsert_skill(&skill("local", "local", None)).unwrap();
+
+    let mut server = mockito::Server::new();
+    let mock = server
+        .mock("GET", "/repos/acme/skills/commits")
+        .match_query(mockito::Matcher::AllOf(vec![
+            mockito::Matcher::UrlEncoded("per_page".into(), "1".into()),
+            mockito::Matcher::UrlEncoded("sha".into(), "main".into()),
+            mockito::Matcher::UrlEncoded("path".into(), "skills/pdf".into()),
+        ]))
+        .with_body(r#"[{"commit": {"committer": {"date": "2024-03-01T12:30:15Z"}}}]"#)
+        .expect(1)
+        .create();
+
+    let results = refresh_inner(&store, &server.url(), false).unwrap();
+    assert_eq!(results.len(), 2);
+    let pdf = results.iter().find(|f| f.skill_id == "pdf").unwrap();
+    assert_eq!(pdf.last_commit_at, Some(1_709_296_215_000));
+    assert!(pdf.error.is_none());
+    let mirror = results.iter().find(|f| f.skill_id == "mirror").unwrap();
+    assert_eq!(mirror.level, FreshnessLevel::Unknown);
+    assert!(mirror.error.as_deref().unwrap().contains("GitHub"));
+
+    // Checked within the last day: no second request.
+    refresh_inner(&store, &server.url(), false).unwrap();
+    mock.assert();
+    assert_eq!(list_skill_freshness(&store).unwrap(), results);
+}
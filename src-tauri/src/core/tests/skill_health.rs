@@ -0,0 +1,104 @@
+use std::fs;
+use std::path::Path;
+
+use crate::core::content_hash::hash_dir;
+use crate::core::skill_drift::record_skill_manifest;
+use crate::core::skill_store::{SkillRecord, SkillStore, SkillTargetRecord};
+
+use super::{skills_health, TargetHealth};
+
+fn add_target(store: &SkillStore, tool: &str, path: &Path, mode: &str, hash: Option<String>) {
+    store
+        .upsert_skill_target(&SkillTargetRecord {
+            id: tool.to_string(),
+            skill_id: "s1".to_string(),
+            tool: tool.to_string(),
+            target_path: path.to_string_lossy().to_string(),
+            mode: mode.to_string(),
+            status: "ok".to_string(),
+            last_error: None,
+            synced_at: Some(1),
+            content_hash: hash,
+        })
+        .unwrap();
+}
+
+#[test]
+fn aggregates_targets_source_drift_and_size() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    let central = dir.path().join("central/pdf");
+    fs::create_dir_all(&central).unwrap();
+    fs::write(central.join("SKILL.md"), "# pdf\n").unwrap();
+    let source = dir.path().join("source/pdf");
+    store
+        .upsert_skill(&SkillRecord {
+            id: "s1".to_string(),
+            name: "pdf".to_string(),
+            source_type: "local".to_string(),
+            source_ref: Some(source.to_string_lossy().to_string()),
+            source_revision: None,
+            central_path: central.to_string_lossy().to_string(),
+            content_hash: None,
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: "ok".to_string(),
+            group_name: None,
+        })
+        .unwrap();
+    record_skill_manifest(&store, "s1", &central);
+    let synced_hash = hash_dir(&central).ok();
+
+    let fresh = dir.path().join("fresh/pdf");
+    fs::create_dir_all(&fresh).unwrap();
+    fs::write(fresh.join("SKILL.md"), "# pdf\n").unwrap();
+    add_target(&store, "fresh_tool", &fresh, "copy", synced_hash.clone());
+    add_target(
+        &store,
+        "gone_tool",
+        &dir.path().join("gone/pdf"),
+        "copy",
+        None,
+    );
+    add_target(
+        &store,
+        "remote:h1:claude_code",
+        &dir.path().join("r"),
+        "copy",
+        None,
+    );
+
+    fs::write(central.join("SKILL.md"), "# pdf, edited\n").unwrap();
+
+    let report = skills_health(&store, true).unwrap();
+    assert_eq!(report.len(), 1);
+    let health = &report[0];
+    let states: Vec<(&str, TargetHealth)> = health
+        .targets
+        .iter()
+        .map(|t| (t.tool.as_str(), t.state))
+        .collect();
+    assert!(states.contains(&("fresh_tool", TargetHealth::Stale)));
+    assert!(states.contains(&("gone_tool", TargetHealth::Broken)));
+    assert!(states.contains(&("remote:h1:claude_code", TargetHealth::Skipped)));
+    assert_eq!(health.has_update, None);
+    assert_eq!(health.source_reachable, Some(false));
+    assert!(health.source_error.is_some());
+    assert!(health.central_drifted);
+    assert_eq!(health.drifted_targets, vec!["fresh_tool".to_string()]);
+    assert_eq!(health.bytes, 14);
+    assert_eq!(health.files, 1);
+
+    fs::create_dir_all(&source).unwrap();
+    assert_eq!(
+        skills_health(&store, true).unwrap()[0].source_reachable,
+        Some(true)
+    );
+    assert_eq!(
+        skills_health(&store, false).unwrap()[0].source_reachable,
+        None
+    );
+}
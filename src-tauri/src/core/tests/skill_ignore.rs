@@ -0,0 +1,40 @@
+use std::fs;
+
+use super::*;
+
+#[test]
+fn builtins_and_patterns() {
+    let ignore = SkillIgnore::parse("# junk\n*.log\nbuild/\n/drafts\ndocs/**/*.tmp\n!keep.log\n\n");
+    let excluded = |p: &str, dir: bool| ignore.is_excluded(Path::new(p), dir);
+
+    assert!(excluded(".git", true));
+    assert!(excluded("sub/node_modules", true));
+    assert!(excluded(".DS_Store", false));
+    assert!(excluded("a.log", false));
+    assert!(excluded("deep/b.log", false));
+    assert!(!excluded("keep.log", false));
+    assert!(excluded("x/build", true));
+    assert!(!excluded("build", false));
+    assert!(excluded("drafts", true));
+    assert!(!excluded("sub/drafts", true));
+    assert!(excluded("docs/a.tmp", false));
+    assert!(excluded("docs/x/y/a.tmp", false));
+    assert!(!excluded("other/a.tmp", false));
+    assert!(!excluded("SKILL.md", false));
+    assert!(!SkillIgnore::default().is_excluded(Path::new(".git"), true));
+}
+
+#[test]
+fn excludes_any_only_counts_skillignore_rules() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir_all(dir.path().join("node_modules")).unwrap();
+    fs::write(dir.path().join("SKILL.md"), "x").unwrap();
+    assert!(!SkillIgnore::load(dir.path()).excludes_any(dir.path()));
+
+    fs::write(dir.path().join(SKILLIGNORE_FILE), "*.log\n").unwrap();
+    let ignore = SkillIgnore::load(dir.path());
+    assert!(ignore.has_rules());
+    assert!(!ignore.excludes_any(dir.path()));
+    fs::write(dir.path().join("run.log"), "x").unwrap();
+    assert!(ignore.excludes_any(dir.path()));
+}
@@ -0,0 +1,57 @@
+use crate::core::errors::{find_coded, ErrorCode};
+use crate::core::skill_authoring::write_skill_content;
+use crate::core::skill_store::{SkillRecord, SkillStore};
+
+use super::{ensure_unlocked, is_skill_locked, set_skill_locked};
+
+fn record(skill_dir: &std::path::Path) -> SkillRecord {
+    SkillRecord {
+        id: "s1".to_string(),
+        name: "demo".to_string(),
+        source_type: "local".to_string(),
+        source_ref: None,
+        source_revision: None,
+        central_path: skill_dir.to_string_lossy().to_string(),
+        content_hash: None,
+        created_at: 1,
+        updated_at: 1,
+        last_sync_at: None,
+        last_seen_at: 1,
+        status: "ok".to_string(),
+        group_name: None,
+    }
+}
+
+#[test]
+fn locked_skills_refuse_edits_until_unlocked() {
+    let central = tempfile::tempdir().unwrap();
+    let skill_dir = central.path().join("demo");
+    std::fs::create_dir_all(&skill_dir).unwrap();
+    std::fs::write(skill_dir.join("SKILL.md"), "v1").unwrap();
+
+    let db = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(db.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    let skill = record(&skill_dir);
+    store.upsert_skill(&skill).unwrap();
+
+    assert!(!is_skill_locked(&store, "s1"));
+    assert!(set_skill_locked(&store, "s1", true).unwrap());
+    assert!(is_skill_locked(&store, "s1"));
+
+    let err = ensure_unlocked(&store, &skill).unwrap_err();
+    let coded = find_coded(&err).unwrap();
+    assert_eq!(coded.code, ErrorCode::SkillLocked);
+    assert_eq!(coded.payload.as_ref().unwrap()["skill_id"], "s1");
+
+    let err = write_skill_content(&store, "s1", "SKILL.md", "v2").unwrap_err();
+    assert_eq!(find_coded(&err).unwrap().code, ErrorCode::SkillLocked);
+    assert_eq!(
+        std::fs::read_to_string(skill_dir.join("SKILL.md")).unwrap(),
+        "v1"
+    );
+
+    assert!(!set_skill_locked(&store, "s1", false).unwrap());
+    ensure_unlocked(&store, &skill).unwrap();
+    assert!(set_skill_locked(&store, "missing", true).is_err());
+}
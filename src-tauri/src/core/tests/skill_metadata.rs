@@ -0,0 +1,37 @@
+use super::*;
+
+#[test]
+fn parses_name_description_version_and_tags() {
+    let meta = parse_metadata(
+        "---\nname: pdf\ndescription: \"Fill PDF forms\"\nversion: 1.2.0\ntags: [docs, \"forms\"]\n---\nbody\n",
+    )
+    .unwrap();
+    assert_eq!(
+        meta,
+        SkillMetadata {
+            name: Some("pdf".to_string()),
+            description: Some("Fill PDF forms".to_string()),
+            version: Some("1.2.0".to_string()),
+            tags: vec!["docs".to_string(), "forms".to_string()],
+            dependencies: Vec::new(),
+        }
+    );
+}
+
+#[test]
+fn reads_nested_version_and_list_tags() {
+    let meta = parse_metadata(
+        "---\nname: pdf\nmetadata:\n  author: me\n  version: \"2.0\"\ntags:\n  - a\n  - b\n---\n",
+    )
+    .unwrap();
+    assert_eq!(meta.version.as_deref(), Some("2.0"));
+    assert_eq!(meta.tags, vec!["a", "b"]);
+    assert_eq!(meta.description, None);
+
+    assert!(parse_metadata("# no frontmatter").is_none());
+    assert_eq!(parse_list("a, b"), vec!["a", "b"]);
+
+    // Stored before `dependencies` existed.
+    let old: SkillMetadata = serde_json::from_str(r#"{"name":"pdf","tags":[]}"#).unwrap();
+    assert!(old.dependencies.is_empty());
+}
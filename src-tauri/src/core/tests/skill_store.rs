@@ -90,6 +90,66 @@ fn skills_upsert_list_get_delete() {
     assert!(store.get_skill_by_id("a").unwrap().is_none());
 }
 
+#[test]
+fn skill_metadata_survives_upserts() {
+    let (_dir, store) = make_store();
+    let a = make_skill("a", "A", "/central/a", 10);
+    store.upsert_skill(&a).unwrap();
+    assert_eq!(store.get_skill_metadata_json("a").unwrap(), None);
+
+    store
+        .set_skill_metadata_json("a", Some(r#"{"tags":[]}"#))
+        .unwrap();
+    store.upsert_skill(&a).unwrap();
+    assert_eq!(
+        store.get_skill_metadata_json("a").unwrap().as_deref(),
+        Some(r#"{"tags":[]}"#)
+    );
+
+    store.set_skill_metadata_json("a", None).unwrap();
+    assert_eq!(store.get_skill_metadata_json("a").unwrap(), None);
+}
+
+#[test]
+fn skill_dependency_edges_follow_deletes() {
+    let (_dir, store) = make_store();
+    store
+        .upsert_skill(&make_skill("a", "A", "/central/a", 10))
+        .unwrap();
+    store
+        .upsert_skill(&make_skill("b", "B", "/central/b", 10))
+        .unwrap();
+    store
+        .set_skill_dependencies(
+            "a",
+            &[
+                ("clawhub://b".to_string(), Some("b".to_string())),
+                ("clawhub://c".to_string(), None),
+            ],
+        )
+        .unwrap();
+    assert_eq!(
+        store.list_skill_dependents("b").unwrap(),
+        vec![("a".to_string(), "clawhub://b".to_string())]
+    );
+    assert_eq!(
+        store.list_unresolved_dependencies().unwrap(),
+        vec![("a".to_string(), "clawhub://c".to_string())]
+    );
+
+    // Deleting the dependency keeps the edge, unresolved.
+    store.delete_skill("b").unwrap();
+    assert_eq!(
+        store.list_skill_dependencies("a").unwrap(),
+        vec![
+            ("clawhub://b".to_string(), None),
+            ("clawhub://c".to_string(), None)
+        ]
+    );
+    store.delete_skill("a").unwrap();
+    assert!(store.list_unresolved_dependencies().unwrap().is_empty());
+}
+
 #[test]
 fn skill_targets_upsert_unique_constraint_and_list_order() {
     let (_dir, store) = make_store();
@@ -105,6 +165,7 @@ fn skill_targets_upsert_unique_constraint_and_list_order() {
         status: "ok".to_string(),
         last_error: None,
         synced_at: None,
+        content_hash: None,
     };
     store.upsert_skill_target(&t1).unwrap();
     assert_eq!(
@@ -143,6 +204,7 @@ fn skill_targets_upsert_unique_constraint_and_list_order() {
         status: "ok".to_string(),
         last_error: None,
         synced_at: None,
+        content_hash: None,
     };
     store.upsert_skill_target(&t2).unwrap();
 
@@ -170,6 +232,7 @@ fn deleting_skill_cascades_targets() {
         status: "ok".to_string(),
         last_error: None,
         synced_at: None,
+        content_hash: None,
     };
     store.upsert_skill_target(&t).unwrap();
     assert_eq!(store.list_skill_targets("s1").unwrap().len(), 1);
@@ -249,6 +312,7 @@ fn delete_custom_target_cascades_skill_targets() {
         status: "ok".to_string(),
         last_error: None,
         synced_at: None,
+        content_hash: None,
     };
     store.upsert_skill_target(&target).unwrap();
     assert!(store
@@ -0,0 +1,90 @@
+use super::*;
+
+fn vars(name: &str, description: Option<&str>) -> TemplateVars {
+    TemplateVars::new(name, description, Some("Ada"))
+}
+
+#[test]
+fn builtin_templates_have_frontmatter_and_sections() {
+    let dir = tempfile::tempdir().unwrap();
+    let dest = dir.path().join("pdf-tools");
+    render_template(
+        dir.path(),
+        "basic",
+        &vars("pdf-tools", Some("Fill forms:\n  quickly")),
+        &dest,
+    )
+    .unwrap();
+    let text = std::fs::read_to_string(dest.join("SKILL.md")).unwrap();
+    assert!(text.starts_with("---\nname: pdf-tools\ndescription: \"Fill forms: quickly\"\n---\n"));
+    assert!(text.contains("# Pdf Tools"));
+    assert!(text.contains("## When to use"));
+    assert!(text.contains("## Instructions"));
+
+    let minimal = dir.path().join("demo");
+    render_template(dir.path(), "minimal", &vars("demo", None), &minimal).unwrap();
+    let text = std::fs::read_to_string(minimal.join("SKILL.md")).unwrap();
+    assert!(text.contains("description: Describe what demo does"));
+    assert!(!text.contains("## Examples"));
+
+    let missing = dir.path().join("nope");
+    assert!(render_template(dir.path(), "nope", &vars("demo", None), &missing).is_err());
+    assert!(!missing.exists());
+}
+
+#[test]
+fn user_templates_are_listed_and_rendered() {
+    let dir = tempfile::tempdir().unwrap();
+    let user_dir = dir.path().join("templates");
+    let team = user_dir.join("team");
+    std::fs::create_dir_all(team.join("scripts")).unwrap();
+    std::fs::write(
+        team.join("SKILL.md"),
+        "---\nname: {{name}}\ndescription: {{description}}\nauthor: {{author}}\n---\n\n# {{title}} by {{author}}\n",
+    )
+    .unwrap();
+    std::fs::write(team.join("scripts/run.sh"), "echo {{name}}\n").unwrap();
+    // Not a template: no SKILL.md.
+    std::fs::create_dir_all(user_dir.join("notes")).unwrap();
+    // Shadows the built-in.
+    std::fs::create_dir_all(user_dir.join("basic")).unwrap();
+    std::fs::write(
+        user_dir.join("basic/SKILL.md"),
+        "---\nname: {{name}}\n---\n",
+    )
+    .unwrap();
+
+    let ids: Vec<(String, bool)> = templates_in(&user_dir)
+        .into_iter()
+        .map(|t| (t.id, t.builtin))
+        .collect();
+    assert_eq!(
+        ids,
+        vec![
+            ("minimal".to_string(), true),
+            ("basic".to_string(), false),
+            ("team".to_string(), false),
+        ]
+    );
+
+    let dest = dir.path().join("central/my-skill");
+    render_template(
+        &user_dir,
+        "team",
+        &vars("my-skill", Some("Does: things")),
+        &dest,
+    )
+    .unwrap();
+    assert_eq!(
+        std::fs::read_to_string(dest.join("SKILL.md")).unwrap(),
+        "---\nname: my-skill\ndescription: \"Does: things\"\nauthor: Ada\n---\n\n# My Skill by Ada\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(dest.join("scripts/run.sh")).unwrap(),
+        "echo my-skill\n"
+    );
+
+    // Ids cannot reach outside the templates folder.
+    let escaped = dir.path().join("central/escaped");
+    assert!(render_template(&user_dir, "../templates/team", &vars("x", None), &escaped).is_err());
+}
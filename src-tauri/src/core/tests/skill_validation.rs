@@ -0,0 +1,77 @@
+use std::fs;
+
+use super::*;
+
+fn codes(validation: &SkillValidation) -> Vec<&str> {
+    validation.issues.iter().map(|i| i.code.as_str()).collect()
+}
+
+#[test]
+fn reads_frontmatter_fields_and_blocks() {
+    let text = "---\nname: demo\ndescription: >\n  Fill forms\n  quickly\ntags:\n- a\n- b\nnote: \"x: y\"\n---\nbody\n";
+    let fields = frontmatter_fields(text).unwrap();
+    let get = |key: &str| {
+        fields
+            .iter()
+            .find(|(_, k, _)| k == key)
+            .map(|(line, _, v)| (*line, v.as_str()))
+    };
+    assert_eq!(get("name"), Some((2, "demo")));
+    assert_eq!(get("description"), Some((3, "Fill forms quickly")));
+    assert_eq!(get("tags"), Some((6, "- a\n- b")));
+    assert_eq!(get("note"), Some((9, "x: y")));
+
+    assert!(frontmatter_fields("no frontmatter").is_none());
+    assert!(frontmatter_fields("---\nname: demo\n").is_none());
+}
+
+#[test]
+fn a_complete_skill_is_valid() {
+    let dir = tempfile::tempdir().unwrap();
+    let skill = dir.path().join("demo");
+    fs::create_dir_all(skill.join("refs")).unwrap();
+    fs::write(skill.join("refs/api.md"), "api").unwrap();
+    fs::write(
+        skill.join("SKILL.md"),
+        "---\nname: demo\ndescription: Does things\n---\n\nSee [the API](refs/api.md#auth) and [docs](https://example.com).\n",
+    )
+    .unwrap();
+
+    let validation = validate_skill_dir(&skill);
+    assert!(validation.valid);
+    assert!(validation.issues.is_empty(), "{:?}", validation.issues);
+}
+
+#[test]
+fn reports_errors_and_warnings() {
+    let dir = tempfile::tempdir().unwrap();
+    let skill = dir.path().join("demo");
+    fs::create_dir_all(&skill).unwrap();
+    fs::write(
+        skill.join("SKILL.md"),
+        "---\nname: Other Name\n---\n[a](missing.md) [b](/etc/hosts) [c](../x.md)\nRun /Users/me/bin/tool\n",
+    )
+    .unwrap();
+
+    let validation = validate_skill_dir(&skill);
+    assert!(!validation.valid);
+    assert_eq!(
+        codes(&validation),
+        vec![
+            "invalid_name",
+            "name_mismatch",
+            "missing_description",
+            "missing_reference",
+            "absolute_path",
+            "reference_outside_skill",
+            "absolute_path",
+        ]
+    );
+    let missing = &validation.issues[3];
+    assert_eq!(missing.severity, IssueSeverity::Warning);
+    assert_eq!(missing.line, Some(4));
+
+    fs::remove_file(skill.join("SKILL.md")).unwrap();
+    assert_eq!(codes(&validate_skill_dir(&skill)), vec!["missing_skill_md"]);
+    assert!(install_warnings(&skill).is_empty());
+}
@@ -0,0 +1,92 @@
+use std::fs;
+
+use super::*;
+use crate::core::skill_store::SkillTargetRecord;
+
+fn target(tool: &str, path: &Path, mode: &str, hash: &str) -> SkillTargetRecord {
+    SkillTargetRecord {
+        id: format!("t-{}", tool),
+        skill_id: "s1".to_string(),
+        tool: tool.to_string(),
+        target_path: path.to_string_lossy().to_string(),
+        mode: mode.to_string(),
+        status: "ok".to_string(),
+        last_error: None,
+        synced_at: None,
+        content_hash: Some(hash.to_string()),
+    }
+}
+
+#[test]
+fn edits_reach_copy_targets_only() {
+    let dir = tempfile::tempdir().unwrap();
+    let central = dir.path().join("central/demo");
+    fs::create_dir_all(&central).unwrap();
+    fs::write(central.join("SKILL.md"), "v1").unwrap();
+    let old_hash = hash_dir(&central).unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    let skill = SkillRecord {
+        id: "s1".to_string(),
+        name: "demo".to_string(),
+        source_type: "local".to_string(),
+        source_ref: None,
+        source_revision: None,
+        central_path: central.to_string_lossy().to_string(),
+        content_hash: Some(old_hash.clone()),
+        created_at: 1,
+        updated_at: 1,
+        last_sync_at: None,
+        last_seen_at: 1,
+        status: "ok".to_string(),
+        group_name: None,
+    };
+    store.upsert_skill(&skill).unwrap();
+    let copied = dir.path().join("tools/copied");
+    fs::create_dir_all(&copied).unwrap();
+    fs::write(copied.join("SKILL.md"), "v1").unwrap();
+    store
+        .upsert_skill_target(&target("tool_copy", &copied, "copy", &old_hash))
+        .unwrap();
+    let linked = dir.path().join("tools/linked");
+    store
+        .upsert_skill_target(&target("tool_link", &linked, "symlink", &old_hash))
+        .unwrap();
+
+    fs::write(central.join("SKILL.md"), "v2").unwrap();
+    let result = resync_copy_targets(&store, &skill).unwrap();
+    assert_eq!(result.tools, vec!["tool_copy".to_string()]);
+    assert!(result.failed.is_empty());
+    assert_eq!(fs::read_to_string(copied.join("SKILL.md")).unwrap(), "v2");
+    assert!(!linked.exists());
+
+    let new_hash = hash_dir(&central).unwrap();
+    let skill = store.get_skill_by_id("s1").unwrap().unwrap();
+    assert_eq!(skill.content_hash.as_deref(), Some(new_hash.as_str()));
+    let again = resync_copy_targets(&store, &skill).unwrap();
+    assert!(again.tools.is_empty());
+}
+
+#[test]
+fn debouncer_waits_for_quiet_and_respects_the_rate_limit() {
+    let settings = WatchSettings {
+        exclude_globs: Vec::new(),
+        debounce_ms: 500,
+        max_events_per_minute: 1,
+    };
+    let lookup = |_: &Path| Some(&settings);
+    let dir = PathBuf::from("/central/demo");
+    let start = Instant::now();
+    let at = |ms: u64| start + Duration::from_millis(ms);
+    let mut debouncer = Debouncer::default();
+
+    debouncer.touch(dir.clone(), at(0));
+    debouncer.touch(dir.clone(), at(300));
+    assert!(debouncer.due(at(600), lookup).is_empty());
+    assert_eq!(debouncer.due(at(800), lookup), vec![dir.clone()]);
+    assert!(debouncer.due(at(900), lookup).is_empty());
+
+    debouncer.touch(dir.clone(), at(1_000));
+    assert!(debouncer.due(at(2_000), lookup).is_empty());
+    assert_eq!(debouncer.due(at(61_000), lookup), vec![dir]);
+}
@@ -0,0 +1,204 @@
+use std::fs;
+use std::io::Write;
+
+use crate::core::app_paths::AppPaths;
+use crate::core::errors::{find_coded, ErrorCode};
+use crate::core::skill_store::{SkillRecord, SkillStore};
+
+use super::{
+    attach_signature, export_skillpkg, extract_skillpkg, install_skillpkg, read_manifest,
+    signing_payload, verify_signature, write_archive, MAX_FILE_BYTES, SKILLPKG_FORMAT_VERSION,
+};
+
+// Test vector from the minisign-verify crate (legacy, non-prehashed signature).
+const TEST_PUBLIC_KEY: &str = "RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3";
+const TEST_SIGNATURE: &str = "untrusted comment: signature from minisign secret key
+RWQf6LRCGA9i59SLOFxz6NxvASXDJeRtuZykwQepbDEGt87ig1BNpWaVWuNrm73YiIiJbq71Wi+dP9eKL8OC351vwIasSSbXxwA=
+trusted comment: timestamp:1555779966\tfile:test
+QtKMXWyYcwdpZAlPF7tE2ENJkRd1ujvKjlj1m9RtHTBnZPa5WKU5uWRs5GoP5M/VqE81QFuMKI5k/SfNQUaOAA==";
+
+fn make_skill(dir: &std::path::Path) -> SkillRecord {
+    let central = dir.join("central/demo");
+    fs::create_dir_all(central.join("refs")).unwrap();
+    fs::create_dir_all(central.join(".git")).unwrap();
+    fs::write(central.join("SKILL.md"), "# Demo").unwrap();
+    fs::write(central.join("refs/notes.md"), "notes").unwrap();
+    fs::write(central.join(".git/HEAD"), "ref").unwrap();
+
+    SkillRecord {
+        id: "s1".to_string(),
+        name: "demo".to_string(),
+        source_type: "git".to_string(),
+        source_ref: Some("https://github.com/o/r".to_string()),
+        source_revision: Some("abc123".to_string()),
+        central_path: central.to_string_lossy().to_string(),
+        content_hash: None,
+        created_at: 1,
+        updated_at: 1,
+        last_sync_at: None,
+        last_seen_at: 1,
+        status: "ok".to_string(),
+        group_name: None,
+    }
+}
+
+#[test]
+fn export_and_extract_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let skill = make_skill(dir.path());
+    let pkg = dir.path().join("out/demo.skillpkg");
+
+    let manifest = export_skillpkg(&skill, &pkg).unwrap();
+    assert_eq!(manifest.format_version, SKILLPKG_FORMAT_VERSION);
+    let paths: Vec<&str> = manifest.files.iter().map(|f| f.path.as_str()).collect();
+    assert_eq!(paths, vec!["SKILL.md", "refs/notes.md"]);
+    assert_eq!(manifest.source.source_revision.as_deref(), Some("abc123"));
+
+    let read_back = read_manifest(&pkg).unwrap();
+    assert_eq!(read_back.files, manifest.files);
+
+    let dest = dir.path().join("extract");
+    let (_, root, signature_status) = extract_skillpkg(&pkg, &dest, &[]).unwrap();
+    assert_eq!(root, dest.join("demo"));
+    assert_eq!(signature_status, "unsigned");
+    assert_eq!(
+        fs::read_to_string(root.join("refs/notes.md")).unwrap(),
+        "notes"
+    );
+    assert!(!root.join(".git").exists());
+}
+
+#[test]
+fn extract_rejects_tampered_and_unlisted_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let skill = make_skill(dir.path());
+    let pkg = dir.path().join("demo.skillpkg");
+    let manifest = export_skillpkg(&skill, &pkg).unwrap();
+
+    let tampered = dir.path().join("tampered.skillpkg");
+    write_archive(
+        &tampered,
+        &manifest,
+        &[
+            ("SKILL.md".to_string(), b"# Evil".to_vec()),
+            ("refs/notes.md".to_string(), b"notes".to_vec()),
+        ],
+    )
+    .unwrap();
+    let err = extract_skillpkg(&tampered, &dir.path().join("x"), &[]).unwrap_err();
+    assert!(err.to_string().contains("does not match"));
+
+    let extra = dir.path().join("extra.skillpkg");
+    fs::copy(&pkg, &extra).unwrap();
+    {
+        let file = fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&extra)
+            .unwrap();
+        let mut zip = zip::ZipWriter::new_append(file).unwrap();
+        zip.start_file("skill/hook.sh", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(b"rm -rf ~").unwrap();
+        zip.finish().unwrap();
+    }
+    let err = extract_skillpkg(&extra, &dir.path().join("y"), &[]).unwrap_err();
+    assert!(err.to_string().contains("unlisted"));
+}
+
+#[test]
+fn signature_status_and_payload() {
+    let dir = tempfile::tempdir().unwrap();
+    let skill = make_skill(dir.path());
+    let pkg = dir.path().join("demo.skillpkg");
+    let manifest = export_skillpkg(&skill, &pkg).unwrap();
+    assert_eq!(verify_signature(&manifest, &[]).unwrap(), "unsigned");
+
+    // The unsigned manifest.json in the archive is exactly the signing payload.
+    let mut archive = zip::ZipArchive::new(fs::File::open(&pkg).unwrap()).unwrap();
+    let mut raw = Vec::new();
+    std::io::Read::read_to_end(&mut archive.by_name("manifest.json").unwrap(), &mut raw).unwrap();
+    assert_eq!(raw, signing_payload(&manifest).unwrap());
+
+    let signed = attach_signature(&pkg, TEST_SIGNATURE).unwrap();
+    assert!(read_manifest(&pkg).unwrap().signature.is_some());
+    assert_eq!(verify_signature(&signed, &[]).unwrap(), "untrusted");
+    // Signed by the trusted key, but not over this manifest.
+    assert!(verify_signature(&signed, &[TEST_PUBLIC_KEY.to_string()]).is_err());
+    assert!(attach_signature(&pkg, "not a signature").is_err());
+}
+
+#[test]
+fn extract_refuses_sizes_past_the_caps() {
+    let dir = tempfile::tempdir().unwrap();
+    let skill = make_skill(dir.path());
+    let pkg = dir.path().join("demo.skillpkg");
+    let manifest = export_skillpkg(&skill, &pkg).unwrap();
+    let contents = vec![
+        ("SKILL.md".to_string(), b"# Demo".to_vec()),
+        ("refs/notes.md".to_string(), b"notes".to_vec()),
+    ];
+
+    // A manifest claiming a huge file must not size any buffer.
+    let mut huge = manifest.clone();
+    huge.files[0].size = u64::MAX;
+    let huge_pkg = dir.path().join("huge.skillpkg");
+    write_archive(&huge_pkg, &huge, &contents).unwrap();
+    let err = extract_skillpkg(&huge_pkg, &dir.path().join("x"), &[]).unwrap_err();
+    assert!(err.to_string().contains("too large"));
+
+    let mut many = manifest.clone();
+    for file in &mut many.files {
+        file.size = MAX_FILE_BYTES;
+    }
+    many.files = many.files.iter().cycle().take(8).cloned().collect();
+    let many_pkg = dir.path().join("many.skillpkg");
+    write_archive(&many_pkg, &many, &contents).unwrap();
+    let err = extract_skillpkg(&many_pkg, &dir.path().join("y"), &[]).unwrap_err();
+    assert!(err.to_string().contains("too large"));
+
+    // An entry longer than its listed size stops one byte past it.
+    let mut short = manifest;
+    short.files[0].size -= 1;
+    let short_pkg = dir.path().join("short.skillpkg");
+    write_archive(&short_pkg, &short, &contents).unwrap();
+    let err = extract_skillpkg(&short_pkg, &dir.path().join("z"), &[]).unwrap_err();
+    assert!(err.to_string().contains("does not match"));
+}
+
+#[test]
+fn install_asks_before_unverified_packages() {
+    let dir = tempfile::tempdir().unwrap();
+    let skill = make_skill(dir.path());
+    let pkg = dir.path().join("demo.skillpkg");
+    export_skillpkg(&skill, &pkg).unwrap();
+
+    let paths = AppPaths {
+        data_dir: dir.path().join("data"),
+        cache_dir: dir.path().join("cache"),
+        config_dir: dir.path().join("config"),
+    };
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    store
+        .set_setting(
+            "central_repo_path",
+            dir.path().join("hub").to_string_lossy().as_ref(),
+        )
+        .unwrap();
+
+    let Err(err) = install_skillpkg(&paths, &store, &pkg, None, false) else {
+        panic!("unsigned package installed without confirm");
+    };
+    let coded = find_coded(&err).unwrap();
+    assert_eq!(coded.code, ErrorCode::UnverifiedPackage);
+    assert_eq!(
+        coded.payload.as_ref().unwrap()["signature_status"],
+        "unsigned"
+    );
+    assert!(store.list_skills().unwrap().is_empty());
+
+    let result = install_skillpkg(&paths, &store, &pkg, None, true).unwrap();
+    assert_eq!(result.signature_status.as_deref(), Some("unsigned"));
+    assert_eq!(result.name, "demo");
+}
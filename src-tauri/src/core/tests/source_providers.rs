@@ -0,0 +1,74 @@
+use crate::core::installer::parse_github_url;
+use crate::core::skill_store::SkillStore;
+
+use super::*;
+
+fn ghe(id: &str, web_url: &str) -> SourceProvider {
+    SourceProvider {
+        id: id.to_string(),
+        name: String::new(),
+        api_url: format!("{}/api/v3/", web_url),
+        web_url: format!("{}/", web_url),
+    }
+}
+
+#[test]
+fn matches_urls_on_the_provider_host_only() {
+    let corp = validate(ghe("corp", "https://ghe.corp.example")).unwrap();
+    assert_eq!(corp.web_url, "https://ghe.corp.example");
+    assert_eq!(corp.api_url, "https://ghe.corp.example/api/v3");
+    assert_eq!(corp.name, "ghe.corp.example");
+
+    let providers = vec![SourceProvider::github(), corp.clone()];
+    let (found, rest) =
+        match_provider(providers.clone(), "https://ghe.corp.example/team/skills").unwrap();
+    assert_eq!(found.id, "corp");
+    assert_eq!(rest, "team/skills");
+    let (found, _) = match_provider(providers.clone(), "https://github.com/o/r").unwrap();
+    assert_eq!(found.id, GITHUB_PROVIDER_ID);
+    assert!(match_provider(providers.clone(), "https://ghe.corp.example.evil/o/r").is_none());
+    assert!(match_provider(providers, "https://gitlab.com/o/r").is_none());
+    assert_eq!(
+        corp.clone_url("team", "skills"),
+        "https://ghe.corp.example/team/skills.git"
+    );
+}
+
+#[test]
+fn rejects_invalid_providers() {
+    assert!(validate(ghe("github", "https://ghe.corp.example")).is_err());
+    assert!(validate(ghe("bad id", "https://ghe.corp.example")).is_err());
+    assert!(validate(ghe("corp", "https://github.com")).is_err());
+    assert!(validate(ghe("corp", "ftp://ghe.corp.example")).is_err());
+    assert!(validate(ghe("corp", "not a url")).is_err());
+}
+
+#[test]
+fn saved_providers_are_used_for_installs() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+
+    save_source_provider(&store, ghe("acme", "https://git.acme.test")).unwrap();
+    let duplicate = save_source_provider(&store, ghe("acme2", "https://git.acme.test"));
+    assert!(duplicate.is_err());
+    let ids: Vec<String> = list_source_providers(&store)
+        .into_iter()
+        .map(|p| p.id)
+        .collect();
+    assert_eq!(ids, vec!["github", "acme"]);
+    assert_eq!(
+        resolve_provider(Some("acme")).unwrap().api_url,
+        "https://git.acme.test/api/v3"
+    );
+    assert_eq!(resolve_provider(None).unwrap().id, GITHUB_PROVIDER_ID);
+
+    let parsed = parse_github_url("https://git.acme.test/team/skills/tree/dev/pdf");
+    assert_eq!(parsed.clone_url, "https://git.acme.test/team/skills.git");
+    assert_eq!(parsed.branch.as_deref(), Some("dev"));
+    assert_eq!(parsed.subpath.as_deref(), Some("pdf"));
+
+    assert!(delete_source_provider(&store, "acme").unwrap());
+    assert!(!delete_source_provider(&store, "acme").unwrap());
+    assert!(resolve_provider(Some("acme")).is_err());
+}
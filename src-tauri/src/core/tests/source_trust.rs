@@ -0,0 +1,122 @@
+use crate::core::errors::{find_coded, ErrorCode};
+use crate::core::skill_store::SkillStore;
+
+use super::{
+    check_git_source, check_source, evaluate, get_source_trust_policy, git_source,
+    set_source_trust_policy, InstallSource, SourceTrust, SourceTrustPolicy,
+};
+
+fn git(host: &str, owner: &str) -> InstallSource {
+    InstallSource::Git {
+        host: host.to_string(),
+        owner: Some(owner.to_string()),
+    }
+}
+
+#[test]
+fn git_source_reads_host_and_owner_from_every_url_form() {
+    assert_eq!(
+        git_source("anthropics/skills"),
+        git("github.com", "anthropics")
+    );
+    assert_eq!(
+        git_source("https://github.com/Anthropics/skills/tree/main/skills/pdf"),
+        git("github.com", "anthropics")
+    );
+    assert_eq!(
+        git_source("git@gitlab.example.com:team/skills.git"),
+        git("gitlab.example.com", "team")
+    );
+    assert_eq!(
+        git_source("https://example.com/repo.git"),
+        InstallSource::Git {
+            host: "example.com".to_string(),
+            owner: None,
+        }
+    );
+}
+
+#[test]
+fn denylist_wins_and_allowlist_is_only_required_when_enforced() {
+    let mut policy = SourceTrustPolicy {
+        allowed_owners: vec!["anthropics".to_string()],
+        denied_owners: vec!["github.com/evil".to_string()],
+        allowed_publishers: vec!["alice".to_string()],
+        ..Default::default()
+    };
+    assert_eq!(
+        evaluate(&policy, &git("github.com", "evil")),
+        SourceTrust::Denied
+    );
+    assert_eq!(
+        evaluate(&policy, &git("gitlab.com", "evil")),
+        SourceTrust::Trusted
+    );
+    assert_eq!(
+        evaluate(&policy, &git("github.com", "other")),
+        SourceTrust::Trusted
+    );
+
+    policy.require_allowlisted = true;
+    assert_eq!(
+        evaluate(&policy, &git("github.com", "anthropics")),
+        SourceTrust::Trusted
+    );
+    assert_eq!(
+        evaluate(&policy, &git("github.com", "other")),
+        SourceTrust::Untrusted
+    );
+    assert_eq!(
+        evaluate(
+            &policy,
+            &InstallSource::Clawhub {
+                publisher: Some("Alice".to_string())
+            }
+        ),
+        SourceTrust::Trusted
+    );
+    assert_eq!(
+        evaluate(&policy, &InstallSource::Clawhub { publisher: None }),
+        SourceTrust::Untrusted
+    );
+}
+
+#[test]
+fn unlisted_sources_need_confirmation_and_denied_ones_are_refused() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    assert_eq!(
+        get_source_trust_policy(&store),
+        SourceTrustPolicy::default()
+    );
+    check_git_source(&store, "someone/skills", false).unwrap();
+
+    let saved = set_source_trust_policy(
+        &store,
+        SourceTrustPolicy {
+            require_allowlisted: true,
+            allowed_owners: vec![" Anthropics ".to_string(), "anthropics".to_string()],
+            denied_owners: vec!["evil/".to_string()],
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(saved.allowed_owners, vec!["anthropics".to_string()]);
+    assert_eq!(saved.denied_owners, vec!["evil".to_string()]);
+    assert_eq!(get_source_trust_policy(&store), saved);
+
+    check_git_source(&store, "anthropics/skills", false).unwrap();
+
+    let err = check_git_source(&store, "someone/skills", false).unwrap_err();
+    let coded = find_coded(&err).unwrap();
+    assert_eq!(coded.code, ErrorCode::UntrustedSource);
+    let payload = coded.payload.as_ref().unwrap();
+    assert_eq!(payload["kind"], "git");
+    assert_eq!(payload["owner"], "github.com/someone");
+    assert_eq!(payload["source"], "someone/skills");
+    check_git_source(&store, "someone/skills", true).unwrap();
+
+    let err = check_source(&store, &git("github.com", "evil"), "evil/skills", true).unwrap_err();
+    assert_eq!(find_coded(&err).unwrap().code, ErrorCode::SourceDenied);
+}
@@ -0,0 +1,102 @@
+use std::io::ErrorKind;
+use std::time::Duration;
+
+use anyhow::Context;
+
+use crate::core::skill_store::SkillStore;
+
+use super::{
+    backoff_delay, get_retry_policy, is_transient, retry_with, set_retry_policy, RetryPolicy,
+};
+
+fn io_err(kind: ErrorKind) -> anyhow::Error {
+    Err::<(), _>(std::io::Error::new(kind, "boom"))
+        .context("write remote file /x")
+        .unwrap_err()
+}
+
+#[test]
+fn classifies_transport_errors_as_transient() {
+    assert!(is_transient(&io_err(ErrorKind::ConnectionReset)));
+    assert!(is_transient(&io_err(ErrorKind::TimedOut)));
+    assert!(!is_transient(&io_err(ErrorKind::PermissionDenied)));
+    assert!(!is_transient(&anyhow::anyhow!(
+        "remote command 'x' exited with code 1"
+    )));
+
+    let ssh = ssh2::Error::new(ssh2::ErrorCode::Session(-43), "recv failed");
+    assert!(is_transient(&anyhow::Error::new(ssh)));
+    let auth = ssh2::Error::new(ssh2::ErrorCode::Session(-18), "auth failed");
+    assert!(!is_transient(&anyhow::Error::new(auth)));
+}
+
+#[test]
+fn retries_only_transient_failures_up_to_the_limit() {
+    let policy = RetryPolicy {
+        attempts: 2,
+        backoff_ms: 0,
+    };
+
+    let mut calls = Vec::new();
+    let out = retry_with(&policy, "op", |retry| {
+        calls.push(retry);
+        if retry < 2 {
+            Err(io_err(ErrorKind::ConnectionReset))
+        } else {
+            Ok("done")
+        }
+    })
+    .unwrap();
+    assert_eq!(out, "done");
+    assert_eq!(calls, vec![0, 1, 2]);
+
+    let mut calls = 0;
+    let err = retry_with(&policy, "op", |_| -> anyhow::Result<()> {
+        calls += 1;
+        Err(io_err(ErrorKind::BrokenPipe))
+    });
+    assert!(err.is_err());
+    assert_eq!(calls, 3);
+
+    let mut calls = 0;
+    let _ = retry_with(&policy, "op", |_| -> anyhow::Result<()> {
+        calls += 1;
+        Err(anyhow::anyhow!("permission denied"))
+    });
+    assert_eq!(calls, 1);
+}
+
+#[test]
+fn backoff_doubles_and_is_capped() {
+    let policy = RetryPolicy {
+        attempts: 5,
+        backoff_ms: 500,
+    };
+    assert_eq!(backoff_delay(&policy, 0), Duration::from_millis(500));
+    assert_eq!(backoff_delay(&policy, 2), Duration::from_millis(2000));
+    assert_eq!(backoff_delay(&policy, 20), Duration::from_millis(30_000));
+}
+
+#[test]
+fn policy_settings_round_trip_and_validate() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+
+    assert_eq!(get_retry_policy(&store), RetryPolicy::default());
+    let policy = RetryPolicy {
+        attempts: 0,
+        backoff_ms: 250,
+    };
+    set_retry_policy(&store, policy).unwrap();
+    assert_eq!(get_retry_policy(&store), policy);
+
+    assert!(set_retry_policy(
+        &store,
+        RetryPolicy {
+            attempts: 99,
+            backoff_ms: 0
+        }
+    )
+    .is_err());
+}
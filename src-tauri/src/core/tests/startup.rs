@@ -0,0 +1,26 @@
+use super::StartupState;
+
+#[test]
+fn ready_only_after_all_phases_complete() {
+    let state = StartupState::default();
+    assert!(!state.snapshot().ready);
+
+    state.mark_store_ready();
+    let status = state.set_installed_tools(vec!["cursor".to_string()]);
+    assert!(status.tools_detected);
+    assert!(!status.ready);
+
+    let status = state.set_cleanup_result(2, 1);
+    assert!(status.ready);
+    assert_eq!(status.installed_tools, vec!["cursor".to_string()]);
+    assert_eq!(status.removed_temp_dirs, 2);
+    assert_eq!(status.removed_cache_dirs, 1);
+}
+
+#[test]
+fn background_phases_do_not_report_ready_without_store() {
+    let state = StartupState::default();
+    state.set_installed_tools(Vec::new());
+    let status = state.set_cleanup_result(0, 0);
+    assert!(!status.ready);
+}
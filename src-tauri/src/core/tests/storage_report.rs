@@ -0,0 +1,63 @@
+use std::fs;
+
+use super::*;
+use crate::core::skill_store::SkillRecord;
+
+fn record(id: &str, name: &str, central_path: &Path) -> SkillRecord {
+    SkillRecord {
+        id: id.to_string(),
+        name: name.to_string(),
+        source_type: "local".to_string(),
+        source_ref: None,
+        source_revision: None,
+        central_path: central_path.to_string_lossy().to_string(),
+        content_hash: None,
+        created_at: 1,
+        updated_at: 1,
+        last_sync_at: None,
+        last_seen_at: 1,
+        status: "ok".to_string(),
+        group_name: None,
+    }
+}
+
+#[test]
+fn reports_skills_largest_first_and_each_area() {
+    let dir = tempfile::tempdir().unwrap();
+    let central = dir.path().join("central");
+    fs::create_dir_all(central.join("small")).unwrap();
+    fs::write(central.join("small/SKILL.md"), "abc").unwrap();
+    fs::create_dir_all(central.join("big/docs")).unwrap();
+    fs::write(central.join("big/SKILL.md"), "0123456789").unwrap();
+    fs::write(central.join("big/docs/a.md"), "0123456789").unwrap();
+    fs::create_dir_all(central.join(".git")).unwrap();
+    fs::write(central.join(".git/HEAD"), "ref").unwrap();
+    let cache = dir.path().join("cache");
+    fs::create_dir_all(&cache).unwrap();
+    fs::write(cache.join("pack"), "12345").unwrap();
+
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    store
+        .upsert_skill(&record("s1", "small", &central.join("small")))
+        .unwrap();
+    store
+        .upsert_skill(&record("s2", "big", &central.join("big")))
+        .unwrap();
+    store
+        .upsert_skill(&record("s3", "gone", &central.join("gone")))
+        .unwrap();
+
+    let report =
+        build_storage_report(&store, &central, &cache, &dir.path().join("no-trash")).unwrap();
+    let sizes: Vec<_> = report
+        .skills
+        .iter()
+        .map(|s| (s.name.as_str(), s.bytes, s.files))
+        .collect();
+    assert_eq!(sizes, vec![("big", 20, 2), ("small", 3, 1), ("gone", 0, 0)]);
+    assert_eq!(report.total_bytes, 26);
+    assert_eq!(report.history_bytes, 3);
+    assert_eq!(report.git_cache_bytes, 5);
+    assert_eq!(report.trash_bytes, 0);
+}
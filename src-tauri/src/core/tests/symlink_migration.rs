@@ -0,0 +1,83 @@
+use std::collections::HashSet;
+use std::fs;
+
+use crate::core::skill_store::SkillStore;
+
+use super::{detect_symlink_farms_in_home, register_linked_skill, FarmSkill, LINKED_SOURCE_TYPE};
+
+#[test]
+#[cfg(unix)]
+fn detects_links_into_a_shared_dotfiles_dir() {
+    use std::os::unix::fs::symlink;
+
+    let home = tempfile::tempdir().unwrap();
+    let dotfiles = home.path().join("dotfiles/skills");
+    fs::create_dir_all(dotfiles.join("alpha")).unwrap();
+    fs::create_dir_all(dotfiles.join("beta")).unwrap();
+    let central = home.path().join(".skillshub");
+    fs::create_dir_all(central.join("managed")).unwrap();
+
+    fs::create_dir_all(home.path().join(".cursor/skills")).unwrap();
+    fs::create_dir_all(home.path().join(".codex/skills")).unwrap();
+    symlink(
+        dotfiles.join("alpha"),
+        home.path().join(".cursor/skills/alpha"),
+    )
+    .unwrap();
+    symlink(
+        dotfiles.join("alpha"),
+        home.path().join(".codex/skills/alpha"),
+    )
+    .unwrap();
+    // Relative link, still resolved to the farm.
+    symlink(
+        "../../dotfiles/skills/beta",
+        home.path().join(".codex/skills/b"),
+    )
+    .unwrap();
+    // Links into the central repo and plain directories are not farms.
+    symlink(
+        central.join("managed"),
+        home.path().join(".cursor/skills/managed"),
+    )
+    .unwrap();
+    fs::create_dir_all(home.path().join(".cursor/skills/plain")).unwrap();
+
+    let farms = detect_symlink_farms_in_home(home.path(), &central, &HashSet::new()).unwrap();
+    assert_eq!(farms.len(), 1);
+    assert_eq!(farms[0].root, fs::canonicalize(&dotfiles).unwrap());
+
+    let names: Vec<&str> = farms[0].skills.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["alpha", "beta"]);
+    assert_eq!(farms[0].skills[0].links.len(), 2);
+
+    let managed: HashSet<_> = [fs::canonicalize(dotfiles.join("alpha")).unwrap()].into();
+    let farms = detect_symlink_farms_in_home(home.path(), &central, &managed).unwrap();
+    assert_eq!(farms[0].skills.len(), 1);
+    assert_eq!(farms[0].skills[0].name, "beta");
+}
+
+#[test]
+fn registers_linked_skill_in_place() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    let source = dir.path().join("dotfiles/alpha");
+    fs::create_dir_all(&source).unwrap();
+    fs::write(source.join("SKILL.md"), "# Alpha").unwrap();
+
+    let result = register_linked_skill(
+        &store,
+        &FarmSkill {
+            name: "alpha".to_string(),
+            source_dir: source.clone(),
+            links: Vec::new(),
+        },
+    )
+    .unwrap();
+
+    let record = store.get_skill_by_id(&result.skill_id).unwrap().unwrap();
+    assert_eq!(record.source_type, LINKED_SOURCE_TYPE);
+    assert_eq!(record.central_path, source.to_string_lossy());
+    assert!(record.content_hash.is_some());
+}
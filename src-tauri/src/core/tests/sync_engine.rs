@@ -1,7 +1,8 @@
 use std::fs;
 
+use crate::core::errors::{find_coded, ErrorCode};
 use crate::core::sync_engine::{
-    copy_dir_recursive, sync_dir_for_tool_with_overwrite, sync_dir_hybrid,
+    copy_dir_recursive, copy_with_overwrite, sync_dir_for_tool_with_overwrite, sync_dir_hybrid,
     sync_dir_hybrid_with_overwrite, SyncMode,
 };
 
@@ -37,6 +38,7 @@ fn hybrid_sync_creates_link_and_is_idempotent_when_same_link() {
 
     if let Ok(link) = fs::read_link(&target) {
         assert_eq!(link, src_dir.path());
+        assert!(out.mode_reason.is_none());
         let out2 = sync_dir_hybrid(src_dir.path(), &target).unwrap();
         assert!(matches!(out2.mode_used, SyncMode::Symlink));
     }
@@ -54,6 +56,7 @@ fn hybrid_sync_with_overwrite_replaces_existing() {
 
     let err = sync_dir_hybrid_with_overwrite(src_dir.path(), &target, false).unwrap_err();
     assert!(format!("{:#}", err).contains("target already exists"));
+    assert_eq!(find_coded(&err).unwrap().code, ErrorCode::TargetExists);
 
     let out = sync_dir_hybrid_with_overwrite(src_dir.path(), &target, true).unwrap();
     assert!(out.replaced);
@@ -70,6 +73,7 @@ fn cursor_sync_forces_copy() {
 
     let out = sync_dir_for_tool_with_overwrite("cursor", src_dir.path(), &target, false).unwrap();
     assert!(matches!(out.mode_used, SyncMode::Copy));
+    assert!(out.mode_reason.unwrap().contains("Cursor"));
     assert!(target.join("s/a.txt").exists());
     assert_eq!(fs::read(target.join("s/a.txt")).unwrap(), b"ok");
 }
@@ -89,10 +93,96 @@ fn copy_overwrite_replaces_broken_symlink_target() {
     // Create a broken symlink at the target path.
     symlink(dst_dir.path().join("missing"), &target).unwrap();
 
-    let out = crate::core::sync_engine::sync_dir_copy_with_overwrite(src_dir.path(), &target, true)
-        .unwrap();
+    let out = copy_with_overwrite(src_dir.path(), &target, true, |_| Ok(())).unwrap();
 
     assert!(matches!(out.mode_used, SyncMode::Copy));
     assert!(target.join("s/a.txt").exists());
     assert_eq!(fs::read(target.join("s/a.txt")).unwrap(), b"ok");
 }
+
+#[test]
+fn copies_honor_skillignore_and_builtin_excludes() {
+    let src_dir = tempfile::tempdir().unwrap();
+    fs::create_dir_all(src_dir.path().join("node_modules/dep")).unwrap();
+    fs::create_dir_all(src_dir.path().join("cache")).unwrap();
+    fs::write(src_dir.path().join("SKILL.md"), b"ok").unwrap();
+    fs::write(src_dir.path().join(".DS_Store"), b"no").unwrap();
+    fs::write(src_dir.path().join("cache/a"), b"no").unwrap();
+    fs::write(src_dir.path().join(".skillignore"), b"cache/\n").unwrap();
+
+    let dst_dir = tempfile::tempdir().unwrap();
+    let target = dst_dir.path().join("t");
+    let out = sync_dir_hybrid(src_dir.path(), &target).unwrap();
+    // A link would expose `cache/`, so the skill is copied.
+    assert!(matches!(out.mode_used, SyncMode::Copy));
+    assert!(fs::read_link(&target).is_err());
+    assert!(target.join("SKILL.md").exists());
+    assert!(target.join(".skillignore").exists());
+    assert!(!target.join("cache").exists());
+    assert!(!target.join("node_modules").exists());
+    assert!(!target.join(".DS_Store").exists());
+}
+
+#[test]
+fn copy_sync_swaps_in_complete_copies_and_keeps_target_on_failure() {
+    let src_dir = tempfile::tempdir().unwrap();
+    fs::write(src_dir.path().join("a.txt"), b"new").unwrap();
+
+    let dst_dir = tempfile::tempdir().unwrap();
+    let target = dst_dir.path().join("t");
+    fs::create_dir_all(&target).unwrap();
+    fs::write(target.join("old.txt"), b"old").unwrap();
+
+    let missing = src_dir.path().join("missing");
+    assert!(copy_with_overwrite(&missing, &target, true, |_| Ok(())).is_err());
+    assert_eq!(fs::read(target.join("old.txt")).unwrap(), b"old");
+
+    let out = copy_with_overwrite(src_dir.path(), &target, true, |_| Ok(())).unwrap();
+    assert!(out.replaced);
+    assert_eq!(fs::read(target.join("a.txt")).unwrap(), b"new");
+    assert!(!target.join("old.txt").exists());
+
+    // Nothing staged or moved aside is left next to the target.
+    let names: Vec<_> = fs::read_dir(dst_dir.path())
+        .unwrap()
+        .map(|e| e.unwrap().file_name())
+        .collect();
+    assert_eq!(names, vec![std::ffi::OsString::from("t")]);
+}
+
+#[cfg(unix)]
+#[test]
+fn copy_keeps_modes_and_internal_symlinks() {
+    use std::os::unix::fs::{symlink, PermissionsExt};
+
+    let src_dir = tempfile::tempdir().unwrap();
+    let src = src_dir.path().join("skill");
+    fs::create_dir_all(src.join("scripts")).unwrap();
+    fs::write(src.join("scripts/run.sh"), b"#!/bin/sh\n").unwrap();
+    fs::set_permissions(
+        src.join("scripts/run.sh"),
+        fs::Permissions::from_mode(0o755),
+    )
+    .unwrap();
+    symlink("scripts/run.sh", src.join("run")).unwrap();
+    symlink("../run", src.join("scripts/again")).unwrap();
+    symlink("../../outside", src.join("scripts/escape")).unwrap();
+    symlink("/etc/hosts", src.join("hosts")).unwrap();
+
+    let dst_dir = tempfile::tempdir().unwrap();
+    let dst = dst_dir.path().join("copy");
+    copy_dir_recursive(&src, &dst).unwrap();
+
+    let mode = fs::metadata(dst.join("scripts/run.sh"))
+        .unwrap()
+        .permissions()
+        .mode();
+    assert_eq!(mode & 0o777, 0o755);
+    assert_eq!(
+        fs::read_link(dst.join("run")).unwrap(),
+        std::path::Path::new("scripts/run.sh")
+    );
+    assert!(fs::read_link(dst.join("scripts/again")).is_ok());
+    assert!(fs::symlink_metadata(dst.join("scripts/escape")).is_err());
+    assert!(fs::symlink_metadata(dst.join("hosts")).is_err());
+}
@@ -0,0 +1,62 @@
+use std::fs;
+
+use super::*;
+use crate::core::skill_store::SkillRecord;
+use crate::core::sync_engine::{
+    sync_dir_for_tool_with_mode, sync_dir_for_tool_with_overwrite, SyncMode,
+};
+
+#[cfg(unix)]
+#[test]
+fn skill_preference_overrides_tool_preference() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    let source = dir.path().join("central/demo");
+    fs::create_dir_all(&source).unwrap();
+    fs::write(source.join("SKILL.md"), "demo").unwrap();
+    store
+        .upsert_skill(&SkillRecord {
+            id: "s1".to_string(),
+            name: "demo".to_string(),
+            source_type: "local".to_string(),
+            source_ref: None,
+            source_revision: None,
+            central_path: source.to_string_lossy().to_string(),
+            content_hash: None,
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: "ok".to_string(),
+            group_name: None,
+        })
+        .unwrap();
+    let tools = dir.path().join("tools");
+
+    assert!(set_tool_sync_mode(&store, "no_such_tool", Some(SyncModePreference::Copy)).is_err());
+    set_tool_sync_mode(&store, "pochi", Some(SyncModePreference::Copy)).unwrap();
+    let out = sync_dir_for_tool_with_overwrite("pochi", &source, &tools.join("a"), false).unwrap();
+    assert!(matches!(out.mode_used, SyncMode::Copy));
+
+    let prefs = set_skill_sync_mode(&store, "s1", Some(SyncModePreference::Link)).unwrap();
+    assert_eq!(prefs.skills.get("s1"), Some(&SyncModePreference::Link));
+    let out = sync_dir_for_tool_with_overwrite("pochi", &source, &tools.join("b"), false).unwrap();
+    assert!(matches!(out.mode_used, SyncMode::Symlink));
+
+    let out = sync_dir_for_tool_with_mode(
+        "pochi",
+        &source,
+        &tools.join("c"),
+        false,
+        Some(SyncModePreference::Copy),
+    )
+    .unwrap();
+    assert!(matches!(out.mode_used, SyncMode::Copy));
+
+    set_skill_sync_mode(&store, "s1", None).unwrap();
+    let prefs = set_tool_sync_mode(&store, "pochi", None).unwrap();
+    assert_eq!(prefs, SyncModePrefs::default());
+    assert_eq!(get_sync_mode_prefs(&store), SyncModePrefs::default());
+    assert_eq!(preferred_mode("pochi", &source), SyncModePreference::Auto);
+}
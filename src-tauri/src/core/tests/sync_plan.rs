@@ -0,0 +1,86 @@
+use std::fs;
+use std::path::PathBuf;
+
+use super::*;
+use crate::core::errors::{find_coded, ErrorCode};
+
+fn setup() -> (tempfile::TempDir, SkillStore, PathBuf) {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    let source = dir.path().join("central/demo");
+    fs::create_dir_all(source.join("docs")).unwrap();
+    fs::write(source.join("SKILL.md"), "new").unwrap();
+    fs::write(source.join("docs/a.md"), "a").unwrap();
+    (dir, store, source)
+}
+
+fn leftovers(parent: &Path) -> Vec<String> {
+    fs::read_dir(parent)
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|n| n.starts_with('.'))
+        .collect()
+}
+
+#[cfg(unix)]
+#[test]
+fn plans_and_applies_a_new_link() {
+    let (dir, store, source) = setup();
+    let target = dir.path().join("tool/demo");
+    let plan = plan_sync(&store, "other_tool", &source, &target).unwrap();
+    assert_eq!(plan.action, SyncAction::Create);
+    assert_eq!(plan.mode, "symlink");
+    assert_eq!(plan.added, vec!["SKILL.md", "docs/a.md"]);
+    assert!(plan.target_fingerprint.is_none());
+    assert!(!target.exists());
+
+    let outcome = execute_sync_plan(&store, &plan).unwrap();
+    assert!(!outcome.replaced);
+    assert_eq!(fs::read_link(&target).unwrap(), source);
+    let again = plan_sync(&store, "other_tool", &source, &target).unwrap();
+    assert_eq!(again.action, SyncAction::Unchanged);
+    assert_eq!(again.unchanged, 2);
+}
+
+#[cfg(unix)]
+#[test]
+fn replacing_a_folder_lists_overwritten_and_deleted_files() {
+    let (dir, store, source) = setup();
+    let target = dir.path().join("tool/demo");
+    fs::create_dir_all(&target).unwrap();
+    fs::write(target.join("SKILL.md"), "old").unwrap();
+    fs::write(target.join("stale.md"), "x").unwrap();
+
+    let plan = plan_sync(&store, "other_tool", &source, &target).unwrap();
+    assert_eq!(plan.action, SyncAction::Replace);
+    assert_eq!(plan.added, vec!["docs/a.md"]);
+    assert_eq!(plan.overwritten, vec!["SKILL.md"]);
+    assert_eq!(plan.deleted, vec!["stale.md"]);
+
+    let outcome = execute_sync_plan(&store, &plan).unwrap();
+    assert!(outcome.replaced);
+    assert_eq!(fs::read_link(&target).unwrap(), source);
+    assert!(leftovers(target.parent().unwrap()).is_empty());
+}
+
+#[test]
+fn a_target_changed_after_planning_is_left_alone() {
+    let (dir, store, source) = setup();
+    let target = dir.path().join("tool/demo");
+    fs::create_dir_all(&target).unwrap();
+    fs::write(target.join("SKILL.md"), "old").unwrap();
+    let plan = plan_sync(&store, "other_tool", &source, &target).unwrap();
+
+    fs::write(target.join("SKILL.md"), "edited meanwhile").unwrap();
+    let err = ensure_plan_current(&plan).unwrap_err();
+    assert_eq!(
+        find_coded(&err).map(|e| e.code),
+        Some(ErrorCode::SyncPlanStale)
+    );
+    assert_eq!(
+        fs::read_to_string(target.join("SKILL.md")).unwrap(),
+        "edited meanwhile"
+    );
+}
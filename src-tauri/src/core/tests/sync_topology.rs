@@ -0,0 +1,159 @@
+use std::fs;
+
+use crate::core::skill_store::{
+    CustomTargetRecord, RemoteHostRecord, SkillRecord, SkillStore, SkillTargetRecord,
+};
+
+use super::{build_sync_topology, local_target_health};
+
+fn make_store() -> (tempfile::TempDir, SkillStore) {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().expect("ensure_schema");
+    (dir, store)
+}
+
+fn make_skill(id: &str, central_path: &str) -> SkillRecord {
+    SkillRecord {
+        id: id.to_string(),
+        name: id.to_uppercase(),
+        source_type: "local".to_string(),
+        source_ref: None,
+        source_revision: None,
+        central_path: central_path.to_string(),
+        content_hash: None,
+        created_at: 1,
+        updated_at: 1,
+        last_sync_at: None,
+        last_seen_at: 1,
+        status: "ok".to_string(),
+        group_name: None,
+    }
+}
+
+fn make_target(skill_id: &str, tool: &str, target_path: &str) -> SkillTargetRecord {
+    SkillTargetRecord {
+        id: format!("{}-{}", skill_id, tool),
+        skill_id: skill_id.to_string(),
+        tool: tool.to_string(),
+        target_path: target_path.to_string(),
+        mode: "copy".to_string(),
+        status: "ok".to_string(),
+        last_error: None,
+        synced_at: Some(1),
+        content_hash: None,
+    }
+}
+
+#[test]
+fn builds_nodes_and_edges_for_tools_and_custom_targets() {
+    let (dir, store) = make_store();
+    let central = dir.path().join("central/a");
+    fs::create_dir_all(&central).unwrap();
+    let copy_target = dir.path().join("cursor/a");
+    fs::create_dir_all(&copy_target).unwrap();
+
+    store
+        .upsert_skill(&make_skill("a", central.to_string_lossy().as_ref()))
+        .unwrap();
+    store
+        .upsert_remote_host(&RemoteHostRecord {
+            id: "h1".to_string(),
+            label: "VM".to_string(),
+            host: "10.0.0.1".to_string(),
+            port: 22,
+            username: "me".to_string(),
+            auth_method: "key".to_string(),
+            key_path: None,
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            status: "idle".to_string(),
+            group_name: None,
+            health_json: None,
+        })
+        .unwrap();
+    store
+        .upsert_custom_target(&CustomTargetRecord {
+            id: "c1".to_string(),
+            label: "VM skills".to_string(),
+            path: "/srv/skills".to_string(),
+            remote_host_id: Some("h1".to_string()),
+            created_at: 1,
+        })
+        .unwrap();
+    store
+        .upsert_skill_target(&make_target(
+            "a",
+            "cursor",
+            copy_target.to_string_lossy().as_ref(),
+        ))
+        .unwrap();
+    store
+        .upsert_skill_target(&make_target("a", "custom:c1", "/srv/skills/a"))
+        .unwrap();
+
+    let topo = build_sync_topology(&store).unwrap();
+
+    let kinds: Vec<(&str, &str)> = topo
+        .nodes
+        .iter()
+        .map(|n| (n.id.as_str(), n.kind.as_str()))
+        .collect();
+    assert!(kinds.contains(&("skill:a", "skill")));
+    assert!(kinds.contains(&("tool:cursor", "tool")));
+    assert!(kinds.contains(&("custom:c1", "custom_target")));
+    assert!(kinds.contains(&("remote:h1", "remote_host")));
+
+    let custom = topo.nodes.iter().find(|n| n.id == "custom:c1").unwrap();
+    assert_eq!(custom.parent.as_deref(), Some("remote:h1"));
+
+    assert_eq!(topo.edges.len(), 2);
+    let cursor = topo.edges.iter().find(|e| e.to == "tool:cursor").unwrap();
+    assert_eq!(cursor.health, "ok");
+    let remote = topo.edges.iter().find(|e| e.to == "custom:c1").unwrap();
+    assert_eq!(remote.health, "unknown");
+}
+
+#[test]
+fn local_target_health_reports_missing() {
+    let dir = tempfile::tempdir().unwrap();
+    let missing = dir.path().join("nope");
+    assert_eq!(
+        local_target_health(dir.path(), missing.to_string_lossy().as_ref()),
+        "missing"
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn local_target_health_checks_symlink_target() {
+    use std::os::unix::fs::symlink;
+
+    let dir = tempfile::tempdir().unwrap();
+    let central = dir.path().join("central");
+    let other = dir.path().join("other");
+    fs::create_dir_all(&central).unwrap();
+    fs::create_dir_all(&other).unwrap();
+
+    let good = dir.path().join("good");
+    symlink(&central, &good).unwrap();
+    assert_eq!(
+        local_target_health(&central, good.to_string_lossy().as_ref()),
+        "ok"
+    );
+
+    let wrong = dir.path().join("wrong");
+    symlink(&other, &wrong).unwrap();
+    assert_eq!(
+        local_target_health(&central, wrong.to_string_lossy().as_ref()),
+        "mismatch"
+    );
+
+    let broken = dir.path().join("broken");
+    symlink(dir.path().join("gone"), &broken).unwrap();
+    assert_eq!(
+        local_target_health(&central, broken.to_string_lossy().as_ref()),
+        "broken_link"
+    );
+}
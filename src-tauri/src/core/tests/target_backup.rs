@@ -0,0 +1,62 @@
+use std::fs;
+
+use super::*;
+use crate::core::errors::{find_coded, ErrorCode};
+
+#[test]
+fn keeps_and_restores_replaced_content() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    let target = dir.path().join("tool/skills/mine");
+    fs::create_dir_all(&target).unwrap();
+    fs::write(target.join("SKILL.md"), "handwritten").unwrap();
+
+    let kept = backup_target(&store, &target).unwrap().unwrap();
+    assert!(!kept.is_file);
+    fs::remove_dir_all(&target).unwrap();
+    fs::create_dir_all(&target).unwrap();
+    fs::write(target.join("SKILL.md"), "synced").unwrap();
+    assert_eq!(
+        list_overwritten_targets(&store).unwrap(),
+        vec![kept.clone()]
+    );
+
+    let err = restore_overwritten_target(&store, &kept.id, false).unwrap_err();
+    assert_eq!(
+        find_coded(&err).map(|e| e.code),
+        Some(ErrorCode::TargetExists)
+    );
+
+    restore_overwritten_target(&store, &kept.id, true).unwrap();
+    assert_eq!(
+        fs::read_to_string(target.join("SKILL.md")).unwrap(),
+        "handwritten"
+    );
+    // What the restore replaced is kept in turn.
+    let listed = list_overwritten_targets(&store).unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_ne!(listed[0].id, kept.id);
+
+    delete_overwritten_target(&store, &listed[0].id).unwrap();
+    assert!(list_overwritten_targets(&store).unwrap().is_empty());
+    assert!(delete_overwritten_target(&store, "../escape").is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn links_are_not_backed_up() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    let source = dir.path().join("central/demo");
+    fs::create_dir_all(&source).unwrap();
+    let target = dir.path().join("tool/demo");
+    fs::create_dir_all(target.parent().unwrap()).unwrap();
+    std::os::unix::fs::symlink(&source, &target).unwrap();
+
+    assert!(backup_target(&store, &target).unwrap().is_none());
+    assert!(backup_target(&store, &dir.path().join("missing"))
+        .unwrap()
+        .is_none());
+}
@@ -0,0 +1,86 @@
+use std::fs;
+
+use crate::core::errors::{find_coded, ErrorCode};
+
+use super::{inspect_target, target_conflict};
+
+#[test]
+fn copy_with_same_content_matches_source() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("central/pdf");
+    let target = dir.path().join("tool/pdf");
+    for root in [&source, &target] {
+        fs::create_dir_all(root).unwrap();
+        fs::write(root.join("SKILL.md"), "# pdf\n").unwrap();
+    }
+
+    let conflict = inspect_target(&target, &source);
+    assert!(!conflict.is_symlink);
+    assert!(!conflict.points_to_source);
+    assert!(conflict.is_dir);
+    assert_eq!(conflict.bytes, 6);
+    assert!(conflict.modified_ms.is_some());
+    assert_eq!(conflict.content_matches, Some(true));
+
+    fs::write(target.join("SKILL.md"), "# edited\n").unwrap();
+    assert_eq!(
+        inspect_target(&target, &source).content_matches,
+        Some(false)
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn symlink_reports_where_it_points() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("central/pdf");
+    let other = dir.path().join("elsewhere/pdf");
+    for root in [&source, &other] {
+        fs::create_dir_all(root).unwrap();
+    }
+    fs::write(source.join("SKILL.md"), "# pdf\n").unwrap();
+    fs::write(other.join("SKILL.md"), "# other\n").unwrap();
+    fs::create_dir_all(dir.path().join("tool")).unwrap();
+    let target = dir.path().join("tool/pdf");
+
+    std::os::unix::fs::symlink(&source, &target).unwrap();
+    let conflict = inspect_target(&target, &source);
+    assert!(conflict.is_symlink);
+    assert_eq!(
+        conflict.link_target.as_deref(),
+        Some(source.to_string_lossy().as_ref())
+    );
+    assert!(conflict.points_to_source);
+    assert_eq!(conflict.content_matches, Some(true));
+
+    fs::remove_file(&target).unwrap();
+    std::os::unix::fs::symlink(&other, &target).unwrap();
+    let conflict = inspect_target(&target, &source);
+    assert!(!conflict.points_to_source);
+    assert_eq!(conflict.content_matches, Some(false));
+
+    fs::remove_file(&target).unwrap();
+    std::os::unix::fs::symlink(dir.path().join("gone"), &target).unwrap();
+    let conflict = inspect_target(&target, &source);
+    assert!(conflict.is_symlink);
+    assert!(!conflict.is_dir);
+    assert_eq!(conflict.content_matches, None);
+}
+
+#[test]
+fn conflict_error_carries_the_details() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("central/pdf");
+    fs::create_dir_all(&source).unwrap();
+    let target = dir.path().join("pdf.md");
+    fs::write(&target, "loose file").unwrap();
+
+    let err = target_conflict(&target, &source);
+    let coded = find_coded(&err).unwrap();
+    assert_eq!(coded.code, ErrorCode::TargetExists);
+    let payload = coded.payload.as_ref().unwrap();
+    assert_eq!(payload["path"], target.to_string_lossy().as_ref());
+    assert_eq!(payload["is_dir"], false);
+    assert_eq!(payload["bytes"], 10);
+    assert_eq!(payload["content_matches"], false);
+}
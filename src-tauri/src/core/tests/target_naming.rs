@@ -0,0 +1,112 @@
+use std::fs;
+
+use crate::core::skill_store::{SkillRecord, SkillStore, SkillTargetRecord};
+
+use super::{
+    apply_local_rename, get_target_name_template, plan_target_renames, render_target_name,
+    set_target_name_template, validate_template, DEFAULT_TARGET_NAME_TEMPLATE,
+};
+
+fn make_store() -> (tempfile::TempDir, SkillStore) {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().expect("ensure_schema");
+    (dir, store)
+}
+
+fn make_skill(name: &str, source_type: &str) -> SkillRecord {
+    SkillRecord {
+        id: format!("id-{}", name),
+        name: name.to_string(),
+        source_type: source_type.to_string(),
+        source_ref: None,
+        source_revision: None,
+        central_path: format!("/central/{}", name),
+        content_hash: None,
+        created_at: 1,
+        updated_at: 1,
+        last_sync_at: None,
+        last_seen_at: 1,
+        status: "ok".to_string(),
+        group_name: None,
+    }
+}
+
+#[test]
+fn renders_placeholders_and_modifiers() {
+    let skill = make_skill("My Skill", "git");
+    assert_eq!(render_target_name("{name}", &skill), "My Skill");
+    assert_eq!(
+        render_target_name("{source}-{name:kebab}", &skill),
+        "git-my-skill"
+    );
+    assert_eq!(
+        render_target_name("{group}_{name:lower}", &skill),
+        "default_my skill"
+    );
+}
+
+#[test]
+fn validates_templates() {
+    assert!(validate_template("{source}-{name}").is_ok());
+    assert!(validate_template("{source}").is_err());
+    assert!(validate_template("{name}/{source}").is_err());
+    assert!(validate_template("{nmae}").is_err());
+    assert!(validate_template("{name:upper}").is_err());
+    assert!(validate_template("{name").is_err());
+    assert!(validate_template("}{name}").is_err());
+}
+
+#[test]
+fn template_setting_round_trips_with_default() {
+    let (_dir, store) = make_store();
+    assert_eq!(
+        get_target_name_template(&store),
+        DEFAULT_TARGET_NAME_TEMPLATE
+    );
+    set_target_name_template(&store, " {name:kebab} ").unwrap();
+    assert_eq!(get_target_name_template(&store), "{name:kebab}");
+    assert!(set_target_name_template(&store, "{source}").is_err());
+    assert_eq!(get_target_name_template(&store), "{name:kebab}");
+}
+
+#[test]
+fn reapply_renames_local_targets() {
+    let (dir, store) = make_store();
+    let skill = make_skill("My Skill", "local");
+    store.upsert_skill(&skill).unwrap();
+
+    let old = dir.path().join("tool/My Skill");
+    fs::create_dir_all(&old).unwrap();
+    store
+        .upsert_skill_target(&SkillTargetRecord {
+            id: "t1".to_string(),
+            skill_id: skill.id.clone(),
+            tool: "cursor".to_string(),
+            target_path: old.to_string_lossy().to_string(),
+            mode: "copy".to_string(),
+            status: "ok".to_string(),
+            last_error: None,
+            synced_at: Some(1),
+            content_hash: None,
+        })
+        .unwrap();
+
+    assert!(plan_target_renames(&store).unwrap().is_empty());
+
+    set_target_name_template(&store, "{name:kebab}").unwrap();
+    let plan = plan_target_renames(&store).unwrap();
+    assert_eq!(plan.len(), 1);
+    let new_path = dir.path().join("tool/my-skill");
+    assert_eq!(plan[0].to, new_path.to_string_lossy());
+
+    apply_local_rename(&store, &plan[0]).unwrap();
+    assert!(new_path.exists());
+    assert!(!old.exists());
+    let record = store
+        .get_skill_target(&skill.id, "cursor")
+        .unwrap()
+        .unwrap();
+    assert_eq!(record.target_path, new_path.to_string_lossy());
+    assert!(plan_target_renames(&store).unwrap().is_empty());
+}
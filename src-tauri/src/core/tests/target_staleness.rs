@@ -0,0 +1,89 @@
+use crate::core::skill_store::{CustomTargetRecord, RemoteDeploymentRecord, SkillTargetRecord};
+
+use super::{deployment_is_stale, target_is_stale};
+
+fn make_target(tool: &str, mode: &str, hash: Option<&str>) -> SkillTargetRecord {
+    SkillTargetRecord {
+        id: "t1".to_string(),
+        skill_id: "s1".to_string(),
+        tool: tool.to_string(),
+        target_path: "/tmp/target".to_string(),
+        mode: mode.to_string(),
+        status: "ok".to_string(),
+        last_error: None,
+        synced_at: Some(1),
+        content_hash: hash.map(str::to_string),
+    }
+}
+
+fn make_deployment(hash: &str, status: &str) -> RemoteDeploymentRecord {
+    RemoteDeploymentRecord {
+        host_id: "h1".to_string(),
+        skill_id: "s1".to_string(),
+        tool_keys: vec!["codex".to_string()],
+        content_hash: Some(hash.to_string()),
+        synced_at: 1,
+        status: status.to_string(),
+    }
+}
+
+#[test]
+fn copy_targets_compare_hashes() {
+    let current = Some("new");
+    assert!(target_is_stale(
+        &make_target("cursor", "copy", Some("old")),
+        current,
+        &[],
+        &[]
+    ));
+    assert!(!target_is_stale(
+        &make_target("cursor", "copy", Some("new")),
+        current,
+        &[],
+        &[]
+    ));
+    // Symlinks follow the central copy; unknown hashes are not flagged.
+    assert!(!target_is_stale(
+        &make_target("codex", "symlink", Some("old")),
+        current,
+        &[],
+        &[]
+    ));
+    assert!(!target_is_stale(
+        &make_target("cursor", "copy", None),
+        current,
+        &[],
+        &[]
+    ));
+}
+
+#[test]
+fn remote_targets_follow_host_deployment() {
+    let custom = vec![CustomTargetRecord {
+        id: "ct1".to_string(),
+        label: "VM".to_string(),
+        path: "/srv/skills".to_string(),
+        remote_host_id: Some("h1".to_string()),
+        created_at: 1,
+    }];
+    let target = make_target("custom:ct1", "symlink", Some("new"));
+
+    assert!(!target_is_stale(&target, Some("new"), &custom, &[]));
+    assert!(target_is_stale(
+        &target,
+        Some("new"),
+        &custom,
+        &[make_deployment("old", "ok")]
+    ));
+    assert!(!target_is_stale(
+        &target,
+        Some("new"),
+        &custom,
+        &[make_deployment("new", "ok")]
+    ));
+
+    assert!(deployment_is_stale(
+        &make_deployment("new", "stale"),
+        Some("new")
+    ));
+}
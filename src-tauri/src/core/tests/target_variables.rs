@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+
+use crate::core::skill_store::SkillStore;
+
+use super::{
+    custom_target_id, custom_target_key, expand_target_path, forget_project, list_recent_projects,
+    path_variables, remember_project,
+};
+
+fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+#[test]
+fn expands_variables_and_rejects_missing_or_escaping_values() {
+    let template = "{project}/.claude/skills";
+    assert_eq!(path_variables(template), vec!["project"]);
+    assert!(path_variables("/opt/skills").is_empty());
+    assert_eq!(path_variables("{a}/{b}/{a}"), vec!["a", "b"]);
+
+    assert_eq!(
+        expand_target_path(template, &vars(&[("project", "/work/app/")])).unwrap(),
+        "/work/app/.claude/skills"
+    );
+    assert!(expand_target_path(template, &vars(&[])).is_err());
+    assert!(expand_target_path(template, &vars(&[("project", " ")])).is_err());
+    assert!(expand_target_path(template, &vars(&[("project", "/work/../etc")])).is_err());
+}
+
+#[test]
+fn tool_keys_carry_the_expanded_root() {
+    assert_eq!(custom_target_key("ct1", None), "custom:ct1");
+    let key = custom_target_key("ct1", Some("/work/app/.claude/skills"));
+    assert_eq!(key, "custom:ct1@/work/app/.claude/skills");
+    assert_eq!(custom_target_id(&key), Some("ct1"));
+    assert_eq!(custom_target_id("custom:ct1"), Some("ct1"));
+    assert_eq!(custom_target_id("cursor"), None);
+}
+
+#[test]
+fn recent_projects_are_most_recent_first_and_bounded() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+
+    assert!(list_recent_projects(&store).is_empty());
+    for i in 0..12 {
+        remember_project(&store, &format!("/work/p{}", i)).unwrap();
+    }
+    remember_project(&store, "/work/p5/").unwrap();
+    let recent = list_recent_projects(&store);
+    assert_eq!(recent.len(), 10);
+    assert_eq!(recent[0], "/work/p5");
+    assert_eq!(recent[1], "/work/p11");
+    assert_eq!(recent.iter().filter(|p| *p == "/work/p5").count(), 1);
+
+    let recent = forget_project(&store, "/work/p5").unwrap();
+    assert_eq!(recent[0], "/work/p11");
+}
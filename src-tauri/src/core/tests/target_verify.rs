@@ -0,0 +1,120 @@
+use std::fs;
+
+use super::*;
+use crate::core::skill_store::SkillRecord;
+
+fn target(tool: &str, path: &Path, mode: &str, hash: Option<&str>) -> SkillTargetRecord {
+    SkillTargetRecord {
+        id: format!("t-{}", tool),
+        skill_id: "s1".to_string(),
+        tool: tool.to_string(),
+        target_path: path.to_string_lossy().to_string(),
+        mode: mode.to_string(),
+        status: "ok".to_string(),
+        last_error: None,
+        synced_at: None,
+        content_hash: hash.map(str::to_string),
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn marks_and_repairs_broken_targets() {
+    let dir = tempfile::tempdir().unwrap();
+    let central = dir.path().join("central/demo");
+    fs::create_dir_all(&central).unwrap();
+    fs::write(central.join("SKILL.md"), "demo").unwrap();
+    let hash = hash_dir(&central).unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    store
+        .upsert_skill(&SkillRecord {
+            id: "s1".to_string(),
+            name: "demo".to_string(),
+            source_type: "local".to_string(),
+            source_ref: None,
+            source_revision: None,
+            central_path: central.to_string_lossy().to_string(),
+            content_hash: Some(hash.clone()),
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: "ok".to_string(),
+            group_name: None,
+        })
+        .unwrap();
+
+    let tools = dir.path().join("tools");
+    fs::create_dir_all(&tools).unwrap();
+    let linked = tools.join("linked");
+    std::os::unix::fs::symlink(&central, &linked).unwrap();
+    let mut healed = target("tool_ok", &linked, "symlink", None);
+    healed.status = "missing".to_string();
+    healed.last_error = Some("target not found".to_string());
+    store.upsert_skill_target(&healed).unwrap();
+    store
+        .upsert_skill_target(&target(
+            "tool_missing",
+            &tools.join("gone"),
+            "symlink",
+            None,
+        ))
+        .unwrap();
+    let dangling = tools.join("dangling");
+    std::os::unix::fs::symlink(dir.path().join("nowhere"), &dangling).unwrap();
+    store
+        .upsert_skill_target(&target("tool_dangling", &dangling, "symlink", None))
+        .unwrap();
+    let copied = tools.join("copied");
+    fs::create_dir_all(&copied).unwrap();
+    store
+        .upsert_skill_target(&target("tool_stale", &copied, "copy", Some("old")))
+        .unwrap();
+    store
+        .upsert_skill_target(&target(
+            "remote:h1:claude",
+            &tools.join("r"),
+            "symlink",
+            None,
+        ))
+        .unwrap();
+
+    let report = verify_all_targets(&store, false).unwrap();
+    assert_eq!(report.checked, 4);
+    assert_eq!(report.skipped, 1);
+    let mut states: Vec<_> = report
+        .problems
+        .iter()
+        .map(|c| (c.tool.as_str(), c.state, c.repaired))
+        .collect();
+    states.sort_by_key(|(tool, _, _)| *tool);
+    assert_eq!(
+        states,
+        vec![
+            ("tool_dangling", TargetState::Dangling, false),
+            ("tool_missing", TargetState::Missing, false),
+            ("tool_stale", TargetState::Stale, false),
+        ]
+    );
+    let rows = store.list_skill_targets("s1").unwrap();
+    let row = |tool: &str| rows.iter().find(|t| t.tool == tool).unwrap().clone();
+    assert_eq!(row("tool_ok").status, "ok");
+    assert!(row("tool_ok").last_error.is_none());
+    assert_eq!(row("tool_missing").status, "missing");
+    assert_eq!(row("tool_stale").status, "stale");
+
+    let report = verify_all_targets(&store, true).unwrap();
+    assert!(report.problems.iter().all(|c| c.repaired), "{:?}", report);
+    assert!(tools.join("gone/SKILL.md").exists());
+    assert!(dangling.join("SKILL.md").exists());
+    assert!(copied.join("SKILL.md").exists());
+    let rows = store.list_skill_targets("s1").unwrap();
+    let stale = rows.iter().find(|t| t.tool == "tool_stale").unwrap();
+    assert_eq!(stale.content_hash.as_deref(), Some(hash.as_str()));
+
+    assert!(verify_all_targets(&store, false)
+        .unwrap()
+        .problems
+        .is_empty());
+}
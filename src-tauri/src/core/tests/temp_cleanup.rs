@@ -1,7 +1,7 @@
 use std::fs;
 use std::time::Duration;
 
-use super::{cleanup_old_git_temp_dirs_in, mark_temp_dir};
+use super::*;
 
 #[test]
 fn cleanup_removes_only_marked_prefixed_dirs() {
@@ -25,3 +25,52 @@ fn cleanup_removes_only_marked_prefixed_dirs() {
     assert!(d2.exists(), "未标记的不应删除");
     assert!(d3.exists(), "前缀不匹配的不应删除");
 }
+
+#[test]
+fn orphaned_operation_dirs_are_reaped_and_live_ones_kept() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+
+    let live = operation_temp_dir_in(root, "clawhub").unwrap();
+    assert!(live.path().join(OWNER_MARKER).exists());
+    assert!(live
+        .path()
+        .file_name()
+        .unwrap()
+        .to_string_lossy()
+        .starts_with("clawhub-"));
+
+    // Left behind by a crashed run whose process is gone.
+    let orphan = root.join("git-crashed");
+    fs::create_dir_all(&orphan).unwrap();
+    let owner = TempOwner {
+        pid: u32::MAX - 1,
+        instance: "previous-run".to_string(),
+        purpose: "git".to_string(),
+        created_at: now_ms(),
+    };
+    fs::write(
+        orphan.join(OWNER_MARKER),
+        serde_json::to_vec(&owner).unwrap(),
+    )
+    .unwrap();
+    let unmarked = root.join("someone-else");
+    fs::create_dir_all(&unmarked).unwrap();
+
+    let removed = cleanup_orphaned_temp_dirs_in(root, Duration::from_secs(3600)).unwrap();
+    assert_eq!(removed, 1);
+    assert!(!orphan.exists());
+    assert!(unmarked.exists());
+    assert!(
+        live.path().exists(),
+        "dirs of this run stay even at max age 0"
+    );
+    assert_eq!(
+        cleanup_orphaned_temp_dirs_in(root, Duration::from_secs(0)).unwrap(),
+        0
+    );
+
+    let path = live.path().to_path_buf();
+    drop(live);
+    assert!(!path.exists());
+}
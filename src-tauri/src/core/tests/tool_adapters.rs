@@ -81,3 +81,116 @@ fn scan_tool_dir_skips_app_support_path() {
     let out = scan_tool_dir(&tool, &root).unwrap();
     assert!(out.is_empty());
 }
+
+#[test]
+fn path_override_moves_skills_dir_and_install_detection() {
+    use crate::core::skill_store::SkillStore;
+    use crate::core::tool_adapters::{
+        get_tool_path_overrides, is_tool_installed, resolve_default_path, set_tool_path_override,
+    };
+
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    let relocated = dir.path().join("xdg/mux/skills");
+    let mux = adapter_by_key("mux").unwrap();
+    let detected_mux = |force: bool| {
+        crate::core::tool_status::detect_tools(force)
+            .unwrap()
+            .into_iter()
+            .find(|tool| tool.key == "mux")
+            .unwrap()
+    };
+    // Prime the detection cache; the override below must invalidate it.
+    detected_mux(false);
+
+    assert!(set_tool_path_override(&store, "mux", Some(relocated.to_str().unwrap())).is_err());
+    assert!(set_tool_path_override(&store, "nope", Some("/tmp")).is_err());
+    fs::create_dir_all(&relocated).unwrap();
+    set_tool_path_override(&store, "mux", Some(relocated.to_str().unwrap())).unwrap();
+    assert_eq!(resolve_default_path(&mux).unwrap(), relocated);
+    assert!(is_tool_installed(&mux).unwrap());
+    let detected = detected_mux(false);
+    assert!(detected.installed);
+    assert_eq!(detected.skills_dir, relocated.to_string_lossy());
+    assert_eq!(adapters_sharing_skills_dir(&mux).len(), 1);
+    assert!(get_tool_path_overrides(&store).contains_key("mux"));
+
+    set_tool_path_override(&store, "mux", None).unwrap();
+    assert_ne!(resolve_default_path(&mux).unwrap(), relocated);
+    assert_ne!(detected_mux(false).skills_dir, relocated.to_string_lossy());
+    assert!(get_tool_path_overrides(&store).is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn skills_dirs_reached_through_a_symlink_compare_equal() {
+    use crate::core::tool_adapters::canonicalize_existing_prefix;
+
+    let dir = tempfile::tempdir().unwrap();
+    let real = dir.path().join("real");
+    fs::create_dir_all(real.join("skills")).unwrap();
+    let link = dir.path().join("link");
+    std::os::unix::fs::symlink(&real, &link).unwrap();
+
+    assert_eq!(
+        canonicalize_existing_prefix(&link.join("skills")),
+        canonicalize_existing_prefix(&real.join("skills"))
+    );
+    // Not created yet: only the existing parent is resolved.
+    assert_eq!(
+        canonicalize_existing_prefix(&link.join("later/skills")),
+        fs::canonicalize(&real).unwrap().join("later/skills")
+    );
+}
+
+#[test]
+fn adapter_config_adjusts_builtins_and_adds_tools() {
+    use super::{
+        adapters_from_config, builtin_tool_adapters, load_adapter_config, merge_adapters,
+        AdapterConfigEntry,
+    };
+
+    let entry = |key: &str, skills: Option<&str>, detect: Option<&str>| AdapterConfigEntry {
+        key: key.to_string(),
+        display_name: None,
+        skills_dir: skills.map(str::to_string),
+        detect_dir: detect.map(str::to_string),
+        format: None,
+    };
+    let configured = adapters_from_config(vec![
+        entry("codex", Some(".local/codex/skills"), None),
+        entry("acme_agent", Some(".acme/agent/skills"), None),
+    ])
+    .unwrap();
+    let merged = merge_adapters(builtin_tool_adapters(), configured);
+    assert_eq!(merged.len(), builtin_tool_adapters().len() + 1);
+
+    let codex = merged.iter().find(|a| a.id == ToolId::Codex).unwrap();
+    assert_eq!(codex.relative_skills_dir, ".local/codex/skills");
+    assert_eq!(codex.relative_detect_dir, ".codex");
+    assert_eq!(codex.display_name, "Codex");
+    let acme = merged.last().unwrap();
+    assert_eq!(acme.id.as_key(), "acme_agent");
+    assert_eq!(acme.display_name, "acme_agent");
+    assert_eq!(acme.relative_detect_dir, ".acme/agent");
+
+    assert!(adapters_from_config(vec![entry("Bad Key", Some(".x/skills"), None)]).is_err());
+    assert!(adapters_from_config(vec![entry("new_tool", None, None)]).is_err());
+    assert!(adapters_from_config(vec![entry("x", Some("../escape"), None)]).is_err());
+    assert!(adapters_from_config(vec![entry("x", Some("/abs/skills"), None)]).is_err());
+    assert!(adapters_from_config(vec![
+        entry("x", Some(".x/skills"), None),
+        entry("x", Some(".y/skills"), None),
+    ])
+    .is_err());
+
+    let dir = tempfile::tempdir().unwrap();
+    assert_eq!(
+        load_adapter_config(&dir.path().join("missing.json")).unwrap(),
+        0
+    );
+    let broken = dir.path().join("broken.json");
+    fs::write(&broken, "{").unwrap();
+    assert!(load_adapter_config(&broken).is_err());
+}
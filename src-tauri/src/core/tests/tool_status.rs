@@ -0,0 +1,23 @@
+use super::{detect_tools, invalidate_tool_detection};
+
+#[test]
+fn detects_every_adapter_in_order() {
+    let keys: Vec<String> = detect_tools(true)
+        .unwrap()
+        .into_iter()
+        .map(|tool| tool.key)
+        .collect();
+    let expected: Vec<String> = crate::core::tool_adapters::default_tool_adapters()
+        .iter()
+        .map(|adapter| adapter.id.as_key().to_string())
+        .collect();
+    assert_eq!(keys, expected);
+}
+
+#[test]
+fn cached_and_fresh_detection_agree() {
+    invalidate_tool_detection();
+    let fresh = detect_tools(false).unwrap();
+    assert_eq!(detect_tools(false).unwrap().len(), fresh.len());
+    assert_eq!(detect_tools(true).unwrap().len(), fresh.len());
+}
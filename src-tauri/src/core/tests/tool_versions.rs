@@ -0,0 +1,28 @@
+use super::*;
+
+#[test]
+fn parses_versions_from_common_cli_output() {
+    assert_eq!(
+        parse_version("1.0.42 (Claude Code)").as_deref(),
+        Some("1.0.42")
+    );
+    assert_eq!(
+        parse_version("codex-cli 0.39.0\n").as_deref(),
+        Some("0.39.0")
+    );
+    assert_eq!(parse_version("gemini v0.4.1").as_deref(), Some("0.4.1"));
+    assert_eq!(
+        parse_version("goose 1.2.0-beta.1, build abc").as_deref(),
+        Some("1.2.0-beta.1")
+    );
+    assert_eq!(parse_version("Version: 2.3.").as_deref(), Some("2.3"));
+    assert_eq!(parse_version("no version here 42"), None);
+    assert_eq!(parse_version(""), None);
+}
+
+#[test]
+fn tools_without_a_cli_report_no_version() {
+    assert_eq!(version_binary("windsurf"), None);
+    assert_eq!(detect_version("windsurf"), None);
+    assert_eq!(version_binary("claude_code"), Some("claude"));
+}
@@ -0,0 +1,152 @@
+use std::path::Path;
+use std::time::Duration;
+
+use crate::core::skill_store::{SkillRecord, SkillStore, SkillTargetRecord};
+use crate::core::sync_engine::sync_dir_hybrid_with_overwrite;
+
+use super::{UndoKind, UndoStack};
+
+fn make_store(dir: &Path) -> SkillStore {
+    let store = SkillStore::new(dir.join("test.db"));
+    store.ensure_schema().unwrap();
+    store
+        .upsert_skill(&SkillRecord {
+            id: "s1".to_string(),
+            name: "demo".to_string(),
+            source_type: "local".to_string(),
+            source_ref: None,
+            source_revision: None,
+            central_path: dir.join("central/demo").to_string_lossy().to_string(),
+            content_hash: None,
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: "ok".to_string(),
+            group_name: None,
+        })
+        .unwrap();
+    store
+}
+
+fn target_row(skill_id: &str, tool: &str, path: &Path) -> SkillTargetRecord {
+    SkillTargetRecord {
+        id: format!("{}-{}", skill_id, tool),
+        skill_id: skill_id.to_string(),
+        tool: tool.to_string(),
+        target_path: path.to_string_lossy().to_string(),
+        mode: "copy".to_string(),
+        status: "ok".to_string(),
+        last_error: None,
+        synced_at: Some(1),
+        content_hash: None,
+    }
+}
+
+fn write_skill(dir: &Path, body: &str) {
+    std::fs::create_dir_all(dir).unwrap();
+    std::fs::write(dir.join("SKILL.md"), body).unwrap();
+}
+
+#[test]
+fn undo_unsync_restores_target_and_row() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = make_store(dir.path());
+    let undo = UndoStack::new(dir.path().join("undo"));
+    let target = dir.path().join("tool/skills/demo");
+    write_skill(&target, "v1");
+    let row = target_row("s1", "cursor", &target);
+    store.upsert_skill_target(&row).unwrap();
+
+    let held = undo.stash(&target).unwrap();
+    assert!(!target.exists());
+    store.delete_skill_target("s1", "cursor").unwrap();
+    undo.push(
+        UndoKind::Unsync,
+        "s1",
+        vec!["cursor".to_string()],
+        &target,
+        held,
+        vec![row],
+    );
+
+    let summary = undo.undo_last(&store).unwrap().unwrap();
+    assert_eq!(summary.kind, UndoKind::Unsync);
+    assert_eq!(
+        std::fs::read_to_string(target.join("SKILL.md")).unwrap(),
+        "v1"
+    );
+    assert!(store.get_skill_target("s1", "cursor").unwrap().is_some());
+    assert!(undo.undo_last(&store).unwrap().is_none());
+}
+
+#[test]
+fn undo_overwriting_sync_brings_back_replaced_content() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = make_store(dir.path());
+    let undo = UndoStack::new(dir.path().join("undo"));
+    let source = dir.path().join("central/demo");
+    write_skill(&source, "new");
+    let target = dir.path().join("tool/skills/demo");
+    write_skill(&target, "old");
+
+    let held = undo.stash(&target).unwrap();
+    sync_dir_hybrid_with_overwrite(&source, &target, true).unwrap();
+    store
+        .upsert_skill_target(&target_row("s1", "cursor", &target))
+        .unwrap();
+    undo.push(
+        UndoKind::Sync,
+        "s1",
+        vec!["cursor".to_string()],
+        &target,
+        held,
+        Vec::new(),
+    );
+
+    undo.undo_last(&store).unwrap().unwrap();
+    assert_eq!(
+        std::fs::read_to_string(target.join("SKILL.md")).unwrap(),
+        "old"
+    );
+    assert!(std::fs::read_link(&target).is_err());
+    assert!(store.get_skill_target("s1", "cursor").unwrap().is_none());
+    assert_eq!(
+        std::fs::read_to_string(source.join("SKILL.md")).unwrap(),
+        "new"
+    );
+}
+
+#[test]
+fn expired_operations_are_dropped() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = make_store(dir.path());
+    let undo = UndoStack::with_ttl(dir.path().join("undo"), Duration::ZERO);
+    let target = dir.path().join("tool/skills/demo");
+    write_skill(&target, "v1");
+
+    let held = undo.stash(&target).unwrap();
+    undo.push(
+        UndoKind::Unsync,
+        "s1",
+        vec!["cursor".to_string()],
+        &target,
+        held,
+        Vec::new(),
+    );
+
+    assert!(undo.last().is_none());
+    assert!(undo.undo_last(&store).unwrap().is_none());
+    assert!(!target.exists());
+}
+
+#[test]
+fn temp_dir_holding_area_goes_with_last_clone() {
+    let undo = UndoStack::in_temp_dir().unwrap();
+    let root = undo.root().to_path_buf();
+    let clone = undo.clone();
+    drop(undo);
+    assert!(root.is_dir());
+    drop(clone);
+    assert!(!root.exists());
+}
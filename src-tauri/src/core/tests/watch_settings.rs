@@ -0,0 +1,73 @@
+use crate::core::skill_store::{SkillRecord, SkillStore};
+
+use super::*;
+
+#[test]
+fn globs_match_segments_and_directories() {
+    assert!(glob_matches("*.log", "out/run.log"));
+    assert!(!glob_matches("*.log", "out/run.logs"));
+    assert!(glob_matches("scratch", "scratch/tmp/a.txt"));
+    assert!(glob_matches("scratch/**", "scratch/tmp/a.txt"));
+    assert!(glob_matches("scratch/*", "scratch/a.txt"));
+    assert!(!glob_matches("scratch/*", "notes/scratch.md"));
+    assert!(glob_matches("**/cache/*.bin", "a/b/cache/x.bin"));
+    assert!(glob_matches("**/cache/*.bin", "cache/x.bin"));
+    assert!(glob_matches("data?/x", "data1/x"));
+    assert!(!glob_matches("data?/x", "data12/x"));
+
+    let settings = WatchSettings {
+        exclude_globs: vec!["scratch/**".to_string(), "*.tmp".to_string()],
+        ..WatchSettings::default()
+    };
+    assert!(settings.is_excluded("./scratch/run/out.json"));
+    assert!(settings.is_excluded("refs/a.tmp"));
+    assert!(!settings.is_excluded("SKILL.md"));
+}
+
+#[test]
+fn settings_round_trip_per_skill() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = SkillStore::new(dir.path().join("test.db"));
+    store.ensure_schema().unwrap();
+    store
+        .upsert_skill(&SkillRecord {
+            id: "s1".to_string(),
+            name: "demo".to_string(),
+            source_type: "local".to_string(),
+            source_ref: None,
+            source_revision: None,
+            central_path: dir.path().join("demo").to_string_lossy().to_string(),
+            content_hash: None,
+            created_at: 1,
+            updated_at: 1,
+            last_sync_at: None,
+            last_seen_at: 1,
+            status: "ok".to_string(),
+            group_name: None,
+        })
+        .unwrap();
+
+    assert_eq!(get_watch_settings(&store, "s1"), WatchSettings::default());
+    let saved = set_watch_settings(
+        &store,
+        "s1",
+        Some(WatchSettings {
+            exclude_globs: vec![" ./scratch/** ".to_string(), "".to_string()],
+            debounce_ms: 2000,
+            max_events_per_minute: 5,
+        }),
+    )
+    .unwrap();
+    assert_eq!(saved.exclude_globs, vec!["scratch/**"]);
+    assert_eq!(get_watch_settings(&store, "s1"), saved);
+
+    let bad = WatchSettings {
+        exclude_globs: vec!["../outside".to_string()],
+        ..WatchSettings::default()
+    };
+    assert!(set_watch_settings(&store, "s1", Some(bad)).is_err());
+    assert!(set_watch_settings(&store, "missing", None).is_err());
+
+    set_watch_settings(&store, "s1", None).unwrap();
+    assert_eq!(get_watch_settings(&store, "s1"), WatchSettings::default());
+}
@@ -1,6 +1,15 @@
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
 
 use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::skill_format::{set_declared_formats, SkillFormat};
+use super::skill_store::SkillStore;
+use super::tool_status::invalidate_tool_detection;
+
+pub const TOOL_PATH_OVERRIDES_KEY: &str = "tool_path_overrides";
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ToolId {
@@ -44,6 +53,8 @@ pub enum ToolId {
     Clawdbot,
     Droid,
     Windsurf,
+    /// A tool defined in the adapter config file, by its key.
+    Custom(&'static str),
 }
 
 impl ToolId {
@@ -89,6 +100,7 @@ impl ToolId {
             ToolId::Clawdbot => "clawdbot",
             ToolId::Droid => "droid",
             ToolId::Windsurf => "windsurf",
+            ToolId::Custom(key) => key,
         }
     }
 }
@@ -112,7 +124,16 @@ pub struct DetectedSkill {
     pub link_target: Option<PathBuf>,
 }
 
+/// The built-in adapters merged with the ones from the adapter config file.
 pub fn default_tool_adapters() -> Vec<ToolAdapter> {
+    let configured = configured_cell()
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .clone();
+    merge_adapters(builtin_tool_adapters(), configured)
+}
+
+fn builtin_tool_adapters() -> Vec<ToolAdapter> {
     vec![
         ToolAdapter {
             id: ToolId::Cursor,
@@ -395,22 +416,194 @@ pub fn default_tool_adapters() -> Vec<ToolAdapter> {
     ]
 }
 
+pub const ADAPTER_CONFIG_FILE: &str = "tool_adapters.json";
+
+/// One entry of the adapter config file. A `key` matching a built-in tool
+/// adjusts it; any other key adds a tool.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdapterConfigEntry {
+    pub key: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    /// Relative to home, e.g. `.mytool/skills`.
+    #[serde(default)]
+    pub skills_dir: Option<String>,
+    /// Relative to home; the tool counts as installed when it exists.
+    #[serde(default)]
+    pub detect_dir: Option<String>,
+    /// The layout skills are converted to when synced to this tool.
+    #[serde(default)]
+    pub format: Option<SkillFormat>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AdapterConfigFile {
+    adapters: Vec<AdapterConfigEntry>,
+}
+
+static CONFIGURED: OnceLock<Mutex<Vec<ToolAdapter>>> = OnceLock::new();
+
+fn configured_cell() -> &'static Mutex<Vec<ToolAdapter>> {
+    CONFIGURED.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+/// Read `path` (a JSON adapter config) and use its adapters from now on.
+/// A missing file means no extra adapters. Returns how many were loaded.
+pub fn load_adapter_config(path: &Path) -> Result<usize> {
+    let raw = match std::fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(err) => return Err(err).with_context(|| format!("read {:?}", path)),
+    };
+    let file: AdapterConfigFile =
+        serde_json::from_str(&raw).with_context(|| format!("parse {:?}", path))?;
+    let declared = file
+        .adapters
+        .iter()
+        .filter_map(|entry| Some((entry.key.trim().to_string(), entry.format?)))
+        .collect();
+    let adapters = adapters_from_config(file.adapters)?;
+    let count = adapters.len();
+    *configured_cell()
+        .lock()
+        .unwrap_or_else(|err| err.into_inner()) = adapters;
+    set_declared_formats(declared);
+    invalidate_tool_detection();
+    Ok(count)
+}
+
+fn adapters_from_config(entries: Vec<AdapterConfigEntry>) -> Result<Vec<ToolAdapter>> {
+    let builtins = builtin_tool_adapters();
+    let mut adapters: Vec<ToolAdapter> = Vec::new();
+    for entry in entries {
+        let key = entry.key.trim();
+        if key.is_empty()
+            || !key
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        {
+            anyhow::bail!(
+                "adapter key may only contain lowercase letters, digits and '_': {}",
+                entry.key
+            );
+        }
+        if adapters.iter().any(|a| a.id.as_key() == key) {
+            anyhow::bail!("adapter {} is defined twice", key);
+        }
+        for dir in [&entry.skills_dir, &entry.detect_dir].into_iter().flatten() {
+            if !super::archive::is_safe_relative_path(dir) {
+                anyhow::bail!("adapter {}: {} must be relative to home", key, dir);
+            }
+        }
+        // Adapters live for the whole run, so their strings do too.
+        let leak = |s: String| -> &'static str { Box::leak(s.into_boxed_str()) };
+        let adapter = match builtins.iter().find(|a| a.id.as_key() == key) {
+            Some(builtin) => ToolAdapter {
+                id: builtin.id.clone(),
+                display_name: entry.display_name.map_or(builtin.display_name, leak),
+                relative_skills_dir: entry.skills_dir.map_or(builtin.relative_skills_dir, leak),
+                relative_detect_dir: entry.detect_dir.map_or(builtin.relative_detect_dir, leak),
+            },
+            None => {
+                let skills_dir = entry
+                    .skills_dir
+                    .ok_or_else(|| anyhow::anyhow!("new adapter {} needs a skillsDir", key))?;
+                // Without a detect dir the tool is installed once its skills
+                // dir's parent exists.
+                let detect_dir = entry.detect_dir.unwrap_or_else(|| {
+                    Path::new(&skills_dir)
+                        .parent()
+                        .map(|p| p.to_string_lossy().to_string())
+                        .filter(|p| !p.is_empty())
+                        .unwrap_or_else(|| skills_dir.clone())
+                });
+                ToolAdapter {
+                    id: ToolId::Custom(leak(key.to_string())),
+                    display_name: leak(entry.display_name.unwrap_or_else(|| key.to_string())),
+                    relative_skills_dir: leak(skills_dir),
+                    relative_detect_dir: leak(detect_dir),
+                }
+            }
+        };
+        adapters.push(adapter);
+    }
+    Ok(adapters)
+}
+
+/// Built-ins in their usual order with configured overrides applied, then
+/// the configured tools that are new.
+fn merge_adapters(builtins: Vec<ToolAdapter>, configured: Vec<ToolAdapter>) -> Vec<ToolAdapter> {
+    let mut merged: Vec<ToolAdapter> = builtins
+        .into_iter()
+        .map(|builtin| {
+            configured
+                .iter()
+                .find(|c| c.id == builtin.id)
+                .cloned()
+                .unwrap_or(builtin)
+        })
+        .collect();
+    for adapter in configured {
+        if !merged.iter().any(|a| a.id == adapter.id) {
+            merged.push(adapter);
+        }
+    }
+    merged
+}
+
 /// Tools can share the same global skills directory (e.g. Amp and Kimi Code CLI).
 /// Use this to coordinate UI warnings and avoid duplicate filesystem operations.
+/// Sharing is decided on the resolved directories, so overrides and symlinks
+/// pointing two tools at one place group them too.
 pub fn adapters_sharing_skills_dir(adapter: &ToolAdapter) -> Vec<ToolAdapter> {
+    let dir = canonical_skills_dir(adapter);
     default_tool_adapters()
         .into_iter()
-        .filter(|a| a.relative_skills_dir == adapter.relative_skills_dir)
+        .filter(|a| match &dir {
+            Some(dir) => canonical_skills_dir(a).as_ref() == Some(dir),
+            None => a.relative_skills_dir == adapter.relative_skills_dir,
+        })
         .collect()
 }
 
+fn canonical_skills_dir(adapter: &ToolAdapter) -> Option<PathBuf> {
+    resolve_default_path(adapter)
+        .ok()
+        .map(|dir| canonicalize_existing_prefix(&dir))
+}
+
+/// Resolve symlinks in the longest existing prefix of `path`, so a skills dir
+/// that is not created yet still matches one reached through a linked parent.
+fn canonicalize_existing_prefix(path: &Path) -> PathBuf {
+    let mut missing = Vec::new();
+    let mut current = path;
+    loop {
+        if let Ok(real) = std::fs::canonicalize(current) {
+            return missing.iter().rev().fold(real, |acc, part| acc.join(part));
+        }
+        match (current.parent(), current.file_name()) {
+            (Some(parent), Some(name)) => {
+                missing.push(name);
+                current = parent;
+            }
+            _ => return path.to_path_buf(),
+        }
+    }
+}
+
 pub fn adapter_by_key(key: &str) -> Option<ToolAdapter> {
     default_tool_adapters()
         .into_iter()
         .find(|adapter| adapter.id.as_key() == key)
 }
 
+/// The tool's global skills directory: the user's override if set,
+/// otherwise the standard location under home.
 pub fn resolve_default_path(adapter: &ToolAdapter) -> Result<PathBuf> {
+    if let Some(path) = path_override(adapter) {
+        return Ok(path);
+    }
     let home = dirs::home_dir().context("failed to resolve home directory")?;
     Ok(home.join(adapter.relative_skills_dir))
 }
@@ -420,10 +613,94 @@ pub fn resolve_detect_path(adapter: &ToolAdapter) -> Result<PathBuf> {
     Ok(home.join(adapter.relative_detect_dir))
 }
 
+/// A tool with an overridden skills directory counts as installed while
+/// that directory exists.
 pub fn is_tool_installed(adapter: &ToolAdapter) -> Result<bool> {
+    if let Some(path) = path_override(adapter) {
+        return Ok(path.is_dir());
+    }
     Ok(resolve_detect_path(adapter)?.exists())
 }
 
+static PATH_OVERRIDES: OnceLock<Mutex<HashMap<String, PathBuf>>> = OnceLock::new();
+
+fn overrides_cell() -> &'static Mutex<HashMap<String, PathBuf>> {
+    PATH_OVERRIDES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn path_override(adapter: &ToolAdapter) -> Option<PathBuf> {
+    overrides_cell()
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .get(adapter.id.as_key())
+        .cloned()
+}
+
+/// Tool key -> skills directory for tools whose config dir was relocated.
+pub fn get_tool_path_overrides(store: &SkillStore) -> HashMap<String, String> {
+    store
+        .get_setting(TOOL_PATH_OVERRIDES_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+/// Point `tool_key` at another skills directory, which must exist; `None`
+/// goes back to the default location.
+pub fn set_tool_path_override(
+    store: &SkillStore,
+    tool_key: &str,
+    path: Option<&str>,
+) -> Result<HashMap<String, String>> {
+    if adapter_by_key(tool_key).is_none() {
+        anyhow::bail!("unknown tool: {}", tool_key);
+    }
+    let mut overrides = get_tool_path_overrides(store);
+    match path.map(str::trim).filter(|p| !p.is_empty()) {
+        Some(raw) => {
+            let dir = expand_home(raw)?;
+            if !dir.is_absolute() {
+                anyhow::bail!("skills directory must be an absolute path: {}", raw);
+            }
+            if !dir.is_dir() {
+                anyhow::bail!("skills directory does not exist: {}", dir.display());
+            }
+            overrides.insert(tool_key.to_string(), dir.to_string_lossy().to_string());
+        }
+        None => {
+            overrides.remove(tool_key);
+        }
+    }
+    let raw = serde_json::to_string(&overrides).context("serialize tool path overrides")?;
+    store.set_setting(TOOL_PATH_OVERRIDES_KEY, &raw)?;
+    apply_overrides(&overrides);
+    Ok(overrides)
+}
+
+/// Load saved overrides into the table path resolution uses.
+pub fn load_tool_path_overrides(store: &SkillStore) {
+    apply_overrides(&get_tool_path_overrides(store));
+}
+
+fn apply_overrides(overrides: &HashMap<String, String>) {
+    *overrides_cell()
+        .lock()
+        .unwrap_or_else(|err| err.into_inner()) = overrides
+        .iter()
+        .map(|(key, path)| (key.clone(), PathBuf::from(path)))
+        .collect();
+    invalidate_tool_detection();
+}
+
+fn expand_home(raw: &str) -> Result<PathBuf> {
+    if raw == "~" || raw.starts_with("~/") {
+        let home = dirs::home_dir().context("failed to resolve home directory")?;
+        return Ok(home.join(raw.trim_start_matches('~').trim_start_matches('/')));
+    }
+    Ok(PathBuf::from(raw))
+}
+
 pub fn scan_tool_dir(tool: &ToolAdapter, dir: &Path) -> Result<Vec<DetectedSkill>> {
     let mut results = Vec::new();
     if !dir.exists() {
@@ -0,0 +1,97 @@
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+
+use super::tool_adapters::{
+    default_tool_adapters, is_tool_installed, resolve_default_path, ToolAdapter,
+};
+
+/// Detection results are reused for this long; the tool list is requested on
+/// startup and on every page switch.
+const DETECTION_TTL: Duration = Duration::from_secs(30);
+
+/// Whether one tool is installed and where its skills go.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DetectedTool {
+    pub key: String,
+    pub label: String,
+    pub installed: bool,
+    pub skills_dir: String,
+}
+
+#[derive(Default)]
+struct DetectionCache {
+    /// Bumped by [`invalidate_tool_detection`] so a probe that started
+    /// before an invalidation does not store its stale result.
+    generation: u64,
+    entry: Option<(Vec<DetectedTool>, Instant)>,
+}
+
+static CACHE: OnceLock<Mutex<DetectionCache>> = OnceLock::new();
+
+fn cache() -> MutexGuard<'static, DetectionCache> {
+    CACHE
+        .get_or_init(|| Mutex::new(DetectionCache::default()))
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+}
+
+/// Every adapter's install state, in adapter order. Probes run in parallel;
+/// the result is cached briefly unless `force` is set.
+pub fn detect_tools(force: bool) -> Result<Vec<DetectedTool>> {
+    let generation = {
+        let cache = cache();
+        if !force {
+            if let Some((tools, at)) = &cache.entry {
+                if at.elapsed() < DETECTION_TTL {
+                    return Ok(tools.clone());
+                }
+            }
+        }
+        cache.generation
+    };
+
+    let adapters = default_tool_adapters();
+    let probed: Vec<Result<DetectedTool>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = adapters
+            .iter()
+            .map(|adapter| scope.spawn(move || probe(adapter)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|_| Err(anyhow::anyhow!("tool detection panicked")))
+            })
+            .collect()
+    });
+    let tools = probed.into_iter().collect::<Result<Vec<_>>>()?;
+
+    let mut cache = cache();
+    if cache.generation == generation {
+        cache.entry = Some((tools.clone(), Instant::now()));
+    }
+    Ok(tools)
+}
+
+/// Drop cached results; called when adapters or their paths change.
+pub fn invalidate_tool_detection() {
+    let mut cache = cache();
+    cache.generation = cache.generation.wrapping_add(1);
+    cache.entry = None;
+}
+
+fn probe(adapter: &ToolAdapter) -> Result<DetectedTool> {
+    Ok(DetectedTool {
+        key: adapter.id.as_key().to_string(),
+        label: adapter.display_name.to_string(),
+        installed: is_tool_installed(adapter)?,
+        skills_dir: resolve_default_path(adapter)?.to_string_lossy().to_string(),
+    })
+}
+
+#[cfg(test)]
+#[path = "tests/tool_status.rs"]
+mod tests;
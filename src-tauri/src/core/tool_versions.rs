@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use super::tool_adapters::ToolAdapter;
+
+/// A detected version is reused for this long; tool status is polled often.
+const CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+const VERSION_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// CLI binary that answers `--version` for tools that ship one.
+fn version_binary(tool_key: &str) -> Option<&'static str> {
+    Some(match tool_key {
+        "claude_code" => "claude",
+        "codex" => "codex",
+        "opencode" => "opencode",
+        "gemini_cli" => "gemini",
+        "amp" => "amp",
+        "kimi_cli" => "kimi",
+        "goose" => "goose",
+        "qwen_code" => "qwen",
+        "crush" => "crush",
+        "droid" => "droid",
+        "iflow_cli" => "iflow",
+        "kiro_cli" => "kiro-cli",
+        "cursor" => "cursor",
+        "openclaw" => "openclaw",
+        "mistral_vibe" => "vibe",
+        _ => return None,
+    })
+}
+
+type VersionCache = HashMap<String, (Option<String>, Instant)>;
+
+static CACHE: OnceLock<Mutex<VersionCache>> = OnceLock::new();
+
+fn cache() -> std::sync::MutexGuard<'static, VersionCache> {
+    CACHE
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+}
+
+/// Forget detected versions so the next lookup runs the CLIs again.
+pub fn clear_version_cache() {
+    cache().clear();
+}
+
+/// Versions of `adapters` keyed by tool, `None` where the tool has no CLI or
+/// it could not be run. Lookups run in parallel and are cached.
+pub fn detect_tool_versions(adapters: &[&ToolAdapter]) -> HashMap<String, Option<String>> {
+    let mut versions = HashMap::new();
+    let mut pending = Vec::new();
+    {
+        let cache = cache();
+        for adapter in adapters {
+            let key = adapter.id.as_key();
+            match cache.get(key) {
+                Some((version, at)) if at.elapsed() < CACHE_TTL => {
+                    versions.insert(key.to_string(), version.clone());
+                }
+                _ => pending.push(key),
+            }
+        }
+    }
+
+    let detected: Vec<(String, Option<String>)> = std::thread::scope(|scope| {
+        let handles: Vec<_> = pending
+            .iter()
+            .map(|key| scope.spawn(move || (key.to_string(), detect_version(key))))
+            .collect();
+        handles.into_iter().filter_map(|h| h.join().ok()).collect()
+    });
+    let mut cache = cache();
+    for (key, version) in detected {
+        cache.insert(key.clone(), (version.clone(), Instant::now()));
+        versions.insert(key, version);
+    }
+    versions
+}
+
+fn detect_version(tool_key: &str) -> Option<String> {
+    let bin = find_binary(version_binary(tool_key)?)?;
+    let output = run_version(Command::new(bin).arg("--version"))?;
+    parse_version(&output)
+}
+
+/// Run with a timeout; a CLI waiting for input or a login must not hang the
+/// tool list. Returns stdout, or stderr when stdout is empty.
+fn run_version(cmd: &mut Command) -> Option<String> {
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .ok()?;
+    let start = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if start.elapsed() < VERSION_TIMEOUT => {
+                std::thread::sleep(Duration::from_millis(50));
+            }
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    }
+    let output = child.wait_with_output().ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    if stdout.trim().is_empty() {
+        Some(String::from_utf8_lossy(&output.stderr).to_string())
+    } else {
+        Some(stdout)
+    }
+}
+
+/// First dotted version number in `output`, e.g. `1.0.42` from
+/// `1.0.42 (Claude Code)` or `codex-cli 0.39.0`.
+pub fn parse_version(output: &str) -> Option<String> {
+    output
+        .split(|c: char| c.is_whitespace() || c == '(' || c == ')' || c == ',')
+        .map(|word| word.trim_start_matches('v'))
+        .find(|word| {
+            let mut parts = word.split('.');
+            let numeric = |p: Option<&str>| {
+                p.is_some_and(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()))
+            };
+            numeric(parts.next()) && numeric(parts.next())
+        })
+        .map(|word| {
+            // Keep pre-release suffixes (`1.2.0-beta.1`) but drop trailing dots.
+            word.trim_end_matches('.').to_string()
+        })
+}
+
+/// `name` on PATH or in the usual user install dirs, which GUI apps on
+/// macOS do not get on their PATH.
+fn find_binary(name: &str) -> Option<PathBuf> {
+    let mut dirs: Vec<PathBuf> = std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).collect())
+        .unwrap_or_default();
+    if let Some(home) = dirs::home_dir() {
+        for rel in [".local/bin", ".bun/bin", ".npm-global/bin", ".cargo/bin"] {
+            dirs.push(home.join(rel));
+        }
+    }
+    dirs.push(PathBuf::from("/opt/homebrew/bin"));
+    dirs.push(PathBuf::from("/usr/local/bin"));
+
+    let names: Vec<String> = if cfg!(windows) {
+        ["exe", "cmd", "bat"]
+            .iter()
+            .map(|ext| format!("{}.{}", name, ext))
+            .collect()
+    } else {
+        vec![name.to_string()]
+    };
+    dirs.iter()
+        .flat_map(|dir| names.iter().map(move |n| dir.join(n)))
+        .find(|candidate| candidate.is_file())
+}
+
+#[cfg(test)]
+#[path = "tests/tool_versions.rs"]
+mod tests;
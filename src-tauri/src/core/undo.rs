@@ -0,0 +1,266 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::skill_ignore::SkillIgnore;
+use super::skill_store::{SkillStore, SkillTargetRecord};
+use super::sync_engine::{copy_dir_filtered, sync_dir_hybrid};
+use super::temp_cleanup::{operation_temp_dir, OperationTempDir};
+
+/// How long replaced or removed targets stay recoverable.
+pub const UNDO_TTL: Duration = Duration::from_secs(5 * 60);
+const MAX_UNDO_OPS: usize = 20;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UndoKind {
+    Sync,
+    Unsync,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct UndoSummary {
+    pub id: String,
+    pub kind: UndoKind,
+    pub skill_id: String,
+    pub tools: Vec<String>,
+    pub target_path: PathBuf,
+    pub expires_in_secs: u64,
+}
+
+/// What used to live at a target path before an operation touched it.
+#[derive(Clone, Debug)]
+pub enum HeldContent {
+    /// Directory (or single-file resource) moved into the holding area.
+    Moved(PathBuf),
+    /// Symlink or junction; only its destination is kept.
+    Link(PathBuf),
+}
+
+struct UndoOp {
+    id: String,
+    kind: UndoKind,
+    skill_id: String,
+    tools: Vec<String>,
+    target_path: PathBuf,
+    held: Option<HeldContent>,
+    previous_rows: Vec<SkillTargetRecord>,
+    created: Instant,
+}
+
+/// Short-lived undo stack for local sync/unsync (managed Tauri state).
+/// Nothing survives a restart: the holding area is wiped on construction.
+#[derive(Clone)]
+pub struct UndoStack {
+    root: PathBuf,
+    ttl: Duration,
+    ops: Arc<Mutex<Vec<UndoOp>>>,
+    /// Owns the holding area when it is an operation temp dir, so it goes
+    /// away with the last clone and a crashed run's area gets reaped.
+    _scratch: Option<Arc<OperationTempDir>>,
+}
+
+impl UndoStack {
+    pub fn new(root: PathBuf) -> Self {
+        Self::with_ttl(root, UNDO_TTL)
+    }
+
+    pub fn with_ttl(root: PathBuf, ttl: Duration) -> Self {
+        let _ = std::fs::remove_dir_all(&root);
+        Self {
+            root,
+            ttl,
+            ops: Arc::new(Mutex::new(Vec::new())),
+            _scratch: None,
+        }
+    }
+
+    /// Stack whose holding area is a fresh operation temp dir.
+    pub fn in_temp_dir() -> Result<Self> {
+        let scratch = operation_temp_dir("undo")?;
+        Ok(Self {
+            root: scratch.path().to_path_buf(),
+            ttl: UNDO_TTL,
+            ops: Arc::new(Mutex::new(Vec::new())),
+            _scratch: Some(Arc::new(scratch)),
+        })
+    }
+
+    /// Holding area for stashed content; everything in it is only kept for undo.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Move whatever is at `target` out of the way so it can be restored.
+    pub fn stash(&self, target: &Path) -> Result<Option<HeldContent>> {
+        let meta = match std::fs::symlink_metadata(target) {
+            Ok(meta) => meta,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err).with_context(|| format!("stat {:?}", target)),
+        };
+        if let Ok(dest) = std::fs::read_link(target) {
+            remove_link(target)?;
+            return Ok(Some(HeldContent::Link(dest)));
+        }
+        if !meta.is_dir() && !meta.is_file() {
+            anyhow::bail!("cannot hold target {:?}", target);
+        }
+        std::fs::create_dir_all(&self.root)
+            .with_context(|| format!("create undo dir {:?}", self.root))?;
+        let held = self.root.join(Uuid::new_v4().to_string());
+        move_dir(target, &held)?;
+        Ok(Some(HeldContent::Moved(held)))
+    }
+
+    /// Put stashed content back, e.g. when the operation it made room for failed.
+    pub fn restore(&self, held: &HeldContent, target: &Path) -> Result<()> {
+        if std::fs::symlink_metadata(target).is_ok() {
+            anyhow::bail!("target already exists: {:?}", target);
+        }
+        match held {
+            HeldContent::Moved(dir) => move_dir(dir, target),
+            HeldContent::Link(dest) => sync_dir_hybrid(dest, target).map(|_| ()),
+        }
+    }
+
+    pub fn push(
+        &self,
+        kind: UndoKind,
+        skill_id: &str,
+        tools: Vec<String>,
+        target_path: &Path,
+        held: Option<HeldContent>,
+        previous_rows: Vec<SkillTargetRecord>,
+    ) {
+        let mut ops = self.lock();
+        self.prune(&mut ops);
+        ops.push(UndoOp {
+            id: Uuid::new_v4().to_string(),
+            kind,
+            skill_id: skill_id.to_string(),
+            tools,
+            target_path: target_path.to_path_buf(),
+            held,
+            previous_rows,
+            created: Instant::now(),
+        });
+        while ops.len() > MAX_UNDO_OPS {
+            let oldest = ops.remove(0);
+            discard(&oldest);
+        }
+    }
+
+    pub fn last(&self) -> Option<UndoSummary> {
+        let mut ops = self.lock();
+        self.prune(&mut ops);
+        ops.last().map(|op| self.summary(op))
+    }
+
+    /// Revert the most recent operation: its target and DB rows go back to
+    /// how they were before. Returns `None` when there is nothing to undo.
+    pub fn undo_last(&self, store: &SkillStore) -> Result<Option<UndoSummary>> {
+        let mut ops = self.lock();
+        self.prune(&mut ops);
+        let Some(op) = ops.pop() else {
+            return Ok(None);
+        };
+        let summary = self.summary(&op);
+
+        let result = (|| {
+            if op.kind == UndoKind::Sync {
+                remove_target(&op.target_path)?;
+            }
+            if let Some(held) = &op.held {
+                self.restore(held, &op.target_path)?;
+            }
+            for tool in &op.tools {
+                store.delete_skill_target(&op.skill_id, tool)?;
+            }
+            for row in &op.previous_rows {
+                store.upsert_skill_target(row)?;
+            }
+            Ok::<_, anyhow::Error>(())
+        })();
+        if let Err(err) = result {
+            // Keep it on the stack so the user can retry after fixing the cause.
+            ops.push(op);
+            return Err(err);
+        }
+        Ok(Some(summary))
+    }
+
+    fn summary(&self, op: &UndoOp) -> UndoSummary {
+        UndoSummary {
+            id: op.id.clone(),
+            kind: op.kind,
+            skill_id: op.skill_id.clone(),
+            tools: op.tools.clone(),
+            target_path: op.target_path.clone(),
+            expires_in_secs: self.ttl.saturating_sub(op.created.elapsed()).as_secs(),
+        }
+    }
+
+    fn prune(&self, ops: &mut Vec<UndoOp>) {
+        ops.retain(|op| {
+            let live = op.created.elapsed() < self.ttl;
+            if !live {
+                discard(op);
+            }
+            live
+        });
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<UndoOp>> {
+        self.ops.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+fn discard(op: &UndoOp) {
+    if let Some(HeldContent::Moved(held)) = &op.held {
+        let _ = remove_target(held);
+    }
+}
+
+fn remove_target(path: &Path) -> Result<()> {
+    match std::fs::symlink_metadata(path) {
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err).with_context(|| format!("stat {:?}", path)),
+        Ok(_) if std::fs::read_link(path).is_ok() => remove_link(path),
+        Ok(meta) if meta.is_dir() => {
+            std::fs::remove_dir_all(path).with_context(|| format!("remove dir {:?}", path))
+        }
+        Ok(_) => std::fs::remove_file(path).with_context(|| format!("remove file {:?}", path)),
+    }
+}
+
+fn remove_link(path: &Path) -> Result<()> {
+    // Junctions are removed as directories on Windows.
+    std::fs::remove_file(path)
+        .or_else(|_| std::fs::remove_dir(path))
+        .with_context(|| format!("remove link {:?}", path))
+}
+
+/// Rename, falling back to copy + delete when `to` is on another filesystem.
+/// Nothing is excluded from the copy; this is a move, not a sync.
+fn move_dir(from: &Path, to: &Path) -> Result<()> {
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent).with_context(|| format!("create dir {:?}", parent))?;
+    }
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    if from.is_file() {
+        std::fs::copy(from, to).with_context(|| format!("copy {:?} -> {:?}", from, to))?;
+        return std::fs::remove_file(from).with_context(|| format!("remove file {:?}", from));
+    }
+    copy_dir_filtered(from, to, &SkillIgnore::default())?;
+    std::fs::remove_dir_all(from).with_context(|| format!("remove dir {:?}", from))
+}
+
+#[cfg(test)]
+#[path = "tests/undo.rs"]
+mod tests;
@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::skill_store::SkillStore;
+
+pub const WATCH_SETTINGS_KEY: &str = "skill_watch_settings";
+
+pub const DEFAULT_DEBOUNCE_MS: u64 = 500;
+pub const DEFAULT_MAX_EVENTS_PER_MINUTE: u32 = 30;
+const MAX_DEBOUNCE_MS: u64 = 10 * 60 * 1000;
+
+/// How the file watcher treats one skill: which paths never trigger a
+/// re-sync, how long to wait for changes to settle and how many re-syncs a
+/// busy skill may cause per minute.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchSettings {
+    /// Globs relative to the skill root, e.g. `scratch/**` or `*.log`.
+    #[serde(default)]
+    pub exclude_globs: Vec<String>,
+    #[serde(default = "default_debounce_ms")]
+    pub debounce_ms: u64,
+    /// 0 means unlimited.
+    #[serde(default = "default_max_events_per_minute")]
+    pub max_events_per_minute: u32,
+}
+
+fn default_debounce_ms() -> u64 {
+    DEFAULT_DEBOUNCE_MS
+}
+
+fn default_max_events_per_minute() -> u32 {
+    DEFAULT_MAX_EVENTS_PER_MINUTE
+}
+
+impl Default for WatchSettings {
+    fn default() -> Self {
+        Self {
+            exclude_globs: Vec::new(),
+            debounce_ms: DEFAULT_DEBOUNCE_MS,
+            max_events_per_minute: DEFAULT_MAX_EVENTS_PER_MINUTE,
+        }
+    }
+}
+
+impl WatchSettings {
+    /// Whether a change at `relative_path` (under the skill root, `/`
+    /// separated) should be ignored.
+    pub fn is_excluded(&self, relative_path: &str) -> bool {
+        let path = relative_path.trim_start_matches("./");
+        self.exclude_globs
+            .iter()
+            .any(|glob| glob_matches(glob, path))
+    }
+}
+
+pub fn get_watch_settings(store: &SkillStore, skill_id: &str) -> WatchSettings {
+    read_map(store).remove(skill_id).unwrap_or_default()
+}
+
+/// Save settings for `skill_id`; `None` goes back to the defaults.
+pub fn set_watch_settings(
+    store: &SkillStore,
+    skill_id: &str,
+    settings: Option<WatchSettings>,
+) -> Result<WatchSettings> {
+    if store.get_skill_by_id(skill_id)?.is_none() {
+        anyhow::bail!("skill not found: {}", skill_id);
+    }
+    let mut map = read_map(store);
+    let saved = match settings {
+        Some(settings) => {
+            let settings = validate(settings)?;
+            map.insert(skill_id.to_string(), settings.clone());
+            settings
+        }
+        None => {
+            map.remove(skill_id);
+            WatchSettings::default()
+        }
+    };
+    // Drop settings of skills that no longer exist.
+    let skill_ids: Vec<String> = store.list_skills()?.into_iter().map(|s| s.id).collect();
+    map.retain(|id, _| skill_ids.contains(id));
+    let raw = serde_json::to_string(&map).context("serialize watch settings")?;
+    store.set_setting(WATCH_SETTINGS_KEY, &raw)?;
+    Ok(saved)
+}
+
+fn validate(mut settings: WatchSettings) -> Result<WatchSettings> {
+    if settings.debounce_ms > MAX_DEBOUNCE_MS {
+        anyhow::bail!("debounce must be at most {} ms", MAX_DEBOUNCE_MS);
+    }
+    let mut globs: Vec<String> = Vec::new();
+    for glob in settings.exclude_globs {
+        let glob = glob.trim().trim_start_matches("./").to_string();
+        if glob.is_empty() || globs.contains(&glob) {
+            continue;
+        }
+        if glob.starts_with('/') || glob.split('/').any(|part| part == "..") {
+            anyhow::bail!("exclude glob must stay inside the skill: {}", glob);
+        }
+        globs.push(glob);
+    }
+    settings.exclude_globs = globs;
+    Ok(settings)
+}
+
+/// Minimal glob matching: `*` and `?` stay within one path segment, `**`
+/// spans any number of segments. A glob without `/` matches at any depth.
+pub fn glob_matches(glob: &str, path: &str) -> bool {
+    let glob = glob.trim_end_matches('/');
+    let path_parts: Vec<&str> = path.split('/').filter(|p| !p.is_empty()).collect();
+    if !glob.contains('/') {
+        return path_parts.iter().any(|part| segment_matches(glob, part))
+            || (glob == "**" && !path_parts.is_empty());
+    }
+    let glob_parts: Vec<&str> = glob.split('/').filter(|p| !p.is_empty()).collect();
+    // A directory glob also covers everything inside it.
+    (0..=path_parts.len()).any(|end| parts_match(&glob_parts, &path_parts[..end]))
+}
+
+/// Match `/`-split glob parts against path parts; `**` spans any number.
+pub(crate) fn parts_match(glob: &[&str], path: &[&str]) -> bool {
+    match glob.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| parts_match(rest, &path[skip..])),
+        Some((first, rest)) => path
+            .split_first()
+            .is_some_and(|(part, tail)| segment_matches(first, part) && parts_match(rest, tail)),
+    }
+}
+
+/// `*` and `?` wildcards against a single path segment.
+pub(crate) fn segment_matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+fn read_map(store: &SkillStore) -> HashMap<String, WatchSettings> {
+    store
+        .get_setting(WATCH_SETTINGS_KEY)
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+#[path = "tests/watch_settings.rs"]
+mod tests;
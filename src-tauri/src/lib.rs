@@ -1,9 +1,41 @@
+pub mod cli;
 mod commands;
 mod core;
 
+use core::app_paths::AppPaths;
 use core::skill_store::{default_db_path, migrate_legacy_db_if_needed, SkillStore};
+use core::startup::StartupState;
 use tauri::Manager;
-use tauri_plugin_log::{Target, TargetKind};
+use tauri_plugin_log::{RotationStrategy, Target, TargetKind};
+
+/// Open the active profile's store, shared by the app and the CLI, and load
+/// the settings other modules keep in memory.
+fn open_store(paths: &AppPaths) -> anyhow::Result<SkillStore> {
+    let db_path = default_db_path(paths)?;
+    migrate_legacy_db_if_needed(&db_path)?;
+    let db_path = core::profiles::activate_profile(&db_path)?;
+    let store = SkillStore::new(db_path);
+    store.ensure_schema()?;
+    core::ssh_retry::load_retry_policy(&store);
+    core::http_client::load_http_settings(&store);
+    core::registry_auth::load_registry_auth(&store);
+    core::source_providers::load_source_providers(&store);
+    core::tool_adapters::load_tool_path_overrides(&store);
+    core::skill_format::load_tool_formats(&store);
+    core::sync_modes::load_sync_mode_prefs(&store);
+    core::app_logs::load_log_level(&store);
+    core::locale::load_locale(&store);
+    core::cloud_folders::load_cloud_force_copy(&store);
+    let path = paths
+        .config_dir
+        .join(core::tool_adapters::ADAPTER_CONFIG_FILE);
+    match core::tool_adapters::load_adapter_config(&path) {
+        Ok(0) => {}
+        Ok(n) => log::info!("loaded {} tool adapters from {:?}", n, path),
+        Err(err) => log::warn!("ignoring tool adapter config: {:#}", err),
+    }
+    Ok(store)
+}
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -13,93 +45,200 @@ pub fn run() {
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_process::init())
         .setup(|app| {
+            // Everything reaches the logger; the saved level is applied with
+            // `log::set_max_level` once the store is open.
+            let paths = AppPaths::for_app(app.handle()).map_err(tauri::Error::from)?;
+            let log_dir = core::app_logs::app_log_dir(&paths);
             app.handle().plugin(
                 tauri_plugin_log::Builder::default()
-                    .level(log::LevelFilter::Info)
+                    .level(log::LevelFilter::Trace)
+                    .max_file_size(core::app_logs::MAX_LOG_FILE_BYTES)
+                    .rotation_strategy(RotationStrategy::KeepSome(core::app_logs::KEEP_LOG_FILES))
                     .targets([
-                        Target::new(TargetKind::LogDir { file_name: None }),
+                        Target::new(TargetKind::Folder {
+                            path: log_dir,
+                            file_name: Some(core::app_logs::LOG_FILE_NAME.to_string()),
+                        }),
                         #[cfg(desktop)]
                         Target::new(TargetKind::Stdout),
                     ])
                     .build(),
             )?;
 
-            let db_path = default_db_path(app.handle()).map_err(tauri::Error::from)?;
-            migrate_legacy_db_if_needed(&db_path).map_err(tauri::Error::from)?;
-            let store = SkillStore::new(db_path);
-            store.ensure_schema().map_err(tauri::Error::from)?;
-            app.manage(store.clone());
+            // Register startup state first so the splash screen can poll it immediately.
+            app.manage(StartupState::default());
+            app.manage(core::undo::UndoStack::in_temp_dir().map_err(tauri::Error::from)?);
+            let operations = core::operations::OperationRegistry::for_app(app.handle().clone());
+            app.manage(core::jobs::JobQueue::new(operations.clone()));
+            app.manage(operations);
+            core::skill_events::attach(app.handle().clone());
 
-            // Best-effort cleanup of our own old git temp directories.
-            // Safety:
-            // - Only deletes directories that match prefix `skills-hub-git-*`
-            // - And contain our marker file `.skills-hub-git-temp`
-            // - And are older than the max age.
-            let handle = app.handle().clone();
-            let store_for_cleanup = store.clone();
-            tauri::async_runtime::spawn(async move {
-                let removed = core::temp_cleanup::cleanup_old_git_temp_dirs(
-                    &handle,
-                    std::time::Duration::from_secs(24 * 60 * 60),
-                )
-                .unwrap_or(0);
-                if removed > 0 {
-                    log::info!("cleaned up {} old git temp dirs", removed);
-                }
+            let store = open_store(&paths).map_err(tauri::Error::from)?;
+            app.manage(paths);
+            app.manage(store.clone());
+            app.state::<StartupState>().mark_store_ready();
 
-                let cleanup_days =
-                    core::cache_cleanup::get_git_cache_cleanup_days(&store_for_cleanup);
-                if cleanup_days > 0 {
-                    let max_age =
-                        std::time::Duration::from_secs(cleanup_days as u64 * 24 * 60 * 60);
-                    let removed =
-                        core::cache_cleanup::cleanup_git_cache_dirs(&handle, max_age).unwrap_or(0);
-                    if removed > 0 {
-                        log::info!("cleaned up {} git cache dirs", removed);
-                    }
-                }
-            });
+            // Tool detection and cache cleanup do not block the first window.
+            core::startup::spawn_deferred_startup(app.handle().clone(), store.clone());
+            core::remote_schedule::spawn_remote_scheduler(app.handle().clone(), store.clone());
+            core::project_scan::spawn_project_scanner(app.handle().clone(), store.clone());
+            core::skill_watcher::spawn_skill_watcher(app.handle().clone(), store.clone());
+            core::skill_freshness::spawn_freshness_checker(app.handle().clone(), store);
 
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             commands::get_central_repo_path,
+            commands::plan_central_repo_migration,
             commands::set_central_repo_path,
+            commands::get_central_repo_cloud_status,
+            commands::set_cloud_force_copy,
+            commands::get_shared_central_repo,
+            commands::use_shared_central_repo,
+            commands::refresh_shared_central_repo,
+            commands::leave_shared_central_repo,
             commands::get_tool_status,
+            commands::get_tool_path_overrides,
+            commands::set_tool_path_override,
+            commands::get_tool_formats,
+            commands::set_tool_format,
+            commands::get_sync_mode_prefs,
+            commands::set_tool_sync_mode,
+            commands::set_skill_sync_mode,
+            commands::list_resource_tools,
+            commands::list_mcp_servers,
+            commands::save_mcp_server,
+            commands::delete_mcp_server,
+            commands::list_mcp_tools,
+            commands::sync_mcp_servers,
+            commands::get_skill_history,
+            commands::restore_skill_revision,
+            commands::get_central_remote,
+            commands::set_central_remote,
+            commands::push_central_repo,
+            commands::pull_central_repo,
+            commands::get_storage_report,
+            commands::verify_central_repo,
+            commands::verify_all_targets,
+            commands::list_profiles,
+            commands::create_profile,
+            commands::switch_profile,
+            commands::delete_profile,
             commands::get_git_cache_cleanup_days,
             commands::get_git_cache_ttl_secs,
             commands::set_git_cache_cleanup_days,
             commands::set_git_cache_ttl_secs,
+            commands::get_git_cache_max_mb,
+            commands::set_git_cache_max_mb,
             commands::clear_git_cache_now,
+            commands::get_git_cache_report,
+            commands::evict_git_cache_entry,
+            commands::get_remote_retry_policy,
+            commands::get_log_level,
+            commands::set_log_level,
+            commands::get_recent_logs,
+            commands::get_locale,
+            commands::set_locale,
+            commands::set_remote_retry_policy,
+            commands::get_http_settings,
+            commands::set_http_settings,
+            commands::get_remote_sync_schedule,
+            commands::set_remote_sync_schedule,
+            commands::get_last_scheduled_sync,
+            commands::run_scheduled_sync_now,
+            commands::get_target_name_template,
+            commands::set_target_name_template,
+            commands::reapply_target_naming,
             commands::get_onboarding_plan,
             commands::install_local,
+            commands::list_skill_templates,
+            commands::create_skill,
+            commands::duplicate_skill,
             commands::list_local_skills_cmd,
             commands::install_local_selection,
             commands::install_git,
+            commands::get_source_trust_policy,
+            commands::set_source_trust_policy,
             commands::list_git_skills_cmd,
             commands::install_git_selection,
             commands::sync_skill_dir,
             commands::sync_skill_to_tool,
+            commands::sync_skill_to_tools,
+            commands::sync_all_skills_to_tool,
+            commands::plan_skill_sync,
+            commands::apply_skill_sync_plan,
             commands::unsync_skill_from_tool,
+            commands::sync_skill_to_project,
+            commands::unsync_skill_from_project,
+            commands::list_project_targets,
+            commands::cleanup_project_targets,
+            commands::get_last_undoable_operation,
+            commands::undo_last_operation,
+            commands::list_operations,
+            commands::get_operation,
+            commands::list_jobs,
+            commands::get_job,
+            commands::queue_install_git,
+            commands::queue_update_skills,
+            commands::queue_remote_sync,
             commands::update_managed_skill,
+            commands::reinstall_skill,
             commands::check_skill_updates,
+            commands::list_skill_freshness,
+            commands::refresh_skill_freshness,
             commands::search_github,
+            commands::list_github_repos,
+            commands::list_source_providers,
+            commands::save_source_provider,
+            commands::delete_source_provider,
+            commands::has_github_token,
+            commands::set_github_token,
+            commands::get_github_account,
+            commands::set_github_account,
+            commands::list_github_star_suggestions,
             commands::import_existing_skill,
+            commands::scan_existing_skills,
+            commands::import_existing_skills,
+            commands::detect_symlink_farms,
+            commands::adopt_symlink_farm_skills,
             commands::get_managed_skills,
             commands::delete_managed_skill,
+            commands::list_registry_auth,
+            commands::set_registry_auth,
+            commands::start_registry_device_login,
+            commands::poll_registry_device_login,
             commands::search_clawhub,
+            commands::search_clawhub_page,
+            commands::browse_clawhub,
             commands::get_clawhub_skill_cmd,
+            commands::get_clawhub_skill_readme,
             commands::get_github_tree_cmd,
+            commands::get_github_file,
             commands::install_clawhub_skill,
+            commands::has_clawhub_token,
+            commands::set_clawhub_token,
+            commands::publish_clawhub_skill,
+            commands::export_skill_package,
+            commands::inspect_skill_package,
+            commands::create_backup,
+            commands::verify_backup,
+            commands::install_skill_package,
+            commands::attach_skill_package_signature,
+            commands::get_skillpkg_trusted_keys,
+            commands::set_skillpkg_trusted_keys,
             commands::list_remote_hosts,
             commands::add_remote_host,
             commands::update_remote_host,
+            commands::update_remote_host_group,
             commands::delete_remote_host,
             commands::test_remote_connection,
+            commands::check_remote_host_health,
             commands::get_remote_tool_status,
+            commands::sync_skills_to_hosts,
             commands::sync_all_skills_to_remote,
             commands::sync_remote_skill_to_tool,
             commands::unsync_remote_skill_from_tool,
+            commands::remove_remote_skill,
             commands::list_remote_skills,
             commands::sync_selected_skills_to_remote,
             commands::list_custom_targets,
@@ -107,9 +246,40 @@ pub fn run() {
             commands::delete_custom_target,
             commands::sync_skill_to_custom_target,
             commands::unsync_skill_from_custom_target,
+            commands::list_recent_projects,
+            commands::forget_recent_project,
+            commands::get_workspace_roots,
+            commands::set_workspace_roots,
+            commands::scan_projects,
+            commands::list_detected_projects,
             commands::browse_remote_directory,
             commands::read_skill_content,
+            commands::list_skill_files,
+            commands::read_skill_file,
+            commands::write_skill_content,
+            commands::validate_skill,
+            commands::detect_skill_drift,
+            commands::get_skills_health,
+            commands::get_skill_dependencies,
+            commands::get_skill_dependents,
+            commands::install_skill_dependencies,
+            commands::read_remote_skill_content,
             commands::update_skill_group,
+            commands::set_skill_propagate_to_remotes,
+            commands::set_skill_locked,
+            commands::approve_skill,
+            commands::get_quarantine_new_installs,
+            commands::set_quarantine_new_installs,
+            commands::get_skill_watch_settings,
+            commands::set_skill_watch_settings,
+            commands::find_orphan_targets,
+            commands::remove_dangling_links,
+            commands::forget_missing_targets,
+            commands::list_overwritten_targets,
+            commands::restore_overwritten_target,
+            commands::delete_overwritten_target,
+            commands::get_sync_topology,
+            commands::get_startup_status,
             commands::is_homebrew_installed,
             commands::brew_upgrade_cask
         ])
@@ -21,13 +21,19 @@ import SettingsModal from './components/skills/modals/SettingsModal'
 import SkillDetailModal from './components/skills/modals/SkillDetailModal'
 import type {
   ClawHubSkill,
+  CommandErrorData,
   CustomTarget,
+  ErrorCode,
   GitSkillCandidate,
   InstallResultDto,
   LocalSkillCandidate,
   ManagedSkill,
+  DependencyInstallResult,
   OnboardingPlan,
+  SkillDependency,
+  SkillDependent,
   SkillUpdateStatus,
+  TargetConflict,
   ToolOption,
   ToolStatusDto,
   UpdateResultDto,
@@ -37,6 +43,35 @@ import type {
   RemoteSkillsDto,
 } from './components/skills/types'
 
+// A rejected command, keeping the backend's code and payload so callers
+// can branch on them.
+class CommandError extends Error {
+  code: ErrorCode
+  payload: Record<string, unknown>
+
+  constructor(data: CommandErrorData) {
+    super(data.message)
+    this.name = 'CommandError'
+    this.code = data.code
+    this.payload = data.payload ?? {}
+  }
+}
+
+const isCommandErrorData = (value: unknown): value is CommandErrorData =>
+  typeof value === 'object' &&
+  value !== null &&
+  'code' in value &&
+  'message' in value
+
+const hasErrorCode = (err: unknown, code: ErrorCode): err is CommandError =>
+  err instanceof CommandError && err.code === code
+
+// A TARGET_EXISTS target that already holds this skill's content.
+const targetAlreadySynced = (err: CommandError) => {
+  const conflict = err.payload as Partial<TargetConflict>
+  return conflict.points_to_source === true || conflict.content_matches === true
+}
+
 function App() {
   const { t, i18n } = useTranslation()
   const language = i18n.resolvedLanguage ?? i18n.language ?? 'en'
@@ -66,6 +101,9 @@ function App() {
   const [gitUrl, setGitUrl] = useState('')
   const [gitName, setGitName] = useState('')
   const [pendingDeleteId, setPendingDeleteId] = useState<string | null>(null)
+  const [pendingDeleteDependents, setPendingDeleteDependents] = useState<
+    string[]
+  >([])
   const [isEditMode, setIsEditMode] = useState(false)
   const [selectedSkillIds, setSelectedSkillIds] = useState<Set<string>>(new Set())
   const [gitCandidates, setGitCandidates] = useState<GitSkillCandidate[]>([])
@@ -128,21 +166,53 @@ function App() {
         throw new Error(t('errors.notTauri'))
       }
       const { invoke } = await import('@tauri-apps/api/core')
-      return invoke<T>(command, args)
+      try {
+        return await invoke<T>(command, args)
+      } catch (err) {
+        throw isCommandErrorData(err) ? new CommandError(err) : err
+      }
     },
     [isTauri, t],
   )
+  // Installs from sources outside the allowlist go ahead once the user
+  // agrees; flagged content is pointed out before the skill is synced.
+  const invokeInstall = useCallback(
+    async (command: string, args: Record<string, unknown>) => {
+      let result: InstallResultDto
+      try {
+        result = await invokeTauri<InstallResultDto>(command, args)
+      } catch (err) {
+        if (!hasErrorCode(err, 'UNTRUSTED_SOURCE')) throw err
+        const owner = String(err.payload.owner ?? err.payload.source ?? '')
+        if (!window.confirm(t('untrustedSourceConfirm', { owner }))) throw err
+        result = await invokeTauri<InstallResultDto>(command, {
+          ...args,
+          confirmUntrusted: true,
+        })
+      }
+      if (result.content_warnings.length > 0) {
+        toast.warning(
+          t('contentWarningsFound', {
+            name: result.name,
+            count: result.content_warnings.length,
+          }),
+          {
+            description: result.content_warnings
+              .slice(0, 3)
+              .map((f) => `${f.file}:${f.line} ${f.excerpt}`)
+              .join('\n'),
+          },
+        )
+      }
+      return result
+    },
+    [invokeTauri, t],
+  )
   const formatErrorMessage = useCallback(
     (raw: string) => {
       if (raw.includes('skill already exists in central repo')) {
         return t('errors.skillExistsInHub')
       }
-      if (raw.startsWith('TARGET_EXISTS|')) {
-        return t('errors.targetExists')
-      }
-      if (raw.startsWith('TOOL_NOT_INSTALLED|')) {
-        return t('errors.toolNotInstalled')
-      }
       if (raw.includes('未在该仓库中发现可导入的 Skills')) {
         return t('errors.noSkillsFoundInRepo')
       }
@@ -259,6 +329,36 @@ function App() {
     }
   }, [invokeTauri])
 
+  // Offer to install what a newly installed skill declares but is missing.
+  const offerDependencyInstall = useCallback(
+    async (skillId: string) => {
+      const deps = await invokeTauri<SkillDependency[]>(
+        'get_skill_dependencies',
+        { skillId },
+      )
+      const missing = deps
+        .filter((dep) => dep.source && !dep.installed_skill_id)
+        .map((dep) => dep.spec)
+      if (missing.length === 0) return
+      const ok = window.confirm(
+        t('dependencies.installConfirm', { specs: missing.join('\n') }),
+      )
+      if (!ok) return
+      const result = await invokeTauri<DependencyInstallResult>(
+        'install_skill_dependencies',
+        { skillId, specs: missing },
+      )
+      await loadManagedSkills()
+      showActionErrors(
+        result.failed.map((failure) => ({
+          title: t('dependencies.installFailed', { spec: failure.spec }),
+          message: failure.error,
+        })),
+      )
+    },
+    [invokeTauri, loadManagedSkills, showActionErrors, t],
+  )
+
   const checkForSkillUpdates = useCallback(async () => {
     try {
       const statuses = await invokeTauri<SkillUpdateStatus[]>('check_skill_updates')
@@ -278,6 +378,43 @@ function App() {
     }
   }, [isTauri, loadManagedSkills])
 
+  // Other windows and background work report skill changes as events, so
+  // the list stays current without polling.
+  useEffect(() => {
+    if (!isTauri) return
+    const events = [
+      'skill:installed',
+      'skill:updated',
+      'skill:synced',
+      'skill:deleted',
+      'target:broken',
+      'remote:sync-finished',
+    ]
+    let disposed = false
+    let timer: ReturnType<typeof setTimeout> | undefined
+    const unlisteners: Array<() => void> = []
+    const refresh = () => {
+      clearTimeout(timer)
+      timer = setTimeout(() => void loadManagedSkills(), 200)
+    }
+    void (async () => {
+      const { listen } = await import('@tauri-apps/api/event')
+      for (const event of events) {
+        const unlisten = await listen(event, refresh)
+        if (disposed) {
+          unlisten()
+        } else {
+          unlisteners.push(unlisten)
+        }
+      }
+    })()
+    return () => {
+      disposed = true
+      clearTimeout(timer)
+      unlisteners.forEach((unlisten) => unlisten())
+    }
+  }, [isTauri, loadManagedSkills])
+
   const loadCustomTargets = useCallback(async () => {
     if (!isTauri) return
     try {
@@ -319,6 +456,14 @@ function App() {
     }
   }, [language, languageStorageKey])
 
+  // Backend error hints follow the UI language.
+  useEffect(() => {
+    if (!isTauri || !['en', 'zh-CN', 'zh-TW'].includes(language)) return
+    invokeTauri('set_locale', { locale: language }).catch((err) => {
+      console.warn('Failed to sync locale to backend:', err)
+    })
+  }, [isTauri, invokeTauri, language])
+
   useEffect(() => {
     if (typeof window === 'undefined') return
     const media = window.matchMedia('(prefers-color-scheme: dark)')
@@ -380,6 +525,15 @@ function App() {
       })
   }, [isTauri, invokeTauri])
 
+  useEffect(() => {
+    if (!isTauri) return
+    invokeTauri<number>('get_git_cache_max_mb')
+      .then((mb) => setGitCacheMaxMb(mb))
+      .catch((err) => {
+        setError(err instanceof Error ? err.message : String(err))
+      })
+  }, [isTauri, invokeTauri])
+
   useEffect(() => {
     if (isTauri) {
       void loadPlan()
@@ -514,6 +668,7 @@ function App() {
   const [storagePath, setStoragePath] = useState<string>(t('notAvailable'))
   const [gitCacheCleanupDays, setGitCacheCleanupDays] = useState<number>(30)
   const [gitCacheTtlSecs, setGitCacheTtlSecs] = useState<number>(60)
+  const [gitCacheMaxMb, setGitCacheMaxMb] = useState<number>(2048)
   const handlePickStoragePath = useCallback(async () => {
     try {
       if (!isTauri) {
@@ -567,6 +722,22 @@ function App() {
     },
     [invokeTauri, isTauri],
   )
+  const handleGitCacheMaxMbChange = useCallback(
+    async (nextMb: number) => {
+      const normalized = Math.max(0, Math.round(nextMb))
+      setGitCacheMaxMb(normalized)
+      if (!isTauri) return
+      try {
+        const updated = await invokeTauri<number>('set_git_cache_max_mb', {
+          mb: normalized,
+        })
+        setGitCacheMaxMb(updated)
+      } catch (err) {
+        setError(err instanceof Error ? err.message : String(err))
+      }
+    },
+    [invokeTauri, isTauri],
+  )
   const handleClearGitCacheNow = useCallback(async () => {
     if (!isTauri) {
       setError(t('errors.notTauri'))
@@ -600,6 +771,23 @@ function App() {
     () => managedSkills.find((skill) => skill.id === pendingDeleteId) ?? null,
     [managedSkills, pendingDeleteId],
   )
+  useEffect(() => {
+    setPendingDeleteDependents([])
+    if (!pendingDeleteId || !isTauri) return
+    let cancelled = false
+    invokeTauri<SkillDependent[]>('get_skill_dependents', {
+      skillId: pendingDeleteId,
+    })
+      .then((dependents) => {
+        if (!cancelled) {
+          setPendingDeleteDependents(dependents.map((d) => d.name))
+        }
+      })
+      .catch(() => {})
+    return () => {
+      cancelled = true
+    }
+  }, [invokeTauri, isTauri, pendingDeleteId])
   const newlyInstalledToolsText = useMemo(() => {
     if (!toolStatus || toolStatus.newly_installed.length === 0) return ''
     return toolStatus.newly_installed
@@ -1030,7 +1218,8 @@ function App() {
 
   const handleBatchUpdateSkills = useCallback(
     async (skillIds: string[]) => {
-      const skills = managedSkills.filter((s) => skillIds.includes(s.id))
+      // Locked skills keep their tuned content; they are only updated one at a time.
+      const skills = managedSkills.filter((s) => skillIds.includes(s.id) && !s.locked)
       if (skills.length === 0) return
       setLoading(true)
       setLoadingStartAt(Date.now())
@@ -1216,16 +1405,18 @@ function App() {
             })
           } catch (err) {
             const raw = err instanceof Error ? err.message : String(err)
-            if (raw.startsWith('TARGET_EXISTS|')) {
-              const targetPath = raw.split('|')[1] ?? ''
+            if (hasErrorCode(err, 'TARGET_EXISTS')) {
+              const targetPath = String(err.payload.path ?? '')
               collectedErrors.push({
                 title: t('errors.syncFailedTitle', {
                   name: group.name,
                   tool: tool.label,
                 }),
-                message: t('errors.syncTargetExistsMessage', {
-                  path: targetPath,
-                }),
+                message: targetAlreadySynced(err)
+                  ? t('errors.targetAlreadySynced', { path: targetPath })
+                  : t('errors.syncTargetExistsMessage', {
+                      path: targetPath,
+                    }),
               })
             } else {
               collectedErrors.push({
@@ -1281,10 +1472,11 @@ function App() {
       setLoading(true)
       setLoadingStartAt(Date.now())
       try {
-        const installResult = await invokeTauri<InstallResultDto>(
+        const installResult = await invokeInstall(
           'install_clawhub_skill',
           { slug, version, name: null },
         )
+        await offerDependencyInstall(installResult.skill_id)
 
         // Sync to selected tools
         const selectedInstalledIds = tools
@@ -1332,9 +1524,11 @@ function App() {
       }
     },
     [
+      invokeInstall,
       invokeTauri,
       isInstalled,
       loadManagedSkills,
+      offerDependencyInstall,
       showActionErrors,
       syncTargets,
       t,
@@ -1458,10 +1652,11 @@ function App() {
       const isFolderUrl = url.includes('/tree/') || url.includes('/blob/')
 
       if (isFolderUrl) {
-        const created = await invokeTauri<InstallResultDto>('install_git', {
+        const created = await invokeInstall('install_git', {
           repoUrl: url,
           name: gitName.trim() || undefined,
         })
+        await offerDependencyInstall(created.skill_id)
         {
           const selectedInstalledIds = tools
             .filter((tool) => syncTargets[tool.id] && isInstalled(tool.id))
@@ -1517,7 +1712,7 @@ function App() {
             setError(t('errors.skillAlreadyExists', { name: candidates[0].name }))
             return
           }
-          const created = await invokeTauri<InstallResultDto>(
+          const created = await invokeInstall(
             'install_git_selection',
             {
               repoUrl: url,
@@ -1757,7 +1952,7 @@ function App() {
           }),
         )
         try {
-          const created = await invokeTauri<InstallResultDto>(
+          const created = await invokeInstall(
             'install_git_selection',
             {
               repoUrl: gitCandidatesRepoUrl,
@@ -1896,7 +2091,7 @@ function App() {
               })
             } catch (err) {
               const raw = err instanceof Error ? err.message : String(err)
-              if (raw.startsWith('TOOL_NOT_INSTALLED|')) continue
+              if (hasErrorCode(err, 'TOOL_NOT_INSTALLED')) continue
               collectedErrors.push({
                 title: t('errors.syncFailedTitle', {
                   name: skill.name,
@@ -1981,10 +2176,14 @@ function App() {
         await loadManagedSkills()
       } catch (err) {
         const raw = err instanceof Error ? err.message : String(err)
-        if (raw.startsWith('TARGET_EXISTS|')) {
-          const targetPath = raw.split('|')[1] ?? ''
-          setError(t('errors.targetExistsDetail', { path: targetPath }))
-        } else if (raw.startsWith('TOOL_NOT_INSTALLED|')) {
+        if (hasErrorCode(err, 'TARGET_EXISTS')) {
+          const targetPath = String(err.payload.path ?? '')
+          setError(
+            targetAlreadySynced(err)
+              ? t('errors.targetAlreadySynced', { path: targetPath })
+              : t('errors.targetExistsDetail', { path: targetPath }),
+          )
+        } else if (hasErrorCode(err, 'TOOL_NOT_INSTALLED')) {
           // Tool disappeared between detection and click; silently refresh.
           setError(t('errors.toolNotInstalled'))
         } else {
@@ -2038,9 +2237,13 @@ function App() {
         await loadManagedSkills()
       } catch (err) {
         const raw = err instanceof Error ? err.message : String(err)
-        if (raw.startsWith('TARGET_EXISTS|')) {
-          const targetPath = raw.split('|')[1] ?? ''
-          setError(t('errors.targetExistsDetail', { path: targetPath }))
+        if (hasErrorCode(err, 'TARGET_EXISTS')) {
+          const targetPath = String(err.payload.path ?? '')
+          setError(
+            targetAlreadySynced(err)
+              ? t('errors.targetAlreadySynced', { path: targetPath })
+              : t('errors.targetExistsDetail', { path: targetPath }),
+          )
         } else {
           setError(raw)
         }
@@ -2071,8 +2274,14 @@ function App() {
       setLoadingStartAt(Date.now())
       setError(null)
       try {
+        if (skill.locked && !window.confirm(t('lockedUpdateConfirm', { name: skill.name }))) {
+          return
+        }
         setActionMessage(t('actions.updating', { name: skill.name }))
-        await invokeTauri<UpdateResultDto>('update_managed_skill', { skillId: skill.id })
+        await invokeTauri<UpdateResultDto>('update_managed_skill', {
+          skillId: skill.id,
+          force: skill.locked ?? false,
+        })
         // Clear update badge for this skill immediately
         setUpdateStatuses((prev) => {
           const next = { ...prev }
@@ -2102,6 +2311,53 @@ function App() {
     [handleUpdateManaged],
   )
 
+  const handleDuplicateSkill = useCallback(
+    async (skill: ManagedSkill) => {
+      const newName = window
+        .prompt(t('duplicate.prompt', { name: skill.name }), `${skill.name}-custom`)
+        ?.trim()
+      if (!newName) return
+      setLoading(true)
+      setLoadingStartAt(Date.now())
+      setError(null)
+      try {
+        await invokeTauri<InstallResultDto>('duplicate_skill', {
+          skillId: skill.id,
+          newName,
+        })
+        await loadManagedSkills()
+        setSelectedSkill(null)
+        setSuccessToastMessage(t('duplicate.done', { name: newName }))
+      } catch (err) {
+        setError(err instanceof Error ? err.message : String(err))
+      } finally {
+        setLoading(false)
+        setLoadingStartAt(null)
+      }
+    },
+    [invokeTauri, loadManagedSkills, t],
+  )
+
+  const handleApproveSkill = useCallback(
+    async (skill: ManagedSkill) => {
+      setLoading(true)
+      setLoadingStartAt(Date.now())
+      setError(null)
+      try {
+        await invokeTauri('approve_skill', { skillId: skill.id })
+        setSelectedSkill((prev) => (prev?.id === skill.id ? { ...prev, status: 'ok' } : prev))
+        await loadManagedSkills()
+        setSuccessToastMessage(t('quarantine.approved', { name: skill.name }))
+      } catch (err) {
+        setError(err instanceof Error ? err.message : String(err))
+      } finally {
+        setLoading(false)
+        setLoadingStartAt(null)
+      }
+    },
+    [invokeTauri, loadManagedSkills, t],
+  )
+
   const handleSharedCancel = useCallback(() => {
     if (loading) return
     setPendingSharedToggle(null)
@@ -2267,12 +2523,14 @@ function App() {
         storagePath={storagePath}
         gitCacheCleanupDays={gitCacheCleanupDays}
         gitCacheTtlSecs={gitCacheTtlSecs}
+        gitCacheMaxMb={gitCacheMaxMb}
         themePreference={themePreference}
         onPickStoragePath={handlePickStoragePath}
         onChangeLanguage={changeLanguage}
         onThemeChange={handleThemeChange}
         onGitCacheCleanupDaysChange={handleGitCacheCleanupDaysChange}
         onGitCacheTtlSecsChange={handleGitCacheTtlSecsChange}
+        onGitCacheMaxMbChange={handleGitCacheMaxMbChange}
         onClearGitCacheNow={handleClearGitCacheNow}
         onOpenRemoteHosts={handleOpenRemoteHosts}
         onRequestClose={handleCloseSettings}
@@ -2315,6 +2573,7 @@ function App() {
         open={Boolean(pendingDeleteId)}
         loading={loading}
         skillName={pendingDeleteSkill?.name ?? null}
+        dependents={pendingDeleteDependents}
         onRequestClose={handleCloseDelete}
         onConfirm={() => {
           if (pendingDeleteSkill) void handleDeleteManaged(pendingDeleteSkill)
@@ -2354,6 +2613,8 @@ function App() {
         loading={loading}
         formatRelative={formatRelative}
         onUpdate={handleUpdateSkill}
+        onDuplicate={handleDuplicateSkill}
+        onApprove={handleApproveSkill}
         onDelete={handleDeletePrompt}
         onToggleTool={handleToggleToolForSkill}
         onRequestClose={() => setSelectedSkill(null)}
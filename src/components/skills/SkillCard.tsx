@@ -97,11 +97,24 @@ const SkillCard = ({
         <div className="skill-header-row">
           <div className="skill-name">
             {skill.name}
+            {skill.metadata?.version ? (
+              <span className="skill-version">v{skill.metadata.version}</span>
+            ) : null}
             {hasUpdate ? (
               <span className="update-dot" title={t('updateAvailable')} />
             ) : null}
+            {skill.status === 'quarantined' ? (
+              <span className="skill-quarantined" title={t('quarantine.hint')}>
+                {t('quarantine.badge')}
+              </span>
+            ) : null}
           </div>
         </div>
+        {skill.metadata?.description ? (
+          <div className="skill-description" title={skill.metadata.description}>
+            {skill.metadata.description}
+          </div>
+        ) : null}
 
         <div className="skill-meta-row">
           {github ? (
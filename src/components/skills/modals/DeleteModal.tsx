@@ -6,6 +6,7 @@ type DeleteModalProps = {
   open: boolean
   loading: boolean
   skillName: string | null
+  dependents: string[]
   onRequestClose: () => void
   onConfirm: () => void
   t: TFunction
@@ -15,6 +16,7 @@ const DeleteModal = ({
   open,
   loading,
   skillName,
+  dependents,
   onRequestClose,
   onConfirm,
   t,
@@ -49,6 +51,13 @@ const DeleteModal = ({
             <ul>
               <li>{t('delete.warningRemoveFromTools')}</li>
               <li>{t('delete.warningDeleteFromHub')}</li>
+              {dependents.length > 0 ? (
+                <li>
+                  {t('delete.warningDependents', {
+                    names: dependents.join(', '),
+                  })}
+                </li>
+              ) : null}
             </ul>
           </div>
         </div>
@@ -2,7 +2,7 @@ import { memo, useCallback, useEffect, useMemo, useState } from 'react'
 import { ChevronRight, ExternalLink, FolderOpen, FolderPlus, Github, Globe, Monitor, RefreshCw, Trash2 } from 'lucide-react'
 import { toast } from 'sonner'
 import type { TFunction } from 'i18next'
-import type { CustomTarget, RemoteHost, ToolOption } from '../types'
+import type { CloudFolderStatus, CustomTarget, HttpSettings, RemoteHost, ToolOption } from '../types'
 
 type SettingsModalProps = {
   open: boolean
@@ -11,12 +11,14 @@ type SettingsModalProps = {
   storagePath: string
   gitCacheCleanupDays: number
   gitCacheTtlSecs: number
+  gitCacheMaxMb: number
   themePreference: 'system' | 'light' | 'dark'
   onPickStoragePath: () => void
   onChangeLanguage: (lang: string) => void
   onThemeChange: (nextTheme: 'system' | 'light' | 'dark') => void
   onGitCacheCleanupDaysChange: (nextDays: number) => void
   onGitCacheTtlSecsChange: (nextSecs: number) => void
+  onGitCacheMaxMbChange: (nextMb: number) => void
   onClearGitCacheNow: () => void
   onOpenRemoteHosts: () => void
   onRequestClose: () => void
@@ -37,11 +39,13 @@ const SettingsModal = ({
   storagePath,
   gitCacheCleanupDays,
   gitCacheTtlSecs,
+  gitCacheMaxMb,
   themePreference,
   onPickStoragePath,
   onThemeChange,
   onGitCacheCleanupDaysChange,
   onGitCacheTtlSecsChange,
+  onGitCacheMaxMbChange,
   onClearGitCacheNow,
   onChangeLanguage,
   onOpenRemoteHosts,
@@ -94,6 +98,73 @@ const SettingsModal = ({
     void loadAppVersion()
   }, [loadAppVersion, open])
 
+  const [cloudStatus, setCloudStatus] = useState<CloudFolderStatus | null>(null)
+
+  useEffect(() => {
+    if (!open || !isTauri) {
+      setCloudStatus(null)
+      return
+    }
+    invokeTauri<CloudFolderStatus>('get_central_repo_cloud_status')
+      .then(setCloudStatus)
+      .catch(() => setCloudStatus(null))
+  }, [invokeTauri, isTauri, open, storagePath])
+
+  const [quarantineNew, setQuarantineNew] = useState(false)
+
+  useEffect(() => {
+    if (!open || !isTauri) return
+    invokeTauri<boolean>('get_quarantine_new_installs')
+      .then(setQuarantineNew)
+      .catch(() => setQuarantineNew(false))
+  }, [invokeTauri, isTauri, open])
+
+  const handleQuarantineNew = useCallback(
+    async (enabled: boolean) => {
+      try {
+        setQuarantineNew(await invokeTauri<boolean>('set_quarantine_new_installs', { enabled }))
+      } catch (err) {
+        toast.error(err instanceof Error ? err.message : String(err))
+      }
+    },
+    [invokeTauri],
+  )
+
+  const [httpSettings, setHttpSettings] = useState<HttpSettings | null>(null)
+  const [savingHttp, setSavingHttp] = useState(false)
+
+  useEffect(() => {
+    if (!open || !isTauri) return
+    invokeTauri<HttpSettings>('get_http_settings')
+      .then(setHttpSettings)
+      .catch(() => setHttpSettings(null))
+  }, [invokeTauri, isTauri, open])
+
+  const handleSaveHttpSettings = useCallback(async () => {
+    if (!httpSettings) return
+    setSavingHttp(true)
+    try {
+      setHttpSettings(await invokeTauri<HttpSettings>('set_http_settings', { settings: httpSettings }))
+      toast.success(t('networkSaved'))
+    } catch (err) {
+      toast.error(err instanceof Error ? err.message : String(err))
+    } finally {
+      setSavingHttp(false)
+    }
+  }, [httpSettings, invokeTauri, t])
+
+  const handleCloudForceCopy = useCallback(
+    async (enabled: boolean) => {
+      try {
+        const forceCopy = await invokeTauri<boolean>('set_cloud_force_copy', { enabled })
+        setCloudStatus((prev) => (prev ? { ...prev, force_copy: forceCopy } : prev))
+      } catch (err) {
+        toast.error(err instanceof Error ? err.message : String(err))
+      }
+    },
+    [invokeTauri],
+  )
+
   const isRemoteMode = newCustomRemoteHostId !== ''
 
   const handleAddCustomTarget = useCallback(async () => {
@@ -281,8 +352,95 @@ const SettingsModal = ({
               </button>
             </div>
             <div className="settings-helper">{t('skillsStorageHint')}</div>
+            {cloudStatus?.provider ? (
+              <>
+                <div className="settings-helper" style={{ color: 'var(--status-warning)' }}>
+                  {t('cloudFolderWarning', { provider: t(`cloudProvider.${cloudStatus.provider}`) })}
+                </div>
+                <label className="inline-checkbox">
+                  <input
+                    type="checkbox"
+                    checked={cloudStatus.force_copy}
+                    onChange={(event) => void handleCloudForceCopy(event.target.checked)}
+                  />
+                  {t('cloudForceCopy')}
+                </label>
+              </>
+            ) : null}
+            <label className="inline-checkbox">
+              <input
+                type="checkbox"
+                checked={quarantineNew}
+                onChange={(event) => void handleQuarantineNew(event.target.checked)}
+              />
+              {t('quarantine.setting')}
+            </label>
           </div>
 
+          {httpSettings ? (
+            <div className="settings-field">
+              <label className="settings-label" htmlFor="settings-proxy">
+                {t('networkProxy')}
+              </label>
+              <div className="settings-input-row">
+                <input
+                  id="settings-proxy"
+                  className="settings-input mono"
+                  placeholder="socks5://127.0.0.1:1080"
+                  value={httpSettings.proxy_url ?? ''}
+                  onChange={(event) =>
+                    setHttpSettings({ ...httpSettings, proxy_url: event.target.value || null })
+                  }
+                />
+                <button
+                  className="btn btn-secondary settings-browse"
+                  type="button"
+                  disabled={savingHttp}
+                  onClick={() => void handleSaveHttpSettings()}
+                >
+                  {t('networkSave')}
+                </button>
+              </div>
+              <div className="settings-input-row">
+                <input
+                  className="settings-input mono"
+                  placeholder={t('networkNoProxyPlaceholder')}
+                  value={httpSettings.no_proxy ?? ''}
+                  onChange={(event) =>
+                    setHttpSettings({ ...httpSettings, no_proxy: event.target.value || null })
+                  }
+                />
+                <input
+                  className="settings-input"
+                  type="number"
+                  min={1}
+                  max={600}
+                  step={1}
+                  aria-label={t('networkTimeout')}
+                  title={t('networkTimeout')}
+                  value={httpSettings.timeout_secs}
+                  onChange={(event) => {
+                    const next = Number(event.target.value)
+                    if (!Number.isNaN(next)) {
+                      setHttpSettings({ ...httpSettings, timeout_secs: next })
+                    }
+                  }}
+                />
+              </div>
+              <label className="inline-checkbox">
+                <input
+                  type="checkbox"
+                  checked={httpSettings.ssh_via_proxy}
+                  onChange={(event) =>
+                    setHttpSettings({ ...httpSettings, ssh_via_proxy: event.target.checked })
+                  }
+                />
+                {t('networkSshViaProxy')}
+              </label>
+              <div className="settings-helper">{t('networkProxyHint')}</div>
+            </div>
+          ) : null}
+
           <div className="settings-field">
             <label className="settings-label" htmlFor="settings-git-cache-days">
               {t('gitCacheCleanupDays')}
@@ -338,6 +496,29 @@ const SettingsModal = ({
             <div className="settings-helper">{t('gitCacheTtlHint')}</div>
           </div>
 
+          <div className="settings-field">
+            <label className="settings-label" htmlFor="settings-git-cache-max-mb">
+              {t('gitCacheMaxMb')}
+            </label>
+            <div className="settings-input-row">
+              <input
+                id="settings-git-cache-max-mb"
+                className="settings-input"
+                type="number"
+                min={0}
+                step={256}
+                value={gitCacheMaxMb}
+                onChange={(event) => {
+                  const next = Number(event.target.value)
+                  if (!Number.isNaN(next)) {
+                    onGitCacheMaxMbChange(next)
+                  }
+                }}
+              />
+            </div>
+            <div className="settings-helper">{t('gitCacheMaxMbHint')}</div>
+          </div>
+
           <div className="settings-field">
             <label className="settings-label">
               {t('remote.remoteHosts')}
@@ -1,9 +1,9 @@
 import { memo, useEffect, useState } from 'react'
-import { Box, Cloud, Copy, ExternalLink, FileText, Folder, Github, Monitor, RefreshCw, Trash2 } from 'lucide-react'
+import { Box, Cloud, Copy, CopyPlus, ExternalLink, FileText, Folder, Github, Monitor, RefreshCw, Trash2 } from 'lucide-react'
 import { toast } from 'sonner'
 import { openUrl } from '@tauri-apps/plugin-opener'
 import type { TFunction } from 'i18next'
-import type { CustomTarget, ManagedSkill, RemoteHost, RemoteSkillsDto, RemoteToolInfoDto, ToolOption } from '../types'
+import type { CustomTarget, ManagedSkill, RemoteHost, RemoteSkillsDto, RemoteToolInfoDto, SkillFileContent, SkillFileEntry, ToolOption } from '../types'
 
 type ClawHubDetail = {
     slug: string
@@ -23,6 +23,8 @@ type SkillDetailModalProps = {
     loading: boolean
     formatRelative: (ms: number | null | undefined) => string
     onUpdate: (skill: ManagedSkill) => void
+    onDuplicate: (skill: ManagedSkill) => void
+    onApprove: (skill: ManagedSkill) => void
     onDelete: (skillId: string) => void
     onToggleTool: (skill: ManagedSkill, toolId: string) => void
     onRequestClose: () => void
@@ -43,6 +45,8 @@ const SkillDetailModal = ({
     loading,
     formatRelative,
     onUpdate,
+    onDuplicate,
+    onApprove,
     onDelete,
     onToggleTool,
     onRequestClose,
@@ -58,7 +62,9 @@ const SkillDetailModal = ({
 }: SkillDetailModalProps) => {
     const [clawHubDetail, setClawHubDetail] = useState<ClawHubDetail | null>(null)
     const [detailLoading, setDetailLoading] = useState(false)
-    const [skillContent, setSkillContent] = useState<string | null>(null)
+    const [skillFiles, setSkillFiles] = useState<SkillFileEntry[]>([])
+    const [selectedFile, setSelectedFile] = useState('SKILL.md')
+    const [skillContent, setSkillContent] = useState<SkillFileContent | null>(null)
     const [skillContentLoading, setSkillContentLoading] = useState(false)
 
     useEffect(() => {
@@ -84,7 +90,21 @@ const SkillDetailModal = ({
         return () => { cancelled = true }
     }, [skill, invokeTauri])
 
-    // Load SKILL.md content
+    // List the skill's files; the preview starts at SKILL.md
+    useEffect(() => {
+        setSelectedFile('SKILL.md')
+        setSkillFiles([])
+        if (!skill) return
+        let cancelled = false
+        invokeTauri<SkillFileEntry[]>('list_skill_files', { skillId: skill.id })
+            .then((files) => {
+                if (!cancelled) setSkillFiles(files.filter((f) => !f.is_dir))
+            })
+            .catch(() => {})
+        return () => { cancelled = true }
+    }, [skill, invokeTauri])
+
+    // Load the selected file
     useEffect(() => {
         if (!skill) {
             setSkillContent(null)
@@ -94,7 +114,10 @@ const SkillDetailModal = ({
         const load = async () => {
             setSkillContentLoading(true)
             try {
-                const content = await invokeTauri<string>('read_skill_content', { skillId: skill.id })
+                const content = await invokeTauri<SkillFileContent>('read_skill_file', {
+                    skillId: skill.id,
+                    relativePath: selectedFile,
+                })
                 if (!cancelled) setSkillContent(content)
             } catch {
                 if (!cancelled) setSkillContent(null)
@@ -104,7 +127,7 @@ const SkillDetailModal = ({
         }
         void load()
         return () => { cancelled = true }
-    }, [skill, invokeTauri])
+    }, [skill, selectedFile, invokeTauri])
 
     if (!skill) return null
 
@@ -355,16 +378,41 @@ const SkillDetailModal = ({
                         })}
                     </div>
 
-                    {/* SKILL.md preview */}
+                    {/* File preview */}
                     <div className="detail-section">
                         <div className="detail-meta-label">
                             <FileText size={14} />
-                            SKILL.md
+                            {skillFiles.length > 1 ? (
+                                <select
+                                    className="detail-file-select"
+                                    value={selectedFile}
+                                    onChange={(e) => setSelectedFile(e.target.value)}
+                                >
+                                    {skillFiles.map((file) => (
+                                        <option key={file.path} value={file.path}>
+                                            {file.path}
+                                        </option>
+                                    ))}
+                                </select>
+                            ) : (
+                                selectedFile
+                            )}
                         </div>
                         {skillContentLoading ? (
                             <div className="detail-meta-item" style={{ opacity: 0.5 }}>{t('loadingDetail')}</div>
-                        ) : skillContent ? (
-                            <pre className="skill-preview-content">{skillContent}</pre>
+                        ) : skillContent?.binary ? (
+                            <div className="detail-meta-item" style={{ opacity: 0.5 }}>
+                                {t('skillFiles.binary', { size: skillContent.size })}
+                            </div>
+                        ) : skillContent?.content ? (
+                            <>
+                                <pre className="skill-preview-content">{skillContent.content}</pre>
+                                {skillContent.truncated && (
+                                    <div className="detail-meta-item" style={{ opacity: 0.5 }}>
+                                        {t('skillFiles.truncated', { size: skillContent.size })}
+                                    </div>
+                                )}
+                            </>
                         ) : (
                             <div className="detail-meta-item" style={{ opacity: 0.5 }}>—</div>
                         )}
@@ -390,6 +438,26 @@ const SkillDetailModal = ({
                         >
                             {t('close')}
                         </button>
+                        {skill.status === 'quarantined' ? (
+                            <button
+                                className="btn btn-secondary"
+                                type="button"
+                                onClick={() => onApprove(skill)}
+                                disabled={loading}
+                                title={t('quarantine.hint')}
+                            >
+                                {t('quarantine.approve')}
+                            </button>
+                        ) : null}
+                        <button
+                            className="btn btn-secondary"
+                            type="button"
+                            onClick={() => onDuplicate(skill)}
+                            disabled={loading}
+                        >
+                            <CopyPlus size={14} />
+                            {t('duplicate.button')}
+                        </button>
                         <button
                             className="btn btn-primary"
                             type="button"
@@ -40,8 +40,56 @@ export type ManagedSkill = {
     status: string
     target_path: string
     synced_at?: number | null
+    is_stale?: boolean
   }[]
   group_name?: string | null
+  propagate_to_remotes?: boolean
+  remote_targets?: {
+    host_id: string
+    tool: string
+    target_path: string
+    synced_at?: number | null
+    is_stale: boolean
+  }[]
+  remote_deployments?: {
+    host_id: string
+    tool_keys: string[]
+    synced_at: number
+    is_stale: boolean
+  }[]
+  resource_type?: 'skill' | 'agent' | 'command'
+  metadata?: SkillMetadata
+  // Locked skills refuse updates without `force` and in-app edits.
+  locked?: boolean
+}
+
+export type SkillMetadata = {
+  name: string | null
+  description: string | null
+  version: string | null
+  tags: string[]
+  dependencies: string[]
+}
+
+export type DependencySource =
+  | { kind: 'clawhub'; slug: string }
+  | { kind: 'git'; url: string }
+
+export type SkillDependency = {
+  spec: string
+  source: DependencySource | null
+  installed_skill_id: string | null
+}
+
+export type SkillDependent = {
+  skill_id: string
+  name: string
+  spec: string
+}
+
+export type DependencyInstallResult = {
+  installed: InstallResultDto[]
+  failed: { spec: string; error: string }[]
 }
 
 export type GitSkillCandidate = {
@@ -58,11 +106,49 @@ export type LocalSkillCandidate = {
   reason?: string | null
 }
 
+export type SkillIssue = {
+  severity: 'error' | 'warning'
+  code: string
+  message: string
+  line: number | null
+}
+
+export type SkillFileEntry = {
+  path: string
+  is_dir: boolean
+  size: number
+}
+
+export type SkillFileContent = {
+  path: string
+  size: number
+  content: string | null
+  binary: boolean
+  truncated: boolean
+}
+
+export type SkillValidation = {
+  valid: boolean
+  issues: SkillIssue[]
+}
+
 export type InstallResultDto = {
   skill_id: string
   name: string
   central_path: string
   content_hash?: string | null
+  warnings: SkillIssue[]
+  content_warnings: ContentFinding[]
+  signature_status?: 'verified' | 'unsigned' | 'untrusted' | null
+}
+
+// Something in an installed skill's files worth reviewing before syncing.
+export type ContentFinding = {
+  kind: 'secret' | 'pipe_to_shell' | 'suspicious_instruction'
+  code: string
+  file: string
+  line: number
+  excerpt: string
 }
 
 export type ToolInfoDto = {
@@ -70,6 +156,7 @@ export type ToolInfoDto = {
   label: string
   installed: boolean
   skills_dir: string
+  version?: string | null
 }
 
 export type ToolStatusDto = {
@@ -84,6 +171,21 @@ export type UpdateResultDto = {
   content_hash?: string | null
   source_revision?: string | null
   updated_targets: string[]
+  remotes?: {
+    pushed: string[]
+    stale: string[]
+    errors: string[]
+  }
+}
+
+export type StarSuggestion = {
+  full_name: string
+  html_url: string
+  description?: string | null
+  stars: number
+  clone_url: string
+  skill_paths: string[]
+  installed: boolean
 }
 
 export type ClawHubSkill = {
@@ -93,6 +195,20 @@ export type ClawHubSkill = {
   version?: string | null
   score: number
   updatedAt?: number | null
+  downloads?: number | null
+  stars?: number | null
+}
+
+export type ClawHubPublishResult = {
+  slug: string
+  version: string
+  url?: string | null
+}
+
+export type ClawHubSearchPage = {
+  results: ClawHubSkill[]
+  nextCursor?: string | null
+  nextOffset?: number | null
 }
 
 export type SkillUpdateStatus = {
@@ -104,6 +220,15 @@ export type SkillUpdateStatus = {
   error?: string | null
 }
 
+export type SkillFreshness = {
+  skill_id: string
+  name: string
+  last_commit_at?: number | null
+  checked_at: number
+  level: 'active' | 'stale' | 'abandoned' | 'unknown'
+  error?: string | null
+}
+
 export type RemoteHost = {
   id: string
   label: string
@@ -116,6 +241,20 @@ export type RemoteHost = {
   updated_at: number
   last_sync_at?: number | null
   status: string
+  group_name?: string | null
+  health?: RemoteHostHealth | null
+}
+
+export type RemoteHostHealth = {
+  checked_at: number
+  reachable: boolean
+  latency_ms?: number | null
+  os?: string | null
+  arch?: string | null
+  home?: string | null
+  free_disk_bytes?: number | null
+  skillshub_writable: boolean
+  error?: string | null
 }
 
 export type RemoteToolInfoDto = {
@@ -129,6 +268,20 @@ export type RemoteToolStatusDto = {
   tools: RemoteToolInfoDto[]
 }
 
+export type SkillSyncCell = {
+  skill_id: string
+  name: string
+  status: 'ok' | 'skipped' | 'error'
+  error?: string | null
+}
+
+export type HostSyncResult = {
+  host_id: string
+  label: string
+  error?: string | null
+  skills: SkillSyncCell[]
+}
+
 export type RemoteSyncResultDto = {
   syncedSkills: string[]
 }
@@ -143,6 +296,7 @@ export type RemoteSkillsDto = {
   hostId: string
   skills: string[]
   toolLinks: RemoteToolLinkDto[]
+  staleSkills?: string[]
 }
 
 export type CustomTarget = {
@@ -151,4 +305,622 @@ export type CustomTarget = {
   path: string
   remote_host_id?: string | null
   created_at: number
+  variables?: string[]
+}
+
+export type UndoSummary = {
+  id: string
+  kind: 'sync' | 'unsync'
+  skill_id: string
+  tools: string[]
+  target_path: string
+  expires_in_secs: number
+}
+
+export type OperationKind =
+  | 'install'
+  | 'sync'
+  | 'update'
+  | 'remote_push'
+  | 'publish'
+  | 'migrate'
+
+export type OperationStatus =
+  | { state: 'queued' }
+  | { state: 'running'; progress?: number | null }
+  | { state: 'done' }
+  | { state: 'failed'; error: string }
+
+export type Operation = {
+  id: string
+  kind: OperationKind
+  label: string
+  status: OperationStatus
+  created_at: number
+  updated_at: number
+}
+
+export type LogLevel = 'off' | 'error' | 'warn' | 'info' | 'debug' | 'trace'
+
+/** One record from `get_recent_logs`, oldest first. */
+export type LogEntry = {
+  timestamp: string
+  level: Exclude<LogLevel, 'off'>
+  target: string
+  message: string
+}
+
+/** A background job from `list_jobs` / `get_job`; `T` is what it returns. */
+export type Job<T = unknown> = Operation & {
+  logs: string[]
+  result: T | null
+  error: CommandErrorData | null
+}
+
+export type UpdateSkillsJobResult = {
+  updated: UpdateResultDto[]
+  failed: { skill_id: string; error: CommandErrorData }[]
+}
+
+export type RemoteSyncSchedule = {
+  enabled: boolean
+  host_ids: string[]
+  tool_keys: string[]
+  interval_minutes: number
+  run_at_start: boolean
+}
+
+export type ScheduledSyncRun = {
+  trigger: 'startup' | 'interval' | 'manual'
+  started_at: number
+  finished_at: number
+  error?: string | null
+  hosts: HostSyncResult[]
+}
+
+export type DetectedProject = {
+  path: string
+  markers: string[]
+}
+
+export type RegistryAuth =
+  | { kind: 'basic'; username: string; password: string }
+  | { kind: 'bearer'; token: string }
+  | {
+      kind: 'oidc'
+      device_authorization_endpoint: string
+      token_endpoint: string
+      client_id: string
+      scope?: string | null
+    }
+
+export type RegistryAuthSummary = {
+  origin: string
+  kind: 'basic' | 'bearer' | 'oidc'
+  username?: string | null
+  signed_in: boolean
+  expires_at?: number | null
+}
+
+export type DeviceLogin = {
+  device_code: string
+  user_code: string
+  verification_uri: string
+  verification_uri_complete?: string | null
+  expires_in: number
+  interval: number
+}
+
+export type SharedCentralRepo = {
+  shared: boolean
+  path: string
+  skill_count: number
+}
+
+export type WatchSettings = {
+  exclude_globs: string[]
+  debounce_ms: number
+  max_events_per_minute: number
+}
+
+export type SyncModePreference = 'auto' | 'link' | 'copy'
+
+export type SyncModePrefs = {
+  tools: Record<string, SyncModePreference>
+  skills: Record<string, SyncModePreference>
+}
+
+export type BackupManifest = {
+  format_version: number
+  created_at: number
+  db_sha256: string
+  files: { path: string; size: number; sha256: string }[]
+}
+
+export type BackupVerification = {
+  format_version: number
+  created_at: number
+  files_checked: number
+  files_sampled: number
+  skill_count?: number | null
+  problems: string[]
+  ok: boolean
+}
+
+export type SyncResult = {
+  mode_used: string
+  mode_reason?: string | null
+  target_path: string
+  warnings: string[]
+}
+
+export type ToolSyncResult = {
+  tool: string
+  result?: SyncResult | null
+  error?: CommandErrorData | null
+}
+
+export type ExistingCopy = {
+  tool: string
+  path: string
+}
+
+export type ExistingSkill = {
+  name: string
+  content_hash: string
+  copies: ExistingCopy[]
+  managed_skill_id?: string | null
+  name_conflict: boolean
+}
+
+export type ExistingSkillScan = {
+  tools_scanned: number
+  skills: ExistingSkill[]
+}
+
+export type ImportExistingResult = {
+  imported: InstallResultDto[]
+  linked: number
+  failed: string[]
+}
+
+export type SkillSyncIssue = {
+  skill_id: string
+  name: string
+  message: string
+}
+
+export type AdoptToolResult = {
+  tool: string
+  synced: string[]
+  already_synced: number
+  skipped: SkillSyncIssue[]
+  failed: SkillSyncIssue[]
+}
+
+export type DanglingLink = {
+  tool: string
+  path: string
+  link_target: string
+}
+
+export type MissingTarget = {
+  skill_id: string
+  skill_name: string
+  tool: string
+  target_path: string
+}
+
+export type OrphanReport = {
+  dangling_links: DanglingLink[]
+  missing_targets: MissingTarget[]
+}
+
+export type OverwrittenTarget = {
+  id: string
+  original_path: string
+  is_file: boolean
+  created_at: number
+}
+
+export type ReinstallResult = {
+  skill_id: string
+  name: string
+  content_hash: string
+  source_revision?: string | null
+  hash_matches: boolean
+  resynced_targets: string[]
+  warnings: string[]
+}
+
+export type ClawHubReadme = {
+  slug: string
+  version?: string | null
+  path: string
+  content: string
+  truncated: boolean
+}
+
+export type RepoSummary = {
+  full_name: string
+  html_url: string
+  description?: string | null
+  stars: number
+  updated_at: string
+  clone_url: string
+  language?: string | null
+  topics: string[]
+}
+
+export type RepoSearchFilters = {
+  topic?: string
+  language?: string
+  minStars?: number
+  sort?: 'stars' | 'updated'
+}
+
+export type GitHubFile = {
+  path: string
+  gitRef?: string | null
+  content: string
+  truncated: boolean
+}
+
+export type SourceProvider = {
+  id: string
+  name: string
+  apiUrl: string
+  webUrl: string
+}
+
+export type ProjectTargetStatus = {
+  id: string
+  skill_id: string
+  project_path: string
+  tool: string
+  target_path: string
+  mode: string
+  synced_at: number
+  status: 'ok' | 'stale' | 'missing' | 'project_missing'
+}
+
+export type ProjectSyncResult = {
+  target: ProjectTargetStatus
+  warnings: string[]
+}
+
+export type McpServer = {
+  name: string
+  command?: string | null
+  args: string[]
+  env: Record<string, string>
+  url?: string | null
+}
+
+export type McpSyncResult = {
+  tool: string
+  config_path: string
+  written: string[]
+  removed: string[]
+  conflicts: string[]
+}
+
+export type CentralRemoteAuth =
+  | { kind: 'none' }
+  | { kind: 'token'; username?: string | null; token: string }
+  | { kind: 'ssh_agent'; username?: string | null }
+  | {
+      kind: 'ssh_key'
+      username?: string | null
+      key_path?: string | null
+      passphrase?: string | null
+    }
+
+export type CentralRemote = {
+  url: string
+  branch?: string | null
+  auth: CentralRemoteAuth
+}
+
+export type CentralRemoteSummary = {
+  url: string
+  branch: string
+  auth_kind: 'none' | 'token' | 'ssh_agent' | 'ssh_key'
+  username: string | null
+}
+
+export type PullConflict = {
+  name: string
+  reason:
+    | 'both_changed'
+    | 'deleted_remotely'
+    | 'git_clone'
+    | 'unknown_type'
+    | 'missing_locally'
+}
+
+export type PullResult = {
+  commit: string | null
+  added: string[]
+  updated: string[]
+  conflicts: PullConflict[]
+}
+
+export type SkillUsage = {
+  skill_id: string
+  name: string
+  central_path: string
+  bytes: number
+  files: number
+}
+
+export type StorageReport = {
+  central_path: string
+  total_bytes: number
+  skills: SkillUsage[]
+  history_bytes: number
+  git_cache_bytes: number
+  trash_bytes: number
+}
+
+export type GitCacheEntry = {
+  key: string
+  repo_url: string | null
+  branch: string | null
+  head: string | null
+  bytes: number
+  files: number
+  last_used_ms: number
+  last_fetched_ms: number | null
+}
+
+export type GitCacheReport = {
+  root: string
+  total_bytes: number
+  max_bytes: number | null
+  entries: GitCacheEntry[]
+}
+
+export type FileDrift = {
+  path: string
+  change: 'added' | 'removed' | 'modified'
+}
+
+export type TargetDrift = {
+  tool: string
+  target_path: string
+  project_path: string | null
+  files: FileDrift[]
+}
+
+export type SkillDrift = {
+  skill_id: string
+  name: string
+  has_baseline: boolean
+  central: FileDrift[]
+  targets: TargetDrift[]
+}
+
+export type TargetHealthStatus = {
+  tool: string
+  target_path: string
+  mode: string
+  state: 'ok' | 'broken' | 'stale' | 'skipped'
+  detail: string | null
+}
+
+export type SkillHealth = {
+  skill_id: string
+  name: string
+  source_type: string
+  targets: TargetHealthStatus[]
+  has_update: boolean | null
+  source_reachable: boolean | null
+  source_error: string | null
+  central_drifted: boolean
+  drifted_targets: string[]
+  bytes: number
+  files: number
+}
+
+export type IntegrityIssue = {
+  kind: 'missing_dir' | 'hash_mismatch' | 'orphan' | 'broken_target'
+  skill_id: string | null
+  name: string
+  path: string
+  tool: string | null
+  repaired: boolean
+  error: string | null
+}
+
+export type IntegrityReport = {
+  checked: number
+  issues: IntegrityIssue[]
+}
+
+export type Profile = {
+  id: string
+  name: string
+}
+
+export type ProfileList = {
+  active: string
+  next: string
+  profiles: Profile[]
+}
+
+export type PlannedMove = {
+  skill_id: string | null
+  name: string
+  from: string
+  to: string
+  bytes: number
+}
+
+export type MigrationPlan = {
+  from: string
+  to: string
+  moves: PlannedMove[]
+  total_bytes: number
+  needs_copy: boolean
+  available_bytes: number | null
+  blockers: string[]
+}
+
+export type TargetState = 'ok' | 'missing' | 'dangling' | 'replaced' | 'stale'
+
+export type TargetCheck = {
+  skill_id: string
+  skill_name: string
+  tool: string
+  target_path: string
+  mode: string
+  state: TargetState
+  detail: string | null
+  repaired: boolean
+  error: string | null
+}
+
+export type TargetVerifyReport = {
+  checked: number
+  skipped: number
+  problems: TargetCheck[]
+}
+
+export type WatchResync = {
+  skill_id: string
+  name: string
+  tools: string[]
+  failed: { tool: string; error: string }[]
+}
+
+export type SyncPlan = {
+  tool: string
+  source: string
+  target: string
+  mode: 'symlink' | 'junction' | 'copy'
+  action: 'create' | 'unchanged' | 'replace'
+  added: string[]
+  overwritten: string[]
+  deleted: string[]
+  unchanged: number
+  existing_link: string | null
+  target_fingerprint: string | null
+}
+
+export type SkillTemplate = {
+  id: string
+  description: string | null
+  builtin: boolean
+}
+
+export type TemplateList = {
+  templates: SkillTemplate[]
+  user_dir: string
+}
+
+export type CreateSkillResult = {
+  skill: InstallResultDto
+  synced: ToolSyncResult[]
+}
+
+export type SkillWriteResult = {
+  skill_id: string
+  relative_path: string
+  content_hash: string | null
+  updated_targets: string[]
+}
+
+// Payloads of the skill lifecycle events emitted by the backend.
+export type SkillChangedEvent = {
+  skill_id: string
+  name: string
+}
+
+export type SkillSyncedEvent = {
+  skill_id: string
+  tool: string
+  target_path: string
+  mode: string
+}
+
+export type TargetBrokenEvent = {
+  skill_id: string
+  tool: string
+  target_path: string
+  state: TargetState
+  detail?: string | null
+}
+
+export type RemoteSyncFinishedEvent = {
+  host_id: string
+  synced: string[]
+  error?: string | null
+}
+
+export type ErrorCode =
+  | 'OTHER'
+  | 'MULTI_SKILLS'
+  | 'TARGET_EXISTS'
+  | 'TOOL_NOT_INSTALLED'
+  | 'GITHUB_RATE_LIMITED'
+  | 'SKILL_INVALID'
+  | 'SYNC_PLAN_STALE'
+  | 'CENTRAL_REMOTE_DIVERGED'
+  | 'CENTRAL_REMOTE_NOT_SET'
+  | 'CENTRAL_REPO_READ_ONLY'
+  | 'CLAWHUB_TOKEN_REQUIRED'
+  | 'MCP_UNSUPPORTED'
+  | 'RESOURCE_UNSUPPORTED'
+  | 'MIGRATION_BLOCKED'
+  | 'SKILL_LOCKED'
+  | 'UNTRUSTED_SOURCE'
+  | 'SOURCE_DENIED'
+  | 'SKILL_QUARANTINED'
+  | 'UNVERIFIED_PACKAGE'
+
+// Allowlists and denylists installs are checked against. Owner entries are
+// `owner` or `host/owner`.
+export type SourceTrustPolicy = {
+  require_allowlisted: boolean
+  allowed_owners: string[]
+  denied_owners: string[]
+  allowed_publishers: string[]
+  denied_publishers: string[]
+}
+
+// TARGET_EXISTS payload from sync commands.
+export type TargetConflict = {
+  path: string
+  is_symlink: boolean
+  link_target: string | null
+  points_to_source: boolean
+  is_dir: boolean
+  bytes: number
+  modified_ms: number | null
+  content_matches: boolean | null
+}
+
+export type CloudProvider = 'icloud_drive' | 'dropbox' | 'one_drive'
+
+export type CloudFolderStatus = {
+  path: string
+  provider: CloudProvider | null
+  force_copy: boolean
+}
+
+export type HttpSettings = {
+  proxy_url: string | null
+  no_proxy: string | null
+  connect_timeout_secs: number
+  timeout_secs: number
+  max_retries: number
+  ssh_via_proxy: boolean
+}
+
+// What every command rejects with.
+export type CommandErrorData = {
+  code: ErrorCode
+  message: string
+  payload?: Record<string, unknown> | null
 }
@@ -50,6 +50,12 @@ export const resources = {
       batchUpdate: 'Update',
       batchDelete: 'Delete',
       batchDeleteConfirm: 'Are you sure you want to delete {{count}} skills? This will remove them from all tools and delete their local copies.',
+      contentWarningsFound:
+        '{{name}} has {{count}} item(s) to review (possible secrets, piped installers or suspicious instructions) before you sync it.',
+      untrustedSourceConfirm:
+        '{{owner}} is not on your trusted sources list. Install from it anyway?',
+      lockedUpdateConfirm:
+        '{{name}} is locked. Update it from its source anyway? Local tuning will be replaced.',
       toolsLabel: 'Tools',
       activeTools: 'Active Tools',
       sourceLabel: 'Source',
@@ -89,10 +95,29 @@ export const resources = {
       skillsStorageHint: 'Local copies of Git skills will be stored here.',
       gitCacheCleanupDays: 'Git cache cleanup (days)',
       gitCacheCleanupHint:
-        'Remove cached Git repos not fetched within the past N days. Set to 0 to disable.',
+        'Remove cached Git repos not used within the past N days. Set to 0 to disable.',
       gitCacheTtlSecs: 'Git cache freshness (seconds)',
       gitCacheTtlHint:
         'Skip git fetch when the cache was updated within this window. Set to 0 to always fetch.',
+      gitCacheMaxMb: 'Git cache size limit (MB)',
+      gitCacheMaxMbHint:
+        'When the cache grows past this size, the least recently used repos are removed first. Set to 0 for no limit.',
+      cloudFolderWarning:
+        'This folder is synced by {{provider}}. Linked skills can break when files are evicted or synced to other machines.',
+      cloudForceCopy: 'Copy skills instead of linking them',
+      networkProxy: 'Network proxy',
+      networkProxyHint:
+        'Used for ClawHub, GitHub and registry requests and for cloning over HTTPS. Accepts http://, https://, socks5:// or socks5h://; leave empty to use HTTPS_PROXY from the environment. The number is the request timeout in seconds.',
+      networkNoProxyPlaceholder: 'Hosts that skip the proxy, e.g. localhost,.internal',
+      networkTimeout: 'Request timeout (seconds)',
+      networkSshViaProxy: 'Also connect to remote hosts over SSH through this proxy (HTTP CONNECT, http:// only)',
+      networkSave: 'Save',
+      networkSaved: 'Network settings saved',
+      cloudProvider: {
+        icloud_drive: 'iCloud Drive',
+        dropbox: 'Dropbox',
+        one_drive: 'OneDrive',
+      },
       appUpdates: 'App updates',
       updateHint: 'Click "Check" to look for updates.',
       checkForUpdates: 'Check',
@@ -185,6 +210,8 @@ export const resources = {
         targetExists: 'Target folder already exists. Please remove it and try again.',
         targetExistsDetail:
           'Target folder already exists: {{path}}. For safety it was not overwritten.',
+        targetAlreadySynced:
+          '{{path}} already holds this skill with the same content; nothing was changed.',
         toolNotInstalled: 'The selected tool is not installed. Please refresh and retry.',
         noSkillsFoundInRepo: 'No skills found in this repository.',
         requireLocalPath: 'Please enter a local path.',
@@ -238,11 +265,33 @@ export const resources = {
         hoursAgo: '{{hours}}h ago',
         daysAgo: '{{days}}d ago',
       },
+      dependencies: {
+        installConfirm:
+          'This skill needs skills that are not installed:\n{{specs}}\n\nInstall them now?',
+        installFailed: 'Could not install dependency {{spec}}',
+      },
+      skillFiles: {
+        binary: 'Binary file ({{size}} bytes), not shown',
+        truncated: 'Showing the start of a {{size}} byte file',
+      },
+      quarantine: {
+        badge: 'Quarantined',
+        hint: 'Installed while quarantine is on; approve it before syncing it to any tool.',
+        approve: 'Approve',
+        approved: 'Approved {{name}}',
+        setting: 'Quarantine new installs until approved',
+      },
+      duplicate: {
+        button: 'Duplicate',
+        prompt: 'Name for the editable copy of {{name}}:',
+        done: 'Created {{name}}',
+      },
       delete: {
         confirmPrefix: 'Are you sure you want to delete ',
         confirmSuffix: '?',
         warningRemoveFromTools: 'Remove from synced tools',
         warningDeleteFromHub: 'Delete local copy from Hub',
+        warningDependents: 'Still needed by: {{names}}',
         confirmButton: 'Yes, Delete',
       },
       layout: {
@@ -430,6 +479,9 @@ export const resources = {
       batchUpdate: '更新',
       batchDelete: '删除',
       batchDeleteConfirm: '确定要删除这 {{count}} 个 Skill 吗？这将从所有工具中移除并删除本地副本。',
+      contentWarningsFound: '{{name}} 有 {{count}} 处内容需要检查（可能的密钥、管道安装脚本或可疑指令），请确认后再同步。',
+      untrustedSourceConfirm: '{{owner}} 不在受信任来源列表中，仍要安装吗？',
+      lockedUpdateConfirm: '{{name}} 已锁定。仍要从来源更新吗？本地调整将被覆盖。',
       toolsLabel: '工具',
       activeTools: '活跃工具',
       sourceLabel: '来源',
@@ -471,6 +523,23 @@ export const resources = {
       gitCacheTtlSecs: 'Git 缓存新鲜期（秒）',
       gitCacheTtlHint:
         '在该时间窗口内命中缓存会跳过 fetch，设为 0 表示每次都拉取。',
+      gitCacheMaxMb: 'Git 缓存大小上限（MB）',
+      gitCacheMaxMbHint: '缓存超过该大小时，优先删除最久未使用的仓库，设为 0 表示不限制。',
+      cloudFolderWarning: '该目录由 {{provider}} 同步，文件被移出本地或同步到其他设备时，软链接的 Skill 可能失效。',
+      cloudForceCopy: '复制 Skill 而不是创建链接',
+      networkProxy: '网络代理',
+      networkProxyHint:
+        '用于 ClawHub、GitHub、注册表请求以及 HTTPS 克隆。支持 http://、https://、socks5:// 或 socks5h://；留空则使用环境变量 HTTPS_PROXY。数字为请求超时秒数。',
+      networkNoProxyPlaceholder: '不走代理的主机，如 localhost,.internal',
+      networkTimeout: '请求超时（秒）',
+      networkSshViaProxy: '远程主机的 SSH 连接也经过此代理（HTTP CONNECT，仅限 http://）',
+      networkSave: '保存',
+      networkSaved: '网络设置已保存',
+      cloudProvider: {
+        icloud_drive: 'iCloud 云盘',
+        dropbox: 'Dropbox',
+        one_drive: 'OneDrive',
+      },
       appUpdates: '应用更新',
       updateHint: '点击"检查更新"获取最新版本。',
       checkForUpdates: '检查更新',
@@ -560,6 +629,7 @@ export const resources = {
         targetExists: '目标目录已存在，请先清理后重试。',
         targetExistsDetail:
           '目标目录已存在同名 Skill：{{path}}。为安全起见未覆盖。\n你可以：先手动清理该目录后重试。',
+        targetAlreadySynced: '{{path}} 中已是该 Skill 的相同内容，无需同步。',
         toolNotInstalled: '未检测到该工具已安装，请刷新后重试。',
         noSkillsFoundInRepo: '该仓库未发现可导入的 Skills。',
         requireLocalPath: '请输入本地路径',
@@ -609,11 +679,32 @@ export const resources = {
         hoursAgo: '{{hours}} 小时前',
         daysAgo: '{{days}} 天前',
       },
+      dependencies: {
+        installConfirm: '该技能依赖以下未安装的技能：\n{{specs}}\n\n现在安装吗？',
+        installFailed: '无法安装依赖 {{spec}}',
+      },
+      skillFiles: {
+        binary: '二进制文件（{{size}} 字节），不显示',
+        truncated: '仅显示 {{size}} 字节文件的开头',
+      },
+      quarantine: {
+        badge: '待审核',
+        hint: '在隔离模式下安装；批准后才能同步到工具。',
+        approve: '批准',
+        approved: '已批准 {{name}}',
+        setting: '新安装的 Skill 需批准后才能同步',
+      },
+      duplicate: {
+        button: '复制',
+        prompt: '为 {{name}} 的可编辑副本命名：',
+        done: '已创建 {{name}}',
+      },
       delete: {
         confirmPrefix: '确定要删除 ',
         confirmSuffix: '？',
         warningRemoveFromTools: '将从已同步的工具中移除',
         warningDeleteFromHub: '删除 Hub 的本地副本',
+        warningDependents: '以下技能依赖它：{{names}}',
         confirmButton: '确认删除',
       },
       layout: {
@@ -801,6 +892,9 @@ export const resources = {
       batchUpdate: '更新',
       batchDelete: '刪除',
       batchDeleteConfirm: '確定要刪除這 {{count}} 個 Skill 嗎？這將從所有工具中移除並刪除本地副本。',
+      contentWarningsFound: '{{name}} 有 {{count}} 處內容需要檢查（可能的金鑰、管線安裝腳本或可疑指令），請確認後再同步。',
+      untrustedSourceConfirm: '{{owner}} 不在受信任來源清單中，仍要安裝嗎？',
+      lockedUpdateConfirm: '{{name}} 已鎖定。仍要從來源更新嗎？本機調整將被覆蓋。',
       toolsLabel: '工具',
       activeTools: '使用中工具',
       sourceLabel: '來源',
@@ -842,6 +936,23 @@ export const resources = {
       gitCacheTtlSecs: 'Git 快取新鮮期（秒）',
       gitCacheTtlHint:
         '在該時間窗口內命中快取會跳過 fetch，設為 0 表示每次都拉取。',
+      gitCacheMaxMb: 'Git 快取大小上限（MB）',
+      gitCacheMaxMbHint: '快取超過該大小時，優先刪除最久未使用的儲存庫，設為 0 表示不限制。',
+      cloudFolderWarning: '此資料夾由 {{provider}} 同步，檔案被移出本機或同步到其他裝置時，連結的 Skill 可能失效。',
+      cloudForceCopy: '複製 Skill 而非建立連結',
+      networkProxy: '網路代理',
+      networkProxyHint:
+        '用於 ClawHub、GitHub、註冊表請求以及 HTTPS 複製。支援 http://、https://、socks5:// 或 socks5h://；留空則使用環境變數 HTTPS_PROXY。數字為請求逾時秒數。',
+      networkNoProxyPlaceholder: '不經代理的主機，如 localhost,.internal',
+      networkTimeout: '請求逾時（秒）',
+      networkSshViaProxy: '遠端主機的 SSH 連線也經過此代理（HTTP CONNECT，僅限 http://）',
+      networkSave: '儲存',
+      networkSaved: '網路設定已儲存',
+      cloudProvider: {
+        icloud_drive: 'iCloud 雲碟',
+        dropbox: 'Dropbox',
+        one_drive: 'OneDrive',
+      },
       appUpdates: '應用程式更新',
       updateHint: '點擊「檢查更新」取得最新版本。',
       checkForUpdates: '檢查更新',
@@ -931,6 +1042,7 @@ export const resources = {
         targetExists: '目標資料夾已存在，請先清理後重試。',
         targetExistsDetail:
           '目標資料夾已存在同名 Skill：{{path}}。為安全起見未覆蓋。\n你可以：先手動清理該資料夾後重試。',
+        targetAlreadySynced: '{{path}} 中已是該 Skill 的相同內容，無需同步。',
         toolNotInstalled: '未偵測到該工具已安裝，請重新整理後重試。',
         noSkillsFoundInRepo: '該儲存庫未發現可匯入的 Skills。',
         requireLocalPath: '請輸入本機路徑',
@@ -980,11 +1092,32 @@ export const resources = {
         hoursAgo: '{{hours}} 小時前',
         daysAgo: '{{days}} 天前',
       },
+      dependencies: {
+        installConfirm: '此技能依賴以下未安裝的技能：\n{{specs}}\n\n現在安裝嗎？',
+        installFailed: '無法安裝依賴 {{spec}}',
+      },
+      skillFiles: {
+        binary: '二進位檔案（{{size}} 位元組），不顯示',
+        truncated: '僅顯示 {{size}} 位元組檔案的開頭',
+      },
+      quarantine: {
+        badge: '待審核',
+        hint: '在隔離模式下安裝；核准後才能同步到工具。',
+        approve: '核准',
+        approved: '已核准 {{name}}',
+        setting: '新安裝的 Skill 需核准後才能同步',
+      },
+      duplicate: {
+        button: '複製',
+        prompt: '為 {{name}} 的可編輯副本命名：',
+        done: '已建立 {{name}}',
+      },
       delete: {
         confirmPrefix: '確定要刪除 ',
         confirmSuffix: '？',
         warningRemoveFromTools: '將從已同步的工具中移除',
         warningDeleteFromHub: '刪除 Hub 的本機副本',
+        warningDependents: '以下技能依賴它：{{names}}',
         confirmButton: '確認刪除',
       },
       layout: {